@@ -19,7 +19,10 @@ use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::error;
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    MempoolClientRequest, MempoolClientSender, PeerBroadcastStatus, SenderPendingTransactions,
+    SubmissionStatus,
+};
 use aptos_state_view::TStateView;
 use aptos_storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
@@ -35,7 +38,10 @@ use aptos_types::{
     contract_event::EventWithVersion,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
-    on_chain_config::{GasSchedule, GasScheduleV2, OnChainConfig, OnChainExecutionConfig},
+    on_chain_config::{
+        BaseFee, BlockGasLimitType, GasSchedule, GasScheduleV2, OnChainConfig,
+        OnChainExecutionConfig,
+    },
     state_store::{
         state_key::{StateKey, StateKeyInner},
         state_key_prefix::StateKeyPrefix,
@@ -174,12 +180,19 @@ impl Context {
         self.node_config.api.max_submit_transaction_batch_size
     }
 
-    pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
+    /// Submits the transaction to mempool, which only forwards it to trusted peers if `private`
+    pub async fn submit_transaction(
+        &self,
+        txn: SignedTransaction,
+        private: bool,
+    ) -> Result<SubmissionStatus> {
         let (req_sender, callback) = oneshot::channel();
-        self.mp_sender
-            .clone()
-            .send(MempoolClientRequest::SubmitTransaction(txn, req_sender))
-            .await?;
+        let request = if private {
+            MempoolClientRequest::SubmitPrivateTransaction(txn, req_sender)
+        } else {
+            MempoolClientRequest::SubmitTransaction(txn, req_sender)
+        };
+        self.mp_sender.clone().send(request).await?;
 
         callback.await?
     }
@@ -593,7 +606,9 @@ impl Context {
             .into_iter()
             .map(|t| {
                 // Update the timestamp if the next block occurs
-                if let Some(txn) = t.transaction.try_as_block_metadata() {
+                if let Some(txn) = t.transaction.try_as_block_metadata_ext() {
+                    timestamp = txn.timestamp_usecs();
+                } else if let Some(txn) = t.transaction.try_as_block_metadata() {
                     timestamp = txn.timestamp_usecs();
                 }
                 let txn = converter.try_into_onchain_transaction(timestamp, t)?;
@@ -768,6 +783,61 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    pub async fn get_mempool_pending_transactions(
+        &self,
+        sender: Option<AccountAddress>,
+        start: Option<AccountAddress>,
+        limit: u16,
+    ) -> Result<Vec<SenderPendingTransactions>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetPendingTransactions(
+                sender,
+                start,
+                limit as usize,
+                req_sender,
+            ))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    pub async fn get_mempool_broadcast_statuses(&self) -> Result<Vec<PeerBroadcastStatus>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetBroadcastStatuses(req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    /// Evicts the transaction from mempool, and returns whether it was in mempool
+    pub async fn evict_mempool_transaction(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+    ) -> Result<bool> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::EvictTransaction(
+                sender,
+                sequence_number,
+                req_sender,
+            ))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
@@ -846,6 +916,22 @@ impl Context {
             deprioritized_gas_estimate: Some(min_gas_unit_price),
             gas_estimate: min_gas_unit_price,
             prioritized_gas_estimate: Some(self.next_bucket(min_gas_unit_price)),
+            base_fee_per_gas: None,
+        }
+    }
+
+    /// Transactions have to pay at least the base fee to be included, so the estimates are
+    /// raised to it, and it is returned alongside them.
+    fn apply_base_fee(&self, estimation: GasEstimation, base_fee_per_gas: u64) -> GasEstimation {
+        GasEstimation {
+            deprioritized_gas_estimate: estimation
+                .deprioritized_gas_estimate
+                .map(|price| price.max(base_fee_per_gas)),
+            gas_estimate: estimation.gas_estimate.max(base_fee_per_gas),
+            prioritized_gas_estimate: estimation
+                .prioritized_gas_estimate
+                .map(|price| price.max(self.next_bucket(base_fee_per_gas))),
+            base_fee_per_gas: Some(base_fee_per_gas),
         }
     }
 
@@ -912,6 +998,17 @@ impl Context {
     pub fn estimate_gas_price<E: InternalError>(
         &self,
         ledger_info: &LedgerInfo,
+    ) -> Result<GasEstimation, E> {
+        let estimation = self.estimate_gas_price_from_history(ledger_info)?;
+        Ok(match self.base_fee_per_gas(ledger_info)? {
+            Some(base_fee_per_gas) => self.apply_base_fee(estimation, base_fee_per_gas),
+            None => estimation,
+        })
+    }
+
+    fn estimate_gas_price_from_history<E: InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
     ) -> Result<GasEstimation, E> {
         let config = &self.node_config.api.gas_estimation;
         let min_gas_unit_price = self.min_gas_unit_price(ledger_info)?;
@@ -924,6 +1021,7 @@ impl Context {
                 deprioritized_gas_estimate: Some(static_override.low),
                 gas_estimate: static_override.market,
                 prioritized_gas_estimate: Some(static_override.aggressive),
+                base_fee_per_gas: None,
             });
         }
 
@@ -1095,6 +1193,7 @@ impl Context {
             deprioritized_gas_estimate: Some(low_price),
             gas_estimate: market_price,
             prioritized_gas_estimate: Some(aggressive_price),
+            base_fee_per_gas: None,
         };
         // 4. Update cache
         // GC old entries
@@ -1187,6 +1286,10 @@ impl Context {
         }
     }
 
+    /// Returns the per-block limit on the sum of the `gas_used` of the committed transactions, if
+    /// any. The effective gas limit of `BlockGasLimitType::ComplexLimitV1` weights execution, IO
+    /// and storage gas separately and penalizes conflicts, so it can't be compared with `gas_used`
+    /// and is not returned.
     pub fn block_gas_limit<E: InternalError>(
         &self,
         ledger_info: &LedgerInfo,
@@ -1220,8 +1323,15 @@ impl Context {
                 })?;
             let storage_adapter = StorageAdapter::new(&state_view);
 
-            let block_gas_limit = OnChainExecutionConfig::fetch_config(&storage_adapter)
-                .and_then(|config| config.block_gas_limit());
+            let block_gas_limit =
+                OnChainExecutionConfig::fetch_config(&storage_adapter).and_then(|config| {
+                    match config.block_gas_limit_type() {
+                        BlockGasLimitType::Limit(limit) => Some(limit),
+                        BlockGasLimitType::NoLimit | BlockGasLimitType::ComplexLimitV1 { .. } => {
+                            None
+                        },
+                    }
+                });
 
             // Update the cache
             cache.block_gas_limit = block_gas_limit;
@@ -1230,6 +1340,21 @@ impl Context {
         }
     }
 
+    /// Returns the base fee per gas unit at the given ledger version, or `None` if the base fee
+    /// market is disabled.
+    pub fn base_fee_per_gas<E: InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
+    ) -> Result<Option<u64>, E> {
+        let state_view = self
+            .db
+            .state_view_at_version(Some(ledger_info.version()))
+            .map_err(|e| E::internal_with_code(e, AptosErrorCode::InternalError, ledger_info))?;
+        Ok(BaseFee::fetch_base_fee_per_gas(&StorageAdapter::new(
+            &state_view,
+        )))
+    }
+
     pub fn check_api_output_enabled<E: ForbiddenError>(
         &self,
         api_name: &'static str,
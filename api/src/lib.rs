@@ -16,6 +16,7 @@ mod events;
 mod failpoint;
 mod index;
 mod log;
+mod mempool_admin;
 pub mod metrics;
 mod page;
 mod response;
@@ -0,0 +1,129 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admin endpoints for operators to inspect and manage the mempool of the node. Requests are
+//! authenticated by presenting the admin passcode as a bearer token.
+
+use crate::context::Context;
+use aptos_api_types::Address;
+use aptos_crypto::HashValue;
+use aptos_mempool::{PeerBroadcastStatus, SenderPendingTransactions};
+use poem::{
+    handler,
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    web::{Data, Json, Query},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+#[derive(Deserialize, Serialize)]
+pub struct PendingTransactionsQuery {
+    sender: Option<Address>,
+    /// The address after which the page of senders starts
+    start: Option<Address>,
+    /// The maximum number of senders in the page
+    limit: u16,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct EvictTransactionQuery {
+    sender: Address,
+    sequence_number: u64,
+}
+
+/// Lists the pending transactions per sender, including the parked ones, optionally only of
+/// the given sender. Senders are paged in order of address: the next page starts after the last
+/// sender of the previous one.
+#[handler]
+pub async fn get_pending_transactions_poem(
+    context: Data<&Arc<Context>>,
+    headers: &HeaderMap,
+    Query(query): Query<PendingTransactionsQuery>,
+) -> poem::Result<Json<Vec<SenderPendingTransactions>>> {
+    authenticate(&context, headers)?;
+    let max_page_size = context.max_transactions_page_size();
+    if query.limit == 0 || query.limit > max_page_size {
+        return Err(poem::Error::from_string(
+            format!("limit must be between 1 and {}", max_page_size),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+    let pending_txns = context
+        .get_mempool_pending_transactions(
+            query.sender.map(Into::into),
+            query.start.map(Into::into),
+            query.limit,
+        )
+        .await?;
+    Ok(Json(pending_txns))
+}
+
+/// Shows the broadcast status of each upstream peer
+#[handler]
+pub async fn get_broadcast_statuses_poem(
+    context: Data<&Arc<Context>>,
+    headers: &HeaderMap,
+) -> poem::Result<Json<Vec<PeerBroadcastStatus>>> {
+    authenticate(&context, headers)?;
+    let statuses = context.get_mempool_broadcast_statuses().await?;
+    Ok(Json(statuses))
+}
+
+/// Evicts the transaction with the given sender and sequence number from mempool
+#[handler]
+pub async fn evict_transaction_poem(
+    context: Data<&Arc<Context>>,
+    headers: &HeaderMap,
+    Query(query): Query<EvictTransactionQuery>,
+) -> poem::Result<String> {
+    authenticate(&context, headers)?;
+    let sender = query.sender.into();
+    if context
+        .evict_mempool_transaction(sender, query.sequence_number)
+        .await?
+    {
+        Ok(format!(
+            "Evicted transaction {}:{}",
+            sender, query.sequence_number
+        ))
+    } else {
+        Err(poem::Error::from_string(
+            format!(
+                "Transaction {}:{} is not in mempool",
+                sender, query.sequence_number
+            ),
+            StatusCode::NOT_FOUND,
+        ))
+    }
+}
+
+/// Verifies that the admin endpoints are enabled, and that the request presents the passcode
+/// whose hash is configured
+fn authenticate(context: &Context, headers: &HeaderMap) -> poem::Result<()> {
+    let api_config = &context.node_config.api;
+    if !api_config.mempool_admin_enabled {
+        return Err(poem::Error::from_string(
+            "Mempool admin endpoints are not enabled",
+            StatusCode::FORBIDDEN,
+        ));
+    }
+
+    let passcode = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix(BEARER_PREFIX));
+    let passcode_hash = api_config
+        .admin_passcode_sha3_256
+        .as_ref()
+        .and_then(|hash| HashValue::from_hex(hash.trim_start_matches("0x")).ok());
+    match (passcode, passcode_hash) {
+        (Some(passcode), Some(passcode_hash))
+            if HashValue::sha3_256_of(passcode.as_bytes()) == passcode_hash =>
+        {
+            Ok(())
+        },
+        _ => Err(poem::Error::from_status(StatusCode::UNAUTHORIZED)),
+    }
+}
@@ -5,8 +5,8 @@
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
     context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    log::middleware_log, mempool_admin, set_failpoints, state::StateApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::X_APTOS_CLIENT;
@@ -189,6 +189,26 @@ pub fn attach_poem_to_runtime(
                     .at(
                         "/set_failpoint",
                         poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
+                    )
+                    // The authenticated admin endpoints are also outside of the OpenAPI spec.
+                    .nest(
+                        "/admin/mempool",
+                        Route::new()
+                            .at(
+                                "/transactions",
+                                poem::get(mempool_admin::get_pending_transactions_poem)
+                                    .data(context.clone()),
+                            )
+                            .at(
+                                "/broadcasts",
+                                poem::get(mempool_admin::get_broadcast_statuses_poem)
+                                    .data(context.clone()),
+                            )
+                            .at(
+                                "/evict",
+                                poem::post(mempool_admin::evict_transaction_poem)
+                                    .data(context.clone()),
+                            ),
                     ),
             )
             .with(cors)
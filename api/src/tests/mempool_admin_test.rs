@@ -0,0 +1,173 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::NodeConfig;
+use aptos_crypto::HashValue;
+use aptos_mempool::{PeerBroadcastStatus, PendingTransactionStatus, SenderPendingTransactions};
+use poem::http::header::AUTHORIZATION;
+
+const PASSCODE: &str = "passcode";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_mempool_admin_disabled() {
+    let context = new_test_context(current_function_name!());
+
+    let resp = context
+        .reply(admin_request(
+            "GET",
+            "/transactions?limit=10",
+            Some(PASSCODE),
+        ))
+        .await;
+    assert_eq!(resp.status(), 403);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_mempool_admin_endpoints() {
+    let mut context = new_mempool_admin_test_context(current_function_name!());
+
+    // Submit a transaction to mempool
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn).unwrap())
+        .await;
+
+    // Requests without the right passcode are rejected
+    for passcode in [None, Some("wrong passcode")] {
+        let resp = context
+            .reply(admin_request("GET", "/transactions?limit=10", passcode))
+            .await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    // Listing all senders requires a limit within the page size
+    for path in [
+        "/transactions",
+        "/transactions?limit=0",
+        "/transactions?limit=10000",
+    ] {
+        let resp = context
+            .reply(admin_request("GET", path, Some(PASSCODE)))
+            .await;
+        assert_eq!(resp.status(), 400);
+    }
+
+    // The transaction is pending and ready
+    let path = format!("/transactions?sender={}&limit=1", txn.sender());
+    let resp = context
+        .reply(admin_request("GET", &path, Some(PASSCODE)))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let pending_txns: Vec<SenderPendingTransactions> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(pending_txns.len(), 1);
+    assert_eq!(pending_txns[0].sender, txn.sender());
+    assert_eq!(pending_txns[0].transactions.len(), 1);
+    let pending_txn = &pending_txns[0].transactions[0];
+    assert_eq!(pending_txn.hash, txn.clone().committed_hash());
+    assert_eq!(pending_txn.sequence_number, txn.sequence_number());
+    assert!(pending_txn.client_submitted);
+    assert_eq!(pending_txn.status, PendingTransactionStatus::Ready);
+
+    // A transaction of the sender after a gap is parked, until the gap is filled
+    let mut root_account = context.root_account().await;
+    root_account.increment_sequence_number();
+    root_account.increment_sequence_number();
+    let other_account = context.gen_account();
+    let parked_txn = context.create_user_account_by(&mut root_account, &other_account);
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&parked_txn).unwrap())
+        .await;
+    let resp = context
+        .reply(admin_request("GET", &path, Some(PASSCODE)))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let pending_txns: Vec<SenderPendingTransactions> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(pending_txns[0].transactions.len(), 2);
+    let pending_txn = &pending_txns[0].transactions[1];
+    assert_eq!(pending_txn.sequence_number, parked_txn.sequence_number());
+    assert_eq!(pending_txn.status, PendingTransactionStatus::Parked {
+        missing_sequence_numbers: txn.sequence_number() + 1..parked_txn.sequence_number()
+    });
+
+    // The mock mempool doesn't have any upstream peers
+    let resp = context
+        .reply(admin_request("GET", "/broadcasts", Some(PASSCODE)))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let statuses: Vec<PeerBroadcastStatus> = serde_json::from_slice(resp.body()).unwrap();
+    assert!(statuses.is_empty());
+
+    // Evict the transaction, after which it's no longer pending
+    let path = format!(
+        "/evict?sender={}&sequence_number={}",
+        txn.sender(),
+        txn.sequence_number()
+    );
+    let resp = context
+        .reply(admin_request("POST", &path, Some(PASSCODE)))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let resp = context
+        .reply(admin_request("POST", &path, Some(PASSCODE)))
+        .await;
+    assert_eq!(resp.status(), 404);
+
+    // The gap now starts at the evicted transaction
+    let path = format!("/transactions?sender={}&limit=1", txn.sender());
+    let resp = context
+        .reply(admin_request("GET", &path, Some(PASSCODE)))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let pending_txns: Vec<SenderPendingTransactions> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(pending_txns[0].transactions.len(), 1);
+    assert_eq!(
+        pending_txns[0].transactions[0].status,
+        PendingTransactionStatus::Parked {
+            missing_sequence_numbers: txn.sequence_number()..parked_txn.sequence_number()
+        }
+    );
+    let path = format!(
+        "/evict?sender={}&sequence_number={}",
+        parked_txn.sender(),
+        parked_txn.sequence_number()
+    );
+    let resp = context
+        .reply(admin_request("POST", &path, Some(PASSCODE)))
+        .await;
+    assert_eq!(resp.status(), 200);
+
+    let resp = context
+        .reply(admin_request(
+            "GET",
+            "/transactions?limit=10",
+            Some(PASSCODE),
+        ))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let pending_txns: Vec<SenderPendingTransactions> = serde_json::from_slice(resp.body()).unwrap();
+    assert!(pending_txns.is_empty());
+}
+
+fn new_mempool_admin_test_context(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.mempool_admin_enabled = true;
+    node_config.api.admin_passcode_sha3_256 =
+        Some(HashValue::sha3_256_of(PASSCODE.as_bytes()).to_hex_literal());
+    new_test_context_with_config(test_name, node_config)
+}
+
+fn admin_request(method: &str, path: &str, passcode: Option<&str>) -> warp::test::RequestBuilder {
+    let mut req = warp::test::request()
+        .method(method)
+        .path(&format!("/v1/admin/mempool{}", path))
+        .body("");
+    if let Some(passcode) = passcode {
+        req = req.header(AUTHORIZATION.as_str(), format!("Bearer {}", passcode));
+    }
+    req
+}
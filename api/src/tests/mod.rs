@@ -8,6 +8,7 @@ mod converter_test;
 mod events_test;
 mod index_test;
 mod invalid_post_request_test;
+mod mempool_admin_test;
 mod modules;
 mod multisig_transactions_test;
 mod objects;
@@ -40,13 +40,13 @@ use aptos_types::{
 };
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use poem_openapi::{
-    param::{Path, Query},
+    param::{Header, Path, Query},
     payload::Json,
     ApiRequest, OpenApi,
 };
 use std::sync::Arc;
 
-generate_success_response!(SubmitTransactionResponse, (202, Accepted));
+generate_success_response!(SubmitTransactionResponse, (202, Accepted), (200, Replaced));
 
 generate_error_response!(
     SubmitTransactionError,
@@ -285,6 +285,14 @@ impl TransactionsApi {
     /// To submit a transaction as BCS, you must submit a SignedTransaction
     /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
     /// Make sure to use the `application/x.aptos.signed_transaction+bcs` Content-Type.
+    ///
+    /// A pending transaction with the same sender and sequence number can be
+    /// replaced by submitting a transaction with a sufficiently higher gas unit
+    /// price. In that case, a 200 is returned instead of a 202.
+    ///
+    /// If the `X-Aptos-Private-Submission` header is set to true, the node only
+    /// forwards the transaction to its trusted peers (e.g. validators and VFNs),
+    /// and never broadcasts it to other public peers.
     // TODO: Point to examples of both of these flows, in multiple languages.
     #[oai(
         path = "/transactions",
@@ -296,6 +304,9 @@ impl TransactionsApi {
         &self,
         accept_type: AcceptType,
         data: SubmitTransactionPost,
+        /// If true, the transaction is only forwarded to trusted peers
+        #[oai(name = "X-Aptos-Private-Submission")]
+        private_submission: Header<Option<bool>>,
     ) -> SubmitTransactionResult<PendingTransaction> {
         data.verify()
             .context("Submitted transaction invalid'")
@@ -313,8 +324,13 @@ impl TransactionsApi {
             .check_api_output_enabled("Submit transaction", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
         let signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
-        self.create(&accept_type, &ledger_info, signed_transaction)
-            .await
+        self.create(
+            &accept_type,
+            &ledger_info,
+            signed_transaction,
+            private_submission.0.unwrap_or_default(),
+        )
+        .await
     }
 
     /// Submit batch transactions
@@ -339,6 +355,10 @@ impl TransactionsApi {
     /// To submit a transaction as BCS, you must submit a SignedTransaction
     /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
     /// Make sure to use the `application/x.aptos.signed_transaction+bcs` Content-Type.
+    ///
+    /// If the `X-Aptos-Private-Submission` header is set to true, the node only
+    /// forwards the transactions to its trusted peers (e.g. validators and VFNs),
+    /// and never broadcasts them to other public peers.
     #[oai(
         path = "/transactions/batch",
         method = "post",
@@ -349,6 +369,9 @@ impl TransactionsApi {
         &self,
         accept_type: AcceptType,
         data: SubmitTransactionsBatchPost,
+        /// If true, the transactions are only forwarded to trusted peers
+        #[oai(name = "X-Aptos-Private-Submission")]
+        private_submission: Header<Option<bool>>,
     ) -> SubmitTransactionsBatchResult<TransactionsBatchSubmissionResult> {
         data.verify()
             .context("Submitted transactions invalid")
@@ -377,8 +400,13 @@ impl TransactionsApi {
                 &ledger_info,
             ));
         }
-        self.create_batch(&accept_type, &ledger_info, signed_transactions_batch)
-            .await
+        self.create_batch(
+            &accept_type,
+            &ledger_info,
+            signed_transactions_batch,
+            private_submission.0.unwrap_or_default(),
+        )
+        .await
     }
 
     /// Simulate transaction
@@ -587,6 +615,10 @@ impl TransactionsApi {
     /// (aggressive). Using a more aggressive value increases the likelihood that the transaction
     /// will make it into the next block; more aggressive values are computed with a larger history
     /// and higher percentile statistics. More details are in AIP-34.
+    ///
+    /// When the base fee market is enabled, every estimate is at least the current base fee, which
+    /// is returned as well. The full gas unit price is charged: the part covered by the base fee is
+    /// burned, and all of the price above it is paid as a tip.
     #[oai(
         path = "/estimate_gas_price",
         method = "get",
@@ -607,6 +639,8 @@ impl TransactionsApi {
                 BasicResponseStatus::Ok,
             )),
             AcceptType::Bcs => {
+                // The BCS response keeps its original layout, so fields added to `GasEstimation`
+                // since, such as the base fee, are only returned as JSON.
                 let gas_estimation_bcs = GasEstimationBcs {
                     gas_estimate: gas_estimation.gas_estimate,
                 };
@@ -1004,18 +1038,23 @@ impl TransactionsApi {
         }
     }
 
-    /// Submits a single transaction, and converts mempool codes to errors
-    async fn create_internal(&self, txn: SignedTransaction) -> Result<(), AptosError> {
+    /// Submits a single transaction, and converts mempool codes to a response status or errors
+    async fn create_internal(
+        &self,
+        txn: SignedTransaction,
+        private: bool,
+    ) -> Result<SubmitTransactionResponseStatus, AptosError> {
         let (mempool_status, vm_status_opt) = self
             .context
-            .submit_transaction(txn)
+            .submit_transaction(txn, private)
             .await
             .context("Mempool failed to initially evaluate submitted transaction")
             .map_err(|err| {
                 aptos_api_types::AptosError::new_with_error_code(err, AptosErrorCode::InternalError)
             })?;
         match mempool_status.code {
-            MempoolStatusCode::Accepted => Ok(()),
+            MempoolStatusCode::Accepted => Ok(SubmitTransactionResponseStatus::Accepted),
+            MempoolStatusCode::Replaced => Ok(SubmitTransactionResponseStatus::Replaced),
             MempoolStatusCode::MempoolIsFull | MempoolStatusCode::TooManyTransactions => {
                 Err(AptosError::new_with_error_code(
                     &mempool_status.message,
@@ -1062,9 +1101,10 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         ledger_info: &LedgerInfo,
         txn: SignedTransaction,
+        private: bool,
     ) -> SubmitTransactionResult<PendingTransaction> {
-        match self.create_internal(txn.clone()).await {
-            Ok(()) => match accept_type {
+        match self.create_internal(txn.clone(), private).await {
+            Ok(status) => match accept_type {
                 AcceptType::Json => {
                     let state_view = self
                         .context
@@ -1089,20 +1129,14 @@ impl TransactionsApi {
                                 AptosErrorCode::InternalError,
                                 ledger_info,
                             ))?;
-                    SubmitTransactionResponse::try_from_json((
-                        pending_txn,
-                        ledger_info,
-                        SubmitTransactionResponseStatus::Accepted,
-                    ))
+                    SubmitTransactionResponse::try_from_json((pending_txn, ledger_info, status))
                 },
                 // With BCS, we don't return the pending transaction for efficiency, because there
                 // is no new information.  The hash can be retrieved by hashing the original
                 // transaction.
-                AcceptType::Bcs => SubmitTransactionResponse::try_from_bcs((
-                    (),
-                    ledger_info,
-                    SubmitTransactionResponseStatus::Accepted,
-                )),
+                AcceptType::Bcs => {
+                    SubmitTransactionResponse::try_from_bcs(((), ledger_info, status))
+                },
             },
             Err(error) => match error.error_code {
                 AptosErrorCode::InternalError => Err(
@@ -1133,11 +1167,12 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         ledger_info: &LedgerInfo,
         txns: Vec<SignedTransaction>,
+        private: bool,
     ) -> SubmitTransactionsBatchResult<TransactionsBatchSubmissionResult> {
         // Iterate through transactions keeping track of failures
         let mut txn_failures = Vec::new();
         for (idx, txn) in txns.iter().enumerate() {
-            if let Err(error) = self.create_internal(txn.clone()).await {
+            if let Err(error) = self.create_internal(txn.clone(), private).await {
                 txn_failures.push(TransactionsBatchSingleSubmissionFailure {
                     error,
                     transaction_index: idx,
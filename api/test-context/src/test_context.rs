@@ -39,6 +39,7 @@ use aptos_types::{
     block_metadata::BlockMetadata,
     chain_id::ChainId,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    on_chain_config::BlockGasLimitType,
     transaction::{Transaction, TransactionPayload, TransactionStatus},
 };
 use aptos_vm::AptosVM;
@@ -607,7 +608,11 @@ impl TestContext {
         let parent_id = self.executor.committed_block_id();
         let result = self
             .executor
-            .execute_block((metadata.id(), txns.clone()).into(), parent_id, None)
+            .execute_block(
+                (metadata.id(), txns.clone()).into(),
+                parent_id,
+                BlockGasLimitType::NoLimit,
+            )
             .unwrap();
         let mut compute_status = result.compute_status().clone();
         assert_eq!(compute_status.len(), txns.len(), "{:?}", result);
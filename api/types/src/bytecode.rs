@@ -95,6 +95,8 @@ pub trait Bytecode {
                 mutable: true,
                 to: Box::new(self.new_move_type(t.borrow())),
             },
+            // Function types have no API representation yet
+            SignatureToken::Function(..) => MoveType::Unparsable(format!("{:?}", token)),
         }
     }
 
@@ -151,6 +151,14 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     timestamp: timestamp.into(),
                 })
             },
+            ValidatorTransaction(_) => {
+                Transaction::ValidatorTransaction(crate::transaction::ValidatorTransaction {
+                    info,
+                    events,
+                    timestamp: timestamp.into(),
+                })
+            },
+            BlockMetadataExt(txn) => (txn.block_metadata(), info, events).into(),
         })
     }
 
@@ -800,7 +808,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
             MoveTypeLayout::Struct(struct_layout) => {
                 self.try_into_vm_value_struct(struct_layout, val)?
             },
-            MoveTypeLayout::Signer => {
+            MoveTypeLayout::Signer | MoveTypeLayout::Function => {
                 bail!("unexpected move type {:?} for value {:?}", layout, val)
             },
         })
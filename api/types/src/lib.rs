@@ -53,8 +53,9 @@ pub use transaction::{
     SubmitTransactionRequest, Transaction, TransactionData, TransactionId, TransactionInfo,
     TransactionOnChainData, TransactionPayload, TransactionSignature, TransactionSigningMessage,
     TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
-    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
-    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
+    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, ValidatorTransaction,
+    VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload,
+    WriteTableItem,
 };
 pub use view::ViewRequest;
 pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
@@ -168,6 +168,7 @@ pub enum Transaction {
     GenesisTransaction(GenesisTransaction),
     BlockMetadataTransaction(BlockMetadataTransaction),
     StateCheckpointTransaction(StateCheckpointTransaction),
+    ValidatorTransaction(ValidatorTransaction),
 }
 
 impl Transaction {
@@ -178,6 +179,7 @@ impl Transaction {
             Transaction::PendingTransaction(_) => 0,
             Transaction::GenesisTransaction(_) => 0,
             Transaction::StateCheckpointTransaction(txn) => txn.timestamp.0,
+            Transaction::ValidatorTransaction(txn) => txn.timestamp.0,
         }
     }
 
@@ -188,6 +190,7 @@ impl Transaction {
             Transaction::PendingTransaction(_) => None,
             Transaction::GenesisTransaction(txn) => Some(txn.info.version.into()),
             Transaction::StateCheckpointTransaction(txn) => Some(txn.info.version.into()),
+            Transaction::ValidatorTransaction(txn) => Some(txn.info.version.into()),
         }
     }
 
@@ -198,6 +201,7 @@ impl Transaction {
             Transaction::PendingTransaction(_txn) => false,
             Transaction::GenesisTransaction(txn) => txn.info.success,
             Transaction::StateCheckpointTransaction(txn) => txn.info.success,
+            Transaction::ValidatorTransaction(txn) => txn.info.success,
         }
     }
 
@@ -212,6 +216,7 @@ impl Transaction {
             Transaction::PendingTransaction(_txn) => "pending".to_owned(),
             Transaction::GenesisTransaction(txn) => txn.info.vm_status.clone(),
             Transaction::StateCheckpointTransaction(txn) => txn.info.vm_status.clone(),
+            Transaction::ValidatorTransaction(txn) => txn.info.vm_status.clone(),
         }
     }
 
@@ -222,6 +227,7 @@ impl Transaction {
             Transaction::GenesisTransaction(_) => "genesis_transaction",
             Transaction::BlockMetadataTransaction(_) => "block_metadata_transaction",
             Transaction::StateCheckpointTransaction(_) => "state_checkpoint_transaction",
+            Transaction::ValidatorTransaction(_) => "validator_transaction",
         }
     }
 
@@ -234,6 +240,7 @@ impl Transaction {
             },
             Transaction::GenesisTransaction(txn) => &txn.info,
             Transaction::StateCheckpointTransaction(txn) => &txn.info,
+            Transaction::ValidatorTransaction(txn) => &txn.info,
         })
     }
 }
@@ -382,6 +389,20 @@ pub struct StateCheckpointTransaction {
     pub timestamp: U64,
 }
 
+/// A validator transaction
+///
+/// This updates on-chain state on behalf of the validators, outside of the user
+/// transactions of a block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ValidatorTransaction {
+    #[serde(flatten)]
+    #[oai(flatten)]
+    pub info: TransactionInfo,
+    /// The events emitted by the validator transaction
+    pub events: Vec<Event>,
+    pub timestamp: U64,
+}
+
 /// A request to submit a transaction
 ///
 /// This requires a transaction and a signature of it
@@ -1436,4 +1457,8 @@ pub struct GasEstimation {
     pub gas_estimate: u64,
     /// The prioritized estimate for the gas unit price
     pub prioritized_gas_estimate: Option<u64>,
+    /// The base fee per gas unit every transaction currently has to pay, if the base fee
+    /// market is enabled. It is burned, all of the gas unit price above it is paid as a tip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<u64>,
 }
@@ -224,6 +224,37 @@ impl Aggregator {
         Ok(())
     }
 
+    /// Implements logic for a bounded addition: instead of failing, returns
+    /// `false` if adding `value` exceeds the limit of an aggregator. Note that
+    /// if aggregator stores a delta, the check is speculative and the limit is
+    /// validated against the base value only when the delta is materialized.
+    pub fn try_add(&mut self, value: u128) -> PartialVMResult<bool> {
+        // Failed additions do not change the state of an aggregator, so it is
+        // safe to keep using it afterwards.
+        match self.add(value) {
+            Ok(()) => Ok(true),
+            Err(e) if e.major_status() == StatusCode::ABORTED => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Implements logic for a bounded subtraction: instead of failing, returns
+    /// `false` if the value of an aggregator is smaller than `value`. To make
+    /// the check precise, the aggregator is materialized first.
+    pub fn try_sub(
+        &mut self,
+        resolver: &dyn AggregatorResolver,
+        id: &AggregatorID,
+        value: u128,
+    ) -> PartialVMResult<bool> {
+        self.read_and_materialize(resolver, id)?;
+        match self.sub(value) {
+            Ok(()) => Ok(true),
+            Err(e) if e.major_status() == StatusCode::ABORTED => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Implements logic for reading the value of an aggregator. As a
     /// result, the aggregator knows it value (i.e. its state changes to
     /// `Data`).
@@ -490,6 +521,48 @@ mod test {
         assert_err!(aggregator.sub(2));
     }
 
+    #[test]
+    fn test_try_add() {
+        let mut aggregator_data = AggregatorData::default();
+
+        // 0 + 150 + 100 > 200, but the aggregator keeps its value.
+        aggregator_data.create_new_aggregator(aggregator_id_for_test(200), 200);
+        let aggregator = aggregator_data
+            .get_aggregator(aggregator_id_for_test(200), 200)
+            .expect("Get aggregator failed");
+        assert!(aggregator.try_add(150).unwrap());
+        assert!(!aggregator.try_add(100).unwrap());
+        assert_eq!(aggregator.value, 150);
+        assert_eq!(aggregator.state, AggregatorState::Data);
+
+        // +0 to +800 > 600 fails speculatively, but +400 is recorded as a delta.
+        let aggregator = aggregator_data
+            .get_aggregator(aggregator_id_for_test(600), 600)
+            .expect("Get aggregator failed");
+        assert!(!aggregator.try_add(800).unwrap());
+        assert!(aggregator.try_add(400).unwrap());
+        assert_eq!(aggregator.value, 400);
+        assert_eq!(aggregator.state, AggregatorState::PositiveDelta);
+    }
+
+    #[test]
+    fn test_try_sub() {
+        let mut aggregator_data = AggregatorData::default();
+        let mut resolver = AggregatorStore::default();
+        let id = aggregator_id_for_test(600);
+        resolver.set_from_id(id, 300);
+
+        // Subtraction materializes the aggregator: 300 - 400 < 0, but 300 - 200 is ok.
+        let aggregator = aggregator_data
+            .get_aggregator(id, 600)
+            .expect("Get aggregator failed");
+        assert!(!aggregator.try_sub(&resolver, &id, 400).unwrap());
+        assert_eq!(aggregator.state, AggregatorState::Data);
+        assert_eq!(aggregator.value, 300);
+        assert!(aggregator.try_sub(&resolver, &id, 200).unwrap());
+        assert_eq!(aggregator.value, 100);
+    }
+
     #[test]
     fn test_commutative() {
         let mut aggregator_data = AggregatorData::default();
@@ -12,7 +12,7 @@ use aptos_state_view::TStateView;
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
-    on_chain_config::{Features, OnChainConfig, TimedFeatures},
+    on_chain_config::{BlockGasLimitType, Features, OnChainConfig, TimedFeatures},
     transaction::{
         SignedTransaction, Transaction, TransactionInfo, TransactionOutput, TransactionPayload,
         Version,
@@ -57,7 +57,7 @@ impl AptosDebugger {
         txns: Vec<Transaction>,
     ) -> Result<Vec<TransactionOutput>> {
         let state_view = DebuggerStateView::new(self.debugger.clone(), version);
-        AptosVM::execute_block(txns, &state_view, None)
+        AptosVM::execute_block(txns, &state_view, BlockGasLimitType::NoLimit)
             .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
     }
 
@@ -235,7 +235,7 @@ impl AptosDebugger {
             TimedFeatures::enable_all(),
         )
         .unwrap();
-        let mut session = move_vm.new_session(&state_view_storage, SessionId::Void);
+        let mut session = move_vm.new_session(&state_view_storage, SessionId::Void, None);
         f(&mut session).map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
         let change_set = session
             .finish(
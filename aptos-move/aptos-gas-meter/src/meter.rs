@@ -126,6 +126,8 @@ where
             Gt => GT,
             Le => LE,
             Ge => GE,
+
+            CallClosure => CALL_CLOSURE_BASE,
         }
     }
 
@@ -266,6 +268,24 @@ where
         }
     }
 
+    #[inline]
+    fn charge_pack_closure(
+        &mut self,
+        is_generic: bool,
+        captured: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let num_args = NumArgs::new(captured.len() as u64);
+
+        match is_generic {
+            false => self
+                .algebra
+                .charge_execution(PACK_CLOSURE_BASE + PACK_CLOSURE_PER_ARG * num_args),
+            true => self.algebra.charge_execution(
+                PACK_CLOSURE_GENERIC_BASE + PACK_CLOSURE_GENERIC_PER_ARG * num_args,
+            ),
+        }
+    }
+
     #[inline]
     fn charge_unpack(
         &mut self,
@@ -201,6 +201,13 @@ where
             args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
         ) -> PartialVMResult<()>;
 
+        [PACK_CLOSURE]
+        fn charge_pack_closure(
+            &mut self,
+            is_generic: bool,
+            captured: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
         [UNPACK]
         fn charge_unpack(
             &mut self,
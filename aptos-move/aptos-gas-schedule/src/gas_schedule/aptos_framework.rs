@@ -184,6 +184,15 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [transaction_context_get_script_hash_base: InternalGas, "transaction_context.get_script_hash.base", 4000],
         // Based on SHA3-256's cost
         [transaction_context_generate_unique_address_base: InternalGas, { 10.. => "transaction_context.generate_unique_address.base" }, 80000],
+        [transaction_context_sender_base: InternalGas, { 13.. => "transaction_context.sender.base" }, 4000],
+        [transaction_context_secondary_signers_base: InternalGas, { 13.. => "transaction_context.secondary_signers.base" }, 4000],
+        [transaction_context_secondary_signers_per_signer: InternalGasPerArg, { 13.. => "transaction_context.secondary_signers.per_signer" }, 400],
+        [transaction_context_gas_payer_base: InternalGas, { 13.. => "transaction_context.gas_payer.base" }, 4000],
+        [transaction_context_max_gas_amount_base: InternalGas, { 13.. => "transaction_context.max_gas_amount.base" }, 4000],
+        [transaction_context_gas_unit_price_base: InternalGas, { 13.. => "transaction_context.gas_unit_price.base" }, 4000],
+        [transaction_context_chain_id_base: InternalGas, { 13.. => "transaction_context.chain_id.base" }, 4000],
+        [transaction_context_entry_function_payload_base: InternalGas, { 13.. => "transaction_context.entry_function_payload.base" }, 4000],
+        [transaction_context_entry_function_payload_per_byte: InternalGasPerByte, { 13.. => "transaction_context.entry_function_payload.per_byte" }, 40],
 
         [code_request_publish_base: InternalGas, "code.request_publish.base", 10000],
         [code_request_publish_per_byte: InternalGasPerByte, "code.request_publish.per_byte", 40],
@@ -200,11 +209,16 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [aggregator_sub_base: InternalGas, "aggregator.sub.base", 6000],
         [aggregator_destroy_base: InternalGas, "aggregator.destroy.base", 10000],
         [aggregator_factory_new_aggregator_base: InternalGas, "aggregator_factory.new_aggregator.base", 10000],
+        [aggregator_factory_new_aggregator_v2_base: InternalGas, { 13.. => "aggregator_factory.new_aggregator_v2.base" }, 10000],
 
         [aggregator_v2_create_snapshot_base: InternalGas, {11.. => "aggregator_v2.create_snapshot.base"}, 6000],
         [aggregator_v2_copy_snapshot_base: InternalGas, {11.. => "aggregator_v2.copy_snapshot.base"}, 6000],
         [aggregator_v2_read_snapshot_base: InternalGas, {11.. => "aggregator_v2.read_snapshot.base"}, 6000],
         [aggregator_v2_string_concat_base: InternalGas, {11.. => "aggregator_v2.string_concat.base"}, 6000],
+        [aggregator_v2_try_add_base: InternalGas, { 13.. => "aggregator_v2.try_add.base" }, 6000],
+        [aggregator_v2_try_sub_base: InternalGas, { 13.. => "aggregator_v2.try_sub.base" }, 6000],
+        [aggregator_v2_read_base: InternalGas, { 13.. => "aggregator_v2.read.base" }, 6000],
+        [aggregator_v2_destroy_base: InternalGas, { 13.. => "aggregator_v2.destroy.base" }, 10000],
 
         [object_exists_at_base: InternalGas, { 7.. => "object.exists_at.base" }, 5000],
         // These are dummy value, they copied from storage gas in aptos-core/aptos-vm/src/aptos_vm_impl.rs
@@ -212,5 +226,13 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [object_exists_at_per_item_loaded: InternalGas, { 7.. => "object.exists_at.per_item_loaded" }, 8000],
         [string_utils_base: InternalGas, { 8.. => "string_utils.format.base" }, 6000],
         [string_utils_per_byte: InternalGasPerByte, { 8.. =>"string_utils.format.per_byte" }, 20],
+
+        [randomness_fetch_and_increment_txn_counter_base: InternalGas, { 13.. => "randomness.fetch_and_increment_txn_counter.base" }, 4000],
+        [randomness_is_unbiasable_base: InternalGas, { 13.. => "randomness.is_unbiasable.base" }, 4000],
+
+        [function_info_is_identifier_base: InternalGas, { 13.. => "function_info.is_identifier.base" }, 4000],
+        [function_info_is_identifier_per_byte: InternalGasPerByte, { 13.. => "function_info.is_identifier.per_byte" }, 20],
+        [function_info_check_dispatch_type_compatibility_impl_base: InternalGas, { 13.. => "function_info.check_dispatch_type_compatibility_impl.base" }, 10000],
+        [dispatchable_fungible_asset_dispatch_base: InternalGas, { 13.. => "dispatchable_fungible_asset.dispatch.base" }, 6000],
     ]
 );
@@ -99,6 +99,24 @@ crate::gas_schedule::macros::define_gas_parameters!(
             "unpack_generic.per_field",
             800
         ],
+        // closure
+        [pack_closure_base: InternalGas, { 14.. => "pack_closure.base" }, 4400],
+        [
+            pack_closure_per_arg: InternalGasPerArg,
+            { 14.. => "pack_closure.per_arg" },
+            800
+        ],
+        [
+            pack_closure_generic_base: InternalGas,
+            { 14.. => "pack_closure_generic.base" },
+            4400
+        ],
+        [
+            pack_closure_generic_per_arg: InternalGasPerArg,
+            { 14.. => "pack_closure_generic.per_arg" },
+            800
+        ],
+        [call_closure_base: InternalGas, { 14.. => "call_closure.base" }, 2000],
         // ref
         [read_ref_base: InternalGas, "read_ref.base", 4000],
         [
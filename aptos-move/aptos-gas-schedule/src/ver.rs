@@ -8,6 +8,15 @@
 ///   - Changing how gas is calculated in any way
 ///
 /// Change log:
+/// - V14
+///   - Function values (closures)
+/// - V13
+///   - Added randomness natives (fetch_and_increment_txn_counter, is_unbiasable)
+///   - Added transaction_context natives for the user transaction (sender, secondary signers,
+///     gas payer, max gas amount, gas unit price, chain id, entry function payload)
+///   - Added aggregator_v2 natives (create, try_add, try_sub, read, destroy)
+///   - Added function_info natives (is_identifier, check_dispatch_type_compatibility) and
+///     the dispatchable_fungible_asset dispatch native
 /// - V12
 ///   - Making resource group charge on first read independent of BTreeMap serialization.
 /// - V11
@@ -44,4 +53,4 @@
 ///       global operations.
 /// - V1
 ///   - TBA
-pub const LATEST_GAS_FEATURE_VERSION: u64 = 12;
+pub const LATEST_GAS_FEATURE_VERSION: u64 = 14;
@@ -304,6 +304,27 @@ where
         self.base.charge_pack(is_generic, args)
     }
 
+    #[inline]
+    fn charge_pack_closure(
+        &mut self,
+        is_generic: bool,
+        captured: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        self.use_heap_memory(
+            captured
+                .clone()
+                .fold(AbstractValueSize::zero(), |acc, val| {
+                    acc + self
+                        .vm_gas_params()
+                        .misc
+                        .abs_val
+                        .abstract_stack_size(val, self.feature_version())
+                }),
+        )?;
+
+        self.base.charge_pack_closure(is_generic, captured)
+    }
+
     #[inline]
     fn charge_unpack(
         &mut self,
@@ -142,6 +142,18 @@ impl SafeNativeBuilder {
                     OutOfGas => Ok(NativeResult::out_of_gas(context.gas_used)),
                     // TODO(Gas): Check if err is indeed an invariant violation.
                     InvariantViolation(err) => Err(err),
+                    FunctionDispatch {
+                        module_name,
+                        func_name,
+                        ty_args,
+                        args,
+                    } => Ok(NativeResult::CallFunction {
+                        cost: context.gas_used,
+                        module_name,
+                        func_name,
+                        ty_args,
+                        args,
+                    }),
                 },
             }
         };
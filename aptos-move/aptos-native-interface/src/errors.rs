@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_binary_format::errors::PartialVMError;
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
+use smallvec::SmallVec;
 
 /// Saner representation of a native function error.
 #[allow(unused)]
@@ -23,6 +26,19 @@ pub enum SafeNativeError {
     /// Indicating that the native function ran into some internal errors that shall not normally
     /// be triggerable by user inputs.
     InvariantViolation(PartialVMError),
+
+    /// Indicating that the native function wants the VM to call the given Move function in its
+    /// place, passing on the given arguments. The return values of the called function become
+    /// the return values of the native function.
+    ///
+    /// The VM checks that the target is a public function with a signature compatible with the
+    /// native function, and that its module is not already active on the call stack.
+    FunctionDispatch {
+        module_name: ModuleId,
+        func_name: Identifier,
+        ty_args: Vec<Type>,
+        args: SmallVec<[Value; 1]>,
+    },
 }
 
 // Allows us to keep using the `?` operator on function calls that return `PartialVMResult` inside safe natives.
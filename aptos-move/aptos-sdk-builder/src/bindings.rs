@@ -0,0 +1,884 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed Rust bindings for Move packages.
+//!
+//! For every module of a package, the generated crate has a Rust module with
+//! - an `entry` module with a builder of a `TransactionPayload` for each entry function,
+//! - a `view` module with a caller over `aptos_rest_client::Client` for each view function, and
+//! - a serde struct for each Move struct. Its BCS representation is the one of the Move struct,
+//!   so resources and events can be decoded from BCS.
+//!
+//! Structs of dependencies which are used by the package are generated into the `deps` module,
+//! by address and module, e.g., `deps::x1::coin::Coin`.
+
+use anyhow::{anyhow, bail, Result};
+use heck::SnakeCase;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{
+        FunctionDefinitionIndex, SignatureToken, StructDefinitionIndex, StructFieldInformation,
+        StructHandleIndex,
+    },
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::{IdentStr, Identifier},
+    language_storage::ModuleId,
+};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::Write,
+    path::PathBuf,
+};
+
+/// A module of the package to generate bindings for.
+pub struct BindingsModule<'a> {
+    pub module: &'a CompiledModule,
+    /// Source map of the module, from which the names of parameters and type parameters are
+    /// taken. Without one, they are named by position.
+    pub source_map: Option<&'a SourceMap>,
+    /// Names of the functions of the module which are marked as `#[view]`
+    pub view_functions: BTreeSet<Identifier>,
+}
+
+/// How to write the crate with bindings for a package.
+pub struct BindingsInstaller {
+    install_dir: PathBuf,
+    /// The dependency on `aptos-sdk` in the `Cargo.toml` of the generated crate, e.g.,
+    /// `{ git = "https://github.com/aptos-labs/aptos-core", branch = "devnet" }`
+    aptos_sdk_dependency: String,
+}
+
+impl BindingsInstaller {
+    pub fn new(install_dir: PathBuf, aptos_sdk_dependency: String) -> Self {
+        Self {
+            install_dir,
+            aptos_sdk_dependency,
+        }
+    }
+
+    /// Writes the crate `crate_name` with bindings for the `modules` of the package
+    /// `package_name`, returning the path of the crate. `dependencies` must contain the modules
+    /// defining the structs which are used by the package.
+    pub fn install(
+        &self,
+        crate_name: &str,
+        package_name: &str,
+        modules: &[BindingsModule],
+        dependencies: &[&CompiledModule],
+    ) -> Result<PathBuf> {
+        let mut generator = Generator::new(modules, dependencies);
+        let mut files = BTreeMap::new();
+        for module in modules {
+            let file_name = format!("{}.rs", module.module.self_id().name());
+            files.insert(file_name, generator.package_module(module)?);
+        }
+        let deps = generator.dependency_structs()?;
+
+        let crate_path = self.install_dir.join(crate_name);
+        let src_path = crate_path.join("src");
+        fs::create_dir_all(&src_path)?;
+        fs::write(
+            crate_path.join("Cargo.toml"),
+            format!(
+                r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+anyhow = "1.0"
+aptos-sdk = {}
+serde = {{ version = "1.0", features = ["derive"] }}
+serde_json = "1.0"
+"#,
+                crate_name, self.aptos_sdk_dependency
+            ),
+        )?;
+
+        let mut lib = format!(
+            r#"//! Rust bindings for the Move package `{}`.
+//!
+//! This code was generated by `aptos move generate-rust-bindings`. Do not modify!
+
+#![allow(dead_code, unused_imports, clippy::too_many_arguments)]
+
+pub use aptos_sdk;
+
+"#,
+            package_name
+        );
+        if let Some(deps) = deps {
+            lib.push_str("pub mod deps;\n");
+            fs::write(src_path.join("deps.rs"), deps)?;
+        }
+        for module in modules {
+            lib.push_str(&format!(
+                "pub mod {};\n",
+                quote_ident(module.module.self_id().name().as_str())
+            ));
+        }
+        fs::write(src_path.join("lib.rs"), lib)?;
+        for (file_name, contents) in files {
+            fs::write(src_path.join(file_name), contents)?;
+        }
+        Ok(crate_path)
+    }
+}
+
+/// Identifies a struct by its module and name.
+type StructKey = (ModuleId, Identifier);
+
+struct Generator<'a> {
+    /// Modules of the package and of its dependencies, by id
+    modules: BTreeMap<ModuleId, &'a CompiledModule>,
+    package_modules: BTreeSet<ModuleId>,
+    /// Structs of dependencies which are used by the package, and which have been generated
+    /// or still need to be
+    dependency_structs: BTreeSet<StructKey>,
+    pending_structs: Vec<StructKey>,
+}
+
+/// A parameter of a generated function.
+struct Param {
+    name: String,
+    token: SignatureToken,
+    rust_type: String,
+}
+
+impl<'a> Generator<'a> {
+    fn new(modules: &[BindingsModule<'a>], dependencies: &[&'a CompiledModule]) -> Self {
+        let package_modules = modules.iter().map(|m| m.module.self_id()).collect();
+        let modules = modules
+            .iter()
+            .map(|m| m.module)
+            .chain(dependencies.iter().copied())
+            .map(|module| (module.self_id(), module))
+            .collect();
+        Self {
+            modules,
+            package_modules,
+            dependency_structs: BTreeSet::new(),
+            pending_structs: vec![],
+        }
+    }
+
+    /// Returns the contents of the Rust module for a module of the package.
+    fn package_module(&mut self, bindings: &BindingsModule) -> Result<Vec<u8>> {
+        indented(|out| self.output_package_module(out, bindings))
+    }
+
+    fn output_package_module(
+        &mut self,
+        out: &mut IndentedWriter<&mut Vec<u8>>,
+        bindings: &BindingsModule,
+    ) -> Result<()> {
+        let module = bindings.module;
+        let module_id = module.self_id();
+        writeln!(
+            out,
+            "//! Bindings for the Move module `{}`.\n",
+            module_id_str(&module_id)
+        )?;
+        output_imports(out)?;
+        writeln!(
+            out,
+            r#"
+pub const MODULE_ADDRESS: AccountAddress = AccountAddress::new({:?});
+pub const MODULE_NAME: &str = "{}";
+
+pub fn module_id() -> ModuleId {{
+    ModuleId::new(MODULE_ADDRESS, Identifier::new(MODULE_NAME).unwrap())
+}}"#,
+            module_id.address().into_bytes(),
+            module_id.name()
+        )?;
+
+        for (idx, def) in module.struct_defs().iter().enumerate() {
+            if let StructFieldInformation::Declared(_) = def.field_information {
+                self.output_struct(
+                    out,
+                    module,
+                    bindings.source_map,
+                    StructDefinitionIndex(idx as u16),
+                )?;
+            }
+        }
+
+        writeln!(
+            out,
+            "\n/// Builders of payloads of transactions calling the entry functions of the module\npub mod entry {{"
+        )?;
+        out.indent();
+        writeln!(out, "use super::*;")?;
+        for (idx, def) in module.function_defs().iter().enumerate() {
+            if def.is_entry {
+                self.output_entry_function(
+                    out,
+                    module,
+                    bindings.source_map,
+                    FunctionDefinitionIndex(idx as u16),
+                )?;
+            }
+        }
+        out.unindent();
+        writeln!(out, "}}")?;
+
+        writeln!(
+            out,
+            "\n/// Callers of the view functions of the module\npub mod view {{"
+        )?;
+        out.indent();
+        writeln!(out, "use super::*;")?;
+        for (idx, def) in module.function_defs().iter().enumerate() {
+            let name = module.identifier_at(module.function_handle_at(def.function).name);
+            if bindings.view_functions.contains(name) {
+                self.output_view_function(
+                    out,
+                    module,
+                    bindings.source_map,
+                    FunctionDefinitionIndex(idx as u16),
+                )?;
+            }
+        }
+        out.unindent();
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    /// Returns the contents of the `deps` module, with all structs of dependencies which are
+    /// used by the package, or `None` if there are none.
+    fn dependency_structs(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut structs_by_module: BTreeMap<ModuleId, Vec<u8>> = BTreeMap::new();
+        while let Some((module_id, name)) = self.pending_structs.pop() {
+            let module = *self.modules.get(&module_id).ok_or_else(|| {
+                anyhow!(
+                    "Module {} is used by the package, but not among its dependencies",
+                    module_id_str(&module_id)
+                )
+            })?;
+            let def_idx = find_struct(module, &name)
+                .ok_or_else(|| anyhow!("Struct {} not found", struct_str(&module_id, &name)))?;
+            if let StructFieldInformation::Native = module.struct_def_at(def_idx).field_information
+            {
+                bail!(
+                    "Native struct {} has no Rust representation",
+                    struct_str(&module_id, &name)
+                )
+            }
+            let code = indented(|out| self.output_struct(out, module, None, def_idx))?;
+            structs_by_module.entry(module_id).or_default().extend(code);
+        }
+        if structs_by_module.is_empty() {
+            return Ok(None);
+        }
+
+        indented(|out| {
+            writeln!(
+                out,
+                "//! Structs of dependencies which are used by the package, by address and module."
+            )?;
+            let mut current_address = None;
+            for (module_id, structs) in structs_by_module {
+                if current_address != Some(*module_id.address()) {
+                    if current_address.is_some() {
+                        out.unindent();
+                        writeln!(out, "}}")?;
+                    }
+                    writeln!(out, "\npub mod {} {{", address_ident(module_id.address()))?;
+                    out.indent();
+                    current_address = Some(*module_id.address());
+                }
+                writeln!(
+                    out,
+                    "\n/// Structs of the Move module `{}`\npub mod {} {{",
+                    module_id_str(&module_id),
+                    quote_ident(module_id.name().as_str())
+                )?;
+                out.indent();
+                output_imports(out)?;
+                out.write_all(&structs)?;
+                out.unindent();
+                writeln!(out, "}}")?;
+            }
+            out.unindent();
+            writeln!(out, "}}")?;
+            Ok(())
+        })
+        .map(Some)
+    }
+
+    fn output_struct(
+        &mut self,
+        out: &mut IndentedWriter<&mut Vec<u8>>,
+        module: &CompiledModule,
+        source_map: Option<&SourceMap>,
+        def_idx: StructDefinitionIndex,
+    ) -> Result<()> {
+        let def = module.struct_def_at(def_idx);
+        let handle = module.struct_handle_at(def.struct_handle);
+        let name = module.identifier_at(handle.name);
+        let fields = match &def.field_information {
+            StructFieldInformation::Declared(fields) => fields,
+            StructFieldInformation::Native => return Ok(()),
+        };
+        let type_param_names = (0..handle.type_parameters.len())
+            .map(|i| {
+                source_map
+                    .and_then(|map| map.get_struct_source_map(def_idx).ok())
+                    .and_then(|map| map.type_parameters.get(i))
+                    .map_or_else(|| format!("T{}", i), |(name, _)| quote_ident(name))
+            })
+            .collect::<Vec<_>>();
+        // Phantom type parameters do not occur in fields, and are left out in Rust
+        let rust_type_params = handle
+            .type_parameters
+            .iter()
+            .zip(&type_param_names)
+            .filter(|(param, _)| !param.is_phantom)
+            .map(|(_, name)| name.clone())
+            .collect::<Vec<_>>();
+        let generics = if rust_type_params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", rust_type_params.join(", "))
+        };
+
+        writeln!(
+            out,
+            "\n/// Move struct `{}`",
+            struct_str(&module.self_id(), name)
+        )?;
+        writeln!(
+            out,
+            "#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]\npub struct {}{} {{",
+            quote_ident(name.as_str()),
+            generics
+        )?;
+        out.indent();
+        for field in fields {
+            let rust_type = self.rust_type(module, &field.signature.0, Some(&type_param_names))?;
+            writeln!(
+                out,
+                "pub {}: {},",
+                quote_ident(module.identifier_at(field.name).as_str()),
+                rust_type
+            )?;
+        }
+        out.unindent();
+        writeln!(out, "}}")?;
+
+        let type_args_param = if handle.type_parameters.is_empty() {
+            ("", "vec![]")
+        } else {
+            ("type_args: Vec<TypeTag>", "type_args")
+        };
+        writeln!(
+            out,
+            r#"
+impl{0} {1}{0} {{
+    /// Returns the type of the struct on chain
+    pub fn struct_tag({2}) -> StructTag {{
+        StructTag {{
+            address: AccountAddress::new({3:?}),
+            module: Identifier::new("{4}").unwrap(),
+            name: Identifier::new("{5}").unwrap(),
+            type_params: {6},
+        }}
+    }}"#,
+            generics,
+            quote_ident(name.as_str()),
+            type_args_param.0,
+            module.self_id().address().into_bytes(),
+            module.self_id().name(),
+            name,
+            type_args_param.1,
+        )?;
+        if handle.abilities.has_key() {
+            writeln!(
+                out,
+                r#"
+    /// Fetches the resource stored at `address`
+    pub async fn fetch(
+        client: &Client,
+        address: AccountAddress,{}
+    ) -> anyhow::Result<Self>
+    where
+        Self: serde::de::DeserializeOwned,
+    {{
+        let resource_type = Self::struct_tag({}).to_canonical_string();
+        Ok(client
+            .get_account_resource_bcs::<Self>(address, &resource_type)
+            .await?
+            .into_inner())
+    }}"#,
+                if handle.type_parameters.is_empty() {
+                    ""
+                } else {
+                    "\n        type_args: Vec<TypeTag>,"
+                },
+                if handle.type_parameters.is_empty() {
+                    ""
+                } else {
+                    "type_args"
+                },
+            )?;
+        }
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    fn output_entry_function(
+        &mut self,
+        out: &mut IndentedWriter<&mut Vec<u8>>,
+        module: &CompiledModule,
+        source_map: Option<&SourceMap>,
+        def_idx: FunctionDefinitionIndex,
+    ) -> Result<()> {
+        let (name, type_params, params) =
+            match self.function_params(module, source_map, def_idx, &[]) {
+                Ok(signature) => signature,
+                Err(err) => return output_skipped(out, module, def_idx, err),
+            };
+        writeln!(
+            out,
+            "\n/// Builds the payload of a transaction calling `{}`",
+            function_str(module, name)
+        )?;
+        writeln!(
+            out,
+            "pub fn {}({}) -> TransactionPayload {{",
+            quote_ident(name.as_str()),
+            signature(&type_params, &params)
+        )?;
+        out.indent();
+        writeln!(
+            out,
+            "TransactionPayload::EntryFunction(EntryFunction::new(\n    module_id(),\n    Identifier::new(\"{}\").unwrap(),\n    vec![{}],\n    vec![",
+            name,
+            type_params.join(", ")
+        )?;
+        out.indent();
+        out.indent();
+        for param in &params {
+            writeln!(out, "bcs::to_bytes(&{}).unwrap(),", param.name)?;
+        }
+        out.unindent();
+        out.unindent();
+        writeln!(out, "    ],\n))")?;
+        out.unindent();
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    fn output_view_function(
+        &mut self,
+        out: &mut IndentedWriter<&mut Vec<u8>>,
+        module: &CompiledModule,
+        source_map: Option<&SourceMap>,
+        def_idx: FunctionDefinitionIndex,
+    ) -> Result<()> {
+        let reserved = ["client", "request", "values"];
+        let signature_and_returns = self
+            .function_params(module, source_map, def_idx, &reserved)
+            .and_then(|(name, type_params, params)| {
+                let handle = module.function_handle_at(module.function_def_at(def_idx).function);
+                let returns = module
+                    .signature_at(handle.return_)
+                    .0
+                    .iter()
+                    .map(|token| self.rust_type(module, token, None))
+                    .collect::<Result<Vec<_>>>()?;
+                let arguments = params
+                    .iter()
+                    .map(|param| json_argument(module, &param.name, &param.token, 0))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((name, type_params, params, returns, arguments))
+            });
+        let (name, type_params, params, returns, arguments) = match signature_and_returns {
+            Ok(signature) => signature,
+            Err(err) => return output_skipped(out, module, def_idx, err),
+        };
+        let return_type = if returns.len() == 1 {
+            returns[0].clone()
+        } else {
+            format!("({})", returns.join(", "))
+        };
+        let return_value = if returns.len() == 1 {
+            "bcs::from_bytes(&values[0])?".to_string()
+        } else {
+            format!(
+                "({})",
+                (0..returns.len())
+                    .map(|i| format!("bcs::from_bytes(&values[{}])?,", i))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        };
+
+        writeln!(
+            out,
+            "\n/// Calls the view function `{}`",
+            function_str(module, name)
+        )?;
+        let sig = signature(&type_params, &params);
+        writeln!(
+            out,
+            "pub async fn {}(client: &Client{}{}) -> anyhow::Result<{}> {{",
+            quote_ident(name.as_str()),
+            if sig.is_empty() { "" } else { ", " },
+            sig,
+            return_type
+        )?;
+        out.indent();
+        writeln!(
+            out,
+            "let request = ViewRequest {{\n    function: EntryFunctionId {{\n        module: module_id().into(),\n        name: IdentifierWrapper(Identifier::new(\"{}\").unwrap()),\n    }},\n    type_arguments: vec![{}],\n    arguments: vec![{}],\n}};",
+            name,
+            type_params
+                .iter()
+                .map(|param| format!("(&{}).into()", param))
+                .collect::<Vec<_>>()
+                .join(", "),
+            arguments.join(", "),
+        )?;
+        writeln!(
+            out,
+            "let values = client.view_bcs(&request, None).await?.into_inner();"
+        )?;
+        writeln!(
+            out,
+            "anyhow::ensure!(\n    values.len() == {0},\n    \"expected {0} return value(s), got {{}}\",\n    values.len()\n);",
+            returns.len()
+        )?;
+        writeln!(out, "Ok({})", return_value)?;
+        out.unindent();
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    /// Returns the name, the names of the type parameters, and the parameters of a function
+    /// without its signer parameters. Parameter names in `reserved` are suffixed with `_`.
+    fn function_params<'m>(
+        &mut self,
+        module: &'m CompiledModule,
+        source_map: Option<&SourceMap>,
+        def_idx: FunctionDefinitionIndex,
+        reserved: &[&str],
+    ) -> Result<(&'m IdentStr, Vec<String>, Vec<Param>)> {
+        let handle = module.function_handle_at(module.function_def_at(def_idx).function);
+        let function_source_map =
+            source_map.and_then(|map| map.get_function_source_map(def_idx).ok());
+        let mut used_names = reserved
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<BTreeSet<_>>();
+        let mut unique_name = |name: String| {
+            let mut name = quote_ident(&name);
+            while !used_names.insert(name.clone()) {
+                name.push('_');
+            }
+            name
+        };
+
+        let params = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| !is_signer(token))
+            .map(|(i, token)| {
+                let name = function_source_map
+                    .and_then(|map| map.parameters.get(i))
+                    .map_or_else(|| format!("arg{}", i), |(name, _)| name.clone());
+                Ok(Param {
+                    name: unique_name(name),
+                    token: token.clone(),
+                    rust_type: self.rust_type(module, token, None)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let type_params = (0..handle.type_parameters.len())
+            .map(|i| {
+                let name = function_source_map
+                    .and_then(|map| map.type_parameters.get(i))
+                    .map_or_else(
+                        || format!("type_arg{}", i),
+                        |(name, _)| name.to_snake_case(),
+                    );
+                unique_name(name)
+            })
+            .collect();
+        Ok((module.identifier_at(handle.name), type_params, params))
+    }
+
+    /// Returns the Rust type of a Move type. Type parameters are only allowed for fields of
+    /// structs, where their names are given by `type_params`.
+    fn rust_type(
+        &mut self,
+        module: &CompiledModule,
+        token: &SignatureToken,
+        type_params: Option<&[String]>,
+    ) -> Result<String> {
+        use SignatureToken as S;
+        Ok(match token {
+            S::Bool => "bool".to_string(),
+            S::U8 => "u8".to_string(),
+            S::U16 => "u16".to_string(),
+            S::U32 => "u32".to_string(),
+            S::U64 => "u64".to_string(),
+            S::U128 => "u128".to_string(),
+            S::U256 => "U256".to_string(),
+            S::Address => "AccountAddress".to_string(),
+            S::Vector(elem) => format!("Vec<{}>", self.rust_type(module, elem, type_params)?),
+            S::Struct(idx) => self.struct_type(module, *idx, &[], type_params)?,
+            S::StructInstantiation(idx, type_args) => {
+                self.struct_type(module, *idx, type_args, type_params)?
+            },
+            S::TypeParameter(idx) => match type_params {
+                Some(names) => names[*idx as usize].clone(),
+                None => bail!("Values of generic types are not supported"),
+            },
+            S::Signer | S::Reference(_) | S::MutableReference(_) => {
+                bail!("Values of type {:?} have no Rust representation", token)
+            },
+        })
+    }
+
+    fn struct_type(
+        &mut self,
+        module: &CompiledModule,
+        idx: StructHandleIndex,
+        type_args: &[SignatureToken],
+        type_params: Option<&[String]>,
+    ) -> Result<String> {
+        let handle = module.struct_handle_at(idx);
+        let key = (
+            module.module_id_for_handle(module.module_handle_at(handle.module)),
+            module.identifier_at(handle.name).to_owned(),
+        );
+        let rust_type_args = handle
+            .type_parameters
+            .iter()
+            .zip(type_args)
+            .filter(|(param, _)| !param.is_phantom)
+            .map(|(_, type_arg)| self.rust_type(module, type_arg, type_params))
+            .collect::<Result<Vec<_>>>()?;
+
+        let path = match well_known_type(&key) {
+            Some(path) => path.to_string(),
+            None if self.package_modules.contains(&key.0) => format!(
+                "crate::{}::{}",
+                quote_ident(key.0.name().as_str()),
+                quote_ident(key.1.as_str())
+            ),
+            None => {
+                let path = format!(
+                    "crate::deps::{}::{}::{}",
+                    address_ident(key.0.address()),
+                    quote_ident(key.0.name().as_str()),
+                    quote_ident(key.1.as_str())
+                );
+                if self.dependency_structs.insert(key.clone()) {
+                    self.pending_structs.push(key);
+                }
+                path
+            },
+        };
+        Ok(if rust_type_args.is_empty() {
+            path
+        } else {
+            format!("{}<{}>", path, rust_type_args.join(", "))
+        })
+    }
+}
+
+/// Structs of the framework with a Rust type of the same BCS representation.
+fn well_known_type((module_id, name): &StructKey) -> Option<&'static str> {
+    if module_id.address() != &AccountAddress::ONE {
+        return None;
+    }
+    match (module_id.name().as_str(), name.as_str()) {
+        ("string", "String") | ("ascii", "String") => Some("std::string::String"),
+        ("option", "Option") => Some("std::option::Option"),
+        // An object is represented by its address
+        ("object", "Object") => Some("AccountAddress"),
+        _ => None,
+    }
+}
+
+/// Returns the expression converting the argument `expr` of a view function to JSON, in the
+/// format expected by the REST API.
+fn json_argument(
+    module: &CompiledModule,
+    expr: &str,
+    token: &SignatureToken,
+    depth: usize,
+) -> Result<String> {
+    use SignatureToken as S;
+    let elems = |elem: &SignatureToken| -> Result<String> {
+        let var = format!("v{}", depth);
+        Ok(format!(
+            "{}.into_iter().map(|{}| {}).collect::<Vec<_>>()",
+            expr,
+            var,
+            json_argument(module, &var, elem, depth + 1)?
+        ))
+    };
+    Ok(match token {
+        S::Bool | S::U8 | S::U16 | S::U32 => format!("serde_json::json!({})", expr),
+        S::U64 | S::U128 | S::U256 => format!("serde_json::json!({}.to_string())", expr),
+        S::Address => format!("serde_json::json!({}.to_hex_literal())", expr),
+        S::Vector(elem) if **elem == S::U8 => {
+            format!("serde_json::json!(HexEncodedBytes::from({}))", expr)
+        },
+        S::Vector(elem) => format!("serde_json::json!({})", elems(elem)?),
+        S::Struct(idx) | S::StructInstantiation(idx, _) => {
+            let handle = module.struct_handle_at(*idx);
+            let key = (
+                module.module_id_for_handle(module.module_handle_at(handle.module)),
+                module.identifier_at(handle.name).to_owned(),
+            );
+            match (well_known_type(&key), token) {
+                (Some("std::string::String"), _) => format!("serde_json::json!({})", expr),
+                (Some("AccountAddress"), _) => {
+                    format!("serde_json::json!({}.to_hex_literal())", expr)
+                },
+                (Some("std::option::Option"), S::StructInstantiation(_, type_args)) => {
+                    format!(
+                        "serde_json::json!({{ \"vec\": {} }})",
+                        elems(&type_args[0])?
+                    )
+                },
+                _ => bail!(
+                    "Arguments of type {} are not supported",
+                    struct_str(&key.0, &key.1)
+                ),
+            }
+        },
+        _ => bail!("Arguments of type {:?} are not supported", token),
+    })
+}
+
+/// Runs `output` on an indented writer, returning what it wrote.
+fn indented(
+    output: impl FnOnce(&mut IndentedWriter<&mut Vec<u8>>) -> Result<()>,
+) -> Result<Vec<u8>> {
+    let mut code = vec![];
+    output(&mut IndentedWriter::new(&mut code, IndentConfig::Space(4)))?;
+    Ok(code)
+}
+
+fn output_imports(out: &mut IndentedWriter<&mut Vec<u8>>) -> Result<()> {
+    writeln!(
+        out,
+        r#"use aptos_sdk::{{
+    bcs,
+    move_types::{{
+        account_address::AccountAddress,
+        identifier::Identifier,
+        language_storage::{{ModuleId, StructTag, TypeTag}},
+        u256::U256,
+    }},
+    rest_client::{{
+        aptos_api_types::{{EntryFunctionId, HexEncodedBytes, IdentifierWrapper, ViewRequest}},
+        Client,
+    }},
+    types::transaction::{{EntryFunction, TransactionPayload}},
+}};
+use serde::{{Deserialize, Serialize}};"#
+    )?;
+    Ok(())
+}
+
+/// Records in the generated code that no binding could be generated for a function.
+fn output_skipped(
+    out: &mut IndentedWriter<&mut Vec<u8>>,
+    module: &CompiledModule,
+    def_idx: FunctionDefinitionIndex,
+    err: anyhow::Error,
+) -> Result<()> {
+    let handle = module.function_handle_at(module.function_def_at(def_idx).function);
+    writeln!(
+        out,
+        "\n// No binding for `{}`: {}",
+        function_str(module, module.identifier_at(handle.name)),
+        err
+    )?;
+    Ok(())
+}
+
+fn signature(type_params: &[String], params: &[Param]) -> String {
+    type_params
+        .iter()
+        .map(|name| format!("{}: TypeTag", name))
+        .chain(
+            params
+                .iter()
+                .map(|param| format!("{}: {}", param.name, param.rust_type)),
+        )
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn find_struct(module: &CompiledModule, name: &IdentStr) -> Option<StructDefinitionIndex> {
+    module
+        .struct_defs()
+        .iter()
+        .position(|def| {
+            module.identifier_at(module.struct_handle_at(def.struct_handle).name) == name
+        })
+        .map(|idx| StructDefinitionIndex(idx as u16))
+}
+
+fn is_signer(token: &SignatureToken) -> bool {
+    match token {
+        SignatureToken::Signer => true,
+        SignatureToken::Reference(inner) => **inner == SignatureToken::Signer,
+        _ => false,
+    }
+}
+
+fn module_id_str(module_id: &ModuleId) -> String {
+    format!(
+        "{}::{}",
+        module_id.address().to_hex_literal(),
+        module_id.name()
+    )
+}
+
+fn struct_str(module_id: &ModuleId, name: &IdentStr) -> String {
+    format!("{}::{}", module_id_str(module_id), name)
+}
+
+fn function_str(module: &CompiledModule, name: &IdentStr) -> String {
+    struct_str(&module.self_id(), name)
+}
+
+/// The name of the Rust module for the dependencies at `address`, e.g., `x1` for `0x1`.
+fn address_ident(address: &AccountAddress) -> String {
+    format!("x{}", address.short_str_lossless())
+}
+
+/// Quotes Move identifiers which are keywords in Rust.
+fn quote_ident(name: &str) -> String {
+    match name {
+        // These cannot be raw identifiers
+        "self" | "super" | "crate" | "Self" => format!("{}_", name),
+        _ if RUST_KEYWORDS.contains(&name) => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
@@ -5,6 +5,7 @@
 use aptos_types::transaction::EntryABI;
 use std::{ffi::OsStr, fs, io::Read, path::Path};
 
+pub mod bindings;
 pub mod golang;
 pub mod rust;
 
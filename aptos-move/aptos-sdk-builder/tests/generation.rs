@@ -2,8 +2,13 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_sdk_builder as buildgen;
+use aptos_sdk_builder::{
+    self as buildgen,
+    bindings::{BindingsInstaller, BindingsModule},
+};
 use aptos_types::transaction::EntryABI;
+use move_binary_format::access::ModuleAccess;
+use move_core_types::identifier::Identifier;
 use serde_generate as serdegen;
 use serde_generate::SourceInstaller as _;
 use serde_reflection::Registry;
@@ -93,3 +98,71 @@ fn test_that_rust_entry_fun_code_compiles() {
         EXPECTED_SCRIPT_FUN_OUTPUT,
     );
 }
+
+#[test]
+fn test_rust_bindings_for_framework_module() {
+    let modules = aptos_cached_packages::head_release_bundle().compiled_modules();
+    let coin = modules
+        .iter()
+        .find(|module| module.self_id().name().as_str() == "coin")
+        .unwrap();
+    let view_functions = aptos_framework::get_metadata_from_compiled_module(coin)
+        .unwrap()
+        .fun_attributes
+        .into_iter()
+        .filter(|(_, attrs)| attrs.iter().any(|attr| attr.is_view_function()))
+        .map(|(name, _)| Identifier::new(name).unwrap())
+        .collect();
+
+    let dir = tempdir().unwrap();
+    let sdk_path = std::fs::canonicalize("../../sdk").unwrap();
+    let crate_path = BindingsInstaller::new(
+        dir.path().to_path_buf(),
+        format!("{{ path = \"{}\" }}", sdk_path.display()),
+    )
+    .install(
+        "coin_bindings",
+        "AptosFramework",
+        &[BindingsModule {
+            module: coin,
+            source_map: None,
+            view_functions,
+        }],
+        &modules.iter().collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    let lib = std::fs::read_to_string(crate_path.join("src/lib.rs")).unwrap();
+    assert!(lib.contains("pub mod deps;\npub mod coin;\n"));
+    let coin_code = std::fs::read_to_string(crate_path.join("src/coin.rs")).unwrap();
+    // Without a source map, parameters are named by position
+    assert!(coin_code.contains(
+        "pub fn transfer(type_arg0: TypeTag, arg1: AccountAddress, arg2: u64) -> TransactionPayload {"
+    ));
+    assert!(coin_code.contains(
+        "pub async fn balance(client: &Client, type_arg0: TypeTag, arg0: AccountAddress) -> anyhow::Result<u64> {"
+    ));
+    // Phantom type parameters are left out, and structs of dependencies are generated too
+    assert!(coin_code.contains("pub struct CoinStore {"));
+    assert!(coin_code.contains("pub coin: crate::coin::Coin,"));
+    assert!(coin_code.contains("pub deposit_events: crate::deps::x1::event::EventHandle,"));
+    assert!(coin_code.contains("pub async fn fetch("));
+    let deps_code = std::fs::read_to_string(crate_path.join("src/deps.rs")).unwrap();
+    assert!(deps_code.contains("pub mod event {"));
+    assert!(deps_code.contains("pub struct EventHandle {"));
+    assert!(deps_code.contains("pub guid: crate::deps::x1::guid::GUID,"));
+
+    // The generated crate must compile against the SDK. Pin the versions of its dependencies to
+    // the ones of this workspace, and use a stable `target` dir to avoid recompiling crates every
+    // time.
+    std::fs::copy("../../Cargo.lock", crate_path.join("Cargo.lock")).unwrap();
+    let target_dir = std::env::current_dir().unwrap().join("../../target");
+    let status = Command::new("cargo")
+        .current_dir(&crate_path)
+        .arg("build")
+        .arg("--target-dir")
+        .arg(target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
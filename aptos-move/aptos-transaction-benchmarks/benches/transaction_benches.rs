@@ -4,9 +4,10 @@
 
 use aptos_language_e2e_tests::account_universe::P2PTransferGen;
 use aptos_transaction_benchmarks::{
-    measurement::wall_time_measurement, transactions::TransactionBencher,
+    fungible_asset_transfers::FungibleAssetTransferBench, measurement::wall_time_measurement,
+    transactions::TransactionBencher,
 };
-use criterion::{criterion_group, criterion_main, measurement::Measurement, Criterion};
+use criterion::{criterion_group, criterion_main, measurement::Measurement, BatchSize, Criterion};
 use proptest::prelude::*;
 
 //
@@ -25,10 +26,26 @@ fn peer_to_peer<M: Measurement + 'static>(c: &mut Criterion<M>) {
     });
 }
 
+fn fungible_asset_many_to_one<M: Measurement + 'static>(c: &mut Criterion<M>) {
+    for (name, concurrent_receiver) in [
+        ("fungible_asset_many_to_one_parallel", false),
+        ("fungible_asset_many_to_one_concurrent_parallel", true),
+    ] {
+        let bench = FungibleAssetTransferBench::new(1_000, concurrent_receiver);
+        c.bench_function(name, |b| {
+            b.iter_batched(
+                || bench.gen_transactions(),
+                |txns| bench.execute(txns, num_cpus::get()),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
 criterion_group!(
     name = txn_benches;
     config = wall_time_measurement().sample_size(10);
-    targets = peer_to_peer
+    targets = peer_to_peer, fungible_asset_many_to_one
 );
 
 criterion_main!(txn_benches);
@@ -0,0 +1,249 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transactions::RAYON_EXEC_POOL;
+use aptos_block_executor::txn_commit_hook::NoOpTransactionCommitHook;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_framework::{BuildOptions, BuiltPackage};
+use aptos_language_e2e_tests::{account::Account, executor::FakeExecutor};
+use aptos_types::{
+    account_address::{create_derived_object_address, create_object_address, AccountAddress},
+    on_chain_config::{BlockGasLimitType, FeatureFlag},
+    transaction::{
+        EntryFunction, ExecutionStatus, SignedTransaction, Transaction, TransactionOutput,
+        TransactionPayload, TransactionStatus,
+    },
+    vm_status::VMStatus,
+};
+use aptos_vm::block_executor::{AptosTransactionOutput, BlockAptosVM};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    value::MoveValue,
+};
+use std::{path::PathBuf, sync::Arc, time::Instant};
+
+/// Address the example fungible asset package is published at.
+const ADMIN_ADDRESS: &str = "0xcafe";
+/// Seed of the metadata object created by the `coin_example` module.
+const ASSET_SYMBOL: &[u8] = b"YOLO";
+/// Number of primary stores funded by a single mint transaction during setup.
+const MINT_BATCH_SIZE: usize = 500;
+/// Amount of the fungible asset every sender starts with.
+const INITIAL_BALANCE: u64 = 1_000_000;
+/// Amount of gas coins every account starts with.
+const INITIAL_GAS_BALANCE: u64 = 1_000_000_000_000;
+const MAX_GAS_AMOUNT: u64 = 2_000_000;
+const GAS_UNIT_PRICE: u64 = 100;
+
+/// Benchmarks a block of many-to-one fungible asset transfers: every transaction transfers from
+/// the primary store of a distinct sender to the primary store of the same receiver, as is the
+/// case for e.g. exchange deposit addresses. If `concurrent_receiver` is set, the receiver's
+/// store is upgraded to keep its balance in an aggregator, so deposits into it do not conflict.
+pub struct FungibleAssetTransferBench {
+    executor: FakeExecutor,
+    senders: Vec<Account>,
+    receiver: AccountAddress,
+    metadata: AccountAddress,
+}
+
+impl FungibleAssetTransferBench {
+    /// Sets up the fungible asset, funds `num_senders` senders and creates the receiver's store.
+    pub fn new(num_senders: usize, concurrent_receiver: bool) -> Self {
+        let mut executor = FakeExecutor::from_head_genesis();
+        enable_features(&mut executor, vec![
+            FeatureFlag::MODULE_EVENT,
+            FeatureFlag::CONCURRENT_FUNGIBLE_BALANCE,
+        ]);
+
+        let admin =
+            executor.new_account_at(AccountAddress::from_hex_literal(ADMIN_ADDRESS).unwrap());
+        publish_example_package(&mut executor, &admin);
+        let metadata = create_object_address(*admin.address(), ASSET_SYMBOL);
+
+        let senders = executor.create_accounts(num_senders, INITIAL_GAS_BALANCE, 0);
+        let receiver = executor
+            .create_accounts(1, INITIAL_GAS_BALANCE, 0)
+            .pop()
+            .unwrap();
+        let mut admin_sequence_number = 1;
+        let mut mint = |executor: &mut FakeExecutor, to: Vec<AccountAddress>, amount: u64| {
+            let amounts = vec![amount; to.len()];
+            let txn = sign_txn(
+                &admin,
+                entry_function(
+                    *admin.address(),
+                    "managed_fungible_asset",
+                    "mint_to_primary_stores",
+                    vec![],
+                    vec![
+                        bcs::to_bytes(&metadata).unwrap(),
+                        bcs::to_bytes(&to).unwrap(),
+                        bcs::to_bytes(&amounts).unwrap(),
+                    ],
+                ),
+                admin_sequence_number,
+            );
+            admin_sequence_number += 1;
+            executor.execute_and_apply(txn);
+        };
+
+        for batch in senders.chunks(MINT_BATCH_SIZE) {
+            let to = batch.iter().map(|sender| *sender.address()).collect();
+            mint(&mut executor, to, INITIAL_BALANCE);
+        }
+        // Make sure the receiver's primary store exists, so that it can be upgraded.
+        mint(&mut executor, vec![*receiver.address()], 1);
+
+        if concurrent_receiver {
+            let store = create_derived_object_address(*receiver.address(), metadata);
+            let txn = sign_txn(
+                &receiver,
+                entry_function(
+                    AccountAddress::ONE,
+                    "fungible_asset",
+                    "upgrade_store_to_concurrent",
+                    vec![framework_struct_tag("fungible_asset", "FungibleStore")],
+                    vec![bcs::to_bytes(&store).unwrap()],
+                ),
+                0,
+            );
+            executor.execute_and_apply(txn);
+        }
+
+        Self {
+            executor,
+            senders,
+            receiver: *receiver.address(),
+            metadata,
+        }
+    }
+
+    /// Generates a block with a single transfer from every sender to the receiver.
+    pub fn gen_transactions(&self) -> Vec<Transaction> {
+        self.senders
+            .iter()
+            .map(|sender| {
+                let txn = sign_txn(
+                    sender,
+                    entry_function(
+                        AccountAddress::ONE,
+                        "primary_fungible_store",
+                        "transfer",
+                        vec![framework_struct_tag("fungible_asset", "Metadata")],
+                        vec![
+                            bcs::to_bytes(&self.metadata).unwrap(),
+                            bcs::to_bytes(&self.receiver).unwrap(),
+                            bcs::to_bytes(&1u64).unwrap(),
+                        ],
+                    ),
+                    0,
+                );
+                Transaction::UserTransaction(txn)
+            })
+            .collect()
+    }
+
+    /// Executes the block with the given concurrency level on top of the setup state, and
+    /// returns the achieved TPS. All transactions are expected to succeed.
+    pub fn execute(&self, transactions: Vec<Transaction>, concurrency_level: usize) -> usize {
+        let block_size = transactions.len();
+        let timer = Instant::now();
+        let output = BlockAptosVM::execute_block::<
+            _,
+            NoOpTransactionCommitHook<AptosTransactionOutput, VMStatus>,
+        >(
+            Arc::clone(&RAYON_EXEC_POOL),
+            transactions,
+            self.executor.get_state_view(),
+            concurrency_level,
+            BlockGasLimitType::NoLimit,
+            None,
+        )
+        .expect("VM should not fail to start");
+        let exec_time = timer.elapsed().as_millis();
+
+        assert_all_succeeded(&output);
+        block_size * 1000 / (exec_time as usize).max(1)
+    }
+}
+
+fn assert_all_succeeded(output: &[TransactionOutput]) {
+    output.iter().for_each(|txn_output| {
+        assert_eq!(
+            txn_output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+    });
+}
+
+fn enable_features(executor: &mut FakeExecutor, features: Vec<FeatureFlag>) {
+    let features = features.into_iter().map(|f| f as u64).collect::<Vec<_>>();
+    executor.exec("features", "change_feature_flags", vec![], vec![
+        MoveValue::Signer(AccountAddress::ONE)
+            .simple_serialize()
+            .unwrap(),
+        bcs::to_bytes(&features).unwrap(),
+        bcs::to_bytes(&Vec::<u64>::new()).unwrap(),
+    ]);
+}
+
+fn publish_example_package(executor: &mut FakeExecutor, admin: &Account) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../move-examples/fungible_asset/managed_fungible_asset");
+    let mut options = BuildOptions::default();
+    options
+        .named_addresses
+        .insert("example_addr".to_string(), *admin.address());
+    let package = BuiltPackage::build(path, options).expect("building package must succeed");
+    let metadata = package
+        .extract_metadata()
+        .expect("extracting package metadata must succeed");
+    let txn = sign_txn(
+        admin,
+        aptos_stdlib::code_publish_package_txn(
+            bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
+            package.extract_code(),
+        ),
+        0,
+    );
+    executor.execute_and_apply(txn);
+}
+
+fn sign_txn(
+    account: &Account,
+    payload: TransactionPayload,
+    sequence_number: u64,
+) -> SignedTransaction {
+    account
+        .transaction()
+        .payload(payload)
+        .sequence_number(sequence_number)
+        .max_gas_amount(MAX_GAS_AMOUNT)
+        .gas_unit_price(GAS_UNIT_PRICE)
+        .sign()
+}
+
+fn entry_function(
+    address: AccountAddress,
+    module: &str,
+    function: &str,
+    ty_args: Vec<TypeTag>,
+    args: Vec<Vec<u8>>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(address, Identifier::new(module).unwrap()),
+        Identifier::new(function).unwrap(),
+        ty_args,
+        args,
+    ))
+}
+
+fn framework_struct_tag(module: &str, name: &str) -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address: AccountAddress::ONE,
+        module: Identifier::new(module).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }))
+}
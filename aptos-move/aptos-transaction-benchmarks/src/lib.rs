@@ -5,6 +5,7 @@
 #![forbid(unsafe_code)]
 
 mod benchmark_runner;
+pub mod fungible_asset_transfers;
 pub mod measurement;
 pub mod transaction_bench_state;
 pub mod transactions;
@@ -6,7 +6,9 @@ use aptos_crypto::_once_cell::sync::Lazy;
 use aptos_language_e2e_tests::account_universe::P2PTransferGen;
 use aptos_metrics_core::{register_int_gauge, IntGauge};
 use aptos_push_metrics::MetricsPusher;
-use aptos_transaction_benchmarks::transactions::TransactionBencher;
+use aptos_transaction_benchmarks::{
+    fungible_asset_transfers::FungibleAssetTransferBench, transactions::TransactionBencher,
+};
 use aptos_vm_logging::disable_speculative_logging;
 use clap::{Parser, Subcommand};
 use proptest::prelude::*;
@@ -30,6 +32,7 @@ struct Args {
 enum BenchmarkCommand {
     ParamSweep(ParamSweepOpt),
     Execute(ExecuteOpt),
+    FungibleAssetTransfers(FungibleAssetTransfersOpt),
 }
 
 #[derive(Debug, Parser)]
@@ -89,6 +92,26 @@ struct ExecuteOpt {
     pub generate_then_execute: bool,
 }
 
+/// Compares throughput of many-to-one fungible asset transfers into a plain and into a
+/// concurrent receiver store.
+#[derive(Debug, Parser)]
+struct FungibleAssetTransfersOpt {
+    #[clap(long, default_value_t = 10000)]
+    pub block_size: usize,
+
+    #[clap(long, default_value_t = 2)]
+    pub num_warmups: usize,
+
+    #[clap(long, default_value_t = 10)]
+    pub num_runs: usize,
+
+    #[clap(long)]
+    pub concurrency_level: Option<usize>,
+
+    #[clap(long)]
+    pub skip_sequential: bool,
+}
+
 fn param_sweep(opt: ParamSweepOpt) {
     disable_speculative_logging();
 
@@ -195,6 +218,47 @@ fn execute(opt: ExecuteOpt) {
     println!("Avg Parallel TPS = {:?}", sum / par_tps.len())
 }
 
+fn fungible_asset_transfers(opt: FungibleAssetTransfersOpt) {
+    disable_speculative_logging();
+    let concurrency_level = opt.concurrency_level.unwrap_or_else(num_cpus::get);
+
+    for concurrent_receiver in [false, true] {
+        println!(
+            "RUN many-to-one fungible asset transfers: block_size = {}, concurrent_receiver = {}",
+            opt.block_size, concurrent_receiver
+        );
+        let bench = FungibleAssetTransferBench::new(opt.block_size, concurrent_receiver);
+        let txns = bench.gen_transactions();
+
+        let mut par_tps = Vec::new();
+        let mut seq_tps = Vec::new();
+        for i in 0..opt.num_warmups + opt.num_runs {
+            let par = bench.execute(txns.clone(), concurrency_level);
+            let seq = if opt.skip_sequential {
+                0
+            } else {
+                bench.execute(txns.clone(), 1)
+            };
+            if i >= opt.num_warmups {
+                par_tps.push(par);
+                seq_tps.push(seq);
+            }
+        }
+
+        if !opt.skip_sequential {
+            println!(
+                "Avg Sequential TPS = {:?}",
+                seq_tps.iter().sum::<usize>() / seq_tps.len()
+            );
+        }
+        println!(
+            "Avg Parallel TPS = {:?} (concurrency_level = {})",
+            par_tps.iter().sum::<usize>() / par_tps.len(),
+            concurrency_level
+        );
+    }
+}
+
 fn main() {
     aptos_logger::Logger::new().init();
     START_TIME.set(
@@ -211,6 +275,7 @@ fn main() {
     match args.command {
         BenchmarkCommand::ParamSweep(opt) => param_sweep(opt),
         BenchmarkCommand::Execute(opt) => execute(opt),
+        BenchmarkCommand::FungibleAssetTransfers(opt) => fungible_asset_transfers(opt),
     }
 }
 
@@ -213,7 +213,7 @@ where
             transactions,
             self.state_view.as_ref(),
             1,
-            maybe_block_gas_limit,
+            maybe_block_gas_limit.into(),
             None,
         )
         .expect("VM should not fail to start");
@@ -238,7 +238,7 @@ where
                 self.state_view.clone(),
                 transactions,
                 concurrency_level_per_shard,
-                maybe_block_gas_limit,
+                maybe_block_gas_limit.into(),
             )
             .expect("VM should not fail to start");
         let exec_time = timer.elapsed().as_millis();
@@ -262,7 +262,7 @@ where
             transactions,
             self.state_view.as_ref(),
             concurrency_level_per_shard,
-            maybe_block_gas_limit,
+            maybe_block_gas_limit.into(),
             None,
         )
         .expect("VM should not fail to start");
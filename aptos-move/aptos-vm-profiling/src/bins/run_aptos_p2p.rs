@@ -3,7 +3,9 @@
 
 use anyhow::Result;
 use aptos_language_e2e_tests::{account::AccountData, data_store::FakeDataStore};
-use aptos_types::{transaction::Transaction, write_set::WriteSet};
+use aptos_types::{
+    on_chain_config::BlockGasLimitType, transaction::Transaction, write_set::WriteSet,
+};
 use aptos_vm::{AptosVM, VMExecutor};
 use std::{
     collections::HashMap,
@@ -44,7 +46,7 @@ fn main() -> Result<()> {
         })
         .collect();
 
-    let res = AptosVM::execute_block(txns, &state_store, None)?;
+    let res = AptosVM::execute_block(txns, &state_store, BlockGasLimitType::NoLimit)?;
     for i in 0..NUM_TXNS {
         assert!(res[i as usize].status().status().unwrap().is_success());
     }
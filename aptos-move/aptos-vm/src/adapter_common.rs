@@ -6,10 +6,12 @@ use crate::move_vm_ext::{AptosMoveResolver, MoveResolverExt, SessionExt, Session
 use anyhow::Result;
 use aptos_types::{
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     transaction::{
         SignatureCheckedTransaction, SignedTransaction, Transaction, TransactionStatus,
         WriteSetPayload,
     },
+    validator_txn::ValidatorTransaction,
     vm_status::{StatusCode, VMStatus},
 };
 use aptos_vm_logging::log_schema::AdapterLogSchema;
@@ -59,15 +61,22 @@ pub trait VMAdapter {
         session: &mut SessionExt,
         resolver: &impl AptosMoveResolver,
         transaction: &SignatureCheckedTransaction,
-        allow_too_new: bool,
+        allow_not_yet_executable: bool,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
         self.check_transaction_format(transaction)?;
 
+        // Mempool keeps transactions which can't be executed yet, but may be later: those with a
+        // sequence number that is too new, and those priced below the current base fee.
         let prologue_status = self.run_prologue(session, resolver, transaction, log_context);
         match prologue_status {
             Err(err)
-                if !allow_too_new || err.status_code() != StatusCode::SEQUENCE_NUMBER_TOO_NEW =>
+                if !allow_not_yet_executable
+                    || !matches!(
+                        err.status_code(),
+                        StatusCode::SEQUENCE_NUMBER_TOO_NEW
+                            | StatusCode::GAS_UNIT_PRICE_BELOW_BASE_FEE
+                    ) =>
             {
                 Err(err)
             },
@@ -86,6 +95,8 @@ pub enum PreprocessedTransaction {
     BlockMetadata(BlockMetadata),
     InvalidSignature,
     StateCheckpoint,
+    ValidatorTransaction(ValidatorTransaction),
+    BlockMetadataExt(BlockMetadataExt),
 }
 
 /// Check the signature (if any) of a transaction. If the signature is OK, the result
@@ -106,6 +117,10 @@ pub fn preprocess_transaction<A: VMAdapter>(txn: Transaction) -> PreprocessedTra
             PreprocessedTransaction::UserTransaction(Box::new(checked_txn))
         },
         Transaction::StateCheckpoint(_) => PreprocessedTransaction::StateCheckpoint,
+        Transaction::ValidatorTransaction(txn) => {
+            PreprocessedTransaction::ValidatorTransaction(txn)
+        },
+        Transaction::BlockMetadataExt(b) => PreprocessedTransaction::BlockMetadataExt(b),
     }
 }
 
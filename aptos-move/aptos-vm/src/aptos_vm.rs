@@ -20,7 +20,7 @@ use crate::{
 use anyhow::{anyhow, Result};
 use aptos_block_executor::txn_commit_hook::NoOpTransactionCommitHook;
 use aptos_crypto::HashValue;
-use aptos_framework::natives::code::PublishRequest;
+use aptos_framework::natives::{code::PublishRequest, randomness::RandomnessContext};
 use aptos_gas_algebra::Gas;
 use aptos_gas_meter::{AptosGasMeter, StandardGasAlgebra, StandardGasMeter};
 use aptos_gas_schedule::VMGasParameters;
@@ -32,8 +32,12 @@ use aptos_types::{
     account_config::new_block_event_key,
     block_executor::partitioner::PartitionedTransactions,
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     fee_statement::FeeStatement,
-    on_chain_config::{new_epoch_event_key, ConfigStorage, FeatureFlag, TimedFeatureOverride},
+    on_chain_config::{
+        new_epoch_event_key, BaseFee, BlockGasLimitType, ConfigStorage, FeatureFlag,
+        TimedFeatureOverride,
+    },
     transaction::{
         EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle, Multisig,
         MultisigTransactionPayload, SignatureCheckedTransaction, SignedTransaction, Transaction,
@@ -54,12 +58,13 @@ use move_binary_format::{
     access::ModuleAccess,
     compatibility::Compatibility,
     errors::{verification_error, Location, PartialVMError, VMError, VMResult},
+    file_format::Visibility,
     CompiledModule, IndexKind,
 };
 use move_core_types::{
     account_address::AccountAddress,
     ident_str,
-    identifier::Identifier,
+    identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, TypeTag},
     transaction_argument::convert_txn_args,
     value::{serialize_values, MoveValue},
@@ -293,9 +298,11 @@ impl AptosVM {
         change_set_configs: &ChangeSetConfigs,
     ) -> (VMStatus, VMOutput) {
         // Clear side effects: create new session and clear refunds from fee statement.
-        let mut session = self
-            .0
-            .new_session(resolver, SessionId::epilogue_meta(txn_data));
+        let mut session = self.0.new_session(
+            resolver,
+            SessionId::epilogue_meta(txn_data),
+            Some(txn_data.as_user_transaction_context()),
+        );
         let fee_statement = AptosVM::fee_statement_from_gas_meter(txn_data, gas_meter, 0);
 
         match TransactionStatus::from_vm_status(
@@ -408,6 +415,14 @@ impl AptosVM {
             &function,
             struct_constructors,
         )?;
+        if self.0.get_features().is_on_chain_randomness_enabled()
+            && is_private_entry_function(session, script_fn)?
+        {
+            session
+                .get_native_extensions()
+                .get_mut::<RandomnessContext>()
+                .mark_unbiasable();
+        }
         Ok(session.execute_entry_function(
             script_fn.module(),
             script_fn.function(),
@@ -532,7 +547,14 @@ impl AptosVM {
 
         // TODO(Gas): Charge for aggregator writes
         let session_id = SessionId::epilogue_meta(txn_data);
-        RespawnedSession::spawn(&self.0, session_id, resolver, change_set, storage_refund)
+        RespawnedSession::spawn(
+            &self.0,
+            session_id,
+            resolver,
+            change_set,
+            storage_refund,
+            Some(txn_data.as_user_transaction_context()),
+        )
     }
 
     // Execute a multisig transaction:
@@ -740,6 +762,7 @@ impl AptosVM {
             resolver,
             VMChangeSet::empty(),
             0.into(),
+            Some(txn_data.as_user_transaction_context()),
         )?;
 
         let execution_error = ExecutionError::try_from(execution_error)
@@ -832,12 +855,13 @@ impl AptosVM {
 
     /// Deserialize a module bundle.
     fn deserialize_module_bundle(&self, modules: &ModuleBundle) -> VMResult<Vec<CompiledModule>> {
-        let max_version = if self
-            .0
-            .get_features()
-            .is_enabled(FeatureFlag::VM_BINARY_FORMAT_V6)
-        {
-            6
+        let features = self.0.get_features();
+        let max_version = if features.is_enabled(FeatureFlag::VM_BINARY_FORMAT_V6) {
+            if features.is_enabled(FeatureFlag::VM_BINARY_FORMAT_V7) {
+                7
+            } else {
+                6
+            }
         } else {
             5
         };
@@ -1070,8 +1094,15 @@ impl AptosVM {
         log_context: &AdapterLogSchema,
         gas_meter: &mut impl AptosGasMeter,
     ) -> (VMStatus, VMOutput) {
+        let txn_data =
+            TransactionMetadata::new_with_base_fee(txn, BaseFee::fetch_base_fee_per_gas(resolver));
+
         // Revalidate the transaction.
-        let mut session = self.0.new_session(resolver, SessionId::prologue(txn));
+        let mut session = self.0.new_session(
+            resolver,
+            SessionId::prologue(txn),
+            Some(txn_data.as_user_transaction_context()),
+        );
         if let Err(err) = self.validate_signature_checked_transaction(
             &mut session,
             resolver,
@@ -1091,11 +1122,14 @@ impl AptosVM {
             // By releasing resource group cache, we start with a fresh slate for resource group
             // cost accounting.
             resolver.release_resource_group_cache();
-            session = self.0.new_session(resolver, SessionId::txn(txn));
+            session = self.0.new_session(
+                resolver,
+                SessionId::txn(txn),
+                Some(txn_data.as_user_transaction_context()),
+            );
         }
 
         let storage_gas_params = unwrap_or_discard!(self.0.get_storage_gas_parameters(log_context));
-        let txn_data = TransactionMetadata::new(txn);
 
         // We keep track of whether any newly published modules are loaded into the Vm's loader
         // cache as part of executing transactions. This would allow us to decide whether the cache
@@ -1240,7 +1274,7 @@ impl AptosVM {
                 VMChangeSet::try_from_storage_change_set(change_set.clone(), &change_set_configs)
             },
             WriteSetPayload::Script { script, execute_as } => {
-                let mut tmp_session = self.0.new_session(resolver, session_id);
+                let mut tmp_session = self.0.new_session(resolver, session_id, None);
                 let senders = match txn_sender {
                     None => vec![*execute_as],
                     Some(sender) => vec![sender, *execute_as],
@@ -1350,28 +1384,51 @@ impl AptosVM {
             ))
         });
 
-        let txn_data = TransactionMetadata {
-            sender: account_config::reserved_vm_address(),
-            max_gas_amount: 0.into(),
-            ..Default::default()
-        };
+        let session_id = SessionId::block_meta(&block_metadata);
+        let args = block_metadata.get_prologue_move_args(account_config::reserved_vm_address());
+        self.run_block_prologue(resolver, session_id, BLOCK_PROLOGUE, args, log_context)
+    }
+
+    pub(crate) fn process_block_prologue_ext(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        block_metadata_ext: BlockMetadataExt,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(VMStatus, VMOutput), VMStatus> {
+        fail_point!("move_adapter::process_block_prologue_ext", |_| {
+            Err(VMStatus::error(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                None,
+            ))
+        });
+
+        let session_id = SessionId::block_meta(block_metadata_ext.block_metadata());
+        let args = block_metadata_ext.get_prologue_move_args(account_config::reserved_vm_address());
+        self.run_block_prologue(resolver, session_id, BLOCK_PROLOGUE_EXT, args, log_context)
+    }
+
+    fn run_block_prologue(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        session_id: SessionId,
+        function_name: &IdentStr,
+        args: Vec<MoveValue>,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(VMStatus, VMOutput), VMStatus> {
         let mut gas_meter = UnmeteredGasMeter;
-        let mut session = self
-            .0
-            .new_session(resolver, SessionId::block_meta(&block_metadata));
+        let mut session = self.0.new_session(resolver, session_id, None);
 
-        let args = serialize_values(&block_metadata.get_prologue_move_args(txn_data.sender));
         session
             .execute_function_bypass_visibility(
                 &BLOCK_MODULE,
-                BLOCK_PROLOGUE,
+                function_name,
                 vec![],
-                args,
+                serialize_values(&args),
                 &mut gas_meter,
             )
             .map(|_return_vals| ())
             .or_else(|e| {
-                expect_only_successful_execution(e, BLOCK_PROLOGUE.as_str(), log_context)
+                expect_only_successful_execution(e, function_name.as_str(), log_context)
             })?;
         SYSTEM_TRANSACTIONS_EXECUTED.inc();
 
@@ -1506,6 +1563,14 @@ impl AptosVM {
     }
 }
 
+/// Returns whether the entry function of a transaction is private, so that no other code in the
+/// transaction can observe its outcome. Only such functions may use on-chain randomness.
+fn is_private_entry_function(session: &SessionExt, script_fn: &EntryFunction) -> VMResult<bool> {
+    let (visibility, is_entry) =
+        session.load_function_visibility(script_fn.module(), script_fn.function())?;
+    Ok(is_entry && visibility == Visibility::Private)
+}
+
 // Executor external API
 impl VMExecutor for AptosVM {
     /// Execute a block of `transactions`. The output vector will have the exact same length as the
@@ -1516,7 +1581,7 @@ impl VMExecutor for AptosVM {
     fn execute_block(
         transactions: Vec<Transaction>,
         state_view: &(impl StateView + Sync),
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         fail_point!("move_adapter::execute_block", |_| {
             Err(VMStatus::error(
@@ -1540,7 +1605,7 @@ impl VMExecutor for AptosVM {
             transactions,
             state_view,
             Self::get_concurrency_level(),
-            maybe_block_gas_limit,
+            block_gas_limit_type,
             None,
         );
         if ret.is_ok() {
@@ -1554,7 +1619,7 @@ impl VMExecutor for AptosVM {
         sharded_block_executor: &ShardedBlockExecutor<S, C>,
         transactions: PartitionedTransactions,
         state_view: Arc<S>,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
         info!(
@@ -1568,7 +1633,7 @@ impl VMExecutor for AptosVM {
             state_view,
             transactions,
             AptosVM::get_concurrency_level(),
-            maybe_block_gas_limit,
+            block_gas_limit_type,
         );
         if ret.is_ok() {
             // Record the histogram count for transactions per block.
@@ -1606,7 +1671,17 @@ impl VMValidator for AptosVM {
         };
 
         let resolver = self.as_move_resolver(state_view);
-        let mut session = self.0.new_session(&resolver, SessionId::prologue(&txn));
+        let mut session = self.0.new_session(
+            &resolver,
+            SessionId::prologue(&txn),
+            Some(
+                TransactionMetadata::new_with_base_fee(
+                    &txn,
+                    BaseFee::fetch_base_fee_per_gas(&resolver),
+                )
+                .as_user_transaction_context(),
+            ),
+        );
         let validation_result = self.validate_signature_checked_transaction(
             &mut session,
             &resolver,
@@ -1641,7 +1716,7 @@ impl VMAdapter for AptosVM {
         resolver: &'r impl AptosMoveResolver,
         session_id: SessionId,
     ) -> SessionExt<'r, '_> {
-        self.0.new_session(resolver, session_id)
+        self.0.new_session(resolver, session_id, None)
     }
 
     fn check_signature(txn: SignedTransaction) -> Result<SignatureCheckedTransaction> {
@@ -1666,7 +1741,10 @@ impl VMAdapter for AptosVM {
         transaction: &SignatureCheckedTransaction,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
-        let txn_data = TransactionMetadata::new(transaction);
+        let txn_data = TransactionMetadata::new_with_base_fee(
+            transaction,
+            BaseFee::fetch_base_fee_per_gas(resolver),
+        );
         self.run_prologue_with_payload(
             session,
             resolver,
@@ -1699,6 +1777,15 @@ impl VMAdapter for AptosVM {
                     self.process_block_prologue(resolver, block_metadata.clone(), log_context)?;
                 (vm_status, output, Some("block_prologue".to_string()))
             },
+            PreprocessedTransaction::BlockMetadataExt(block_metadata_ext) => {
+                fail_point!("aptos_vm::execution::block_metadata_ext");
+                let (vm_status, output) = self.process_block_prologue_ext(
+                    resolver,
+                    block_metadata_ext.clone(),
+                    log_context,
+                )?;
+                (vm_status, output, Some("block_prologue_ext".to_string()))
+            },
             PreprocessedTransaction::WaypointWriteSet(write_set_payload) => {
                 let (vm_status, output) = self.process_waypoint_change_set(
                     resolver,
@@ -1792,6 +1879,12 @@ impl VMAdapter for AptosVM {
                 let output = VMOutput::empty_with_status(status);
                 (VMStatus::Executed, output, Some("state_checkpoint".into()))
             },
+            PreprocessedTransaction::ValidatorTransaction(txn) => {
+                fail_point!("aptos_vm::execution::validator_transaction");
+                let (vm_status, output) =
+                    self.process_validator_transaction(resolver, txn.clone(), log_context)?;
+                (vm_status, output, Some("validator_transaction".to_string()))
+            },
         })
     }
 }
@@ -1829,8 +1922,13 @@ impl AptosSimulationVM {
         }
 
         // Revalidate the transaction.
-        let txn_data = TransactionMetadata::new(txn);
-        let mut session = self.0.new_session(resolver, SessionId::txn_meta(&txn_data));
+        let txn_data =
+            TransactionMetadata::new_with_base_fee(txn, BaseFee::fetch_base_fee_per_gas(resolver));
+        let mut session = self.0.new_session(
+            resolver,
+            SessionId::txn_meta(&txn_data),
+            Some(txn_data.as_user_transaction_context()),
+        );
         if let Err(err) =
             self.validate_simulated_transaction(&mut session, resolver, txn, &txn_data, log_context)
         {
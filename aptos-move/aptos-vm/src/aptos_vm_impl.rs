@@ -29,7 +29,10 @@ use aptos_types::{
         ApprovedExecutionHashes, ConfigStorage, ConfigurationResource, FeatureFlag, Features,
         GasSchedule, GasScheduleV2, OnChainConfig, TimedFeatures, Version,
     },
-    transaction::{AbortInfo, ExecutionStatus, Multisig, TransactionStatus},
+    transaction::{
+        user_transaction_context::UserTransactionContext, AbortInfo, ExecutionStatus, Multisig,
+        TransactionStatus,
+    },
     vm_status::{StatusCode, VMStatus},
 };
 use aptos_vm_logging::{log_schema::AdapterLogSchema, prelude::*};
@@ -337,13 +340,13 @@ impl AptosVMImpl {
         }
 
         // The submitted gas price is greater than the maximum gas unit price set by the VM.
-        if txn_data.gas_unit_price() > txn_gas_params.max_price_per_gas_unit {
+        if txn_data.max_gas_unit_price() > txn_gas_params.max_price_per_gas_unit {
             speculative_warn!(
                 log_context,
                 format!(
                     "[VM] Gas unit error; min {}, submitted {}",
                     txn_gas_params.max_price_per_gas_unit,
-                    txn_data.gas_unit_price()
+                    txn_data.max_gas_unit_price()
                 ),
             );
             return Err(VMStatus::error(
@@ -649,8 +652,10 @@ impl AptosVMImpl {
         &self,
         resolver: &'r impl AptosMoveResolver,
         session_id: SessionId,
+        user_transaction_context_opt: Option<UserTransactionContext>,
     ) -> SessionExt<'r, '_> {
-        self.move_vm.new_session(resolver, session_id)
+        self.move_vm
+            .new_session(resolver, session_id, user_transaction_context_opt)
     }
 
     pub fn load_module(
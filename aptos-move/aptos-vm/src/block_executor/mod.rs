@@ -29,6 +29,7 @@ use aptos_types::{
     contract_event::ContractEvent,
     executable::ExecutableTestType,
     fee_statement::FeeStatement,
+    on_chain_config::BlockGasLimitType,
     state_store::state_key::StateKey,
     transaction::{Transaction, TransactionOutput, TransactionStatus},
     write_set::WriteOp,
@@ -196,7 +197,7 @@ impl BlockAptosVM {
         transactions: Vec<Transaction>,
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
         transaction_commit_listener: Option<L>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let _timer = BLOCK_EXECUTOR_EXECUTE_BLOCK_SECONDS.start_timer();
@@ -227,7 +228,7 @@ impl BlockAptosVM {
         >::new(
             concurrency_level,
             executor_thread_pool,
-            maybe_block_gas_limit,
+            block_gas_limit_type,
             transaction_commit_listener,
         );
 
@@ -33,6 +33,8 @@ pub const ESEQUENCE_NUMBER_TOO_BIG: u64 = 1008;
 pub const ESECONDARY_KEYS_ADDRESSES_COUNT_MISMATCH: u64 = 1009;
 // Gas payer account missing in gas payer tx
 pub const EGAS_PAYER_ACCOUNT_MISSING: u64 = 1010;
+// Gas unit price is below the base fee of the current block.
+pub const EGAS_UNIT_PRICE_BELOW_BASE_FEE: u64 = 1011;
 
 // Specified account is not a multisig account.
 const EACCOUNT_NOT_MULTISIG: u64 = 2002;
@@ -121,6 +123,9 @@ pub fn convert_prologue_error(
                 (INVALID_ARGUMENT, EGAS_PAYER_ACCOUNT_MISSING) => {
                     StatusCode::GAS_PAYER_ACCOUNT_MISSING
                 },
+                (INVALID_ARGUMENT, EGAS_UNIT_PRICE_BELOW_BASE_FEE) => {
+                    StatusCode::GAS_UNIT_PRICE_BELOW_BASE_FEE
+                },
                 (category, reason) => {
                     let err_msg = format!("[aptos_vm] Unexpected prologue Move abort: {:?}::{:?} (Category: {:?} Reason: {:?})",
                     location, code, category, reason);
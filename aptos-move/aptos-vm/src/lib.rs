@@ -118,6 +118,7 @@ pub mod system_module_names;
 pub mod testing;
 pub mod transaction_metadata;
 mod transaction_validation;
+mod validator_txns;
 mod verifier;
 
 pub use crate::aptos_vm::AptosVM;
@@ -125,6 +126,7 @@ use crate::sharded_block_executor::{executor_client::ExecutorClient, ShardedBloc
 use aptos_state_view::StateView;
 use aptos_types::{
     block_executor::partitioner::PartitionedTransactions,
+    on_chain_config::BlockGasLimitType,
     transaction::{SignedTransaction, Transaction, TransactionOutput, VMValidatorResult},
     vm_status::VMStatus,
 };
@@ -152,7 +154,7 @@ pub trait VMExecutor: Send + Sync {
     fn execute_block(
         transactions: Vec<Transaction>,
         state_view: &(impl StateView + Sync),
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus>;
 
     /// Executes a block of transactions using a sharded block executor and returns the results.
@@ -160,7 +162,7 @@ pub trait VMExecutor: Send + Sync {
         sharded_block_executor: &ShardedBlockExecutor<S, E>,
         transactions: PartitionedTransactions,
         state_view: Arc<S>,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus>;
 }
 
@@ -13,6 +13,7 @@ use aptos_types::{
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
+    transaction::user_transaction_context::UserTransactionContext,
     write_set::TransactionWrite,
 };
 use aptos_vm_types::{change_set::VMChangeSet, storage::ChangeSetConfigs};
@@ -41,6 +42,7 @@ impl<'r, 'l> RespawnedSession<'r, 'l> {
         base_state_view: &'r dyn StateView,
         previous_session_change_set: VMChangeSet,
         storage_refund: Fee,
+        user_transaction_context_opt: Option<UserTransactionContext>,
     ) -> Result<Self, VMStatus> {
         let state_view = ChangeSetStateView::new(base_state_view, previous_session_change_set)?;
 
@@ -53,7 +55,9 @@ impl<'r, 'l> RespawnedSession<'r, 'l> {
                     vm.get_features(),
                 )
             },
-            session_builder: |resolver| Some(vm.new_session(resolver, session_id)),
+            session_builder: |resolver| {
+                Some(vm.new_session(resolver, session_id, user_transaction_context_opt))
+            },
             storage_refund,
         }
         .build())
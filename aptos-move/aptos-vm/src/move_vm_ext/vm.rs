@@ -10,6 +10,7 @@ use aptos_framework::natives::{
     code::NativeCodeContext,
     cryptography::{algebra::AlgebraContext, ristretto255_point::NativeRistrettoPointContext},
     event::NativeEventContext,
+    randomness::RandomnessContext,
     state_storage::NativeStateStorageContext,
     transaction_context::NativeTransactionContext,
 };
@@ -17,7 +18,10 @@ use aptos_gas_algebra::DynamicExpression;
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
 use aptos_native_interface::SafeNativeBuilder;
 use aptos_table_natives::NativeTableContext;
-use aptos_types::on_chain_config::{FeatureFlag, Features, TimedFeatureFlag, TimedFeatures};
+use aptos_types::{
+    on_chain_config::{FeatureFlag, Features, TimedFeatureFlag, TimedFeatures},
+    transaction::user_transaction_context::UserTransactionContext,
+};
 use move_binary_format::errors::VMResult;
 use move_bytecode_verifier::VerifierConfig;
 use move_vm_runtime::{
@@ -33,7 +37,11 @@ pub struct MoveVmExt {
 
 pub fn get_max_binary_format_version(features: &Features, gas_feature_version: u64) -> u32 {
     if features.is_enabled(FeatureFlag::VM_BINARY_FORMAT_V6) && gas_feature_version >= 5 {
-        6
+        if features.is_enabled(FeatureFlag::VM_BINARY_FORMAT_V7) && gas_feature_version >= 14 {
+            7
+        } else {
+            6
+        }
     } else {
         5
     }
@@ -52,9 +60,9 @@ impl MoveVmExt {
     where
         F: Fn(DynamicExpression) + Send + Sync + 'static,
     {
-        // Note: binary format v6 adds a few new integer types and their corresponding instructions.
-        //       Therefore it depends on a new version of the gas schedule and cannot be allowed if
-        //       the gas schedule hasn't been updated yet.
+        // Note: binary format v6 adds a few new integer types and their corresponding instructions,
+        //       and v7 adds closures. Therefore they depend on a new version of the gas schedule
+        //       and cannot be allowed if the gas schedule hasn't been updated yet.
         let max_binary_format_version =
             get_max_binary_format_version(&features, gas_feature_version);
 
@@ -149,6 +157,7 @@ impl MoveVmExt {
         &self,
         resolver: &'r S,
         session_id: SessionId,
+        user_transaction_context_opt: Option<UserTransactionContext>,
     ) -> SessionExt<'r, '_> {
         let mut extensions = NativeContextExtensions::default();
         let txn_hash: [u8; 32] = session_id
@@ -185,10 +194,12 @@ impl MoveVmExt {
             txn_hash.to_vec(),
             script_hash,
             self.chain_id,
+            user_transaction_context_opt,
         ));
         extensions.add(NativeCodeContext::default());
         extensions.add(NativeStateStorageContext::new(resolver));
         extensions.add(NativeEventContext::default());
+        extensions.add(RandomnessContext::new());
 
         // The VM code loader has bugs around module upgrade. After a module upgrade, the internal
         // cache needs to be flushed to work around those bugs.
@@ -28,7 +28,7 @@ use {
     aptos_framework::natives::{
         aggregator_natives::NativeAggregatorContext, code::NativeCodeContext,
         cryptography::ristretto255_point::NativeRistrettoPointContext,
-        transaction_context::NativeTransactionContext,
+        randomness::RandomnessContext, transaction_context::NativeTransactionContext,
     },
     move_vm_runtime::native_extensions::NativeContextExtensions,
     once_cell::sync::Lazy,
@@ -149,9 +149,14 @@ fn unit_test_extensions_hook(exts: &mut NativeContextExtensions) {
         vec![1],
         vec![1],
         ChainId::test().id(),
+        None,
     )); // We use the testing environment chain ID here
     exts.add(NativeAggregatorContext::new([0; 32], &*DUMMY_RESOLVER));
     exts.add(NativeRistrettoPointContext::new());
     exts.add(AlgebraContext::new());
     exts.add(NativeEventContext::default());
+    // Unit tests call the randomness API directly, rather than from a private entry function
+    let mut randomness_context = RandomnessContext::new();
+    randomness_context.mark_unbiasable();
+    exts.add(randomness_context);
 }
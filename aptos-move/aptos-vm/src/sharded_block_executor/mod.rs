@@ -13,6 +13,7 @@ use aptos_logger::{info, trace};
 use aptos_state_view::StateView;
 use aptos_types::{
     block_executor::partitioner::{PartitionedTransactions, SubBlocksForShard},
+    on_chain_config::BlockGasLimitType,
     transaction::{analyzed_transaction::AnalyzedTransaction, TransactionOutput},
 };
 use move_core_types::vm_status::VMStatus;
@@ -43,7 +44,7 @@ pub enum ExecutorShardCommand<S> {
         Arc<S>,
         SubBlocksForShard<AnalyzedTransaction>,
         usize,
-        Option<u64>,
+        BlockGasLimitType,
     ),
     Stop,
 }
@@ -71,7 +72,7 @@ impl<S: StateView + Sync + Send + 'static, C: ExecutorClient<S>> ShardedBlockExe
         state_view: Arc<S>,
         transactions: PartitionedTransactions,
         concurrency_level_per_shard: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let _timer = SHARDED_BLOCK_EXECUTION_SECONDS.start_timer();
         let num_executor_shards = self.executor_client.num_shards();
@@ -88,7 +89,7 @@ impl<S: StateView + Sync + Send + 'static, C: ExecutorClient<S>> ShardedBlockExe
                 state_view,
                 transactions,
                 concurrency_level_per_shard,
-                maybe_block_gas_limit,
+                block_gas_limit_type,
             )?
             .into_inner();
         // wait for all remote executors to send the result back and append them in order by shard id
@@ -17,6 +17,7 @@ use aptos_types::{
     block_executor::partitioner::{
         ShardId, SubBlock, SubBlocksForShard, TransactionWithDependencies,
     },
+    on_chain_config::BlockGasLimitType,
     transaction::{analyzed_transaction::AnalyzedTransaction, TransactionOutput},
 };
 use aptos_vm_logging::disable_speculative_logging;
@@ -63,7 +64,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
         round: usize,
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         disable_speculative_logging();
         trace!(
@@ -82,7 +83,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
             round,
             state_view,
             concurrency_level,
-            maybe_block_gas_limit,
+            block_gas_limit_type,
         )
     }
 
@@ -95,7 +96,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
         round: usize,
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let (callback, callback_receiver) = oneshot::channel();
 
@@ -123,7 +124,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                         .collect(),
                     cross_shard_state_view.as_ref(),
                     concurrency_level,
-                    maybe_block_gas_limit,
+                    block_gas_limit_type,
                     cross_shard_commit_sender,
                 );
                 if let Some(shard_id) = shard_id {
@@ -154,7 +155,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
         transactions: SubBlocksForShard<AnalyzedTransaction>,
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<Vec<TransactionOutput>>, VMStatus> {
         let mut result = vec![];
         for (round, sub_block) in transactions.into_sub_blocks().into_iter().enumerate() {
@@ -175,7 +176,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                 round,
                 state_view,
                 concurrency_level,
-                maybe_block_gas_limit,
+                block_gas_limit_type.clone(),
             )?);
             trace!(
                 "Finished executing sub block for shard {} and round {}",
@@ -199,7 +200,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                     state_view,
                     transactions,
                     concurrency_level_per_shard,
-                    maybe_block_gas_limit,
+                    block_gas_limit_type,
                 ) => {
                     trace!(
                         "Shard {} received ExecuteBlock command of block size {} ",
@@ -210,7 +211,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                         transactions,
                         state_view.as_ref(),
                         concurrency_level_per_shard,
-                        maybe_block_gas_limit,
+                        block_gas_limit_type,
                     );
                     drop(state_view);
                     self.coordinator_client.send_execution_result(ret);
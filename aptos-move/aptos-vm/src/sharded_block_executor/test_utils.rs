@@ -12,6 +12,7 @@ use aptos_language_e2e_tests::{
 };
 use aptos_types::{
     block_executor::partitioner::PartitionedTransactions,
+    on_chain_config::BlockGasLimitType,
     state_store::state_key::StateKeyInner,
     transaction::{analyzed_transaction::AnalyzedTransaction, Transaction, TransactionOutput},
 };
@@ -119,7 +120,7 @@ pub fn test_sharded_block_executor_no_conflict<E: ExecutorClient<FakeDataStore>>
             Arc::new(executor.data_store().clone()),
             partitioned_txns.clone(),
             2,
-            None,
+            BlockGasLimitType::NoLimit,
         )
         .unwrap();
 
@@ -127,8 +128,12 @@ pub fn test_sharded_block_executor_no_conflict<E: ExecutorClient<FakeDataStore>>
         .into_iter()
         .map(|t| t.into_txn())
         .collect();
-    let unsharded_txn_output =
-        AptosVM::execute_block(ordered_txns, executor.data_store(), None).unwrap();
+    let unsharded_txn_output = AptosVM::execute_block(
+        ordered_txns,
+        executor.data_store(),
+        BlockGasLimitType::NoLimit,
+    )
+    .unwrap();
     compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
 }
 
@@ -171,12 +176,16 @@ pub fn sharded_block_executor_with_conflict<E: ExecutorClient<FakeDataStore>>(
             Arc::new(executor.data_store().clone()),
             partitioned_txns,
             concurrency,
-            None,
+            BlockGasLimitType::NoLimit,
         )
         .unwrap();
 
-    let unsharded_txn_output =
-        AptosVM::execute_block(execution_ordered_txns, executor.data_store(), None).unwrap();
+    let unsharded_txn_output = AptosVM::execute_block(
+        execution_ordered_txns,
+        executor.data_store(),
+        BlockGasLimitType::NoLimit,
+    )
+    .unwrap();
     compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
 }
 
@@ -223,11 +232,15 @@ pub fn sharded_block_executor_with_random_transfers<E: ExecutorClient<FakeDataSt
             Arc::new(executor.data_store().clone()),
             partitioned_txns,
             concurrency,
-            None,
+            BlockGasLimitType::NoLimit,
         )
         .unwrap();
 
-    let unsharded_txn_output =
-        AptosVM::execute_block(execution_ordered_txns, executor.data_store(), None).unwrap();
+    let unsharded_txn_output = AptosVM::execute_block(
+        execution_ordered_txns,
+        executor.data_store(),
+        BlockGasLimitType::NoLimit,
+    )
+    .unwrap();
     compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
 }
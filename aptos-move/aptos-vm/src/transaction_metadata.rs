@@ -7,7 +7,11 @@ use aptos_gas_algebra::{FeePerGasUnit, Gas, NumBytes};
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
-    transaction::{authenticator::AuthenticationKey, SignedTransaction, TransactionPayload},
+    transaction::{
+        authenticator::AuthenticationKey,
+        user_transaction_context::{EntryFunctionPayload, UserTransactionContext},
+        EntryFunction, SignedTransaction, TransactionPayload,
+    },
 };
 use std::convert::TryFrom;
 
@@ -21,11 +25,13 @@ pub struct TransactionMetadata {
     pub fee_payer_authentication_key: Option<Vec<u8>>,
     pub max_gas_amount: Gas,
     pub gas_unit_price: FeePerGasUnit,
+    pub max_gas_unit_price: FeePerGasUnit,
     pub transaction_size: NumBytes,
     pub expiration_timestamp_secs: u64,
     pub chain_id: ChainId,
     pub script_hash: Vec<u8>,
     pub script_size: NumBytes,
+    pub entry_function_payload: Option<EntryFunction>,
 }
 
 impl TransactionMetadata {
@@ -48,6 +54,7 @@ impl TransactionMetadata {
                 .map(|signer| signer.authentication_key().to_vec()),
             max_gas_amount: txn.max_gas_amount().into(),
             gas_unit_price: txn.gas_unit_price().into(),
+            max_gas_unit_price: txn.gas_unit_price().into(),
             transaction_size: (txn.raw_txn_bytes_len() as u64).into(),
             expiration_timestamp_secs: txn.expiration_timestamp_secs(),
             chain_id: txn.chain_id(),
@@ -63,9 +70,25 @@ impl TransactionMetadata {
                 TransactionPayload::Script(s) => (s.code().len() as u64).into(),
                 _ => NumBytes::zero(),
             },
+            entry_function_payload: match txn.payload() {
+                TransactionPayload::EntryFunction(e) => Some(e.clone()),
+                _ => None,
+            },
         }
     }
 
+    /// Creates the metadata of a transaction executed under `base_fee_per_gas`, i.e., with the gas
+    /// unit price lowered to the one charged: the base fee plus at most the max priority fee. The
+    /// rest of the gas unit price (the max fee) is never charged. If the base fee market is
+    /// disabled (`None`), the full gas unit price is charged.
+    pub fn new_with_base_fee(txn: &SignedTransaction, base_fee_per_gas: Option<u64>) -> Self {
+        let mut txn_data = Self::new(txn);
+        if let Some(base_fee_per_gas) = base_fee_per_gas {
+            txn_data.gas_unit_price = txn.effective_gas_unit_price(base_fee_per_gas).into();
+        }
+        txn_data
+    }
+
     pub fn max_gas_amount(&self) -> Gas {
         self.max_gas_amount
     }
@@ -74,6 +97,11 @@ impl TransactionMetadata {
         self.gas_unit_price
     }
 
+    /// The gas unit price signed by the sender, which is the most `gas_unit_price` can be.
+    pub fn max_gas_unit_price(&self) -> FeePerGasUnit {
+        self.max_gas_unit_price
+    }
+
     pub fn fee_payer(&self) -> Option<AccountAddress> {
         self.fee_payer.to_owned()
     }
@@ -119,6 +147,26 @@ impl TransactionMetadata {
     pub fn is_multi_agent(&self) -> bool {
         !(self.secondary_signers.is_empty() && self.fee_payer.is_none())
     }
+
+    pub fn entry_function_payload(&self) -> Option<&EntryFunction> {
+        self.entry_function_payload.as_ref()
+    }
+
+    /// Returns the data of this transaction exposed to Move by `transaction_context`. Only
+    /// entry function payloads are exposed, multisig and script payloads are not.
+    pub fn as_user_transaction_context(&self) -> UserTransactionContext {
+        UserTransactionContext::new(
+            self.sender,
+            self.secondary_signers.clone(),
+            self.fee_payer.unwrap_or(self.sender),
+            self.max_gas_amount.into(),
+            self.gas_unit_price.into(),
+            self.chain_id,
+            self.entry_function_payload
+                .as_ref()
+                .map(EntryFunctionPayload::from),
+        )
+    }
 }
 
 impl Default for TransactionMetadata {
@@ -136,11 +184,13 @@ impl Default for TransactionMetadata {
             fee_payer_authentication_key: None,
             max_gas_amount: 100_000_000.into(),
             gas_unit_price: 0.into(),
+            max_gas_unit_price: 0.into(),
             transaction_size: 0.into(),
             expiration_timestamp_secs: 0,
             chain_id: ChainId::test(),
             script_hash: vec![],
             script_size: NumBytes::zero(),
+            entry_function_payload: None,
         }
     }
 }
@@ -0,0 +1,35 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{counters::SYSTEM_TRANSACTIONS_EXECUTED, move_vm_ext::MoveResolverExt, AptosVM};
+use aptos_logger::prelude::*;
+use aptos_types::{
+    transaction::{ExecutionStatus, TransactionStatus},
+    validator_txn::ValidatorTransaction,
+    vm_status::VMStatus,
+};
+use aptos_vm_logging::log_schema::AdapterLogSchema;
+use aptos_vm_types::output::VMOutput;
+
+impl AptosVM {
+    /// Executes a validator transaction. Validator transactions were verified by the voters of
+    /// the block they are in, so they are not subject to the prologue, the epilogue or gas.
+    pub(crate) fn process_validator_transaction(
+        &self,
+        _resolver: &impl MoveResolverExt,
+        txn: ValidatorTransaction,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(VMStatus, VMOutput), VMStatus> {
+        let output = match txn {
+            ValidatorTransaction::DummyTopic1(_) => {
+                debug!(
+                    *log_context,
+                    "[aptos_vm] Executing a dummy validator transaction"
+                );
+                VMOutput::empty_with_status(TransactionStatus::Keep(ExecutionStatus::Success))
+            },
+        };
+        SYSTEM_TRANSACTIONS_EXECUTED.inc();
+        Ok((VMStatus::Executed, output))
+    }
+}
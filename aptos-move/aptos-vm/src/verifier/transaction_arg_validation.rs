@@ -204,7 +204,7 @@ pub(crate) fn is_valid_txn_arg(
                 false
             }
         },
-        Signer | Reference(_) | MutableReference(_) | TyParam(_) => false,
+        Signer | Reference(_) | MutableReference(_) | TyParam(_) | Function(..) => false,
     }
 }
 
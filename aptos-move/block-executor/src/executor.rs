@@ -9,6 +9,7 @@ use crate::{
         TASK_VALIDATE_SECONDS, VM_INIT_SECONDS, WORK_WITH_TASK_SECONDS,
     },
     errors::*,
+    limit_processor::{BlockGasLimitProcessor, ReadWriteSummary},
     scheduler::{DependencyStatus, ExecutionTaskType, Scheduler, SchedulerTask, Wave},
     task::{ExecutionStatus, ExecutorTask, Transaction, TransactionOutput},
     txn_commit_hook::TransactionCommitHook,
@@ -16,14 +17,14 @@ use crate::{
     view::{LatestView, ParallelState, SequentialState, ViewState},
 };
 use aptos_aggregator::delta_change_set::serialize;
-use aptos_logger::{debug, info};
+use aptos_logger::debug;
 use aptos_mvhashmap::{
     types::{MVDataError, MVDataOutput, TxnIndex, Version},
     unsync_map::UnsyncMap,
     MVHashMap,
 };
 use aptos_state_view::TStateView;
-use aptos_types::{executable::Executable, fee_statement::FeeStatement, write_set::WriteOp};
+use aptos_types::{executable::Executable, on_chain_config::BlockGasLimitType, write_set::WriteOp};
 use aptos_vm_logging::{clear_speculative_txn_logs, init_speculative_logs};
 use num_cpus;
 use rayon::ThreadPool;
@@ -75,7 +76,7 @@ pub struct BlockExecutor<T, E, S, L, X> {
     // threads that may be concurrently participating in parallel execution.
     concurrency_level: usize,
     executor_thread_pool: Arc<ThreadPool>,
-    maybe_block_gas_limit: Option<u64>,
+    block_gas_limit_type: BlockGasLimitType,
     transaction_commit_hook: Option<L>,
     phantom: PhantomData<(T, E, S, L, X)>,
 }
@@ -93,7 +94,7 @@ where
     pub fn new(
         concurrency_level: usize,
         executor_thread_pool: Arc<ThreadPool>,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
         transaction_commit_hook: Option<L>,
     ) -> Self {
         assert!(
@@ -104,7 +105,7 @@ where
         Self {
             concurrency_level,
             executor_thread_pool,
-            maybe_block_gas_limit,
+            block_gas_limit_type,
             transaction_commit_hook,
             phantom: PhantomData,
         }
@@ -269,14 +270,12 @@ where
 
     fn coordinator_commit_hook(
         &self,
-        maybe_block_gas_limit: Option<u64>,
+        block_limit_processor: &mut BlockGasLimitProcessor<T::Key>,
         scheduler: &Scheduler,
         post_commit_txs: &Vec<Sender<u32>>,
         worker_idx: &mut usize,
         scheduler_task: &mut SchedulerTask,
         last_input_output: &TxnLastInputOutput<T::Key, E::Output, E::Error>,
-        accumulated_fee_statement: &mut FeeStatement,
-        txn_fee_statements: &mut Vec<FeeStatement>,
     ) {
         while let Some(txn_idx) = scheduler.try_commit() {
             // Create a CommitGuard to ensure Coordinator sends the committed txn index to Worker.
@@ -287,29 +286,17 @@ where
 
             if let Some(fee_statement) = last_input_output.fee_statement(txn_idx) {
                 // For committed txns with Success status, calculate the accumulated gas costs.
-                accumulated_fee_statement.add_fee_statement(&fee_statement);
-                txn_fee_statements.push(fee_statement);
-
-                if let Some(per_block_gas_limit) = maybe_block_gas_limit {
-                    // When the accumulated execution and io gas of the committed txns exceeds
-                    // PER_BLOCK_GAS_LIMIT, early halt BlockSTM. Storage gas does not count towards
-                    // the per block gas limit, as we measure execution related cost here.
-                    let accumulated_non_storage_gas = accumulated_fee_statement
-                        .execution_gas_used()
-                        + accumulated_fee_statement.io_gas_used();
-                    if accumulated_non_storage_gas >= per_block_gas_limit {
-                        counters::EXCEED_PER_BLOCK_GAS_LIMIT_COUNT
-                            .with_label_values(&[counters::Mode::PARALLEL])
-                            .inc();
-                        info!(
-                            "[BlockSTM]: Parallel execution early halted due to \
-                             accumulated_non_storage_gas {} >= PER_BLOCK_GAS_LIMIT {}",
-                            accumulated_non_storage_gas, per_block_gas_limit,
-                        );
-
-                        // Set the execution output status to be SkipRest, to skip the rest of the txns.
-                        last_input_output.update_to_skip_rest(txn_idx);
-                    }
+                let txn_read_write_summary = block_limit_processor
+                    .needs_read_write_summary()
+                    .then(|| Self::txn_read_write_summary(last_input_output, txn_idx));
+                block_limit_processor
+                    .accumulate_fee_statement(fee_statement, txn_read_write_summary);
+
+                // When the accumulated effective gas of the committed txns exceeds the per
+                // block gas limit, early halt BlockSTM.
+                if block_limit_processor.should_end_block(counters::Mode::PARALLEL) {
+                    // Set the execution output status to be SkipRest, to skip the rest of the txns.
+                    last_input_output.update_to_skip_rest(txn_idx);
                 }
             }
 
@@ -330,21 +317,9 @@ where
                 // Either all txn committed, or a committed txn caused an early halt.
                 scheduler.halt();
 
-                counters::update_parallel_block_gas_counters(
-                    accumulated_fee_statement,
-                    (txn_idx + 1) as usize,
-                );
-                counters::update_parallel_txn_gas_counters(txn_fee_statements);
-
-                let accumulated_non_storage_gas = accumulated_fee_statement.execution_gas_used()
-                    + accumulated_fee_statement.io_gas_used();
-                info!(
-                    "[BlockSTM]: Parallel execution completed. {} out of {} txns committed. \
-		     accumulated_non_storage_gas = {}, limit = {:?}",
+                block_limit_processor.finish_parallel_update_counters_and_log_info(
                     txn_idx + 1,
                     scheduler.num_txns(),
-                    accumulated_non_storage_gas,
-                    maybe_block_gas_limit,
                 );
                 break;
             }
@@ -359,6 +334,27 @@ where
         }
     }
 
+    /// Summarizes the keys read and written by the committed txn at `txn_idx`.
+    fn txn_read_write_summary(
+        last_input_output: &TxnLastInputOutput<T::Key, E::Output, E::Error>,
+        txn_idx: TxnIndex,
+    ) -> ReadWriteSummary<T::Key> {
+        let read_set = last_input_output
+            .read_set(txn_idx)
+            .expect("[BlockSTM]: Prior read-set must be recorded");
+        let txn_output = last_input_output
+            .txn_output(txn_idx)
+            .expect("[BlockSTM]: Execution output must be recorded after execution");
+        match txn_output.output_status() {
+            ExecutionStatus::Success(output) | ExecutionStatus::SkipRest(output) => {
+                ReadWriteSummary::new(read_set.iter().map(|r| r.path().clone()), output)
+            },
+            ExecutionStatus::Abort(_) => {
+                unreachable!("Read write summary requested for an aborted txn")
+            },
+        }
+    }
+
     fn worker_commit_hook(
         &self,
         txn_idx: TxnIndex,
@@ -437,21 +433,19 @@ where
         let mut scheduler_task = SchedulerTask::NoTask;
         let mut worker_idx = 0;
 
-        let mut accumulated_fee_statement = FeeStatement::zero();
-        let mut txn_fee_statements = Vec::with_capacity(block.len());
+        let mut block_limit_processor =
+            BlockGasLimitProcessor::new(self.block_gas_limit_type.clone(), block.len());
         loop {
             // Only one thread does try_commit to avoid contention.
             match &role {
                 CommitRole::Coordinator(post_commit_txs) => {
                     self.coordinator_commit_hook(
-                        self.maybe_block_gas_limit,
+                        &mut block_limit_processor,
                         scheduler,
                         post_commit_txs,
                         &mut worker_idx,
                         &mut scheduler_task,
                         last_input_output,
-                        &mut accumulated_fee_statement,
-                        &mut txn_fee_statements,
                     );
                 },
                 CommitRole::Worker(rx) => {
@@ -630,15 +624,13 @@ where
 
         let mut ret = Vec::with_capacity(num_txns);
 
-        let mut accumulated_fee_statement = FeeStatement::zero();
+        let mut block_limit_processor =
+            BlockGasLimitProcessor::new(self.block_gas_limit_type.clone(), num_txns);
 
         for (idx, txn) in signature_verified_block.iter().enumerate() {
             let unsync_view = LatestView::<T, S, X>::new(
                 base_view,
-                ViewState::Unsync(SequentialState {
-                    unsync_map: &data_map,
-                    _counter: &0,
-                }),
+                ViewState::Unsync(SequentialState::new(&data_map, &0)),
                 idx as TxnIndex,
             );
             let res = executor.execute_transaction(&unsync_view, txn, idx as TxnIndex, true);
@@ -662,8 +654,16 @@ where
                     }
                     // Calculating the accumulated gas costs of the committed txns.
                     let fee_statement = output.fee_statement();
-                    accumulated_fee_statement.add_fee_statement(&fee_statement);
                     counters::update_sequential_txn_gas_counters(&fee_statement);
+                    let txn_read_write_summary =
+                        block_limit_processor.needs_read_write_summary().then(|| {
+                            ReadWriteSummary::new(
+                                unsync_view.take_sequential_reads().into_iter(),
+                                &output,
+                            )
+                        });
+                    block_limit_processor
+                        .accumulate_fee_statement(fee_statement, txn_read_write_summary);
 
                     // No delta writes are needed for sequential execution.
                     output.incorporate_delta_writes(vec![]);
@@ -686,41 +686,15 @@ where
                 break;
             }
 
-            if let Some(per_block_gas_limit) = self.maybe_block_gas_limit {
-                // When the accumulated gas of the committed txns
-                // exceeds per_block_gas_limit, halt sequential execution.
-                let accumulated_non_storage_gas = accumulated_fee_statement.execution_gas_used()
-                    + accumulated_fee_statement.io_gas_used();
-                if accumulated_non_storage_gas >= per_block_gas_limit {
-                    counters::EXCEED_PER_BLOCK_GAS_LIMIT_COUNT
-                        .with_label_values(&[counters::Mode::SEQUENTIAL])
-                        .inc();
-                    info!(
-                        "[Execution]: Sequential execution early halted due to \
-                        accumulated_non_storage_gas {} >= PER_BLOCK_GAS_LIMIT {}, {} txns committed.",
-                        accumulated_non_storage_gas,
-                        per_block_gas_limit,
-                        ret.len()
-                    );
-                    break;
-                }
+            // When the accumulated effective gas of the committed txns exceeds the per block
+            // gas limit, halt sequential execution.
+            if block_limit_processor.should_end_block(counters::Mode::SEQUENTIAL) {
+                break;
             }
         }
 
-        if ret.len() == num_txns {
-            let accumulated_non_storage_gas = accumulated_fee_statement.execution_gas_used()
-                + accumulated_fee_statement.io_gas_used();
-            info!(
-                "[Execution]: Sequential execution completed. \
-		 {} out of {} txns committed. accumulated_non_storage_gas = {}, limit = {:?}",
-                ret.len(),
-                num_txns,
-                accumulated_non_storage_gas,
-                self.maybe_block_gas_limit,
-            );
-        }
-
-        counters::update_sequential_block_gas_counters(&accumulated_fee_statement, ret.len());
+        block_limit_processor
+            .finish_sequential_update_counters_and_log_info(ret.len() as u32, num_txns as u32);
         ret.resize_with(num_txns, E::Output::skip_output);
         Ok(ret)
     }
@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    task::{Transaction, TransactionOutput},
+};
+use aptos_logger::info;
+use aptos_types::{
+    executable::ModulePath, fee_statement::FeeStatement, on_chain_config::BlockGasLimitType,
+};
+use std::{collections::HashSet, hash::Hash};
+
+/// Keys read and written by a committed transaction, used to detect read-write conflicts
+/// between transactions for the conflict penalty of the block gas limit.
+///
+/// Module paths are not tracked, as module reads are served from the VM loader cache and hence
+/// not observed deterministically. Aggregator (v1) paths written or updated by the transaction
+/// are not counted as reads, because sequential execution reads them to materialize deltas,
+/// while parallel execution does not.
+#[derive(Debug)]
+pub(crate) struct ReadWriteSummary<K> {
+    reads: HashSet<K>,
+    writes: HashSet<K>,
+}
+
+impl<K: Hash + Eq + Clone + ModulePath> ReadWriteSummary<K> {
+    pub(crate) fn new<O>(reads: impl Iterator<Item = K>, output: &O) -> Self
+    where
+        O: TransactionOutput,
+        O::Txn: Transaction<Key = K>,
+    {
+        let aggregator_keys: HashSet<K> = output
+            .aggregator_v1_write_set()
+            .into_keys()
+            .chain(output.aggregator_v1_delta_set().into_keys())
+            .collect();
+        let reads = reads
+            .filter(|key| key.module_path().is_none() && !aggregator_keys.contains(key))
+            .collect();
+        let writes = output
+            .resource_write_set()
+            .into_keys()
+            .chain(aggregator_keys)
+            .collect();
+        Self { reads, writes }
+    }
+
+    /// Returns true if this transaction read any key written by the `previous` transaction.
+    pub(crate) fn conflicts_with_previous(&self, previous: &Self) -> bool {
+        !self.reads.is_disjoint(&previous.writes)
+    }
+}
+
+/// Accumulates the effective gas of the committed transactions of a block and decides when the
+/// block must be cut according to the on-chain `BlockGasLimitType`. Parallel and sequential
+/// execution feed the same committed transactions in the same order, so they end the block at
+/// the same transaction.
+pub(crate) struct BlockGasLimitProcessor<K> {
+    block_gas_limit_type: BlockGasLimitType,
+    accumulated_effective_block_gas: u64,
+    accumulated_fee_statement: FeeStatement,
+    txn_fee_statements: Vec<FeeStatement>,
+    txn_read_write_summaries: Vec<ReadWriteSummary<K>>,
+}
+
+impl<K: Hash + Eq + Clone + ModulePath> BlockGasLimitProcessor<K> {
+    pub(crate) fn new(block_gas_limit_type: BlockGasLimitType, init_size: usize) -> Self {
+        Self {
+            block_gas_limit_type,
+            accumulated_effective_block_gas: 0,
+            accumulated_fee_statement: FeeStatement::zero(),
+            txn_fee_statements: Vec::with_capacity(init_size),
+            txn_read_write_summaries: Vec::with_capacity(init_size),
+        }
+    }
+
+    /// Whether the read-write summary of the transactions must be provided to
+    /// `accumulate_fee_statement`, i.e. whether the conflict penalty is enabled.
+    pub(crate) fn needs_read_write_summary(&self) -> bool {
+        self.block_gas_limit_type
+            .conflict_penalty_window()
+            .is_some()
+    }
+
+    pub(crate) fn accumulate_fee_statement(
+        &mut self,
+        fee_statement: FeeStatement,
+        txn_read_write_summary: Option<ReadWriteSummary<K>>,
+    ) {
+        self.accumulated_fee_statement
+            .add_fee_statement(&fee_statement);
+        self.txn_fee_statements.push(fee_statement);
+
+        let conflict_multiplier =
+            if let Some(window) = self.block_gas_limit_type.conflict_penalty_window() {
+                let txn_read_write_summary = txn_read_write_summary
+                    .expect("Read write summary must be provided when conflict penalty is enabled");
+                let multiplier =
+                    self.compute_conflict_multiplier(&txn_read_write_summary, window as usize);
+                self.txn_read_write_summaries.push(txn_read_write_summary);
+                multiplier
+            } else {
+                1
+            };
+
+        let raw_gas_used = fee_statement
+            .execution_gas_used()
+            .saturating_mul(
+                self.block_gas_limit_type
+                    .execution_gas_effective_multiplier(),
+            )
+            .saturating_add(
+                fee_statement
+                    .io_gas_used()
+                    .saturating_mul(self.block_gas_limit_type.io_gas_effective_multiplier()),
+            )
+            .saturating_add(
+                fee_statement
+                    .storage_fee_used()
+                    .saturating_mul(self.block_gas_limit_type.storage_fee_effective_multiplier()),
+            );
+        self.accumulated_effective_block_gas = self
+            .accumulated_effective_block_gas
+            .saturating_add(conflict_multiplier.saturating_mul(raw_gas_used));
+    }
+
+    /// The penalty multiplier of a transaction is one plus the number of transactions, among the
+    /// previous `window` committed ones, that wrote a key the transaction read.
+    fn compute_conflict_multiplier(&self, current: &ReadWriteSummary<K>, window: usize) -> u64 {
+        let start = self.txn_read_write_summaries.len().saturating_sub(window);
+        1 + self.txn_read_write_summaries[start..]
+            .iter()
+            .filter(|previous| current.conflicts_with_previous(previous))
+            .count() as u64
+    }
+
+    /// Returns true (and records it) if the committed transactions exhausted the block gas limit.
+    pub(crate) fn should_end_block(&self, mode: &'static str) -> bool {
+        if let Some(per_block_gas_limit) = self.block_gas_limit_type.block_gas_limit() {
+            if self.accumulated_effective_block_gas >= per_block_gas_limit {
+                counters::EXCEED_PER_BLOCK_GAS_LIMIT_COUNT
+                    .with_label_values(&[mode])
+                    .inc();
+                info!(
+                    "[BlockSTM]: execution ({}) early halted due to \
+                     accumulated_effective_block_gas {} >= PER_BLOCK_GAS_LIMIT {}",
+                    mode, self.accumulated_effective_block_gas, per_block_gas_limit,
+                );
+                return true;
+            }
+        }
+        false
+    }
+
+    pub(crate) fn finish_parallel_update_counters_and_log_info(
+        &self,
+        num_committed: u32,
+        num_total: u32,
+    ) {
+        counters::update_parallel_block_gas_counters(
+            &self.accumulated_fee_statement,
+            num_committed as usize,
+        );
+        counters::update_parallel_txn_gas_counters(&self.txn_fee_statements);
+        self.log_info(counters::Mode::PARALLEL, num_committed, num_total);
+    }
+
+    pub(crate) fn finish_sequential_update_counters_and_log_info(
+        &self,
+        num_committed: u32,
+        num_total: u32,
+    ) {
+        counters::update_sequential_block_gas_counters(
+            &self.accumulated_fee_statement,
+            num_committed as usize,
+        );
+        self.log_info(counters::Mode::SEQUENTIAL, num_committed, num_total);
+    }
+
+    fn log_info(&self, mode: &'static str, num_committed: u32, num_total: u32) {
+        info!(
+            "[BlockSTM]: {} execution completed. {} out of {} txns committed. \
+             accumulated_effective_block_gas = {}, limit = {:?}",
+            mode,
+            num_committed,
+            num_total,
+            self.accumulated_effective_block_gas,
+            self.block_gas_limit_type,
+        );
+    }
+}
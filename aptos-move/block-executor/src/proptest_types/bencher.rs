@@ -13,7 +13,10 @@ use crate::{
     },
     txn_commit_hook::NoOpTransactionCommitHook,
 };
-use aptos_types::{contract_event::ReadWriteEvent, executable::ExecutableTestType};
+use aptos_types::{
+    contract_event::ReadWriteEvent, executable::ExecutableTestType,
+    on_chain_config::BlockGasLimitType,
+};
 use criterion::{BatchSize, Bencher as CBencher};
 use num_cpus;
 use proptest::{
@@ -134,7 +137,12 @@ where
             EmptyDataView<KeyType<K>, ValueType<V>>,
             NoOpTransactionCommitHook<MockOutput<KeyType<K>, ValueType<V>, E>, usize>,
             ExecutableTestType,
-        >::new(num_cpus::get(), executor_thread_pool, None, None)
+        >::new(
+            num_cpus::get(),
+            executor_thread_pool,
+            BlockGasLimitType::NoLimit,
+            None,
+        )
         .execute_transactions_parallel((), &self.transactions, &data_view);
 
         self.baseline_output.assert_output(&output);
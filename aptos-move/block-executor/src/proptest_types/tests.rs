@@ -14,7 +14,10 @@ use crate::{
     },
     txn_commit_hook::NoOpTransactionCommitHook,
 };
-use aptos_types::{contract_event::ReadWriteEvent, executable::ExecutableTestType};
+use aptos_types::{
+    contract_event::ReadWriteEvent, executable::ExecutableTestType,
+    on_chain_config::BlockGasLimitType,
+};
 use claims::assert_ok;
 use num_cpus;
 use proptest::{
@@ -75,7 +78,7 @@ fn run_transactions<K, V, E>(
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            maybe_block_gas_limit,
+            maybe_block_gas_limit.into(),
             None,
         )
         .execute_transactions_parallel((), &transactions, &data_view);
@@ -213,7 +216,7 @@ fn deltas_writes_mixed_with_block_gas_limit(num_txns: usize, maybe_block_gas_lim
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            maybe_block_gas_limit,
+            maybe_block_gas_limit.into(),
             None,
         )
         .execute_transactions_parallel((), &transactions, &data_view);
@@ -267,7 +270,7 @@ fn deltas_resolver_with_block_gas_limit(num_txns: usize, maybe_block_gas_limit:
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            maybe_block_gas_limit,
+            maybe_block_gas_limit.into(),
             None,
         )
         .execute_transactions_parallel((), &transactions, &data_view);
@@ -426,7 +429,7 @@ fn publishing_fixed_params_with_block_gas_limit(
     >::new(
         num_cpus::get(),
         executor_thread_pool,
-        maybe_block_gas_limit,
+        maybe_block_gas_limit.into(),
         None,
     )
     .execute_transactions_parallel((), &transactions, &data_view);
@@ -472,7 +475,7 @@ fn publishing_fixed_params_with_block_gas_limit(
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            Some(max(w_index, r_index) as u64 * MAX_GAS_PER_TXN + 1),
+            BlockGasLimitType::Limit(max(w_index, r_index) as u64 * MAX_GAS_PER_TXN + 1),
             None,
         ) // Ensure enough gas limit to commit the module txns (4 is maximum gas per txn)
         .execute_transactions_parallel((), &transactions, &data_view);
@@ -626,3 +629,70 @@ fn module_publishing_races_with_block_gas_limit_test() {
         );
     }
 }
+
+#[test]
+// With the conflict penalty, parallel and sequential execution must still cut the block at
+// the same transaction.
+fn conflict_penalty_block_gas_limit_parallel_sequential_agree_test() {
+    let mut runner = TestRunner::default();
+
+    let universe = vec(any::<[u8; 32]>(), 10)
+        .new_tree(&mut runner)
+        .expect("creating a new value should succeed")
+        .current();
+    let transaction_gen = vec(any::<TransactionGen<[u8; 32]>>(), 1000)
+        .new_tree(&mut runner)
+        .expect("creating a new value should succeed")
+        .current();
+    let transactions: Vec<MockTransaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>, MockEvent>> =
+        transaction_gen
+            .into_iter()
+            .map(|txn_gen| txn_gen.materialize(&universe, (false, false)))
+            .collect();
+
+    let data_view = EmptyDataView::<KeyType<[u8; 32]>, ValueType<[u8; 32]>> {
+        phantom: PhantomData,
+    };
+    let executor_thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_cpus::get())
+            .build()
+            .unwrap(),
+    );
+
+    let block_gas_limit_type = BlockGasLimitType::ComplexLimitV1 {
+        effective_block_gas_limit: rand::thread_rng().gen_range(0, 1000 * MAX_GAS_PER_TXN),
+        execution_gas_effective_multiplier: 1,
+        io_gas_effective_multiplier: 2,
+        storage_fee_effective_multiplier: 1,
+        conflict_penalty_window: 8,
+    };
+    let executor = BlockExecutor::<
+        MockTransaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>, MockEvent>,
+        MockTask<KeyType<[u8; 32]>, ValueType<[u8; 32]>, MockEvent>,
+        EmptyDataView<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
+        NoOpTransactionCommitHook<
+            MockOutput<KeyType<[u8; 32]>, ValueType<[u8; 32]>, MockEvent>,
+            usize,
+        >,
+        ExecutableTestType,
+    >::new(
+        num_cpus::get(),
+        executor_thread_pool,
+        block_gas_limit_type,
+        None,
+    );
+
+    let parallel_output = executor
+        .execute_transactions_parallel((), &transactions, &data_view)
+        .unwrap();
+    let sequential_output = executor
+        .execute_transactions_sequential((), &transactions, &data_view)
+        .unwrap();
+
+    assert_eq!(parallel_output.len(), sequential_output.len());
+    for (parallel, sequential) in parallel_output.iter().zip(sequential_output.iter()) {
+        assert_eq!(parallel.read_results, sequential.read_results);
+        assert_eq!(parallel.total_gas, sequential.total_gas);
+    }
+}
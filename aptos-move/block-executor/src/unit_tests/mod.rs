@@ -19,6 +19,7 @@ use aptos_mvhashmap::types::TxnIndex;
 use aptos_types::{
     contract_event::ReadWriteEvent,
     executable::{ExecutableTestType, ModulePath},
+    on_chain_config::BlockGasLimitType,
     write_set::TransactionWrite,
 };
 use claims::{assert_matches, assert_some_eq};
@@ -51,7 +52,12 @@ where
         DeltaDataView<K, V>,
         NoOpTransactionCommitHook<MockOutput<K, V, E>, usize>,
         ExecutableTestType,
-    >::new(num_cpus::get(), executor_thread_pool, None, None)
+    >::new(
+        num_cpus::get(),
+        executor_thread_pool,
+        BlockGasLimitType::NoLimit,
+        None,
+    )
     .execute_transactions_parallel((), &transactions, &data_view);
 
     let baseline = BaselineOutput::generate(&transactions, None);
@@ -25,6 +25,7 @@ use aptos_types::{
 use aptos_vm_logging::{log_schema::AdapterLogSchema, prelude::*};
 use std::{
     cell::RefCell,
+    collections::HashSet,
     fmt::Debug,
     sync::{atomic::AtomicU32, Arc},
 };
@@ -163,6 +164,18 @@ impl<'a, T: Transaction, X: Executable> ParallelState<'a, T, X> {
 pub(crate) struct SequentialState<'a, T: Transaction, X: Executable> {
     pub(crate) unsync_map: &'a UnsyncMap<T::Key, T::Value, X>,
     pub(crate) _counter: &'a u32,
+    // Keys read by the transaction, needed by the conflict penalty of the block gas limit.
+    pub(crate) read_set: RefCell<HashSet<T::Key>>,
+}
+
+impl<'a, T: Transaction, X: Executable> SequentialState<'a, T, X> {
+    pub(crate) fn new(unsync_map: &'a UnsyncMap<T::Key, T::Value, X>, counter: &'a u32) -> Self {
+        Self {
+            unsync_map,
+            _counter: counter,
+            read_set: RefCell::new(HashSet::new()),
+        }
+    }
 }
 
 pub(crate) enum ViewState<'a, T: Transaction, X: Executable> {
@@ -204,6 +217,16 @@ impl<'a, T: Transaction, S: TStateView<Key = T::Key>, X: Executable> LatestView<
         }
     }
 
+    /// Drains the keys read in the sequential setting.
+    pub(crate) fn take_sequential_reads(&self) -> HashSet<T::Key> {
+        match &self.latest_view {
+            ViewState::Sync(_) => {
+                unreachable!("Take sequential reads called in parallel setting")
+            },
+            ViewState::Unsync(state) => state.read_set.take(),
+        }
+    }
+
     fn get_base_value(&self, state_key: &T::Key) -> anyhow::Result<Option<StateValue>> {
         let ret = self.base_view.get_state_value(state_key);
 
@@ -279,10 +302,13 @@ impl<'a, T: Transaction, S: TStateView<Key = T::Key>, X: Executable> TStateView
                     }
                 },
             },
-            ViewState::Unsync(state) => state.unsync_map.fetch_data(state_key).map_or_else(
-                || self.get_base_value(state_key),
-                |v| Ok(v.as_state_value()),
-            ),
+            ViewState::Unsync(state) => {
+                state.read_set.borrow_mut().insert(state_key.clone());
+                state.unsync_map.fetch_data(state_key).map_or_else(
+                    || self.get_base_value(state_key),
+                    |v| Ok(v.as_state_value()),
+                )
+            },
         }
     }
 
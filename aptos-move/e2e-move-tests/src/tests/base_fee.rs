@@ -0,0 +1,146 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, transaction_fee, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::{BaseFee, FeatureFlag, OnChainConfig},
+    transaction::{SignedTransaction, TransactionStatus},
+};
+use move_core_types::vm_status::StatusCode;
+
+fn read_base_fee(h: &MoveHarness) -> u64 {
+    h.read_resource::<BaseFee>(&AccountAddress::ONE, BaseFee::struct_tag())
+        .unwrap()
+        .base_fee_per_gas
+}
+
+#[test]
+fn base_fee_discards_underpriced_transactions() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::BASE_FEE_MARKET], vec![]);
+    transaction_fee::initialize_base_fee_market(&mut h, 200, 1_000_000, 8, 100);
+    assert_eq!(read_base_fee(&h), 200);
+
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+
+    h.set_default_gas_unit_price(199);
+    let txn =
+        h.create_transaction_payload(&alice, aptos_stdlib::aptos_coin_transfer(*bob.address(), 1));
+    // Validation accepts the transaction, so that mempool keeps it until the base fee drops.
+    assert_eq!(h.executor.verify_transaction(txn.clone()).status(), None);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::GAS_UNIT_PRICE_BELOW_BASE_FEE)
+    );
+
+    h.set_default_gas_unit_price(200);
+    let txn =
+        h.create_transaction_payload(&alice, aptos_stdlib::aptos_coin_transfer(*bob.address(), 1));
+    assert_success!(h.run(txn));
+}
+
+fn transfer_with_max_priority_fee(
+    h: &MoveHarness,
+    sender: &Account,
+    receiver: &Account,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+) -> SignedTransaction {
+    sender
+        .transaction()
+        .sequence_number(h.sequence_number(sender.address()))
+        .max_gas_amount(2_000_000)
+        .gas_unit_price(max_fee_per_gas)
+        .payload(aptos_stdlib::aptos_coin_transfer(*receiver.address(), 1))
+        .raw()
+        .with_max_priority_fee_per_gas(max_priority_fee_per_gas)
+        .sign(&sender.privkey, sender.pubkey.clone())
+        .unwrap()
+        .into_inner()
+}
+
+#[test]
+fn base_fee_refunds_max_fee_above_max_priority_fee() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::BASE_FEE_MARKET], vec![]);
+    transaction_fee::initialize_base_fee_market(&mut h, 200, 1_000_000, 8, 100);
+
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+
+    // Only the base fee plus the max priority fee is charged, the rest of the max fee is refunded.
+    let balance = h.read_aptos_balance(alice.address());
+    let output = h.run_raw(transfer_with_max_priority_fee(&h, &alice, &bob, 1_000, 10));
+    assert_success!(output.status().to_owned());
+    assert_eq!(
+        balance - h.read_aptos_balance(alice.address()),
+        1 + output.gas_used() * 210
+    );
+
+    // The max fee still caps the price charged.
+    let balance = h.read_aptos_balance(alice.address());
+    let output = h.run_raw(transfer_with_max_priority_fee(&h, &alice, &bob, 205, 10));
+    assert_success!(output.status().to_owned());
+    assert_eq!(
+        balance - h.read_aptos_balance(alice.address()),
+        1 + output.gas_used() * 205
+    );
+
+    // Without a max priority fee all of the max fee is charged.
+    h.set_default_gas_unit_price(1_000);
+    let balance = h.read_aptos_balance(alice.address());
+    let txn =
+        h.create_transaction_payload(&alice, aptos_stdlib::aptos_coin_transfer(*bob.address(), 1));
+    let output = h.run_raw(txn);
+    assert_success!(output.status().to_owned());
+    assert_eq!(
+        balance - h.read_aptos_balance(alice.address()),
+        1 + output.gas_used() * 1_000
+    );
+}
+
+#[test]
+fn base_fee_follows_block_fullness() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::BASE_FEE_MARKET], vec![]);
+    transaction_fee::initialize_base_fee_market(&mut h, 200, 1, 8, 100);
+
+    // An empty block decreases the base fee by 1/8.
+    h.new_block_with_metadata(AccountAddress::ZERO, vec![]);
+    assert_eq!(read_base_fee(&h), 175);
+
+    // A block above the target increases it in proportion to the excess gas.
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+    h.set_default_gas_unit_price(175);
+    let txn =
+        h.create_transaction_payload(&alice, aptos_stdlib::aptos_coin_transfer(*bob.address(), 1));
+    let output = h.run_raw(txn);
+    assert_success!(output.status().to_owned());
+    h.new_block_with_metadata(AccountAddress::ZERO, vec![]);
+    assert_eq!(read_base_fee(&h), 175 + 175 * (output.gas_used() - 1) / 8);
+
+    // The base fee never drops below the minimum.
+    for _ in 0..20 {
+        h.new_block_with_metadata(AccountAddress::ZERO, vec![]);
+    }
+    assert_eq!(read_base_fee(&h), 100);
+}
+
+#[test]
+fn base_fee_disabled_without_feature() {
+    let mut h = MoveHarness::new_with_features(vec![], vec![FeatureFlag::BASE_FEE_MARKET]);
+    transaction_fee::initialize_base_fee_market(&mut h, 200, 1_000_000, 8, 100);
+
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+    h.set_default_gas_unit_price(100);
+    let txn =
+        h.create_transaction_payload(&alice, aptos_stdlib::aptos_coin_transfer(*bob.address(), 1));
+    assert_success!(h.run(txn));
+
+    // The base fee is not adjusted while the feature is disabled.
+    h.new_block_with_metadata(AccountAddress::ZERO, vec![]);
+    assert_eq!(read_base_fee(&h), 200);
+}
@@ -0,0 +1,97 @@
+/// A fungible asset which charges a fee of 10% on withdrawals, paid to the primary store of the issuer, counts the
+/// deposits into its stores, and displays balances scaled by a factor of two.
+module 0xcafe::fee_token {
+    use aptos_framework::dispatchable_fungible_asset;
+    use aptos_framework::function_info::{Self, FunctionInfo};
+    use aptos_framework::fungible_asset::{Self, FungibleAsset, Metadata, MintRef, TransferRef};
+    use aptos_framework::object::{Self, ConstructorRef, Object};
+    use aptos_framework::primary_fungible_store;
+    use std::option;
+    use std::signer;
+    use std::string;
+
+    const ASSET_SYMBOL: vector<u8> = b"FEE";
+
+    struct Refs has key {
+        mint_ref: MintRef,
+    }
+
+    struct DepositCounter has key {
+        count: u64,
+    }
+
+    fun init_module(creator: &signer) {
+        let constructor_ref = &create_asset(creator, ASSET_SYMBOL);
+        dispatchable_fungible_asset::register_dispatch_functions(
+            constructor_ref,
+            option::some(function_named(b"withdraw")),
+            option::some(function_named(b"deposit")),
+            option::some(function_named(b"derived_balance")),
+        );
+        move_to(creator, Refs { mint_ref: fungible_asset::generate_mint_ref(constructor_ref) });
+        move_to(creator, DepositCounter { count: 0 });
+    }
+
+    #[view]
+    public fun metadata(): Object<Metadata> {
+        object::address_to_object(object::create_object_address(&@0xcafe, ASSET_SYMBOL))
+    }
+
+    public fun withdraw<T: key>(store: Object<T>, amount: u64, transfer_ref: &TransferRef): FungibleAsset {
+        let fee = amount / 10;
+        if (fee > 0) {
+            let fee_store = primary_fungible_store::ensure_primary_store_exists(@0xcafe, metadata());
+            let fee_asset = fungible_asset::withdraw_with_ref(transfer_ref, store, fee);
+            fungible_asset::deposit_with_ref(transfer_ref, fee_store, fee_asset);
+        };
+        fungible_asset::withdraw_with_ref(transfer_ref, store, amount)
+    }
+
+    public fun deposit<T: key>(
+        store: Object<T>,
+        fa: FungibleAsset,
+        transfer_ref: &TransferRef,
+    ) acquires DepositCounter {
+        let counter = borrow_global_mut<DepositCounter>(@0xcafe);
+        counter.count = counter.count + 1;
+        fungible_asset::deposit_with_ref(transfer_ref, store, fa);
+    }
+
+    public fun derived_balance<T: key>(store: Object<T>): u64 {
+        fungible_asset::balance(store) * 2
+    }
+
+    public entry fun mint(admin: &signer, to: address, amount: u64) acquires Refs {
+        let refs = borrow_global<Refs>(signer::address_of(admin));
+        primary_fungible_store::mint(&refs.mint_ref, to, amount);
+    }
+
+    /// Registers `derived_balance` as withdraw function of a new asset, which does not match the expected signature.
+    public entry fun create_with_invalid_withdraw_function(creator: &signer) {
+        let constructor_ref = &create_asset(creator, b"INVALID");
+        dispatchable_fungible_asset::register_dispatch_functions(
+            constructor_ref,
+            option::some(function_named(b"derived_balance")),
+            option::none(),
+            option::none(),
+        );
+    }
+
+    fun create_asset(creator: &signer, symbol: vector<u8>): ConstructorRef {
+        let constructor_ref = object::create_named_object(creator, symbol);
+        primary_fungible_store::create_primary_store_enabled_fungible_asset(
+            &constructor_ref,
+            option::none(),
+            string::utf8(symbol),
+            string::utf8(symbol),
+            8,
+            string::utf8(b""),
+            string::utf8(b""),
+        );
+        constructor_ref
+    }
+
+    fun function_named(name: vector<u8>): FunctionInfo {
+        function_info::new_function_info(@0xcafe, string::utf8(b"fee_token"), string::utf8(name))
+    }
+}
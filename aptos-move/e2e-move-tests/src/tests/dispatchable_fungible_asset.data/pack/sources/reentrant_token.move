@@ -0,0 +1,60 @@
+/// A fungible asset whose withdraw function deposits part of the withdrawn amount via the primary store API, which
+/// dispatches back into this module and is hence rejected by the VM.
+module 0xcafe::reentrant_token {
+    use aptos_framework::dispatchable_fungible_asset;
+    use aptos_framework::function_info;
+    use aptos_framework::fungible_asset::{Self, FungibleAsset, Metadata, MintRef, TransferRef};
+    use aptos_framework::object::{Self, Object};
+    use aptos_framework::primary_fungible_store;
+    use std::option;
+    use std::signer;
+    use std::string;
+
+    const ASSET_SYMBOL: vector<u8> = b"REENTRANT";
+
+    struct Refs has key {
+        mint_ref: MintRef,
+    }
+
+    fun init_module(creator: &signer) {
+        let constructor_ref = &object::create_named_object(creator, ASSET_SYMBOL);
+        primary_fungible_store::create_primary_store_enabled_fungible_asset(
+            constructor_ref,
+            option::none(),
+            string::utf8(ASSET_SYMBOL),
+            string::utf8(ASSET_SYMBOL),
+            8,
+            string::utf8(b""),
+            string::utf8(b""),
+        );
+        let module_name = string::utf8(b"reentrant_token");
+        dispatchable_fungible_asset::register_dispatch_functions(
+            constructor_ref,
+            option::some(function_info::new_function_info(@0xcafe, module_name, string::utf8(b"withdraw"))),
+            option::some(function_info::new_function_info(@0xcafe, module_name, string::utf8(b"deposit"))),
+            option::none(),
+        );
+        move_to(creator, Refs { mint_ref: fungible_asset::generate_mint_ref(constructor_ref) });
+    }
+
+    #[view]
+    public fun metadata(): Object<Metadata> {
+        object::address_to_object(object::create_object_address(&@0xcafe, ASSET_SYMBOL))
+    }
+
+    public fun withdraw<T: key>(store: Object<T>, amount: u64, transfer_ref: &TransferRef): FungibleAsset {
+        let fa = fungible_asset::withdraw_with_ref(transfer_ref, store, amount);
+        let fee = fungible_asset::extract(&mut fa, 1);
+        primary_fungible_store::deposit(@0xcafe, fee);
+        fa
+    }
+
+    public fun deposit<T: key>(store: Object<T>, fa: FungibleAsset, transfer_ref: &TransferRef) {
+        fungible_asset::deposit_with_ref(transfer_ref, store, fa);
+    }
+
+    public entry fun mint(admin: &signer, to: address, amount: u64) acquires Refs {
+        let refs = borrow_global<Refs>(signer::address_of(admin));
+        primary_fungible_store::mint(&refs.mint_ref, to, amount);
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, tests::common, MoveHarness};
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::{create_derived_object_address, create_object_address, AccountAddress},
+    on_chain_config::FeatureFlag,
+    transaction::{ExecutionStatus, TransactionStatus},
+};
+use move_core_types::{language_storage::TypeTag, parser::parse_struct_tag};
+use serde::Deserialize;
+
+/// Mimics `0xcafe::fee_token::DepositCounter`
+#[derive(Deserialize)]
+struct DepositCounter {
+    count: u64,
+}
+
+fn setup(enabled: Vec<FeatureFlag>, disabled: Vec<FeatureFlag>) -> (MoveHarness, Account) {
+    let mut h = MoveHarness::new_with_features(enabled, disabled);
+    let admin = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    (h, admin)
+}
+
+fn publish(h: &mut MoveHarness, admin: &Account) -> TransactionStatus {
+    h.publish_package(
+        admin,
+        &common::test_dir_path("dispatchable_fungible_asset.data/pack"),
+    )
+}
+
+fn metadata_type() -> TypeTag {
+    TypeTag::Struct(Box::new(
+        parse_struct_tag("0x1::fungible_asset::Metadata").unwrap(),
+    ))
+}
+
+fn store_type() -> TypeTag {
+    TypeTag::Struct(Box::new(
+        parse_struct_tag("0x1::fungible_asset::FungibleStore").unwrap(),
+    ))
+}
+
+fn mint(
+    h: &mut MoveHarness,
+    admin: &Account,
+    module: &str,
+    to: &AccountAddress,
+    amount: u64,
+) -> TransactionStatus {
+    h.run_entry_function(
+        admin,
+        str::parse(&format!("0xcafe::{}::mint", module)).unwrap(),
+        vec![],
+        vec![bcs::to_bytes(to).unwrap(), bcs::to_bytes(&amount).unwrap()],
+    )
+}
+
+fn transfer(
+    h: &mut MoveHarness,
+    sender: &Account,
+    metadata: AccountAddress,
+    to: &AccountAddress,
+    amount: u64,
+) -> TransactionStatus {
+    h.run_entry_function(
+        sender,
+        str::parse("0x1::primary_fungible_store::transfer").unwrap(),
+        vec![metadata_type()],
+        vec![
+            bcs::to_bytes(&metadata).unwrap(),
+            bcs::to_bytes(to).unwrap(),
+            bcs::to_bytes(&amount).unwrap(),
+        ],
+    )
+}
+
+fn balance(h: &mut MoveHarness, owner: &AccountAddress, metadata: AccountAddress) -> u64 {
+    let result = h
+        .execute_view_function(
+            str::parse("0x1::primary_fungible_store::balance").unwrap(),
+            vec![metadata_type()],
+            vec![
+                bcs::to_bytes(owner).unwrap(),
+                bcs::to_bytes(&metadata).unwrap(),
+            ],
+        )
+        .unwrap();
+    bcs::from_bytes::<u64>(&result[0]).unwrap()
+}
+
+fn derived_balance(h: &mut MoveHarness, store: AccountAddress) -> u64 {
+    let result = h
+        .execute_view_function(
+            str::parse("0x1::dispatchable_fungible_asset::derived_balance").unwrap(),
+            vec![store_type()],
+            vec![bcs::to_bytes(&store).unwrap()],
+        )
+        .unwrap();
+    bcs::from_bytes::<u64>(&result[0]).unwrap()
+}
+
+#[test]
+fn test_dispatchable_fungible_asset() {
+    let (mut h, admin) = setup(vec![FeatureFlag::DISPATCHABLE_FUNGIBLE_ASSET], vec![]);
+    assert_success!(publish(&mut h, &admin));
+
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+    let metadata = create_object_address(*admin.address(), b"FEE");
+
+    // Minting does not dispatch.
+    assert_success!(mint(&mut h, &admin, "fee_token", alice.address(), 1000));
+    assert_eq!(balance(&mut h, alice.address(), metadata), 1000);
+
+    // The withdraw function charges a fee of 10% to the sender, paid to the issuer, and the
+    // deposit function counts the deposit.
+    assert_success!(transfer(&mut h, &alice, metadata, bob.address(), 100));
+    assert_eq!(balance(&mut h, alice.address(), metadata), 890);
+    assert_eq!(balance(&mut h, bob.address(), metadata), 100);
+    assert_eq!(balance(&mut h, admin.address(), metadata), 10);
+    let counter = h
+        .read_resource::<DepositCounter>(
+            admin.address(),
+            parse_struct_tag("0xcafe::fee_token::DepositCounter").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(counter.count, 1);
+
+    let bob_store = create_derived_object_address(*bob.address(), metadata);
+    assert_eq!(derived_balance(&mut h, bob_store), 200);
+
+    // Moving the asset without dispatching is not allowed.
+    let alice_store = create_derived_object_address(*alice.address(), metadata);
+    let status = h.run_entry_function(
+        &alice,
+        str::parse("0x1::fungible_asset::transfer").unwrap(),
+        vec![store_type()],
+        vec![
+            bcs::to_bytes(&alice_store).unwrap(),
+            bcs::to_bytes(&bob_store).unwrap(),
+            bcs::to_bytes(&10u64).unwrap(),
+        ],
+    );
+    // EINVALID_DISPATCHABLE_OPERATIONS
+    assert_abort!(status, 0x1001C);
+}
+
+#[test]
+fn test_dispatch_function_signature_mismatch() {
+    let (mut h, admin) = setup(vec![FeatureFlag::DISPATCHABLE_FUNGIBLE_ASSET], vec![]);
+    assert_success!(publish(&mut h, &admin));
+
+    let status = h.run_entry_function(
+        &admin,
+        str::parse("0xcafe::fee_token::create_with_invalid_withdraw_function").unwrap(),
+        vec![],
+        vec![],
+    );
+    // EWITHDRAW_FUNCTION_SIGNATURE_MISMATCH
+    assert_abort!(status, 0x10019);
+}
+
+#[test]
+fn test_dispatch_reentrancy() {
+    let (mut h, admin) = setup(vec![FeatureFlag::DISPATCHABLE_FUNGIBLE_ASSET], vec![]);
+    assert_success!(publish(&mut h, &admin));
+
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+    let metadata = create_object_address(*admin.address(), b"REENTRANT");
+
+    assert_success!(mint(
+        &mut h,
+        &admin,
+        "reentrant_token",
+        alice.address(),
+        1000
+    ));
+    // The withdraw function deposits through the primary store API, which dispatches back into
+    // its module.
+    let status = transfer(&mut h, &alice, metadata, bob.address(), 100);
+    assert!(matches!(
+        status,
+        TransactionStatus::Keep(ExecutionStatus::ExecutionFailure { .. })
+    ));
+    assert_eq!(balance(&mut h, alice.address(), metadata), 1000);
+}
+
+#[test]
+fn test_dispatchable_fungible_asset_disabled() {
+    let (mut h, admin) = setup(vec![], vec![FeatureFlag::DISPATCHABLE_FUNGIBLE_ASSET]);
+    // EDISPATCHABLE_FUNGIBLE_ASSET_NOT_ENABLED, raised by `init_module`.
+    assert_abort!(publish(&mut h, &admin), 0x30018);
+}
@@ -2,8 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{assert_success, tests::common, MoveHarness};
-use aptos_types::account_address::{self, AccountAddress};
-use move_core_types::{identifier::Identifier, language_storage::StructTag};
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::{self, AccountAddress},
+    on_chain_config::FeatureFlag,
+};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Eq, PartialEq)]
@@ -149,3 +156,175 @@ fn test_basic_fungible_token() {
     alice_store.balance = 10;
     assert_eq!(alice_store, bob_store);
 }
+
+fn fungible_store_tag() -> StructTag {
+    StructTag {
+        address: AccountAddress::ONE,
+        module: Identifier::new("fungible_asset").unwrap(),
+        name: Identifier::new("FungibleStore").unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn transfer_between_primary_stores(
+    h: &mut MoveHarness,
+    admin: &Account,
+    metadata: AccountAddress,
+    from: &AccountAddress,
+    to: &AccountAddress,
+    amount: u64,
+) {
+    let result = h.run_entry_function(
+        admin,
+        str::parse(&format!(
+            "0x{}::managed_fungible_asset::transfer_between_primary_stores",
+            (*admin.address()).to_hex()
+        ))
+        .unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(&metadata).unwrap(),
+            bcs::to_bytes(&vec![from]).unwrap(),
+            bcs::to_bytes(&vec![to]).unwrap(),
+            bcs::to_bytes(&vec![amount]).unwrap(),
+        ],
+    );
+    assert_success!(result);
+}
+
+fn primary_store_balance(
+    h: &mut MoveHarness,
+    owner: &AccountAddress,
+    metadata: AccountAddress,
+) -> u64 {
+    let balance = h
+        .execute_view_function(
+            str::parse("0x1::primary_fungible_store::balance").unwrap(),
+            vec![TypeTag::Struct(Box::new(StructTag {
+                address: AccountAddress::ONE,
+                module: Identifier::new("fungible_asset").unwrap(),
+                name: Identifier::new("Metadata").unwrap(),
+                type_params: vec![],
+            }))],
+            vec![
+                bcs::to_bytes(owner).unwrap(),
+                bcs::to_bytes(&metadata).unwrap(),
+            ],
+        )
+        .unwrap()
+        .pop()
+        .unwrap();
+    bcs::from_bytes::<u64>(&balance).unwrap()
+}
+
+#[test]
+fn test_concurrent_fungible_balance() {
+    let mut h = MoveHarness::new();
+    h.enable_features(
+        vec![
+            FeatureFlag::MODULE_EVENT,
+            FeatureFlag::CONCURRENT_FUNGIBLE_BALANCE,
+        ],
+        vec![],
+    );
+
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xface").unwrap());
+
+    let mut build_options = aptos_framework::BuildOptions::default();
+    build_options
+        .named_addresses
+        .insert("example_addr".to_string(), *alice.address());
+    assert_success!(h.publish_package_with_options(
+        &alice,
+        &common::test_dir_path("../../../move-examples/fungible_asset/managed_fungible_asset"),
+        build_options.clone(),
+    ));
+    assert_success!(h.publish_package_with_options(
+        &alice,
+        &common::test_dir_path("../../../move-examples/fungible_asset/managed_fungible_token"),
+        build_options,
+    ));
+
+    let token_addr = account_address::create_token_address(
+        *alice.address(),
+        "test collection name",
+        "test token name",
+    );
+    let bob_primary_store_addr =
+        account_address::create_derived_object_address(*bob.address(), token_addr);
+
+    let result = h.run_entry_function(
+        &alice,
+        str::parse(&format!(
+            "0x{}::managed_fungible_asset::mint_to_primary_stores",
+            (*alice.address()).to_hex()
+        ))
+        .unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(&token_addr).unwrap(),
+            bcs::to_bytes(&vec![alice.address()]).unwrap(),
+            bcs::to_bytes(&vec![100u64]).unwrap(),
+        ],
+    );
+    assert_success!(result);
+    transfer_between_primary_stores(
+        &mut h,
+        &alice,
+        token_addr,
+        alice.address(),
+        bob.address(),
+        10,
+    );
+
+    // Bob upgrades his primary store, moving the existing balance into an aggregator.
+    let result = h.run_entry_function(
+        &bob,
+        str::parse("0x1::fungible_asset::upgrade_store_to_concurrent").unwrap(),
+        vec![TypeTag::Struct(Box::new(fungible_store_tag()))],
+        vec![bcs::to_bytes(&bob_primary_store_addr).unwrap()],
+    );
+    assert_success!(result);
+    assert_eq!(primary_store_balance(&mut h, bob.address(), token_addr), 10);
+
+    // Deposits into and withdrawals from the upgraded store go through the aggregator.
+    for _ in 0..3 {
+        transfer_between_primary_stores(
+            &mut h,
+            &alice,
+            token_addr,
+            alice.address(),
+            bob.address(),
+            15,
+        );
+    }
+    transfer_between_primary_stores(
+        &mut h,
+        &alice,
+        token_addr,
+        bob.address(),
+        alice.address(),
+        5,
+    );
+    assert_eq!(primary_store_balance(&mut h, bob.address(), token_addr), 50);
+    assert_eq!(
+        primary_store_balance(&mut h, alice.address(), token_addr),
+        50
+    );
+
+    // The balance stored in the resource itself is no longer used.
+    let bob_store: FungibleStore = h
+        .read_resource_from_resource_group(
+            &bob_primary_store_addr,
+            StructTag {
+                address: AccountAddress::ONE,
+                module: Identifier::new("object").unwrap(),
+                name: Identifier::new("ObjectGroup").unwrap(),
+                type_params: vec![],
+            },
+            fungible_store_tag(),
+        )
+        .unwrap();
+    assert_eq!(bob_store.balance, 0);
+}
@@ -5,10 +5,12 @@ mod access_path_test;
 mod aggregator;
 mod aggregator_v2;
 mod attributes;
+mod base_fee;
 mod chain_id;
 mod code_publishing;
 mod common;
 mod constructor_args;
+mod dispatchable_fungible_asset;
 mod error_map;
 mod fee_payer;
 mod fungible_asset;
@@ -26,6 +28,7 @@ mod missing_gas_parameter;
 mod module_event;
 mod new_integer_types;
 mod nft_dao;
+mod object_code_deployment;
 mod offer_rotation_capability;
 mod offer_signer_capability;
 mod per_category_gas_limits;
@@ -41,6 +44,7 @@ mod string_args;
 mod token_event_store;
 mod token_objects;
 mod too_large;
+mod transaction_context;
 mod transaction_fee;
 mod type_too_large;
 mod vector_numeric_address;
@@ -0,0 +1,134 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, MoveHarness};
+use aptos_framework::natives::code::{PackageRegistry, UpgradePolicy};
+use aptos_language_e2e_tests::account::Account;
+use aptos_package_builder::PackageBuilder;
+use aptos_types::{
+    account_address::{create_object_code_deployment_address, AccountAddress},
+    on_chain_config::FeatureFlag,
+    transaction::TransactionPayload,
+};
+use move_core_types::parser::parse_struct_tag;
+
+/// Builds a package with a module at the named address `object_code`, returning the payload
+/// created by `make_payload` from the serialized metadata and the code.
+fn build_package(
+    object_address: AccountAddress,
+    module_source: &str,
+    make_payload: impl FnOnce(Vec<u8>, Vec<Vec<u8>>) -> TransactionPayload,
+) -> TransactionPayload {
+    let mut pack = PackageBuilder::new("ObjectPackage").with_policy(UpgradePolicy::compat());
+    pack.add_alias("object_code", &object_address.to_hex_literal());
+    pack.add_source("m", module_source);
+    let pack_dir = pack.write_to_temp().unwrap();
+    let package = aptos_framework::BuiltPackage::build(
+        pack_dir.path().to_owned(),
+        aptos_framework::BuildOptions::default(),
+    )
+    .expect("building package must succeed");
+    let code = package.extract_code();
+    let metadata = package
+        .extract_metadata()
+        .expect("extracting package metadata must succeed");
+    make_payload(
+        bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
+        code,
+    )
+}
+
+fn publish(h: &mut MoveHarness, acc: &Account) -> (AccountAddress, TransactionPayload) {
+    let object_address =
+        create_object_code_deployment_address(*acc.address(), h.sequence_number(acc.address()) + 1);
+    let payload = build_package(
+        object_address,
+        "module object_code::m { public fun f(): u64 { 1 } }",
+        aptos_cached_packages::aptos_stdlib::object_code_deployment_publish,
+    );
+    (object_address, payload)
+}
+
+fn upgrade_payload(object_address: AccountAddress) -> TransactionPayload {
+    build_package(
+        object_address,
+        "module object_code::m { public fun f(): u64 { 2 } public fun g() {} }",
+        |metadata_serialized, code| {
+            aptos_cached_packages::aptos_stdlib::object_code_deployment_upgrade(
+                metadata_serialized,
+                code,
+                object_address,
+            )
+        },
+    )
+}
+
+fn read_registry(h: &MoveHarness, object_address: &AccountAddress) -> PackageRegistry {
+    h.read_resource::<PackageRegistry>(
+        object_address,
+        parse_struct_tag("0x1::code::PackageRegistry").unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn object_code_deployment_publish_and_upgrade() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::OBJECT_CODE_DEPLOYMENT], vec![]);
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    let other = h.new_account_at(AccountAddress::from_hex_literal("0xbeef").unwrap());
+
+    let (object_address, payload) = publish(&mut h, &acc);
+    assert_success!(h.run_transaction_payload(&acc, payload));
+    let registry = read_registry(&h, &object_address);
+    assert_eq!(registry.packages.len(), 1);
+    assert_eq!(registry.packages[0].name, "ObjectPackage");
+    assert_eq!(registry.packages[0].upgrade_number, 0);
+
+    // Only the owner of the object can upgrade the code.
+    let status = h.run_transaction_payload(&other, upgrade_payload(object_address));
+    assert_abort!(status, 0x50002);
+
+    assert_success!(h.run_transaction_payload(&acc, upgrade_payload(object_address)));
+    let registry = read_registry(&h, &object_address);
+    assert_eq!(registry.packages[0].upgrade_number, 1);
+    assert_eq!(registry.packages[0].modules.len(), 1);
+
+    // Once frozen, the code cannot be upgraded anymore.
+    let freeze = aptos_cached_packages::aptos_stdlib::object_code_deployment_freeze_code_object(
+        object_address,
+    );
+    assert_abort!(h.run_transaction_payload(&other, freeze.clone()), 0x5000A);
+    assert_success!(h.run_transaction_payload(&acc, freeze));
+    let registry = read_registry(&h, &object_address);
+    assert_eq!(
+        registry.packages[0].upgrade_policy,
+        UpgradePolicy::immutable()
+    );
+    // EUPGRADE_IMMUTABLE
+    let status = h.run_transaction_payload(&acc, upgrade_payload(object_address));
+    assert_abort!(status, 0x10002);
+}
+
+#[test]
+fn object_code_deployment_new_object_per_publish() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::OBJECT_CODE_DEPLOYMENT], vec![]);
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    let (first_address, payload) = publish(&mut h, &acc);
+    assert_success!(h.run_transaction_payload(&acc, payload));
+    let (second_address, payload) = publish(&mut h, &acc);
+    assert_success!(h.run_transaction_payload(&acc, payload));
+
+    assert_ne!(first_address, second_address);
+    assert_eq!(read_registry(&h, &second_address).packages.len(), 1);
+}
+
+#[test]
+fn object_code_deployment_disabled() {
+    let mut h = MoveHarness::new_with_features(vec![], vec![FeatureFlag::OBJECT_CODE_DEPLOYMENT]);
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    let (_, payload) = publish(&mut h, &acc);
+    // EOBJECT_CODE_DEPLOYMENT_NOT_SUPPORTED
+    assert_abort!(h.run_transaction_payload(&acc, payload), 0xD0001);
+}
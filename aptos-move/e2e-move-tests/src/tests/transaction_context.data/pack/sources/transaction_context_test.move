@@ -0,0 +1,46 @@
+module 0xcafe::transaction_context_test {
+    use std::option;
+    use std::string::String;
+    use aptos_framework::transaction_context;
+
+    /// Since tests in e2e-move-tests/ can only call entry functions which don't have return values, we store the
+    /// transaction context in this resource, which we read back in the e2e-move-tests/ test.
+    struct TransactionContextStore has key {
+        sender: address,
+        secondary_signers: vector<address>,
+        gas_payer: address,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        chain_id: u8,
+        account_address: address,
+        module_name: String,
+        function_name: String,
+        type_arg_names: vector<String>,
+        args: vector<vector<u8>>,
+    }
+
+    fun store_transaction_context(sender: &signer) {
+        let payload = option::extract(&mut transaction_context::entry_function_payload());
+        move_to(sender, TransactionContextStore {
+            sender: transaction_context::sender(),
+            secondary_signers: transaction_context::secondary_signers(),
+            gas_payer: transaction_context::gas_payer(),
+            max_gas_amount: transaction_context::max_gas_amount(),
+            gas_unit_price: transaction_context::gas_unit_price(),
+            chain_id: transaction_context::chain_id(),
+            account_address: transaction_context::account_address(&payload),
+            module_name: transaction_context::module_name(&payload),
+            function_name: transaction_context::function_name(&payload),
+            type_arg_names: transaction_context::type_arg_names(&payload),
+            args: transaction_context::args(&payload),
+        });
+    }
+
+    public entry fun store_entry_function<T>(sender: &signer, _amount: u64, _recipient: address) {
+        store_transaction_context(sender);
+    }
+
+    public entry fun store_multi_agent(sender: &signer, _second: &signer) {
+        store_transaction_context(sender);
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, tests::common, MoveHarness};
+use aptos_language_e2e_tests::account::{Account, TransactionBuilder};
+use aptos_types::{
+    account_address::AccountAddress, on_chain_config::FeatureFlag, transaction::EntryFunction,
+};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+    parser::parse_struct_tag,
+};
+use serde::{Deserialize, Serialize};
+
+/// Mimics `0xcafe::transaction_context_test::TransactionContextStore`
+#[derive(Deserialize, Serialize)]
+struct TransactionContextStore {
+    sender: AccountAddress,
+    secondary_signers: Vec<AccountAddress>,
+    gas_payer: AccountAddress,
+    max_gas_amount: u64,
+    gas_unit_price: u64,
+    chain_id: u8,
+    account_address: AccountAddress,
+    module_name: String,
+    function_name: String,
+    type_arg_names: Vec<String>,
+    args: Vec<Vec<u8>>,
+}
+
+fn setup(enabled: Vec<FeatureFlag>, disabled: Vec<FeatureFlag>) -> (MoveHarness, Account) {
+    let mut h = MoveHarness::new_with_features(enabled, disabled);
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    assert_success!(h.publish_package(
+        &account,
+        &common::test_dir_path("transaction_context.data/pack"),
+    ));
+    (h, account)
+}
+
+fn read_store(h: &MoveHarness, addr: &AccountAddress) -> TransactionContextStore {
+    h.read_resource::<TransactionContextStore>(
+        addr,
+        parse_struct_tag("0xcafe::transaction_context_test::TransactionContextStore").unwrap(),
+    )
+    .unwrap()
+}
+
+fn entry_function(name: &str, ty_args: Vec<TypeTag>, args: Vec<Vec<u8>>) -> EntryFunction {
+    EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::from_hex_literal("0xcafe").unwrap(),
+            Identifier::new("transaction_context_test").unwrap(),
+        ),
+        Identifier::new(name).unwrap(),
+        ty_args,
+        args,
+    )
+}
+
+#[test]
+fn test_transaction_context_entry_function() {
+    let (mut h, _) = setup(vec![FeatureFlag::TRANSACTION_CONTEXT_EXTENSION], vec![]);
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+
+    let recipient = AccountAddress::from_hex_literal("0xb0b").unwrap();
+    let args = vec![
+        bcs::to_bytes(&42u64).unwrap(),
+        bcs::to_bytes(&recipient).unwrap(),
+    ];
+    let ty_arg = TypeTag::Struct(Box::new(
+        parse_struct_tag("0x1::aptos_coin::AptosCoin").unwrap(),
+    ));
+    let txn = TransactionBuilder::new(alice.clone())
+        .entry_function(entry_function(
+            "store_entry_function",
+            vec![ty_arg.clone()],
+            args.clone(),
+        ))
+        .sequence_number(h.sequence_number(alice.address()))
+        .max_gas_amount(1_000_000)
+        .gas_unit_price(100)
+        .sign();
+    assert_success!(h.run(txn));
+
+    let store = read_store(&h, alice.address());
+    assert_eq!(store.sender, *alice.address());
+    assert!(store.secondary_signers.is_empty());
+    assert_eq!(store.gas_payer, *alice.address());
+    assert_eq!(store.max_gas_amount, 1_000_000);
+    assert_eq!(store.gas_unit_price, 100);
+    assert_eq!(store.chain_id, 4);
+    assert_eq!(
+        store.account_address,
+        AccountAddress::from_hex_literal("0xcafe").unwrap()
+    );
+    assert_eq!(store.module_name, "transaction_context_test");
+    assert_eq!(store.function_name, "store_entry_function");
+    assert_eq!(store.type_arg_names, vec![ty_arg.to_canonical_string()]);
+    assert_eq!(store.args, args);
+}
+
+#[test]
+fn test_transaction_context_multi_agent_fee_payer() {
+    let (mut h, _) = setup(
+        vec![
+            FeatureFlag::TRANSACTION_CONTEXT_EXTENSION,
+            FeatureFlag::GAS_PAYER_ENABLED,
+        ],
+        vec![],
+    );
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+    let payer = h.new_account_at(AccountAddress::from_hex_literal("0xea51d").unwrap());
+
+    let txn = TransactionBuilder::new(alice.clone())
+        .secondary_signers(vec![bob.clone()])
+        .fee_payer(payer.clone())
+        .entry_function(entry_function("store_multi_agent", vec![], vec![]))
+        .sequence_number(h.sequence_number(alice.address()))
+        .max_gas_amount(1_000_000)
+        .gas_unit_price(1)
+        .sign_fee_payer();
+    assert_success!(h.run(txn));
+
+    let store = read_store(&h, alice.address());
+    assert_eq!(store.sender, *alice.address());
+    assert_eq!(store.secondary_signers, vec![*bob.address()]);
+    assert_eq!(store.gas_payer, *payer.address());
+    assert_eq!(store.function_name, "store_multi_agent");
+    assert!(store.type_arg_names.is_empty());
+    assert!(store.args.is_empty());
+}
+
+#[test]
+fn test_transaction_context_feature_disabled() {
+    let (mut h, _) = setup(vec![], vec![FeatureFlag::TRANSACTION_CONTEXT_EXTENSION]);
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+
+    let status = h.run_entry_function(
+        &alice,
+        str::parse("0xcafe::transaction_context_test::store_entry_function").unwrap(),
+        vec![TypeTag::U64],
+        vec![
+            bcs::to_bytes(&42u64).unwrap(),
+            bcs::to_bytes(alice.address()).unwrap(),
+        ],
+    );
+    // ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED
+    assert_abort!(status, 0x30002);
+}
@@ -29,3 +29,28 @@ pub fn upgrade_burn_percentage(harness: &mut MoveHarness, burn_percentage: u8) {
             MoveValue::U8(burn_percentage).simple_serialize().unwrap(),
         ]);
 }
+
+pub fn initialize_base_fee_market(
+    harness: &mut MoveHarness,
+    initial_base_fee: u64,
+    target_block_gas: u64,
+    max_change_denominator: u64,
+    minimum_base_fee: u64,
+) {
+    harness.executor.exec(
+        "transaction_fee",
+        "initialize_base_fee_market",
+        vec![],
+        vec![
+            MoveValue::Signer(AccountAddress::ONE)
+                .simple_serialize()
+                .unwrap(),
+            MoveValue::U64(initial_base_fee).simple_serialize().unwrap(),
+            MoveValue::U64(target_block_gas).simple_serialize().unwrap(),
+            MoveValue::U64(max_change_denominator)
+                .simple_serialize()
+                .unwrap(),
+            MoveValue::U64(minimum_base_fee).simple_serialize().unwrap(),
+        ],
+    );
+}
@@ -37,7 +37,8 @@ use aptos_types::{
     chain_id::ChainId,
     contract_event::ContractEvent,
     on_chain_config::{
-        Features, OnChainConfig, TimedFeatureOverride, TimedFeatures, ValidatorSet, Version,
+        BlockGasLimitType, Features, OnChainConfig, TimedFeatureOverride, TimedFeatures,
+        ValidatorSet, Version,
     },
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
@@ -453,7 +454,7 @@ impl FakeExecutor {
             txn_block,
             &self.data_store,
             usize::min(4, num_cpus::get()),
-            None,
+            BlockGasLimitType::NoLimit,
             None,
         )
     }
@@ -475,7 +476,11 @@ impl FakeExecutor {
             }
         }
 
-        let output = AptosVM::execute_block(txn_block.clone(), &self.data_store, None);
+        let output = AptosVM::execute_block(
+            txn_block.clone(),
+            &self.data_store,
+            BlockGasLimitType::NoLimit,
+        );
 
         let no_parallel = if let Some(no_parallel) = self.no_parallel_exec {
             no_parallel
@@ -729,7 +734,7 @@ impl FakeExecutor {
         let mut i = 0;
         let mut times = Vec::new();
         while i < iterations {
-            let mut session = vm.new_session(&remote_view, SessionId::void());
+            let mut session = vm.new_session(&remote_view, SessionId::void(), None);
 
             // load function name into cache to ensure cache is hot
             let _ = session.load_function(module, &Self::name(function_name), &type_params.clone());
@@ -798,7 +803,7 @@ impl FakeExecutor {
             )
             .unwrap();
             let remote_view = StorageAdapter::new(&self.data_store);
-            let mut session = vm.new_session(&remote_view, SessionId::void());
+            let mut session = vm.new_session(&remote_view, SessionId::void(), None);
 
             let fun_name = Self::name(function_name);
             let should_error = fun_name.clone().into_string().ends_with(POSTFIX);
@@ -869,7 +874,7 @@ impl FakeExecutor {
             )
             .unwrap();
             let remote_view = StorageAdapter::new(&self.data_store);
-            let mut session = vm.new_session(&remote_view, SessionId::void());
+            let mut session = vm.new_session(&remote_view, SessionId::void(), None);
             session
                 .execute_function_bypass_visibility(
                     &Self::module(module_name),
@@ -920,7 +925,7 @@ impl FakeExecutor {
         )
         .unwrap();
         let remote_view = StorageAdapter::new(&self.data_store);
-        let mut session = vm.new_session(&remote_view, SessionId::void());
+        let mut session = vm.new_session(&remote_view, SessionId::void(), None);
         session
             .execute_function_bypass_visibility(
                 &Self::module(module_name),
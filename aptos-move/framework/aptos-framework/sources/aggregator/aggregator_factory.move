@@ -8,8 +8,10 @@ module aptos_framework::aggregator_factory {
 
     use aptos_framework::system_addresses;
     use aptos_framework::aggregator::Aggregator;
+    use aptos_framework::aggregator_v2;
     use aptos_std::table::{Self, Table};
 
+    friend aptos_framework::fungible_asset;
     friend aptos_framework::genesis;
     friend aptos_framework::optional_aggregator;
 
@@ -51,11 +53,76 @@ module aptos_framework::aggregator_factory {
         create_aggregator_internal(limit)
     }
 
+    /// Creates a new version 2 aggregator instance which overflows on exceeding `max_value`.
+    /// `IntElement` can be either `u64` or `u128`.
+    public(friend) fun create_aggregator_v2_internal<IntElement: copy + drop>(
+        max_value: IntElement
+    ): aggregator_v2::Aggregator<IntElement> acquires AggregatorFactory {
+        assert!(
+            exists<AggregatorFactory>(@aptos_framework),
+            error::not_found(EAGGREGATOR_FACTORY_NOT_FOUND)
+        );
+
+        let aggregator_factory = borrow_global_mut<AggregatorFactory>(@aptos_framework);
+        new_aggregator_v2(aggregator_factory, max_value)
+    }
+
     /// Returns a new aggregator.
     native fun new_aggregator(aggregator_factory: &mut AggregatorFactory, limit: u128): Aggregator;
 
+    /// Returns a new version 2 aggregator.
+    native fun new_aggregator_v2<IntElement: copy + drop>(
+        aggregator_factory: &mut AggregatorFactory,
+        max_value: IntElement
+    ): aggregator_v2::Aggregator<IntElement>;
+
     #[test_only]
     public fun initialize_aggregator_factory_for_test(aptos_framework: &signer) {
         initialize_aggregator_factory(aptos_framework);
     }
+
+    #[test(aptos_framework = @aptos_framework)]
+    fun test_aggregator_v2(aptos_framework: &signer) acquires AggregatorFactory {
+        initialize_aggregator_factory(aptos_framework);
+        let aggregator = create_aggregator_v2_internal<u64>(10);
+        assert!(aggregator_v2::max_value(&aggregator) == 10, 0);
+
+        assert!(aggregator_v2::try_add(&mut aggregator, 5), 0);
+        assert!(aggregator_v2::try_add(&mut aggregator, 5), 0);
+        assert!(!aggregator_v2::try_add(&mut aggregator, 1), 0);
+        assert!(aggregator_v2::read(&aggregator) == 10, 0);
+
+        assert!(aggregator_v2::try_sub(&mut aggregator, 4), 0);
+        assert!(!aggregator_v2::try_sub(&mut aggregator, 7), 0);
+        assert!(aggregator_v2::read(&aggregator) == 6, 0);
+
+        aggregator_v2::destroy(aggregator);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x020001, location = aptos_framework::aggregator_v2)]
+    fun test_aggregator_v2_overflow(aptos_framework: &signer) acquires AggregatorFactory {
+        initialize_aggregator_factory(aptos_framework);
+        let aggregator = create_aggregator_v2_internal<u128>(10);
+        aggregator_v2::add(&mut aggregator, 11);
+        aggregator_v2::destroy(aggregator);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x020002, location = aptos_framework::aggregator_v2)]
+    fun test_aggregator_v2_underflow(aptos_framework: &signer) acquires AggregatorFactory {
+        initialize_aggregator_factory(aptos_framework);
+        let aggregator = create_aggregator_v2_internal<u64>(10);
+        aggregator_v2::add(&mut aggregator, 5);
+        aggregator_v2::sub(&mut aggregator, 6);
+        aggregator_v2::destroy(aggregator);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x030007, location = Self)]
+    fun test_aggregator_v2_invalid_type(aptos_framework: &signer) acquires AggregatorFactory {
+        initialize_aggregator_factory(aptos_framework);
+        let aggregator = create_aggregator_v2_internal<u8>(10);
+        aggregator_v2::destroy(aggregator);
+    }
 }
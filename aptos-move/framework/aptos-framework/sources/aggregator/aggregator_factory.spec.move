@@ -39,6 +39,22 @@ spec aptos_framework::aggregator_factory {
         aborts_if !exists<AggregatorFactory>(@aptos_framework);
     }
 
+    /// The native aborts if `IntElement` is neither `u64` nor `u128`.
+    spec new_aggregator_v2<IntElement: copy + drop>(
+        aggregator_factory: &mut AggregatorFactory,
+        max_value: IntElement
+    ): aggregator_v2::Aggregator<IntElement> {
+        pragma opaque;
+        pragma aborts_if_is_partial;
+    }
+
+    spec create_aggregator_v2_internal<IntElement: copy + drop>(
+        max_value: IntElement
+    ): aggregator_v2::Aggregator<IntElement> {
+        pragma aborts_if_is_partial;
+        include CreateAggregatorInternalAbortsIf;
+    }
+
     spec native fun spec_new_aggregator(limit: u128): Aggregator;
 
 }
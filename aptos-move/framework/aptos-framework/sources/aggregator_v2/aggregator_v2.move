@@ -1,19 +1,77 @@
 /// This module provides an interface for aggregators (version 2).
-/// Only skeleton - for AggregagtorSnapshot - is provided at this time,
-/// to allow transition of usages.
+/// Aggregators are integer counters which support concurrent additions and
+/// subtractions, bounded by `max_value`. Aggregators can only be created by
+/// the framework, via `aggregator_factory`.
 module aptos_framework::aggregator_v2 {
+    use std::error;
     use std::string::String;
 
+    /// The value of aggregator overflows. Raised by unconditional add() call
+    const EAGGREGATOR_OVERFLOW: u64 = 1;
+
+    /// The value of aggregator underflows (goes below zero). Raised by unconditional sub() call
+    const EAGGREGATOR_UNDERFLOW: u64 = 2;
+
     /// The generic type supplied to the aggregator snapshot is not supported.
     const EUNSUPPORTED_AGGREGATOR_SNAPSHOT_TYPE: u64 = 5;
 
     /// The aggregator snapshots feature flag is not enabled.
     const EAGGREGATOR_SNAPSHOTS_NOT_ENABLED: u64 = 6;
 
+    /// The generic type supplied to the aggregator is not supported.
+    const EUNSUPPORTED_AGGREGATOR_TYPE: u64 = 7;
+
+    /// Represents an integer which supports concurrent additions and subtractions.
+    /// `IntElement` can be `u64` or `u128`. The value is stored outside of this
+    /// struct, identified by `handle` and `key`, so that changes to it are
+    /// tracked as deltas and do not conflict when executed in parallel.
+    struct Aggregator<IntElement> has store {
+        handle: address,
+        key: address,
+        max_value: IntElement,
+    }
+
     struct AggregatorSnapshot<Element> has store, drop {
         value: Element,
     }
 
+    /// Returns `max_value` exceeding which aggregator overflows.
+    public fun max_value<IntElement: copy + drop>(aggregator: &Aggregator<IntElement>): IntElement {
+        aggregator.max_value
+    }
+
+    /// Adds `value` to aggregator.
+    /// If addition would exceed the max_value, `false` is returned, and aggregator value is left unchanged.
+    /// Note that the check does not read the value of the aggregator, so concurrent
+    /// additions to the same aggregator do not conflict with each other.
+    public native fun try_add<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement): bool;
+
+    /// Adds `value` to aggregator, unconditionally.
+    /// If addition would exceed the max_value, EAGGREGATOR_OVERFLOW exception will be thrown.
+    public fun add<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement) {
+        assert!(try_add(aggregator, value), error::out_of_range(EAGGREGATOR_OVERFLOW));
+    }
+
+    /// Subtracts `value` from aggregator.
+    /// If subtraction would result in a negative value, `false` is returned, and aggregator value is left unchanged.
+    /// Note that the check reads the value of the aggregator, so it conflicts with
+    /// concurrent modifications of the same aggregator.
+    public native fun try_sub<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement): bool;
+
+    /// Subtracts `value` from aggregator, unconditionally.
+    /// If subtraction would result in a negative value, EAGGREGATOR_UNDERFLOW exception will be thrown.
+    public fun sub<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement) {
+        assert!(try_sub(aggregator, value), error::out_of_range(EAGGREGATOR_UNDERFLOW));
+    }
+
+    /// Returns a value stored in this aggregator.
+    /// Note: This operation conflicts with concurrent modifications of the same aggregator,
+    /// and should be avoided on the hot path.
+    public native fun read<IntElement>(aggregator: &Aggregator<IntElement>): IntElement;
+
+    /// Destroys an aggregator and removes its value from storage.
+    public native fun destroy<IntElement>(aggregator: Aggregator<IntElement>);
+
     public native fun create_snapshot<Element: copy + drop>(value: Element): AggregatorSnapshot<Element>;
 
     public native fun copy_snapshot<Element: copy + drop>(snapshot: &AggregatorSnapshot<Element>): AggregatorSnapshot<Element>;
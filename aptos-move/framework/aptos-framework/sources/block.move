@@ -7,6 +7,7 @@ module aptos_framework::block {
 
     use aptos_framework::account;
     use aptos_framework::event::{Self, EventHandle};
+    use aptos_framework::randomness;
     use aptos_framework::reconfiguration;
     use aptos_framework::stake;
     use aptos_framework::state_storage;
@@ -108,8 +109,65 @@ module aptos_framework::block {
         previous_block_votes_bitvec: vector<u8>,
         timestamp: u64
     ) acquires BlockResource {
+        let epoch_interval = block_prologue_common(
+            &vm,
+            hash,
+            epoch,
+            round,
+            proposer,
+            failed_proposer_indices,
+            previous_block_votes_bitvec,
+            timestamp
+        );
+        randomness::on_new_block(&vm, epoch, round, option::none());
+        if (timestamp - reconfiguration::last_reconfiguration_time() >= epoch_interval) {
+            reconfiguration::reconfigure();
+        };
+    }
+
+    /// `block_prologue()` for blocks with a randomness seed, which the runtime runs for
+    /// `BlockMetadataExt` transactions.
+    fun block_prologue_ext(
+        vm: signer,
+        hash: address,
+        epoch: u64,
+        round: u64,
+        proposer: address,
+        failed_proposer_indices: vector<u64>,
+        previous_block_votes_bitvec: vector<u8>,
+        timestamp: u64,
+        randomness_seed: vector<u8>,
+    ) acquires BlockResource {
+        let epoch_interval = block_prologue_common(
+            &vm,
+            hash,
+            epoch,
+            round,
+            proposer,
+            failed_proposer_indices,
+            previous_block_votes_bitvec,
+            timestamp
+        );
+        randomness::on_new_block(&vm, epoch, round, option::some(randomness_seed));
+        if (timestamp - reconfiguration::last_reconfiguration_time() >= epoch_interval) {
+            reconfiguration::reconfigure();
+        };
+    }
+
+    /// Updates the block resource and the per-block state of other modules, and returns the epoch
+    /// interval.
+    fun block_prologue_common(
+        vm: &signer,
+        hash: address,
+        epoch: u64,
+        round: u64,
+        proposer: address,
+        failed_proposer_indices: vector<u64>,
+        previous_block_votes_bitvec: vector<u8>,
+        timestamp: u64
+    ): u64 acquires BlockResource {
         // Operational constraint: can only be invoked by the VM.
-        system_addresses::assert_vm(&vm);
+        system_addresses::assert_vm(vm);
 
         // Blocks can only be produced by a valid proposer or by the VM itself for Nil blocks (no user txs).
         assert!(
@@ -135,7 +193,7 @@ module aptos_framework::block {
             failed_proposer_indices,
             time_microseconds: timestamp,
         };
-        emit_new_block_event(&vm, &mut block_metadata_ref.new_block_events, new_block_event);
+        emit_new_block_event(vm, &mut block_metadata_ref.new_block_events, new_block_event);
 
         if (features::collect_and_distribute_gas_fees()) {
             // Assign the fees collected from the previous block to the previous block proposer.
@@ -146,14 +204,15 @@ module aptos_framework::block {
             transaction_fee::register_proposer_for_fee_collection(proposer);
         };
 
+        // Adjust the base fee to how full the previous block was.
+        transaction_fee::update_base_fee();
+
         // Performance scores have to be updated before the epoch transition as the transaction that triggers the
         // transition is the last block in the previous epoch.
         stake::update_performance_statistics(proposer_index, failed_proposer_indices);
         state_storage::on_new_block(reconfiguration::current_epoch());
 
-        if (timestamp - reconfiguration::last_reconfiguration_time() >= block_metadata_ref.epoch_interval) {
-            reconfiguration::reconfigure();
-        };
+        block_metadata_ref.epoch_interval
     }
 
     #[view]
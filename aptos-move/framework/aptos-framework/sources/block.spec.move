@@ -10,13 +10,29 @@ spec aptos_framework::block {
     }
 
     spec block_prologue {
+        pragma verify_duration_estimate = 120; // TODO: set because of timeout (property proved)
+
+        include BlockRequirement;
+        aborts_if false;
+    }
+
+    spec block_prologue_ext {
+        pragma verify_duration_estimate = 120;
+
+        include BlockRequirement;
+        aborts_if false;
+    }
+
+    spec schema BlockRequirement {
         use aptos_framework::chain_status;
         use aptos_framework::coin::CoinInfo;
         use aptos_framework::aptos_coin::AptosCoin;
         use aptos_framework::transaction_fee;
         use aptos_framework::staking_config;
 
-        pragma verify_duration_estimate = 120; // TODO: set because of timeout (property proved)
+        vm: signer;
+        proposer: address;
+        timestamp: u64;
 
         requires chain_status::is_operating();
         requires system_addresses::is_vm(vm);
@@ -28,8 +44,6 @@ spec aptos_framework::block {
         requires exists<CoinInfo<AptosCoin>>(@aptos_framework);
         include transaction_fee::RequiresCollectedFeesPerValueLeqBlockAptosSupply;
         include staking_config::StakingRewardsConfigRequirement;
-
-        aborts_if false;
     }
 
     spec emit_genesis_block_event {
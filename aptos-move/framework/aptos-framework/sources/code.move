@@ -7,6 +7,7 @@ module aptos_framework::code {
     use std::features;
 
     use aptos_framework::util;
+    use aptos_framework::object::{Self, Object};
     use aptos_framework::system_addresses;
     use aptos_std::copyable_any::Any;
     use std::option::Option;
@@ -90,6 +91,12 @@ module aptos_framework::code {
     /// Creating a package with incompatible upgrade policy is disabled.
     const EINCOMPATIBLE_POLICY_DISABLED: u64 = 0x8;
 
+    /// No package registry exists at the address of the code object.
+    const ECODE_OBJECT_DOES_NOT_EXIST: u64 = 0x9;
+
+    /// The signer does not own the code object.
+    const ENOT_PACKAGE_OWNER: u64 = 0xA;
+
     /// Whether unconditional code upgrade with no compatibility check is allowed. This
     /// publication mode should only be used for modules which aren't shared with user others.
     /// The developer is responsible for not breaking memory layout of any resources he already
@@ -192,6 +199,23 @@ module aptos_framework::code {
         publish_package(owner, util::from_bytes<PackageMetadata>(metadata_serialized), code)
     }
 
+    /// Make all packages published at the address of `code_object` immutable. Only the owner of the object can
+    /// do this.
+    public fun freeze_code_object(publisher: &signer, code_object: Object<PackageRegistry>) acquires PackageRegistry {
+        let code_object_addr = object::object_address(&code_object);
+        assert!(exists<PackageRegistry>(code_object_addr), error::not_found(ECODE_OBJECT_DOES_NOT_EXIST));
+        assert!(
+            object::is_owner(code_object, signer::address_of(publisher)),
+            error::permission_denied(ENOT_PACKAGE_OWNER)
+        );
+
+        let registry = borrow_global_mut<PackageRegistry>(code_object_addr);
+        vector::for_each_mut(&mut registry.packages, |pack| {
+            let pack: &mut PackageMetadata = pack;
+            pack.upgrade_policy = upgrade_policy_immutable();
+        });
+    }
+
     // Helpers
     // -------
 
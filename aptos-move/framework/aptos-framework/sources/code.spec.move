@@ -35,6 +35,11 @@ spec aptos_framework::code {
         pragma verify = false;
     }
 
+    spec freeze_code_object {
+        // TODO: Can't verify 'vector::for_each_mut' loop.
+        pragma verify = false;
+    }
+
     spec check_upgradability(old_pack: &PackageMetadata, new_pack: &PackageMetadata, new_modules: &vector<String>) {
         // TODO: Can't verify 'vector::enumerate' loop.
         pragma aborts_if_is_partial;
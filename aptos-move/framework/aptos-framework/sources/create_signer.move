@@ -11,6 +11,7 @@
 module aptos_framework::create_signer {
     friend aptos_framework::account;
     friend aptos_framework::aptos_account;
+    friend aptos_framework::fungible_asset;
     friend aptos_framework::genesis;
     friend aptos_framework::multisig_account;
     friend aptos_framework::object;
@@ -0,0 +1,160 @@
+/// This module allows issuers of fungible assets to customize the behavior of their asset, by registering Move
+/// functions that are dispatched to on withdrawals, deposits and balance queries of the asset. This can be used to
+/// implement e.g. transfer fees, allow lists or assets whose balance grows over time.
+///
+/// The functions must have the signatures of the native functions in this module, without the last parameter:
+/// ```
+/// public fun withdraw<T: key>(store: Object<T>, amount: u64, transfer_ref: &TransferRef): FungibleAsset;
+/// public fun deposit<T: key>(store: Object<T>, fa: FungibleAsset, transfer_ref: &TransferRef);
+/// public fun derived_balance<T: key>(store: Object<T>): u64;
+/// ```
+/// The `TransferRef` passed to the functions is kept by this module, and allows them to move the asset via
+/// `fungible_asset::withdraw_with_ref` and `fungible_asset::deposit_with_ref`.
+///
+/// Once functions are registered, withdrawals from and deposits into stores of the asset must go through this
+/// module, and `fungible_asset::withdraw` and `fungible_asset::deposit` abort. Minting and the functions using a
+/// `TransferRef` or a `BurnRef` do not dispatch. The VM does not allow dispatching into a module which is already
+/// active on the call stack, so the registered functions cannot withdraw from or deposit into stores of their own
+/// asset through this module.
+module aptos_framework::dispatchable_fungible_asset {
+    use aptos_framework::fungible_asset::{Self, FungibleAsset, TransferRef};
+    use aptos_framework::function_info::FunctionInfo;
+    use aptos_framework::object::{Self, ConstructorRef, Object};
+
+    use std::error;
+    use std::option::{Self, Option};
+
+    /// The registered withdraw function withdrew less than the requested amount from the store.
+    const EAMOUNT_MISMATCH: u64 = 1;
+    /// The registered withdraw function returned a different fungible asset than the one of the store.
+    const EFUNGIBLE_ASSET_MISMATCH: u64 = 2;
+
+    #[resource_group_member(group = aptos_framework::object::ObjectGroup)]
+    /// The transfer ref passed to the registered functions, kept at the metadata object.
+    struct TransferRefStore has key {
+        transfer_ref: TransferRef
+    }
+
+    /// Register the functions to dispatch to on withdrawals, deposits and balance queries of the fungible asset
+    /// created with `constructor_ref`. Any of the functions may be left unset.
+    public fun register_dispatch_functions(
+        constructor_ref: &ConstructorRef,
+        withdraw_function: Option<FunctionInfo>,
+        deposit_function: Option<FunctionInfo>,
+        derived_balance_function: Option<FunctionInfo>,
+    ) {
+        fungible_asset::register_dispatch_functions(
+            constructor_ref,
+            withdraw_function,
+            deposit_function,
+            derived_balance_function,
+        );
+        let store_signer = &object::generate_signer(constructor_ref);
+        move_to(store_signer, TransferRefStore {
+            transfer_ref: fungible_asset::generate_transfer_ref(constructor_ref),
+        });
+    }
+
+    /// Withdraw `amount` of the fungible asset from `store` by the owner, dispatching to the withdraw function of
+    /// the asset if any.
+    public fun withdraw<T: key>(
+        owner: &signer,
+        store: Object<T>,
+        amount: u64,
+    ): FungibleAsset acquires TransferRefStore {
+        fungible_asset::withdraw_sanity_check(owner, store, false);
+        let func_opt = fungible_asset::withdraw_dispatch_function(store);
+        if (option::is_some(&func_opt)) {
+            let start_balance = fungible_asset::balance(store);
+            let func = option::extract(&mut func_opt);
+            let fa = dispatchable_withdraw(store, amount, borrow_transfer_ref(store), func);
+            let end_balance = fungible_asset::balance(store);
+            assert!(
+                fungible_asset::asset_metadata(&fa) == fungible_asset::store_metadata(store),
+                error::invalid_state(EFUNGIBLE_ASSET_MISMATCH)
+            );
+            assert!(amount <= start_balance - end_balance, error::aborted(EAMOUNT_MISMATCH));
+            fa
+        } else {
+            fungible_asset::withdraw(owner, store, amount)
+        }
+    }
+
+    /// Deposit `fa` into `store`, dispatching to the deposit function of the asset if any.
+    public fun deposit<T: key>(store: Object<T>, fa: FungibleAsset) acquires TransferRefStore {
+        fungible_asset::deposit_sanity_check(store, false);
+        let func_opt = fungible_asset::deposit_dispatch_function(store);
+        if (option::is_some(&func_opt)) {
+            let func = option::extract(&mut func_opt);
+            dispatchable_deposit(store, fa, borrow_transfer_ref(store), func);
+        } else {
+            fungible_asset::deposit(store, fa);
+        }
+    }
+
+    /// Transfer `amount` of the fungible asset from `from`, which should be owned by `sender`, to `to`,
+    /// dispatching to the withdraw and deposit functions of the asset if any.
+    public entry fun transfer<T: key>(
+        sender: &signer,
+        from: Object<T>,
+        to: Object<T>,
+        amount: u64,
+    ) acquires TransferRefStore {
+        let fa = withdraw(sender, from, amount);
+        deposit(to, fa);
+    }
+
+    #[view]
+    /// Get the balance of `store`, as computed by the derived balance function of the asset if any.
+    public fun derived_balance<T: key>(store: Object<T>): u64 {
+        let func_opt = fungible_asset::derived_balance_dispatch_function(store);
+        if (option::is_some(&func_opt)) {
+            let func = option::extract(&mut func_opt);
+            dispatchable_derived_balance(store, func)
+        } else {
+            fungible_asset::balance(store)
+        }
+    }
+
+    inline fun borrow_transfer_ref<T: key>(store: Object<T>): &TransferRef acquires TransferRefStore {
+        let metadata_addr = object::object_address(&fungible_asset::store_metadata(store));
+        &borrow_global<TransferRefStore>(metadata_addr).transfer_ref
+    }
+
+    native fun dispatchable_withdraw<T: key>(
+        store: Object<T>,
+        amount: u64,
+        transfer_ref: &TransferRef,
+        function: FunctionInfo,
+    ): FungibleAsset;
+
+    native fun dispatchable_deposit<T: key>(
+        store: Object<T>,
+        fa: FungibleAsset,
+        transfer_ref: &TransferRef,
+        function: FunctionInfo,
+    );
+
+    native fun dispatchable_derived_balance<T: key>(
+        store: Object<T>,
+        function: FunctionInfo,
+    ): u64;
+
+    #[test_only]
+    use std::string;
+    #[test_only]
+    use aptos_framework::function_info;
+
+    #[test(creator = @0xcafe)]
+    #[expected_failure(abort_code = 0x30018, location = aptos_framework::fungible_asset)]
+    fun test_register_not_enabled(creator: &signer) {
+        let (constructor_ref, _) = fungible_asset::create_test_token(creator);
+        fungible_asset::init_test_metadata(&constructor_ref);
+        let derived_balance = function_info::new_function_info(
+            @aptos_framework,
+            string::utf8(b"fungible_asset"),
+            string::utf8(b"balance"),
+        );
+        register_dispatch_functions(&constructor_ref, option::none(), option::none(), option::some(derived_balance));
+    }
+}
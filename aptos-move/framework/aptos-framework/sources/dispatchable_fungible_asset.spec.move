@@ -0,0 +1,18 @@
+spec aptos_framework::dispatchable_fungible_asset {
+    spec module {
+        // TODO: verification disabled until this module is specified.
+        pragma verify = false;
+    }
+
+    spec dispatchable_withdraw {
+        pragma opaque;
+    }
+
+    spec dispatchable_deposit {
+        pragma opaque;
+    }
+
+    spec dispatchable_derived_balance {
+        pragma opaque;
+    }
+}
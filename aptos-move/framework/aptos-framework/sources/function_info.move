@@ -0,0 +1,89 @@
+/// The `function_info` module defines the `FunctionInfo` type, which describes a Move function by the
+/// address and name of its module and its own name, and acts like a function pointer. The framework can
+/// dispatch to such functions at runtime from dedicated natives, e.g. in `dispatchable_fungible_asset`.
+///
+/// Only public functions may be dispatched to, and the VM refuses to dispatch into a module that is already
+/// active on the call stack, so dispatching cannot be used to re-enter a module in the middle of its execution.
+module aptos_framework::function_info {
+    use std::error;
+    use std::string::{Self, String};
+
+    friend aptos_framework::fungible_asset;
+
+    /// The module name or the function name is not a valid Move identifier.
+    const EINVALID_IDENTIFIER: u64 = 1;
+
+    /// A function which can be dispatched to at runtime.
+    struct FunctionInfo has copy, drop, store {
+        module_address: address,
+        module_name: String,
+        function_name: String,
+    }
+
+    /// Creates a new function info describing the function `function_name` in the module
+    /// `module_address::module_name`. The function does not need to exist yet.
+    public fun new_function_info(
+        module_address: address,
+        module_name: String,
+        function_name: String,
+    ): FunctionInfo {
+        assert!(is_identifier(string::bytes(&module_name)), error::invalid_argument(EINVALID_IDENTIFIER));
+        assert!(is_identifier(string::bytes(&function_name)), error::invalid_argument(EINVALID_IDENTIFIER));
+        FunctionInfo {
+            module_address,
+            module_name,
+            function_name,
+        }
+    }
+
+    /// Returns the address of the module of the function.
+    public fun module_address(function_info: &FunctionInfo): address {
+        function_info.module_address
+    }
+
+    /// Returns the name of the module of the function.
+    public fun module_name(function_info: &FunctionInfo): String {
+        function_info.module_name
+    }
+
+    /// Returns the name of the function.
+    public fun function_name(function_info: &FunctionInfo): String {
+        function_info.function_name
+    }
+
+    /// Returns whether `dispatch_target` can be dispatched to from the native function `framework_function`:
+    /// the target must be a public Move function, with the signature of the native function without its
+    /// last parameter, which is the `FunctionInfo` of the target. Functions which do not exist are not
+    /// compatible.
+    ///
+    /// This loads the modules of both functions.
+    public(friend) fun check_dispatch_type_compatibility(
+        framework_function: &FunctionInfo,
+        dispatch_target: &FunctionInfo,
+    ): bool {
+        check_dispatch_type_compatibility_impl(*framework_function, *dispatch_target)
+    }
+
+    native fun check_dispatch_type_compatibility_impl(lhs: FunctionInfo, rhs: FunctionInfo): bool;
+    native fun is_identifier(s: &vector<u8>): bool;
+
+    #[test]
+    fun test_new_function_info() {
+        let function_info = new_function_info(@0xcafe, string::utf8(b"hooks"), string::utf8(b"withdraw"));
+        assert!(module_address(&function_info) == @0xcafe, 0);
+        assert!(module_name(&function_info) == string::utf8(b"hooks"), 1);
+        assert!(function_name(&function_info) == string::utf8(b"withdraw"), 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001, location = Self)]
+    fun test_invalid_module_name() {
+        new_function_info(@0xcafe, string::utf8(b"hooks::inner"), string::utf8(b"withdraw"));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001, location = Self)]
+    fun test_invalid_function_name() {
+        new_function_info(@0xcafe, string::utf8(b"hooks"), string::utf8(b"1withdraw"));
+    }
+}
@@ -0,0 +1,26 @@
+spec aptos_framework::function_info {
+    spec module {
+        pragma verify = true;
+    }
+
+    spec new_function_info(
+        module_address: address,
+        module_name: String,
+        function_name: String,
+    ): FunctionInfo {
+        aborts_if !spec_is_identifier(string::bytes(module_name));
+        aborts_if !spec_is_identifier(string::bytes(function_name));
+        ensures result == FunctionInfo { module_address, module_name, function_name };
+    }
+
+    spec check_dispatch_type_compatibility_impl(lhs: FunctionInfo, rhs: FunctionInfo): bool {
+        pragma opaque;
+    }
+
+    spec is_identifier(s: &vector<u8>): bool {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_is_identifier(s);
+    }
+    spec fun spec_is_identifier(s: vector<u8>): bool;
+}
@@ -1,15 +1,22 @@
 /// This defines the fungible asset module that can issue fungible asset of any `Metadata` object. The
 /// metadata object can be any object that equipped with `Metadata` resource.
 module aptos_framework::fungible_asset {
+    use aptos_framework::aggregator_factory;
+    use aptos_framework::aggregator_v2::{Self, Aggregator};
+    use aptos_framework::create_signer::create_signer;
     use aptos_framework::event;
+    use aptos_framework::function_info::{Self, FunctionInfo};
     use aptos_framework::object::{Self, Object, ConstructorRef, DeleteRef};
     use std::string;
 
     use std::error;
+    use std::features;
     use std::option::{Self, Option};
     use std::signer;
     use std::string::String;
 
+    friend aptos_framework::dispatchable_fungible_asset;
+
     /// Amount cannot be zero.
     const EAMOUNT_CANNOT_BE_ZERO: u64 = 1;
     /// The transfer ref and the fungible asset do not match.
@@ -52,6 +59,25 @@ module aptos_framework::fungible_asset {
     const ESUPPLY_UNDERFLOW: u64 = 20;
     /// Supply resource is not found for a metadata object.
     const ESUPPLY_NOT_FOUND: u64 = 21;
+    /// Concurrent fungible balances are not enabled.
+    const ECONCURRENT_BALANCE_NOT_ENABLED: u64 = 22;
+    /// The store already keeps its balance in an aggregator.
+    const ECONCURRENT_BALANCE_ALREADY_ENABLED: u64 = 23;
+    /// Dispatchable fungible assets are not enabled.
+    const EDISPATCHABLE_FUNGIBLE_ASSET_NOT_ENABLED: u64 = 24;
+    /// The withdraw function does not match the signature of `dispatchable_fungible_asset::dispatchable_withdraw`.
+    const EWITHDRAW_FUNCTION_SIGNATURE_MISMATCH: u64 = 25;
+    /// The deposit function does not match the signature of `dispatchable_fungible_asset::dispatchable_deposit`.
+    const EDEPOSIT_FUNCTION_SIGNATURE_MISMATCH: u64 = 26;
+    /// The derived balance function does not match the signature of
+    /// `dispatchable_fungible_asset::dispatchable_derived_balance`.
+    const EDERIVED_BALANCE_FUNCTION_SIGNATURE_MISMATCH: u64 = 27;
+    /// The fungible asset has dispatch functions, so it has to be moved through `dispatchable_fungible_asset`.
+    const EINVALID_DISPATCHABLE_OPERATIONS: u64 = 28;
+    /// Dispatch functions have already been registered for the fungible asset.
+    const EDISPATCH_FUNCTIONS_ALREADY_REGISTERED: u64 = 29;
+    /// The object is not the metadata object of a fungible asset.
+    const EFUNGIBLE_METADATA_NOT_FOUND: u64 = 30;
 
     //
     // Constants
@@ -102,6 +128,15 @@ module aptos_framework::fungible_asset {
         frozen: bool,
     }
 
+    #[resource_group_member(group = aptos_framework::object::ObjectGroup)]
+    /// The balance of a store that has been upgraded via `upgrade_store_to_concurrent`. If present, the `balance`
+    /// field of `FungibleStore` is always zero and the actual balance is kept in an aggregator, so that deposits
+    /// into the same store do not conflict with each other. The aggregator is bounded by the maximum possible
+    /// supply rather than by `u64`, so that deltas applied by concurrent deposits can never overflow.
+    struct ConcurrentFungibleBalance has key {
+        balance: Aggregator<u128>,
+    }
+
     #[resource_group_member(group = aptos_framework::object::ObjectGroup)]
     struct FungibleAssetEvents has key {
         deposit_events: event::EventHandle<DepositEvent>,
@@ -109,6 +144,15 @@ module aptos_framework::fungible_asset {
         frozen_events: event::EventHandle<FrozenEvent>,
     }
 
+    #[resource_group_member(group = aptos_framework::object::ObjectGroup)]
+    /// The functions registered by the issuer of a fungible asset, which are dispatched to by
+    /// `dispatchable_fungible_asset` on withdrawals, deposits and balance queries of the asset.
+    struct DispatchFunctionStore has key {
+        withdraw_function: Option<FunctionInfo>,
+        deposit_function: Option<FunctionInfo>,
+        derived_balance_function: Option<FunctionInfo>,
+    }
+
     /// FungibleAsset can be passed into function for type safety and to guarantee a specific amount.
     /// FungibleAsset is ephemeral and cannot be stored directly. It must be deposited back into a store.
     struct FungibleAsset {
@@ -147,6 +191,20 @@ module aptos_framework::fungible_asset {
         frozen: bool,
     }
 
+    #[event]
+    /// Emitted when fungible assets are deposited into a store with a concurrent balance.
+    struct Deposit has drop, store {
+        store: address,
+        amount: u64,
+    }
+
+    #[event]
+    /// Emitted when fungible assets are withdrawn from a store with a concurrent balance.
+    struct Withdraw has drop, store {
+        store: address,
+        amount: u64,
+    }
+
     /// Make an existing object fungible by adding the Metadata resource.
     /// This returns the capabilities to mint, burn, and transfer.
     /// maximum_supply defines the behavior of maximum supply when monitoring:
@@ -206,6 +264,56 @@ module aptos_framework::fungible_asset {
         TransferRef { metadata }
     }
 
+    /// Register the functions to dispatch to on withdrawals, deposits and balance queries of the fungible asset
+    /// created with `constructor_ref`. Each function must have the signature of the respective native in
+    /// `dispatchable_fungible_asset` without its last parameter.
+    public(friend) fun register_dispatch_functions(
+        constructor_ref: &ConstructorRef,
+        withdraw_function: Option<FunctionInfo>,
+        deposit_function: Option<FunctionInfo>,
+        derived_balance_function: Option<FunctionInfo>,
+    ) {
+        assert!(
+            features::dispatchable_fungible_asset_enabled(),
+            error::invalid_state(EDISPATCHABLE_FUNGIBLE_ASSET_NOT_ENABLED)
+        );
+        check_dispatch_function(&withdraw_function, b"dispatchable_withdraw", EWITHDRAW_FUNCTION_SIGNATURE_MISMATCH);
+        check_dispatch_function(&deposit_function, b"dispatchable_deposit", EDEPOSIT_FUNCTION_SIGNATURE_MISMATCH);
+        check_dispatch_function(
+            &derived_balance_function,
+            b"dispatchable_derived_balance",
+            EDERIVED_BALANCE_FUNCTION_SIGNATURE_MISMATCH
+        );
+
+        let metadata_addr = object::address_from_constructor_ref(constructor_ref);
+        assert!(exists<Metadata>(metadata_addr), error::not_found(EFUNGIBLE_METADATA_NOT_FOUND));
+        assert!(
+            !exists<DispatchFunctionStore>(metadata_addr),
+            error::already_exists(EDISPATCH_FUNCTIONS_ALREADY_REGISTERED)
+        );
+        move_to(&object::generate_signer(constructor_ref), DispatchFunctionStore {
+            withdraw_function,
+            deposit_function,
+            derived_balance_function,
+        });
+    }
+
+    /// Abort with `error_code` unless `function`, if set, can be dispatched to from the native `dispatcher` of
+    /// `dispatchable_fungible_asset`.
+    fun check_dispatch_function(function: &Option<FunctionInfo>, dispatcher: vector<u8>, error_code: u64) {
+        if (option::is_some(function)) {
+            let dispatcher = function_info::new_function_info(
+                @aptos_framework,
+                string::utf8(b"dispatchable_fungible_asset"),
+                string::utf8(dispatcher),
+            );
+            assert!(
+                function_info::check_dispatch_type_compatibility(&dispatcher, option::borrow(function)),
+                error::invalid_argument(error_code)
+            );
+        }
+    }
+
     #[view]
     /// Get the current supply from the `metadata` object.
     public fun supply<T: key>(metadata: Object<T>): Option<u128> acquires Supply {
@@ -272,14 +380,70 @@ module aptos_framework::fungible_asset {
 
     #[view]
     /// Get the balance of a given store.
-    public fun balance<T: key>(store: Object<T>): u64 acquires FungibleStore {
-        if (store_exists(object::object_address(&store))) {
+    ///
+    /// Note that for stores with a concurrent balance this reads the aggregator, and hence conflicts with
+    /// concurrent deposits into the store.
+    public fun balance<T: key>(store: Object<T>): u64 acquires FungibleStore, ConcurrentFungibleBalance {
+        let store_addr = object::object_address(&store);
+        if (exists<ConcurrentFungibleBalance>(store_addr)) {
+            let balance = &borrow_global<ConcurrentFungibleBalance>(store_addr).balance;
+            (aggregator_v2::read(balance) as u64)
+        } else if (store_exists(store_addr)) {
             borrow_store_resource(&store).balance
         } else {
             0
         }
     }
 
+    #[view]
+    /// Return whether a store keeps its balance in an aggregator.
+    public fun concurrent_balance_enabled<T: key>(store: Object<T>): bool {
+        exists<ConcurrentFungibleBalance>(object::object_address(&store))
+    }
+
+    #[view]
+    /// Return whether the fungible asset of a store has dispatch functions registered, in which case withdrawals
+    /// from and deposits into the store have to go through `dispatchable_fungible_asset`.
+    public fun is_store_dispatchable<T: key>(store: Object<T>): bool acquires FungibleStore {
+        exists<DispatchFunctionStore>(object::object_address(&store_metadata(store)))
+    }
+
+    /// Get the function to dispatch to on withdrawals from `store`, if any.
+    public(friend) fun withdraw_dispatch_function<T: key>(
+        store: Object<T>
+    ): Option<FunctionInfo> acquires FungibleStore, DispatchFunctionStore {
+        let metadata_addr = object::object_address(&store_metadata(store));
+        if (exists<DispatchFunctionStore>(metadata_addr)) {
+            borrow_global<DispatchFunctionStore>(metadata_addr).withdraw_function
+        } else {
+            option::none()
+        }
+    }
+
+    /// Get the function to dispatch to on deposits into `store`, if any.
+    public(friend) fun deposit_dispatch_function<T: key>(
+        store: Object<T>
+    ): Option<FunctionInfo> acquires FungibleStore, DispatchFunctionStore {
+        let metadata_addr = object::object_address(&store_metadata(store));
+        if (exists<DispatchFunctionStore>(metadata_addr)) {
+            borrow_global<DispatchFunctionStore>(metadata_addr).deposit_function
+        } else {
+            option::none()
+        }
+    }
+
+    /// Get the function to dispatch to when querying the balance of `store`, if any.
+    public(friend) fun derived_balance_dispatch_function<T: key>(
+        store: Object<T>
+    ): Option<FunctionInfo> acquires FungibleStore, DispatchFunctionStore {
+        let metadata_addr = object::object_address(&store_metadata(store));
+        if (exists<DispatchFunctionStore>(metadata_addr)) {
+            borrow_global<DispatchFunctionStore>(metadata_addr).derived_balance_function
+        } else {
+            option::none()
+        }
+    }
+
     #[view]
     /// Return whether a store is frozen.
     ///
@@ -314,7 +478,7 @@ module aptos_framework::fungible_asset {
         from: Object<T>,
         to: Object<T>,
         amount: u64,
-    ) acquires FungibleStore, FungibleAssetEvents {
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, DispatchFunctionStore {
         let fa = withdraw(sender, from, amount);
         deposit(to, fa);
     }
@@ -342,10 +506,43 @@ module aptos_framework::fungible_asset {
         object::object_from_constructor_ref<FungibleStore>(constructor_ref)
     }
 
+    /// Upgrade `store` to keep its balance in an aggregator, so that concurrent deposits into it can be executed
+    /// in parallel. This is useful for stores receiving a lot of transfers, e.g. exchange deposit addresses.
+    /// Deposits into and withdrawals from an upgraded store emit `Deposit` and `Withdraw` module events instead of
+    /// events on the store's event handles. The upgrade cannot be reverted.
+    public entry fun upgrade_store_to_concurrent<T: key>(
+        owner: &signer,
+        store: Object<T>,
+    ) acquires FungibleStore {
+        assert!(
+            features::concurrent_fungible_balance_enabled() && features::module_event_enabled(),
+            error::invalid_state(ECONCURRENT_BALANCE_NOT_ENABLED)
+        );
+        assert!(object::owns(store, signer::address_of(owner)), error::permission_denied(ENOT_STORE_OWNER));
+        let store_addr = object::object_address(&store);
+        assert!(
+            !exists<ConcurrentFungibleBalance>(store_addr),
+            error::already_exists(ECONCURRENT_BALANCE_ALREADY_ENABLED)
+        );
+
+        let store = borrow_global_mut<FungibleStore>(store_addr);
+        let balance = aggregator_factory::create_aggregator_v2_internal(MAX_U128);
+        aggregator_v2::add(&mut balance, (store.balance as u128));
+        store.balance = 0;
+        move_to(&create_signer(store_addr), ConcurrentFungibleBalance { balance });
+    }
+
     /// Used to delete a store.  Requires the store to be completely empty prior to removing it
-    public fun remove_store(delete_ref: &DeleteRef) acquires FungibleStore, FungibleAssetEvents {
+    public fun remove_store(
+        delete_ref: &DeleteRef
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance {
         let store = &object::object_from_delete_ref<FungibleStore>(delete_ref);
         let addr = object::object_address(store);
+        if (exists<ConcurrentFungibleBalance>(addr)) {
+            let ConcurrentFungibleBalance { balance } = move_from<ConcurrentFungibleBalance>(addr);
+            assert!(aggregator_v2::read(&balance) == 0, error::permission_denied(EBALANCE_IS_NOT_ZERO));
+            aggregator_v2::destroy(balance);
+        };
         let FungibleStore { metadata: _, balance, frozen: _ }
             = move_from<FungibleStore>(addr);
         assert!(balance == 0, error::permission_denied(EBALANCE_IS_NOT_ZERO));
@@ -360,22 +557,58 @@ module aptos_framework::fungible_asset {
     }
 
     /// Withdraw `amount` of the fungible asset from `store` by the owner.
+    ///
+    /// Aborts if the fungible asset has a withdraw function registered, use `dispatchable_fungible_asset::withdraw`
+    /// instead.
     public fun withdraw<T: key>(
         owner: &signer,
         store: Object<T>,
         amount: u64,
-    ): FungibleAsset acquires FungibleStore, FungibleAssetEvents {
+    ): FungibleAsset acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, DispatchFunctionStore {
+        withdraw_sanity_check(owner, store, true);
+        withdraw_internal(object::object_address(&store), amount)
+    }
+
+    /// Check that `owner` may withdraw from `store`. If `abort_on_dispatch` is set, also check that the fungible
+    /// asset has no withdraw function registered, which the withdrawal would bypass.
+    public(friend) fun withdraw_sanity_check<T: key>(
+        owner: &signer,
+        store: Object<T>,
+        abort_on_dispatch: bool,
+    ) acquires FungibleStore, DispatchFunctionStore {
         assert!(object::owns(store, signer::address_of(owner)), error::permission_denied(ENOT_STORE_OWNER));
+        assert!(
+            !abort_on_dispatch || option::is_none(&withdraw_dispatch_function(store)),
+            error::invalid_argument(EINVALID_DISPATCHABLE_OPERATIONS)
+        );
         assert!(!is_frozen(store), error::invalid_argument(ESTORE_IS_FROZEN));
-        withdraw_internal(object::object_address(&store), amount)
     }
 
     /// Deposit `amount` of the fungible asset to `store`.
-    public fun deposit<T: key>(store: Object<T>, fa: FungibleAsset) acquires FungibleStore, FungibleAssetEvents {
-        assert!(!is_frozen(store), error::invalid_argument(ESTORE_IS_FROZEN));
+    ///
+    /// Aborts if the fungible asset has a deposit function registered, use `dispatchable_fungible_asset::deposit`
+    /// instead.
+    public fun deposit<T: key>(
+        store: Object<T>,
+        fa: FungibleAsset
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, DispatchFunctionStore {
+        deposit_sanity_check(store, true);
         deposit_internal(store, fa);
     }
 
+    /// Check that `store` accepts deposits. If `abort_on_dispatch` is set, also check that the fungible asset has
+    /// no deposit function registered, which the deposit would bypass.
+    public(friend) fun deposit_sanity_check<T: key>(
+        store: Object<T>,
+        abort_on_dispatch: bool,
+    ) acquires FungibleStore, DispatchFunctionStore {
+        assert!(
+            !abort_on_dispatch || option::is_none(&deposit_dispatch_function(store)),
+            error::invalid_argument(EINVALID_DISPATCHABLE_OPERATIONS)
+        );
+        assert!(!is_frozen(store), error::invalid_argument(ESTORE_IS_FROZEN));
+    }
+
     /// Mint the specified `amount` of the fungible asset.
     public fun mint(ref: &MintRef, amount: u64): FungibleAsset acquires Supply {
         assert!(amount > 0, error::invalid_argument(EAMOUNT_CANNOT_BE_ZERO));
@@ -389,9 +622,12 @@ module aptos_framework::fungible_asset {
     }
 
     /// Mint the specified `amount` of the fungible asset to a destination store.
+    ///
+    /// Minting does not dispatch to the deposit function of the fungible asset, if any.
     public fun mint_to<T: key>(ref: &MintRef, store: Object<T>, amount: u64)
-    acquires FungibleStore, FungibleAssetEvents, Supply {
-        deposit(store, mint(ref, amount));
+    acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply {
+        assert!(!is_frozen(store), error::invalid_argument(ESTORE_IS_FROZEN));
+        deposit_internal(store, mint(ref, amount));
     }
 
     /// Enable/disable a store's ability to do direct transfers of the fungible asset.
@@ -426,7 +662,7 @@ module aptos_framework::fungible_asset {
         ref: &BurnRef,
         store: Object<T>,
         amount: u64
-    ) acquires FungibleStore, FungibleAssetEvents, Supply {
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply {
         let metadata = ref.metadata;
         assert!(metadata == store_metadata(store), error::invalid_argument(EBURN_REF_AND_STORE_MISMATCH));
         let store_addr = object::object_address(&store);
@@ -438,7 +674,7 @@ module aptos_framework::fungible_asset {
         ref: &TransferRef,
         store: Object<T>,
         amount: u64
-    ): FungibleAsset acquires FungibleStore, FungibleAssetEvents {
+    ): FungibleAsset acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance {
         assert!(
             ref.metadata == store_metadata(store),
             error::invalid_argument(ETRANSFER_REF_AND_STORE_MISMATCH),
@@ -451,7 +687,7 @@ module aptos_framework::fungible_asset {
         ref: &TransferRef,
         store: Object<T>,
         fa: FungibleAsset
-    ) acquires FungibleStore, FungibleAssetEvents {
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance {
         assert!(
             ref.metadata == fa.metadata,
             error::invalid_argument(ETRANSFER_REF_AND_FUNGIBLE_ASSET_MISMATCH)
@@ -465,7 +701,7 @@ module aptos_framework::fungible_asset {
         from: Object<T>,
         to: Object<T>,
         amount: u64,
-    ) acquires FungibleStore, FungibleAssetEvents {
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance {
         let fa = withdraw_with_ref(transfer_ref, from, amount);
         deposit_with_ref(transfer_ref, to, fa);
     }
@@ -503,13 +739,25 @@ module aptos_framework::fungible_asset {
         assert!(amount == 0, error::invalid_argument(EAMOUNT_IS_NOT_ZERO));
     }
 
-    fun deposit_internal<T: key>(store: Object<T>, fa: FungibleAsset) acquires FungibleStore, FungibleAssetEvents {
+    fun deposit_internal<T: key>(
+        store: Object<T>,
+        fa: FungibleAsset
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance {
         let FungibleAsset { metadata, amount } = fa;
         if (amount == 0) return;
 
         let store_metadata = store_metadata(store);
         assert!(metadata == store_metadata, error::invalid_argument(EFUNGIBLE_ASSET_AND_STORE_MISMATCH));
         let store_addr = object::object_address(&store);
+        if (exists<ConcurrentFungibleBalance>(store_addr)) {
+            // Neither `FungibleStore` nor the event handles are modified, so that concurrent deposits into the
+            // same store only produce commutative deltas to the aggregator.
+            let balance = &mut borrow_global_mut<ConcurrentFungibleBalance>(store_addr).balance;
+            aggregator_v2::add(balance, (amount as u128));
+            event::emit(Deposit { store: store_addr, amount });
+            return
+        };
+
         let store = borrow_global_mut<FungibleStore>(store_addr);
         store.balance = store.balance + amount;
 
@@ -521,8 +769,19 @@ module aptos_framework::fungible_asset {
     fun withdraw_internal(
         store_addr: address,
         amount: u64,
-    ): FungibleAsset acquires FungibleStore, FungibleAssetEvents {
+    ): FungibleAsset acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance {
         assert!(amount != 0, error::invalid_argument(EAMOUNT_CANNOT_BE_ZERO));
+        if (exists<ConcurrentFungibleBalance>(store_addr)) {
+            let balance = &mut borrow_global_mut<ConcurrentFungibleBalance>(store_addr).balance;
+            assert!(
+                aggregator_v2::try_sub(balance, (amount as u128)),
+                error::invalid_argument(EINSUFFICIENT_BALANCE)
+            );
+            event::emit(Withdraw { store: store_addr, amount });
+            let metadata = borrow_global<FungibleStore>(store_addr).metadata;
+            return FungibleAsset { metadata, amount }
+        };
+
         let store = borrow_global_mut<FungibleStore>(store_addr);
         assert!(store.balance >= amount, error::invalid_argument(EINSUFFICIENT_BALANCE));
         store.balance = store.balance - amount;
@@ -660,7 +919,9 @@ module aptos_framework::fungible_asset {
     }
 
     #[test(creator = @0xcafe)]
-    fun test_create_and_remove_store(creator: &signer) acquires FungibleStore, FungibleAssetEvents {
+    fun test_create_and_remove_store(
+        creator: &signer
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance {
         let (_, _, _, metadata) = create_fungible_asset(creator);
         let creator_ref = object::create_object_from_account(creator);
         create_store(&creator_ref, metadata);
@@ -672,7 +933,7 @@ module aptos_framework::fungible_asset {
     fun test_e2e_basic_flow(
         creator: &signer,
         aaron: &signer,
-    ) acquires FungibleStore, FungibleAssetEvents, Supply {
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply, DispatchFunctionStore {
         let (mint_ref, transfer_ref, burn_ref, test_token) = create_fungible_asset(creator);
         let metadata = mint_ref.metadata;
         let creator_store = create_test_store(creator, metadata);
@@ -704,7 +965,7 @@ module aptos_framework::fungible_asset {
     #[expected_failure(abort_code = 0x10003, location = Self)]
     fun test_frozen(
         creator: &signer
-    ) acquires FungibleStore, FungibleAssetEvents, Supply {
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply, DispatchFunctionStore {
         let (mint_ref, transfer_ref, _burn_ref, _) = create_fungible_asset(creator);
 
         let creator_store = create_test_store(creator, mint_ref.metadata);
@@ -717,7 +978,7 @@ module aptos_framework::fungible_asset {
     fun test_transfer_with_ref(
         creator: &signer,
         aaron: &signer,
-    ) acquires FungibleStore, FungibleAssetEvents, Supply {
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply {
         let (mint_ref, transfer_ref, _burn_ref, _) = create_fungible_asset(creator);
         let metadata = mint_ref.metadata;
         let creator_store = create_test_store(creator, metadata);
@@ -734,6 +995,94 @@ module aptos_framework::fungible_asset {
         assert!(!!is_frozen(aaron_store), 4);
     }
 
+    #[test_only]
+    fun enable_concurrent_balance_for_test(aptos_framework: &signer) {
+        features::change_feature_flags(
+            aptos_framework,
+            vector[features::get_concurrent_fungible_balance_feature(), features::get_module_event_feature()],
+            vector[]
+        );
+        aggregator_factory::initialize_aggregator_factory_for_test(aptos_framework);
+    }
+
+    #[test(aptos_framework = @aptos_framework, creator = @0xcafe, aaron = @0xface)]
+    fun test_concurrent_balance(
+        aptos_framework: &signer,
+        creator: &signer,
+        aaron: &signer,
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply, DispatchFunctionStore {
+        enable_concurrent_balance_for_test(aptos_framework);
+        let (mint_ref, _transfer_ref, burn_ref, _) = create_fungible_asset(creator);
+        let metadata = mint_ref.metadata;
+        let creator_store = create_test_store(creator, metadata);
+        let aaron_store = create_test_store(aaron, metadata);
+
+        mint_to(&mint_ref, creator_store, 30);
+        upgrade_store_to_concurrent(creator, creator_store);
+        assert!(concurrent_balance_enabled(creator_store), 1);
+        assert!(!concurrent_balance_enabled(aaron_store), 2);
+        assert!(balance(creator_store) == 30, 3);
+        assert!(borrow_store_resource(&creator_store).balance == 0, 4);
+
+        mint_to(&mint_ref, creator_store, 70);
+        transfer(creator, creator_store, aaron_store, 60);
+        transfer(aaron, aaron_store, creator_store, 20);
+        burn_from(&burn_ref, creator_store, 10);
+        assert!(balance(creator_store) == 50, 5);
+        assert!(balance(aaron_store) == 40, 6);
+    }
+
+    #[test(aptos_framework = @aptos_framework, creator = @0xcafe)]
+    fun test_remove_concurrent_store(
+        aptos_framework: &signer,
+        creator: &signer,
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply {
+        enable_concurrent_balance_for_test(aptos_framework);
+        let (mint_ref, _transfer_ref, burn_ref, metadata) = create_fungible_asset(creator);
+        let creator_ref = object::create_object_from_account(creator);
+        let store = create_store(&creator_ref, metadata);
+        upgrade_store_to_concurrent(creator, store);
+        mint_to(&mint_ref, store, 10);
+        burn_from(&burn_ref, store, 10);
+        let delete_ref = object::generate_delete_ref(&creator_ref);
+        remove_store(&delete_ref);
+    }
+
+    #[test(aptos_framework = @aptos_framework, creator = @0xcafe)]
+    #[expected_failure(abort_code = 0x10004, location = Self)]
+    fun test_concurrent_balance_insufficient(
+        aptos_framework: &signer,
+        creator: &signer,
+    ) acquires FungibleStore, FungibleAssetEvents, ConcurrentFungibleBalance, Supply, DispatchFunctionStore {
+        enable_concurrent_balance_for_test(aptos_framework);
+        let (mint_ref, _transfer_ref, burn_ref, _) = create_fungible_asset(creator);
+        let creator_store = create_test_store(creator, mint_ref.metadata);
+        upgrade_store_to_concurrent(creator, creator_store);
+        mint_to(&mint_ref, creator_store, 10);
+        burn(&burn_ref, withdraw(creator, creator_store, 11));
+    }
+
+    #[test(aptos_framework = @aptos_framework, creator = @0xcafe)]
+    #[expected_failure(abort_code = 0x80017, location = Self)]
+    fun test_upgrade_store_twice(
+        aptos_framework: &signer,
+        creator: &signer,
+    ) acquires FungibleStore {
+        enable_concurrent_balance_for_test(aptos_framework);
+        let (mint_ref, _transfer_ref, _burn_ref, _) = create_fungible_asset(creator);
+        let creator_store = create_test_store(creator, mint_ref.metadata);
+        upgrade_store_to_concurrent(creator, creator_store);
+        upgrade_store_to_concurrent(creator, creator_store);
+    }
+
+    #[test(creator = @0xcafe)]
+    #[expected_failure(abort_code = 0x30016, location = Self)]
+    fun test_upgrade_store_not_enabled(creator: &signer) acquires FungibleStore {
+        let (mint_ref, _transfer_ref, _burn_ref, _) = create_fungible_asset(creator);
+        let creator_store = create_test_store(creator, mint_ref.metadata);
+        upgrade_store_to_concurrent(creator, creator_store);
+    }
+
     #[test(creator = @0xcafe)]
     fun test_merge_and_exact(creator: &signer) acquires Supply {
         let (mint_ref, _transfer_ref, burn_ref, _) = create_fungible_asset(creator);
@@ -0,0 +1,123 @@
+/// This module allows publishing packages to object addresses instead of account addresses, which removes the need
+/// for resource accounts to host upgradeable apps.
+///
+/// `publish` creates a new object owned by the publisher and publishes the package under the `PackageRegistry` of
+/// the object. The address of the object is derived from the publisher address and the sequence number of the
+/// publishing transaction, so it can be computed ahead of time to set the named address of the package:
+/// ```
+/// object::create_object_address(&publisher, bcs(OBJECT_CODE_DEPLOYMENT_DOMAIN_SEPARATOR) ++ bcs(sequence_number + 1))
+/// ```
+/// where `sequence_number` is the sequence number of the publish transaction.
+///
+/// The `ExtendRef` of the object is kept at the object, and only the current owner of the object can use it to
+/// `upgrade` the package or to make it immutable via `freeze_code_object`. Transferring the object transfers the
+/// right to upgrade the code.
+module aptos_framework::object_code_deployment {
+    use std::bcs;
+    use std::error;
+    use std::features;
+    use std::signer;
+    use std::vector;
+    use aptos_framework::account;
+    use aptos_framework::code::{Self, PackageRegistry};
+    use aptos_framework::event;
+    use aptos_framework::object::{Self, ExtendRef, Object};
+
+    /// Object code deployment feature not supported.
+    const EOBJECT_CODE_DEPLOYMENT_NOT_SUPPORTED: u64 = 1;
+    /// Not the owner of the `code_object`.
+    const ENOT_CODE_OBJECT_OWNER: u64 = 2;
+    /// `code_object` does not exist.
+    const ECODE_OBJECT_DOES_NOT_EXIST: u64 = 3;
+
+    const OBJECT_CODE_DEPLOYMENT_DOMAIN_SEPARATOR: vector<u8> = b"aptos_framework::object_code_deployment";
+
+    #[resource_group_member(group = aptos_framework::object::ObjectGroup)]
+    /// Internal struct, attached to the object, that holds the ref used to upgrade the code.
+    struct ManagingRefs has key {
+        /// The ref used to generate the signer of the code object.
+        extend_ref: ExtendRef,
+    }
+
+    #[event]
+    /// Event emitted when code is published to an object.
+    struct Publish has drop, store {
+        object_address: address,
+    }
+
+    #[event]
+    /// Event emitted when code in an existing object is upgraded.
+    struct Upgrade has drop, store {
+        object_address: address,
+    }
+
+    #[event]
+    /// Event emitted when code in an existing object is made immutable.
+    struct Freeze has drop, store {
+        object_address: address,
+    }
+
+    /// Creates a new object with a unique address derived from the publisher address and the sequence number of the
+    /// transaction, and publishes the package to it. The publisher becomes the owner of the object.
+    public entry fun publish(
+        publisher: &signer,
+        metadata_serialized: vector<u8>,
+        code: vector<vector<u8>>,
+    ) {
+        assert!(
+            features::object_code_deployment_enabled(),
+            error::unavailable(EOBJECT_CODE_DEPLOYMENT_NOT_SUPPORTED),
+        );
+
+        let publisher_address = signer::address_of(publisher);
+        let object_seed = object_seed(publisher_address);
+        let constructor_ref = &object::create_named_object(publisher, object_seed);
+        let code_signer = &object::generate_signer(constructor_ref);
+        code::publish_package_txn(code_signer, metadata_serialized, code);
+
+        event::emit(Publish { object_address: signer::address_of(code_signer) });
+
+        move_to(code_signer, ManagingRefs {
+            extend_ref: object::generate_extend_ref(constructor_ref),
+        });
+    }
+
+    /// Upgrades the package published at `code_object`. Only the owner of the object can do this.
+    public entry fun upgrade(
+        publisher: &signer,
+        metadata_serialized: vector<u8>,
+        code: vector<vector<u8>>,
+        code_object: Object<PackageRegistry>,
+    ) acquires ManagingRefs {
+        assert!(
+            object::is_owner(code_object, signer::address_of(publisher)),
+            error::permission_denied(ENOT_CODE_OBJECT_OWNER),
+        );
+
+        let code_object_address = object::object_address(&code_object);
+        assert!(exists<ManagingRefs>(code_object_address), error::not_found(ECODE_OBJECT_DOES_NOT_EXIST));
+
+        let extend_ref = &borrow_global<ManagingRefs>(code_object_address).extend_ref;
+        let code_signer = &object::generate_signer_for_extending(extend_ref);
+        code::publish_package_txn(code_signer, metadata_serialized, code);
+
+        event::emit(Upgrade { object_address: code_object_address });
+    }
+
+    /// Makes the packages published at `code_object` immutable. Only the owner of the object can do this.
+    public entry fun freeze_code_object(publisher: &signer, code_object: Object<PackageRegistry>) {
+        code::freeze_code_object(publisher, code_object);
+
+        event::emit(Freeze { object_address: object::object_address(&code_object) });
+    }
+
+    /// The seed of the object created by a publish transaction, built from the sequence number the publisher account
+    /// will have after the transaction.
+    inline fun object_seed(publisher: address): vector<u8> {
+        let sequence_number = account::get_sequence_number(publisher) + 1;
+        let seeds = vector[];
+        vector::append(&mut seeds, bcs::to_bytes(&OBJECT_CODE_DEPLOYMENT_DOMAIN_SEPARATOR));
+        vector::append(&mut seeds, bcs::to_bytes(&sequence_number));
+        seeds
+    }
+}
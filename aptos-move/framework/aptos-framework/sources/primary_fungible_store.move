@@ -12,6 +12,7 @@
 /// 4. The fungible asset metadata object calls `deposit` on the recipient's primary store to deposit `amount` of
 /// fungible asset to it. This emits an deposit event.
 module aptos_framework::primary_fungible_store {
+    use aptos_framework::dispatchable_fungible_asset;
     use aptos_framework::fungible_asset::{Self, FungibleAsset, FungibleStore, Metadata, MintRef, TransferRef, BurnRef};
     use aptos_framework::object::{Self, Object, ConstructorRef, DeriveRef};
 
@@ -128,14 +129,14 @@ module aptos_framework::primary_fungible_store {
         let store = primary_store(signer::address_of(owner), metadata);
         // Check if the store object has been burnt or not. If so, unburn it first.
         may_be_unburn(owner, store);
-        fungible_asset::withdraw(owner, store, amount)
+        dispatchable_fungible_asset::withdraw(owner, store, amount)
     }
 
     /// Deposit fungible asset `fa` to the given account's primary store.
     public fun deposit(owner: address, fa: FungibleAsset) acquires DeriveRefPod {
         let metadata = fungible_asset::asset_metadata(&fa);
         let store = ensure_primary_store_exists(owner, metadata);
-        fungible_asset::deposit(store, fa);
+        dispatchable_fungible_asset::deposit(store, fa);
     }
 
     /// Transfer `amount` of fungible asset from sender's primary store to receiver's primary store.
@@ -149,7 +150,7 @@ module aptos_framework::primary_fungible_store {
         // Check if the sender store object has been burnt or not. If so, unburn it first.
         may_be_unburn(sender, sender_store);
         let recipient_store = ensure_primary_store_exists(recipient, metadata);
-        fungible_asset::transfer(sender, sender_store, recipient_store, amount);
+        dispatchable_fungible_asset::transfer(sender, sender_store, recipient_store, amount);
     }
 
     /// Mint to the primary store of `owner`.
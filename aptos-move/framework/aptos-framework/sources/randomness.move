@@ -0,0 +1,239 @@
+/// This module provides access to randomness derived by the validators for every block.
+///
+/// The proposer of a block signs the epoch and round of the block with its BLS12-381 consensus key,
+/// and the seed of the block is the hash of that signature. BLS signatures are unique, so the seed
+/// can be verified by all validators and the proposer can not pick another seed for the same round.
+/// The seed is unknown to the senders of the transactions in the block when they submit them.
+///
+/// Such a seed is NOT unpredictable or unbiasable: the proposer knows the seed of its blocks in
+/// advance, and can withhold a block whose seed it does not like. Validators therefore do not
+/// produce seeds until the seed is derived from a threshold signature of the validators, and every
+/// call of the randomness API aborts with `ERANDOMNESS_NOT_AVAILABLE` in the meantime.
+///
+/// Every call of the randomness API hashes the seed together with the hash of the transaction and
+/// a counter, so different calls in the same transaction, and calls in different transactions of
+/// the same block, produce independent values.
+///
+/// The randomness API may only be called from private entry functions: a public function could be
+/// called from a script or another module which inspects the outcome and aborts if it does not like
+/// it, trying again with another transaction until it gets the outcome it wants.
+module aptos_framework::randomness {
+    use std::error;
+    use std::hash;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    use aptos_framework::system_addresses;
+    use aptos_framework::transaction_context;
+
+    friend aptos_framework::block;
+
+    const DST: vector<u8> = b"APTOS_RANDOMNESS";
+
+    /// Randomness API calls must originate from a private entry function, as a caller which can
+    /// observe the outcome could abort the transaction until it gets an outcome it likes.
+    const E_API_USE_IS_BIASABLE: u64 = 1;
+    /// The randomness seed of the current block is not available, as on-chain randomness is
+    /// disabled or the block has no proposer.
+    const ERANDOMNESS_NOT_AVAILABLE: u64 = 2;
+    /// The range to sample from is empty.
+    const EEMPTY_RANGE: u64 = 3;
+
+    /// The randomness seed of the current block.
+    struct PerBlockRandomness has drop, key {
+        epoch: u64,
+        round: u64,
+        seed: Option<vector<u8>>,
+    }
+
+    /// Called in genesis, or by governance on chains created before on-chain randomness.
+    public fun initialize(aptos_framework: &signer) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        if (!exists<PerBlockRandomness>(@aptos_framework)) {
+            move_to(aptos_framework, PerBlockRandomness {
+                epoch: 0,
+                round: 0,
+                seed: option::none(),
+            });
+        }
+    }
+
+    /// Invoked in the block prologue to update the randomness seed of the current block.
+    public(friend) fun on_new_block(vm: &signer, epoch: u64, round: u64, seed: Option<vector<u8>>) acquires PerBlockRandomness {
+        system_addresses::assert_vm(vm);
+        if (exists<PerBlockRandomness>(@aptos_framework)) {
+            let randomness = borrow_global_mut<PerBlockRandomness>(@aptos_framework);
+            randomness.epoch = epoch;
+            randomness.round = round;
+            randomness.seed = seed;
+        };
+    }
+
+    /// Generates the next 32 random bytes. Repeated calls yield different results.
+    fun next_32_bytes(): vector<u8> acquires PerBlockRandomness {
+        assert!(is_unbiasable(), error::permission_denied(E_API_USE_IS_BIASABLE));
+        assert!(exists<PerBlockRandomness>(@aptos_framework), error::invalid_state(ERANDOMNESS_NOT_AVAILABLE));
+        let randomness = borrow_global<PerBlockRandomness>(@aptos_framework);
+        assert!(option::is_some(&randomness.seed), error::invalid_state(ERANDOMNESS_NOT_AVAILABLE));
+
+        let input = DST;
+        vector::append(&mut input, *option::borrow(&randomness.seed));
+        vector::append(&mut input, transaction_context::get_txn_hash());
+        vector::append(&mut input, fetch_and_increment_txn_counter());
+        hash::sha3_256(input)
+    }
+
+    /// Generates a sequence of `n` random bytes.
+    public fun bytes(n: u64): vector<u8> acquires PerBlockRandomness {
+        let result = vector[];
+        while (vector::length(&result) < n) {
+            vector::append(&mut result, next_32_bytes());
+        };
+        while (vector::length(&result) > n) {
+            vector::pop_back(&mut result);
+        };
+        result
+    }
+
+    /// Generates a number uniformly at random.
+    public fun u8_integer(): u8 acquires PerBlockRandomness {
+        (take_integer(8) as u8)
+    }
+
+    /// Generates a number uniformly at random.
+    public fun u16_integer(): u16 acquires PerBlockRandomness {
+        (take_integer(16) as u16)
+    }
+
+    /// Generates a number uniformly at random.
+    public fun u32_integer(): u32 acquires PerBlockRandomness {
+        (take_integer(32) as u32)
+    }
+
+    /// Generates a number uniformly at random.
+    public fun u64_integer(): u64 acquires PerBlockRandomness {
+        (take_integer(64) as u64)
+    }
+
+    /// Generates a number uniformly at random.
+    public fun u128_integer(): u128 acquires PerBlockRandomness {
+        (take_integer(128) as u128)
+    }
+
+    /// Generates a number uniformly at random.
+    public fun u256_integer(): u256 acquires PerBlockRandomness {
+        take_integer(256)
+    }
+
+    /// Generates a number `n` in `[min_incl, max_excl)` uniformly at random.
+    ///
+    /// The number is sampled by reducing a random `u256`, so its distribution differs from the
+    /// uniform one by at most 2^-192.
+    public fun u64_range(min_incl: u64, max_excl: u64): u64 acquires PerBlockRandomness {
+        assert!(min_incl < max_excl, error::invalid_argument(EEMPTY_RANGE));
+        let range = ((max_excl - min_incl) as u256);
+        let sample = ((take_integer(256) % range) as u64);
+        min_incl + sample
+    }
+
+    /// Generates a permutation of `[0, 1, ..., n-1]` uniformly at random.
+    public fun permutation(n: u64): vector<u64> acquires PerBlockRandomness {
+        let values = vector[];
+        let i = 0;
+        while (i < n) {
+            vector::push_back(&mut values, i);
+            i = i + 1;
+        };
+
+        // Fisher-Yates shuffle
+        let tail = n;
+        while (tail > 1) {
+            let pop_position = u64_range(0, tail);
+            tail = tail - 1;
+            vector::swap(&mut values, pop_position, tail);
+        };
+        values
+    }
+
+    /// Returns a random integer of `bits` bits, taken from the next 32 random bytes.
+    fun take_integer(bits: u16): u256 acquires PerBlockRandomness {
+        let raw = next_32_bytes();
+        let result = 0;
+        let i = 0;
+        while (i < bits / 8) {
+            result = (result << 8) | (*vector::borrow(&raw, (i as u64)) as u256);
+            i = i + 1;
+        };
+        result
+    }
+
+    /// Returns the current value of the counter of randomness API calls in this transaction, and
+    /// increments it.
+    native fun fetch_and_increment_txn_counter(): vector<u8>;
+
+    /// Returns whether the transaction is running a private entry function, so its outcome can not
+    /// be inspected by a caller.
+    native fun is_unbiasable(): bool;
+
+    #[test_only]
+    public fun initialize_for_testing(aptos_framework: &signer) acquires PerBlockRandomness {
+        initialize(aptos_framework);
+        set_seed(x"0000000000000000000000000000000000000000000000000000000000000000");
+    }
+
+    #[test_only]
+    public fun set_seed(seed: vector<u8>) acquires PerBlockRandomness {
+        let randomness = borrow_global_mut<PerBlockRandomness>(@aptos_framework);
+        randomness.seed = option::some(seed);
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_bytes_and_integers(fx: signer) acquires PerBlockRandomness {
+        initialize_for_testing(&fx);
+        assert!(vector::length(&bytes(0)) == 0, 0);
+        assert!(vector::length(&bytes(20)) == 20, 1);
+        assert!(vector::length(&bytes(100)) == 100, 2);
+        // Every call yields fresh randomness
+        assert!(bytes(32) != bytes(32), 3);
+        assert!(u256_integer() != u256_integer(), 4);
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_u64_range(fx: signer) acquires PerBlockRandomness {
+        initialize_for_testing(&fx);
+        let i = 0;
+        while (i < 50) {
+            let sample = u64_range(10, 20);
+            assert!(sample >= 10 && sample < 20, 0);
+            i = i + 1;
+        };
+        assert!(u64_range(7, 8) == 7, 1);
+    }
+
+    #[test(fx = @aptos_framework)]
+    #[expected_failure(abort_code = 0x10003, location = Self)]
+    fun test_u64_range_empty(fx: signer) acquires PerBlockRandomness {
+        initialize_for_testing(&fx);
+        u64_range(5, 5);
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_permutation(fx: signer) acquires PerBlockRandomness {
+        initialize_for_testing(&fx);
+        let values = permutation(20);
+        assert!(vector::length(&values) == 20, 0);
+        let i = 0;
+        while (i < 20) {
+            assert!(vector::contains(&values, &i), 1);
+            i = i + 1;
+        };
+        assert!(vector::is_empty(&permutation(0)), 2);
+    }
+
+    #[test(fx = @aptos_framework)]
+    #[expected_failure(abort_code = 0x30002, location = Self)]
+    fun test_no_seed(fx: signer) acquires PerBlockRandomness {
+        initialize(&fx);
+        u64_integer();
+    }
+}
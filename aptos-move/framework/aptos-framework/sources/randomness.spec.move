@@ -0,0 +1,39 @@
+spec aptos_framework::randomness {
+    spec module {
+        pragma verify = true;
+    }
+
+    spec on_new_block(vm: &signer, epoch: u64, round: u64, seed: Option<vector<u8>>) {
+        aborts_if !system_addresses::is_vm(vm);
+        ensures exists<PerBlockRandomness>(@aptos_framework) ==>
+            global<PerBlockRandomness>(@aptos_framework).seed == seed;
+    }
+
+    spec fetch_and_increment_txn_counter(): vector<u8> {
+        pragma opaque;
+        aborts_if false;
+    }
+
+    spec is_unbiasable(): bool {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_is_unbiasable();
+    }
+    spec fun spec_is_unbiasable(): bool;
+
+    spec next_32_bytes(): vector<u8> {
+        pragma verify = false;
+    }
+
+    spec take_integer(bits: u16): u256 {
+        pragma verify = false;
+    }
+
+    spec bytes(n: u64): vector<u8> {
+        pragma verify = false;
+    }
+
+    spec permutation(n: u64): vector<u64> {
+        pragma verify = false;
+    }
+}
@@ -1,9 +1,20 @@
 module aptos_framework::transaction_context {
 
+    use std::error;
     use std::features;
+    use std::option::{Self, Option};
+    use std::string::{Self, String};
+    use std::vector;
+
+    friend aptos_framework::randomness;
 
     /// AUID feature is not supported.
     const EAUID_NOT_SUPPORTED: u64 = 1;
+    /// The transaction context extension feature is not enabled.
+    const ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED: u64 = 2;
+    /// The transaction context is only available in the prologue, execution and epilogue of a
+    /// user transaction.
+    const ETRANSACTION_CONTEXT_NOT_AVAILABLE: u64 = 3;
 
     /// A wrapper denoting aptos unique identifer (AUID)
     /// for storing an address
@@ -11,8 +22,17 @@ module aptos_framework::transaction_context {
         unique_address: address
     }
 
+    /// The entry function called by the current user transaction.
+    struct EntryFunctionPayload has copy, drop {
+        account_address: address,
+        module_name: String,
+        function_name: String,
+        ty_arg_names: vector<String>,
+        args: vector<vector<u8>>,
+    }
+
     /// Return the transaction hash of the current transaction.
-    native fun get_txn_hash(): vector<u8>;
+    public(friend) native fun get_txn_hash(): vector<u8>;
 
     /// Return the transaction hash of the current transaction.
     /// Internally calls the private function `get_txn_hash`.
@@ -55,6 +75,104 @@ module aptos_framework::transaction_context {
         auid.unique_address
     }
 
+    /// Return the sender of the current user transaction.
+    public fun sender(): address {
+        assert_transaction_context_extension_enabled();
+        sender_internal()
+    }
+    native fun sender_internal(): address;
+
+    /// Return the secondary signers of the current user transaction. Empty unless the transaction
+    /// is a multi-agent transaction.
+    public fun secondary_signers(): vector<address> {
+        assert_transaction_context_extension_enabled();
+        secondary_signers_internal()
+    }
+    native fun secondary_signers_internal(): vector<address>;
+
+    /// Return the account paying the gas of the current user transaction: the fee payer if the
+    /// transaction has one, otherwise the sender.
+    public fun gas_payer(): address {
+        assert_transaction_context_extension_enabled();
+        gas_payer_internal()
+    }
+    native fun gas_payer_internal(): address;
+
+    /// Return the max gas amount of the current user transaction, in gas units.
+    public fun max_gas_amount(): u64 {
+        assert_transaction_context_extension_enabled();
+        max_gas_amount_internal()
+    }
+    native fun max_gas_amount_internal(): u64;
+
+    /// Return the gas unit price of the current user transaction, in octas.
+    public fun gas_unit_price(): u64 {
+        assert_transaction_context_extension_enabled();
+        gas_unit_price_internal()
+    }
+    native fun gas_unit_price_internal(): u64;
+
+    /// Return the chain ID the current user transaction is signed for.
+    public fun chain_id(): u8 {
+        assert_transaction_context_extension_enabled();
+        chain_id_internal()
+    }
+    native fun chain_id_internal(): u8;
+
+    /// Return the entry function called by the current user transaction, or none if its payload
+    /// is a script or a multisig transaction.
+    public fun entry_function_payload(): Option<EntryFunctionPayload> {
+        assert_transaction_context_extension_enabled();
+        if (!has_entry_function_payload_internal()) {
+            return option::none()
+        };
+        let (account_address, module_name, function_name, ty_arg_names, args) =
+            entry_function_payload_internal();
+        let ty_arg_strings = vector[];
+        vector::for_each(ty_arg_names, |name| {
+            vector::push_back(&mut ty_arg_strings, string::utf8(name));
+        });
+        option::some(EntryFunctionPayload {
+            account_address,
+            module_name: string::utf8(module_name),
+            function_name: string::utf8(function_name),
+            ty_arg_names: ty_arg_strings,
+            args,
+        })
+    }
+    native fun has_entry_function_payload_internal(): bool;
+    native fun entry_function_payload_internal(): (address, vector<u8>, vector<u8>, vector<vector<u8>>, vector<vector<u8>>);
+
+    public fun account_address(payload: &EntryFunctionPayload): address {
+        payload.account_address
+    }
+
+    public fun module_name(payload: &EntryFunctionPayload): String {
+        payload.module_name
+    }
+
+    public fun function_name(payload: &EntryFunctionPayload): String {
+        payload.function_name
+    }
+
+    /// Return the type arguments of the entry function, in their canonical string representation,
+    /// e.g. `0000000000000000000000000000000000000000000000000000000000000001::aptos_coin::AptosCoin`.
+    public fun type_arg_names(payload: &EntryFunctionPayload): vector<String> {
+        payload.ty_arg_names
+    }
+
+    /// Return the BCS-serialized arguments of the entry function, excluding its signers.
+    public fun args(payload: &EntryFunctionPayload): vector<vector<u8>> {
+        payload.args
+    }
+
+    fun assert_transaction_context_extension_enabled() {
+        assert!(
+            features::transaction_context_extension_enabled(),
+            error::invalid_state(ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED)
+        );
+    }
+
     #[test(fx = @std)]
     fun test_auid_uniquess(fx: signer) {
         use std::features;
@@ -80,4 +198,18 @@ module aptos_framework::transaction_context {
             i = i + 1;
         };
     }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x30003, location = Self)]
+    fun test_sender_outside_user_transaction(fx: signer) {
+        let feature = features::get_transaction_context_extension_feature();
+        features::change_feature_flags(&fx, vector[feature], vector[]);
+        sender();
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x30002, location = Self)]
+    fun test_sender_feature_disabled() {
+        sender();
+    }
 }
@@ -16,4 +16,32 @@ spec aptos_framework::transaction_context {
         ensures [abstract] result == spec_generate_unique_address();
     }
     spec fun spec_generate_unique_address(): address;
+    spec sender_internal(): address {
+        pragma opaque;
+    }
+    spec secondary_signers_internal(): vector<address> {
+        pragma opaque;
+    }
+    spec gas_payer_internal(): address {
+        pragma opaque;
+    }
+    spec max_gas_amount_internal(): u64 {
+        pragma opaque;
+    }
+    spec gas_unit_price_internal(): u64 {
+        pragma opaque;
+    }
+    spec chain_id_internal(): u8 {
+        pragma opaque;
+    }
+    spec has_entry_function_payload_internal(): bool {
+        pragma opaque;
+    }
+    spec entry_function_payload_internal(): (address, vector<u8>, vector<u8>, vector<vector<u8>>, vector<vector<u8>>) {
+        pragma opaque;
+    }
+    spec entry_function_payload(): Option<EntryFunctionPayload> {
+        // TODO: loop over the type argument names is not verified.
+        pragma verify = false;
+    }
 }
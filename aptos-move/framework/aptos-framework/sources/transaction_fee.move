@@ -1,10 +1,13 @@
 /// This module provides an interface to burn or collect and redistribute transaction fees.
 module aptos_framework::transaction_fee {
+    use aptos_framework::aggregator::{Self, Aggregator};
+    use aptos_framework::aggregator_factory;
     use aptos_framework::coin::{Self, AggregatableCoin, BurnCapability, Coin, MintCapability};
     use aptos_framework::aptos_coin::AptosCoin;
     use aptos_framework::stake;
     use aptos_framework::system_addresses;
     use std::error;
+    use std::features;
     use std::option::{Self, Option};
     use aptos_framework::event;
 
@@ -20,6 +23,17 @@ module aptos_framework::transaction_fee {
     /// The burn percentage is out of range [0, 100].
     const EINVALID_BURN_PERCENTAGE: u64 = 3;
 
+    /// The base fee market is already initialized.
+    const EALREADY_BASE_FEE_MARKET: u64 = 4;
+
+    /// The target block gas and the max change denominator of the base fee market must be positive.
+    const EINVALID_BASE_FEE_CONFIG: u64 = 5;
+
+    /// The base fee market is not initialized.
+    const ENO_BASE_FEE_MARKET: u64 = 6;
+
+    const MAX_U64: u128 = 18446744073709551615;
+
     /// Stores burn capability to burn the gas fees.
     struct AptosCoinCapabilities has key {
         burn_cap: BurnCapability<AptosCoin>,
@@ -38,6 +52,27 @@ module aptos_framework::transaction_fee {
         burn_percentage: u8,
     }
 
+    /// Parameters of the base fee market. Every block, the base fee moves towards the price at
+    /// which blocks use `target_block_gas` gas units, by at most `1 / max_change_denominator` of
+    /// its value.
+    struct BaseFeeConfig has key {
+        target_block_gas: u64,
+        max_change_denominator: u64,
+        minimum_base_fee: u64,
+    }
+
+    /// The minimum gas unit price of the transactions of the current block. Transactions are still
+    /// charged their full gas unit price: the part of the fee covered by the base fee is always
+    /// burned, and all of the rest is a tip which can be collected. There is no cap on the tip.
+    struct BaseFee has key {
+        base_fee_per_gas: u64,
+    }
+
+    /// Gas units used by the transactions executed in the current block.
+    struct GasUsedPerBlock has key {
+        amount: Aggregator,
+    }
+
     #[event]
     /// Summary of the fees charged and refunds issued for a transaction.
     ///
@@ -101,6 +136,128 @@ module aptos_framework::transaction_fee {
         }
     }
 
+    /// Initializes the base fee market, which only takes effect once the `BASE_FEE_MARKET` feature
+    /// is enabled. Should be called by on-chain governance.
+    public fun initialize_base_fee_market(
+        aptos_framework: &signer,
+        initial_base_fee: u64,
+        target_block_gas: u64,
+        max_change_denominator: u64,
+        minimum_base_fee: u64,
+    ) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        assert!(!exists<BaseFee>(@aptos_framework), error::already_exists(EALREADY_BASE_FEE_MARKET));
+        assert!(
+            target_block_gas > 0 && max_change_denominator > 0,
+            error::invalid_argument(EINVALID_BASE_FEE_CONFIG)
+        );
+
+        move_to(aptos_framework, BaseFeeConfig { target_block_gas, max_change_denominator, minimum_base_fee });
+        let base_fee_per_gas = if (initial_base_fee < minimum_base_fee) { minimum_base_fee } else { initial_base_fee };
+        move_to(aptos_framework, BaseFee { base_fee_per_gas });
+        move_to(aptos_framework, GasUsedPerBlock {
+            amount: aggregator_factory::create_aggregator(aptos_framework, MAX_U64),
+        });
+    }
+
+    /// Updates the parameters of the base fee market. The current base fee is adjusted by them
+    /// starting with the next block. Should be called by on-chain governance.
+    public fun update_base_fee_config(
+        aptos_framework: &signer,
+        target_block_gas: u64,
+        max_change_denominator: u64,
+        minimum_base_fee: u64,
+    ) acquires BaseFeeConfig {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        assert!(exists<BaseFeeConfig>(@aptos_framework), error::not_found(ENO_BASE_FEE_MARKET));
+        assert!(
+            target_block_gas > 0 && max_change_denominator > 0,
+            error::invalid_argument(EINVALID_BASE_FEE_CONFIG)
+        );
+
+        let config = borrow_global_mut<BaseFeeConfig>(@aptos_framework);
+        config.target_block_gas = target_block_gas;
+        config.max_change_denominator = max_change_denominator;
+        config.minimum_base_fee = minimum_base_fee;
+    }
+
+    fun is_base_fee_market_enabled(): bool {
+        features::base_fee_market_enabled() && exists<BaseFee>(@aptos_framework)
+    }
+
+    #[view]
+    /// Returns the base fee per gas unit transactions must pay in the current block, or zero if
+    /// the base fee market is disabled.
+    public fun base_fee_per_gas(): u64 acquires BaseFee {
+        if (is_base_fee_market_enabled()) {
+            borrow_global<BaseFee>(@aptos_framework).base_fee_per_gas
+        } else {
+            0
+        }
+    }
+
+    /// Records the gas used by a transaction in epilogue, so that the next block can adjust the
+    /// base fee to the fullness of this one.
+    public(friend) fun record_gas_used(gas_used: u64) acquires GasUsedPerBlock {
+        if (is_base_fee_market_enabled()) {
+            let gas_used_per_block = &mut borrow_global_mut<GasUsedPerBlock>(@aptos_framework).amount;
+            aggregator::add(gas_used_per_block, (gas_used as u128));
+        }
+    }
+
+    /// Adjusts the base fee to the gas used by the previous block. This function can only be called
+    /// at the beginning of the block.
+    public(friend) fun update_base_fee() acquires BaseFee, BaseFeeConfig, GasUsedPerBlock {
+        if (!is_base_fee_market_enabled()) {
+            return
+        };
+        let gas_used_per_block = &mut borrow_global_mut<GasUsedPerBlock>(@aptos_framework).amount;
+        let gas_used = aggregator::read(gas_used_per_block);
+        aggregator::sub(gas_used_per_block, gas_used);
+
+        let config = borrow_global<BaseFeeConfig>(@aptos_framework);
+        let base_fee = borrow_global_mut<BaseFee>(@aptos_framework);
+        base_fee.base_fee_per_gas = next_base_fee(config, base_fee.base_fee_per_gas, gas_used);
+    }
+
+    /// Computes the base fee following a block which used `gas_used` gas units.
+    fun next_base_fee(config: &BaseFeeConfig, base_fee_per_gas: u64, gas_used: u128): u64 {
+        let current = (base_fee_per_gas as u128);
+        let target = (config.target_block_gas as u128);
+        let denominator = (config.max_change_denominator as u128);
+        let next = if (gas_used > target) {
+            let delta = current * (gas_used - target) / target / denominator;
+            // Always increase by at least one, so that a low base fee can still grow.
+            if (delta == 0) {
+                delta = 1;
+            };
+            current + delta
+        } else {
+            current - current * (target - gas_used) / target / denominator
+        };
+
+        let minimum = (config.minimum_base_fee as u128);
+        if (next < minimum) {
+            config.minimum_base_fee
+        } else if (next > MAX_U64) {
+            (MAX_U64 as u64)
+        } else {
+            (next as u64)
+        }
+    }
+
+    /// Returns the part of a transaction fee covered by the base fee, which must be burned. The rest
+    /// of the fee is the tip. `txn_gas_price` is the price charged, i.e. the base fee plus at most the
+    /// max priority fee of the transaction; the rest of its max fee is refunded and never charged.
+    public(friend) fun base_fee_amount(gas_used: u64, txn_gas_price: u64): u64 acquires BaseFee {
+        let base_fee_per_gas = base_fee_per_gas();
+        if (base_fee_per_gas < txn_gas_price) {
+            base_fee_per_gas * gas_used
+        } else {
+            txn_gas_price * gas_used
+        }
+    }
+
     /// Registers the proposer of the block for gas fees collection. This function
     /// can only be called at the beginning of the block.
     public(friend) fun register_proposer_for_fee_collection(proposer_addr: address) acquires CollectedFeesPerBlock {
@@ -227,9 +384,6 @@ module aptos_framework::transaction_fee {
         event::emit(fee_statement)
     }
 
-    #[test_only]
-    use aptos_framework::aggregator_factory;
-
     #[test(aptos_framework = @aptos_framework)]
     fun test_initialize_fee_collection_and_distribution(aptos_framework: signer) acquires CollectedFeesPerBlock {
         aggregator_factory::initialize_aggregator_factory_for_test(&aptos_framework);
@@ -245,6 +399,25 @@ module aptos_framework::transaction_fee {
         assert!(collected_fees.burn_percentage == 25, 0);
     }
 
+    #[test]
+    fun test_next_base_fee() {
+        let config = BaseFeeConfig { target_block_gas: 1000, max_change_denominator: 8, minimum_base_fee: 10 };
+
+        // Full blocks increase and empty blocks decrease the base fee by 1/8.
+        assert!(next_base_fee(&config, 800, 2000) == 900, 0);
+        assert!(next_base_fee(&config, 800, 0) == 700, 0);
+        // Blocks at the target keep the base fee.
+        assert!(next_base_fee(&config, 800, 1000) == 800, 0);
+        // Blocks above the target always increase the base fee.
+        assert!(next_base_fee(&config, 12, 1001) == 13, 0);
+        // The base fee never drops below the minimum.
+        assert!(next_base_fee(&config, 10, 0) == 10, 0);
+        // The base fee saturates at the maximum.
+        assert!(next_base_fee(&config, (MAX_U64 as u64), 2000) == (MAX_U64 as u64), 0);
+
+        let BaseFeeConfig { target_block_gas: _, max_change_denominator: _, minimum_base_fee: _ } = config;
+    }
+
     #[test(aptos_framework = @aptos_framework)]
     fun test_burn_fraction_calculation(aptos_framework: signer) acquires AptosCoinCapabilities {
         use aptos_framework::aptos_coin;
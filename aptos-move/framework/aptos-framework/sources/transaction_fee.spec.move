@@ -61,6 +61,36 @@ spec aptos_framework::transaction_fee {
             option::spec_borrow(global<CollectedFeesPerBlock>(@aptos_framework).proposer) == proposer_addr;
     }
 
+    spec BaseFeeConfig {
+        invariant target_block_gas > 0;
+        invariant max_change_denominator > 0;
+    }
+
+    spec initialize_base_fee_market {
+        // TODO: verification disabled until the base fee market is specified.
+        pragma verify = false;
+    }
+
+    spec update_base_fee_config {
+        pragma verify = false;
+    }
+
+    spec record_gas_used {
+        pragma verify = false;
+    }
+
+    spec update_base_fee {
+        pragma verify = false;
+    }
+
+    spec next_base_fee {
+        pragma verify = false;
+    }
+
+    spec base_fee_amount {
+        pragma verify = false;
+    }
+
     spec burn_coin_fraction(coin: &mut Coin<AptosCoin>, burn_percentage: u8) {
         use aptos_framework::coin::CoinInfo;
         use aptos_framework::aptos_coin::AptosCoin;
@@ -44,6 +44,7 @@ module aptos_framework::transaction_validation {
     const PROLOGUE_ESEQUENCE_NUMBER_TOO_BIG: u64 = 1008;
     const PROLOGUE_ESECONDARY_KEYS_ADDRESSES_COUNT_MISMATCH: u64 = 1009;
     const PROLOGUE_EFEE_PAYER_NOT_ENABLED: u64 = 1010;
+    const PROLOGUE_EGAS_UNIT_PRICE_BELOW_BASE_FEE: u64 = 1011;
 
 
     /// Only called during genesis to initialize system resources for this module.
@@ -114,6 +115,13 @@ module aptos_framework::transaction_validation {
         );
         let balance = coin::balance<AptosCoin>(gas_payer);
         assert!(balance >= max_transaction_fee, error::invalid_argument(PROLOGUE_ECANT_PAY_GAS_DEPOSIT));
+
+        // Checked last: mempool accepts transactions failing only this check, and keeps them until
+        // the base fee drops, but they can't be executed in the current block.
+        assert!(
+            txn_gas_price >= transaction_fee::base_fee_per_gas(),
+            error::invalid_argument(PROLOGUE_EGAS_UNIT_PRICE_BELOW_BASE_FEE),
+        );
     }
 
     fun module_prologue(
@@ -247,6 +255,10 @@ module aptos_framework::transaction_validation {
 
     /// Epilogue function with explicit gas payer specified, is run after a transaction is successfully executed.
     /// Called by the Adapter
+    ///
+    /// Under the base fee market `txn_gas_price` is the price charged, which the Adapter computes as
+    /// `min(max_fee, base_fee + max_priority_fee)` from the gas unit price signed by the sender (the max
+    /// fee). Only that price is charged per unit of gas used, so the rest of the max fee is refunded.
     fun epilogue_gas_payer(
         account: signer,
         gas_payer: address,
@@ -270,14 +282,18 @@ module aptos_framework::transaction_validation {
             error::out_of_range(PROLOGUE_ECANT_PAY_GAS_DEPOSIT),
         );
 
+        transaction_fee::record_gas_used(gas_used);
+
         let amount_to_burn = if (features::collect_and_distribute_gas_fees()) {
             // TODO(gas): We might want to distinguish the refundable part of the charge and burn it or track
             // it separately, so that we don't increase the total supply by refunding.
 
             // If transaction fees are redistributed to validators, collect them here for
-            // later redistribution.
-            transaction_fee::collect_fee(gas_payer, transaction_fee_amount);
-            0
+            // later redistribution. The part covered by the base fee is always burned, so only
+            // the tip is collected.
+            let base_fee_amount = transaction_fee::base_fee_amount(gas_used, txn_gas_price);
+            transaction_fee::collect_fee(gas_payer, transaction_fee_amount - base_fee_amount);
+            base_fee_amount
         } else {
             // Otherwise, just burn the fee.
             // TODO: this branch should be removed completely when transaction fee collection
@@ -549,6 +549,25 @@ pub enum EntryFunctionCall {
         approved: bool,
     },
 
+    /// Makes the packages published at `code_object` immutable. Only the owner of the object can do this.
+    ObjectCodeDeploymentFreezeCodeObject {
+        code_object: AccountAddress,
+    },
+
+    /// Creates a new object with a unique address derived from the publisher address and the sequence number of the
+    /// transaction, and publishes the package to it. The publisher becomes the owner of the object.
+    ObjectCodeDeploymentPublish {
+        metadata_serialized: Vec<u8>,
+        code: Vec<Vec<u8>>,
+    },
+
+    /// Upgrades the package published at `code_object`. Only the owner of the object can do this.
+    ObjectCodeDeploymentUpgrade {
+        metadata_serialized: Vec<u8>,
+        code: Vec<Vec<u8>>,
+        code_object: AccountAddress,
+    },
+
     /// Entry function that can be used to transfer, if allow_ungated_transfer is set true.
     ObjectTransferCall {
         object: AccountAddress,
@@ -1201,6 +1220,18 @@ impl EntryFunctionCall {
                 sequence_number,
                 approved,
             } => multisig_account_vote_transanction(multisig_account, sequence_number, approved),
+            ObjectCodeDeploymentFreezeCodeObject { code_object } => {
+                object_code_deployment_freeze_code_object(code_object)
+            },
+            ObjectCodeDeploymentPublish {
+                metadata_serialized,
+                code,
+            } => object_code_deployment_publish(metadata_serialized, code),
+            ObjectCodeDeploymentUpgrade {
+                metadata_serialized,
+                code,
+                code_object,
+            } => object_code_deployment_upgrade(metadata_serialized, code, code_object),
             ObjectTransferCall { object, to } => object_transfer_call(object, to),
             ResourceAccountCreateResourceAccount {
                 seed,
@@ -2843,6 +2874,71 @@ pub fn multisig_account_vote_transanction(
     ))
 }
 
+/// Makes the packages published at `code_object` immutable. Only the owner of the object can do this.
+pub fn object_code_deployment_freeze_code_object(
+    code_object: AccountAddress,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("object_code_deployment").to_owned(),
+        ),
+        ident_str!("freeze_code_object").to_owned(),
+        vec![],
+        vec![bcs::to_bytes(&code_object).unwrap()],
+    ))
+}
+
+/// Creates a new object with a unique address derived from the publisher address and the sequence number of the
+/// transaction, and publishes the package to it. The publisher becomes the owner of the object.
+pub fn object_code_deployment_publish(
+    metadata_serialized: Vec<u8>,
+    code: Vec<Vec<u8>>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("object_code_deployment").to_owned(),
+        ),
+        ident_str!("publish").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&metadata_serialized).unwrap(),
+            bcs::to_bytes(&code).unwrap(),
+        ],
+    ))
+}
+
+/// Upgrades the package published at `code_object`. Only the owner of the object can do this.
+pub fn object_code_deployment_upgrade(
+    metadata_serialized: Vec<u8>,
+    code: Vec<Vec<u8>>,
+    code_object: AccountAddress,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("object_code_deployment").to_owned(),
+        ),
+        ident_str!("upgrade").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&metadata_serialized).unwrap(),
+            bcs::to_bytes(&code).unwrap(),
+            bcs::to_bytes(&code_object).unwrap(),
+        ],
+    ))
+}
+
 /// Entry function that can be used to transfer, if allow_ungated_transfer is set true.
 pub fn object_transfer_call(object: AccountAddress, to: AccountAddress) -> TransactionPayload {
     TransactionPayload::EntryFunction(EntryFunction::new(
@@ -4722,6 +4818,45 @@ mod decoder {
         }
     }
 
+    pub fn object_code_deployment_freeze_code_object(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::ObjectCodeDeploymentFreezeCodeObject {
+                code_object: bcs::from_bytes(script.args().get(0)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn object_code_deployment_publish(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::ObjectCodeDeploymentPublish {
+                metadata_serialized: bcs::from_bytes(script.args().get(0)?).ok()?,
+                code: bcs::from_bytes(script.args().get(1)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn object_code_deployment_upgrade(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::ObjectCodeDeploymentUpgrade {
+                metadata_serialized: bcs::from_bytes(script.args().get(0)?).ok()?,
+                code: bcs::from_bytes(script.args().get(1)?).ok()?,
+                code_object: bcs::from_bytes(script.args().get(2)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn object_transfer_call(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
         if let TransactionPayload::EntryFunction(script) = payload {
             Some(EntryFunctionCall::ObjectTransferCall {
@@ -5618,6 +5753,18 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "multisig_account_vote_transanction".to_string(),
             Box::new(decoder::multisig_account_vote_transanction),
         );
+        map.insert(
+            "object_code_deployment_freeze_code_object".to_string(),
+            Box::new(decoder::object_code_deployment_freeze_code_object),
+        );
+        map.insert(
+            "object_code_deployment_publish".to_string(),
+            Box::new(decoder::object_code_deployment_publish),
+        );
+        map.insert(
+            "object_code_deployment_upgrade".to_string(),
+            Box::new(decoder::object_code_deployment_upgrade),
+        );
         map.insert(
             "object_transfer_call".to_string(),
             Box::new(decoder::object_transfer_call),
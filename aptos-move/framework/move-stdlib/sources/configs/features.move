@@ -251,6 +251,83 @@ module std::features {
         is_enabled(AGGREGATOR_SNAPSHOTS)
     }
 
+    /// Whether private entry functions may use `aptos_framework::randomness`. Validators do not
+    /// produce randomness seeds yet, as a seed signed by the proposer alone can be biased by the
+    /// proposer, so the randomness API aborts even when this feature is enabled.
+    /// Lifetime: transient
+    const ON_CHAIN_RANDOMNESS: u64 = 33;
+
+    public fun get_on_chain_randomness_feature(): u64 { ON_CHAIN_RANDOMNESS }
+
+    public fun on_chain_randomness_enabled(): bool acquires Features {
+        is_enabled(ON_CHAIN_RANDOMNESS)
+    }
+
+    /// Whether the natives exposing the sender, signers, gas parameters and payload of the current
+    /// user transaction in `aptos_framework::transaction_context` are enabled.
+    /// Lifetime: transient
+    const TRANSACTION_CONTEXT_EXTENSION: u64 = 34;
+
+    public fun get_transaction_context_extension_feature(): u64 { TRANSACTION_CONTEXT_EXTENSION }
+
+    public fun transaction_context_extension_enabled(): bool acquires Features {
+        is_enabled(TRANSACTION_CONTEXT_EXTENSION)
+    }
+
+    /// Whether fungible stores can be upgraded to keep their balance in an aggregator, so that
+    /// concurrent deposits into the same store do not conflict with each other.
+    /// Lifetime: transient
+    const CONCURRENT_FUNGIBLE_BALANCE: u64 = 35;
+
+    public fun get_concurrent_fungible_balance_feature(): u64 { CONCURRENT_FUNGIBLE_BALANCE }
+
+    public fun concurrent_fungible_balance_enabled(): bool acquires Features {
+        is_enabled(CONCURRENT_FUNGIBLE_BALANCE)
+    }
+
+    /// Whether issuers of fungible assets can register Move functions which are dispatched to on
+    /// withdrawals, deposits and balance queries of their asset.
+    /// Lifetime: transient
+    const DISPATCHABLE_FUNGIBLE_ASSET: u64 = 36;
+
+    public fun get_dispatchable_fungible_asset_feature(): u64 { DISPATCHABLE_FUNGIBLE_ASSET }
+
+    public fun dispatchable_fungible_asset_enabled(): bool acquires Features {
+        is_enabled(DISPATCHABLE_FUNGIBLE_ASSET)
+    }
+
+    /// Whether packages can be published to and upgraded at object addresses via
+    /// `object_code_deployment`.
+    /// Lifetime: transient
+    const OBJECT_CODE_DEPLOYMENT: u64 = 37;
+
+    public fun get_object_code_deployment_feature(): u64 { OBJECT_CODE_DEPLOYMENT }
+
+    public fun object_code_deployment_enabled(): bool acquires Features {
+        is_enabled(OBJECT_CODE_DEPLOYMENT)
+    }
+
+    /// Whether transactions pay a per-block base fee, adjusted by block fullness, which is burned
+    /// while only the gas unit price above it is collected.
+    /// Lifetime: transient
+    const BASE_FEE_MARKET: u64 = 38;
+
+    public fun get_base_fee_market_feature(): u64 { BASE_FEE_MARKET }
+
+    public fun base_fee_market_enabled(): bool acquires Features {
+        is_enabled(BASE_FEE_MARKET)
+    }
+
+    /// Whether bytecode version v7 is enabled, which adds function values (closures).
+    /// Lifetime: transient
+    const VM_BINARY_FORMAT_V7: u64 = 39;
+
+    public fun get_vm_binary_format_v7(): u64 { VM_BINARY_FORMAT_V7 }
+
+    public fun allow_vm_binary_format_v7(): bool acquires Features {
+        is_enabled(VM_BINARY_FORMAT_V7)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
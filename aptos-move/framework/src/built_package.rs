@@ -281,6 +281,17 @@ impl BuiltPackage {
             })
     }
 
+    /// Returns an iterator for all compiled proper (non-script) modules together with their
+    /// names and source maps.
+    pub fn named_modules(&self) -> impl Iterator<Item = &NamedCompiledModule> {
+        self.package
+            .root_modules()
+            .filter_map(|unit| match &unit.unit {
+                CompiledUnit::Module(named_module) => Some(named_module),
+                CompiledUnit::Script(_) => None,
+            })
+    }
+
     /// Returns an iterator for all compiled proper (non-script) modules, including
     /// modules that are dependencies of the root modules.
     pub fn all_modules(&self) -> impl Iterator<Item = &CompiledModule> {
@@ -1,14 +1,19 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::natives::aggregator_natives::{helpers::get_handle, NativeAggregatorContext};
-use aptos_aggregator::aggregator_extension::{extension_error, AggregatorHandle, AggregatorID};
+use crate::natives::aggregator_natives::{
+    aggregator_v2::EUNSUPPORTED_AGGREGATOR_TYPE, helpers::get_handle, NativeAggregatorContext,
+};
+use aptos_aggregator::aggregator_extension::{
+    extension_error, AggregatorData, AggregatorHandle, AggregatorID,
+};
 use aptos_crypto::hash::DefaultHasher;
 use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
 use aptos_native_interface::{
-    safely_pop_arg, RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeResult,
+    safely_pop_arg, RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError,
+    SafeNativeResult,
 };
-use aptos_types::account_address::AccountAddress;
+use aptos_types::{account_address::AccountAddress, state_store::table::TableHandle};
 use move_vm_runtime::native_functions::NativeFunction;
 use move_vm_types::{
     loaded_data::runtime_types::Type,
@@ -41,13 +46,86 @@ fn native_new_aggregator(
     let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
     let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
 
+    let key = create_aggregator(
+        &mut aggregator_data,
+        &aggregator_context.txn_hash(),
+        handle,
+        limit,
+    )?;
+
+    Ok(smallvec![Value::struct_(Struct::pack(vec![
+        Value::address(handle.0),
+        Value::address(key.0),
+        Value::u128(limit),
+    ]))])
+}
+
+/***************************************************************************************************
+ * native fun new_aggregator_v2<IntElement>(aggregator_factory: &mut AggregatorFactory, max_value: IntElement): Aggregator<IntElement>;
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_new_aggregator_v2(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(ty_args.len(), 1);
+    debug_assert_eq!(args.len(), 2);
+
+    context.charge(AGGREGATOR_FACTORY_NEW_AGGREGATOR_V2_BASE)?;
+
+    // Aggregators v2 are generic over the value type, so keep the Move value
+    // of `max_value` to store it in the returned struct as is.
+    let (max_value, max_value_value) = match ty_args[0] {
+        Type::U64 => {
+            let max_value = safely_pop_arg!(args, u64);
+            (max_value as u128, Value::u64(max_value))
+        },
+        Type::U128 => {
+            let max_value = safely_pop_arg!(args, u128);
+            (max_value, Value::u128(max_value))
+        },
+        _ => {
+            return Err(SafeNativeError::Abort {
+                abort_code: EUNSUPPORTED_AGGREGATOR_TYPE,
+            })
+        },
+    };
+    let handle = get_handle(&safely_pop_arg!(args, StructRef))?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    let key = create_aggregator(
+        &mut aggregator_data,
+        &aggregator_context.txn_hash(),
+        handle,
+        max_value,
+    )?;
+
+    Ok(smallvec![Value::struct_(Struct::pack(vec![
+        Value::address(handle.0),
+        Value::address(key.0),
+        max_value_value,
+    ]))])
+}
+
+/// Creates a new aggregator with a fresh key under `handle`, and returns the key.
+fn create_aggregator(
+    aggregator_data: &mut AggregatorData,
+    txn_hash: &[u8],
+    handle: TableHandle,
+    limit: u128,
+) -> SafeNativeResult<AggregatorHandle> {
     // Every aggregator instance uses a unique key in its id. Here we can reuse
     // the strategy from `table` implementation: taking hash of transaction and
     // number of aggregator instances created so far.
     let num_aggregators_len = aggregator_data.num_aggregators() as u32;
 
     let mut hasher = DefaultHasher::new(&[0_u8; 0]);
-    hasher.update(&aggregator_context.txn_hash());
+    hasher.update(txn_hash);
     hasher.update(&num_aggregators_len.to_be_bytes());
     let hash = hasher.finish().to_vec();
     let key = AggregatorHandle(
@@ -57,12 +135,7 @@ fn native_new_aggregator(
 
     let id = AggregatorID::new(handle, key);
     aggregator_data.create_new_aggregator(id, limit);
-
-    Ok(smallvec![Value::struct_(Struct::pack(vec![
-        Value::address(handle.0),
-        Value::address(key.0),
-        Value::u128(limit),
-    ]))])
+    Ok(key)
 }
 
 /***************************************************************************************************
@@ -72,7 +145,10 @@ fn native_new_aggregator(
 pub fn make_all(
     builder: &SafeNativeBuilder,
 ) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
-    let natives = [("new_aggregator", native_new_aggregator as RawSafeNative)];
+    let natives = [
+        ("new_aggregator", native_new_aggregator as RawSafeNative),
+        ("new_aggregator_v2", native_new_aggregator_v2),
+    ];
 
     builder.make_named_natives(natives)
 }
@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::natives::{
-    aggregator_natives::helpers_v2::{
-        aggregator_snapshot_value_as_bytes, aggregator_snapshot_value_as_u128,
-        aggregator_snapshot_value_as_u64, string_to_bytes,
+    aggregator_natives::{
+        helpers_v2::{
+            aggregator_snapshot_value_as_bytes, aggregator_snapshot_value_as_u128,
+            aggregator_snapshot_value_as_u64, aggregator_v2_info, string_to_bytes,
+            u128_as_integer_value, unpack_aggregator_v2_struct,
+        },
+        NativeAggregatorContext,
     },
     AccountAddress,
 };
@@ -28,6 +32,31 @@ pub const EUNSUPPORTED_AGGREGATOR_SNAPSHOT_TYPE: u64 = 0x03_0005;
 /// The aggregator snapshots feature is not enabled.
 pub const EAGGREGATOR_SNAPSHOTS_NOT_ENABLED: u64 = 0x03_0006;
 
+/// The generic type supplied to the aggregator is not supported.
+pub const EUNSUPPORTED_AGGREGATOR_TYPE: u64 = 0x03_0007;
+
+/// Checks if the type argument `type_arg` can be used as an aggregator value,
+/// i.e. it is either `u64` or `u128`.
+fn assert_aggregator_type(type_arg: &Type) -> SafeNativeResult<()> {
+    match type_arg {
+        Type::U64 | Type::U128 => Ok(()),
+        _ => Err(SafeNativeError::Abort {
+            abort_code: EUNSUPPORTED_AGGREGATOR_TYPE,
+        }),
+    }
+}
+
+/// Pops an aggregator value of type `type_arg` from the arguments and widens it to `u128`.
+fn pop_aggregator_value(args: &mut VecDeque<Value>, type_arg: &Type) -> SafeNativeResult<u128> {
+    match type_arg {
+        Type::U64 => Ok(safely_pop_arg!(args, u64) as u128),
+        Type::U128 => Ok(safely_pop_arg!(args, u128)),
+        _ => Err(SafeNativeError::Abort {
+            abort_code: EUNSUPPORTED_AGGREGATOR_TYPE,
+        }),
+    }
+}
+
 /// Checks if the type argument `type_arg` is a string type.
 fn is_string_type(context: &SafeNativeContext, type_arg: &Type) -> SafeNativeResult<bool> {
     let ty = context.deref().type_to_fully_annotated_layout(type_arg)?;
@@ -212,6 +241,102 @@ fn native_string_concat(
     Ok(smallvec![move_snapshot_value])
 }
 
+/***************************************************************************************************
+ * native fun try_add<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement): bool;
+ **************************************************************************************************/
+
+fn native_try_add(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(ty_args.len(), 1);
+    debug_assert_eq!(args.len(), 2);
+    context.charge(AGGREGATOR_V2_TRY_ADD_BASE)?;
+
+    let value = pop_aggregator_value(&mut args, &ty_args[0])?;
+    let (id, max_value) = aggregator_v2_info(&safely_pop_arg!(args, StructRef), &ty_args[0])?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    let aggregator = aggregator_data.get_aggregator(id, max_value)?;
+
+    // Additions do not read the value, so concurrent additions to the same
+    // aggregator do not conflict with each other.
+    let success = aggregator.try_add(value)?;
+    Ok(smallvec![Value::bool(success)])
+}
+
+/***************************************************************************************************
+ * native fun try_sub<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement): bool;
+ **************************************************************************************************/
+
+fn native_try_sub(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(ty_args.len(), 1);
+    debug_assert_eq!(args.len(), 2);
+    context.charge(AGGREGATOR_V2_TRY_SUB_BASE)?;
+
+    let value = pop_aggregator_value(&mut args, &ty_args[0])?;
+    let (id, max_value) = aggregator_v2_info(&safely_pop_arg!(args, StructRef), &ty_args[0])?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    let aggregator = aggregator_data.get_aggregator(id, max_value)?;
+
+    let success = aggregator.try_sub(aggregator_context.resolver, &id, value)?;
+    Ok(smallvec![Value::bool(success)])
+}
+
+/***************************************************************************************************
+ * native fun read<IntElement>(aggregator: &Aggregator<IntElement>): IntElement;
+ **************************************************************************************************/
+
+fn native_read(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(ty_args.len(), 1);
+    debug_assert_eq!(args.len(), 1);
+    context.charge(AGGREGATOR_V2_READ_BASE)?;
+    assert_aggregator_type(&ty_args[0])?;
+
+    let (id, max_value) = aggregator_v2_info(&safely_pop_arg!(args, StructRef), &ty_args[0])?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    let aggregator = aggregator_data.get_aggregator(id, max_value)?;
+
+    let value = aggregator.read_and_materialize(aggregator_context.resolver, &id)?;
+    Ok(smallvec![u128_as_integer_value(value, &ty_args[0])?])
+}
+
+/***************************************************************************************************
+ * native fun destroy<IntElement>(aggregator: Aggregator<IntElement>);
+ **************************************************************************************************/
+
+fn native_destroy(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(ty_args.len(), 1);
+    debug_assert_eq!(args.len(), 1);
+    context.charge(AGGREGATOR_V2_DESTROY_BASE)?;
+
+    let id = unpack_aggregator_v2_struct(safely_pop_arg!(args, Struct))?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    aggregator_data.remove_aggregator(id);
+
+    Ok(smallvec![])
+}
+
 /***************************************************************************************************
  * module
  **************************************************************************************************/
@@ -224,6 +349,10 @@ pub fn make_all(
         ("copy_snapshot", native_copy_snapshot),
         ("read_snapshot", native_read_snapshot),
         ("string_concat", native_string_concat),
+        ("try_add", native_try_add),
+        ("try_sub", native_try_sub),
+        ("read", native_read),
+        ("destroy", native_destroy),
     ];
     builder.make_named_natives(natives)
 }
@@ -1,12 +1,75 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 use crate::natives::aggregator_natives::helpers::get_aggregator_field;
-use aptos_aggregator::aggregator_extension::extension_error;
+use aptos_aggregator::aggregator_extension::{extension_error, AggregatorHandle, AggregatorID};
+use aptos_types::{account_address::AccountAddress, state_store::table::TableHandle};
 use move_binary_format::errors::PartialVMResult;
-use move_vm_types::values::{Struct, StructRef, Value};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    values::{Struct, StructRef, Value},
+};
 
 const VALUE_FIELD_INDEX: usize = 0;
 
+/// Indices of `handle`, `key` and `max_value` fields in the `Aggregator` Move
+/// struct.
+const HANDLE_FIELD_INDEX: usize = 0;
+const KEY_FIELD_INDEX: usize = 1;
+const MAX_VALUE_FIELD_INDEX: usize = 2;
+
+/// Converts an integer Move value of type `ty` (either `u64` or `u128`) to `u128`.
+pub(crate) fn integer_value_as_u128(value: Value, ty: &Type) -> PartialVMResult<u128> {
+    match ty {
+        Type::U64 => Ok(value.value_as::<u64>()? as u128),
+        Type::U128 => value.value_as::<u128>(),
+        _ => Err(extension_error("unsupported aggregator type")),
+    }
+}
+
+/// Converts `u128` to an integer Move value of type `ty` (either `u64` or `u128`).
+pub(crate) fn u128_as_integer_value(value: u128, ty: &Type) -> PartialVMResult<Value> {
+    match ty {
+        Type::U64 => u64::try_from(value)
+            .map(Value::u64)
+            .map_err(|_| extension_error("aggregator value does not fit into u64")),
+        Type::U128 => Ok(Value::u128(value)),
+        _ => Err(extension_error("unsupported aggregator type")),
+    }
+}
+
+/// Returns ID and a maximum value of aggregator based on a reference to
+/// `Aggregator<IntElement>` Move struct, where `ty` is the type of `IntElement`.
+pub(crate) fn aggregator_v2_info(
+    aggregator: &StructRef,
+    ty: &Type,
+) -> PartialVMResult<(AggregatorID, u128)> {
+    let handle =
+        get_aggregator_field(aggregator, HANDLE_FIELD_INDEX)?.value_as::<AccountAddress>()?;
+    let key = get_aggregator_field(aggregator, KEY_FIELD_INDEX)?.value_as::<AccountAddress>()?;
+    let max_value =
+        integer_value_as_u128(get_aggregator_field(aggregator, MAX_VALUE_FIELD_INDEX)?, ty)?;
+    Ok((
+        AggregatorID::new(TableHandle(handle), AggregatorHandle(key)),
+        max_value,
+    ))
+}
+
+/// Given an `Aggregator<IntElement>` Move struct, unpacks it and returns its ID.
+pub(crate) fn unpack_aggregator_v2_struct(aggregator: Struct) -> PartialVMResult<AggregatorID> {
+    let mut fields = aggregator.unpack()?;
+    let mut pop_address = |msg: &str| {
+        fields.next().map_or(Err(extension_error(msg)), |v| {
+            v.value_as::<AccountAddress>()
+        })
+    };
+    let handle = pop_address("unable to pop `handle` field")?;
+    let key = pop_address("unable to pop `key` field")?;
+    Ok(AggregatorID::new(
+        TableHandle(handle),
+        AggregatorHandle(key),
+    ))
+}
+
 /// Returns ID of aggregator snapshot based on a reference to `AggregatorSnapshot` Move struct.
 pub(crate) fn aggregator_snapshot_value_as_u128(
     aggregator_snapshot: &StructRef,
@@ -0,0 +1,58 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::function_info::extract_function_info;
+use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
+use aptos_native_interface::{
+    safely_pop_arg, RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError,
+    SafeNativeResult,
+};
+use move_vm_runtime::native_functions::NativeFunction;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    values::{Struct, Value},
+};
+use smallvec::SmallVec;
+use std::collections::VecDeque;
+
+/***************************************************************************************************
+ * native fun dispatchable_withdraw / dispatchable_deposit / dispatchable_derived_balance
+ *
+ *   Calls the function described by the last argument in place of the native, passing on all
+ *   other arguments. The VM checks that the target is compatible with the native.
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_dispatch(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(DISPATCHABLE_FUNGIBLE_ASSET_DISPATCH_BASE)?;
+
+    let (module_name, func_name) = extract_function_info(safely_pop_arg!(args, Struct))?;
+
+    Err(SafeNativeError::FunctionDispatch {
+        module_name,
+        func_name,
+        ty_args,
+        args: args.into_iter().collect(),
+    })
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(
+    builder: &SafeNativeBuilder,
+) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
+    let natives = [
+        ("dispatchable_withdraw", native_dispatch as RawSafeNative),
+        ("dispatchable_deposit", native_dispatch),
+        ("dispatchable_derived_balance", native_dispatch),
+    ];
+
+    builder.make_named_natives(natives)
+}
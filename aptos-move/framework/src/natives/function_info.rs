@@ -0,0 +1,125 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
+use aptos_native_interface::{
+    safely_pop_arg, RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError,
+    SafeNativeResult,
+};
+use move_core_types::{
+    account_address::AccountAddress, gas_algebra::NumBytes, identifier::Identifier,
+    language_storage::ModuleId, vm_status::StatusCode,
+};
+use move_vm_runtime::native_functions::NativeFunction;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::PartialVMError,
+    values::{Struct, Value, VectorRef},
+};
+use smallvec::{smallvec, SmallVec};
+use std::collections::VecDeque;
+
+/// Gets the identifier embedded in a Move `string::String` struct.
+fn get_identifier(v: Value) -> SafeNativeResult<Identifier> {
+    let bytes = v
+        .value_as::<Struct>()?
+        .unpack()?
+        .next()
+        .ok_or_else(|| PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR))?
+        .value_as::<Vec<u8>>()?;
+    String::from_utf8(bytes)
+        .ok()
+        .and_then(|name| Identifier::new(name).ok())
+        .ok_or_else(|| {
+            SafeNativeError::InvariantViolation(PartialVMError::new(
+                StatusCode::INTERNAL_TYPE_ERROR,
+            ))
+        })
+}
+
+/// Gets the module and function name of a Move `function_info::FunctionInfo` struct. The names
+/// are validated when the struct is created, so invalid names are an invariant violation.
+pub(crate) fn extract_function_info(v: Struct) -> SafeNativeResult<(ModuleId, Identifier)> {
+    let mut fields = v.unpack()?.collect::<Vec<_>>();
+    if fields.len() != 3 {
+        return Err(SafeNativeError::InvariantViolation(PartialVMError::new(
+            StatusCode::INTERNAL_TYPE_ERROR,
+        )));
+    }
+    let function_name = get_identifier(fields.pop().unwrap())?;
+    let module_name = get_identifier(fields.pop().unwrap())?;
+    let module_address = fields.pop().unwrap().value_as::<AccountAddress>()?;
+    Ok((ModuleId::new(module_address, module_name), function_name))
+}
+
+/***************************************************************************************************
+ * native fun is_identifier
+ *
+ *   gas cost: base_cost + per_byte * string_length
+ *
+ **************************************************************************************************/
+fn native_is_identifier(
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert!(args.len() == 1);
+
+    let s_arg = safely_pop_arg!(args, VectorRef);
+    let s_ref = s_arg.as_bytes_ref();
+
+    context.charge(
+        FUNCTION_INFO_IS_IDENTIFIER_BASE
+            + FUNCTION_INFO_IS_IDENTIFIER_PER_BYTE * NumBytes::new(s_ref.len() as u64),
+    )?;
+
+    let result = std::str::from_utf8(&s_ref).map_or(false, Identifier::is_valid);
+    Ok(smallvec![Value::bool(result)])
+}
+
+/***************************************************************************************************
+ * native fun check_dispatch_type_compatibility_impl
+ *
+ *   Returns whether the function described by the second argument may be dispatched to from the
+ *   native function described by the first argument. See `NativeResult::CallFunction` in the
+ *   Move VM for the requirements on the signatures.
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_check_dispatch_type_compatibility_impl(
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert!(args.len() == 2);
+
+    context.charge(FUNCTION_INFO_CHECK_DISPATCH_TYPE_COMPATIBILITY_IMPL_BASE)?;
+
+    let (target_module, target_function) = extract_function_info(safely_pop_arg!(args, Struct))?;
+    let (native_module, native_function) = extract_function_info(safely_pop_arg!(args, Struct))?;
+
+    let result = context.is_dispatch_compatible(
+        (&native_module, native_function.as_ident_str()),
+        (&target_module, target_function.as_ident_str()),
+    )?;
+    Ok(smallvec![Value::bool(result)])
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(
+    builder: &SafeNativeBuilder,
+) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
+    let natives = [
+        ("is_identifier", native_is_identifier as RawSafeNative),
+        (
+            "check_dispatch_type_compatibility_impl",
+            native_check_dispatch_type_compatibility_impl,
+        ),
+    ];
+
+    builder.make_named_natives(natives)
+}
@@ -9,10 +9,13 @@ pub mod code;
 pub mod create_signer;
 pub mod cryptography;
 pub mod debug;
+pub mod dispatchable_fungible_asset;
 pub mod event;
+pub mod function_info;
 pub mod hash;
 mod helpers;
 pub mod object;
+pub mod randomness;
 pub mod state_storage;
 pub mod string_utils;
 pub mod transaction_context;
@@ -78,8 +81,14 @@ pub fn all_natives(
     add_natives_from_module!("aggregator_factory", aggregator_factory::make_all(builder));
     add_natives_from_module!("aggregator_v2", aggregator_v2::make_all(builder));
     add_natives_from_module!("object", object::make_all(builder));
+    add_natives_from_module!("randomness", randomness::make_all(builder));
     add_natives_from_module!("debug", debug::make_all(builder));
     add_natives_from_module!("string_utils", string_utils::make_all(builder));
+    add_natives_from_module!("function_info", function_info::make_all(builder));
+    add_natives_from_module!(
+        "dispatchable_fungible_asset",
+        dispatchable_fungible_asset::make_all(builder)
+    );
 
     make_table_from_iter(framework_addr, natives)
 }
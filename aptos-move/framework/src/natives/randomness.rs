@@ -0,0 +1,91 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
+use aptos_native_interface::{
+    RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeResult,
+};
+use better_any::{Tid, TidAble};
+use move_vm_runtime::native_functions::NativeFunction;
+use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
+use smallvec::{smallvec, SmallVec};
+use std::collections::VecDeque;
+
+/// The randomness context extension. The VM marks it as unbiasable when the transaction runs a
+/// private entry function, which is the only context where the randomness API may be used.
+#[derive(Default, Tid)]
+pub struct RandomnessContext {
+    /// The number of randomness API calls made by this transaction so far
+    txn_counter: u64,
+    unbiasable: bool,
+}
+
+impl RandomnessContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mark_unbiasable(&mut self) {
+        self.unbiasable = true;
+    }
+
+    pub fn is_unbiasable(&self) -> bool {
+        self.unbiasable
+    }
+}
+
+/***************************************************************************************************
+ * native fun fetch_and_increment_txn_counter
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_fetch_and_increment_txn_counter(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(RANDOMNESS_FETCH_AND_INCREMENT_TXN_COUNTER_BASE)?;
+
+    let randomness_context = context.extensions_mut().get_mut::<RandomnessContext>();
+    let counter = randomness_context.txn_counter;
+    randomness_context.txn_counter += 1;
+
+    Ok(smallvec![Value::vector_u8(counter.to_le_bytes().to_vec())])
+}
+
+/***************************************************************************************************
+ * native fun is_unbiasable
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_is_unbiasable(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(RANDOMNESS_IS_UNBIASABLE_BASE)?;
+
+    let randomness_context = context.extensions().get::<RandomnessContext>();
+
+    Ok(smallvec![Value::bool(randomness_context.is_unbiasable())])
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(
+    builder: &SafeNativeBuilder,
+) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
+    let natives = [
+        (
+            "fetch_and_increment_txn_counter",
+            native_fetch_and_increment_txn_counter as RawSafeNative,
+        ),
+        ("is_unbiasable", native_is_unbiasable),
+    ];
+
+    builder.make_named_natives(natives)
+}
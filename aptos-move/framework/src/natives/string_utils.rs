@@ -18,7 +18,7 @@ use move_core_types::{
 use move_vm_runtime::native_functions::NativeFunction;
 use move_vm_types::{
     loaded_data::runtime_types::Type,
-    values::{Reference, Struct, Value, Vector, VectorRef},
+    values::{Closure, Reference, Struct, Value, Vector, VectorRef},
 };
 use smallvec::{smallvec, SmallVec};
 use std::{collections::VecDeque, fmt::Write, ops::Deref};
@@ -303,6 +303,10 @@ fn native_format_impl(
             )?;
             out.push('}');
         },
+        MoveTypeLayout::Function => {
+            let closure = val.value_as::<Closure>()?;
+            write!(out, "{}::{}", closure.module_id(), closure.fun_name()).unwrap();
+        },
     };
     if context.include_int_type {
         write!(out, "{}", suffix).unwrap();
@@ -3,16 +3,29 @@
 
 use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
 use aptos_native_interface::{
-    RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeResult,
+    RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError, SafeNativeResult,
+};
+use aptos_types::transaction::{
+    authenticator::{AuthenticationKey, AuthenticationKeyPreimage},
+    user_transaction_context::UserTransactionContext,
 };
-use aptos_types::transaction::authenticator::{AuthenticationKey, AuthenticationKeyPreimage};
 use better_any::{Tid, TidAble};
-use move_core_types::account_address::AccountAddress;
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_algebra::{NumArgs, NumBytes},
+};
 use move_vm_runtime::native_functions::NativeFunction;
-use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    values::{Value, Vector},
+};
 use smallvec::{smallvec, SmallVec};
 use std::collections::VecDeque;
 
+/// Abort code when the user transaction context is not available, as the current session does
+/// not belong to a user transaction (0x03 == INVALID_STATE)
+const ETRANSACTION_CONTEXT_NOT_AVAILABLE: u64 = 0x03_0003;
+
 /// The native transaction context extension. This needs to be attached to the
 /// NativeContextExtensions value which is passed into session functions, so its accessible from
 /// natives of this extension.
@@ -23,23 +36,39 @@ pub struct NativeTransactionContext {
     auid_counter: u64,
     script_hash: Vec<u8>,
     chain_id: u8,
+    /// The data of the user transaction this session belongs to, if any.
+    user_transaction_context_opt: Option<UserTransactionContext>,
 }
 
 impl NativeTransactionContext {
     /// Create a new instance of a native transaction context. This must be passed in via an
     /// extension into VM session functions.
-    pub fn new(txn_hash: Vec<u8>, script_hash: Vec<u8>, chain_id: u8) -> Self {
+    pub fn new(
+        txn_hash: Vec<u8>,
+        script_hash: Vec<u8>,
+        chain_id: u8,
+        user_transaction_context_opt: Option<UserTransactionContext>,
+    ) -> Self {
         Self {
             txn_hash,
             auid_counter: 0,
             script_hash,
             chain_id,
+            user_transaction_context_opt,
         }
     }
 
     pub fn chain_id(&self) -> u8 {
         self.chain_id
     }
+
+    fn user_transaction_context(&self) -> SafeNativeResult<&UserTransactionContext> {
+        self.user_transaction_context_opt
+            .as_ref()
+            .ok_or(SafeNativeError::Abort {
+                abort_code: ETRANSACTION_CONTEXT_NOT_AVAILABLE,
+            })
+    }
 }
 
 /***************************************************************************************************
@@ -111,6 +140,211 @@ fn native_get_script_hash(
     )])
 }
 
+/***************************************************************************************************
+ * native fun sender_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_sender_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_SENDER_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let user_transaction_context = transaction_context.user_transaction_context()?;
+
+    Ok(smallvec![Value::address(user_transaction_context.sender())])
+}
+
+/***************************************************************************************************
+ * native fun secondary_signers_internal
+ *
+ *   gas cost: base_cost + per_signer * num_secondary_signers
+ *
+ **************************************************************************************************/
+fn native_secondary_signers_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_SECONDARY_SIGNERS_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let secondary_signers = transaction_context
+        .user_transaction_context()?
+        .secondary_signers()
+        .to_vec();
+
+    context.charge(
+        TRANSACTION_CONTEXT_SECONDARY_SIGNERS_PER_SIGNER
+            * NumArgs::new(secondary_signers.len() as u64),
+    )?;
+
+    Ok(smallvec![Value::vector_address(secondary_signers)])
+}
+
+/***************************************************************************************************
+ * native fun gas_payer_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_gas_payer_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_GAS_PAYER_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let user_transaction_context = transaction_context.user_transaction_context()?;
+
+    Ok(smallvec![Value::address(
+        user_transaction_context.gas_payer()
+    )])
+}
+
+/***************************************************************************************************
+ * native fun max_gas_amount_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_max_gas_amount_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_MAX_GAS_AMOUNT_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let user_transaction_context = transaction_context.user_transaction_context()?;
+
+    Ok(smallvec![Value::u64(
+        user_transaction_context.max_gas_amount()
+    )])
+}
+
+/***************************************************************************************************
+ * native fun gas_unit_price_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_gas_unit_price_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_GAS_UNIT_PRICE_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let user_transaction_context = transaction_context.user_transaction_context()?;
+
+    Ok(smallvec![Value::u64(
+        user_transaction_context.gas_unit_price()
+    )])
+}
+
+/***************************************************************************************************
+ * native fun chain_id_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_chain_id_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_CHAIN_ID_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let user_transaction_context = transaction_context.user_transaction_context()?;
+
+    Ok(smallvec![Value::u8(user_transaction_context.chain_id())])
+}
+
+/***************************************************************************************************
+ * native fun has_entry_function_payload_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_has_entry_function_payload_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_ENTRY_FUNCTION_PAYLOAD_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let user_transaction_context = transaction_context.user_transaction_context()?;
+
+    Ok(smallvec![Value::bool(
+        user_transaction_context.entry_function_payload().is_some()
+    )])
+}
+
+/***************************************************************************************************
+ * native fun entry_function_payload_internal
+ *
+ *   Returns the account address, module name, function name, type argument names and arguments
+ *   of the entry function called by the current user transaction.
+ *
+ *   gas cost: base_cost + per_byte * total_bytes_of_names_and_args
+ *
+ **************************************************************************************************/
+fn native_entry_function_payload_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_ENTRY_FUNCTION_PAYLOAD_BASE)?;
+
+    let transaction_context = context.extensions().get::<NativeTransactionContext>();
+    let payload = transaction_context
+        .user_transaction_context()?
+        .entry_function_payload()
+        .cloned()
+        .ok_or(SafeNativeError::Abort {
+            abort_code: ETRANSACTION_CONTEXT_NOT_AVAILABLE,
+        })?;
+
+    let num_bytes = payload.module_name.len()
+        + payload.function_name.len()
+        + payload.ty_arg_names.iter().map(String::len).sum::<usize>()
+        + payload.args.iter().map(Vec::len).sum::<usize>();
+    context.charge(
+        TRANSACTION_CONTEXT_ENTRY_FUNCTION_PAYLOAD_PER_BYTE * NumBytes::new(num_bytes as u64),
+    )?;
+
+    let bytes_ty = Type::Vector(Box::new(Type::U8));
+    let ty_arg_names = Vector::pack(
+        &bytes_ty,
+        payload
+            .ty_arg_names
+            .into_iter()
+            .map(|name| Value::vector_u8(name.into_bytes()))
+            .collect(),
+    )?;
+    let args = Vector::pack(
+        &bytes_ty,
+        payload.args.into_iter().map(Value::vector_u8).collect(),
+    )?;
+
+    Ok(smallvec![
+        Value::address(payload.account_address),
+        Value::vector_u8(payload.module_name.into_bytes()),
+        Value::vector_u8(payload.function_name.into_bytes()),
+        ty_arg_names,
+        args,
+    ])
+}
+
 /***************************************************************************************************
  * module
  *
@@ -122,6 +356,23 @@ pub fn make_all(
         ("get_script_hash", native_get_script_hash as RawSafeNative),
         ("generate_unique_address", native_generate_unique_address),
         ("get_txn_hash", native_get_txn_hash),
+        ("sender_internal", native_sender_internal),
+        (
+            "secondary_signers_internal",
+            native_secondary_signers_internal,
+        ),
+        ("gas_payer_internal", native_gas_payer_internal),
+        ("max_gas_amount_internal", native_max_gas_amount_internal),
+        ("gas_unit_price_internal", native_gas_unit_price_internal),
+        ("chain_id_internal", native_chain_id_internal),
+        (
+            "has_entry_function_payload_internal",
+            native_has_entry_function_payload_internal,
+        ),
+        (
+            "entry_function_payload_internal",
+            native_entry_function_payload_internal,
+        ),
     ];
 
     builder.make_named_natives(natives)
@@ -23,6 +23,7 @@ use aptos_framework::ReleaseBundle;
 use aptos_logger::{prelude::*, telemetry_log_writer::TelemetryLog, Level, LoggerFilterUpdater};
 use aptos_state_sync_driver::driver_factory::StateSyncRuntimes;
 use aptos_types::chain_id::ChainId;
+use aptos_validator_transaction_pool::VTxnPoolState;
 use clap::Parser;
 use futures::channel::mpsc;
 use hex::{FromHex, FromHexError};
@@ -473,6 +474,9 @@ pub fn setup_environment_and_start_node(
     // Set the Aptos VM configurations
     utils::set_aptos_vm_configurations(&node_config);
 
+    // Set the compression options of the network protocols
+    network::set_network_compression_options(&node_config)?;
+
     // Obtain the chain_id from the DB
     let chain_id = utils::fetch_chain_id(&db_rw)?;
 
@@ -541,6 +545,10 @@ pub fn setup_environment_and_start_node(
             peers_and_metadata,
         );
 
+    // Create the validator transaction pool, which producers of validator transactions put their
+    // transactions into and consensus pulls them from
+    let vtxn_pool = VTxnPoolState::default();
+
     // Create the consensus runtime (this blocks on state sync first)
     let consensus_runtime = consensus_network_interfaces.map(|consensus_network_interfaces| {
         // Wait until state sync has been initialized
@@ -556,6 +564,7 @@ pub fn setup_environment_and_start_node(
             consensus_network_interfaces,
             consensus_notifier,
             consensus_to_mempool_sender,
+            vtxn_pool,
         )
     });
 
@@ -15,9 +15,12 @@ use aptos_network::{
         interface::{NetworkClient, NetworkServiceEvents},
         storage::PeersAndMetadata,
     },
-    protocols::network::{
-        NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
-        NetworkServiceConfig,
+    protocols::{
+        network::{
+            NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
+            NetworkServiceConfig,
+        },
+        wire::handshake::v1::{set_zstd_protocol_options_once, ZstdProtocolOptions},
     },
     ProtocolId,
 };
@@ -30,6 +33,9 @@ use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use tokio::runtime::Runtime;
 
+/// The file (in the data directory) that persists the peer bans of the public network
+const PUBLIC_NETWORK_PEER_BANS_FILE: &str = "public_network_peer_bans.json";
+
 /// A simple struct that holds both the network client
 /// and receiving interfaces for an application.
 pub struct ApplicationNetworkInterfaces<T> {
@@ -45,6 +51,13 @@ struct ApplicationNetworkHandle<T> {
     pub network_events: NetworkEvents<T>,
 }
 
+/// Sets the compression options of the zstd encoded network protocols
+pub fn set_network_compression_options(node_config: &NodeConfig) -> anyhow::Result<()> {
+    let options = ZstdProtocolOptions::from_config(&node_config.network_compression)?;
+    set_zstd_protocol_options_once(options);
+    Ok(())
+}
+
 /// TODO: make this configurable (e.g., for compression)
 /// Returns the network application config for the consensus client and service
 pub fn consensus_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
@@ -65,7 +78,12 @@ pub fn consensus_network_configuration(node_config: &NodeConfig) -> NetworkAppli
 
 /// Returns the network application config for the mempool client and service
 pub fn mempool_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
-    let direct_send_protocols = vec![ProtocolId::MempoolDirectSend];
+    let direct_send_protocols = vec![
+        ProtocolId::MempoolDirectSendZstd,
+        ProtocolId::MempoolDirectSend,
+        // Least preferred, so only announces that private transactions can be broadcast to us
+        ProtocolId::MempoolPrivateDirectSend,
+    ];
     let rpc_protocols = vec![]; // Mempool does not use RPC
 
     let network_client_config =
@@ -104,7 +122,10 @@ pub fn peer_monitoring_network_configuration(node_config: &NodeConfig) -> Networ
 /// Returns the network application config for the storage service client and server
 pub fn storage_service_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
     let direct_send_protocols = vec![]; // The storage service does not use direct send
-    let rpc_protocols = vec![ProtocolId::StorageServiceRpc];
+    let rpc_protocols = vec![
+        ProtocolId::StorageServiceRpcZstd,
+        ProtocolId::StorageServiceRpc,
+    ];
     let max_network_channel_size = node_config
         .state_sync
         .storage_service
@@ -148,7 +169,27 @@ fn extract_network_ids(node_config: &NodeConfig) -> Vec<NetworkId> {
 /// Creates the global peers and metadata struct
 pub fn create_peers_and_metadata(node_config: &NodeConfig) -> Arc<PeersAndMetadata> {
     let network_ids = extract_network_ids(node_config);
-    PeersAndMetadata::new(&network_ids)
+    let peers_and_metadata = PeersAndMetadata::new(&network_ids);
+
+    // Configure the peer reputations of each network. Bans on public
+    // networks are persisted, so that they survive restarts.
+    for network_config in extract_network_configs(node_config) {
+        let network_id = network_config.network_id;
+        let bans_file = if network_id.is_public_network() {
+            Some(
+                node_config
+                    .get_data_dir()
+                    .join(PUBLIC_NETWORK_PEER_BANS_FILE),
+            )
+        } else {
+            None
+        };
+        peers_and_metadata
+            .set_peer_reputation_config(&network_id, network_config.peer_reputation, bans_file)
+            .expect("Failed to set the peer reputation config!");
+    }
+
+    peers_and_metadata
 }
 
 /// Sets up all networks and returns the appropriate application network interfaces
@@ -20,6 +20,7 @@ use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_time_service::TimeService;
 use aptos_types::chain_id::ChainId;
+use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::channel::{mpsc, mpsc::Sender};
 use std::{sync::Arc, time::Instant};
 use tokio::runtime::Runtime;
@@ -83,6 +84,7 @@ pub fn start_consensus_runtime(
     consensus_network_interfaces: ApplicationNetworkInterfaces<ConsensusMsg>,
     consensus_notifier: ConsensusNotifier,
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
+    vtxn_pool: VTxnPoolState,
 ) -> Runtime {
     let instant = Instant::now();
     let consensus_runtime = aptos_consensus::consensus_provider::start_consensus(
@@ -94,6 +96,7 @@ pub fn start_consensus_runtime(
         db_rw,
         consensus_reconfig_subscription
             .expect("Consensus requires a reconfiguration subscription!"),
+        vtxn_pool,
     );
     debug!("Consensus started in {} ms", instant.elapsed().as_millis());
     consensus_runtime
@@ -9,6 +9,7 @@ use crate::{
     },
     utils,
 };
+use aptos_crypto::HashValue;
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
@@ -72,6 +73,13 @@ pub struct ApiConfig {
     pub runtime_worker_multiplier: usize,
     /// Configs for computing unit gas price estimation
     pub gas_estimation: GasEstimationConfig,
+    /// Enables the mempool admin endpoints, which are authenticated by `admin_passcode_sha3_256`
+    #[serde(default = "default_disabled")]
+    pub mempool_admin_enabled: bool,
+    /// The hex encoded SHA3-256 hash of the passcode that admin requests must present as a
+    /// bearer token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_passcode_sha3_256: Option<String>,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -116,6 +124,8 @@ impl Default for ApiConfig {
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
+            mempool_admin_enabled: default_disabled(),
+            admin_passcode_sha3_256: None,
         }
     }
 }
@@ -163,6 +173,22 @@ impl ConfigSanitizer for ApiConfig {
             ));
         }
 
+        // Verify that the admin endpoints are authenticated by a valid passcode hash
+        if api_config.mempool_admin_enabled {
+            let passcode_hash = api_config.admin_passcode_sha3_256.as_ref().ok_or_else(|| {
+                Error::ConfigSanitizerFailed(
+                    sanitizer_name.clone(),
+                    "The mempool admin endpoints are enabled, but no admin passcode is set!".into(),
+                )
+            })?;
+            if HashValue::from_hex(passcode_hash.trim_start_matches("0x")).is_err() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "admin_passcode_sha3_256 must be a hex encoded SHA3-256 hash!".into(),
+                ));
+            }
+        }
+
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
 
         Ok(())
@@ -227,4 +253,34 @@ mod tests {
             .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_mempool_admin_passcode() {
+        // Create a node config with the mempool admin endpoints enabled, but no passcode
+        let mut node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                mempool_admin_enabled: true,
+                admin_passcode_sha3_256: None,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because there's no passcode
+        let error = ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Set an invalid passcode hash and verify that it still fails
+        node_config.api.admin_passcode_sha3_256 = Some("passcode".into());
+        let error = ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Set a valid passcode hash and verify that it succeeds
+        node_config.api.admin_passcode_sha3_256 =
+            Some(HashValue::sha3_256_of(b"passcode").to_hex_literal());
+        ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet()).unwrap();
+    }
 }
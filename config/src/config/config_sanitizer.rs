@@ -5,8 +5,9 @@ use crate::config::{
     node_config_loader::NodeType,
     utils::{are_failpoints_enabled, get_config_name},
     ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig,
-    IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig, NodeConfig,
-    PeerMonitoringServiceConfig, StateSyncConfig, StorageConfig,
+    IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
+    NetworkCompressionConfig, NodeConfig, PeerMonitoringServiceConfig, StateSyncConfig,
+    StorageConfig,
 };
 use aptos_types::chain_id::ChainId;
 use std::collections::HashSet;
@@ -53,6 +54,7 @@ impl ConfigSanitizer for NodeConfig {
         InspectionServiceConfig::sanitize(node_config, node_type, chain_id)?;
         LoggerConfig::sanitize(node_config, node_type, chain_id)?;
         MempoolConfig::sanitize(node_config, node_type, chain_id)?;
+        NetworkCompressionConfig::sanitize(node_config, node_type, chain_id)?;
         PeerMonitoringServiceConfig::sanitize(node_config, node_type, chain_id)?;
         StateSyncConfig::sanitize(node_config, node_type, chain_id)?;
         StorageConfig::sanitize(node_config, node_type, chain_id)?;
@@ -139,6 +141,30 @@ fn sanitize_fullnode_network_configs(
             ));
         }
 
+        // Verify the bandwidth limits
+        if let Err(error) = fullnode_network_config.verify_rate_limits() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                error.to_string(),
+            ));
+        }
+
+        // Verify the peer reputation config
+        if let Err(error) = fullnode_network_config.peer_reputation.verify() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                error.to_string(),
+            ));
+        }
+
+        // Verify the peer exchange config
+        if let Err(error) = fullnode_network_config.peer_exchange.verify() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                error.to_string(),
+            ));
+        }
+
         // Prepare the network id
         fullnode_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -189,6 +215,22 @@ fn sanitize_validator_network_config(
             ));
         }
 
+        // Verify the bandwidth limits
+        if let Err(error) = validator_network_config.verify_rate_limits() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                error.to_string(),
+            ));
+        }
+
+        // Verify the peer reputation config
+        if let Err(error) = validator_network_config.peer_reputation.verify() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                error.to_string(),
+            ));
+        }
+
         // Prepare the network id
         validator_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -199,7 +241,10 @@ fn sanitize_validator_network_config(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{config::NetworkConfig, network_id::NetworkId};
+    use crate::{
+        config::{NetworkConfig, PeerExchangeConfig, PeerReputationConfig, RateLimitConfig},
+        network_id::NetworkId,
+    };
 
     #[test]
     fn test_sanitize_missing_pfn_network_configs() {
@@ -367,4 +412,130 @@ mod tests {
         .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_rate_limit_config() {
+        // Create a node config with a rate limit that can never be met
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                outbound_rate_limit_config: Some(RateLimitConfig {
+                    peer_byte_bucket_rate: Some(1024),
+                    peer_byte_bucket_size: Some(512),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_partial_rate_limit_config() {
+        // Create a node config with a network bucket rate, but no size
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                inbound_rate_limit_config: Some(RateLimitConfig {
+                    network_byte_bucket_rate: Some(1024),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_peer_reputation_config() {
+        // Create a node config with scores that never decay
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                peer_reputation: PeerReputationConfig {
+                    score_half_life_secs: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_ban_prefix_len() {
+        // Create a node config with an IPv4 prefix longer than an address
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                peer_reputation: PeerReputationConfig {
+                    ipv4_ban_prefix_len: 33,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_peer_exchange_config() {
+        // Create a node config that never exchanges any peers
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                peer_exchange: PeerExchangeConfig {
+                    max_peers_per_response: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
@@ -7,9 +7,10 @@ use crate::config::{
     node_config_loader::NodeType, Error, NodeConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
 use aptos_global_constants::DEFAULT_BUCKETS;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{chain_id::ChainId, PeerId};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::collections::HashSet;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -53,6 +54,14 @@ pub struct MempoolConfig {
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// The minimum percentage by which the gas unit price of a transaction must exceed that of
+    /// the transaction in Mempool with the same sender and sequence number to replace it.
+    pub replace_by_fee_min_gas_price_increase_pct: u64,
+    /// Whether to journal the client-submitted transactions in Mempool to disk (in the storage
+    /// directory), so that they are re-validated and added back after a restart.
+    pub enable_persistence: bool,
+    /// Configuration of private transaction submission
+    pub private_submission: PrivateSubmissionConfig,
 }
 
 impl Default for MempoolConfig {
@@ -77,17 +86,51 @@ impl Default for MempoolConfig {
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            replace_by_fee_min_gas_price_increase_pct: 10,
+            enable_persistence: false,
+            private_submission: PrivateSubmissionConfig::default(),
         }
     }
 }
 
+/// Private transactions are only broadcast to the trusted peers on the public network, e.g., to
+/// trusted validators and VFNs, and never gossiped to other public peers. Peers on the validator
+/// and VFN networks are always trusted.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrivateSubmissionConfig {
+    /// Whether all client-submitted transactions are private, instead of only those that are
+    /// submitted as private
+    pub private_by_default: bool,
+    /// The public network peers that private transactions are broadcast to
+    pub trusted_peers: HashSet<PeerId>,
+}
+
 impl ConfigSanitizer for MempoolConfig {
     fn sanitize(
-        _node_config: &mut NodeConfig,
+        node_config: &mut NodeConfig,
         _node_type: NodeType,
         _chain_id: ChainId,
     ) -> Result<(), Error> {
-        Ok(()) // TODO: add reasonable verifications
+        let sanitizer_name = Self::get_sanitizer_name();
+        let private_submission_config = &node_config.mempool.private_submission;
+
+        // Verify that private transactions can be broadcast to at least one trusted peer
+        if private_submission_config.private_by_default
+            && private_submission_config.trusted_peers.is_empty()
+            && node_config.validator_network.is_none()
+            && node_config
+                .full_node_networks
+                .iter()
+                .all(|network| network.network_id.is_public_network())
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Transactions are private by default, but there are no trusted peers to broadcast them to!".into(),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -142,6 +185,67 @@ impl ConfigOptimizer for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{config::NetworkConfig, network_id::NetworkId};
+
+    #[test]
+    fn test_sanitize_private_by_default_without_trusted_peers() {
+        // Create a PFN config with private transactions by default, but no trusted peers
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                private_submission: PrivateSubmissionConfig {
+                    private_by_default: true,
+                    trusted_peers: HashSet::new(),
+                },
+                ..Default::default()
+            },
+            full_node_networks: vec![NetworkConfig::network_with_id(NetworkId::Public)],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = MempoolConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Add a trusted peer and verify that the config is now valid
+        node_config
+            .mempool
+            .private_submission
+            .trusted_peers
+            .insert(PeerId::random());
+        MempoolConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+
+        // A VFN always trusts its validator, so no trusted peers are required
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                private_submission: PrivateSubmissionConfig {
+                    private_by_default: true,
+                    trusted_peers: HashSet::new(),
+                },
+                ..Default::default()
+            },
+            full_node_networks: vec![
+                NetworkConfig::network_with_id(NetworkId::Vfn),
+                NetworkConfig::network_with_id(NetworkId::Public),
+            ],
+            ..Default::default()
+        };
+        MempoolConfig::sanitize(
+            &mut node_config,
+            NodeType::ValidatorFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+    }
 
     #[test]
     fn test_optimize_vfn_configs() {
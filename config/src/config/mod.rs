@@ -17,6 +17,7 @@ mod indexer_grpc_config;
 mod inspection_service_config;
 mod logger_config;
 mod mempool_config;
+mod network_compression_config;
 mod network_config;
 mod node_config;
 mod node_config_loader;
@@ -42,6 +43,7 @@ pub use indexer_grpc_config::*;
 pub use inspection_service_config::*;
 pub use logger_config::*;
 pub use mempool_config::*;
+pub use network_compression_config::*;
 pub use network_config::*;
 pub use node_config::*;
 pub use node_config_loader::sanitize_node_config;
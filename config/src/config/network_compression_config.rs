@@ -0,0 +1,127 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The range of valid zstd compression levels
+const MIN_ZSTD_COMPRESSION_LEVEL: i32 = 1;
+const MAX_ZSTD_COMPRESSION_LEVEL: i32 = 22;
+
+/// The compression of the messages sent with the zstd encoded network
+/// protocols. Peers that don't support these protocols fall back to LZ4.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkCompressionConfig {
+    /// The compression of consensus messages
+    pub consensus: ZstdCompressionConfig,
+    /// The compression of mempool messages
+    pub mempool: ZstdCompressionConfig,
+    /// The compression of storage service (i.e., state sync) messages. Note: the
+    /// responses are also compressed with LZ4 if the data client sets `use_compression`.
+    pub state_sync: ZstdCompressionConfig,
+}
+
+impl Default for NetworkCompressionConfig {
+    fn default() -> Self {
+        Self {
+            // Consensus messages are latency sensitive, so compress them fast
+            consensus: ZstdCompressionConfig {
+                level: 1,
+                dictionary_path: None,
+            },
+            mempool: ZstdCompressionConfig::default(),
+            state_sync: ZstdCompressionConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ZstdCompressionConfig {
+    /// The zstd compression level (1 to 22). Higher levels are slower, but compress better.
+    pub level: i32,
+    /// The path of a zstd dictionary (as trained by `zstd --train`) to compress with.
+    /// Messages compressed with a dictionary can only be decompressed by peers that
+    /// have the same dictionary, so peers exchange the dictionary IDs in the handshake
+    /// and fall back to LZ4 if they differ. Peers running versions that don't know the
+    /// dictionary IDs are treated as having no dictionaries.
+    pub dictionary_path: Option<PathBuf>,
+}
+
+impl Default for ZstdCompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: 3,
+            dictionary_path: None,
+        }
+    }
+}
+
+impl ConfigSanitizer for NetworkCompressionConfig {
+    fn sanitize(
+        node_config: &mut NodeConfig,
+        _node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let compression_config = &node_config.network_compression;
+
+        // Verify that the compression levels are valid
+        for (name, config) in [
+            ("consensus", &compression_config.consensus),
+            ("mempool", &compression_config.mempool),
+            ("state_sync", &compression_config.state_sync),
+        ] {
+            if !(MIN_ZSTD_COMPRESSION_LEVEL..=MAX_ZSTD_COMPRESSION_LEVEL).contains(&config.level) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The {} zstd compression level must be between {} and {}, but got: {}",
+                        name, MIN_ZSTD_COMPRESSION_LEVEL, MAX_ZSTD_COMPRESSION_LEVEL, config.level
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_compression_levels() {
+        // Verify that the default config is valid
+        let mut node_config = NodeConfig::default();
+        NetworkCompressionConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::test())
+            .unwrap();
+
+        // Verify that invalid compression levels are rejected
+        for level in [0, 23, -1] {
+            let mut node_config = NodeConfig {
+                network_compression: NetworkCompressionConfig {
+                    state_sync: ZstdCompressionConfig {
+                        level,
+                        dictionary_path: None,
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let error = NetworkCompressionConfig::sanitize(
+                &mut node_config,
+                NodeType::Validator,
+                ChainId::test(),
+            )
+            .unwrap_err();
+            assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        }
+    }
+}
@@ -121,6 +121,12 @@ pub struct NetworkConfig {
     pub max_message_size: usize,
     /// The maximum number of parallel message deserialization tasks that can run (per application)
     pub max_parallel_deserialization_tasks: Option<usize>,
+    /// Scheduling of outbound messages of different protocol classes on each connection
+    pub outbound_queues: OutboundQueuesConfig,
+    /// Scoring of peer misbehavior, and banning of misbehaving peers
+    pub peer_reputation: PeerReputationConfig,
+    /// Exchange of peer addresses with other peers (only on the public network)
+    pub peer_exchange: PeerExchangeConfig,
 }
 
 impl Default for NetworkConfig {
@@ -162,6 +168,9 @@ impl NetworkConfig {
             outbound_rx_buffer_size_bytes: Some(OUTBOUND_TCP_RX_BUFFER_SIZE),
             outbound_tx_buffer_size_bytes: Some(OUTBOUND_TCP_TX_BUFFER_SIZE),
             max_parallel_deserialization_tasks: None,
+            outbound_queues: OutboundQueuesConfig::default(),
+            peer_reputation: PeerReputationConfig::default(),
+            peer_exchange: PeerExchangeConfig::default(),
         };
 
         // Configure the number of parallel deserialization tasks
@@ -336,6 +345,20 @@ impl NetworkConfig {
         }
         Ok(())
     }
+
+    /// Verifies the inbound and outbound rate limiting configs, if any
+    pub fn verify_rate_limits(&self) -> Result<(), Error> {
+        for config in [
+            &self.inbound_rate_limit_config,
+            &self.outbound_rate_limit_config,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            config.verify()?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -344,6 +367,7 @@ pub enum DiscoveryMethod {
     Onchain,
     File(FileDiscovery),
     Rest(RestDiscovery),
+    Dns(DnsDiscovery),
     None,
 }
 
@@ -361,13 +385,29 @@ pub struct RestDiscovery {
     pub interval_secs: u64,
 }
 
+/// Periodically re-resolves the DNS names in the addresses of the seeds, so
+/// that changes to their DNS records are picked up without restarts.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DnsDiscovery {
+    pub interval_secs: u64,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct RateLimitConfig {
     /// Maximum number of bytes/s for an IP
     pub ip_byte_bucket_rate: usize,
     /// Maximum burst of bytes for an IP
     pub ip_byte_bucket_size: usize,
+    /// Maximum number of bytes/s for a peer (unlimited if not set)
+    pub peer_byte_bucket_rate: Option<usize>,
+    /// Maximum burst of bytes for a peer (unlimited if not set)
+    pub peer_byte_bucket_size: Option<usize>,
+    /// Maximum number of bytes/s for all peers of the network combined (unlimited if not set)
+    pub network_byte_bucket_rate: Option<usize>,
+    /// Maximum burst of bytes for all peers of the network combined (unlimited if not set)
+    pub network_byte_bucket_size: Option<usize>,
     /// Initial amount of tokens initially in the bucket
     pub initial_bucket_fill_percentage: u8,
     /// Allow for disabling the throttles
@@ -379,12 +419,267 @@ impl Default for RateLimitConfig {
         Self {
             ip_byte_bucket_rate: IP_BYTE_BUCKET_RATE,
             ip_byte_bucket_size: IP_BYTE_BUCKET_SIZE,
+            peer_byte_bucket_rate: None,
+            peer_byte_bucket_size: None,
+            network_byte_bucket_rate: None,
+            network_byte_bucket_size: None,
             initial_bucket_fill_percentage: 25,
             enabled: true,
         }
     }
 }
 
+impl RateLimitConfig {
+    /// Verifies that every bucket can be filled and holds at least a second's worth of tokens,
+    /// and that the rate and size of the optional buckets are set together
+    pub fn verify(&self) -> Result<(), Error> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.initial_bucket_fill_percentage > 100 {
+            return Err(Error::InvariantViolation(format!(
+                "Initial bucket fill percentage must be at most 100, found: {}",
+                self.initial_bucket_fill_percentage
+            )));
+        }
+        let mut buckets = vec![("ip", self.ip_byte_bucket_rate, self.ip_byte_bucket_size)];
+        for (name, rate, size) in [
+            (
+                "peer",
+                self.peer_byte_bucket_rate,
+                self.peer_byte_bucket_size,
+            ),
+            (
+                "network",
+                self.network_byte_bucket_rate,
+                self.network_byte_bucket_size,
+            ),
+        ] {
+            match (rate, size) {
+                (Some(rate), Some(size)) => buckets.push((name, rate, size)),
+                (None, None) => {},
+                _ => {
+                    return Err(Error::InvariantViolation(format!(
+                        "The {} byte bucket rate and size must either both be set or both be unset, found rate: {:?}, size: {:?}",
+                        name, rate, size
+                    )));
+                },
+            }
+        }
+        for (name, rate, size) in buckets {
+            if rate == 0 || size < rate {
+                return Err(Error::InvariantViolation(format!(
+                    "The {} byte bucket rate must be positive and at most its size, found rate: {}, size: {}",
+                    name, rate, size
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How a connection picks the protocol class to send the next outbound message of
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboundSchedulingPolicy {
+    /// Always send a message of the highest priority class with pending messages
+    StrictPriority,
+    /// Share the connection between classes with pending messages, in proportion
+    /// to their weights (in bytes sent)
+    WeightedFair,
+}
+
+/// What a connection does with a new outbound message when its class queue is full
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboundDropPolicy {
+    /// Wait for room in the queue. This applies backpressure to the senders.
+    Block,
+    /// Drop the new message
+    DropNewest,
+    /// Drop the oldest message in the queue
+    DropOldest,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutboundQueueConfig {
+    /// Priority of the class under strict priority scheduling (higher is sent first)
+    pub priority: u8,
+    /// Weight of the class under weighted fair scheduling
+    pub weight: u32,
+    /// Maximum number of pending messages of the class
+    pub max_queue_size: usize,
+    /// What to do with new messages when the queue is full
+    pub drop_policy: OutboundDropPolicy,
+}
+
+/// Configuration of the outbound queues of each connection. Every protocol
+/// belongs to one class, and every class has a bounded queue of its own.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutboundQueuesConfig {
+    /// How the next message to send is picked among the classes
+    pub scheduling_policy: OutboundSchedulingPolicy,
+    /// Consensus messages and rpcs
+    pub consensus: OutboundQueueConfig,
+    /// Health checker, peer monitoring and discovery messages, and errors
+    pub control: OutboundQueueConfig,
+    /// State sync messages and storage service rpcs
+    pub state_sync: OutboundQueueConfig,
+    /// Mempool messages and rpcs
+    pub mempool: OutboundQueueConfig,
+}
+
+impl Default for OutboundQueuesConfig {
+    fn default() -> Self {
+        Self {
+            scheduling_policy: OutboundSchedulingPolicy::WeightedFair,
+            consensus: OutboundQueueConfig {
+                priority: 3,
+                weight: 8,
+                max_queue_size: NETWORK_CHANNEL_SIZE,
+                drop_policy: OutboundDropPolicy::Block,
+            },
+            control: OutboundQueueConfig {
+                priority: 4,
+                weight: 1,
+                max_queue_size: NETWORK_CHANNEL_SIZE,
+                drop_policy: OutboundDropPolicy::Block,
+            },
+            state_sync: OutboundQueueConfig {
+                priority: 2,
+                weight: 4,
+                max_queue_size: NETWORK_CHANNEL_SIZE,
+                drop_policy: OutboundDropPolicy::Block,
+            },
+            // Mempool broadcasts are retried if they go unacknowledged
+            mempool: OutboundQueueConfig {
+                priority: 1,
+                weight: 2,
+                max_queue_size: NETWORK_CHANNEL_SIZE,
+                drop_policy: OutboundDropPolicy::DropOldest,
+            },
+        }
+    }
+}
+
+/// Configuration of the reputation of peers. Applications report misbehaving peers
+/// (e.g., for sending invalid data), which adds a penalty to the peer's score. Scores
+/// decay over time, and peers whose score reaches the ban threshold are disconnected
+/// and banned for a while. Trusted peers (e.g., validators and preferred upstreams)
+/// are never banned.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerReputationConfig {
+    /// Allow for disabling the banning of peers
+    pub enabled: bool,
+    /// The score at which a peer is banned
+    pub ban_threshold: u64,
+    /// How long a peer is banned for
+    pub ban_duration_secs: u64,
+    /// The time it takes for a peer's score to decay by half
+    pub score_half_life_secs: u64,
+    /// The penalty for sending a message that can't be handled (e.g., of the wrong type)
+    pub unexpected_message_penalty: u64,
+    /// The penalty for sending invalid data (e.g., that fails proof verification)
+    pub invalid_data_penalty: u64,
+    /// The length of the prefix of the IPv4 address banned along with a peer. Only
+    /// the exact address is banned by default (i.e., 32), as honest peers can share
+    /// a prefix (e.g., 24 bans the whole /24).
+    pub ipv4_ban_prefix_len: u8,
+    /// The length of the prefix of the IPv6 address banned along with a peer. Only
+    /// the exact address is banned by default (i.e., 128).
+    pub ipv6_ban_prefix_len: u8,
+}
+
+impl Default for PeerReputationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ban_threshold: 100,
+            ban_duration_secs: 3600, // 1 hour
+            score_half_life_secs: 600,
+            unexpected_message_penalty: 10,
+            invalid_data_penalty: 50,
+            ipv4_ban_prefix_len: 32,
+            ipv6_ban_prefix_len: 128,
+        }
+    }
+}
+
+impl PeerReputationConfig {
+    /// Verifies that scores decay, that peers can be banned, and that the
+    /// banned prefixes are valid
+    pub fn verify(&self) -> Result<(), Error> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.ban_threshold == 0 || self.score_half_life_secs == 0 {
+            return Err(Error::InvariantViolation(format!(
+                "The ban threshold and score half life must be positive, found threshold: {}, half life: {}",
+                self.ban_threshold, self.score_half_life_secs
+            )));
+        }
+        if self.ipv4_ban_prefix_len > 32 || self.ipv6_ban_prefix_len > 128 {
+            return Err(Error::InvariantViolation(format!(
+                "The banned prefix lengths must be at most 32 (IPv4) and 128 (IPv6), found IPv4: {}, IPv6: {}",
+                self.ipv4_ban_prefix_len, self.ipv6_ban_prefix_len
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Configuration of the exchange of peer addresses on the public network. Nodes
+/// periodically ask a random peer for the addresses of other public peers, and
+/// share the addresses of the peers they've dialed successfully in return.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerExchangeConfig {
+    /// Allow for disabling the peer exchange
+    pub enabled: bool,
+    /// The interval at which to ask a peer for addresses
+    pub exchange_interval_ms: u64,
+    /// The timeout for peer exchange requests
+    pub request_timeout_ms: u64,
+    /// The maximum number of peers to share in each response
+    pub max_peers_per_response: usize,
+    /// The maximum number of exchanged peers to hold on to (to dial)
+    pub max_exchanged_peers: usize,
+}
+
+impl Default for PeerExchangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            exchange_interval_ms: 60_000, // 1 minute
+            request_timeout_ms: 10_000,
+            max_peers_per_response: 20,
+            max_exchanged_peers: 100,
+        }
+    }
+}
+
+impl PeerExchangeConfig {
+    /// Verifies that peers are exchanged at all
+    pub fn verify(&self) -> Result<(), Error> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.exchange_interval_ms == 0
+            || self.max_peers_per_response == 0
+            || self.max_exchanged_peers == 0
+        {
+            return Err(Error::InvariantViolation(format!(
+                "The exchange interval and the peer limits must be positive, found interval: {}, peers per response: {}, exchanged peers: {}",
+                self.exchange_interval_ms, self.max_peers_per_response, self.max_exchanged_peers
+            )));
+        }
+        Ok(())
+    }
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
@@ -525,4 +820,25 @@ mod tests {
         network_config.configure_num_deserialization_tasks();
         assert_eq!(network_config.max_parallel_deserialization_tasks, Some(1));
     }
+
+    #[test]
+    fn test_rate_limit_config_without_peer_and_network_buckets() {
+        // Deserialize a rate limit config that only limits IP addresses
+        let config: RateLimitConfig = serde_yaml::from_str(
+            r#"
+            ip_byte_bucket_rate: 1024
+            ip_byte_bucket_size: 2048
+            "#,
+        )
+        .unwrap();
+
+        // Verify that peers and the network aren't limited
+        assert_eq!(config.ip_byte_bucket_rate, 1024);
+        assert_eq!(config.ip_byte_bucket_size, 2048);
+        assert_eq!(config.peer_byte_bucket_rate, None);
+        assert_eq!(config.peer_byte_bucket_size, None);
+        assert_eq!(config.network_byte_bucket_rate, None);
+        assert_eq!(config.network_byte_bucket_size, None);
+        config.verify().unwrap();
+    }
 }
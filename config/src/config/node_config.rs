@@ -6,8 +6,8 @@ use crate::{
         node_config_loader::NodeConfigLoader, persistable_config::PersistableConfig,
         utils::RootPath, ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig,
         IndexerConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
-        NetworkConfig, PeerMonitoringServiceConfig, SafetyRulesTestConfig, StateSyncConfig,
-        StorageConfig,
+        NetworkCompressionConfig, NetworkConfig, PeerMonitoringServiceConfig,
+        SafetyRulesTestConfig, StateSyncConfig, StorageConfig,
     },
     network_id::NetworkId,
 };
@@ -51,6 +51,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub mempool: MempoolConfig,
     #[serde(default)]
+    pub network_compression: NetworkCompressionConfig,
+    #[serde(default)]
     pub peer_monitoring_service: PeerMonitoringServiceConfig,
     #[serde(default)]
     pub state_sync: StateSyncConfig,
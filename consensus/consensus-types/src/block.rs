@@ -14,10 +14,13 @@ use aptos_types::{
     account_address::AccountAddress,
     block_info::BlockInfo,
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
+    randomness::{randomness_seed, RandomnessSeedMessage},
     transaction::{SignedTransaction, Transaction, Version},
     validator_signer::ValidatorSigner,
+    validator_txn::ValidatorTransaction,
     validator_verifier::ValidatorVerifier,
 };
 use mirai_annotations::debug_checked_verify_eq;
@@ -102,6 +105,10 @@ impl Block {
         self.block_data.payload()
     }
 
+    pub fn validator_txns(&self) -> Option<&Vec<ValidatorTransaction>> {
+        self.block_data.validator_txns()
+    }
+
     pub fn payload_size(&self) -> usize {
         match self.block_data.payload() {
             None => 0,
@@ -271,6 +278,51 @@ impl Block {
                 validator.verify(*author, &self.block_data, signature)?;
                 self.quorum_cert().verify(validator)
             },
+            BlockType::ProposalWithRandomness {
+                author,
+                randomness_proof,
+                ..
+            } => {
+                let signature = self
+                    .signature
+                    .as_ref()
+                    .ok_or_else(|| format_err!("Missing signature in Proposal"))?;
+                validator.verify(*author, &self.block_data, signature)?;
+                validator
+                    .verify(
+                        *author,
+                        &RandomnessSeedMessage::new(self.epoch(), self.round()),
+                        randomness_proof,
+                    )
+                    .map_err(|e| format_err!("Invalid randomness proof in Proposal: {}", e))?;
+                self.quorum_cert().verify(validator)
+            },
+            BlockType::ProposalExt {
+                validator_txns,
+                author,
+                randomness_proof,
+                ..
+            } => {
+                let signature = self
+                    .signature
+                    .as_ref()
+                    .ok_or_else(|| format_err!("Missing signature in Proposal"))?;
+                validator.verify(*author, &self.block_data, signature)?;
+                if let Some(randomness_proof) = randomness_proof {
+                    validator
+                        .verify(
+                            *author,
+                            &RandomnessSeedMessage::new(self.epoch(), self.round()),
+                            randomness_proof,
+                        )
+                        .map_err(|e| format_err!("Invalid randomness proof in Proposal: {}", e))?;
+                }
+                for txn in validator_txns {
+                    txn.verify(validator)
+                        .map_err(|e| format_err!("Invalid validator txn in Proposal: {}", e))?;
+                }
+                self.quorum_cert().verify(validator)
+            },
         }
     }
 
@@ -295,6 +347,10 @@ impl Block {
                 self.payload().map_or(true, |p| p.is_empty()),
                 "Reconfiguration suffix should not carry payload"
             );
+            ensure!(
+                self.validator_txns().map_or(true, |txns| txns.is_empty()),
+                "Reconfiguration suffix should not carry validator txns"
+            );
         }
         if let Some(failed_authors) = self.block_data().failed_authors() {
             // when validating for being well formed,
@@ -361,23 +417,41 @@ impl Block {
         txns: Vec<SignedTransaction>,
         block_gas_limit: Option<u64>,
     ) -> Vec<Transaction> {
+        // Validator txns are executed right after the block metadata, before any user txn.
+        let validator_txns = self
+            .validator_txns()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .map(Transaction::ValidatorTransaction);
         if block_gas_limit.is_some() {
             // After the per-block gas limit change, StateCheckpoint txn
             // is inserted after block execution
-            once(Transaction::BlockMetadata(
-                self.new_block_metadata(validators),
-            ))
-            .chain(txns.into_iter().map(Transaction::UserTransaction))
-            .collect()
+            once(self.new_block_metadata_txn(validators))
+                .chain(validator_txns)
+                .chain(txns.into_iter().map(Transaction::UserTransaction))
+                .collect()
         } else {
             // Before the per-block gas limit change, StateCheckpoint txn
             // is inserted here for compatibility.
-            once(Transaction::BlockMetadata(
-                self.new_block_metadata(validators),
-            ))
-            .chain(txns.into_iter().map(Transaction::UserTransaction))
-            .chain(once(Transaction::StateCheckpoint(self.id)))
-            .collect()
+            once(self.new_block_metadata_txn(validators))
+                .chain(validator_txns)
+                .chain(txns.into_iter().map(Transaction::UserTransaction))
+                .chain(once(Transaction::StateCheckpoint(self.id)))
+                .collect()
+        }
+    }
+
+    /// Blocks with a randomness proof carry their seed in a `BlockMetadataExt` transaction, all
+    /// other blocks keep using the original `BlockMetadata` transaction.
+    fn new_block_metadata_txn(&self, validators: &[AccountAddress]) -> Transaction {
+        let block_metadata = self.new_block_metadata(validators);
+        match self.block_data.randomness_proof() {
+            Some(proof) => Transaction::BlockMetadataExt(BlockMetadataExt::new_v1(
+                block_metadata,
+                randomness_seed(proof),
+            )),
+            None => Transaction::BlockMetadata(block_metadata),
         }
     }
 
@@ -7,12 +7,13 @@ use crate::{
     quorum_cert::QuorumCert,
     vote_data::VoteData,
 };
-use aptos_crypto::hash::HashValue;
+use aptos_crypto::{bls12381, hash::HashValue};
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_txn::ValidatorTransaction,
 };
 use mirai_annotations::*;
 use serde::{Deserialize, Serialize};
@@ -42,6 +43,27 @@ pub enum BlockType {
     /// from the previous epoch.  The genesis block is used as the first root block of the
     /// BlockTree for all epochs.
     Genesis,
+    /// A proposal in an epoch with on-chain randomness, which additionally carries the proof of
+    /// the block's randomness seed (see `aptos_types::randomness`).
+    ProposalWithRandomness {
+        payload: Payload,
+        author: Author,
+        failed_authors: Vec<(Round, Author)>,
+        /// The author's signature of the `RandomnessSeedMessage` of the block
+        randomness_proof: bls12381::Signature,
+    },
+    /// A proposal in an epoch with validator transactions enabled, which additionally carries the
+    /// validator transactions to execute right after the block metadata (see
+    /// `aptos_types::validator_txn`).
+    ProposalExt {
+        validator_txns: Vec<ValidatorTransaction>,
+        payload: Payload,
+        author: Author,
+        failed_authors: Vec<(Round, Author)>,
+        /// The author's signature of the `RandomnessSeedMessage` of the block, if on-chain
+        /// randomness is enabled
+        randomness_proof: Option<bls12381::Signature>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
@@ -79,10 +101,11 @@ pub struct BlockData {
 
 impl BlockData {
     pub fn author(&self) -> Option<Author> {
-        if let BlockType::Proposal { author, .. } = self.block_type {
-            Some(author)
-        } else {
-            None
+        match self.block_type {
+            BlockType::Proposal { author, .. }
+            | BlockType::ProposalWithRandomness { author, .. }
+            | BlockType::ProposalExt { author, .. } => Some(author),
+            BlockType::NilBlock { .. } | BlockType::Genesis => None,
         }
     }
 
@@ -99,8 +122,31 @@ impl BlockData {
     }
 
     pub fn payload(&self) -> Option<&Payload> {
-        if let BlockType::Proposal { payload, .. } = &self.block_type {
-            Some(payload)
+        match &self.block_type {
+            BlockType::Proposal { payload, .. }
+            | BlockType::ProposalWithRandomness { payload, .. }
+            | BlockType::ProposalExt { payload, .. } => Some(payload),
+            BlockType::NilBlock { .. } | BlockType::Genesis => None,
+        }
+    }
+
+    /// The proposer's proof of the randomness seed of the block, if it has one
+    pub fn randomness_proof(&self) -> Option<&bls12381::Signature> {
+        match &self.block_type {
+            BlockType::ProposalWithRandomness {
+                randomness_proof, ..
+            } => Some(randomness_proof),
+            BlockType::ProposalExt {
+                randomness_proof, ..
+            } => randomness_proof.as_ref(),
+            BlockType::Proposal { .. } | BlockType::NilBlock { .. } | BlockType::Genesis => None,
+        }
+    }
+
+    /// The validator transactions of the block, if it is a proposal that can carry them
+    pub fn validator_txns(&self) -> Option<&Vec<ValidatorTransaction>> {
+        if let BlockType::ProposalExt { validator_txns, .. } = &self.block_type {
+            Some(validator_txns)
         } else {
             None
         }
@@ -132,6 +178,12 @@ impl BlockData {
         match self.block_type {
             BlockType::Proposal {
                 ref failed_authors, ..
+            }
+            | BlockType::ProposalWithRandomness {
+                ref failed_authors, ..
+            }
+            | BlockType::ProposalExt {
+                ref failed_authors, ..
             } => Some(failed_authors),
             BlockType::NilBlock { ref failed_authors } => Some(failed_authors),
             BlockType::Genesis => None,
@@ -259,6 +311,67 @@ impl BlockData {
         }
     }
 
+    pub fn new_proposal_ext(
+        validator_txns: Vec<ValidatorTransaction>,
+        payload: Payload,
+        author: Author,
+        failed_authors: Vec<(Round, Author)>,
+        round: Round,
+        timestamp_usecs: u64,
+        quorum_cert: QuorumCert,
+    ) -> Self {
+        Self {
+            epoch: quorum_cert.certified_block().epoch(),
+            round,
+            timestamp_usecs,
+            quorum_cert,
+            block_type: BlockType::ProposalExt {
+                validator_txns,
+                payload,
+                author,
+                failed_authors,
+                randomness_proof: None,
+            },
+        }
+    }
+
+    /// Attaches the author's proof of the block's randomness seed to a proposal.
+    pub fn with_randomness_proof(self, randomness_proof: bls12381::Signature) -> Self {
+        let block_type = match self.block_type {
+            BlockType::Proposal {
+                payload,
+                author,
+                failed_authors,
+            }
+            | BlockType::ProposalWithRandomness {
+                payload,
+                author,
+                failed_authors,
+                ..
+            } => BlockType::ProposalWithRandomness {
+                payload,
+                author,
+                failed_authors,
+                randomness_proof,
+            },
+            BlockType::ProposalExt {
+                validator_txns,
+                payload,
+                author,
+                failed_authors,
+                ..
+            } => BlockType::ProposalExt {
+                validator_txns,
+                payload,
+                author,
+                failed_authors,
+                randomness_proof: Some(randomness_proof),
+            },
+            block_type @ (BlockType::NilBlock { .. } | BlockType::Genesis) => block_type,
+        };
+        Self { block_type, ..self }
+    }
+
     /// It's a reconfiguration suffix block if the parent block's executed state indicates next epoch.
     pub fn is_reconfiguration_suffix(&self) -> bool {
         self.quorum_cert.certified_block().has_reconfiguration()
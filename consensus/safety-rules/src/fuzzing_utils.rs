@@ -26,6 +26,7 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     proof::AccumulatorExtensionProof,
     proptest_types::{AccountInfoUniverse, BlockInfoGen},
+    randomness::RandomnessSeedMessage,
     transaction::SignedTransaction,
     validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
 };
@@ -231,6 +232,9 @@ pub fn arb_safety_rules_input() -> impl Strategy<Value = SafetyRulesInput> {
         arb_timeout().prop_map(|input| {
             SafetyRulesInput::SignTimeoutWithQC(Box::new(input), Box::new(None))
         }),
+        (any::<u64>(), any::<u64>()).prop_map(|(epoch, round)| {
+            SafetyRulesInput::SignRandomnessSeed(Box::new(RandomnessSeedMessage::new(epoch, round)))
+        }),
     ]
 }
 
@@ -14,6 +14,7 @@ use aptos_infallible::RwLock;
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    randomness::RandomnessSeedMessage,
 };
 use std::sync::Arc;
 
@@ -43,6 +44,13 @@ impl TSafetyRules for LocalClient {
         self.internal.write().sign_proposal(block_data)
     }
 
+    fn sign_randomness_seed(
+        &mut self,
+        message: &RandomnessSeedMessage,
+    ) -> Result<bls12381::Signature, Error> {
+        self.internal.write().sign_randomness_seed(message)
+    }
+
     fn sign_timeout_with_qc(
         &mut self,
         timeout: &TwoChainTimeout,
@@ -50,6 +50,7 @@ pub enum LogEntry {
     OneChainRound,
     PreferredRound,
     SignProposal,
+    SignRandomnessSeed,
     SignTimeoutWithQC,
     State,
     Waypoint,
@@ -68,6 +69,7 @@ impl LogEntry {
             LogEntry::OneChainRound => "one_chain_round",
             LogEntry::PreferredRound => "preferred_round",
             LogEntry::SignProposal => "sign_proposal",
+            LogEntry::SignRandomnessSeed => "sign_randomness_seed",
             LogEntry::SignTimeoutWithQC => "sign_timeout_with_qc",
             LogEntry::State => "state",
             LogEntry::Waypoint => "waypoint",
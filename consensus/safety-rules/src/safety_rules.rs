@@ -26,6 +26,7 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    randomness::RandomnessSeedMessage,
     validator_signer::ValidatorSigner,
     waypoint::Waypoint,
 };
@@ -325,6 +326,18 @@ impl SafetyRules {
         Ok(signature)
     }
 
+    fn guarded_sign_randomness_seed(
+        &mut self,
+        message: &RandomnessSeedMessage,
+    ) -> Result<bls12381::Signature, Error> {
+        self.signer()?;
+
+        let safety_data = self.persistent_storage.safety_data()?;
+        self.verify_epoch(message.epoch(), &safety_data)?;
+
+        self.sign(message)
+    }
+
     fn guarded_sign_commit_vote(
         &mut self,
         ledger_info: LedgerInfoWithSignatures,
@@ -379,6 +392,15 @@ impl TSafetyRules for SafetyRules {
         run_and_log(cb, |log| log.round(round), LogEntry::SignProposal)
     }
 
+    fn sign_randomness_seed(
+        &mut self,
+        message: &RandomnessSeedMessage,
+    ) -> Result<bls12381::Signature, Error> {
+        let round = message.round();
+        let cb = || self.guarded_sign_randomness_seed(message);
+        run_and_log(cb, |log| log.round(round), LogEntry::SignRandomnessSeed)
+    }
+
     fn sign_timeout_with_qc(
         &mut self,
         timeout: &TwoChainTimeout,
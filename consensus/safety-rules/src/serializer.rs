@@ -14,6 +14,7 @@ use aptos_infallible::RwLock;
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    randomness::RandomnessSeedMessage,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -29,6 +30,7 @@ pub enum SafetyRulesInput {
     ),
     ConstructAndSignVoteTwoChain(Box<VoteProposal>, Box<Option<TwoChainTimeoutCertificate>>),
     SignCommitVote(Box<LedgerInfoWithSignatures>, Box<LedgerInfo>),
+    SignRandomnessSeed(Box<RandomnessSeedMessage>),
 }
 
 pub struct SerializerService {
@@ -69,6 +71,9 @@ impl SerializerService {
                     .internal
                     .sign_commit_vote(*ledger_info, *new_ledger_info),
             ),
+            SafetyRulesInput::SignRandomnessSeed(message) => {
+                serde_json::to_vec(&self.internal.sign_randomness_seed(&message))
+            },
         };
 
         Ok(output?)
@@ -114,6 +119,17 @@ impl TSafetyRules for SerializerClient {
         serde_json::from_slice(&response)?
     }
 
+    fn sign_randomness_seed(
+        &mut self,
+        message: &RandomnessSeedMessage,
+    ) -> Result<bls12381::Signature, Error> {
+        let _timer = counters::start_timer("external", LogEntry::SignRandomnessSeed.as_str());
+        let response = self.request(SafetyRulesInput::SignRandomnessSeed(Box::new(
+            message.clone(),
+        )))?;
+        serde_json::from_slice(&response)?
+    }
+
     fn sign_timeout_with_qc(
         &mut self,
         timeout: &TwoChainTimeout,
@@ -13,6 +13,7 @@ use aptos_crypto::bls12381;
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    randomness::RandomnessSeedMessage,
 };
 
 /// Interface for SafetyRules
@@ -31,6 +32,13 @@ pub trait TSafetyRules {
     /// A Block is a signed BlockData along with some additional metadata.
     fn sign_proposal(&mut self, block_data: &BlockData) -> Result<bls12381::Signature, Error>;
 
+    /// Signs the randomness seed message of a block proposed by this validator in the current
+    /// epoch. The signature is the proof of the block's randomness seed.
+    fn sign_randomness_seed(
+        &mut self,
+        message: &RandomnessSeedMessage,
+    ) -> Result<bls12381::Signature, Error>;
+
     /// Sign the timeout together with highest qc for 2-chain protocol.
     fn sign_timeout_with_qc(
         &mut self,
@@ -16,6 +16,7 @@ use aptos_types::{
     block_info::BlockInfo,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    randomness::RandomnessSeedMessage,
     validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
 };
@@ -56,6 +57,7 @@ pub fn run_test_suite(safety_rules: &Callback) {
     test_2chain_rules(safety_rules);
     test_2chain_timeout(safety_rules);
     test_sign_commit_vote(safety_rules);
+    test_sign_randomness_seed(safety_rules);
     test_bad_execution_output(safety_rules);
 }
 
@@ -577,6 +579,34 @@ fn test_2chain_timeout(constructor: &Callback) {
     ));
 }
 
+/// Test that the randomness seed is only signed for the current epoch, and verifies against the
+/// validator's key
+fn test_sign_randomness_seed(constructor: &Callback) {
+    let (mut safety_rules, signer) = constructor();
+    let (proof, genesis_qc) = test_utils::make_genesis(&signer);
+    let round = genesis_qc.certified_block().round();
+    safety_rules.initialize(&proof).unwrap();
+
+    let a1 = test_utils::make_proposal_with_qc(round + 1, genesis_qc, &signer);
+    let epoch = a1.block().epoch();
+    let message = RandomnessSeedMessage::new(epoch, a1.block().round());
+    let randomness_proof = safety_rules.sign_randomness_seed(&message).unwrap();
+    ValidatorVerifier::new_single(signer.author(), signer.public_key())
+        .verify(signer.author(), &message, &randomness_proof)
+        .unwrap();
+    // The proof is unique, so signing again yields the same seed
+    assert_eq!(
+        safety_rules.sign_randomness_seed(&message).unwrap(),
+        randomness_proof
+    );
+
+    let message = RandomnessSeedMessage::new(epoch + 1, a1.block().round());
+    assert_eq!(
+        safety_rules.sign_randomness_seed(&message).unwrap_err(),
+        Error::IncorrectEpoch(epoch + 1, epoch)
+    );
+}
+
 /// Test that we can succesfully sign a valid commit vote
 fn test_sign_commit_vote(constructor: &Callback) {
     // we construct a chain of proposals
@@ -22,6 +22,7 @@ use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
 use aptos_storage_interface::DbReaderWriter;
+use aptos_validator_transaction_pool::VTxnPoolState;
 use aptos_vm::AptosVM;
 use futures::channel::mpsc;
 use std::sync::Arc;
@@ -36,6 +37,7 @@ pub fn start_consensus(
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    vtxn_pool: VTxnPoolState,
 ) -> Runtime {
     let runtime = aptos_runtimes::spawn_named_runtime("consensus".into(), None);
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
@@ -73,6 +75,7 @@ pub fn start_consensus(
         quorum_store_db,
         reconfig_events,
         bounded_executor,
+        vtxn_pool,
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_service_events, self_receiver);
@@ -74,6 +74,7 @@ use aptos_types::{
     },
     validator_verifier::ValidatorVerifier,
 };
+use aptos_validator_transaction_pool::VTxnPoolState;
 use fail::fail_point;
 use futures::{
     channel::{
@@ -140,6 +141,7 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     bounded_executor: BoundedExecutor,
     // recovery_mode is set to true when the recovery manager is spawned
     recovery_mode: bool,
+    vtxn_pool: VTxnPoolState,
 }
 
 impl<P: OnChainConfigProvider> EpochManager<P> {
@@ -155,6 +157,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         quorum_store_storage: Arc<dyn QuorumStoreStorage>,
         reconfig_events: ReconfigNotificationListener<P>,
         bounded_executor: BoundedExecutor,
+        vtxn_pool: VTxnPoolState,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -186,6 +189,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             batch_retrieval_tx: None,
             bounded_executor,
             recovery_mode: false,
+            vtxn_pool,
         }
     }
 
@@ -682,7 +686,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         let (payload_manager, quorum_store_msg_tx) = quorum_store_builder.init_payload_manager();
         let transaction_shuffler =
             create_transaction_shuffler(onchain_execution_config.transaction_shuffler_type());
-        let block_gas_limit = onchain_execution_config.block_gas_limit();
+        let block_gas_limit_type = onchain_execution_config.block_gas_limit_type();
         let transaction_deduper =
             create_transaction_deduper(onchain_execution_config.transaction_deduper_type());
         self.quorum_store_msg_tx = quorum_store_msg_tx;
@@ -697,7 +701,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             &epoch_state,
             payload_manager.clone(),
             transaction_shuffler,
-            block_gas_limit,
+            block_gas_limit_type,
             transaction_deduper,
         );
         let state_computer = if onchain_consensus_config.decoupled_execution() {
@@ -744,6 +748,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             pipeline_backpressure_config,
             chain_health_backoff_config,
             self.quorum_store_enabled,
+            self.vtxn_pool.clone(),
+            onchain_consensus_config.effective_validator_txn_config(),
         );
 
         let (round_manager_tx, round_manager_rx) = aptos_channel::new(
@@ -18,7 +18,10 @@ use aptos_consensus_types::{block::Block, executed_block::ExecutedBlock};
 use aptos_crypto::HashValue;
 use aptos_executor_types::{Error as ExecutionError, StateComputeResult};
 use aptos_logger::prelude::*;
-use aptos_types::{epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures};
+use aptos_types::{
+    epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockGasLimitType,
+};
 use fail::fail_point;
 use futures::{
     channel::{mpsc::UnboundedSender, oneshot},
@@ -126,7 +129,7 @@ impl StateComputer for OrderingStateComputer {
         _: &EpochState,
         _payload_manager: Arc<PayloadManager>,
         _: Arc<dyn TransactionShuffler>,
-        _: Option<u64>,
+        _: BlockGasLimitType,
         _: Arc<dyn TransactionDeduper>,
     ) {
     }
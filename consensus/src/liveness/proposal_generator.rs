@@ -24,8 +24,10 @@ use aptos_consensus_types::{
     quorum_cert::QuorumCert,
 };
 use aptos_logger::{error, sample, sample::SampleRate, warn};
+use aptos_types::on_chain_config::ValidatorTxnConfig;
+use aptos_validator_transaction_pool::{TransactionFilter, VTxnPoolState};
 use futures::future::BoxFuture;
-use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use std::{cmp::min, collections::BTreeMap, sync::Arc, time::Duration};
 
 #[cfg(test)]
 #[path = "proposal_generator_test.rs"]
@@ -168,6 +170,11 @@ pub struct ProposalGenerator {
     // Last round that a proposal was generated
     last_round_generated: Round,
     quorum_store_enabled: bool,
+
+    // The local pool the validator transactions of a proposal are pulled from.
+    vtxn_pool: VTxnPoolState,
+    // Whether validator transactions are enabled, and the per-block limits on them.
+    vtxn_config: ValidatorTxnConfig,
 }
 
 impl ProposalGenerator {
@@ -183,6 +190,8 @@ impl ProposalGenerator {
         pipeline_backpressure_config: PipelineBackpressureConfig,
         chain_health_backoff_config: ChainHealthBackoffConfig,
         quorum_store_enabled: bool,
+        vtxn_pool: VTxnPoolState,
+        vtxn_config: ValidatorTxnConfig,
     ) -> Self {
         Self {
             author,
@@ -197,6 +206,8 @@ impl ProposalGenerator {
             chain_health_backoff_config,
             last_round_generated: 0,
             quorum_store_enabled,
+            vtxn_pool,
+            vtxn_config,
         }
     }
 
@@ -245,10 +256,11 @@ impl ProposalGenerator {
 
         let hqc = self.ensure_highest_quorum_cert(round)?;
 
-        let (payload, timestamp) = if hqc.certified_block().has_reconfiguration() {
+        let (validator_txns, payload, timestamp) = if hqc.certified_block().has_reconfiguration() {
             // Reconfiguration rule - we propose empty blocks with parents' timestamp
             // after reconfiguration until it's committed
             (
+                vec![],
                 Payload::empty(self.quorum_store_enabled),
                 hqc.certified_block().timestamp_usecs(),
             )
@@ -309,12 +321,35 @@ impl ProposalGenerator {
                 .max(max_pending_block_bytes as f32 / self.max_block_bytes as f32);
             PROPOSER_PENDING_BLOCKS_COUNT.set(pending_blocks.len() as i64);
             PROPOSER_PENDING_BLOCKS_FILL_FRACTION.set(max_fill_fraction as f64);
+
+            // Validator txns come out of the same block budget as the user txns.
+            let validator_txns = if self.vtxn_config.enabled() {
+                let pending_validator_txns = pending_blocks
+                    .iter()
+                    .flat_map(|block| block.block().validator_txns())
+                    .flatten();
+                self.vtxn_pool.pull(
+                    min(max_block_txns, self.vtxn_config.per_block_limit_txn_count()),
+                    min(
+                        max_block_bytes,
+                        self.vtxn_config.per_block_limit_total_bytes(),
+                    ),
+                    TransactionFilter::pending_txns(pending_validator_txns),
+                )
+            } else {
+                vec![]
+            };
+            let validator_txns_bytes: u64 = validator_txns
+                .iter()
+                .map(|txn| txn.size_in_bytes() as u64)
+                .sum();
+
             let payload = self
                 .payload_client
                 .pull_payload(
                     self.quorum_store_poll_time.saturating_sub(proposal_delay),
-                    max_block_txns,
-                    max_block_bytes,
+                    max_block_txns.saturating_sub(validator_txns.len() as u64),
+                    max_block_bytes.saturating_sub(validator_txns_bytes),
                     payload_filter,
                     wait_callback,
                     pending_ordering,
@@ -324,7 +359,7 @@ impl ProposalGenerator {
                 .await
                 .context("Fail to retrieve payload")?;
 
-            (payload, timestamp.as_micros() as u64)
+            (validator_txns, payload, timestamp.as_micros() as u64)
         };
 
         let quorum_cert = hqc.as_ref().clone();
@@ -335,14 +370,26 @@ impl ProposalGenerator {
             proposer_election,
         );
         // create block proposal
-        Ok(BlockData::new_proposal(
-            payload,
-            self.author,
-            failed_authors,
-            round,
-            timestamp,
-            quorum_cert,
-        ))
+        if self.vtxn_config.enabled() {
+            Ok(BlockData::new_proposal_ext(
+                validator_txns,
+                payload,
+                self.author,
+                failed_authors,
+                round,
+                timestamp,
+                quorum_cert,
+            ))
+        } else {
+            Ok(BlockData::new_proposal(
+                payload,
+                self.author,
+                failed_authors,
+                round,
+                timestamp,
+                quorum_cert,
+            ))
+        }
     }
 
     async fn calculate_max_block_sizes(
@@ -18,7 +18,11 @@ use aptos_consensus_types::{
     block::{block_test_utils::certificate_for_genesis, Block},
     common::Author,
 };
-use aptos_types::validator_signer::ValidatorSigner;
+use aptos_types::{
+    on_chain_config::ValidatorTxnConfig, validator_signer::ValidatorSigner,
+    validator_txn::ValidatorTransaction,
+};
+use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::{future::BoxFuture, FutureExt};
 use std::{sync::Arc, time::Duration};
 
@@ -42,6 +46,8 @@ async fn test_proposal_generation_empty_tree() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        VTxnPoolState::default(),
+        ValidatorTxnConfig::default_disabled(),
     );
     let mut proposer_election =
         UnequivocalProposerElection::new(Box::new(RotatingProposer::new(vec![signer.author()], 1)));
@@ -66,6 +72,38 @@ async fn test_proposal_generation_empty_tree() {
     assert!(proposal_err.is_some());
 }
 
+#[tokio::test]
+async fn test_proposal_generation_with_validator_txns() {
+    let signer = ValidatorSigner::random(None);
+    let block_store = build_empty_tree();
+    let vtxn_pool = VTxnPoolState::default();
+    let vtxn = ValidatorTransaction::dummy(vec![0xFF; 10]);
+    let _guard = vtxn_pool.put(Arc::new(vtxn.clone()));
+    let mut proposal_generator = ProposalGenerator::new(
+        signer.author(),
+        block_store.clone(),
+        Arc::new(MockPayloadManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        Duration::ZERO,
+        10,
+        1000,
+        10,
+        PipelineBackpressureConfig::new_no_backoff(),
+        ChainHealthBackoffConfig::new_no_backoff(),
+        false,
+        vtxn_pool,
+        ValidatorTxnConfig::default_enabled(),
+    );
+    let mut proposer_election =
+        UnequivocalProposerElection::new(Box::new(RotatingProposer::new(vec![signer.author()], 1)));
+
+    let proposal_data = proposal_generator
+        .generate_proposal(1, &mut proposer_election, empty_callback())
+        .await
+        .unwrap();
+    assert_eq!(proposal_data.validator_txns(), Some(&vec![vtxn]));
+}
+
 #[tokio::test]
 async fn test_proposal_generation_parent() {
     let mut inserter = TreeInserter::default();
@@ -82,6 +120,8 @@ async fn test_proposal_generation_parent() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        VTxnPoolState::default(),
+        ValidatorTxnConfig::default_disabled(),
     );
     let mut proposer_election = UnequivocalProposerElection::new(Box::new(RotatingProposer::new(
         vec![inserter.signer().author()],
@@ -154,6 +194,8 @@ async fn test_old_proposal_generation() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        VTxnPoolState::default(),
+        ValidatorTxnConfig::default_disabled(),
     );
     let mut proposer_election = UnequivocalProposerElection::new(Box::new(RotatingProposer::new(
         vec![inserter.signer().author()],
@@ -191,6 +233,8 @@ async fn test_correct_failed_authors() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        VTxnPoolState::default(),
+        ValidatorTxnConfig::default_disabled(),
     );
     let mut proposer_election = UnequivocalProposerElection::new(Box::new(RotatingProposer::new(
         vec![author, peer1, peer2],
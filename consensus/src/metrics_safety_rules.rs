@@ -15,6 +15,7 @@ use aptos_safety_rules::{ConsensusState, Error, TSafetyRules};
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    randomness::RandomnessSeedMessage,
 };
 use std::sync::Arc;
 
@@ -93,6 +94,13 @@ impl TSafetyRules for MetricsSafetyRules {
         self.retry(|inner| monitor!("safety_rules", inner.sign_proposal(block_data)))
     }
 
+    fn sign_randomness_seed(
+        &mut self,
+        message: &RandomnessSeedMessage,
+    ) -> Result<bls12381::Signature, Error> {
+        self.retry(|inner| monitor!("safety_rules", inner.sign_randomness_seed(message)))
+    }
+
     fn sign_timeout_with_qc(
         &mut self,
         timeout: &TwoChainTimeout,
@@ -147,6 +155,7 @@ mod tests {
     use aptos_types::{
         epoch_change::EpochChangeProof,
         ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+        randomness::RandomnessSeedMessage,
     };
     use claims::{assert_matches, assert_ok};
 
@@ -197,6 +206,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn sign_randomness_seed(
+            &mut self,
+            _: &RandomnessSeedMessage,
+        ) -> Result<bls12381::Signature, Error> {
+            unimplemented!()
+        }
+
         fn sign_timeout_with_qc(
             &mut self,
             _: &TwoChainTimeout,
@@ -113,6 +113,7 @@ pub struct ConsensusNetworkClient<NetworkClient> {
 
 /// Supported protocols in preferred order (from highest priority to lowest).
 pub const RPC: &[ProtocolId] = &[
+    ProtocolId::ConsensusRpcZstd,
     ProtocolId::ConsensusRpcCompressed,
     ProtocolId::ConsensusRpcBcs,
     ProtocolId::ConsensusRpcJson,
@@ -120,6 +121,7 @@ pub const RPC: &[ProtocolId] = &[
 
 /// Supported protocols in preferred order (from highest priority to lowest).
 pub const DIRECT_SEND: &[ProtocolId] = &[
+    ProtocolId::ConsensusDirectSendZstd,
     ProtocolId::ConsensusDirectSendCompressed,
     ProtocolId::ConsensusDirectSendBcs,
     ProtocolId::ConsensusDirectSendJson,
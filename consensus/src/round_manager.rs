@@ -664,6 +664,46 @@ impl RoundManager {
             expected_failed_authors,
         );
 
+        // A seed signed by the proposer alone can be predicted and biased by the proposer, so
+        // randomness proofs are not accepted until the seed is derived from a threshold signature
+        // of the validators
+        ensure!(
+            proposal.block_data().randomness_proof().is_none(),
+            "[RoundManager] Proposal for block {} has an unexpected randomness proof",
+            proposal.round(),
+        );
+
+        // The validator txns themselves are verified along with the proposal's signature
+        let vtxn_config = self.onchain_config.effective_validator_txn_config();
+        ensure!(
+            proposal.validator_txns().is_some() == vtxn_config.enabled(),
+            "[RoundManager] Proposal for block {} carries validator txns: {}, expected: {}",
+            proposal.round(),
+            proposal.validator_txns().is_some(),
+            vtxn_config.enabled(),
+        );
+        if let Some(validator_txns) = proposal.validator_txns() {
+            let num_validator_txns = validator_txns.len() as u64;
+            ensure!(
+                num_validator_txns <= vtxn_config.per_block_limit_txn_count(),
+                "[RoundManager] Proposal for block {} has {} validator txns, exceeding the limit {}",
+                proposal.round(),
+                num_validator_txns,
+                vtxn_config.per_block_limit_txn_count(),
+            );
+            let validator_txns_bytes: u64 = validator_txns
+                .iter()
+                .map(|txn| txn.size_in_bytes() as u64)
+                .sum();
+            ensure!(
+                validator_txns_bytes <= vtxn_config.per_block_limit_total_bytes(),
+                "[RoundManager] Proposal for block {} has {} bytes of validator txns, exceeding the limit {}",
+                proposal.round(),
+                validator_txns_bytes,
+                vtxn_config.per_block_limit_total_bytes(),
+            );
+        }
+
         let block_time_since_epoch = Duration::from_micros(proposal.timestamp_usecs());
 
         ensure!(
@@ -35,11 +35,12 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    on_chain_config::{OnChainConsensusConfig, ValidatorSet},
+    on_chain_config::{OnChainConsensusConfig, ValidatorSet, ValidatorTxnConfig},
     validator_info::ValidatorInfo,
     validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
 };
+use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::{channel::mpsc, executor::block_on};
 use maplit::hashmap;
 use once_cell::sync::Lazy;
@@ -170,6 +171,8 @@ fn create_node_for_fuzzing() -> RoundManager {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        VTxnPoolState::default(),
+        ValidatorTxnConfig::default_disabled(),
     );
 
     //
@@ -63,12 +63,13 @@ use aptos_secure_storage::Storage;
 use aptos_types::{
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
-    on_chain_config::OnChainConsensusConfig,
+    on_chain_config::{OnChainConsensusConfig, ValidatorTxnConfig},
     transaction::SignedTransaction,
     validator_signer::ValidatorSigner,
     validator_verifier::{generate_validator_verifier, random_validator_verifier},
     waypoint::Waypoint,
 };
+use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::{
     channel::{mpsc, oneshot},
     executor::block_on,
@@ -253,6 +254,8 @@ impl NodeSetup {
             PipelineBackpressureConfig::new_no_backoff(),
             ChainHealthBackoffConfig::new_no_backoff(),
             false,
+            VTxnPoolState::default(),
+            ValidatorTxnConfig::default_disabled(),
         );
 
         let round_state = Self::create_round_state(time_service);
@@ -22,7 +22,8 @@ use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::{
     account_address::AccountAddress, contract_event::ContractEvent, epoch_state::EpochState,
-    ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
+    ledger_info::LedgerInfoWithSignatures, on_chain_config::BlockGasLimitType,
+    transaction::Transaction,
 };
 use fail::fail_point;
 use futures::{SinkExt, StreamExt};
@@ -58,7 +59,7 @@ pub struct ExecutionProxy {
     write_mutex: AsyncMutex<LogicalTime>,
     payload_manager: Mutex<Option<Arc<PayloadManager>>>,
     transaction_shuffler: Mutex<Option<Arc<dyn TransactionShuffler>>>,
-    maybe_block_gas_limit: Mutex<Option<u64>>,
+    block_gas_limit_type: Mutex<BlockGasLimitType>,
     transaction_deduper: Mutex<Option<Arc<dyn TransactionDeduper>>>,
 }
 
@@ -93,7 +94,7 @@ impl ExecutionProxy {
             write_mutex: AsyncMutex::new(LogicalTime::new(0, 0)),
             payload_manager: Mutex::new(None),
             transaction_shuffler: Mutex::new(None),
-            maybe_block_gas_limit: Mutex::new(None),
+            block_gas_limit_type: Mutex::new(BlockGasLimitType::NoLimit),
             transaction_deduper: Mutex::new(None),
         }
     }
@@ -129,7 +130,7 @@ impl StateComputer for ExecutionProxy {
         let deduped_txns = txn_deduper.dedup(txns);
         let shuffled_txns = txn_shuffler.shuffle(deduped_txns);
 
-        let block_gas_limit = *self.maybe_block_gas_limit.lock();
+        let block_gas_limit_type = self.block_gas_limit_type.lock().clone();
 
         // TODO: figure out error handling for the prologue txn
         let executor = self.executor.clone();
@@ -137,7 +138,7 @@ impl StateComputer for ExecutionProxy {
         let transactions_to_execute = block.transactions_to_execute(
             &self.validators.lock(),
             shuffled_txns.clone(),
-            block_gas_limit,
+            block_gas_limit_type.block_gas_limit(),
         );
 
         let compute_result = monitor!(
@@ -146,7 +147,7 @@ impl StateComputer for ExecutionProxy {
                 executor.execute_block(
                     (block_id, transactions_to_execute).into(),
                     parent_block_id,
-                    block_gas_limit,
+                    block_gas_limit_type,
                 )
             })
             .await
@@ -158,7 +159,7 @@ impl StateComputer for ExecutionProxy {
         // notify mempool about failed transaction
         if let Err(e) = self
             .txn_notifier
-            .notify_failed_txn(shuffled_txns, &compute_result)
+            .notify_failed_txn(block, shuffled_txns, &compute_result)
             .await
         {
             error!(
@@ -191,7 +192,7 @@ impl StateComputer for ExecutionProxy {
         let txn_deduper = self.transaction_deduper.lock().as_ref().unwrap().clone();
         let txn_shuffler = self.transaction_shuffler.lock().as_ref().unwrap().clone();
 
-        let block_gas_limit = *self.maybe_block_gas_limit.lock();
+        let block_gas_limit_type = self.block_gas_limit_type.lock().clone();
 
         for block in blocks {
             block_ids.push(block.id());
@@ -207,7 +208,7 @@ impl StateComputer for ExecutionProxy {
             txns.extend(block.transactions_to_commit(
                 &self.validators.lock(),
                 shuffled_txns,
-                block_gas_limit,
+                block_gas_limit_type.block_gas_limit(),
             ));
             reconfig_events.extend(block.reconfig_event());
         }
@@ -301,7 +302,7 @@ impl StateComputer for ExecutionProxy {
         epoch_state: &EpochState,
         payload_manager: Arc<PayloadManager>,
         transaction_shuffler: Arc<dyn TransactionShuffler>,
-        block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
         transaction_deduper: Arc<dyn TransactionDeduper>,
     ) {
         *self.validators.lock() = epoch_state
@@ -312,7 +313,7 @@ impl StateComputer for ExecutionProxy {
         self.transaction_shuffler
             .lock()
             .replace(transaction_shuffler);
-        *self.maybe_block_gas_limit.lock() = block_gas_limit;
+        *self.block_gas_limit_type.lock() = block_gas_limit_type;
         self.transaction_deduper.lock().replace(transaction_deduper);
     }
 
@@ -358,7 +359,7 @@ async fn test_commit_sync_race() {
             &self,
             _block: ExecutableBlock,
             _parent_block_id: HashValue,
-            _maybe_block_gas_limit: Option<u64>,
+            _block_gas_limit_type: BlockGasLimitType,
         ) -> Result<StateComputeResult, ExecutionError> {
             Ok(StateComputeResult::new_dummy())
         }
@@ -367,7 +368,7 @@ async fn test_commit_sync_race() {
             &self,
             _block: ExecutableBlock,
             _parent_block_id: HashValue,
-            _maybe_block_gas_limit: Option<u64>,
+            _block_gas_limit_type: BlockGasLimitType,
         ) -> Result<StateCheckpointOutput, ExecutionError> {
             todo!()
         }
@@ -401,6 +402,7 @@ async fn test_commit_sync_race() {
     impl TxnNotifier for RecordedCommit {
         async fn notify_failed_txn(
             &self,
+            _block: &Block,
             _txns: Vec<SignedTransaction>,
             _compute_results: &StateComputeResult,
         ) -> Result<(), MempoolError> {
@@ -457,7 +459,7 @@ async fn test_commit_sync_race() {
         &EpochState::empty(),
         Arc::new(PayloadManager::DirectMempool),
         create_transaction_shuffler(TransactionShufflerType::NoShuffling),
-        None,
+        BlockGasLimitType::NoLimit,
         create_transaction_deduper(TransactionDeduperType::NoDedup),
     );
     executor
@@ -47,6 +47,7 @@ use aptos_types::{
     validator_info::ValidatorInfo,
     waypoint::Waypoint,
 };
+use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::{channel::mpsc, StreamExt};
 use maplit::hashmap;
 use std::{collections::HashMap, iter::FromIterator, sync::Arc};
@@ -157,6 +158,7 @@ impl SMRNode {
             quorum_store_storage,
             reconfig_listener,
             bounded_executor,
+            VTxnPoolState::default(),
         );
         let (network_task, network_receiver) =
             NetworkTask::new(network_service_events, self_receiver);
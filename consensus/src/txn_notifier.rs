@@ -3,7 +3,7 @@
 
 use crate::{error::MempoolError, monitor};
 use anyhow::{format_err, Result};
-use aptos_consensus_types::common::RejectedTransactionSummary;
+use aptos_consensus_types::{block::Block, common::RejectedTransactionSummary};
 use aptos_executor_types::StateComputeResult;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_types::transaction::{SignedTransaction, TransactionStatus};
@@ -19,6 +19,7 @@ pub trait TxnNotifier: Send + Sync {
     /// state sync.)
     async fn notify_failed_txn(
         &self,
+        block: &Block,
         txns: Vec<SignedTransaction>,
         compute_results: &StateComputeResult,
     ) -> Result<(), MempoolError>;
@@ -48,6 +49,7 @@ impl MempoolNotifier {
 impl TxnNotifier for MempoolNotifier {
     async fn notify_failed_txn(
         &self,
+        block: &Block,
         txns: Vec<SignedTransaction>,
         compute_results: &StateComputeResult,
     ) -> Result<(), MempoolError> {
@@ -57,18 +59,22 @@ impl TxnNotifier for MempoolNotifier {
             return Ok(());
         }
         let compute_status = compute_results.compute_status();
-        if txns.len() + 2 != compute_status.len() {
+        // the validator txns are executed between the block meta and the user txns
+        let num_validator_txns = block.validator_txns().map_or(0, |txns| txns.len());
+        if txns.len() + num_validator_txns + 2 != compute_status.len() {
             // reconfiguration suffix blocks don't have any transactions
             if compute_status.is_empty() {
                 return Ok(());
             }
             return Err(format_err!(
-                "Block meta and state checkpoint txns are expected. txns len: {}, compute status len: {}",
+                "Block meta and state checkpoint txns are expected. txns len: {}, validator txns len: {}, compute status len: {}",
                 txns.len(),
+                num_validator_txns,
                 compute_status.len(),
             ).into());
         }
-        let user_txn_status = &compute_status[1..txns.len() + 1];
+        let user_txn_status =
+            &compute_status[1 + num_validator_txns..1 + num_validator_txns + txns.len()];
         for (txn, status) in txns.iter().zip_eq(user_txn_status) {
             if let TransactionStatus::Discard(reason) = status {
                 rejected_txns.push(RejectedTransactionSummary {
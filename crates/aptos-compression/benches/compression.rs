@@ -0,0 +1,146 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+extern crate criterion;
+
+use aptos_compression::{metrics::CompressionClient, ZstdOptions};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    transaction::{
+        ExecutionStatus, RawTransaction, Script, SignedTransaction, Transaction, TransactionOutput,
+        TransactionPayload, TransactionStatus,
+    },
+    write_set::WriteSet,
+};
+use criterion::{BenchmarkId, Criterion, Throughput};
+
+const MAX_COMPRESSION_SIZE: usize = 64 * 1024 * 1024;
+
+/// The zstd compression levels to compare against LZ4
+const ZSTD_LEVELS: [i32; 4] = [1, 3, 9, 19];
+
+/// Compares LZ4 and zstd (at different levels) on state sync like payloads
+fn bench_group(c: &mut Criterion) {
+    for (name, data) in [
+        ("transactions", create_transactions(1000)),
+        ("transaction_outputs", create_transaction_outputs(1000)),
+    ] {
+        let mut group = c.benchmark_group(format!("compression_{}", name));
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.sample_size(10);
+
+        // Benchmark the compression
+        group.bench_function("lz4_compress", |b| b.iter(|| compress_lz4(data.clone())));
+        for level in ZSTD_LEVELS {
+            let options = ZstdOptions {
+                level,
+                dictionary: None,
+            };
+            group.bench_with_input(
+                BenchmarkId::new("zstd_compress", level),
+                &options,
+                |b, options| b.iter(|| compress_zstd(data.clone(), options)),
+            );
+        }
+
+        // Benchmark the decompression
+        let compressed_data = compress_lz4(data.clone());
+        group.bench_function("lz4_decompress", |b| {
+            b.iter(|| {
+                aptos_compression::decompress(
+                    &compressed_data,
+                    CompressionClient::StateSync,
+                    MAX_COMPRESSION_SIZE,
+                )
+                .unwrap()
+            })
+        });
+        for level in ZSTD_LEVELS {
+            let options = ZstdOptions {
+                level,
+                dictionary: None,
+            };
+            let compressed_data = compress_zstd(data.clone(), &options);
+            group.bench_with_input(
+                BenchmarkId::new("zstd_decompress", level),
+                &options,
+                |b, options| {
+                    b.iter(|| {
+                        aptos_compression::decompress_zstd(
+                            &compressed_data,
+                            CompressionClient::StateSync,
+                            MAX_COMPRESSION_SIZE,
+                            options,
+                        )
+                        .unwrap()
+                    })
+                },
+            );
+        }
+
+        group.finish();
+    }
+}
+
+fn compress_lz4(data: Vec<u8>) -> Vec<u8> {
+    aptos_compression::compress(data, CompressionClient::StateSync, MAX_COMPRESSION_SIZE).unwrap()
+}
+
+fn compress_zstd(data: Vec<u8>, options: &ZstdOptions) -> Vec<u8> {
+    aptos_compression::compress_zstd(
+        data,
+        CompressionClient::StateSync,
+        MAX_COMPRESSION_SIZE,
+        options,
+    )
+    .unwrap()
+}
+
+/// Creates the BCS encoding of the given number of signed transactions
+fn create_transactions(num_transactions: u64) -> Vec<u8> {
+    let transactions: Vec<_> = (0..num_transactions)
+        .map(|sequence_number| {
+            let private_key = Ed25519PrivateKey::generate_for_testing();
+            let raw_transaction = RawTransaction::new(
+                AccountAddress::random(),
+                sequence_number,
+                TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+                0,
+                0,
+                0,
+                ChainId::new(10),
+            );
+            Transaction::UserTransaction(SignedTransaction::new(
+                raw_transaction.clone(),
+                private_key.public_key(),
+                private_key.sign(&raw_transaction).unwrap(),
+            ))
+        })
+        .collect();
+    bcs::to_bytes(&transactions).unwrap()
+}
+
+/// Creates the BCS encoding of the given number of transaction outputs
+fn create_transaction_outputs(num_outputs: u64) -> Vec<u8> {
+    let outputs: Vec<_> = (0..num_outputs)
+        .map(|_| {
+            TransactionOutput::new(
+                WriteSet::default(),
+                vec![],
+                0,
+                TransactionStatus::Keep(ExecutionStatus::MiscellaneousError(None)),
+            )
+        })
+        .collect();
+    bcs::to_bytes(&outputs).unwrap()
+}
+
+criterion_group!(
+    name = compression_benches;
+    config = Criterion::default();
+    targets = bench_group
+);
+criterion_main!(compression_benches);
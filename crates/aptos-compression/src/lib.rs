@@ -16,6 +16,8 @@ use thiserror::Error;
 /// sent across the network (e.g., by state sync and consensus).
 /// Internally, it uses LZ4 in fast mode to compress the data.
 /// See <https://github.com/10xGenomics/lz4-rs> for more information.
+/// Zstd is offered as well (see [`compress_zstd`]), for data that
+/// is worth a better compression ratio.
 ///
 /// Note: the crate also exposes some basic compression metrics
 /// that can be used to track the cumulative compression ratio
@@ -23,6 +25,11 @@ use thiserror::Error;
 pub mod metrics;
 #[cfg(test)]
 mod tests;
+mod zstd_compression;
+
+pub use zstd_compression::{
+    compress_zstd, decompress_zstd, ZstdDictionary, ZstdOptions, DEFAULT_ZSTD_COMPRESSION_LEVEL,
+};
 
 /// The acceleration parameter to use for FAST compression mode.
 /// This was determined anecdotally.
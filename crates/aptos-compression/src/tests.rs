@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::CompressionClient;
+use crate::{CompressionClient, ZstdDictionary, ZstdOptions};
 use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::HashValue, PrivateKey, SigningKey, Uniform};
 use aptos_types::{
     account_address::AccountAddress,
@@ -66,6 +66,139 @@ fn test_compression_limits() {
     assert!(maybe_decompressed_bytes.is_err());
 }
 
+#[test]
+fn test_zstd_compression() {
+    // Test all compression levels
+    let outputs_with_proof = create_output_list_with_proof(13434, 14000, 15000);
+    for level in 1..=22 {
+        let options = ZstdOptions {
+            level,
+            dictionary: None,
+        };
+        test_zstd_compress_and_decompress(outputs_with_proof.clone(), &options);
+    }
+
+    // Test transactions with proof and epoch ending ledger infos
+    let options = ZstdOptions::default();
+    let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
+    test_zstd_compress_and_decompress(transactions_with_proof, &options);
+    let epoch_ending_ledger_infos = create_epoch_ending_ledger_infos(0, 999);
+    test_zstd_compress_and_decompress(epoch_ending_ledger_infos, &options);
+}
+
+#[test]
+fn test_zstd_compression_limits() {
+    let too_small_bytes = 1;
+    let options = ZstdOptions::default();
+    let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
+
+    // Test compression limit
+    let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+    let maybe_compressed_bytes = crate::compress_zstd(
+        bcs_encoded_bytes,
+        CompressionClient::StateSync,
+        too_small_bytes,
+        &options,
+    );
+    assert!(maybe_compressed_bytes.is_err());
+
+    // Test decompression limit
+    let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+    let compressed_bytes = crate::compress_zstd(
+        bcs_encoded_bytes,
+        CompressionClient::StateSync,
+        MAX_COMPRESSION_SIZE,
+        &options,
+    )
+    .unwrap();
+    let maybe_decompressed_bytes = crate::decompress_zstd(
+        &compressed_bytes,
+        CompressionClient::StateSync,
+        too_small_bytes,
+        &options,
+    );
+    assert!(maybe_decompressed_bytes.is_err());
+
+    // Test decompression of data that isn't zstd compressed
+    let lz4_compressed_bytes = crate::compress(
+        bcs::to_bytes(&transactions_with_proof).unwrap(),
+        CompressionClient::StateSync,
+        MAX_COMPRESSION_SIZE,
+    )
+    .unwrap();
+    let maybe_decompressed_bytes = crate::decompress_zstd(
+        &lz4_compressed_bytes,
+        CompressionClient::StateSync,
+        MAX_COMPRESSION_SIZE,
+        &options,
+    );
+    assert!(maybe_decompressed_bytes.is_err());
+}
+
+#[test]
+fn test_zstd_dictionary_compression() {
+    // Train a dictionary on single transactions
+    let samples: Vec<_> = (0..1000)
+        .map(|sequence_number| bcs::to_bytes(&create_test_transaction(sequence_number)).unwrap())
+        .collect();
+    let dictionary = zstd::dict::from_samples(&samples, 16 * 1024).unwrap();
+    let options = ZstdOptions {
+        level: 3,
+        dictionary: Some(ZstdDictionary::new(dictionary).unwrap()),
+    };
+
+    // Test compression with the dictionary
+    let transaction = create_test_transaction(2000);
+    test_zstd_compress_and_decompress(transaction.clone(), &options);
+
+    // Data compressed with the dictionary can't be decompressed without it
+    let compressed_bytes = crate::compress_zstd(
+        bcs::to_bytes(&transaction).unwrap(),
+        CompressionClient::Mempool,
+        MAX_COMPRESSION_SIZE,
+        &options,
+    )
+    .unwrap();
+    let maybe_decompressed_bytes = crate::decompress_zstd(
+        &compressed_bytes,
+        CompressionClient::Mempool,
+        MAX_COMPRESSION_SIZE,
+        &ZstdOptions::default(),
+    );
+    assert!(maybe_decompressed_bytes.is_err());
+
+    // But data compressed without a dictionary can be decompressed with one
+    test_zstd_compress_and_decompress(transaction.clone(), &ZstdOptions::default());
+    let compressed_bytes = crate::compress_zstd(
+        bcs::to_bytes(&transaction).unwrap(),
+        CompressionClient::Mempool,
+        MAX_COMPRESSION_SIZE,
+        &ZstdOptions::default(),
+    )
+    .unwrap();
+    let decompressed_bytes = crate::decompress_zstd(
+        &compressed_bytes,
+        CompressionClient::Mempool,
+        MAX_COMPRESSION_SIZE,
+        &options,
+    )
+    .unwrap();
+    assert_eq!(transaction, bcs::from_bytes(&decompressed_bytes).unwrap());
+}
+
+#[test]
+fn test_zstd_invalid_dictionary() {
+    // Raw content dictionaries are rejected
+    assert!(ZstdDictionary::new(vec![]).is_err());
+    assert!(ZstdDictionary::new(b"raw content dictionary".to_vec()).is_err());
+
+    // Dictionaries without an ID are rejected
+    let mut dictionary = vec![0x37, 0xA4, 0x30, 0xEC, 0, 0, 0, 0];
+    assert!(ZstdDictionary::new(dictionary.clone()).is_err());
+    dictionary[4] = 1;
+    assert_eq!(ZstdDictionary::new(dictionary).unwrap().id(), 1);
+}
+
 /// Ensures that the given object can be compressed and decompressed successfully
 /// when BCS encoded.
 fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(object: T) {
@@ -196,3 +329,29 @@ fn create_transaction_list_with_proof(
 
     transaction_list_with_proof
 }
+
+/// Ensures that the given object can be compressed and decompressed successfully
+/// with zstd when BCS encoded.
+fn test_zstd_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(
+    object: T,
+    options: &ZstdOptions,
+) {
+    let bcs_encoded_bytes = bcs::to_bytes(&object).unwrap();
+    let compressed_bytes = crate::compress_zstd(
+        bcs_encoded_bytes,
+        CompressionClient::StateSync,
+        MAX_COMPRESSION_SIZE,
+        options,
+    )
+    .unwrap();
+    let decompressed_bytes = crate::decompress_zstd(
+        &compressed_bytes,
+        CompressionClient::StateSync,
+        MAX_COMPRESSION_SIZE,
+        options,
+    )
+    .unwrap();
+    let decoded_object = bcs::from_bytes::<T>(&decompressed_bytes).unwrap();
+
+    assert_eq!(object, decoded_object);
+}
@@ -0,0 +1,277 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Zstd compression, for data that is worth a better compression ratio
+//! than LZ4 offers in fast mode (e.g., state sync payloads). Data may be
+//! compressed with a dictionary, which helps most for small messages with
+//! a shared structure. Only holders of the same dictionary can decompress it.
+//! See <https://github.com/gyscos/zstd-rs> for more information.
+
+use crate::{
+    calculate_relative_size,
+    metrics::{
+        increment_compression_byte_count, increment_compression_error,
+        start_compression_operation_timer, CompressionClient, COMPRESS, COMPRESSED_BYTES,
+        DECOMPRESS, RAW_BYTES,
+    },
+    CompressedData, CompressionError,
+};
+use aptos_logger::prelude::*;
+use std::{
+    io::{Error, ErrorKind},
+    sync::Arc,
+};
+use zstd::bulk::{Compressor, Decompressor};
+
+/// The compression level zstd itself defaults to
+pub const DEFAULT_ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// The magic numbers starting zstd frames and (non-raw) zstd dictionaries
+const FRAME_MAGIC_NUMBER: u32 = 0xFD2FB528;
+const DICTIONARY_MAGIC_NUMBER: u32 = 0xEC30A437;
+
+/// A zstd dictionary, as trained by `zstd --train` on samples of the data
+/// to compress.
+#[derive(Clone, Debug)]
+pub struct ZstdDictionary {
+    id: u32,
+    bytes: Arc<Vec<u8>>,
+}
+
+impl ZstdDictionary {
+    /// Wraps the given dictionary. Raw content dictionaries are not
+    /// supported, as they carry no ID to recognize the data compressed
+    /// with them by.
+    pub fn new(bytes: Vec<u8>) -> Result<Self, CompressionError> {
+        if bytes.len() < 8 || read_u32(&bytes[..4]) != DICTIONARY_MAGIC_NUMBER {
+            return Err(CompressionError(
+                "The zstd dictionary has no dictionary header! Raw content dictionaries are not supported.".into(),
+            ));
+        }
+        let id = read_u32(&bytes[4..8]);
+        if id == 0 {
+            return Err(CompressionError(
+                "The zstd dictionary must have a non-zero ID!".into(),
+            ));
+        }
+        Ok(Self {
+            id,
+            bytes: Arc::new(bytes),
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+/// The options to compress data with zstd
+#[derive(Clone, Debug)]
+pub struct ZstdOptions {
+    /// The compression level (1 to 22). Higher levels are slower, but compress better.
+    pub level: i32,
+    /// The dictionary to compress with, if any
+    pub dictionary: Option<ZstdDictionary>,
+}
+
+impl Default for ZstdOptions {
+    fn default() -> Self {
+        Self {
+            level: DEFAULT_ZSTD_COMPRESSION_LEVEL,
+            dictionary: None,
+        }
+    }
+}
+
+/// Compresses the raw data stream with zstd
+pub fn compress_zstd(
+    raw_data: Vec<u8>,
+    client: CompressionClient,
+    max_bytes: usize,
+    options: &ZstdOptions,
+) -> Result<CompressedData, CompressionError> {
+    if raw_data.len() > max_bytes {
+        return Err(CompressionError(format!(
+            "Uncompressed size greater than max. size: {}, max: {}",
+            raw_data.len(),
+            max_bytes
+        )));
+    }
+    // Start the compression timer
+    let timer = start_compression_operation_timer(COMPRESS, client.clone());
+
+    // Compress the data. The frames carry the content size, to bound the
+    // decompressed size before decompressing.
+    let dictionary = options
+        .dictionary
+        .as_ref()
+        .map_or(&[][..], |dictionary| &dictionary.bytes[..]);
+    let compressed_data = match Compressor::with_dictionary(options.level, dictionary)
+        .and_then(|mut compressor| compressor.compress(&raw_data))
+    {
+        Ok(compressed_data) => compressed_data,
+        Err(error) => {
+            increment_compression_error(COMPRESS, client);
+            return Err(CompressionError(format!(
+                "Failed to compress the data: {}",
+                error
+            )));
+        },
+    };
+
+    // Ensure that the compressed data size is not greater than the max bytes limit
+    if compressed_data.len() > max_bytes {
+        return Err(CompressionError(format!(
+            "Compressed size greater than max. size: {}, max: {}",
+            compressed_data.len(),
+            max_bytes
+        )));
+    }
+
+    // Stop the timer and update the metrics
+    let compression_duration = timer.stop_and_record();
+    increment_compression_byte_count(RAW_BYTES, client.clone(), raw_data.len() as u64);
+    increment_compression_byte_count(COMPRESSED_BYTES, client, compressed_data.len() as u64);
+
+    // Log the relative data compression statistics
+    let relative_data_size = calculate_relative_size(&raw_data, &compressed_data);
+    trace!(
+        "Compressed {} bytes to {} bytes ({} %) with zstd in {} seconds.",
+        raw_data.len(),
+        compressed_data.len(),
+        relative_data_size,
+        compression_duration
+    );
+
+    Ok(compressed_data)
+}
+
+/// Decompresses the zstd compressed data stream. Data compressed with a
+/// dictionary is decompressed with the dictionary of the options, which
+/// must be the same.
+pub fn decompress_zstd(
+    compressed_data: &[u8],
+    client: CompressionClient,
+    max_size: usize,
+    options: &ZstdOptions,
+) -> Result<Vec<u8>, CompressionError> {
+    // Start the decompression timer
+    let timer = start_compression_operation_timer(DECOMPRESS, client.clone());
+
+    // Check the size and dictionary of the data
+    let (size, dictionary_id) = match get_frame_size_and_dictionary(compressed_data, max_size) {
+        Ok(frame_header) => frame_header,
+        Err(error) => {
+            increment_compression_error(DECOMPRESS, client);
+            return Err(CompressionError(format!(
+                "Failed to parse the frame header: {}",
+                error
+            )));
+        },
+    };
+    let dictionary = match (dictionary_id, options.dictionary.as_ref()) {
+        (0, _) => &[][..],
+        (id, Some(dictionary)) if id == dictionary.id => &dictionary.bytes[..],
+        (id, _) => {
+            increment_compression_error(DECOMPRESS, client);
+            return Err(CompressionError(format!(
+                "The data was compressed with an unknown dictionary: {}",
+                id
+            )));
+        },
+    };
+
+    // Decompress the data
+    let raw_data = match Decompressor::with_dictionary(dictionary)
+        .and_then(|mut decompressor| decompressor.decompress(compressed_data, size))
+    {
+        Ok(raw_data) => raw_data,
+        Err(error) => {
+            increment_compression_error(DECOMPRESS, client);
+            return Err(CompressionError(format!(
+                "Failed to decompress the data: {}",
+                error
+            )));
+        },
+    };
+
+    // Stop the timer and log the relative data compression statistics
+    let decompression_duration = timer.stop_and_record();
+    let relative_data_size = calculate_relative_size(compressed_data, &raw_data);
+    trace!(
+        "Decompressed {} bytes to {} bytes ({} %) with zstd in {} seconds.",
+        compressed_data.len(),
+        raw_data.len(),
+        relative_data_size,
+        decompression_duration
+    );
+
+    Ok(raw_data)
+}
+
+/// Parses the content size and dictionary ID (0 if none) from the header of
+/// a single zstd frame.
+/// see: https://github.com/facebook/zstd/blob/dev/doc/zstd_compression_format.md#frame_header
+fn get_frame_size_and_dictionary(src: &[u8], max_size: usize) -> std::io::Result<(usize, u32)> {
+    if src.len() < 5 || read_u32(&src[..4]) != FRAME_MAGIC_NUMBER {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Source buffer must start with a zstd frame header.",
+        ));
+    }
+
+    // Find the lengths of the header fields from the frame header descriptor
+    let descriptor = src[4];
+    let single_segment = descriptor & 0x20 != 0;
+    let window_descriptor_len = if single_segment { 0 } else { 1 };
+    let dictionary_id_len = [0, 1, 2, 4][(descriptor & 0x03) as usize];
+    let content_size_len = match descriptor >> 6 {
+        0 if single_segment => 1,
+        0 => 0,
+        1 => 2,
+        2 => 4,
+        _ => 8,
+    };
+    if content_size_len == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The frame header must contain the content size.",
+        ));
+    }
+    let dictionary_id_start = 5 + window_descriptor_len;
+    let content_size_start = dictionary_id_start + dictionary_id_len;
+    if src.len() < content_size_start + content_size_len {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Source buffer must contain the entire frame header.",
+        ));
+    }
+
+    let dictionary_id = read_u64(&src[dictionary_id_start..content_size_start]) as u32;
+    let mut size = read_u64(&src[content_size_start..content_size_start + content_size_len]);
+    if content_size_len == 2 {
+        size += 256;
+    }
+
+    if size > max_size as u64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Given size parameter is too big: {} > {}", size, max_size),
+        ));
+    }
+
+    Ok((size as usize, dictionary_id))
+}
+
+/// Reads a little-endian u32 from the given 4 bytes
+fn read_u32(bytes: &[u8]) -> u32 {
+    read_u64(bytes) as u32
+}
+
+/// Reads a little-endian integer of up to 8 bytes
+fn read_u64(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |value, byte| (value << 8) | *byte as u64)
+}
@@ -307,6 +307,21 @@ impl Client {
         self.json(response).await
     }
 
+    /// Executes a view function, returning the BCS encoded values it returns.
+    pub async fn view_bcs(
+        &self,
+        request: &ViewRequest,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Vec<u8>>>> {
+        let mut url = self.build_path("view")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("ledger_version={}", version).as_str()));
+        }
+
+        let response = self.post_bcs(url, serde_json::to_value(request)?).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn simulate(
         &self,
         txn: &SignedTransaction,
@@ -189,7 +189,7 @@ impl ApiError {
             ApiError::VersionPruned(_) => "Version pruned",
             ApiError::BlockPruned(_) => "Block pruned",
             ApiError::InvalidInput(_) => "Invalid input",
-            ApiError::InvalidTransactionUpdate(_) => "Invalid transaction update.  Can only update gas unit price",
+            ApiError::InvalidTransactionUpdate(_) => "Invalid transaction update.  Can only replace with a sufficiently higher gas unit price",
             ApiError::SequenceNumberTooOld(_) => "Sequence number too old.  Please create a new transaction with an updated sequence number",
             ApiError::VmError(_) => "Transaction submission failed due to VM error",
             ApiError::MempoolIsFull(_) => "Mempool is full all accounts",
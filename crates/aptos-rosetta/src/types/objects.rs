@@ -853,6 +853,7 @@ pub enum TransactionType {
     Genesis,
     BlockMetadata,
     StateCheckpoint,
+    Validator,
 }
 
 impl Display for TransactionType {
@@ -863,6 +864,7 @@ impl Display for TransactionType {
             Genesis => "Genesis",
             BlockMetadata => "BlockResource",
             StateCheckpoint => "StateCheckpoint",
+            Validator => "Validator",
         })
     }
 }
@@ -880,6 +882,7 @@ impl Transaction {
             GenesisTransaction(_) => (TransactionType::Genesis, None, txn.info, txn.events),
             BlockMetadata(_) => (TransactionType::BlockMetadata, None, txn.info, txn.events),
             StateCheckpoint(_) => (TransactionType::StateCheckpoint, None, txn.info, vec![]),
+            ValidatorTransaction(_) => (TransactionType::Validator, None, txn.info, txn.events),
         };
 
         // Operations must be sequential and operation index must always be in the same order
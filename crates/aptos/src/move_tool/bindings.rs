@@ -0,0 +1,240 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{
+            CliCommand, CliError, CliTypedResult, MovePackageDir, ProfileOptions, RestOptions,
+        },
+        utils::dir_default_to_current,
+    },
+    move_tool::CachedPackageRegistry,
+};
+use aptos_framework::{
+    get_metadata_from_compiled_module, unzip_metadata, BuildOptions, BuiltPackage,
+};
+use aptos_sdk_builder::bindings::{BindingsInstaller, BindingsModule};
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use heck::KebabCase;
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+const DEFAULT_APTOS_SDK_DEPENDENCY: &str =
+    r#"{ git = "https://github.com/aptos-labs/aptos-core", branch = "devnet" }"#;
+
+/// Generates a Rust crate with typed bindings for a Move package
+///
+/// The crate has a module for each module of the package, with builders of transaction payloads
+/// for its entry functions, async callers of its view functions over the REST client, and serde
+/// structs for its structs, which decode resources and events from BCS.
+///
+/// Bindings are generated for the local package in `--package-dir`, or for the published
+/// package `--package` at `--account`.
+#[derive(Parser)]
+pub struct GenerateRustBindings {
+    /// Address of the account the package is published at
+    ///
+    /// If set, bindings are generated for the published package instead of a local one.
+    #[clap(long, value_parser = crate::common::types::load_account_arg, requires = "package")]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// Name of the published package
+    #[clap(long, requires = "account")]
+    pub package: Option<String>,
+
+    /// Name of the generated crate. Defaults to `<package name>-bindings`
+    #[clap(long)]
+    pub crate_name: Option<String>,
+
+    /// Directory to write the crate to. Defaults to the current directory.
+    #[clap(long, value_parser)]
+    pub crate_dir: Option<PathBuf>,
+
+    /// The `aptos-sdk` dependency of the generated crate, in `Cargo.toml` syntax
+    #[clap(long, default_value = DEFAULT_APTOS_SDK_DEPENDENCY)]
+    pub aptos_sdk_dependency: String,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// The modules of a package and of its dependencies.
+struct PackageModules {
+    name: String,
+    modules: Vec<(CompiledModule, Option<SourceMap>)>,
+    dependencies: Vec<CompiledModule>,
+}
+
+#[async_trait]
+impl CliCommand<PathBuf> for GenerateRustBindings {
+    fn command_name(&self) -> &'static str {
+        "GenerateRustBindings"
+    }
+
+    async fn execute(self) -> CliTypedResult<PathBuf> {
+        let package = match (self.account, &self.package) {
+            (Some(account), Some(package)) => self.published_package(account, package).await?,
+            _ => self.local_package()?,
+        };
+
+        let modules = package
+            .modules
+            .iter()
+            .map(|(module, source_map)| BindingsModule {
+                module,
+                source_map: source_map.as_ref(),
+                view_functions: view_functions(module),
+            })
+            .collect::<Vec<_>>();
+        let crate_name = self
+            .crate_name
+            .unwrap_or_else(|| format!("{}-bindings", package.name.to_kebab_case()));
+        let crate_path = BindingsInstaller::new(
+            dir_default_to_current(self.crate_dir)?,
+            self.aptos_sdk_dependency,
+        )
+        .install(
+            &crate_name,
+            &package.name,
+            &modules,
+            &package.dependencies.iter().collect::<Vec<_>>(),
+        )
+        .map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to generate bindings: {:#}", err))
+        })?;
+        println!(
+            "Generated bindings for {} module(s) of package {} in `{}`",
+            modules.len(),
+            package.name,
+            crate_path.display()
+        );
+        Ok(crate_path)
+    }
+}
+
+impl GenerateRustBindings {
+    fn local_package(&self) -> CliTypedResult<PackageModules> {
+        let build_options = BuildOptions {
+            dev: self.move_options.dev,
+            named_addresses: self.move_options.named_addresses(),
+            install_dir: self.move_options.output_dir.clone(),
+            skip_fetch_latest_git_deps: self.move_options.skip_fetch_latest_git_deps,
+            bytecode_version: self.move_options.bytecode_version,
+            compiler_version: self.move_options.compiler_version,
+            skip_attribute_checks: self.move_options.skip_attribute_checks,
+            ..BuildOptions::default()
+        };
+        let package = BuiltPackage::build(self.move_options.get_package_path()?, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let modules = package
+            .named_modules()
+            .map(|named| (named.module.clone(), Some(named.source_map.clone())))
+            .collect::<Vec<_>>();
+        let module_ids = modules
+            .iter()
+            .map(|(module, _)| module.self_id())
+            .collect::<BTreeSet<_>>();
+        let dependencies = package
+            .all_modules()
+            .filter(|module| !module_ids.contains(&module.self_id()))
+            .cloned()
+            .collect();
+        Ok(PackageModules {
+            name: package.name().to_string(),
+            modules,
+            dependencies,
+        })
+    }
+
+    async fn published_package(
+        &self,
+        account: AccountAddress,
+        package_name: &str,
+    ) -> CliTypedResult<PackageModules> {
+        let url = self.rest_options.url(&self.profile_options)?;
+        let client = self.rest_options.client(&self.profile_options)?;
+        let registry = CachedPackageRegistry::create(url, account).await?;
+        let package = registry
+            .get_package(package_name)
+            .await
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+
+        let mut modules = vec![];
+        for name in package.module_names() {
+            let bytes = client
+                .get_account_module_bcs(account, name)
+                .await?
+                .into_inner();
+            let module = deserialize_module(&bytes)?;
+            // Source maps are only part of the package metadata if they were included when the
+            // package was published
+            let source_map = package
+                .module(name)
+                .ok()
+                .map(|metadata| metadata.zipped_source_map_raw().to_vec())
+                .filter(|zipped| !zipped.is_empty())
+                .and_then(|zipped| unzip_metadata(&zipped).ok())
+                .and_then(|bytes| bcs::from_bytes::<SourceMap>(&bytes).ok());
+            modules.push((module, source_map));
+        }
+
+        // Fetch all modules the package depends on, which define the structs it may use
+        let mut fetched = modules
+            .iter()
+            .map(|(module, _)| module.self_id())
+            .collect::<BTreeSet<_>>();
+        let mut pending = modules
+            .iter()
+            .flat_map(|(module, _)| module.immediate_dependencies())
+            .collect::<Vec<ModuleId>>();
+        let mut dependencies = BTreeMap::new();
+        while let Some(module_id) = pending.pop() {
+            if !fetched.insert(module_id.clone()) {
+                continue;
+            }
+            let bytes = client
+                .get_account_module_bcs(*module_id.address(), module_id.name().as_str())
+                .await?
+                .into_inner();
+            let module = deserialize_module(&bytes)?;
+            pending.extend(module.immediate_dependencies());
+            dependencies.insert(module_id, module);
+        }
+
+        Ok(PackageModules {
+            name: package.name().to_string(),
+            modules,
+            dependencies: dependencies.into_values().collect(),
+        })
+    }
+}
+
+fn deserialize_module(bytes: &[u8]) -> CliTypedResult<CompiledModule> {
+    CompiledModule::deserialize(bytes)
+        .map_err(|err| CliError::UnexpectedError(format!("Invalid module bytecode: {}", err)))
+}
+
+/// Returns the functions of `module` which are marked as `#[view]`.
+fn view_functions(module: &CompiledModule) -> BTreeSet<Identifier> {
+    get_metadata_from_compiled_module(module)
+        .map(|metadata| {
+            metadata
+                .fun_attributes
+                .into_iter()
+                .filter(|(_, attrs)| attrs.iter().any(|attr| attr.is_view_function()))
+                .filter_map(|(name, _)| Identifier::new(name).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
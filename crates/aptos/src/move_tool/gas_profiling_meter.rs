@@ -0,0 +1,59 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_gas_algebra::Gas;
+use aptos_gas_meter::{AptosGasMeter, StandardGasAlgebra, StandardGasMeter};
+use aptos_gas_schedule::{AptosGasParameters, LATEST_GAS_FEATURE_VERSION};
+use aptos_vm_types::storage::StorageGasParameters;
+use move_binary_format::errors::VMResult;
+use move_core_types::{identifier::IdentStr, language_storage::ModuleId};
+use move_unit_test::test_runner::GasProfilingMeter;
+use move_vm_runtime::session::{SerializedReturnValues, Session};
+
+/// Charges unit tests with the `StandardGasMeter` used for transactions, so that the gas used by a
+/// test matches the execution and IO gas of a transaction doing the same work. Storage fees are
+/// not charged, as tests do not produce a write set.
+///
+/// The meter charges internal gas units, which are converted to the external gas units that
+/// transactions are charged in (and `max_gas_amount` is specified in), rounding up.
+pub struct AptosGasProfilingMeter {
+    gas_params: AptosGasParameters,
+}
+
+impl AptosGasProfilingMeter {
+    pub fn new(gas_params: AptosGasParameters) -> Self {
+        Self { gas_params }
+    }
+}
+
+impl GasProfilingMeter for AptosGasProfilingMeter {
+    fn execute_function(
+        &self,
+        session: &mut Session,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        args: Vec<Vec<u8>>,
+    ) -> (VMResult<SerializedReturnValues>, u64) {
+        let vm_gas_params = self.gas_params.vm.clone();
+        let storage_gas_params =
+            StorageGasParameters::unlimited(vm_gas_params.txn.free_write_bytes_quota);
+        // A test may use as much gas as a single transaction
+        let balance = vm_gas_params.txn.maximum_number_of_gas_units;
+        let mut gas_meter = StandardGasMeter::new(StandardGasAlgebra::new(
+            LATEST_GAS_FEATURE_VERSION,
+            vm_gas_params,
+            storage_gas_params,
+            balance,
+        ));
+        let result = session.execute_function_bypass_visibility(
+            module_id,
+            function_name,
+            vec![],
+            args,
+            &mut gas_meter,
+        );
+        let gas_used: Gas = (gas_meter.execution_gas_used() + gas_meter.io_gas_used())
+            .to_unit_round_up_with_params(&self.gas_params.vm.txn);
+        (result, gas_used.into())
+    }
+}
@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aptos_debug_natives;
+pub mod bindings;
 pub mod coverage;
 mod disassembler;
+mod gas_profiling_meter;
 mod manifest;
+pub mod mutate;
 pub mod package_hooks;
 mod show;
 pub mod stored_package;
@@ -28,6 +31,7 @@ use crate::{
     move_tool::{
         coverage::SummaryCoverage,
         disassembler::Disassemble,
+        gas_profiling_meter::AptosGasProfilingMeter,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
     },
     CliCommand, CliResult,
@@ -37,13 +41,17 @@ use aptos_framework::{
     build_model, docgen::DocgenOptions, extended_checks, natives::code::UpgradePolicy,
     prover::ProverOptions, BuildOptions, BuiltPackage,
 };
-use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
+use aptos_gas_schedule::{
+    AptosGasParameters, InitialGasSchedule, MiscGasParameters, NativeGasParameters,
+};
 use aptos_rest_client::aptos_api_types::{
     EntryFunctionId, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
 };
 use aptos_transactional_test_harness::run_aptos_test;
 use aptos_types::{
-    account_address::{create_resource_address, AccountAddress},
+    account_address::{
+        create_object_code_deployment_address, create_resource_address, AccountAddress,
+    },
     transaction::{TransactionArgument, TransactionPayload},
 };
 use async_trait::async_trait;
@@ -59,7 +67,9 @@ use move_core_types::{identifier::Identifier, language_storage::ModuleId, u256::
 use move_package::{
     source_package::layout::SourcePackageLayout, BuildConfig, CompilerConfig, CompilerVersion,
 };
-use move_unit_test::UnitTestingConfig;
+use move_unit_test::{
+    fuzz::DEFAULT_FUZZ_ITERATIONS, test_runner::GasProfilingMeter, UnitTestingConfig,
+};
 pub use package_hooks::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -68,6 +78,7 @@ use std::{
     fmt::{Display, Formatter},
     path::{Path, PathBuf},
     str::FromStr,
+    sync::Arc,
 };
 pub use stored_package::*;
 use tokio::task;
@@ -87,11 +98,14 @@ pub enum MoveTool {
     #[clap(subcommand)]
     Coverage(coverage::CoveragePackage),
     CreateResourceAccountAndPublishPackage(CreateResourceAccountAndPublishPackage),
+    DeployObject(DeployObjectCode),
     Disassemble(Disassemble),
     Document(DocumentPackage),
     Download(DownloadPackage),
+    GenerateRustBindings(bindings::GenerateRustBindings),
     Init(InitPackage),
     List(ListPackage),
+    Mutate(mutate::MutatePackage),
     Prove(ProvePackage),
     Publish(PublishPackage),
     Run(RunFunction),
@@ -100,6 +114,7 @@ pub enum MoveTool {
     Show(show::ShowTool),
     Test(TestPackage),
     TransactionalTest(TransactionalTestOpts),
+    UpgradeObject(UpgradeObjectCode),
     VerifyPackage(VerifyPackage),
     View(ViewFunction),
 }
@@ -115,11 +130,14 @@ impl MoveTool {
             MoveTool::CreateResourceAccountAndPublishPackage(tool) => {
                 tool.execute_serialized_success().await
             },
+            MoveTool::DeployObject(tool) => tool.execute_serialized().await,
             MoveTool::Disassemble(tool) => tool.execute_serialized().await,
             MoveTool::Document(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
+            MoveTool::GenerateRustBindings(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
             MoveTool::List(tool) => tool.execute_serialized().await,
+            MoveTool::Mutate(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
             MoveTool::Publish(tool) => tool.execute_serialized().await,
             MoveTool::Run(tool) => tool.execute_serialized().await,
@@ -127,6 +145,7 @@ impl MoveTool {
             MoveTool::Show(tool) => tool.execute_serialized().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
             MoveTool::TransactionalTest(tool) => tool.execute_serialized_success().await,
+            MoveTool::UpgradeObject(tool) => tool.execute_serialized().await,
             MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
             MoveTool::View(tool) => tool.execute_serialized().await,
         }
@@ -446,8 +465,38 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    /// Seed for the inputs generated for tests with parameters not assigned in their test attribute
+    ///
+    /// Tests taking parameters of primitive, address, or vector types which are not assigned in
+    /// `#[test(...)]` are run repeatedly with generated inputs, and failing inputs are shrunk to a
+    /// minimal one. If no seed is given, a random one is used and reported for failing tests.
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// Number of generated inputs each test with unassigned parameters is run with
+    #[clap(long, default_value_t = DEFAULT_FUZZ_ITERATIONS)]
+    pub fuzz_iterations: u64,
+
+    /// Run tests with the production gas schedule and record the gas used by each test
+    ///
+    /// The gas used by each passing test is recorded in `.gas-snapshot` in the package directory.
+    /// The run fails if a test uses more gas than recorded, beyond `--gas-snapshot-tolerance`.
+    #[clap(long)]
+    pub gas_snapshot: bool,
+
+    /// Percentage by which the gas used by a test may exceed its gas snapshot
+    #[clap(long, default_value_t = 0, requires = "gas_snapshot")]
+    pub gas_snapshot_tolerance: u64,
+
+    /// Overwrite the gas snapshot with the gas used in this run, even where it increased
+    #[clap(long, requires = "gas_snapshot")]
+    pub update_gas_snapshot: bool,
 }
 
+/// File in the package directory in which `aptos move test --gas-snapshot` records gas usage
+const GAS_SNAPSHOT_FILE: &str = ".gas-snapshot";
+
 #[async_trait]
 impl CliCommand<&'static str> for TestPackage {
     fn command_name(&self) -> &'static str {
@@ -492,7 +541,26 @@ impl CliCommand<&'static str> for TestPackage {
             }
         }
         let path = self.move_options.get_package_path()?;
-        let result = move_cli::base::test::run_move_unit_tests(
+        // Gas snapshots are taken with the production gas schedule, both for instructions and
+        // natives. Otherwise no gas is charged.
+        let (natives, gas_meter) = if self.gas_snapshot {
+            let gas_params = AptosGasParameters::initial();
+            let natives = aptos_debug_natives::aptos_debug_natives(
+                gas_params.natives.clone(),
+                gas_params.vm.misc.clone(),
+            );
+            let gas_meter: Arc<dyn GasProfilingMeter> =
+                Arc::new(AptosGasProfilingMeter::new(gas_params));
+            (natives, Some(gas_meter))
+        } else {
+            // TODO(Gas): we may want to switch to non-zero costs in the future
+            let natives = aptos_debug_natives::aptos_debug_natives(
+                NativeGasParameters::zeros(),
+                MiscGasParameters::zeros(),
+            );
+            (natives, None)
+        };
+        let result = move_cli::base::test::run_move_unit_tests_with_gas_meter(
             path.as_path(),
             config.clone(),
             UnitTestingConfig {
@@ -500,14 +568,16 @@ impl CliCommand<&'static str> for TestPackage {
                 report_stacktrace_on_abort: true,
                 report_storage_on_error: self.dump_state,
                 ignore_compile_warnings: self.ignore_compile_warnings,
+                seed: self.seed,
+                fuzz_iterations: self.fuzz_iterations,
+                gas_snapshot: self.gas_snapshot.then(|| path.join(GAS_SNAPSHOT_FILE)),
+                gas_snapshot_tolerance: self.gas_snapshot_tolerance,
+                update_gas_snapshot: self.update_gas_snapshot,
                 ..UnitTestingConfig::default_with_bound(None)
             },
-            // TODO(Gas): we may want to switch to non-zero costs in the future
-            aptos_debug_natives::aptos_debug_natives(
-                NativeGasParameters::zeros(),
-                MiscGasParameters::zeros(),
-            ),
+            natives,
             None,
+            gas_meter,
             self.compute_coverage,
             &mut std::io::stdout(),
         )
@@ -693,43 +763,53 @@ impl TryInto<PackagePublicationData> for &PublishPackage {
     type Error = CliError;
 
     fn try_into(self) -> Result<PackagePublicationData, Self::Error> {
-        let package_path = self.move_options.get_package_path()?;
-        let options = self
-            .included_artifacts_args
-            .included_artifacts
-            .build_options(
-                self.move_options.dev,
-                self.move_options.skip_fetch_latest_git_deps,
-                self.move_options.named_addresses(),
-                self.move_options.bytecode_version,
-                self.move_options.compiler_version,
-                self.move_options.skip_attribute_checks,
-            );
-        let package = BuiltPackage::build(package_path, options)
-            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
-        let compiled_units = package.extract_code();
-        let metadata_serialized =
-            bcs::to_bytes(&package.extract_metadata()?).expect("PackageMetadata has BCS");
-        let payload = aptos_cached_packages::aptos_stdlib::code_publish_package_txn(
-            metadata_serialized.clone(),
-            compiled_units.clone(),
-        );
-        let size = bcs::serialized_size(&payload)?;
-        println!("package size {} bytes", size);
-        if !self.override_size_check && size > MAX_PUBLISH_PACKAGE_SIZE {
-            return Err(CliError::UnexpectedError(format!(
-                "The package is larger than {} bytes ({} bytes)! To lower the size \
-                you may want to include fewer artifacts via `--included-artifacts`. \
-                You can also override this check with `--override-size-check",
-                MAX_PUBLISH_PACKAGE_SIZE, size
-            )));
-        }
-        Ok(PackagePublicationData {
-            metadata_serialized,
-            compiled_units,
-            payload,
-        })
+        build_package_publication_data(
+            &self.move_options,
+            &self.included_artifacts_args,
+            self.override_size_check,
+            aptos_cached_packages::aptos_stdlib::code_publish_package_txn,
+        )
+    }
+}
+
+/// Builds the package and the payload publishing it, which is created by `make_payload` from the
+/// serialized metadata and the code of the package.
+fn build_package_publication_data(
+    move_options: &MovePackageDir,
+    included_artifacts_args: &IncludedArtifactsArgs,
+    override_size_check: bool,
+    make_payload: impl FnOnce(Vec<u8>, Vec<Vec<u8>>) -> TransactionPayload,
+) -> CliTypedResult<PackagePublicationData> {
+    let package_path = move_options.get_package_path()?;
+    let options = included_artifacts_args.included_artifacts.build_options(
+        move_options.dev,
+        move_options.skip_fetch_latest_git_deps,
+        move_options.named_addresses(),
+        move_options.bytecode_version,
+        move_options.compiler_version,
+        move_options.skip_attribute_checks,
+    );
+    let package = BuiltPackage::build(package_path, options)
+        .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+    let compiled_units = package.extract_code();
+    let metadata_serialized =
+        bcs::to_bytes(&package.extract_metadata()?).expect("PackageMetadata has BCS");
+    let payload = make_payload(metadata_serialized.clone(), compiled_units.clone());
+    let size = bcs::serialized_size(&payload)?;
+    println!("package size {} bytes", size);
+    if !override_size_check && size > MAX_PUBLISH_PACKAGE_SIZE {
+        return Err(CliError::UnexpectedError(format!(
+            "The package is larger than {} bytes ({} bytes)! To lower the size \
+            you may want to include fewer artifacts via `--included-artifacts`. \
+            You can also override this check with `--override-size-check",
+            MAX_PUBLISH_PACKAGE_SIZE, size
+        )));
     }
+    Ok(PackagePublicationData {
+        metadata_serialized,
+        compiled_units,
+        payload,
+    })
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug)]
@@ -995,6 +1075,146 @@ impl CliCommand<TransactionSummary> for CreateResourceAccountAndPublishPackage {
     }
 }
 
+/// Publishes the modules in a Move package to the Aptos blockchain under a new object
+///
+/// The object is owned by the sender, who is the only one able to upgrade the package.
+#[derive(Parser)]
+pub struct DeployObjectCode {
+    /// The named address for compiling and using in the contract
+    ///
+    /// This will take the derived address of the new object and put it in this location
+    #[clap(long)]
+    pub(crate) address_name: String,
+
+    /// Whether to override the check for maximal size of published data
+    #[clap(long)]
+    pub(crate) override_size_check: bool,
+
+    #[clap(flatten)]
+    pub(crate) included_artifacts_args: IncludedArtifactsArgs,
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for DeployObjectCode {
+    fn command_name(&self) -> &'static str {
+        "DeployObject"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let DeployObjectCode {
+            address_name,
+            override_size_check,
+            included_artifacts_args,
+            mut move_options,
+            txn_options,
+        } = self;
+
+        let sender_address = txn_options.sender_address()?;
+        let sequence_number = txn_options.sequence_number(sender_address).await?;
+        let object_address =
+            create_object_code_deployment_address(sender_address, sequence_number + 1);
+        move_options.add_named_address(address_name, object_address.to_string());
+
+        let package_publication_data = build_package_publication_data(
+            &move_options,
+            &included_artifacts_args,
+            override_size_check,
+            aptos_cached_packages::aptos_stdlib::object_code_deployment_publish,
+        )?;
+
+        let message = format!(
+            "Do you want to publish this package at object address {}?",
+            object_address
+        );
+        prompt_yes_with_override(&message, txn_options.prompt_options)?;
+
+        let summary = txn_options
+            .submit_transaction(package_publication_data.payload)
+            .await
+            .map(TransactionSummary::from)?;
+        println!(
+            "Code was successfully deployed to object address {}",
+            object_address
+        );
+        Ok(summary)
+    }
+}
+
+/// Upgrades the modules in a Move package published under an object
+///
+/// Only the owner of the object can upgrade the package.
+#[derive(Parser)]
+pub struct UpgradeObjectCode {
+    /// The named address for compiling and using in the contract
+    ///
+    /// This will take the address of the object and put it in this location
+    #[clap(long)]
+    pub(crate) address_name: String,
+
+    /// Address of the object the package was deployed to
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) object_address: AccountAddress,
+
+    /// Whether to override the check for maximal size of published data
+    #[clap(long)]
+    pub(crate) override_size_check: bool,
+
+    #[clap(flatten)]
+    pub(crate) included_artifacts_args: IncludedArtifactsArgs,
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for UpgradeObjectCode {
+    fn command_name(&self) -> &'static str {
+        "UpgradeObject"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let UpgradeObjectCode {
+            address_name,
+            object_address,
+            override_size_check,
+            included_artifacts_args,
+            mut move_options,
+            txn_options,
+        } = self;
+
+        move_options.add_named_address(address_name, object_address.to_string());
+
+        let package_publication_data = build_package_publication_data(
+            &move_options,
+            &included_artifacts_args,
+            override_size_check,
+            |metadata_serialized, code| {
+                aptos_cached_packages::aptos_stdlib::object_code_deployment_upgrade(
+                    metadata_serialized,
+                    code,
+                    object_address,
+                )
+            },
+        )?;
+
+        let message = format!(
+            "Do you want to upgrade the package at object address {}?",
+            object_address
+        );
+        prompt_yes_with_override(&message, txn_options.prompt_options)?;
+
+        txn_options
+            .submit_transaction(package_publication_data.payload)
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
 /// Downloads a package and stores it in a directory named after the package
 ///
 /// This lets you retrieve packages directly from the blockchain for inspection
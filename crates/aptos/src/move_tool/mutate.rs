@@ -0,0 +1,158 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::types::{CliCommand, CliError, CliTypedResult, MovePackageDir},
+    move_tool::aptos_debug_natives,
+};
+use aptos_framework::extended_checks;
+use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
+use async_trait::async_trait;
+use clap::Parser;
+use move_package::{BuildConfig, CompilerConfig};
+use move_unit_test::{mutation::DEFAULT_MUTANT_EXECUTION_BOUND, UnitTestingConfig};
+use serde::Serialize;
+
+/// Runs Move unit tests against mutants of a package
+///
+/// Each mutant changes a single operator, constant, or branch condition in a function of the
+/// package (test-only code is never mutated). A mutant survives if all tests still pass against
+/// it, which indicates behavior that is not checked by any test. Surviving mutants are reported
+/// per function, with the source location of the mutation.
+#[derive(Parser)]
+pub struct MutatePackage {
+    /// A filter string to determine which unit tests to run against each mutant
+    #[clap(long, short)]
+    pub filter: Option<String>,
+
+    /// The maximum number of instructions that can be executed by a test against a mutant
+    ///
+    /// Mutants frequently introduce infinite loops, which are reported as killed once they
+    /// reach this bound.
+    #[clap(long = "instructions", default_value_t = DEFAULT_MUTANT_EXECUTION_BOUND)]
+    pub instruction_execution_bound: u64,
+
+    /// Number of mutants to test in parallel
+    #[clap(long, default_value_t = 8)]
+    pub num_threads: usize,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MutationTestSummary {
+    pub total_mutants: usize,
+    pub killed: usize,
+    pub survived: usize,
+    pub surviving_mutants: Vec<FunctionSurvivors>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionSurvivors {
+    /// Fully qualified function name, e.g. `0x1::coin::transfer`
+    pub function: String,
+    pub mutants: Vec<SurvivingMutant>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SurvivingMutant {
+    pub kind: String,
+    pub mutation: String,
+    pub code_offset: u16,
+    pub location: Option<String>,
+}
+
+#[async_trait]
+impl CliCommand<MutationTestSummary> for MutatePackage {
+    fn command_name(&self) -> &'static str {
+        "MutatePackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<MutationTestSummary> {
+        let config = BuildConfig {
+            dev_mode: self.move_options.dev,
+            additional_named_addresses: self.move_options.named_addresses(),
+            test_mode: true,
+            install_dir: self.move_options.output_dir.clone(),
+            skip_fetch_latest_git_deps: self.move_options.skip_fetch_latest_git_deps,
+            compiler_config: CompilerConfig {
+                known_attributes: extended_checks::get_all_attribute_names().clone(),
+                skip_attribute_checks: self.move_options.skip_attribute_checks,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let path = self.move_options.get_package_path()?;
+        let report = move_cli::base::test::run_move_mutation_tests(
+            path.as_path(),
+            config,
+            UnitTestingConfig {
+                filter: self.filter,
+                gas_limit: Some(self.instruction_execution_bound),
+                num_threads: self.num_threads,
+                ..UnitTestingConfig::default_with_bound(None)
+            },
+            aptos_debug_natives::aptos_debug_natives(
+                NativeGasParameters::zeros(),
+                MiscGasParameters::zeros(),
+            ),
+            None,
+            &mut std::io::stderr(),
+        )
+        .map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to run mutation tests: {:#}", err))
+        })?;
+
+        let surviving_mutants = report
+            .survivors_by_function()
+            .into_iter()
+            .map(|((module_id, function_name), results)| {
+                let function = format!(
+                    "0x{}::{}::{}",
+                    module_id.address().short_str_lossless(),
+                    module_id.name(),
+                    function_name
+                );
+                for result in &results {
+                    eprintln!(
+                        "SURVIVED {} {} mutation {:?} -> {:?} at {}",
+                        function,
+                        result.mutant.kind,
+                        result.mutant.original,
+                        result.mutant.replacement,
+                        result.location.as_deref().unwrap_or("unknown location"),
+                    );
+                }
+                FunctionSurvivors {
+                    function,
+                    mutants: results
+                        .into_iter()
+                        .map(|result| SurvivingMutant {
+                            kind: result.mutant.kind.to_string(),
+                            mutation: format!(
+                                "{:?} -> {:?}",
+                                result.mutant.original, result.mutant.replacement
+                            ),
+                            code_offset: result.mutant.code_offset,
+                            location: result.location.clone(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let total_mutants = report.results.len();
+        let killed = report.num_killed();
+        eprintln!(
+            "Mutation testing result: {} of {} mutants killed",
+            killed, total_mutants
+        );
+        Ok(MutationTestSummary {
+            total_mutants,
+            killed,
+            survived: total_mutants - killed,
+            surviving_mutants,
+        })
+    }
+}
@@ -853,6 +853,11 @@ impl CliTestFramework {
             ignore_compile_warnings: false,
             compute_coverage: false,
             dump_state: false,
+            seed: None,
+            fuzz_iterations: move_unit_test::fuzz::DEFAULT_FUZZ_ITERATIONS,
+            gas_snapshot: false,
+            gas_snapshot_tolerance: 0,
+            update_gas_snapshot: false,
         }
         .execute()
         .await
@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    move_tool::{ArgWithType, FunctionArgType},
+    common::types::CliCommand,
+    move_tool::{mutate::MutatePackage, ArgWithType, FunctionArgType, TestPackage},
     CliResult, Tool,
 };
 use clap::Parser;
-use std::str::FromStr;
+use move_unit_test::gas_snapshot::GasSnapshot;
+use std::{fs, path::Path, str::FromStr};
 
 /// In order to ensure that there aren't duplicate input arguments for untested CLI commands,
 /// we call help on every command to ensure it at least runs
@@ -69,6 +71,7 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "move", "download", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "init", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "list", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "move", "mutate", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "prove", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "publish", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "run", "--help"]).await;
@@ -119,6 +122,105 @@ async fn ensure_can_parse_args_with_urls() {
     );
 }
 
+/// Mutation testing reports mutants killed by the tests of a package, and refuses to run if the
+/// tests already fail against the unmutated package
+#[tokio::test]
+async fn test_move_mutate() {
+    let package_dir = tempfile::tempdir().unwrap();
+    write_math_package(package_dir.path(), 3);
+    let summary = mutate_package(package_dir.path()).execute().await.unwrap();
+    assert!(summary.total_mutants > 0);
+    assert_eq!(summary.killed, summary.total_mutants);
+    assert!(summary.surviving_mutants.is_empty());
+
+    // A failing test would kill every mutant
+    write_math_package(package_dir.path(), 4);
+    let err = mutate_package(package_dir.path())
+        .execute()
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unmutated package"), "{}", err);
+}
+
+/// Gas snapshots record the gas used by each test, and the test run fails if a test uses more gas
+/// than recorded
+#[tokio::test]
+async fn test_move_test_gas_snapshot() {
+    let package_dir = tempfile::tempdir().unwrap();
+    write_math_package(package_dir.path(), 3);
+    let snapshot_path = package_dir.path().join(".gas-snapshot");
+    test_package_with_gas_snapshot(package_dir.path(), &[])
+        .execute()
+        .await
+        .unwrap();
+    let snapshot = GasSnapshot::read(&snapshot_path).unwrap();
+    assert_eq!(snapshot.gas_used.len(), 1);
+    let (test_name, gas_used) = snapshot.gas_used.iter().next().unwrap();
+    assert!(test_name.ends_with("math::test_add"), "{}", test_name);
+    assert!(*gas_used > 0);
+
+    // A test using more gas than recorded fails the run, and the snapshot is kept
+    let lower_snapshot = GasSnapshot::new([(test_name.clone(), 1)].into_iter().collect());
+    lower_snapshot.write(&snapshot_path).unwrap();
+    assert!(test_package_with_gas_snapshot(package_dir.path(), &[])
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(GasSnapshot::read(&snapshot_path).unwrap(), lower_snapshot);
+
+    // Unless the increase is accepted
+    test_package_with_gas_snapshot(package_dir.path(), &["--update-gas-snapshot"])
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(GasSnapshot::read(&snapshot_path).unwrap(), snapshot);
+}
+
+fn test_package_with_gas_snapshot(package_dir: &Path, args: &[&str]) -> TestPackage {
+    TestPackage::parse_from(
+        [
+            "test",
+            "--package-dir",
+            package_dir.to_str().unwrap(),
+            "--gas-snapshot",
+        ]
+        .iter()
+        .chain(args),
+    )
+}
+
+fn mutate_package(package_dir: &Path) -> MutatePackage {
+    MutatePackage::parse_from([
+        "mutate",
+        "--package-dir",
+        package_dir.to_str().unwrap(),
+        "--num-threads",
+        "1",
+    ])
+}
+
+fn write_math_package(package_dir: &Path, expected_sum: u64) {
+    fs::create_dir_all(package_dir.join("sources")).unwrap();
+    fs::write(
+        package_dir.join("Move.toml"),
+        "[package]\nname = \"Math\"\nversion = \"0.0.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        package_dir.join("sources").join("math.move"),
+        format!(
+            "module 0xcafe::math {{\n\
+             \x20   public fun add(a: u64, b: u64): u64 {{ a + b }}\n\
+             \x20   #[test]\n\
+             \x20   fun test_add() {{ assert!(add(1, 2) == {}, 0); }}\n\
+             }}\n",
+            expected_sum
+        ),
+    )
+    .unwrap();
+}
+
 async fn assert_cmd_not_panic(args: &[&str]) {
     // When a command fails, it will have a panic in it due to an improperly setup command
     // thread 'main' panicked at 'Command propose: Argument names must be unique, but 'assume-yes' is
@@ -216,6 +216,31 @@ impl Transaction {
                 vec![],
                 vec![],
             ),
+            APITransaction::ValidatorTransaction(validator_txn) => {
+                let (wsc, wsc_detail) = WriteSetChangeModel::from_write_set_changes(
+                    &validator_txn.info.changes,
+                    validator_txn.info.version.0 as i64,
+                    block_height,
+                );
+                (
+                    Self::from_transaction_info(
+                        &validator_txn.info,
+                        None,
+                        transaction.type_str().to_string(),
+                        0,
+                        block_height,
+                        epoch,
+                    ),
+                    None,
+                    EventModel::from_events(
+                        &validator_txn.events,
+                        validator_txn.info.version.0 as i64,
+                        block_height,
+                    ),
+                    wsc,
+                    wsc_detail,
+                )
+            },
             APITransaction::PendingTransaction(..) => {
                 unreachable!()
             },
@@ -0,0 +1,134 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The validator-local pool of validator transactions (see `aptos_types::validator_txn`).
+//!
+//! Components that produce validator transactions (e.g. oracles) put them into the pool, and the
+//! proposer of a block pulls them when generating the proposal. The pool holds at most one
+//! transaction per topic: a newer transaction of a topic replaces the older one. A transaction
+//! stays in the pool until the [`TxnGuard`] returned by [`VTxnPoolState::put`] is dropped, which
+//! producers do once the transaction is committed or no longer relevant.
+
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_infallible::Mutex;
+use aptos_types::validator_txn::{Topic, ValidatorTransaction};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Which transactions a pull should skip.
+pub enum TransactionFilter {
+    PendingTxnHashSet(HashSet<HashValue>),
+}
+
+impl TransactionFilter {
+    pub fn no_op() -> Self {
+        Self::PendingTxnHashSet(HashSet::new())
+    }
+
+    /// Filters out the transactions that are already in pending (proposed but uncommitted) blocks.
+    pub fn pending_txns<'a>(txns: impl Iterator<Item = &'a ValidatorTransaction>) -> Self {
+        Self::PendingTxnHashSet(txns.map(CryptoHash::hash).collect())
+    }
+
+    pub fn should_exclude(&self, txn: &ValidatorTransaction) -> bool {
+        match self {
+            TransactionFilter::PendingTxnHashSet(set) => set.contains(&txn.hash()),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct VTxnPoolState {
+    inner: Arc<Mutex<PoolStateInner>>,
+}
+
+impl VTxnPoolState {
+    /// Puts a transaction into the pool, replacing the transaction of the same topic if any. The
+    /// transaction is removed from the pool when the returned guard is dropped.
+    pub fn put(&self, txn: Arc<ValidatorTransaction>) -> TxnGuard {
+        let seq_num = self.inner.lock().put(txn);
+        TxnGuard {
+            pool: self.inner.clone(),
+            seq_num,
+        }
+    }
+
+    /// Returns the transactions in the order they were put, skipping the ones excluded by the
+    /// filter, until either `max_items` transactions or `max_bytes` bytes are reached.
+    pub fn pull(
+        &self,
+        max_items: u64,
+        max_bytes: u64,
+        filter: TransactionFilter,
+    ) -> Vec<ValidatorTransaction> {
+        self.inner.lock().pull(max_items, max_bytes, filter)
+    }
+}
+
+#[derive(Default)]
+struct PoolStateInner {
+    next_seq_num: u64,
+    /// The transactions in the pool, by the sequence number they were put with.
+    txn_queue: BTreeMap<u64, (Topic, Arc<ValidatorTransaction>)>,
+    /// The sequence number of the latest transaction of each topic.
+    seq_nums_by_topic: HashMap<Topic, u64>,
+}
+
+impl PoolStateInner {
+    fn put(&mut self, txn: Arc<ValidatorTransaction>) -> u64 {
+        let seq_num = self.next_seq_num;
+        self.next_seq_num += 1;
+
+        let topic = txn.topic();
+        if let Some(old_seq_num) = self.seq_nums_by_topic.insert(topic, seq_num) {
+            self.txn_queue.remove(&old_seq_num);
+        }
+        self.txn_queue.insert(seq_num, (topic, txn));
+        seq_num
+    }
+
+    fn try_delete(&mut self, seq_num: u64) {
+        if let Some((topic, _)) = self.txn_queue.remove(&seq_num) {
+            self.seq_nums_by_topic.remove(&topic);
+        }
+    }
+
+    fn pull(
+        &self,
+        mut max_items: u64,
+        mut max_bytes: u64,
+        filter: TransactionFilter,
+    ) -> Vec<ValidatorTransaction> {
+        let mut ret = vec![];
+        for (_, txn) in self.txn_queue.values() {
+            if max_items == 0 {
+                break;
+            }
+            let size = txn.size_in_bytes() as u64;
+            if size > max_bytes || filter.should_exclude(txn) {
+                continue;
+            }
+            ret.push(txn.as_ref().clone());
+            max_items -= 1;
+            max_bytes -= size;
+        }
+        ret
+    }
+}
+
+/// Keeps a transaction in the pool for as long as it is alive.
+pub struct TxnGuard {
+    pool: Arc<Mutex<PoolStateInner>>,
+    seq_num: u64,
+}
+
+impl Drop for TxnGuard {
+    fn drop(&mut self) {
+        self.pool.lock().try_delete(self.seq_num);
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{TransactionFilter, VTxnPoolState};
+use aptos_types::validator_txn::ValidatorTransaction;
+use std::sync::Arc;
+
+#[test]
+fn txn_stays_in_pool_until_guard_dropped() {
+    let pool = VTxnPoolState::default();
+    let txn = ValidatorTransaction::dummy(vec![0xFF; 10]);
+    let guard = pool.put(Arc::new(txn.clone()));
+    assert_eq!(pool.pull(10, 1024, TransactionFilter::no_op()), vec![txn]);
+    drop(guard);
+    assert!(pool.pull(10, 1024, TransactionFilter::no_op()).is_empty());
+}
+
+#[test]
+fn newer_txn_replaces_older_txn_of_same_topic() {
+    let pool = VTxnPoolState::default();
+    let old_txn = ValidatorTransaction::dummy(vec![0x01]);
+    let new_txn = ValidatorTransaction::dummy(vec![0x02]);
+    let old_guard = pool.put(Arc::new(old_txn));
+    let _new_guard = pool.put(Arc::new(new_txn.clone()));
+    assert_eq!(pool.pull(10, 1024, TransactionFilter::no_op()), vec![
+        new_txn.clone()
+    ]);
+
+    // Dropping the guard of the replaced txn does not affect the newer one.
+    drop(old_guard);
+    assert_eq!(pool.pull(10, 1024, TransactionFilter::no_op()), vec![
+        new_txn
+    ]);
+}
+
+#[test]
+fn pull_respects_limits_and_filter() {
+    let pool = VTxnPoolState::default();
+    let txn = ValidatorTransaction::dummy(vec![0xFF; 100]);
+    let _guard = pool.put(Arc::new(txn.clone()));
+
+    assert!(pool.pull(0, 1024, TransactionFilter::no_op()).is_empty());
+    assert!(pool
+        .pull(
+            10,
+            txn.size_in_bytes() as u64 - 1,
+            TransactionFilter::no_op()
+        )
+        .is_empty());
+    assert!(pool
+        .pull(
+            10,
+            1024,
+            TransactionFilter::pending_txns(std::iter::once(&txn))
+        )
+        .is_empty());
+    assert_eq!(
+        pool.pull(1, txn.size_in_bytes() as u64, TransactionFilter::no_op()),
+        vec![txn]
+    );
+}
@@ -660,6 +660,11 @@ pub fn convert_transaction(
         Transaction::StateCheckpointTransaction(_) => {
             transaction::transaction::TransactionType::StateCheckpoint
         },
+        // Validator transactions have no protobuf representation yet, but are still streamed so
+        // that the versions seen by the indexers stay contiguous.
+        Transaction::ValidatorTransaction(_) => {
+            transaction::transaction::TransactionType::Unspecified
+        },
         Transaction::PendingTransaction(_) => panic!("PendingTransaction is not supported"),
     };
 
@@ -670,31 +675,35 @@ pub fn convert_transaction(
                 ut.request.expiration_timestamp_secs.0,
                 chrono::NaiveDateTime::MAX.timestamp() as u64,
             )));
-            transaction::transaction::TxnData::User(transaction::UserTransaction {
-                request: Some(transaction::UserTransactionRequest {
-                    sender: ut.request.sender.to_string(),
-                    sequence_number: ut.request.sequence_number.0,
-                    max_gas_amount: ut.request.max_gas_amount.0,
-                    gas_unit_price: ut.request.gas_unit_price.0,
-                    expiration_timestamp_secs,
-                    payload: Some(convert_transaction_payload(&ut.request.payload)),
-                    signature: convert_transaction_signature(&ut.request.signature),
-                }),
-                events: convert_events(&ut.events),
-            })
+            Some(transaction::transaction::TxnData::User(
+                transaction::UserTransaction {
+                    request: Some(transaction::UserTransactionRequest {
+                        sender: ut.request.sender.to_string(),
+                        sequence_number: ut.request.sequence_number.0,
+                        max_gas_amount: ut.request.max_gas_amount.0,
+                        gas_unit_price: ut.request.gas_unit_price.0,
+                        expiration_timestamp_secs,
+                        payload: Some(convert_transaction_payload(&ut.request.payload)),
+                        signature: convert_transaction_signature(&ut.request.signature),
+                    }),
+                    events: convert_events(&ut.events),
+                },
+            ))
         },
         Transaction::GenesisTransaction(gt) => {
             let payload = match &gt.payload {
                 GenesisPayload::WriteSetPayload(wsp) => convert_write_set(&wsp.write_set),
             };
-            transaction::transaction::TxnData::Genesis(transaction::GenesisTransaction {
-                payload: Some(payload),
-                events: convert_events(&gt.events),
-            })
+            Some(transaction::transaction::TxnData::Genesis(
+                transaction::GenesisTransaction {
+                    payload: Some(payload),
+                    events: convert_events(&gt.events),
+                },
+            ))
         },
         Transaction::BlockMetadataTransaction(bm) => {
             timestamp = Some(convert_timestamp_usecs(bm.timestamp.0));
-            transaction::transaction::TxnData::BlockMetadata(
+            Some(transaction::transaction::TxnData::BlockMetadata(
                 transaction::BlockMetadataTransaction {
                     id: bm.id.to_string(),
                     events: convert_events(&bm.events),
@@ -703,13 +712,14 @@ pub fn convert_transaction(
                     failed_proposer_indices: bm.failed_proposer_indices.clone(),
                     round: bm.round.0,
                 },
-            )
+            ))
         },
         Transaction::StateCheckpointTransaction(_st) => {
-            transaction::transaction::TxnData::StateCheckpoint(
+            Some(transaction::transaction::TxnData::StateCheckpoint(
                 transaction::StateCheckpointTransaction {},
-            )
+            ))
         },
+        Transaction::ValidatorTransaction(_) => None,
         Transaction::PendingTransaction(_) => panic!("PendingTransaction not supported"),
     };
 
@@ -734,6 +744,6 @@ pub fn convert_transaction(
         epoch,
         block_height,
         r#type: txn_type as i32,
-        txn_data: Some(txn_data),
+        txn_data,
     }
 }
@@ -3,6 +3,7 @@
 use aptos_state_view::in_memory_state_view::InMemoryStateView;
 use aptos_types::{
     block_executor::partitioner::SubBlocksForShard,
+    on_chain_config::BlockGasLimitType,
     transaction::{analyzed_transaction::AnalyzedTransaction, TransactionOutput},
     vm_status::VMStatus,
 };
@@ -47,7 +48,7 @@ pub struct ExecuteBlockCommand {
     // directly from the storage.
     pub(crate) state_view: InMemoryStateView,
     pub(crate) concurrency_level: usize,
-    pub(crate) maybe_block_gas_limit: Option<u64>,
+    pub(crate) block_gas_limit_type: BlockGasLimitType,
 }
 
 impl ExecuteBlockCommand {
@@ -57,13 +58,13 @@ impl ExecuteBlockCommand {
         SubBlocksForShard<AnalyzedTransaction>,
         InMemoryStateView,
         usize,
-        Option<u64>,
+        BlockGasLimitType,
     ) {
         (
             self.sub_blocks,
             self.state_view,
             self.concurrency_level,
-            self.maybe_block_gas_limit,
+            self.block_gas_limit_type,
         )
     }
 }
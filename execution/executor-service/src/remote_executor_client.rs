@@ -6,8 +6,8 @@ use aptos_logger::trace;
 use aptos_secure_net::network_controller::{Message, NetworkController};
 use aptos_state_view::StateView;
 use aptos_types::{
-    block_executor::partitioner::PartitionedTransactions, transaction::TransactionOutput,
-    vm_status::VMStatus,
+    block_executor::partitioner::PartitionedTransactions, on_chain_config::BlockGasLimitType,
+    transaction::TransactionOutput, vm_status::VMStatus,
 };
 use aptos_vm::sharded_block_executor::executor_client::{ExecutorClient, ShardedExecutionOutput};
 use crossbeam_channel::{Receiver, Sender};
@@ -85,7 +85,7 @@ impl<S: StateView + Sync + Send + 'static> ExecutorClient<S> for RemoteExecutorC
         state_view: Arc<S>,
         transactions: PartitionedTransactions,
         concurrency_level_per_shard: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<ShardedExecutionOutput, VMStatus> {
         self.thread_pool.scope(|s| {
             let (block, global_txns) = transactions.into();
@@ -96,6 +96,7 @@ impl<S: StateView + Sync + Send + 'static> ExecutorClient<S> for RemoteExecutorC
             for (shard_id, sub_blocks) in block.into_iter().enumerate() {
                 let state_view = state_view.clone();
                 let senders = self.command_txs.clone();
+                let block_gas_limit_type = block_gas_limit_type.clone();
                 s.spawn(move |_| {
                     let execution_request =
                         RemoteExecutionRequest::ExecuteBlock(ExecuteBlockCommand {
@@ -104,7 +105,7 @@ impl<S: StateView + Sync + Send + 'static> ExecutorClient<S> for RemoteExecutorC
                             // serialize only the state values needed for the shard.
                             state_view: S::as_in_memory_state_view(state_view.deref()),
                             concurrency_level: concurrency_level_per_shard,
-                            maybe_block_gas_limit,
+                            block_gas_limit_type,
                         });
 
                     senders[shard_id]
@@ -7,6 +7,7 @@ use aptos_language_e2e_tests::{
 };
 use aptos_types::{
     block_executor::partitioner::PartitionedTransactions,
+    on_chain_config::BlockGasLimitType,
     state_store::state_key::StateKeyInner,
     transaction::{analyzed_transaction::AnalyzedTransaction, Transaction, TransactionOutput},
 };
@@ -113,7 +114,7 @@ pub fn test_sharded_block_executor_no_conflict<E: ExecutorClient<FakeDataStore>>
             Arc::new(executor.data_store().clone()),
             partitioned_txns.clone(),
             2,
-            None,
+            BlockGasLimitType::NoLimit,
         )
         .unwrap();
     let unsharded_txn_output = AptosVM::execute_block(
@@ -122,7 +123,7 @@ pub fn test_sharded_block_executor_no_conflict<E: ExecutorClient<FakeDataStore>>
             .map(|t| t.into_txn())
             .collect(),
         executor.data_store(),
-        None,
+        BlockGasLimitType::NoLimit,
     )
     .unwrap();
     compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
@@ -174,7 +174,7 @@ pub fn test_execution_with_storage_impl_inner(
         .execute_block(
             (block1_id, block1.clone()).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let li1 = gen_ledger_info_with_sigs(1, &output1, block1_id, &[signer.clone()]);
@@ -386,7 +386,7 @@ pub fn test_execution_with_storage_impl_inner(
         .execute_block(
             (block2_id, block2).into(),
             epoch2_genesis_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let li2 = gen_ledger_info_with_sigs(2, &output2, block2_id, &[signer.clone()]);
@@ -405,7 +405,7 @@ pub fn test_execution_with_storage_impl_inner(
         .execute_block(
             (block3_id, block3.clone()).into(),
             epoch3_genesis_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let li3 = gen_ledger_info_with_sigs(3, &output3, block3_id, &[signer]);
@@ -157,9 +157,10 @@ impl InMemoryStateCalculator {
             Ok((updated_state_kvs, Some(self.make_checkpoint()?)))
         } else {
             match txn {
-                Transaction::BlockMetadata(_) | Transaction::UserTransaction(_) => {
-                    Ok((updated_state_kvs, None))
-                },
+                Transaction::BlockMetadata(_)
+                | Transaction::UserTransaction(_)
+                | Transaction::ValidatorTransaction(_)
+                | Transaction::BlockMetadataExt(_) => Ok((updated_state_kvs, None)),
                 Transaction::GenesisTransaction(_) | Transaction::StateCheckpoint(_) => {
                     Ok((updated_state_kvs, Some(self.make_checkpoint()?)))
                 },
@@ -332,7 +333,10 @@ fn ensure_txn_valid_for_vacant_entry(transaction: &Transaction) -> Result<()> {
     // maybe other writeset transactions).
     match transaction {
         Transaction::GenesisTransaction(_) => (),
-        Transaction::BlockMetadata(_) | Transaction::UserTransaction(_) => {
+        Transaction::BlockMetadata(_)
+        | Transaction::UserTransaction(_)
+        | Transaction::ValidatorTransaction(_)
+        | Transaction::BlockMetadataExt(_) => {
             bail!("Write set should be a subset of read set.")
         },
         Transaction::StateCheckpoint(_) => {},
@@ -31,6 +31,7 @@ use aptos_storage_interface::{
 use aptos_types::{
     contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockGasLimitType,
     transaction::{
         Transaction, TransactionInfo, TransactionListWithProof, TransactionOutput,
         TransactionOutputListWithProof, TransactionStatus, Version,
@@ -204,7 +205,11 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         let chunk_output = {
             let _timer = APTOS_EXECUTOR_VM_EXECUTE_CHUNK_SECONDS.start_timer();
             // State sync executor shouldn't have block gas limit.
-            ChunkOutput::by_transaction_execution::<V>(transactions.into(), state_view, None)?
+            ChunkOutput::by_transaction_execution::<V>(
+                transactions.into(),
+                state_view,
+                BlockGasLimitType::NoLimit,
+            )?
         };
         let executed_chunk = Self::apply_chunk_output_for_state_sync(
             verified_target_li,
@@ -535,8 +540,11 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
             .collect::<Vec<Transaction>>();
 
         // State sync executor shouldn't have block gas limit.
-        let chunk_output =
-            ChunkOutput::by_transaction_execution::<V>(txns.into(), state_view, None)?;
+        let chunk_output = ChunkOutput::by_transaction_execution::<V>(
+            txns.into(),
+            state_view,
+            BlockGasLimitType::NoLimit,
+        )?;
         // not `zip_eq`, deliberately
         for (version, txn_out, txn_info, write_set, events) in multizip((
             begin_version..end_version,
@@ -20,6 +20,7 @@ use aptos_types::{
     block_executor::partitioner::{ExecutableTransactions, PartitionedTransactions},
     contract_event::ContractEvent,
     epoch_state::EpochState,
+    on_chain_config::BlockGasLimitType,
     transaction::{ExecutionStatus, Transaction, TransactionOutput, TransactionStatus},
 };
 use aptos_vm::{
@@ -56,18 +57,18 @@ impl ChunkOutput {
     pub fn by_transaction_execution<V: VMExecutor>(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Self> {
         match transactions {
             ExecutableTransactions::Unsharded(txns) => {
                 Self::by_transaction_execution_unsharded::<V>(
                     txns,
                     state_view,
-                    maybe_block_gas_limit,
+                    block_gas_limit_type,
                 )
             },
             ExecutableTransactions::Sharded(txns) => {
-                Self::by_transaction_execution_sharded::<V>(txns, state_view, maybe_block_gas_limit)
+                Self::by_transaction_execution_sharded::<V>(txns, state_view, block_gas_limit_type)
             },
         }
     }
@@ -75,10 +76,10 @@ impl ChunkOutput {
     fn by_transaction_execution_unsharded<V: VMExecutor>(
         transactions: Vec<Transaction>,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Self> {
         let transaction_outputs =
-            Self::execute_block::<V>(transactions.clone(), &state_view, maybe_block_gas_limit)?;
+            Self::execute_block::<V>(transactions.clone(), &state_view, block_gas_limit_type)?;
 
         // to print txn output for debugging, uncomment:
         // println!("{:?}", transaction_outputs.iter().map(|t| t.status() ).collect::<Vec<_>>());
@@ -95,13 +96,13 @@ impl ChunkOutput {
     pub fn by_transaction_execution_sharded<V: VMExecutor>(
         transactions: PartitionedTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Self> {
         let state_view_arc = Arc::new(state_view);
         let transaction_outputs = Self::execute_block_sharded::<V>(
             transactions.clone(),
             state_view_arc.clone(),
-            maybe_block_gas_limit,
+            block_gas_limit_type,
         )?;
 
         // TODO(skedia) add logic to emit counters per shard instead of doing it globally.
@@ -176,13 +177,13 @@ impl ChunkOutput {
     fn execute_block_sharded<V: VMExecutor>(
         partitioned_txns: PartitionedTransactions,
         state_view: Arc<CachedStateView>,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>> {
         Ok(V::execute_block_sharded(
             SHARDED_BLOCK_EXECUTOR.lock().deref(),
             partitioned_txns,
             state_view,
-            maybe_block_gas_limit,
+            block_gas_limit_type,
         )?)
     }
 
@@ -192,12 +193,12 @@ impl ChunkOutput {
     fn execute_block<V: VMExecutor>(
         transactions: Vec<Transaction>,
         state_view: &CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>> {
         Ok(V::execute_block(
             transactions,
             state_view,
-            maybe_block_gas_limit,
+            block_gas_limit_type,
         )?)
     }
 
@@ -209,7 +210,7 @@ impl ChunkOutput {
     fn execute_block<V: VMExecutor>(
         transactions: Vec<Transaction>,
         state_view: &CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>> {
         use aptos_state_view::{StateViewId, TStateView};
         use aptos_types::write_set::WriteSet;
@@ -217,7 +218,7 @@ impl ChunkOutput {
         let transaction_outputs = match state_view.id() {
             // this state view ID implies a genesis block in non-test cases.
             StateViewId::Miscellaneous => {
-                V::execute_block(transactions, state_view, maybe_block_gas_limit)?
+                V::execute_block(transactions, state_view, block_gas_limit_type)?
             },
             _ => transactions
                 .iter()
@@ -311,6 +312,8 @@ pub fn update_counters_for_processed_chunk(
             Transaction::GenesisTransaction(_) => "genesis",
             Transaction::BlockMetadata(_) => "block_metadata",
             Transaction::StateCheckpoint(_) => "state_checkpoint",
+            Transaction::ValidatorTransaction(_) => "validator_transaction",
+            Transaction::BlockMetadataExt(_) => "block_metadata_ext",
         };
 
         metrics::APTOS_PROCESSED_TXNS_COUNT
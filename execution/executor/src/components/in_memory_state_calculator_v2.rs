@@ -168,7 +168,10 @@ impl InMemoryStateCalculatorV2 {
             return true;
         }
         match txn {
-            Transaction::BlockMetadata(_) | Transaction::UserTransaction(_) => false,
+            Transaction::BlockMetadata(_)
+            | Transaction::UserTransaction(_)
+            | Transaction::ValidatorTransaction(_)
+            | Transaction::BlockMetadataExt(_) => false,
             Transaction::GenesisTransaction(_) | Transaction::StateCheckpoint(_) => true,
         }
     }
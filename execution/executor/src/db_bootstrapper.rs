@@ -20,7 +20,7 @@ use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_info::{BlockInfo, GENESIS_EPOCH, GENESIS_ROUND, GENESIS_TIMESTAMP_USECS},
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    on_chain_config::ConfigurationResource,
+    on_chain_config::{BlockGasLimitType, ConfigurationResource},
     state_store::state_key::StateKey,
     timestamp::TimestampResource,
     transaction::{Transaction, Version},
@@ -139,7 +139,7 @@ pub fn calculate_genesis<V: VMExecutor>(
     let (mut output, _, _) = ChunkOutput::by_transaction_execution::<V>(
         vec![genesis_txn.clone()].into(),
         base_state_view,
-        None,
+        BlockGasLimitType::NoLimit,
     )?
     .apply_to_ledger(&executed_trees, None)?;
     ensure!(
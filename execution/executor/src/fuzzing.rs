@@ -16,6 +16,7 @@ use aptos_storage_interface::{
 use aptos_types::{
     block_executor::partitioner::{ExecutableTransactions, PartitionedTransactions},
     ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockGasLimitType,
     test_helpers::transaction_test_helpers::BLOCK_GAS_LIMIT,
     transaction::{Transaction, TransactionOutput, TransactionToCommit, Version},
     vm_status::VMStatus,
@@ -44,7 +45,7 @@ pub fn fuzz_execute_and_commit_blocks(
     for block in blocks {
         let block_id = block.0;
         let _execution_results =
-            executor.execute_block(block.into(), parent_block_id, BLOCK_GAS_LIMIT);
+            executor.execute_block(block.into(), parent_block_id, BLOCK_GAS_LIMIT.into());
         parent_block_id = block_id;
         block_ids.push(block_id);
     }
@@ -58,12 +59,12 @@ impl TransactionBlockExecutor for FakeVM {
     fn execute_transaction_block(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> Result<ChunkOutput> {
         ChunkOutput::by_transaction_execution::<FakeVM>(
             transactions,
             state_view,
-            maybe_block_gas_limit,
+            block_gas_limit_type,
         )
     }
 }
@@ -73,7 +74,7 @@ impl VMExecutor for FakeVM {
         _sharded_block_executor: &ShardedBlockExecutor<S, E>,
         _transactions: PartitionedTransactions,
         _state_view: Arc<S>,
-        _maybe_block_gas_limit: Option<u64>,
+        _block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         Ok(Vec::new())
     }
@@ -81,7 +82,7 @@ impl VMExecutor for FakeVM {
     fn execute_block(
         _transactions: Vec<Transaction>,
         _state_view: &impl StateView,
-        _maybe_block_gas_limit: Option<u64>,
+        _block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         Ok(Vec::new())
     }
@@ -7,6 +7,7 @@ use anyhow::Result;
 use aptos_state_view::TStateView;
 use aptos_types::{
     account_address::AccountAddress,
+    on_chain_config::BlockGasLimitType,
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
@@ -41,7 +42,7 @@ fn test_mock_vm_different_senders() {
         txns.push(encode_mint_transaction(gen_address(i), amount));
     }
 
-    let outputs = MockVM::execute_block(txns.clone(), &MockStateView, None)
+    let outputs = MockVM::execute_block(txns.clone(), &MockStateView, BlockGasLimitType::NoLimit)
         .expect("MockVM should not fail to start");
 
     for (output, txn) in itertools::zip_eq(outputs.iter(), txns.iter()) {
@@ -77,8 +78,8 @@ fn test_mock_vm_same_sender() {
         txns.push(encode_mint_transaction(sender, amount));
     }
 
-    let outputs =
-        MockVM::execute_block(txns, &MockStateView, None).expect("MockVM should not fail to start");
+    let outputs = MockVM::execute_block(txns, &MockStateView, BlockGasLimitType::NoLimit)
+        .expect("MockVM should not fail to start");
 
     for (i, output) in outputs.iter().enumerate() {
         assert_eq!(
@@ -111,8 +112,8 @@ fn test_mock_vm_payment() {
         encode_transfer_transaction(gen_address(0), gen_address(1), 50),
     ];
 
-    let output =
-        MockVM::execute_block(txns, &MockStateView, None).expect("MockVM should not fail to start");
+    let output = MockVM::execute_block(txns, &MockStateView, BlockGasLimitType::NoLimit)
+        .expect("MockVM should not fail to start");
 
     let mut output_iter = output.iter();
     output_iter.next();
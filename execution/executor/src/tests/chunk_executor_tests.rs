@@ -17,6 +17,7 @@ use aptos_executor_types::{BlockExecutorTrait, ChunkExecutorTrait};
 use aptos_storage_interface::DbReaderWriter;
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockGasLimitType,
     test_helpers::transaction_test_helpers::{block, BLOCK_GAS_LIMIT},
     transaction::{TransactionListWithProof, TransactionOutputListWithProof},
 };
@@ -276,7 +277,7 @@ fn test_executor_execute_and_commit_chunk_local_result_mismatch() {
             .execute_block(
                 (block_id, block(txns, BLOCK_GAS_LIMIT)).into(),
                 parent_block_id,
-                BLOCK_GAS_LIMIT,
+                BLOCK_GAS_LIMIT.into(),
             )
             .unwrap();
         let ledger_info = tests::gen_ledger_info(5 + 1, output.root_hash(), block_id, 1);
@@ -324,7 +325,11 @@ fn test_executor_execute_and_commit_chunk_without_verify() {
             .map(|_| encode_mint_transaction(tests::gen_address(rng.gen::<u64>()), 100))
             .collect::<Vec<_>>();
         let output = executor
-            .execute_block((block_id, block(txns, None)).into(), parent_block_id, None)
+            .execute_block(
+                (block_id, block(txns, None)).into(),
+                parent_block_id,
+                BlockGasLimitType::NoLimit,
+            )
             .unwrap();
         let ledger_info = tests::gen_ledger_info(6, output.root_hash(), block_id, 1);
         executor.commit_blocks(vec![block_id], ledger_info).unwrap();
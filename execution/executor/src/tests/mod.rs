@@ -55,7 +55,7 @@ fn execute_and_commit_block(
         .execute_block(
             (id, block(vec![txn], BLOCK_GAS_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let version = 2 * (txn_index + 1);
@@ -154,7 +154,7 @@ fn test_executor_status() {
         .execute_block(
             (block_id, block(vec![txn0, txn1, txn2], BLOCK_GAS_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
 
@@ -184,7 +184,7 @@ fn test_executor_status_consensus_only() {
         .execute_block(
             (block_id, block(vec![txn0, txn1, txn2], BLOCK_GAS_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
 
@@ -214,7 +214,7 @@ fn test_executor_one_block() {
         .execute_block(
             (block_id, block(txns, BLOCK_GAS_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let version = num_user_txns + 1;
@@ -260,14 +260,14 @@ fn test_executor_two_blocks_with_failed_txns() {
         .execute_block(
             (block1_id, block(block1_txns, BLOCK_GAS_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let output2 = executor
         .execute_block(
             (block2_id, block(block2_txns, BLOCK_GAS_LIMIT)).into(),
             block1_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
 
@@ -289,7 +289,7 @@ fn test_executor_commit_twice() {
         .execute_block(
             (block1_id, block(block1_txns, BLOCK_GAS_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let ledger_info = gen_ledger_info(6, output1.root_hash(), block1_id, 1);
@@ -319,7 +319,7 @@ fn test_executor_execute_same_block_multiple_times() {
             .execute_block(
                 (block_id, block(txns.clone(), BLOCK_GAS_LIMIT)).into(),
                 parent_block_id,
-                BLOCK_GAS_LIMIT,
+                BLOCK_GAS_LIMIT.into(),
             )
             .unwrap();
         responses.push(output);
@@ -366,7 +366,7 @@ fn create_transaction_chunks(
         .execute_block(
             (id, txns.clone()).into(),
             executor.committed_block_id(),
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
 
@@ -406,7 +406,7 @@ fn test_noop_block_after_reconfiguration() {
         .execute_block(
             (first_block_id, vec![first_txn]).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     parent_block_id = first_block_id;
@@ -415,7 +415,7 @@ fn test_noop_block_after_reconfiguration() {
         .execute_block(
             (second_block.id, second_block.txns).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     assert_eq!(output1.root_hash(), output2.root_hash());
@@ -600,28 +600,28 @@ fn test_reconfig_suffix_empty_blocks() {
         .execute_block(
             (block_a.id, block_a.txns).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     let output = executor
         .execute_block(
             (block_b.id, block_b.txns).into(),
             block_a.id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     executor
         .execute_block(
             (block_c.id, block_c.txns).into(),
             block_b.id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     executor
         .execute_block(
             (block_d.id, block_d.txns).into(),
             block_c.id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
 
@@ -681,7 +681,7 @@ fn run_transactions_naive(
                     Arc::new(AsyncProofFetcher::new(db.reader.clone())),
                 )
                 .unwrap(),
-            maybe_block_gas_limit,
+            maybe_block_gas_limit.into(),
         )
         .unwrap();
         let (executed, _, _) = out.apply_to_ledger(&ledger_view, None).unwrap();
@@ -721,7 +721,7 @@ proptest! {
 
             let parent_block_id = executor.committed_block_id();
             let output = executor.execute_block(
-                (block_id, block.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT
+                (block_id, block.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT.into()
             ).unwrap();
 
             // assert: txns after the reconfiguration are with status "Retry"
@@ -740,7 +740,7 @@ proptest! {
             // retry txns after reconfiguration
             let retry_block_id = gen_block_id(2);
             let retry_output = executor.execute_block(
-                (retry_block_id, block.txns.iter().skip(reconfig_txn_index as usize + 1).cloned().collect()).into(), parent_block_id, BLOCK_GAS_LIMIT
+                (retry_block_id, block.txns.iter().skip(reconfig_txn_index as usize + 1).cloned().collect()).into(), parent_block_id, BLOCK_GAS_LIMIT.into()
             ).unwrap();
             prop_assert!(retry_output.compute_status().iter().all(|s| matches!(*s, TransactionStatus::Keep(_))));
 
@@ -786,7 +786,7 @@ proptest! {
         {
             parent_block_id = executor.committed_block_id();
             let output_a = executor.execute_block(
-                (block_a.id, block_a.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT
+                (block_a.id, block_a.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT.into()
             ).unwrap();
             root_hash = output_a.root_hash();
             let ledger_info = gen_ledger_info(ledger_version_from_block_size(block_a.txns.len(), BLOCK_GAS_LIMIT) as u64, root_hash, block_a.id, 1);
@@ -797,7 +797,7 @@ proptest! {
         // Now we construct a new executor and run one more block.
         {
             let executor = BlockExecutor::<MockVM>::new(db);
-            let output_b = executor.execute_block((block_b.id, block_b.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT).unwrap();
+            let output_b = executor.execute_block((block_b.id, block_b.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT.into()).unwrap();
             root_hash = output_b.root_hash();
             let ledger_info = gen_ledger_info(
                 (ledger_version_from_block_size(block_a.txns.len(), BLOCK_GAS_LIMIT) + ledger_version_from_block_size(block_b.txns.len(), BLOCK_GAS_LIMIT)) as u64,
@@ -855,13 +855,13 @@ proptest! {
         let first_block_id = gen_block_id(1);
         let _output1 = executor.execute_block(
             (first_block_id, first_block_txns).into(),
-            parent_block_id, BLOCK_GAS_LIMIT
+            parent_block_id, BLOCK_GAS_LIMIT.into()
         ).unwrap();
 
         let second_block_id = gen_block_id(2);
         let output2 = executor.execute_block(
             (second_block_id, block(second_block_txns, BLOCK_GAS_LIMIT)).into(),
-            first_block_id, BLOCK_GAS_LIMIT
+            first_block_id, BLOCK_GAS_LIMIT.into()
         ).unwrap();
 
         let version = chunk_size + overlap_size + num_new_txns + 1;
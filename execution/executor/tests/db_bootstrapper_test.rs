@@ -89,7 +89,7 @@ fn execute_and_commit(txns: Vec<Transaction>, db: &DbReaderWriter, signer: &Vali
         .execute_block(
             (block_id, block(txns, BLOCK_GAS_LIMIT)).into(),
             executor.committed_block_id(),
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
     assert_eq!(output.num_leaves(), target_version + 1);
@@ -146,7 +146,7 @@ fn test_reconfiguration() {
         .execute_block(
             (block_id, txn_block.clone()).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_GAS_LIMIT.into(),
         )
         .unwrap();
 
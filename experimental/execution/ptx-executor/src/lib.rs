@@ -31,6 +31,7 @@ use aptos_state_view::StateView;
 use aptos_storage_interface::cached_state_view::CachedStateView;
 use aptos_types::{
     block_executor::partitioner::{ExecutableTransactions, PartitionedTransactions},
+    on_chain_config::BlockGasLimitType,
     transaction::{Transaction, TransactionOutput},
 };
 use aptos_vm::{
@@ -46,7 +47,7 @@ impl VMExecutor for PtxBlockExecutor {
     fn execute_block(
         transactions: Vec<Transaction>,
         state_view: &(impl StateView + Sync),
-        _maybe_block_gas_limit: Option<u64>,
+        _block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let _timer = TIMER.timer_with(&["block_total"]);
 
@@ -102,7 +103,7 @@ impl VMExecutor for PtxBlockExecutor {
         _sharded_block_executor: &ShardedBlockExecutor<S, E>,
         _transactions: PartitionedTransactions,
         _state_view: Arc<S>,
-        _maybe_block_gas_limit: Option<u64>,
+        _block_gas_limit_type: BlockGasLimitType,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         unimplemented!()
     }
@@ -112,12 +113,12 @@ impl TransactionBlockExecutor for PtxBlockExecutor {
     fn execute_transaction_block(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_gas_limit_type: BlockGasLimitType,
     ) -> anyhow::Result<ChunkOutput> {
         ChunkOutput::by_transaction_execution::<PtxBlockExecutor>(
             transactions,
             state_view,
-            maybe_block_gas_limit,
+            block_gas_limit_type,
         )
     }
 }
@@ -15,7 +15,10 @@ use aptos_types::account_address::AccountAddress;
 use rand::seq::SliceRandom;
 use std::{
     cmp::Ordering,
-    collections::{btree_set::Iter, BTreeMap, BTreeSet, HashMap},
+    collections::{
+        btree_set::{Iter, Range},
+        BTreeMap, BTreeSet, HashMap,
+    },
     iter::Rev,
     ops::Bound,
     time::Duration,
@@ -25,23 +28,35 @@ pub type AccountTransactions = BTreeMap<u64, MempoolTransaction>;
 
 /// PriorityIndex represents the main Priority Queue in Mempool.
 /// It's used to form the transaction block for Consensus.
-/// Transactions are ordered by gas price. Second level ordering is done by expiration time.
+/// Transactions are ordered by their effective tip, i.e. the gas price above the current base fee.
+/// Second level ordering is done by expiration time.
+///
+/// As the base fee is the same for all transactions, ordering by the effective tip follows the
+/// gas price (the max fee). Transactions capping their tip with a max priority fee are still
+/// ordered by their max fee, even though they may pay a lower tip. Transactions priced below the
+/// base fee can't be included in a block and are skipped until the base fee drops.
 ///
 /// We don't store the full content of transactions in the index.
 /// Instead we use `OrderedQueueKey` - logical reference to the transaction in the main store.
 pub struct PriorityIndex {
     data: BTreeSet<OrderedQueueKey>,
+    base_fee_per_gas: u64,
 }
 
-pub type PriorityQueueIter<'a> = Rev<Iter<'a, OrderedQueueKey>>;
+pub type PriorityQueueIter<'a> = Rev<Range<'a, OrderedQueueKey>>;
 
 impl PriorityIndex {
     pub(crate) fn new() -> Self {
         Self {
             data: BTreeSet::new(),
+            base_fee_per_gas: 0,
         }
     }
 
+    pub(crate) fn set_base_fee_per_gas(&mut self, base_fee_per_gas: u64) {
+        self.base_fee_per_gas = base_fee_per_gas;
+    }
+
     pub(crate) fn insert(&mut self, txn: &MempoolTransaction) {
         self.data.insert(self.make_key(txn));
     }
@@ -63,8 +78,11 @@ impl PriorityIndex {
         }
     }
 
+    /// Iterates over the transactions paying at least the base fee, highest effective tip first.
     pub(crate) fn iter(&self) -> PriorityQueueIter {
-        self.data.iter().rev()
+        self.data
+            .range(OrderedQueueKey::lowest_with_score(self.base_fee_per_gas)..)
+            .rev()
     }
 
     pub(crate) fn size(&self) -> usize {
@@ -80,6 +98,21 @@ pub struct OrderedQueueKey {
     pub sequence_number: SequenceInfo,
 }
 
+impl OrderedQueueKey {
+    /// The key ordered before any other key with the given `gas_ranking_score`.
+    fn lowest_with_score(gas_ranking_score: u64) -> Self {
+        Self {
+            gas_ranking_score,
+            expiration_time: Duration::MAX,
+            address: AccountAddress::ZERO,
+            sequence_number: SequenceInfo {
+                transaction_sequence_number: u64::MAX,
+                account_sequence_number: 0,
+            },
+        }
+    }
+}
+
 impl PartialOrd for OrderedQueueKey {
     fn partial_cmp(&self, other: &OrderedQueueKey) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! An on-disk journal of the client-submitted transactions in Mempool, so that they can be
+//! replayed after a restart of the node.
+//!
+//! Serialized signed transactions, along with whether they were submitted privately, identified
+//! by sender and sequence number.
+//! ```text
+//! |<-------------key------------->|<---------value--------->|
+//! | sender | sequence_number (BE) | transaction | private |
+//! ```
+
+use anyhow::{ensure, Result};
+use aptos_logger::prelude::*;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+    Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
+use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use serde::{Deserialize, Serialize};
+use std::{mem::size_of, path::Path, time::Instant};
+
+/// The name of the mempool journal db file
+pub const MEMPOOL_JOURNAL_DB_NAME: &str = "mempool_db";
+
+const TRANSACTION_CF_NAME: &str = "transaction";
+
+define_schema!(
+    TransactionSchema,
+    (AccountAddress, u64),
+    JournaledTransaction,
+    TRANSACTION_CF_NAME
+);
+
+/// A journaled transaction, and whether it's only broadcast to trusted peers.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JournaledTransaction {
+    pub txn: SignedTransaction,
+    pub private: bool,
+}
+
+impl KeyCodec<TransactionSchema> for (AccountAddress, u64) {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded_key = self.0.to_vec();
+        encoded_key.extend_from_slice(&self.1.to_be_bytes());
+        Ok(encoded_key)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == AccountAddress::LENGTH + size_of::<u64>(),
+            "Unexpected data len {}, expected {}.",
+            data.len(),
+            AccountAddress::LENGTH + size_of::<u64>(),
+        );
+        let (address, sequence_number) = data.split_at(AccountAddress::LENGTH);
+        Ok((
+            AccountAddress::try_from(address)?,
+            u64::from_be_bytes(sequence_number.try_into()?),
+        ))
+    }
+}
+
+impl ValueCodec<TransactionSchema> for JournaledTransaction {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+/// The journal of the client-submitted transactions that are in Mempool, i.e., accepted but not
+/// yet committed, rejected or expired.
+pub struct MempoolJournal {
+    db: DB,
+}
+
+impl MempoolJournal {
+    pub fn new<P: AsRef<Path>>(db_root_path: P) -> Self {
+        let column_families = vec![
+            /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
+            TRANSACTION_CF_NAME,
+        ];
+
+        let path = db_root_path.as_ref().join(MEMPOOL_JOURNAL_DB_NAME);
+        let instant = Instant::now();
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open(path.clone(), "mempool", column_families, &opts)
+            .expect("MempoolJournal open failed; unable to continue");
+
+        info!(
+            "Opened MempoolJournal at {:?} in {} ms",
+            path,
+            instant.elapsed().as_millis()
+        );
+
+        Self { db }
+    }
+
+    /// Adds the transaction to the journal, replacing any transaction with the same sender and
+    /// sequence number.
+    pub fn put(&self, txn: &SignedTransaction, private: bool) -> Result<()> {
+        self.db.put::<TransactionSchema>(
+            &(txn.sender(), txn.sequence_number()),
+            &JournaledTransaction {
+                txn: txn.clone(),
+                private,
+            },
+        )
+    }
+
+    /// Removes the transaction with the given sender and sequence number from the journal.
+    pub fn delete(&self, sender: AccountAddress, sequence_number: u64) -> Result<()> {
+        let batch = SchemaBatch::new();
+        batch.delete::<TransactionSchema>(&(sender, sequence_number))?;
+        self.db.write_schemas(batch)
+    }
+
+    /// Returns all transactions in the journal, ordered by sender and sequence number. The
+    /// transactions are kept in the journal, so that they survive another restart before they
+    /// are replayed.
+    pub fn get_all(&self) -> Result<Vec<JournaledTransaction>> {
+        let mut iter = self.db.iter::<TransactionSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        iter.map(|entry| entry.map(|(_, txn)| txn)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::common::TestTransaction;
+    use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_encode_decode() {
+        let txn = TestTransaction::new(0, 7, 1).make_signed_transaction();
+        assert_encode_decode::<TransactionSchema>(
+            &(txn.sender(), txn.sequence_number()),
+            &JournaledTransaction { txn, private: true },
+        );
+    }
+
+    test_no_panic_decoding!(TransactionSchema);
+
+    #[test]
+    fn test_put_delete_get_all() {
+        let tmp_dir = TempPath::new();
+        let journal = MempoolJournal::new(&tmp_dir);
+
+        // Journal transactions, including a private replacement
+        let txns: Vec<_> = [
+            (1, 1, 1, false),
+            (0, 2, 1, false),
+            (0, 1, 1, false),
+            (0, 1, 5, true),
+        ]
+        .into_iter()
+        .map(
+            |(address, sequence_number, gas_price, private)| JournaledTransaction {
+                txn: TestTransaction::new(address, sequence_number, gas_price)
+                    .make_signed_transaction(),
+                private,
+            },
+        )
+        .collect();
+        for journaled_txn in txns.iter() {
+            journal
+                .put(&journaled_txn.txn, journaled_txn.private)
+                .unwrap();
+        }
+        journal.delete(txns[0].txn.sender(), 1).unwrap();
+
+        // The remaining transactions are ordered by sender and sequence number
+        let journaled_txns = journal.get_all().unwrap();
+        assert_eq!(journaled_txns, vec![txns[3].clone(), txns[1].clone()]);
+
+        // The transactions are kept until deleted, also when reopened
+        drop(journal);
+        let journal = MempoolJournal::new(&tmp_dir);
+        assert_eq!(journal.get_all().unwrap(), journaled_txns);
+        journal.delete(txns[1].txn.sender(), 2).unwrap();
+        assert_eq!(journal.get_all().unwrap(), vec![txns[3].clone()]);
+    }
+}
@@ -7,12 +7,13 @@
 use crate::{
     core_mempool::{
         index::TxnPointer,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        journal::{JournaledTransaction, MempoolJournal},
+        transaction::{BroadcastFilter, InsertionInfo, MempoolTransaction, TimelineState},
         transaction_store::TransactionStore,
     },
     counters,
     logging::{LogEntry, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{MultiBucketTimelineIndexIds, SenderPendingTransactions},
 };
 use aptos_config::config::NodeConfig;
 use aptos_consensus_types::common::TransactionInProgress;
@@ -38,8 +39,12 @@ pub struct Mempool {
 
 impl Mempool {
     pub fn new(config: &NodeConfig) -> Self {
+        let journal = config
+            .mempool
+            .enable_persistence
+            .then(|| MempoolJournal::new(config.storage.dir()));
         Mempool {
-            transactions: TransactionStore::new(&config.mempool),
+            transactions: TransactionStore::new(&config.mempool, journal),
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
@@ -52,6 +57,27 @@ impl Mempool {
             .commit_transaction(sender, sequence_number);
     }
 
+    /// Returns all journaled transactions, which were in Mempool before the last restart, to be
+    /// re-validated and added back.
+    pub(crate) fn get_journaled_transactions(&self) -> Vec<JournaledTransaction> {
+        self.transactions.get_journaled_transactions()
+    }
+
+    /// Removes the replayed transactions that didn't make it back into Mempool from the journal.
+    pub(crate) fn remove_replayed_journaled_transactions(
+        &self,
+        txns: impl IntoIterator<Item = (AccountAddress, u64)>,
+    ) {
+        self.transactions
+            .remove_replayed_journaled_transactions(txns)
+    }
+
+    /// Updates the base fee of the next block. Transactions priced below it are kept, but not
+    /// pulled into blocks until the base fee drops.
+    pub(crate) fn set_base_fee_per_gas(&mut self, base_fee_per_gas: u64) {
+        self.transactions.set_base_fee_per_gas(base_fee_per_gas);
+    }
+
     pub(crate) fn log_commit_transaction(
         &self,
         sender: &AccountAddress,
@@ -102,7 +128,11 @@ impl Mempool {
         hash: &HashValue,
         reason: &DiscardedVMStatus,
     ) {
-        if *reason == DiscardedVMStatus::SEQUENCE_NUMBER_TOO_NEW {
+        if matches!(
+            reason,
+            DiscardedVMStatus::SEQUENCE_NUMBER_TOO_NEW
+                | DiscardedVMStatus::GAS_UNIT_PRICE_BELOW_BASE_FEE
+        ) {
             self.log_reject_transaction(sender, sequence_number, counters::COMMIT_IGNORED_LABEL);
             // Do not remove the transaction from mempool
             return;
@@ -118,6 +148,37 @@ impl Mempool {
             .reject_transaction(sender, sequence_number, hash);
     }
 
+    /// Evicts the transaction with the given sender and sequence number on request of an
+    /// operator, and returns whether it was in Mempool.
+    pub(crate) fn evict_transaction(
+        &mut self,
+        sender: &AccountAddress,
+        sequence_number: u64,
+    ) -> bool {
+        self.log_reject_transaction(sender, sequence_number, counters::EVICTED_LABEL);
+        let evicted = self.transactions.evict_transaction(sender, sequence_number);
+        if evicted {
+            info!(
+                LogSchema::new(LogEntry::RemoveTxn)
+                    .txns(TxnsLog::new_txn(*sender, sequence_number)),
+                "Evicted transaction on request of an operator"
+            );
+        }
+        evicted
+    }
+
+    /// Returns the pending transactions of the sender, or of at most `limit` senders after the
+    /// `start` address, including the parked ones.
+    pub(crate) fn get_pending_transactions(
+        &self,
+        sender: Option<AccountAddress>,
+        start: Option<AccountAddress>,
+        limit: usize,
+    ) -> Vec<SenderPendingTransactions> {
+        self.transactions
+            .get_pending_transactions(sender, start, limit)
+    }
+
     pub(crate) fn log_txn_latency(
         insertion_info: InsertionInfo,
         bucket: &str,
@@ -180,6 +241,7 @@ impl Mempool {
         db_sequence_number: u64,
         timeline_state: TimelineState,
         client_submitted: bool,
+        private: bool,
     ) -> MempoolStatus {
         trace!(
             LogSchema::new(LogEntry::AddTxn)
@@ -208,6 +270,7 @@ impl Mempool {
             db_sequence_number,
             now,
             client_submitted,
+            private,
         );
 
         let status = self.transactions.insert(txn_info);
@@ -387,20 +450,33 @@ impl Mempool {
     }
 
     /// Returns block of transactions and new last_timeline_id.
+    /// Transactions excluded by the `filter` are skipped.
     pub(crate) fn read_timeline(
         &self,
         timeline_id: &MultiBucketTimelineIndexIds,
         count: usize,
+        filter: BroadcastFilter,
     ) -> (Vec<SignedTransaction>, MultiBucketTimelineIndexIds) {
-        self.transactions.read_timeline(timeline_id, count)
+        self.transactions.read_timeline(timeline_id, count, filter)
     }
 
     /// Read transactions from timeline from `start_id` (exclusive) to `end_id` (inclusive).
+    /// Transactions excluded by the `filter` are skipped.
     pub(crate) fn timeline_range(
         &self,
         start_end_pairs: &Vec<(u64, u64)>,
+        filter: BroadcastFilter,
     ) -> Vec<SignedTransaction> {
-        self.transactions.timeline_range(start_end_pairs)
+        self.transactions.timeline_range(start_end_pairs, filter)
+    }
+
+    /// Returns true iff the transaction is in Mempool, and is only broadcast to trusted peers.
+    pub(crate) fn is_private_transaction(
+        &self,
+        sender: &AccountAddress,
+        sequence_number: u64,
+    ) -> bool {
+        self.transactions.is_private(sender, sequence_number)
     }
 
     pub fn gen_snapshot(&self) -> TxnsLog {
@@ -3,13 +3,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod index;
+mod journal;
 mod mempool;
 mod transaction;
 mod transaction_store;
 
 pub use self::{
     index::TxnPointer,
+    journal::JournaledTransaction,
     mempool::Mempool as CoreMempool,
-    transaction::{MempoolTransaction, SubmittedBy, TimelineState},
+    transaction::{BroadcastFilter, MempoolTransaction, SubmittedBy, TimelineState},
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
@@ -24,6 +24,8 @@ pub struct MempoolTransaction {
     pub sequence_info: SequenceInfo,
     pub insertion_info: InsertionInfo,
     pub was_parked: bool,
+    // Private transactions are only broadcast to trusted peers
+    pub private: bool,
 }
 
 impl MempoolTransaction {
@@ -35,6 +37,7 @@ impl MempoolTransaction {
         seqno: u64,
         insertion_time: SystemTime,
         client_submitted: bool,
+        private: bool,
     ) -> Self {
         Self {
             sequence_info: SequenceInfo {
@@ -47,6 +50,7 @@ impl MempoolTransaction {
             timeline_state,
             insertion_info: InsertionInfo::new(insertion_time, client_submitted, timeline_state),
             was_parked: false,
+            private,
         }
     }
 
@@ -67,6 +71,27 @@ impl MempoolTransaction {
     }
 }
 
+/// The transactions in the timeline that are broadcast to a peer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BroadcastFilter {
+    // All transactions, e.g., to a trusted peer
+    All,
+    // Only transactions that aren't private, i.e., to an untrusted peer
+    PublicOnly,
+    // Only private transactions, i.e., to a trusted peer that isn't otherwise broadcast to
+    PrivateOnly,
+}
+
+impl BroadcastFilter {
+    pub(crate) fn includes(&self, txn: &MempoolTransaction) -> bool {
+        match self {
+            BroadcastFilter::All => true,
+            BroadcastFilter::PublicOnly => !txn.private,
+            BroadcastFilter::PrivateOnly => txn.private,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Hash, Serialize)]
 pub enum TimelineState {
     // The transaction is ready for broadcast.
@@ -171,6 +196,7 @@ mod test {
             0,
             SystemTime::now(),
             false,
+            false,
         )
     }
 
@@ -8,14 +8,20 @@ use crate::{
             AccountTransactions, MultiBucketTimelineIndex, ParkingLotIndex, PriorityIndex,
             PriorityQueueIter, TTLIndex,
         },
+        journal::{JournaledTransaction, MempoolJournal},
         mempool::Mempool,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction::{
+            BroadcastFilter, InsertionInfo, MempoolTransaction, SubmittedBy, TimelineState,
+        },
         TxnPointer,
     },
     counters,
     counters::{BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL},
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{
+        MultiBucketTimelineIndexIds, PendingTransactionInfo, PendingTransactionStatus,
+        SenderPendingTransactions,
+    },
 };
 use aptos_config::config::MempoolConfig;
 use aptos_crypto::HashValue;
@@ -30,7 +36,7 @@ use std::{
     collections::HashMap,
     mem::size_of,
     ops::Bound,
-    time::{Duration, SystemTime},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 /// Estimated per-txn overhead of indexes. Needs to be updated if additional indexes are added.
@@ -79,10 +85,16 @@ pub struct TransactionStore {
     // eager expiration
     eager_expire_threshold: Option<Duration>,
     eager_expire_time: Duration,
+
+    // replace-by-fee
+    replace_by_fee_min_gas_price_increase_pct: u64,
+
+    // on-disk journal of the client-submitted transactions
+    journal: Option<MempoolJournal>,
 }
 
 impl TransactionStore {
-    pub(crate) fn new(config: &MempoolConfig) -> Self {
+    pub(crate) fn new(config: &MempoolConfig, journal: Option<MempoolJournal>) -> Self {
         Self {
             // main DS
             transactions: HashMap::new(),
@@ -111,6 +123,12 @@ impl TransactionStore {
             // eager expiration
             eager_expire_threshold: config.eager_expire_threshold_ms.map(Duration::from_millis),
             eager_expire_time: Duration::from_millis(config.eager_expire_time_ms),
+
+            // replace-by-fee
+            replace_by_fee_min_gas_price_increase_pct: config
+                .replace_by_fee_min_gas_price_increase_pct,
+
+            journal,
         }
     }
 
@@ -125,6 +143,12 @@ impl TransactionStore {
             .and_then(|txns| txns.get(&sequence_number))
     }
 
+    /// Returns true iff the transaction is in Mempool, and is only broadcast to trusted peers.
+    pub(crate) fn is_private(&self, address: &AccountAddress, sequence_number: u64) -> bool {
+        self.get_mempool_txn(address, sequence_number)
+            .map_or(false, |txn| txn.private)
+    }
+
     /// Fetch transaction by account address + sequence_number.
     pub(crate) fn get(
         &self,
@@ -196,47 +220,47 @@ impl TransactionStore {
         let address = txn.get_sender();
         let txn_seq_num = txn.sequence_info.transaction_sequence_number;
         let acc_seq_num = txn.sequence_info.account_sequence_number;
-        let mut gas_upgraded = false;
+        let mut replaced = false;
 
-        // If the transaction is already in Mempool, we only allow the user to
-        // increase the gas unit price to speed up a transaction, but not the max gas.
+        // If the transaction is already in Mempool, it can only be replaced by a transaction
+        // with a sufficiently higher gas unit price (replace-by-fee). The replacement may differ
+        // in any other input, e.g., to fix the payload of a stuck transaction.
         //
         // Transactions with all the same inputs (but possibly signed differently) are idempotent
         // since the raw transaction is the same
         if let Some(txns) = self.transactions.get_mut(&address) {
             if let Some(current_version) = txns.get_mut(&txn_seq_num) {
-                if current_version.txn.payload() != txn.txn.payload() {
-                    return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
-                        "Transaction already in mempool with a different payload".to_string(),
-                    );
-                } else if current_version.txn.expiration_timestamp_secs()
-                    != txn.txn.expiration_timestamp_secs()
-                {
-                    return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
-                        "Transaction already in mempool with a different expiration timestamp"
-                            .to_string(),
-                    );
-                } else if current_version.txn.max_gas_amount() != txn.txn.max_gas_amount() {
-                    return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
-                        "Transaction already in mempool with a different max gas amount"
-                            .to_string(),
-                    );
-                } else if current_version.get_gas_price() < txn.get_gas_price() {
-                    // Update txn if gas unit price is a larger value than before
-                    if let Some(txn) = txns.remove(&txn_seq_num) {
-                        self.index_remove(&txn);
-                    };
-                    gas_upgraded = true;
-                } else if current_version.get_gas_price() > txn.get_gas_price() {
-                    return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
-                        "Transaction already in mempool with a higher gas price".to_string(),
-                    );
-                } else {
+                let current_gas_price = current_version.get_gas_price();
+                let same_inputs = current_version.txn.payload() == txn.txn.payload()
+                    && current_version.txn.expiration_timestamp_secs()
+                        == txn.txn.expiration_timestamp_secs()
+                    && current_version.txn.max_gas_amount() == txn.txn.max_gas_amount();
+                if same_inputs && current_gas_price == txn.get_gas_price() {
                     // If the transaction is the same, it's an idempotent call
                     // Updating signers is not supported, the previous submission must fail
                     counters::CORE_MEMPOOL_IDEMPOTENT_TXNS.inc();
                     return MempoolStatus::new(MempoolStatusCode::Accepted);
                 }
+
+                let min_gas_price = min_replacement_gas_price(
+                    current_gas_price,
+                    self.replace_by_fee_min_gas_price_increase_pct,
+                );
+                if txn.get_gas_price() < min_gas_price {
+                    return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
+                        format!(
+                            "Transaction already in mempool with gas unit price {}. A replacement requires a gas unit price of at least {}",
+                            current_gas_price, min_gas_price,
+                        ),
+                    );
+                }
+
+                // Replace the txn, removing it from all indexes
+                if let Some(txn) = txns.remove(&txn_seq_num) {
+                    self.index_remove(&txn);
+                };
+                counters::CORE_MEMPOOL_REPLACED_TXNS.inc();
+                replaced = true;
             }
         }
 
@@ -273,15 +297,27 @@ impl TransactionStore {
                 .insert(txn.get_committed_hash(), (txn.get_sender(), txn_seq_num));
             self.sequence_numbers.insert(txn.get_sender(), acc_seq_num);
             self.size_bytes += txn.get_estimated_bytes();
-            if gas_upgraded {
+            if replaced {
                 self.gas_upgraded_index
                     .insert(TxnPointer::from(&txn), txn.get_gas_price());
             }
+            if let Some(journal) = &self.journal {
+                if txn.insertion_info.submitted_by == SubmittedBy::Client {
+                    if let Err(error) = journal.put(&txn.txn, txn.private) {
+                        counters::DB_ERROR.inc();
+                        error!(LogSchema::new(LogEntry::Journal).error(&error));
+                    }
+                }
+            }
             txns.insert(txn_seq_num, txn);
             self.track_indices();
         }
         self.process_ready_transactions(&address, acc_seq_num);
-        MempoolStatus::new(MempoolStatusCode::Accepted)
+        if replaced {
+            MempoolStatus::new(MempoolStatusCode::Replaced)
+        } else {
+            MempoolStatus::new(MempoolStatusCode::Accepted)
+        }
     }
 
     fn track_indices(&self) {
@@ -556,6 +592,17 @@ impl TransactionStore {
         self.hash_index.remove(&txn.get_committed_hash());
         self.size_bytes -= txn.get_estimated_bytes();
         self.gas_upgraded_index.remove(&TxnPointer::from(txn));
+        if let Some(journal) = &self.journal {
+            if txn.insertion_info.submitted_by == SubmittedBy::Client {
+                if let Err(error) = journal.delete(
+                    txn.get_sender(),
+                    txn.sequence_info.transaction_sequence_number,
+                ) {
+                    counters::DB_ERROR.inc();
+                    error!(LogSchema::new(LogEntry::Journal).error(&error));
+                }
+            }
+        }
 
         // Remove account datastructures if there are no more transactions for the account.
         let address = &txn.get_sender();
@@ -576,6 +623,7 @@ impl TransactionStore {
         &self,
         timeline_id: &MultiBucketTimelineIndexIds,
         count: usize,
+        filter: BroadcastFilter,
     ) -> (Vec<SignedTransaction>, MultiBucketTimelineIndexIds) {
         let mut batch = vec![];
         let mut batch_total_bytes: u64 = 0;
@@ -591,6 +639,14 @@ impl TransactionStore {
         {
             for (address, sequence_number) in bucket {
                 if let Some(txn) = self.get_mempool_txn(address, *sequence_number) {
+                    // Skip the excluded transactions, so they aren't read again
+                    if !filter.includes(txn) {
+                        if let TimelineState::Ready(timeline_id) = txn.timeline_state {
+                            last_timeline_id[i] = timeline_id;
+                        }
+                        continue;
+                    }
+
                     let transaction_bytes = txn.txn.raw_txn_bytes_len() as u64;
                     if batch_total_bytes.saturating_add(transaction_bytes) > self.max_batch_bytes {
                         break; // The batch is full
@@ -623,6 +679,7 @@ impl TransactionStore {
     pub(crate) fn timeline_range(
         &self,
         start_end_pairs: &Vec<(u64, u64)>,
+        filter: BroadcastFilter,
     ) -> Vec<SignedTransaction> {
         self.timeline_index
             .timeline_range(start_end_pairs)
@@ -631,6 +688,7 @@ impl TransactionStore {
                 self.transactions
                     .get(account)
                     .and_then(|txns| txns.get(sequence_number))
+                    .filter(|txn| filter.includes(txn))
                     .map(|txn| txn.txn.clone())
             })
             .collect()
@@ -755,10 +813,48 @@ impl TransactionStore {
         self.track_indices();
     }
 
+    /// Returns all transactions in the journal (if any) to be replayed. They are only removed
+    /// from the journal once they leave Mempool again, or if they fail to be replayed.
+    pub(crate) fn get_journaled_transactions(&self) -> Vec<JournaledTransaction> {
+        match &self.journal {
+            Some(journal) => journal.get_all().unwrap_or_else(|error| {
+                counters::DB_ERROR.inc();
+                error!(LogSchema::new(LogEntry::Journal).error(&error));
+                vec![]
+            }),
+            None => vec![],
+        }
+    }
+
+    /// Removes the given replayed transactions from the journal (if any), unless they are in
+    /// Mempool (i.e., they were re-inserted, or resubmitted since).
+    pub(crate) fn remove_replayed_journaled_transactions(
+        &self,
+        txns: impl IntoIterator<Item = (AccountAddress, u64)>,
+    ) {
+        let journal = match &self.journal {
+            Some(journal) => journal,
+            None => return,
+        };
+        for (sender, sequence_number) in txns {
+            if self.get_mempool_txn(&sender, sequence_number).is_some() {
+                continue;
+            }
+            if let Err(error) = journal.delete(sender, sequence_number) {
+                counters::DB_ERROR.inc();
+                error!(LogSchema::new(LogEntry::Journal).error(&error));
+            }
+        }
+    }
+
     pub(crate) fn iter_queue(&self) -> PriorityQueueIter {
         self.priority_index.iter()
     }
 
+    pub(crate) fn set_base_fee_per_gas(&mut self, base_fee_per_gas: u64) {
+        self.priority_index.set_base_fee_per_gas(base_fee_per_gas);
+    }
+
     pub(crate) fn gen_snapshot(&self) -> TxnsLog {
         let mut txns_log = TxnsLog::new();
         for (account, txns) in self.transactions.iter() {
@@ -779,6 +875,109 @@ impl TransactionStore {
         txns_log
     }
 
+    /// Returns the pending transactions of the sender, or of at most `limit` senders ordered by
+    /// address, starting after the `start` address (if any).
+    pub(crate) fn get_pending_transactions(
+        &self,
+        sender: Option<AccountAddress>,
+        start: Option<AccountAddress>,
+        limit: usize,
+    ) -> Vec<SenderPendingTransactions> {
+        let mut senders: Vec<_> = match sender {
+            Some(sender) => self
+                .transactions
+                .get_key_value(&sender)
+                .into_iter()
+                .collect(),
+            None => self
+                .transactions
+                .iter()
+                .filter(|(sender, _)| start.map_or(true, |start| **sender > start))
+                .collect(),
+        };
+
+        // Only sort the senders of the page, rather than all senders in mempool
+        if senders.len() > limit {
+            senders.select_nth_unstable_by_key(limit, |(sender, _)| **sender);
+            senders.truncate(limit);
+        }
+        senders.sort_by_key(|(sender, _)| **sender);
+
+        senders
+            .into_iter()
+            .map(|(sender, txns)| {
+                let sequence_number = self.get_sequence_number(sender).map_or(0, |v| *v);
+
+                // The first gap in the sequence numbers, which keeps the later ones parked
+                let mut missing_start = sequence_number;
+                while txns.contains_key(&missing_start) {
+                    missing_start += 1;
+                }
+                let missing_end = txns
+                    .range(missing_start..)
+                    .next()
+                    .map_or(missing_start, |(txn_sequence_number, _)| {
+                        *txn_sequence_number
+                    });
+
+                let transactions = txns
+                    .iter()
+                    .map(|(txn_sequence_number, txn)| {
+                        let status = if self.parking_lot_index.contains(sender, txn_sequence_number)
+                        {
+                            PendingTransactionStatus::Parked {
+                                missing_sequence_numbers: missing_start..missing_end,
+                            }
+                        } else {
+                            PendingTransactionStatus::Ready
+                        };
+                        PendingTransactionInfo {
+                            hash: txn.get_committed_hash(),
+                            sequence_number: *txn_sequence_number,
+                            gas_unit_price: txn.get_gas_price(),
+                            ranking_score: txn.ranking_score,
+                            expiration_timestamp_secs: txn.txn.expiration_timestamp_secs(),
+                            insertion_timestamp_usecs: txn
+                                .insertion_info
+                                .insertion_time
+                                .duration_since(UNIX_EPOCH)
+                                .map_or(0, |duration| duration.as_micros() as u64),
+                            client_submitted: txn.insertion_info.submitted_by
+                                == SubmittedBy::Client,
+                            private: txn.private,
+                            status,
+                        }
+                    })
+                    .collect();
+                SenderPendingTransactions {
+                    sender: *sender,
+                    sequence_number,
+                    transactions,
+                }
+            })
+            .collect()
+    }
+
+    /// Evicts the transaction with the given sender and sequence number, on request of an
+    /// operator. Returns whether the transaction was in mempool.
+    pub(crate) fn evict_transaction(
+        &mut self,
+        account: &AccountAddress,
+        sequence_number: u64,
+    ) -> bool {
+        let Some(txn) = self
+            .transactions
+            .get_mut(account)
+            .and_then(|txns| txns.remove(&sequence_number))
+        else {
+            return false;
+        };
+        // The later transactions of the account are no longer sequential, so they are skipped
+        // when pulling blocks until the gap is filled
+        self.index_remove(&txn);
+        true
+    }
+
     #[cfg(test)]
     pub(crate) fn get_parking_lot_size(&self) -> usize {
         self.parking_lot_index.size()
@@ -789,3 +988,12 @@ impl TransactionStore {
         &self.transactions
     }
 }
+
+/// Returns the minimum gas unit price of a transaction replacing one with the given gas unit
+/// price, i.e., the gas unit price increased by the given percentage (rounded up), and at least
+/// by one.
+fn min_replacement_gas_price(gas_price: u64, min_increase_pct: u64) -> u64 {
+    let increase = (gas_price as u128 * min_increase_pct as u128 + 99) / 100;
+    let min_gas_price = (gas_price as u128 + increase.max(1)).min(u64::MAX as u128);
+    min_gas_price as u64
+}
@@ -62,8 +62,9 @@ pub use shared_mempool::{
     bootstrap, network,
     network::MempoolSyncMsg,
     types::{
-        MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, QuorumStoreRequest,
-        QuorumStoreResponse, SubmissionStatus,
+        MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, PeerBroadcastStatus,
+        PendingTransactionInfo, PendingTransactionStatus, QuorumStoreRequest, QuorumStoreResponse,
+        SenderPendingTransactions, SubmissionStatus,
     },
 };
 #[cfg(any(test, feature = "fuzzing"))]
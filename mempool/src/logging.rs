@@ -171,8 +171,10 @@ pub enum LogEntry {
     CleanRejectedTxn,
     ProcessReadyTxns,
     DBError,
+    Journal,
     UnexpectedNetworkMsg,
     MempoolSnapshot,
+    Introspection,
 }
 
 #[derive(Clone, Copy, Serialize)]
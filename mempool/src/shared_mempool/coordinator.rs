@@ -11,11 +11,15 @@ use crate::{
     network::MempoolSyncMsg,
     shared_mempool::{
         tasks,
-        tasks::process_committed_transactions,
-        types::{notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification},
+        tasks::{process_base_fee_update, process_committed_transactions},
+        types::{
+            notify_subscribers, MultiBatchId, ScheduledBroadcast, SharedMempool,
+            SharedMempoolNotification, SubmissionStatus,
+        },
     },
     MempoolEventsReceiver, QuorumStoreRequest,
 };
+use anyhow::Result;
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
 use aptos_consensus_types::common::TransactionSummary;
@@ -26,14 +30,18 @@ use aptos_mempool_notifications::{MempoolCommitNotification, MempoolNotification
 use aptos_network::{
     application::{
         interface::{NetworkClientInterface, NetworkServiceEvents},
+        reputation::PeerMisbehavior,
         storage::PeersAndMetadata,
     },
     protocols::network::Event,
 };
-use aptos_types::on_chain_config::{OnChainConfigPayload, OnChainConfigProvider};
+use aptos_types::{
+    on_chain_config::{OnChainConfigPayload, OnChainConfigProvider},
+    transaction::SignedTransaction,
+};
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
-    channel::mpsc,
+    channel::{mpsc, oneshot},
     stream::{select_all, FuturesUnordered},
     FutureExt, StreamExt,
 };
@@ -136,50 +144,144 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
 {
     match request {
         MempoolClientRequest::SubmitTransaction(txn, callback) => {
+            handle_client_submission(smp, bounded_executor, txn, false, callback).await;
+        },
+        MempoolClientRequest::SubmitPrivateTransaction(txn, callback) => {
+            handle_client_submission(smp, bounded_executor, txn, true, callback).await;
+        },
+        MempoolClientRequest::GetTransactionByHash(hash, callback) => {
             // This timer measures how long it took for the bounded executor to *schedule* the
             // task.
             let _timer = counters::task_spawn_latency_timer(
-                counters::CLIENT_EVENT_LABEL,
+                counters::CLIENT_EVENT_GET_TXN_LABEL,
                 counters::SPAWN_LABEL,
             );
             // This timer measures how long it took for the task to go from scheduled to started.
             let task_start_timer = counters::task_spawn_latency_timer(
-                counters::CLIENT_EVENT_LABEL,
+                counters::CLIENT_EVENT_GET_TXN_LABEL,
                 counters::START_LABEL,
             );
             bounded_executor
-                .spawn(tasks::process_client_transaction_submission(
+                .spawn(tasks::process_client_get_transaction(
                     smp.clone(),
-                    txn,
+                    hash,
                     callback,
                     task_start_timer,
                 ))
                 .await;
         },
-        MempoolClientRequest::GetTransactionByHash(hash, callback) => {
+        MempoolClientRequest::GetPendingTransactions(sender, start, limit, callback) => {
             // This timer measures how long it took for the bounded executor to *schedule* the
             // task.
             let _timer = counters::task_spawn_latency_timer(
-                counters::CLIENT_EVENT_GET_TXN_LABEL,
+                counters::CLIENT_EVENT_GET_PENDING_TXNS_LABEL,
                 counters::SPAWN_LABEL,
             );
             // This timer measures how long it took for the task to go from scheduled to started.
             let task_start_timer = counters::task_spawn_latency_timer(
-                counters::CLIENT_EVENT_GET_TXN_LABEL,
+                counters::CLIENT_EVENT_GET_PENDING_TXNS_LABEL,
                 counters::START_LABEL,
             );
             bounded_executor
-                .spawn(tasks::process_client_get_transaction(
+                .spawn(tasks::process_client_get_pending_transactions(
                     smp.clone(),
-                    hash,
+                    sender,
+                    start,
+                    limit,
                     callback,
                     task_start_timer,
                 ))
                 .await;
         },
+        // The other introspection requests of operators are rare and cheap, so they're
+        // processed without spawning a task
+        MempoolClientRequest::GetBroadcastStatuses(callback) => {
+            let statuses = smp.network_interface.broadcast_statuses();
+            tasks::respond_to_introspection_request(callback, statuses);
+        },
+        MempoolClientRequest::EvictTransaction(sender, sequence_number, callback) => {
+            let evicted = smp
+                .mempool
+                .lock()
+                .evict_transaction(&sender, sequence_number);
+            tasks::respond_to_introspection_request(callback, evicted);
+        },
     }
 }
 
+/// Spawn a task for processing a transaction submitted by a client, which is only broadcast to
+/// trusted peers if `private`.
+async fn handle_client_submission<NetworkClient, TransactionValidator>(
+    smp: &mut SharedMempool<NetworkClient, TransactionValidator>,
+    bounded_executor: &BoundedExecutor,
+    txn: SignedTransaction,
+    private: bool,
+    callback: oneshot::Sender<Result<SubmissionStatus>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg> + 'static,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    // This timer measures how long it took for the bounded executor to *schedule* the
+    // task.
+    let _timer =
+        counters::task_spawn_latency_timer(counters::CLIENT_EVENT_LABEL, counters::SPAWN_LABEL);
+    // This timer measures how long it took for the task to go from scheduled to started.
+    let task_start_timer =
+        counters::task_spawn_latency_timer(counters::CLIENT_EVENT_LABEL, counters::START_LABEL);
+    bounded_executor
+        .spawn(tasks::process_client_transaction_submission(
+            smp.clone(),
+            txn,
+            private,
+            callback,
+            task_start_timer,
+        ))
+        .await;
+}
+
+/// Spawn a task for processing a transaction broadcast from a peer. Each transaction is paired
+/// with whether it's private, i.e., only broadcast to trusted peers.
+async fn handle_transaction_broadcast<NetworkClient, TransactionValidator>(
+    bounded_executor: &BoundedExecutor,
+    smp: &mut SharedMempool<NetworkClient, TransactionValidator>,
+    peer: PeerNetworkId,
+    request_id: MultiBatchId,
+    transactions: Vec<(SignedTransaction, bool)>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg> + 'static,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let smp_clone = smp.clone();
+    let ineligible_for_broadcast = (smp.network_interface.is_validator()
+        && !smp.broadcast_within_validator_network())
+        || smp.network_interface.is_upstream_peer(&peer, None);
+    let timeline_state = if ineligible_for_broadcast {
+        TimelineState::NonQualified
+    } else {
+        TimelineState::NotReady
+    };
+    // This timer measures how long it took for the bounded executor to *schedule* the task.
+    let _timer = counters::task_spawn_latency_timer(
+        counters::PEER_BROADCAST_EVENT_LABEL,
+        counters::SPAWN_LABEL,
+    );
+    // This timer measures how long it took for the task to go from scheduled to started.
+    let task_start_timer = counters::task_spawn_latency_timer(
+        counters::PEER_BROADCAST_EVENT_LABEL,
+        counters::START_LABEL,
+    );
+    bounded_executor
+        .spawn(tasks::process_transaction_broadcast(
+            smp_clone,
+            transactions,
+            request_id,
+            timeline_state,
+            peer,
+            task_start_timer,
+        ))
+        .await;
+}
+
 /// Handle removing committed transactions from local mempool immediately.  This should be done
 /// immediately to ensure broadcasts of committed transactions stop as soon as possible.
 fn handle_commit_notification<NetworkClient, TransactionValidator>(
@@ -213,6 +315,7 @@ fn handle_commit_notification<NetworkClient, TransactionValidator>(
             .collect(),
         msg.block_timestamp_usecs,
     );
+    process_base_fee_update(&smp.mempool, &smp.db);
     smp.validator.write().notify_commit();
     let counter_result = if mempool_listener.ack_commit_notification(msg).is_err() {
         error!(LogSchema::event_log(
@@ -281,38 +384,30 @@ async fn handle_network_event<NetworkClient, TransactionValidator>(
                     request_id,
                     transactions,
                 } => {
-                    let smp_clone = smp.clone();
                     let peer = PeerNetworkId::new(network_id, peer_id);
-                    let ineligible_for_broadcast = (smp.network_interface.is_validator()
-                        && !smp.broadcast_within_validator_network())
-                        || smp.network_interface.is_upstream_peer(&peer, None);
-                    let timeline_state = if ineligible_for_broadcast {
-                        TimelineState::NonQualified
-                    } else {
-                        TimelineState::NotReady
-                    };
-                    // This timer measures how long it took for the bounded executor to
-                    // *schedule* the task.
-                    let _timer = counters::task_spawn_latency_timer(
-                        counters::PEER_BROADCAST_EVENT_LABEL,
-                        counters::SPAWN_LABEL,
-                    );
-                    // This timer measures how long it took for the task to go from scheduled
-                    // to started.
-                    let task_start_timer = counters::task_spawn_latency_timer(
-                        counters::PEER_BROADCAST_EVENT_LABEL,
-                        counters::START_LABEL,
-                    );
-                    bounded_executor
-                        .spawn(tasks::process_transaction_broadcast(
-                            smp_clone,
-                            transactions,
-                            request_id,
-                            timeline_state,
-                            peer,
-                            task_start_timer,
-                        ))
-                        .await;
+                    let transactions = transactions.into_iter().map(|txn| (txn, false)).collect();
+                    handle_transaction_broadcast(
+                        bounded_executor,
+                        smp,
+                        peer,
+                        request_id,
+                        transactions,
+                    )
+                    .await;
+                },
+                MempoolSyncMsg::BroadcastTransactionsRequestWithPrivacy {
+                    request_id,
+                    transactions,
+                } => {
+                    let peer = PeerNetworkId::new(network_id, peer_id);
+                    handle_transaction_broadcast(
+                        bounded_executor,
+                        smp,
+                        peer,
+                        request_id,
+                        transactions,
+                    )
+                    .await;
                 },
                 MempoolSyncMsg::BroadcastTransactionsResponse {
                     request_id,
@@ -332,11 +427,21 @@ async fn handle_network_event<NetworkClient, TransactionValidator>(
         },
         Event::RpcRequest(peer_id, _msg, _, _res_tx) => {
             counters::unexpected_msg_count_inc(&network_id);
+            let peer = PeerNetworkId::new(network_id, peer_id);
             sample!(
                 SampleRate::Duration(Duration::from_secs(60)),
-                warn!(LogSchema::new(LogEntry::UnexpectedNetworkMsg)
-                    .peer(&PeerNetworkId::new(network_id, peer_id)))
+                warn!(LogSchema::new(LogEntry::UnexpectedNetworkMsg).peer(&peer))
             );
+
+            // Mempool doesn't use RPCs, so the peer is misbehaving
+            if let Err(error) = smp
+                .network_interface
+                .report_peer_misbehavior(peer, PeerMisbehavior::UnexpectedMessage)
+            {
+                warn!(LogSchema::new(LogEntry::UnexpectedNetworkMsg)
+                    .peer(&peer)
+                    .error(&error.into()));
+            }
         },
     }
 }
@@ -5,12 +5,13 @@
 //! Interface between Mempool and Network layers.
 
 use crate::{
+    core_mempool::BroadcastFilter,
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     shared_mempool::{
         tasks,
         types::{
-            notify_subscribers, MultiBatchId, PeerSyncState, SharedMempool,
+            notify_subscribers, MultiBatchId, PeerBroadcastStatus, PeerSyncState, SharedMempool,
             SharedMempoolNotification,
         },
     },
@@ -23,8 +24,12 @@ use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_network::{
-    application::{error::Error, interface::NetworkClientInterface, metadata::PeerMetadata},
+    application::{
+        error::Error, interface::NetworkClientInterface, metadata::PeerMetadata,
+        reputation::PeerMisbehavior,
+    },
     transport::ConnectionMetadata,
+    ProtocolId,
 };
 use aptos_types::{transaction::SignedTransaction, PeerId};
 use aptos_vm_validator::vm_validator::TransactionValidation;
@@ -59,6 +64,14 @@ pub enum MempoolSyncMsg {
         /// A backpressure signal from the recipient when it is overwhelmed (e.g., mempool is full).
         backoff: bool,
     },
+    /// Broadcast request issued by the sender to a trusted peer, when the batch contains private
+    /// transactions. Each transaction is paired with whether it's private, in which case the
+    /// receiver must not broadcast it to untrusted peers either. Only sent to peers announcing
+    /// `ProtocolId::MempoolPrivateDirectSend`, as other peers can't decode it.
+    BroadcastTransactionsRequestWithPrivacy {
+        request_id: MultiBatchId,
+        transactions: Vec<(SignedTransaction, bool)>,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -297,6 +310,67 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         Ok(())
     }
 
+    /// Peers on the validator and VFN networks are always trusted, other peers only when
+    /// they are configured as trusted for private transaction submission.
+    fn is_trusted_peer(&self, peer: PeerNetworkId) -> bool {
+        !peer.network_id().is_public_network()
+            || self
+                .mempool_config
+                .private_submission
+                .trusted_peers
+                .contains(&peer.peer_id())
+    }
+
+    /// Private transactions are only broadcast to trusted peers that support them, i.e., that
+    /// can decode broadcasts with private transactions. Other peers would drop such broadcasts
+    /// (along with their public transactions), so they are treated as untrusted.
+    fn receives_private_transactions(
+        &self,
+        peer: PeerNetworkId,
+        metadata: &ConnectionMetadata,
+    ) -> bool {
+        self.is_trusted_peer(peer)
+            && metadata
+                .application_protocols
+                .contains(ProtocolId::MempoolPrivateDirectSend)
+    }
+
+    /// Determines which transactions can be broadcast to the peer. Private transactions are
+    /// only sent to trusted peers, which receive them even when they aren't prioritized.
+    fn broadcast_filter(
+        &self,
+        peer: PeerNetworkId,
+        metadata: &ConnectionMetadata,
+    ) -> Result<BroadcastFilter, BroadcastError> {
+        match (
+            self.check_peer_prioritized(peer),
+            self.receives_private_transactions(peer, metadata),
+        ) {
+            (Ok(()), true) => Ok(BroadcastFilter::All),
+            (Ok(()), false) => Ok(BroadcastFilter::PublicOnly),
+            (Err(_), true) => Ok(BroadcastFilter::PrivateOnly),
+            (Err(error), false) => Err(error),
+        }
+    }
+
+    /// Returns the broadcast status of each upstream peer, ordered by peer
+    pub fn broadcast_statuses(&self) -> Vec<PeerBroadcastStatus> {
+        self.sync_states
+            .read()
+            .iter()
+            .map(|(peer, state)| PeerBroadcastStatus {
+                peer: *peer,
+                prioritized: self.check_peer_prioritized(*peer).is_ok(),
+                trusted: self.receives_private_transactions(*peer, &state.metadata),
+                timeline_ids: state.timeline_id.id_per_bucket.clone(),
+                num_pending_broadcasts: state.broadcast_info.sent_batches.len(),
+                num_retry_broadcasts: state.broadcast_info.retry_batches.len(),
+                backoff_mode: state.broadcast_info.backoff_mode,
+            })
+            .sorted_by_key(|status| status.peer)
+            .collect()
+    }
+
     /// Determines the broadcast batch.  There are three types of batches:
     /// * Expired -> This timed out waiting for a response and needs to be resent
     /// * Retry -> This received a response telling it to retry later
@@ -306,15 +380,23 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         peer: PeerNetworkId,
         scheduled_backoff: bool,
         smp: &mut SharedMempool<NetworkClient, TransactionValidator>,
-    ) -> Result<(MultiBatchId, Vec<SignedTransaction>, Option<&str>), BroadcastError> {
+    ) -> Result<
+        (
+            MultiBatchId,
+            Vec<(SignedTransaction, bool)>,
+            BroadcastFilter,
+            Option<&str>,
+        ),
+        BroadcastError,
+    > {
         let mut sync_states = self.sync_states.write();
         // If we don't have any info about the node, we shouldn't broadcast to it
         let state = sync_states
             .get_mut(&peer)
             .ok_or(BroadcastError::PeerNotFound(peer))?;
 
-        // If the peer isn't prioritized, lets not broadcast
-        self.check_peer_prioritized(peer)?;
+        // If the peer isn't prioritized, lets only broadcast private transactions (if trusted)
+        let filter = self.broadcast_filter(peer, &state.metadata)?;
 
         // If backoff mode is on for this peer, only execute broadcasts that were scheduled as a backoff broadcast.
         // This is to ensure the backoff mode is actually honored (there is a chance a broadcast was scheduled
@@ -332,14 +414,14 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             .sent_batches
             .clone()
             .into_iter()
-            .filter(|(id, _batch)| !mempool.timeline_range(&id.0).is_empty())
+            .filter(|(id, _batch)| !mempool.timeline_range(&id.0, filter).is_empty())
             .collect::<BTreeMap<MultiBatchId, SystemTime>>();
         state.broadcast_info.retry_batches = state
             .broadcast_info
             .retry_batches
             .clone()
             .into_iter()
-            .filter(|id| !mempool.timeline_range(&id.0).is_empty())
+            .filter(|id| !mempool.timeline_range(&id.0, filter).is_empty())
             .collect::<BTreeSet<MultiBatchId>>();
 
         // Check for batch to rebroadcast:
@@ -380,20 +462,23 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
                         Some(counters::RETRY_BROADCAST_LABEL)
                     };
 
-                    let txns = mempool.timeline_range(&id.0);
+                    let txns = mempool.timeline_range(&id.0, filter);
                     (id.clone(), txns, metric_label)
                 },
                 None => {
                     // Fresh broadcast
+                    let timeline_id = state.timeline_id(filter);
                     let (txns, new_timeline_id) = mempool.read_timeline(
-                        &state.timeline_id,
+                        timeline_id,
                         self.mempool_config.shared_mempool_batch_size,
+                        filter,
                     );
-                    (
-                        MultiBatchId::from_timeline_ids(&state.timeline_id, &new_timeline_id),
-                        txns,
-                        None,
-                    )
+                    let batch_id = MultiBatchId::from_timeline_ids(timeline_id, &new_timeline_id);
+                    // All transactions read were filtered out, so skip past them for this peer
+                    if txns.is_empty() {
+                        state.update_timeline_ids(filter, &batch_id);
+                    }
+                    (batch_id, txns, None)
                 },
             };
 
@@ -401,7 +486,17 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             return Err(BroadcastError::NoTransactions(peer));
         }
 
-        Ok((batch_id, transactions, metric_label))
+        // Pair the transactions with whether they are private, so trusted peers keep them private
+        let transactions = transactions
+            .into_iter()
+            .map(|txn| {
+                let private = filter != BroadcastFilter::PublicOnly
+                    && mempool.is_private_transaction(&txn.sender(), txn.sequence_number());
+                (txn, private)
+            })
+            .collect();
+
+        Ok((batch_id, transactions, filter, metric_label))
     }
 
     /// Sends a batch to the given peer
@@ -409,11 +504,20 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         &self,
         peer: PeerNetworkId,
         batch_id: MultiBatchId,
-        transactions: Vec<SignedTransaction>,
+        transactions: Vec<(SignedTransaction, bool)>,
     ) -> Result<(), BroadcastError> {
-        let request = MempoolSyncMsg::BroadcastTransactionsRequest {
-            request_id: batch_id,
-            transactions,
+        // Only send the privacy of the transactions if there are private ones, so that the
+        // broadcasts to peers unaware of private transactions are unchanged
+        let request = if transactions.iter().any(|(_, private)| *private) {
+            MempoolSyncMsg::BroadcastTransactionsRequestWithPrivacy {
+                request_id: batch_id,
+                transactions,
+            }
+        } else {
+            MempoolSyncMsg::BroadcastTransactionsRequest {
+                request_id: batch_id,
+                transactions: transactions.into_iter().map(|(txn, _)| txn).collect(),
+            }
         };
 
         if let Err(e) = self.network_client.send_to_peer(request, peer) {
@@ -435,12 +539,23 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         self.network_client.send_to_peer(message, peer)
     }
 
+    /// Reports the misbehavior of the given peer to the network
+    pub fn report_peer_misbehavior(
+        &self,
+        peer: PeerNetworkId,
+        misbehavior: PeerMisbehavior,
+    ) -> Result<(), Error> {
+        self.network_client
+            .report_peer_misbehavior(peer, misbehavior)
+    }
+
     /// Updates the local tracker for a broadcast.  This is used to handle `DirectSend` tracking of
     /// responses
     fn update_broadcast_state(
         &self,
         peer: PeerNetworkId,
         batch_id: MultiBatchId,
+        filter: BroadcastFilter,
         send_time: SystemTime,
     ) -> Result<usize, BroadcastError> {
         let mut sync_states = self.sync_states.write();
@@ -448,8 +563,9 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             .get_mut(&peer)
             .ok_or(BroadcastError::PeerNotFound(peer))?;
 
-        // Update peer sync state with info from above broadcast.
-        state.timeline_id.update(&batch_id);
+        // Update peer sync state with info from above broadcast. Only the position of the filter
+        // is moved, e.g., public transactions skipped for an unprioritized peer aren't passed.
+        state.update_timeline_ids(filter, &batch_id);
         // Turn off backoff mode after every broadcast.
         state.broadcast_info.backoff_mode = false;
         state.broadcast_info.retry_batches.remove(&batch_id);
@@ -468,7 +584,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
     ) -> Result<(), BroadcastError> {
         // Start timer for tracking broadcast latency.
         let start_time = Instant::now();
-        let (batch_id, transactions, metric_label) =
+        let (batch_id, transactions, filter, metric_label) =
             self.determine_broadcast_batch(peer, scheduled_backoff, smp)?;
 
         let num_txns = transactions.len();
@@ -476,7 +592,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         self.send_batch_to_peer(peer, batch_id.clone(), transactions)
             .await?;
         let num_pending_broadcasts =
-            self.update_broadcast_state(peer, batch_id.clone(), send_time)?;
+            self.update_broadcast_state(peer, batch_id.clone(), filter, send_time)?;
         notify_subscribers(SharedMempoolNotification::Broadcast, &smp.subscribers);
 
         // Log all the metrics
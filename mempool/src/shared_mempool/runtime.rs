@@ -7,6 +7,7 @@ use crate::{
     network::MempoolSyncMsg,
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
+        tasks::replay_journaled_transactions,
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
     QuorumStoreRequest,
@@ -32,6 +33,7 @@ use tokio::runtime::{Handle, Runtime};
 ///   - outbound_sync_task (task that periodically broadcasts transactions to peers).
 ///   - inbound_network_task (task that handles inbound mempool messages and network events).
 ///   - gc_task (task that performs GC of all expired transactions by SystemTTL).
+///   - replay_task (task that adds back the transactions journaled before a restart, if any).
 pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     executor: &Handle,
     config: &NodeConfig,
@@ -61,6 +63,14 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
             config.base.role,
         );
 
+    // Get the journaled transactions before any new ones are submitted, and replay them in the
+    // background
+    let journaled_txns = mempool.lock().get_journaled_transactions();
+    if !journaled_txns.is_empty() {
+        let smp = smp.clone();
+        executor.spawn_blocking(move || replay_journaled_transactions(&smp, journaled_txns));
+    }
+
     executor.spawn(coordinator(
         smp,
         executor.clone(),
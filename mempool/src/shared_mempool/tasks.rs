@@ -4,13 +4,13 @@
 
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, JournaledTransaction, TimelineState},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
     shared_mempool::types::{
-        notify_subscribers, MultiBatchId, ScheduledBroadcast, SharedMempool,
-        SharedMempoolNotification, SubmissionStatusBundle,
+        notify_subscribers, MultiBatchId, ScheduledBroadcast, SenderPendingTransactions,
+        SharedMempool, SharedMempoolNotification, SubmissionStatusBundle,
     },
     thread_pool::IO_POOL,
     QuorumStoreRequest, QuorumStoreResponse, SubmissionStatus,
@@ -22,15 +22,18 @@ use aptos_crypto::HashValue;
 use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::prelude::*;
 use aptos_metrics_core::HistogramTimer;
-use aptos_network::application::interface::NetworkClientInterface;
-use aptos_storage_interface::state_view::LatestDbStateCheckpointView;
+use aptos_network::application::{interface::NetworkClientInterface, reputation::PeerMisbehavior};
+use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReader};
 use aptos_types::{
+    account_address::AccountAddress,
     mempool_status::{MempoolStatus, MempoolStatusCode},
     on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig},
     transaction::SignedTransaction,
     vm_status::{DiscardedVMStatus, StatusCode},
 };
-use aptos_vm_validator::vm_validator::{get_account_sequence_number, TransactionValidation};
+use aptos_vm_validator::vm_validator::{
+    get_account_sequence_number, get_base_fee_per_gas, TransactionValidation,
+};
 use futures::{channel::oneshot, stream::FuturesUnordered};
 use rayon::prelude::*;
 use std::{
@@ -107,10 +110,12 @@ pub(crate) async fn execute_broadcast<NetworkClient, TransactionValidator>(
 // Tasks processing txn submission //
 // =============================== //
 
-/// Processes transactions directly submitted by client.
+/// Processes transactions directly submitted by client. Private transactions are only broadcast
+/// to trusted peers.
 pub(crate) async fn process_client_transaction_submission<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
     transaction: SignedTransaction,
+    private: bool,
     callback: oneshot::Sender<Result<SubmissionStatus>>,
     timer: HistogramTimer,
 ) where
@@ -126,7 +131,9 @@ pub(crate) async fn process_client_transaction_submission<NetworkClient, Transac
     } else {
         TimelineState::NotReady
     };
-    let statuses = process_incoming_transactions(&smp, vec![transaction], timeline_state, true);
+    let private = private || smp.config.private_submission.private_by_default;
+    let statuses =
+        process_incoming_transactions(&smp, vec![transaction], timeline_state, true, private);
     log_txn_process_results(&statuses, None);
 
     if let Some(status) = statuses.first() {
@@ -163,10 +170,42 @@ pub(crate) async fn process_client_get_transaction<NetworkClient, TransactionVal
     }
 }
 
+/// Processes the request of an operator for the pending transactions of a sender, or of a page
+/// of senders.
+pub(crate) async fn process_client_get_pending_transactions<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    sender: Option<AccountAddress>,
+    start: Option<AccountAddress>,
+    limit: usize,
+    callback: oneshot::Sender<Vec<SenderPendingTransactions>>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    let pending_txns = smp
+        .mempool
+        .lock()
+        .get_pending_transactions(sender, start, limit);
+    respond_to_introspection_request(callback, pending_txns);
+}
+
+/// Sends the response to an introspection request of an operator.
+pub(crate) fn respond_to_introspection_request<T>(callback: oneshot::Sender<T>, response: T) {
+    if callback.send(response).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::Introspection,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes transactions from other nodes.
 pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
-    transactions: Vec<SignedTransaction>,
+    transactions: Vec<(SignedTransaction, bool)>,
     request_id: MultiBatchId,
     timeline_state: TimelineState,
     peer: PeerNetworkId,
@@ -177,9 +216,41 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
 {
     timer.stop_and_record();
     let _timer = counters::process_txn_submit_latency_timer(peer.network_id());
-    let results = process_incoming_transactions(&smp, transactions, timeline_state, false);
+
+    // Private transactions stay private, so they are only broadcast to trusted peers from here
+    let (private_txns, public_txns): (Vec<_>, Vec<_>) =
+        transactions.into_iter().partition(|(_, private)| *private);
+    let mut results = vec![];
+    for (txns, private) in [(public_txns, false), (private_txns, true)] {
+        if !txns.is_empty() {
+            let txns = txns.into_iter().map(|(txn, _)| txn).collect();
+            results.extend(process_incoming_transactions(
+                &smp,
+                txns,
+                timeline_state,
+                false,
+                private,
+            ));
+        }
+    }
     log_txn_process_results(&results, Some(peer));
 
+    // Transactions with invalid signatures can't have been validated by the
+    // peer before the broadcast, so the broadcast is malformed.
+    let has_invalid_signatures = results
+        .iter()
+        .any(|(_, (_, vm_status))| *vm_status == Some(StatusCode::INVALID_SIGNATURE));
+    if has_invalid_signatures {
+        if let Err(error) = smp
+            .network_interface
+            .report_peer_misbehavior(peer, PeerMisbehavior::InvalidData)
+        {
+            warn!(LogSchema::new(LogEntry::BroadcastTransaction)
+                .peer(&peer)
+                .error(&error.into()));
+        }
+    }
+
     let ack_response = gen_ack_response(request_id, results, &peer);
 
     // Respond to the peer with an ack. Note: ack response messages should be
@@ -256,6 +327,7 @@ pub(crate) fn process_incoming_transactions<NetworkClient, TransactionValidator>
     transactions: Vec<SignedTransaction>,
     timeline_state: TimelineState,
     client_submitted: bool,
+    private: bool,
 ) -> Vec<SubmissionStatusBundle>
 where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
@@ -324,11 +396,80 @@ where
         timeline_state,
         &mut statuses,
         client_submitted,
+        private,
     );
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
     statuses
 }
 
+/// Re-validates the transactions journaled before the last restart, and adds them back to the
+/// local mempool as client-submitted transactions, keeping them private if they were. Expired
+/// transactions are dropped, and at most `capacity` transactions are replayed. Afterwards, the
+/// transactions that didn't make it back into the mempool are removed from the journal.
+pub(crate) fn replay_journaled_transactions<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+    mut transactions: Vec<JournaledTransaction>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    let num_journaled = transactions.len();
+    let journaled_txns: Vec<_> = transactions
+        .iter()
+        .map(|journaled_txn| {
+            (
+                journaled_txn.txn.sender(),
+                journaled_txn.txn.sequence_number(),
+            )
+        })
+        .collect();
+    let now_secs = aptos_infallible::duration_since_epoch().as_secs();
+    transactions.retain(|journaled_txn| journaled_txn.txn.expiration_timestamp_secs() > now_secs);
+    transactions.truncate(smp.config.capacity);
+    if transactions.is_empty() {
+        smp.mempool
+            .lock()
+            .remove_replayed_journaled_transactions(journaled_txns);
+        return;
+    }
+
+    let (private_txns, public_txns): (Vec<_>, Vec<_>) = transactions
+        .into_iter()
+        .partition(|journaled_txn| journaled_txn.private);
+    let mut statuses = vec![];
+    for (txns, private) in [(public_txns, false), (private_txns, true)] {
+        if !txns.is_empty() {
+            let txns = txns
+                .into_iter()
+                .map(|journaled_txn| journaled_txn.txn)
+                .collect();
+            statuses.extend(process_incoming_transactions(
+                smp,
+                txns,
+                TimelineState::NotReady,
+                true,
+                private,
+            ));
+        }
+    }
+    let num_replayed = statuses
+        .iter()
+        .filter(|(_, (mempool_status, _))| {
+            matches!(
+                mempool_status.code,
+                MempoolStatusCode::Accepted | MempoolStatusCode::Replaced
+            )
+        })
+        .count();
+    info!(
+        LogSchema::new(LogEntry::Journal).num_txns(num_replayed),
+        "Replayed {} of {} journaled transactions", num_replayed, num_journaled
+    );
+    smp.mempool
+        .lock()
+        .remove_replayed_journaled_transactions(journaled_txns);
+}
+
 /// Perfoms VM validation on the transactions and inserts those that passes
 /// validation into the mempool.
 #[cfg(not(feature = "consensus-only-perf-test"))]
@@ -338,6 +479,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
     client_submitted: bool,
+    private: bool,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
@@ -364,6 +506,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
                             sequence_info,
                             timeline_state,
                             client_submitted,
+                            private,
                         );
                         statuses.push((transaction, (mempool_status, None)));
                     },
@@ -404,6 +547,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
     client_submitted: bool,
+    private: bool,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
@@ -416,6 +560,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
             sequence_info,
             timeline_state,
             client_submitted,
+            private,
         );
         statuses.push((transaction, (mempool_status, None)));
     }
@@ -441,10 +586,12 @@ fn log_txn_process_results(results: &[SubmissionStatusBundle], sender: Option<Pe
             continue;
         }
         match mempool_status.code {
-            MempoolStatusCode::Accepted => counters::shared_mempool_transactions_processed_inc(
-                counters::SUCCESS_LABEL,
-                &network,
-            ),
+            MempoolStatusCode::Accepted | MempoolStatusCode::Replaced => {
+                counters::shared_mempool_transactions_processed_inc(
+                    counters::SUCCESS_LABEL,
+                    &network,
+                )
+            },
             _ => counters::shared_mempool_transactions_processed_inc(
                 &mempool_status.code.to_string(),
                 &network,
@@ -570,6 +717,19 @@ pub(crate) fn process_committed_transactions(
     }
 }
 
+/// Refreshes the base fee from the latest committed state, as committed blocks adjust it.
+pub(crate) fn process_base_fee_update(mempool: &Mutex<CoreMempool>, db: &Arc<dyn DbReader>) {
+    let base_fee_per_gas = match db.latest_state_checkpoint_view() {
+        Ok(state_view) => get_base_fee_per_gas(&state_view).unwrap_or(0),
+        Err(e) => {
+            error!(LogSchema::new(LogEntry::DBError).error(&e));
+            counters::DB_ERROR.inc();
+            return;
+        },
+    };
+    mempool.lock().set_base_fee_per_gas(base_fee_per_gas);
+}
+
 pub(crate) fn process_rejected_transactions(
     mempool: &Mutex<CoreMempool>,
     transactions: Vec<RejectedTransactionSummary>,
@@ -4,7 +4,7 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool,
+    core_mempool::{BroadcastFilter, CoreMempool},
     network::{MempoolNetworkInterface, MempoolSyncMsg},
 };
 use anyhow::Result;
@@ -20,7 +20,8 @@ use aptos_network::{
 };
 use aptos_storage_interface::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -33,6 +34,7 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
     fmt,
+    ops::Range,
     pin::Pin,
     sync::Arc,
     task::Waker,
@@ -229,7 +231,78 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
+    /// Submits a transaction that is only broadcast to trusted peers
+    SubmitPrivateTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    /// Lists the pending transactions of the sender, or of at most `limit` senders ordered by
+    /// address, starting after the `start` address (if any)
+    GetPendingTransactions(
+        Option<AccountAddress>,
+        Option<AccountAddress>,
+        usize,
+        oneshot::Sender<Vec<SenderPendingTransactions>>,
+    ),
+    /// Gets the broadcast status of each peer
+    GetBroadcastStatuses(oneshot::Sender<Vec<PeerBroadcastStatus>>),
+    /// Evicts the transaction with the sender and sequence number, and returns whether it was
+    /// in mempool
+    EvictTransaction(AccountAddress, u64, oneshot::Sender<bool>),
+}
+
+/// The pending transactions of a sender in mempool, for introspection by operators
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SenderPendingTransactions {
+    pub sender: AccountAddress,
+    /// The sequence number of the next transaction of the sender to be committed
+    pub sequence_number: u64,
+    /// The transactions ordered by sequence number, including the parked ones
+    pub transactions: Vec<PendingTransactionInfo>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PendingTransactionInfo {
+    pub hash: HashValue,
+    pub sequence_number: u64,
+    pub gas_unit_price: u64,
+    pub ranking_score: u64,
+    pub expiration_timestamp_secs: u64,
+    pub insertion_timestamp_usecs: u64,
+    pub client_submitted: bool,
+    pub private: bool,
+    pub status: PendingTransactionStatus,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingTransactionStatus {
+    /// Can be pulled into the next block
+    Ready,
+    /// Parked in the parking lot until the earlier transactions of the sender are submitted
+    /// (or evicted otherwise)
+    Parked {
+        /// The first gap in the sequence numbers of the sender's transactions, i.e., from the
+        /// first sequence number (at or after the account's) that isn't in mempool, up to the
+        /// next one that is
+        missing_sequence_numbers: Range<u64>,
+    },
+}
+
+/// The broadcast status of a peer, for introspection by operators
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerBroadcastStatus {
+    pub peer: PeerNetworkId,
+    /// Whether all transactions are broadcast to the peer, rather than only private ones
+    pub prioritized: bool,
+    /// Whether private transactions are broadcast to the peer
+    pub trusted: bool,
+    /// The position in the timeline of each broadcast bucket up to which transactions have
+    /// been broadcast
+    pub timeline_ids: Vec<u64>,
+    /// The number of broadcasts that haven't been acked yet
+    pub num_pending_broadcasts: usize,
+    /// The number of broadcasts that are pending a resend
+    pub num_retry_broadcasts: usize,
+    pub backoff_mode: bool,
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
@@ -237,10 +310,14 @@ pub type MempoolEventsReceiver = mpsc::Receiver<MempoolClientRequest>;
 
 /// State of last sync with peer:
 /// `timeline_id` is position in log of ready transactions
+/// `private_timeline_id` is position in log of ready private transactions, which are broadcast
+/// to trusted peers that aren't prioritized. It's kept apart from `timeline_id`, so that the
+/// skipped public transactions are still broadcast once the peer is prioritized.
 /// `is_alive` - is connection healthy
 #[derive(Clone, Debug)]
 pub(crate) struct PeerSyncState {
     pub timeline_id: MultiBucketTimelineIndexIds,
+    pub private_timeline_id: MultiBucketTimelineIndexIds,
     pub broadcast_info: BroadcastInfo,
     pub metadata: ConnectionMetadata,
 }
@@ -249,10 +326,32 @@ impl PeerSyncState {
     pub fn new(metadata: ConnectionMetadata, num_broadcast_buckets: usize) -> Self {
         PeerSyncState {
             timeline_id: MultiBucketTimelineIndexIds::new(num_broadcast_buckets),
+            private_timeline_id: MultiBucketTimelineIndexIds::new(num_broadcast_buckets),
             broadcast_info: BroadcastInfo::new(),
             metadata,
         }
     }
+
+    /// Returns the position in log of the transactions broadcast with the given filter
+    pub fn timeline_id(&self, filter: BroadcastFilter) -> &MultiBucketTimelineIndexIds {
+        match filter {
+            BroadcastFilter::All | BroadcastFilter::PublicOnly => &self.timeline_id,
+            BroadcastFilter::PrivateOnly => &self.private_timeline_id,
+        }
+    }
+
+    /// Moves the positions in log past a batch broadcast with the given filter
+    pub fn update_timeline_ids(&mut self, filter: BroadcastFilter, batch_id: &MultiBatchId) {
+        match filter {
+            BroadcastFilter::All => {
+                // The private transactions of the batch were broadcast as well
+                self.timeline_id.update(batch_id);
+                self.private_timeline_id.update(batch_id);
+            },
+            BroadcastFilter::PublicOnly => self.timeline_id.update(batch_id),
+            BroadcastFilter::PrivateOnly => self.private_timeline_id.update(batch_id),
+        }
+    }
 }
 
 /// Identifier for a broadcasted batch of txns.
@@ -343,7 +442,12 @@ impl Ord for MultiBatchId {
 
 #[cfg(test)]
 mod test {
-    use crate::shared_mempool::types::{MultiBatchId, MultiBucketTimelineIndexIds};
+    use crate::{
+        core_mempool::BroadcastFilter,
+        shared_mempool::types::{MultiBatchId, MultiBucketTimelineIndexIds, PeerSyncState},
+    };
+    use aptos_network::transport::ConnectionMetadata;
+    use aptos_types::PeerId;
 
     #[test]
     fn test_multi_bucket_timeline_ids_update() {
@@ -355,6 +459,50 @@ mod test {
         assert_eq!(vec![3, 2, 6], timeline_ids.id_per_bucket);
     }
 
+    #[test]
+    fn test_peer_sync_state_update_timeline_ids() {
+        let mut state = PeerSyncState::new(ConnectionMetadata::mock(PeerId::random()), 1);
+
+        // Private broadcasts to an unprioritized peer don't pass the skipped public transactions
+        state.update_timeline_ids(BroadcastFilter::PrivateOnly, &MultiBatchId(vec![(0, 3)]));
+        assert_eq!(
+            vec![0],
+            state.timeline_id(BroadcastFilter::All).id_per_bucket
+        );
+        assert_eq!(
+            vec![3],
+            state
+                .timeline_id(BroadcastFilter::PrivateOnly)
+                .id_per_bucket
+        );
+
+        // Public broadcasts don't include the private transactions
+        state.update_timeline_ids(BroadcastFilter::PublicOnly, &MultiBatchId(vec![(0, 5)]));
+        assert_eq!(
+            vec![5],
+            state.timeline_id(BroadcastFilter::All).id_per_bucket
+        );
+        assert_eq!(
+            vec![3],
+            state
+                .timeline_id(BroadcastFilter::PrivateOnly)
+                .id_per_bucket
+        );
+
+        // Broadcasts of all transactions include both
+        state.update_timeline_ids(BroadcastFilter::All, &MultiBatchId(vec![(5, 7)]));
+        assert_eq!(
+            vec![7],
+            state.timeline_id(BroadcastFilter::All).id_per_bucket
+        );
+        assert_eq!(
+            vec![7],
+            state
+                .timeline_id(BroadcastFilter::PrivateOnly)
+                .id_per_bucket
+        );
+    }
+
     #[test]
     fn test_multi_batch_id_ordering() {
         let left = MultiBatchId(vec![(0, 3), (1, 4), (2, 5)]);
@@ -123,6 +123,7 @@ pub(crate) fn add_txns_to_mempool(
             transaction.account_seqno,
             TimelineState::NotReady,
             false,
+            false,
         );
         transactions.push(txn);
     }
@@ -141,10 +142,11 @@ pub(crate) fn add_signed_txn(pool: &mut CoreMempool, transaction: SignedTransact
             0,
             TimelineState::NotReady,
             false,
+            false,
         )
         .code
     {
-        MempoolStatusCode::Accepted => Ok(()),
+        MempoolStatusCode::Accepted | MempoolStatusCode::Replaced => Ok(()),
         _ => Err(format_err!("insertion failure")),
     }
 }
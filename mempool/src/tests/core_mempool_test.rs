@@ -3,7 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, MempoolTransaction, SubmittedBy, TimelineState},
+    core_mempool::{
+        BroadcastFilter, CoreMempool, JournaledTransaction, MempoolTransaction, SubmittedBy,
+        TimelineState,
+    },
+    shared_mempool::types::PendingTransactionStatus,
     tests::common::{
         add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool,
         setup_mempool_with_broadcast_buckets, TestTransaction,
@@ -12,6 +16,7 @@ use crate::{
 use aptos_config::config::NodeConfig;
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
+use aptos_temppath::TempPath;
 use aptos_types::{
     mempool_status::MempoolStatusCode, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
 };
@@ -63,6 +68,29 @@ fn test_transaction_ordering_only_seqnos() {
     }
 }
 
+#[test]
+fn test_transaction_ordering_base_fee() {
+    let (mut mempool, mut consensus) = setup_mempool();
+    let transactions = add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(0, 0, 3),
+        TestTransaction::new(1, 0, 5),
+        TestTransaction::new(2, 0, 4),
+    ]);
+
+    // Transactions priced below the base fee are not pulled into blocks.
+    mempool.set_base_fee_per_gas(4);
+    assert_eq!(consensus.get_block(&mut mempool, 3, 1024), vec![
+        transactions[1].clone(),
+        transactions[2].clone()
+    ]);
+
+    // Until the base fee drops.
+    mempool.set_base_fee_per_gas(3);
+    assert_eq!(consensus.get_block(&mut mempool, 3, 1024), vec![
+        transactions[0].clone()
+    ]);
+}
+
 #[test]
 fn test_transaction_metrics() {
     let (mut mempool, _) = setup_mempool();
@@ -74,6 +102,7 @@ fn test_transaction_metrics() {
         0,
         TimelineState::NotReady,
         false,
+        false,
     );
     let txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
     mempool.add_txn(
@@ -82,6 +111,7 @@ fn test_transaction_metrics() {
         0,
         TimelineState::NonQualified,
         false,
+        false,
     );
     let txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
     mempool.add_txn(
@@ -90,6 +120,7 @@ fn test_transaction_metrics() {
         0,
         TimelineState::NotReady,
         true,
+        false,
     );
 
     // Check timestamp returned as end-to-end for broadcast-able transaction
@@ -153,23 +184,114 @@ fn test_fail_for_same_gas_amount_and_not_same_expiration_time() {
 fn test_update_invalid_transaction_in_mempool() {
     let (mut mempool, mut consensus) = setup_mempool();
     let txns = add_txns_to_mempool(&mut mempool, vec![
-        TestTransaction::new(0, 0, 1),
-        TestTransaction::new(1, 0, 2),
+        TestTransaction::new(0, 0, 20),
+        TestTransaction::new(1, 0, 30),
     ]);
     let updated_txn = TestTransaction::make_signed_transaction_with_max_gas_amount(
-        &TestTransaction::new(0, 0, 5),
+        &TestTransaction::new(0, 0, 21),
         200,
     );
-    let _added_tnx = add_signed_txn(&mut mempool, updated_txn);
+    let ret = add_signed_txn(&mut mempool, updated_txn);
+    assert!(ret.is_err());
 
-    // Since both gas price and mas gas amount were updated, the ordering should not have changed.
-    // The second transaction with gas price 2 should come first.
+    // Since the gas price wasn't increased enough to replace the transaction, the ordering
+    // should not have changed. The second transaction with gas price 30 should come first.
     assert_eq!(consensus.get_block(&mut mempool, 1, 1024), vec![
         txns[1].clone()
     ]);
     let next_tnx = consensus.get_block(&mut mempool, 1, 1024);
     assert_eq!(next_tnx, vec![txns[0].clone()]);
-    assert_eq!(next_tnx[0].gas_unit_price(), 1);
+    assert_eq!(next_tnx[0].gas_unit_price(), 20);
+}
+
+#[test]
+fn test_replace_transaction_in_mempool() {
+    let (mut mempool, mut consensus) = setup_mempool();
+    let txns = add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(0, 0, 20),
+        TestTransaction::new(1, 0, 30),
+    ]);
+
+    // Replace the first transaction with one that has other inputs and a higher gas price
+    let replacement = TestTransaction::make_signed_transaction_with_max_gas_amount(
+        &TestTransaction::new(0, 0, 40),
+        200,
+    );
+    let status = mempool.add_txn(
+        replacement.clone(),
+        replacement.gas_unit_price(),
+        0,
+        TimelineState::NotReady,
+        false,
+        false,
+    );
+    assert_eq!(status.code, MempoolStatusCode::Replaced);
+
+    // Only the replacement can be found
+    assert!(mempool.get_by_hash(txns[0].committed_hash()).is_none());
+    assert_eq!(
+        mempool.get_by_hash(replacement.committed_hash()),
+        Some(replacement.clone())
+    );
+
+    // The replacement is ordered by its own gas price
+    assert_eq!(consensus.get_block(&mut mempool, 1, 1024), vec![
+        replacement
+    ]);
+    assert_eq!(consensus.get_block(&mut mempool, 1, 1024), vec![
+        txns[1].clone()
+    ]);
+    assert!(consensus.get_block(&mut mempool, 1, 1024).is_empty());
+}
+
+#[test]
+fn test_replace_transaction_min_gas_price_increase() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.broadcast_buckets = vec![0];
+    config.mempool.replace_by_fee_min_gas_price_increase_pct = 10;
+    let mut mempool = CoreMempool::new(&config);
+    add_txns_to_mempool(&mut mempool, vec![TestTransaction::new(0, 0, 100)]);
+
+    // The gas price must be increased by at least 10%
+    let ret = add_txn(&mut mempool, TestTransaction::new(0, 0, 109));
+    assert!(ret.is_err());
+    let ret = add_txn(&mut mempool, TestTransaction::new(0, 0, 110));
+    assert!(ret.is_ok());
+
+    // A lower gas price never replaces a transaction
+    let ret = add_txn(&mut mempool, TestTransaction::new(0, 0, 100));
+    assert!(ret.is_err());
+
+    // Without a minimum increase, any higher gas price replaces a transaction
+    config.mempool.replace_by_fee_min_gas_price_increase_pct = 0;
+    let mut mempool = CoreMempool::new(&config);
+    add_txns_to_mempool(&mut mempool, vec![TestTransaction::new(0, 0, 100)]);
+    let ret = add_txn(&mut mempool, TestTransaction::new(0, 0, 100));
+    assert!(ret.is_ok()); // Idempotent
+    let ret = add_txn(&mut mempool, TestTransaction::new(0, 0, 101));
+    assert!(ret.is_ok());
+}
+
+#[test]
+fn test_replaced_transaction_is_rebroadcast() {
+    let mut pool = setup_mempool().0;
+    add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(1, 0, 10),
+        TestTransaction::new(1, 1, 10),
+    ]);
+
+    // Both transactions are broadcast
+    let (timeline, timeline_id) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
+    assert_eq!(view(timeline), vec![0, 1]);
+    let (timeline, _) = pool.read_timeline(&timeline_id, 10, BroadcastFilter::All);
+    assert!(view(timeline).is_empty());
+
+    // The replacement is broadcast again
+    add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 0, 100)]);
+    let (timeline, _) = pool.read_timeline(&timeline_id, 10, BroadcastFilter::All);
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0].sequence_number(), 0);
+    assert_eq!(timeline[0].gas_unit_price(), 100);
 }
 
 #[test]
@@ -253,6 +375,18 @@ fn test_reject_transaction() {
         .get(&TestTransaction::get_address(0), 1)
         .is_some());
 
+    // reject with gas unit price below the base fee should have no effect
+    pool.reject_transaction(
+        &TestTransaction::get_address(0),
+        0,
+        &txns[0].clone().committed_hash(),
+        &DiscardedVMStatus::GAS_UNIT_PRICE_BELOW_BASE_FEE,
+    );
+    assert!(pool
+        .get_transaction_store()
+        .get(&TestTransaction::get_address(0), 0)
+        .is_some());
+
     // reject with correct hash should have effect
     pool.reject_transaction(
         &TestTransaction::get_address(0),
@@ -363,25 +497,25 @@ fn test_timeline() {
         TestTransaction::new(1, 5, 1),
     ]);
 
-    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![0, 1]);
     // Txns 3 and 5 should be in parking lot.
     assert_eq!(2, pool.get_parking_lot_size());
 
     // Add txn 2 to unblock txn3.
     add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 2, 1)]);
-    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![0, 1, 2, 3]);
     // Txn 5 should be in parking lot.
     assert_eq!(1, pool.get_parking_lot_size());
 
     // Try different start read position.
-    let (timeline, _) = pool.read_timeline(&vec![2].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![2].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2, 3]);
 
     // Simulate callback from consensus to unblock txn 5.
     pool.commit_transaction(&TestTransaction::get_address(1), 4);
-    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![5]);
     // check parking lot is empty
     assert_eq!(0, pool.get_parking_lot_size());
@@ -397,46 +531,86 @@ fn test_multi_bucket_timeline() {
         TestTransaction::new(1, 5, 300), // bucket 2
     ]);
 
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![0, 1]);
     // Txns 3 and 5 should be in parking lot.
     assert_eq!(2, pool.get_parking_lot_size());
 
     // Add txn 2 to unblock txn3.
     add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 2, 1)]);
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![0, 1, 2, 3]);
     // Txn 5 should be in parking lot.
     assert_eq!(1, pool.get_parking_lot_size());
 
     // Try different start read positions. Expected buckets: [[0, 1, 2], [3], []]
-    let (timeline, _) = pool.read_timeline(&vec![1, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![1, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![1, 2, 3]);
-    let (timeline, _) = pool.read_timeline(&vec![2, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![2, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2, 3]);
-    let (timeline, _) = pool.read_timeline(&vec![0, 1, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 1, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![0, 1, 2]);
-    let (timeline, _) = pool.read_timeline(&vec![1, 1, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![1, 1, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![1, 2]);
-    let (timeline, _) = pool.read_timeline(&vec![2, 1, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![2, 1, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2]);
-    let (timeline, _) = pool.read_timeline(&vec![3, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![3, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![3]);
-    let (timeline, _) = pool.read_timeline(&vec![3, 1, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![3, 1, 0].into(), 10, BroadcastFilter::All);
     assert!(view(timeline).is_empty());
 
     // Ensure high gas is prioritized.
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 1);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 1, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![3]);
 
     // Simulate callback from consensus to unblock txn 5.
     pool.commit_transaction(&TestTransaction::get_address(1), 4);
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![5]);
     // check parking lot is empty
     assert_eq!(0, pool.get_parking_lot_size());
 }
 
+#[test]
+fn test_timeline_private_transactions() {
+    let mut pool = setup_mempool().0;
+    for (sequence_number, private) in [(0, false), (1, true), (2, false), (3, true)] {
+        let txn = TestTransaction::new(1, sequence_number, 1).make_signed_transaction();
+        pool.add_txn(txn, 1, 0, TimelineState::NotReady, true, private);
+    }
+
+    // Private transactions are only read for trusted peers
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
+    assert_eq!(view(timeline), vec![0, 1, 2, 3]);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::PublicOnly);
+    assert_eq!(view(timeline), vec![0, 2]);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::PrivateOnly);
+    assert_eq!(view(timeline), vec![1, 3]);
+
+    // The timeline advances past the filtered out transactions, so they aren't read again
+    let (timeline, timeline_id) =
+        pool.read_timeline(&vec![0].into(), 1, BroadcastFilter::PrivateOnly);
+    assert!(timeline.is_empty());
+    assert_eq!(timeline_id.id_per_bucket, vec![1]);
+    let (timeline, timeline_id) =
+        pool.read_timeline(&timeline_id, 10, BroadcastFilter::PrivateOnly);
+    assert_eq!(view(timeline), vec![1, 3]);
+    assert_eq!(timeline_id.id_per_bucket, vec![4]);
+
+    // Rebroadcasts of a timeline range are also filtered
+    let timeline = pool.timeline_range(&vec![(0, 4)], BroadcastFilter::PublicOnly);
+    assert_eq!(view(timeline), vec![0, 2]);
+    let timeline = pool.timeline_range(&vec![(0, 4)], BroadcastFilter::PrivateOnly);
+    assert_eq!(view(timeline), vec![1, 3]);
+
+    // The privacy of the transactions is broadcast along with them
+    let sender = TestTransaction::get_address(1);
+    let privacy: Vec<_> = (0..5)
+        .map(|sequence_number| pool.is_private_transaction(&sender, sequence_number))
+        .collect();
+    assert_eq!(privacy, vec![false, true, false, true, false]);
+}
+
 #[test]
 fn test_multi_bucket_gas_ranking_update() {
     let mut pool = setup_mempool_with_broadcast_buckets(vec![0, 101, 201]).0;
@@ -448,26 +622,26 @@ fn test_multi_bucket_gas_ranking_update() {
     ]);
 
     // txn 2 and 3 are prioritized
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 2);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 2, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2, 3]);
     // read only bucket 2
-    let (timeline, _) = pool.read_timeline(&vec![10, 10, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![10, 10, 0].into(), 10, BroadcastFilter::All);
     assert!(view(timeline).is_empty());
 
     // resubmit with higher gas: move txn 2 to bucket 2
     add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 2, 400)]);
 
     // txn 2 is now prioritized
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 1);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 1, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2]);
     // then txn 3 is prioritized
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 2);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 2, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2, 3]);
     // read only bucket 2
-    let (timeline, _) = pool.read_timeline(&vec![10, 10, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![10, 10, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2]);
     // read only bucket 1
-    let (timeline, _) = pool.read_timeline(&vec![10, 0, 10].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![10, 0, 10].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![3]);
 }
 
@@ -481,23 +655,23 @@ fn test_multi_bucket_removal() {
         TestTransaction::new(1, 3, 200), // bucket 1
     ]);
 
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![0, 1, 2, 3]);
 
     pool.commit_transaction(&TestTransaction::get_address(1), 0);
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![1, 2, 3]);
 
     pool.commit_transaction(&TestTransaction::get_address(1), 1);
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![2, 3]);
 
     pool.commit_transaction(&TestTransaction::get_address(1), 2);
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert_eq!(view(timeline), vec![3]);
 
     pool.commit_transaction(&TestTransaction::get_address(1), 3);
-    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 10, BroadcastFilter::All);
     assert!(view(timeline).is_empty());
 }
 
@@ -565,6 +739,7 @@ fn test_capacity_bytes() {
                 txn.sequence_info.account_sequence_number,
                 txn.timeline_state,
                 false,
+                false,
             );
             assert_eq!(status.code, MempoolStatusCode::Accepted);
         });
@@ -576,6 +751,7 @@ fn test_capacity_bytes() {
                 txn.sequence_info.account_sequence_number,
                 txn.timeline_state,
                 false,
+                false,
             );
             assert_eq!(status.code, MempoolStatusCode::MempoolIsFull);
         }
@@ -594,6 +770,7 @@ fn new_test_mempool_transaction(address: usize, sequence_number: u64) -> Mempool
         0,
         SystemTime::now(),
         false,
+        false,
     )
 }
 
@@ -662,7 +839,7 @@ fn test_gc_ready_transaction() {
 
     // Insert in the middle transaction that's going to be expired.
     let txn = TestTransaction::new(1, 1, 1).make_signed_transaction_with_expiration_time(0);
-    pool.add_txn(txn, 1, 0, TimelineState::NotReady, false);
+    pool.add_txn(txn, 1, 0, TimelineState::NotReady, false, false);
 
     // Insert few transactions after it.
     // They are supposed to be ready because there's a sequential path from 0 to them.
@@ -670,7 +847,7 @@ fn test_gc_ready_transaction() {
     add_txn(&mut pool, TestTransaction::new(1, 3, 1)).unwrap();
 
     // Check that all txns are ready.
-    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
     assert_eq!(timeline.len(), 4);
 
     // GC expired transaction.
@@ -681,7 +858,7 @@ fn test_gc_ready_transaction() {
     assert_eq!(block.len(), 1);
     assert_eq!(block[0].sequence_number(), 0);
 
-    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
     assert_eq!(timeline.len(), 1);
     assert_eq!(timeline[0].sequence_number(), 0);
 
@@ -689,7 +866,7 @@ fn test_gc_ready_transaction() {
     add_txn(&mut pool, TestTransaction::new(1, 1, 1)).unwrap();
 
     // Make sure txns 2 and 3 can be broadcast after txn 1 is resubmitted
-    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, BroadcastFilter::All);
     assert_eq!(timeline.len(), 4);
 }
 
@@ -701,7 +878,14 @@ fn test_clean_stuck_transactions() {
     }
     let db_sequence_number = 10;
     let txn = TestTransaction::new(0, db_sequence_number, 1).make_signed_transaction();
-    pool.add_txn(txn, 1, db_sequence_number, TimelineState::NotReady, false);
+    pool.add_txn(
+        txn,
+        1,
+        db_sequence_number,
+        TimelineState::NotReady,
+        false,
+        false,
+    );
     let block = pool.get_batch(1, 1024, true, false, vec![]);
     assert_eq!(block.len(), 1);
     assert_eq!(block[0].sequence_number(), 10);
@@ -718,6 +902,7 @@ fn test_get_transaction_by_hash() {
         db_sequence_number,
         TimelineState::NotReady,
         false,
+        false,
     );
     let hash = txn.clone().committed_hash();
     let ret = pool.get_by_hash(hash);
@@ -738,6 +923,7 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
         db_sequence_number,
         TimelineState::NotReady,
         false,
+        false,
     );
     let hash = txn.committed_hash();
 
@@ -749,6 +935,7 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
         db_sequence_number,
         TimelineState::NotReady,
         false,
+        false,
     );
     let new_txn_hash = new_txn.clone().committed_hash();
 
@@ -917,3 +1104,164 @@ fn test_include_gas_upgraded() {
     let batch = pool.get_batch(10, 10240, true, true, vec![high_gas_txn, low_gas_txn]);
     assert_eq!(batch.len(), 0);
 }
+
+#[test]
+fn test_journal_client_submitted_transactions() {
+    let tmp_dir = TempPath::new();
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.enable_persistence = true;
+    config.storage.dir = tmp_dir.path().to_path_buf();
+    let mut pool = CoreMempool::new(&config);
+
+    // Add client-submitted transactions, and one from a peer
+    let client_txns: Vec<_> = [(0, 0, 1), (0, 1, 1), (1, 0, 1)]
+        .into_iter()
+        .map(|(address, sequence_number, gas_price)| {
+            TestTransaction::new(address, sequence_number, gas_price).make_signed_transaction()
+        })
+        .collect();
+    for txn in client_txns.iter() {
+        pool.add_txn(txn.clone(), 1, 0, TimelineState::NotReady, true, false);
+    }
+    let peer_txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
+    pool.add_txn(peer_txn, 1, 0, TimelineState::NotReady, false, false);
+
+    // Privately replace a client-submitted transaction, and commit another one
+    let replacement = TestTransaction::new(0, 1, 10).make_signed_transaction();
+    pool.add_txn(
+        replacement.clone(),
+        1,
+        0,
+        TimelineState::NotReady,
+        true,
+        true,
+    );
+    pool.commit_transaction(&TestTransaction::get_address(1), 0);
+
+    // Only the pending client-submitted transactions are journaled across restarts
+    drop(pool);
+    let pool = CoreMempool::new(&config);
+    let journaled_txns = pool.get_journaled_transactions();
+    assert_eq!(journaled_txns, vec![
+        JournaledTransaction {
+            txn: client_txns[0].clone(),
+            private: false,
+        },
+        JournaledTransaction {
+            txn: replacement.clone(),
+            private: true,
+        },
+    ]);
+
+    // The journal is kept across another restart before the transactions are replayed
+    drop(pool);
+    let mut pool = CoreMempool::new(&config);
+    assert_eq!(pool.get_journaled_transactions(), journaled_txns);
+
+    // Re-inserted transactions stay journaled, while the ones that weren't are removed
+    pool.add_txn(
+        replacement.clone(),
+        1,
+        0,
+        TimelineState::NotReady,
+        true,
+        true,
+    );
+    pool.remove_replayed_journaled_transactions(journaled_txns.iter().map(|journaled_txn| {
+        (
+            journaled_txn.txn.sender(),
+            journaled_txn.txn.sequence_number(),
+        )
+    }));
+    assert_eq!(pool.get_journaled_transactions(), vec![
+        JournaledTransaction {
+            txn: replacement,
+            private: true,
+        }
+    ]);
+}
+
+#[test]
+fn test_get_pending_transactions() {
+    let mut pool = setup_mempool().0;
+    add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(1, 1, 1),
+        TestTransaction::new(1, 3, 1),
+        TestTransaction::new(0, 2, 1),
+    ]);
+
+    // Senders are listed in order of address
+    let senders = |start, limit| -> Vec<_> {
+        pool.get_pending_transactions(None, start, limit)
+            .into_iter()
+            .map(|sender_txns| sender_txns.sender)
+            .collect()
+    };
+    let mut expected_senders = vec![
+        TestTransaction::get_address(0),
+        TestTransaction::get_address(1),
+    ];
+    expected_senders.sort();
+    assert_eq!(senders(None, 10), expected_senders);
+
+    // Senders are paged, with the next page starting after the last sender
+    assert_eq!(senders(None, 1), expected_senders[..1]);
+    assert_eq!(senders(Some(expected_senders[0]), 1), expected_senders[1..]);
+    assert!(senders(Some(expected_senders[1]), 1).is_empty());
+
+    // Transactions in the parking lot are parked
+    let statuses = |sender| {
+        let pending_txns =
+            pool.get_pending_transactions(Some(TestTransaction::get_address(sender)), None, 1);
+        assert_eq!(pending_txns.len(), 1);
+        pending_txns[0]
+            .transactions
+            .iter()
+            .map(|txn| (txn.sequence_number, txn.status.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(statuses(0), vec![(2, PendingTransactionStatus::Parked {
+        missing_sequence_numbers: 0..2
+    })]);
+    assert_eq!(statuses(1), vec![
+        (0, PendingTransactionStatus::Ready),
+        (1, PendingTransactionStatus::Ready),
+        (3, PendingTransactionStatus::Parked {
+            missing_sequence_numbers: 2..3
+        }),
+    ]);
+
+    // Senders without transactions in mempool aren't listed
+    assert!(pool
+        .get_pending_transactions(Some(TestTransaction::get_address(2)), None, 1)
+        .is_empty());
+}
+
+#[test]
+fn test_evict_transaction() {
+    let (mut pool, mut consensus) = setup_mempool();
+    add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(1, 1, 1),
+        TestTransaction::new(1, 2, 1),
+    ]);
+
+    // Evicting a transaction that isn't in mempool fails
+    assert!(!pool.evict_transaction(&TestTransaction::get_address(1), 3));
+    assert!(!pool.evict_transaction(&TestTransaction::get_address(2), 0));
+
+    // The later transactions of the sender aren't pulled into blocks behind the evicted one
+    assert!(pool.evict_transaction(&TestTransaction::get_address(1), 1));
+    let pending_txns =
+        pool.get_pending_transactions(Some(TestTransaction::get_address(1)), None, 1);
+    let sequence_numbers: Vec<_> = pending_txns[0]
+        .transactions
+        .iter()
+        .map(|txn| txn.sequence_number)
+        .collect();
+    assert_eq!(sequence_numbers, vec![0, 2]);
+    let block = consensus.get_block(&mut pool, 10, 1024);
+    assert_eq!(block.len(), 1);
+    assert_eq!(block[0].sequence_number(), 0);
+}
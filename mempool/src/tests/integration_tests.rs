@@ -22,6 +22,10 @@ use aptos_network::{
 use std::time::Duration;
 
 const ALL_PROTOCOLS: [ProtocolId; 1] = [ProtocolId::MempoolDirectSend];
+const PRIVATE_PROTOCOLS: [ProtocolId; 2] = [
+    ProtocolId::MempoolDirectSend,
+    ProtocolId::MempoolPrivateDirectSend,
+];
 static ALL_TXNS: &[TestTransaction] = &[test_transaction(0), test_transaction(1)];
 static TXN_1: &[TestTransaction] = &[test_transaction(0)];
 static TXN_2: &[TestTransaction] = &[test_transaction(1)];
@@ -317,6 +321,29 @@ async fn test_rebroadcast_retry_is_empty() {
         .await;
 }
 
+/// Private transactions are only broadcast to trusted peers that announce support for them
+#[tokio::test]
+async fn test_broadcast_private_txns() {
+    for (protocols, expected_txns) in [
+        (&ALL_PROTOCOLS[..], TXN_2),
+        (&PRIVATE_PROTOCOLS[..], ALL_TXNS),
+    ] {
+        let mut node = MempoolTestFrameworkBuilder::single_validator();
+        let (other_peer_network_id, other_metadata) =
+            validator_mock_connection(ConnectionOrigin::Outbound, protocols);
+
+        // The first txn is private, the second isn't
+        node.add_private_txns_via_client(TXN_1).await;
+        node.add_txns_via_client(TXN_2).await;
+        node.assert_txns_in_mempool(ALL_TXNS);
+
+        // Peers without support for private txns only get the public txn
+        node.connect_self(other_peer_network_id.network_id(), other_metadata.clone());
+        node.send_broadcast_and_receive_ack(other_peer_network_id, expected_txns)
+            .await;
+    }
+}
+
 // -- Multi node tests below here --
 
 /// Tests if the node is a VFN, and it's getting forwarded messages from a PFN.  It should forward
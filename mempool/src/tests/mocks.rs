@@ -166,16 +166,16 @@ impl MockSharedMempool {
         {
             let mut pool = self.mempool.lock();
             for txn in txns {
-                if pool
-                    .add_txn(
-                        txn.clone(),
-                        txn.gas_unit_price(),
-                        0,
-                        TimelineState::NotReady,
-                        false,
-                    )
-                    .code
-                    != MempoolStatusCode::Accepted
+                let status = pool.add_txn(
+                    txn.clone(),
+                    txn.gas_unit_price(),
+                    0,
+                    TimelineState::NotReady,
+                    false,
+                    false,
+                );
+                if status.code != MempoolStatusCode::Accepted
+                    && status.code != MempoolStatusCode::Replaced
                 {
                     return Err(format_err!("failed to insert into mock mempool"));
                 };
@@ -49,7 +49,7 @@ fn test_consensus_events_rejected_txns() {
 
     let pool = smp.mempool.lock();
     // TODO: make less brittle to broadcast buckets changes
-    let (timeline, _) = pool.read_timeline(&vec![0; 10].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0; 10].into(), 10, BroadcastFilter::All);
     assert_eq!(timeline.len(), 2);
     assert_eq!(timeline.first().unwrap(), &kept_txn);
 }
@@ -92,7 +92,7 @@ fn test_mempool_notify_committed_txns() {
 
     let pool = smp.mempool.lock();
     // TODO: make less brittle to broadcast buckets changes
-    let (timeline, _) = pool.read_timeline(&vec![0; 10].into(), 10);
+    let (timeline, _) = pool.read_timeline(&vec![0; 10].into(), 10, BroadcastFilter::All);
     assert_eq!(timeline.len(), 1);
     assert_eq!(timeline.first().unwrap(), &kept_txn);
 }
@@ -156,6 +156,20 @@ impl MempoolNode {
         }
     }
 
+    /// Queues private transactions for sending on a node, uses client
+    pub async fn add_private_txns_via_client(&mut self, txns: &[TestTransaction]) {
+        for txn in sign_transactions(txns) {
+            let (sender, receiver) = oneshot::channel();
+
+            self.mempool_client_sender
+                .send(MempoolClientRequest::SubmitPrivateTransaction(txn, sender))
+                .await
+                .unwrap();
+            let status = receiver.await.unwrap().unwrap();
+            assert_eq!(status.0.code, MempoolStatusCode::Accepted)
+        }
+    }
+
     pub async fn commit_txns(&mut self, txns: &[TestTransaction]) {
         for txn in sign_transactions(txns) {
             self.mempool
@@ -361,37 +375,42 @@ impl MempoolNode {
         };
         assert_eq!(peer_id, expected_peer_id);
         let mempool_message = common::decompress_and_deserialize(&data.to_vec());
-        let request_id = match mempool_message {
+        let (request_id, transactions) = match mempool_message {
             MempoolSyncMsg::BroadcastTransactionsRequest {
                 request_id,
                 transactions,
-            } => {
-                if !block_only_contains_transactions(&transactions, expected_txns) {
-                    let txns: Vec<_> = transactions
-                        .iter()
-                        .map(|txn| (txn.sender(), txn.sequence_number()))
-                        .collect();
-                    let expected_txns: Vec<_> = expected_txns
-                        .iter()
-                        .map(|txn| {
-                            (
-                                TestTransaction::get_address(txn.address),
-                                txn.sequence_number,
-                            )
-                        })
-                        .collect();
-
-                    panic!(
-                        "Request doesn't match. Actual: {:?} Expected: {:?}",
-                        txns, expected_txns
-                    );
-                }
-                request_id
-            },
+            } => (request_id, transactions),
+            MempoolSyncMsg::BroadcastTransactionsRequestWithPrivacy {
+                request_id,
+                transactions,
+            } => (
+                request_id,
+                transactions.into_iter().map(|(txn, _)| txn).collect(),
+            ),
             MempoolSyncMsg::BroadcastTransactionsResponse { .. } => {
                 panic!("We aren't supposed to be getting as response here");
             },
         };
+        if !block_only_contains_transactions(&transactions, expected_txns) {
+            let txns: Vec<_> = transactions
+                .iter()
+                .map(|txn| (txn.sender(), txn.sequence_number()))
+                .collect();
+            let expected_txns: Vec<_> = expected_txns
+                .iter()
+                .map(|txn| {
+                    (
+                        TestTransaction::get_address(txn.address),
+                        txn.sequence_number,
+                    )
+                })
+                .collect();
+
+            panic!(
+                "Request doesn't match. Actual: {:?} Expected: {:?}",
+                txns, expected_txns
+            );
+        }
         let response = MempoolSyncMsg::BroadcastTransactionsResponse {
             request_id,
             retry,
@@ -12,7 +12,8 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
+        DiscoveryMethod, NetworkConfig, OutboundQueuesConfig, Peer, PeerExchangeConfig, PeerRole,
+        PeerSet, RateLimitConfig, RoleType, CONNECTION_BACKOFF_BASE,
         CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS, MAX_CONNECTION_DELAY_MS,
         MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
         NETWORK_CHANNEL_SIZE,
@@ -40,6 +41,7 @@ use aptos_network::{
             NetworkApplicationConfig, NetworkClientConfig, NetworkServiceConfig, NewNetworkEvents,
             NewNetworkSender,
         },
+        peer_exchange::{self, builder::PeerExchangeBuilder},
     },
 };
 use aptos_network_discovery::DiscoveryChangeListener;
@@ -68,6 +70,7 @@ pub struct NetworkBuilder {
     discovery_listeners: Option<Vec<DiscoveryChangeListener<DbBackedOnChainConfig>>>,
     connectivity_manager_builder: Option<ConnectivityManagerBuilder>,
     health_checker_builder: Option<HealthCheckerBuilder>,
+    peer_exchange_builder: Option<PeerExchangeBuilder>,
     peer_manager_builder: PeerManagerBuilder,
     peers_and_metadata: Arc<PeersAndMetadata>,
 }
@@ -90,6 +93,9 @@ impl NetworkBuilder {
         max_concurrent_network_reqs: usize,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        outbound_queues_config: OutboundQueuesConfig,
+        inbound_rate_limit_config: Option<RateLimitConfig>,
+        outbound_rate_limit_config: Option<RateLimitConfig>,
     ) -> Self {
         // A network cannot exist without a PeerManager
         // TODO:  construct this in create and pass it to new() as a parameter. The complication is manual construction of NetworkBuilder in various tests.
@@ -107,6 +113,9 @@ impl NetworkBuilder {
             enable_proxy_protocol,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            outbound_queues_config,
+            inbound_rate_limit_config,
+            outbound_rate_limit_config,
         );
 
         NetworkBuilder {
@@ -117,6 +126,7 @@ impl NetworkBuilder {
             discovery_listeners: None,
             connectivity_manager_builder: None,
             health_checker_builder: None,
+            peer_exchange_builder: None,
             peer_manager_builder,
             peers_and_metadata,
         }
@@ -147,6 +157,9 @@ impl NetworkBuilder {
             MAX_CONCURRENT_NETWORK_REQS,
             MAX_INBOUND_CONNECTIONS,
             TCPBufferCfg::default(),
+            OutboundQueuesConfig::default(),
+            None, /* No inbound rate limit */
+            None, /* No outbound rate limit */
         );
 
         builder.add_connectivity_manager(
@@ -203,6 +216,9 @@ impl NetworkBuilder {
                 config.outbound_rx_buffer_size_bytes,
                 config.outbound_tx_buffer_size_bytes,
             ),
+            config.outbound_queues.clone(),
+            config.inbound_rate_limit_config,
+            config.outbound_rate_limit_config,
         );
 
         network_builder.add_connection_monitoring(
@@ -216,7 +232,7 @@ impl NetworkBuilder {
         let seeds = merge_seeds(config);
 
         network_builder.add_connectivity_manager(
-            seeds,
+            seeds.clone(),
             peers_and_metadata,
             config.max_outbound_connections,
             config.connection_backoff_base,
@@ -243,6 +259,7 @@ impl NetworkBuilder {
             network_builder.add_discovery_change_listener(
                 discovery_method,
                 pubkey,
+                &seeds,
                 reconfig_listener,
             );
         }
@@ -260,6 +277,14 @@ impl NetworkBuilder {
             network_builder.discovery_listeners.as_ref().unwrap().len()
         );
 
+        // Public fullnodes also discover peers through their peers
+        if config.network_id.is_public_network() && config.peer_exchange.enabled {
+            network_builder.add_peer_exchange(
+                config.peer_exchange,
+                config.max_parallel_deserialization_tasks,
+            );
+        }
+
         network_builder
     }
 
@@ -301,6 +326,14 @@ impl NetworkBuilder {
             );
         }
 
+        if let Some(peer_exchange_builder) = self.peer_exchange_builder.as_mut() {
+            peer_exchange_builder.start(executor);
+            debug!(
+                NetworkSchema::new(&self.network_context),
+                "{} Started peer exchange", self.network_context
+            );
+        }
+
         if let Some(discovery_listeners) = self.discovery_listeners.take() {
             discovery_listeners
                 .into_iter()
@@ -372,6 +405,7 @@ impl NetworkBuilder {
         &mut self,
         discovery_method: &DiscoveryMethod,
         pubkey: PublicKey,
+        seeds: &PeerSet,
         reconfig_events: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
     ) {
         let conn_mgr_reqs_tx = self
@@ -403,6 +437,13 @@ impl NetworkBuilder {
                 Duration::from_secs(rest_discovery.interval_secs),
                 self.time_service.clone(),
             ),
+            DiscoveryMethod::Dns(dns_discovery) => DiscoveryChangeListener::dns(
+                self.network_context,
+                conn_mgr_reqs_tx,
+                seeds.clone(),
+                Duration::from_secs(dns_discovery.interval_secs),
+                self.time_service.clone(),
+            ),
             DiscoveryMethod::None => return,
         };
 
@@ -442,6 +483,35 @@ impl NetworkBuilder {
         self
     }
 
+    /// Add a PeerExchange to the network.
+    fn add_peer_exchange(
+        &mut self,
+        config: PeerExchangeConfig,
+        max_parallel_deserialization_tasks: Option<usize>,
+    ) -> &mut Self {
+        let conn_mgr_reqs_tx = self
+            .conn_mgr_reqs_tx()
+            .expect("ConnectivityManager must exist");
+        let (pe_network_tx, pe_network_rx) = self.add_client_and_service(
+            &peer_exchange::peer_exchange_network_config(),
+            max_parallel_deserialization_tasks,
+        );
+        self.peer_exchange_builder = Some(PeerExchangeBuilder::new(
+            self.network_context(),
+            self.time_service.clone(),
+            config,
+            pe_network_tx,
+            pe_network_rx,
+            self.peers_and_metadata.clone(),
+            conn_mgr_reqs_tx,
+        ));
+        debug!(
+            NetworkSchema::new(&self.network_context),
+            "{} Created peer exchange", self.network_context
+        );
+        self
+    }
+
     /// Register a new client and service application with the network. Return
     /// the client interface for sending messages and the service interface
     /// for handling network requests.
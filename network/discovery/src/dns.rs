@@ -0,0 +1,197 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiscoveryError;
+use aptos_config::config::{Peer, PeerSet};
+use aptos_logger::prelude::*;
+use aptos_time_service::{Interval, TimeService, TimeServiceTrait};
+use aptos_types::network_address::{
+    parse_dns_quic, parse_dns_tcp, IpFilter, NetworkAddress, Protocol,
+};
+use futures::{future::BoxFuture, Future, FutureExt, Stream};
+use std::{
+    net::IpAddr,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::net::lookup_host;
+
+/// A discovery stream that periodically re-resolves the DNS names in the
+/// addresses of the seeds. Each resolved IP address becomes an address of the
+/// seed, so that changes to the DNS records reset the dialing of the seeds.
+pub struct DnsStream {
+    seeds: PeerSet,
+    interval: Pin<Box<Interval>>,
+    pending_resolution: Option<BoxFuture<'static, PeerSet>>,
+}
+
+impl DnsStream {
+    pub(crate) fn new(
+        seeds: PeerSet,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        DnsStream {
+            seeds,
+            interval: Box::pin(time_service.interval(interval_duration)),
+            pending_resolution: None,
+        }
+    }
+}
+
+impl Stream for DnsStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Wait for delay, and start resolving the seeds at the interval
+        if self.pending_resolution.is_none() {
+            futures::ready!(self.interval.as_mut().poll_next(cx));
+            let seeds = self.seeds.clone();
+            self.pending_resolution = Some(resolve_seeds(seeds).boxed());
+        }
+
+        // Wait for the resolution to complete
+        let resolved_seeds = futures::ready!(self
+            .pending_resolution
+            .as_mut()
+            .expect("The resolution must be pending!")
+            .as_mut()
+            .poll(cx));
+        self.pending_resolution = None;
+        Poll::Ready(Some(Ok(resolved_seeds)))
+    }
+}
+
+/// Resolves the addresses with DNS names of each seed. Seeds without any
+/// resolved addresses are left out.
+async fn resolve_seeds(seeds: PeerSet) -> PeerSet {
+    let mut resolved_seeds = PeerSet::new();
+    for (peer_id, seed) in seeds {
+        let mut resolved_addresses = vec![];
+        for address in seed.addresses.iter() {
+            resolved_addresses.extend(resolve_address(address).await);
+        }
+        if !resolved_addresses.is_empty() {
+            resolved_seeds.insert(peer_id, Peer::new(resolved_addresses, seed.keys, seed.role));
+        }
+    }
+    resolved_seeds
+}
+
+/// Resolves the DNS name of the address (if any) to an address per resolved
+/// IP address, e.g., `/dns/example.com/tcp/6180/<suffix>` might resolve to
+/// `/ip4/1.2.3.4/tcp/6180/<suffix>`. Failures are logged, and result in no
+/// addresses.
+async fn resolve_address(address: &NetworkAddress) -> Vec<NetworkAddress> {
+    let protocols = address.as_slice();
+    let ((ip_filter, dns_name, port), suffix, is_quic) = match parse_dns_tcp(protocols) {
+        Some(parsed) => (parsed.0, parsed.1, false),
+        None => match parse_dns_quic(protocols) {
+            Some(parsed) => (parsed.0, parsed.1, true),
+            None => return vec![],
+        },
+    };
+
+    let socket_addrs = match lookup_host((dns_name.as_ref(), port)).await {
+        Ok(socket_addrs) => socket_addrs,
+        Err(error) => {
+            warn!(
+                "Failed to resolve the DNS name of seed address {}: {}",
+                address, error
+            );
+            return vec![];
+        },
+    };
+    socket_addrs
+        .filter(|socket_addr| ip_filter.matches(socket_addr.ip()))
+        .filter_map(|socket_addr| {
+            let ip_protocol = match socket_addr.ip() {
+                IpAddr::V4(ip) => Protocol::Ip4(ip),
+                IpAddr::V6(ip) => Protocol::Ip6(ip),
+            };
+            let port_protocol = if is_quic {
+                Protocol::Quic(port)
+            } else {
+                Protocol::Tcp(port)
+            };
+            let protocols = [ip_protocol, port_protocol]
+                .into_iter()
+                .chain(suffix.iter().cloned())
+                .collect();
+            NetworkAddress::from_protocols(protocols).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::config::PeerRole;
+    use aptos_types::PeerId;
+    use futures::StreamExt;
+    use std::str::FromStr;
+
+    const NOISE_SUFFIX: &str =
+        "noise-ik/080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120/handshake/0";
+
+    #[tokio::test]
+    async fn test_resolve_address() {
+        // Addresses without DNS names aren't resolved
+        let address =
+            NetworkAddress::from_str(&format!("/ip4/1.2.3.4/tcp/6180/{}", NOISE_SUFFIX)).unwrap();
+        assert!(resolve_address(&address).await.is_empty());
+
+        // DNS names are resolved to addresses with the same suffix
+        let address =
+            NetworkAddress::from_str(&format!("/dns4/localhost/tcp/6180/{}", NOISE_SUFFIX))
+                .unwrap();
+        let expected_address =
+            NetworkAddress::from_str(&format!("/ip4/127.0.0.1/tcp/6180/{}", NOISE_SUFFIX)).unwrap();
+        assert_eq!(resolve_address(&address).await, vec![expected_address]);
+
+        // Unresolvable DNS names result in no addresses
+        let address = NetworkAddress::from_str(&format!(
+            "/dns/unresolvable.invalid/tcp/6180/{}",
+            NOISE_SUFFIX
+        ))
+        .unwrap();
+        assert!(resolve_address(&address).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dns_stream() {
+        // Create seeds with and without DNS names
+        let dns_address =
+            NetworkAddress::from_str(&format!("/dns4/localhost/quic/6180/{}", NOISE_SUFFIX))
+                .unwrap();
+        let ip_address =
+            NetworkAddress::from_str(&format!("/ip4/1.2.3.4/tcp/6180/{}", NOISE_SUFFIX)).unwrap();
+        let dns_seed = Peer::from_addrs(PeerRole::Upstream, vec![dns_address]);
+        let (dns_peer_id, ip_peer_id) = (PeerId::random(), PeerId::random());
+        let seeds: PeerSet = [
+            (dns_peer_id, dns_seed.clone()),
+            (
+                ip_peer_id,
+                Peer::from_addrs(PeerRole::Upstream, vec![ip_address]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        // Verify that only the seed with a DNS name is discovered, at its resolved address
+        let mut stream = DnsStream::new(seeds, Duration::from_millis(1), TimeService::real());
+        let resolved_seeds = stream.next().await.unwrap().unwrap();
+        let resolved_address =
+            NetworkAddress::from_str(&format!("/ip4/127.0.0.1/quic/6180/{}", NOISE_SUFFIX))
+                .unwrap();
+        let expected_seeds: PeerSet = [(
+            dns_peer_id,
+            Peer::new(vec![resolved_address], dns_seed.keys, PeerRole::Upstream),
+        )]
+        .into_iter()
+        .collect();
+        assert_eq!(resolved_seeds, expected_seeds);
+        assert!(!resolved_seeds.contains_key(&ip_peer_id));
+    }
+}
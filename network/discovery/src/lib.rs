@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    counters::DISCOVERY_COUNTS, file::FileStream, rest::RestStream,
+    counters::DISCOVERY_COUNTS, dns::DnsStream, file::FileStream, rest::RestStream,
     validator_set::ValidatorSetStream,
 };
 use aptos_config::{config::PeerSet, network_id::NetworkContext};
@@ -27,6 +27,7 @@ use std::{
 use tokio::runtime::Handle;
 
 mod counters;
+mod dns;
 mod file;
 mod rest;
 mod validator_set;
@@ -50,6 +51,7 @@ enum DiscoveryChangeStream<P: OnChainConfigProvider> {
     ValidatorSet(ValidatorSetStream<P>),
     File(FileStream),
     Rest(RestStream),
+    Dns(DnsStream),
 }
 
 impl<P: OnChainConfigProvider> Stream for DiscoveryChangeStream<P> {
@@ -60,6 +62,7 @@ impl<P: OnChainConfigProvider> Stream for DiscoveryChangeStream<P> {
             Self::ValidatorSet(stream) => Pin::new(stream).poll_next(cx),
             Self::File(stream) => Pin::new(stream).poll_next(cx),
             Self::Rest(stream) => Pin::new(stream).poll_next(cx),
+            Self::Dns(stream) => Pin::new(stream).poll_next(cx),
         }
     }
 }
@@ -125,6 +128,23 @@ impl<P: OnChainConfigProvider> DiscoveryChangeListener<P> {
         }
     }
 
+    pub fn dns(
+        network_context: NetworkContext,
+        update_channel: aptos_channels::Sender<ConnectivityRequest>,
+        seeds: PeerSet,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        let source_stream =
+            DiscoveryChangeStream::Dns(DnsStream::new(seeds, interval_duration, time_service));
+        DiscoveryChangeListener {
+            discovery_source: DiscoverySource::Dns,
+            network_context,
+            update_channel,
+            source_stream,
+        }
+    }
+
     pub fn start(self, executor: &Handle) {
         spawn_named!("DiscoveryChangeListener", executor, Box::pin(self).run());
     }
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    application::{error::Error, storage::PeersAndMetadata},
+    application::{error::Error, reputation::PeerMisbehavior, storage::PeersAndMetadata},
     protocols::{
         network::{Message, NetworkEvents, NetworkSender},
         wire::handshake::v1::{ProtocolId, ProtocolIdSet},
@@ -48,6 +48,14 @@ pub trait NetworkClientInterface<Message: NetworkMessageTrait>: Clone + Send + S
     /// Returns a handle to the global `PeersAndMetadata` container
     fn get_peers_and_metadata(&self) -> Arc<PeersAndMetadata>;
 
+    /// Reports the misbehavior of the specified peer. If this gets the
+    /// peer banned, the peer is also disconnected.
+    fn report_peer_misbehavior(
+        &self,
+        _peer: PeerNetworkId,
+        _misbehavior: PeerMisbehavior,
+    ) -> Result<(), Error>;
+
     /// Sends the given message to the specified peer. Note: this
     /// method does not guarantee message delivery or handle responses.
     fn send_to_peer(&self, _message: Message, _peer: PeerNetworkId) -> Result<(), Error>;
@@ -163,6 +171,21 @@ impl<Message: NetworkMessageTrait> NetworkClientInterface<Message> for NetworkCl
         self.peers_and_metadata.clone()
     }
 
+    fn report_peer_misbehavior(
+        &self,
+        peer: PeerNetworkId,
+        misbehavior: PeerMisbehavior,
+    ) -> Result<(), Error> {
+        if self
+            .peers_and_metadata
+            .report_peer_misbehavior(peer, misbehavior)?
+        {
+            let network_sender = self.get_sender_for_network_id(&peer.network_id())?;
+            network_sender.request_disconnect_peer(peer.peer_id())?;
+        }
+        Ok(())
+    }
+
     fn send_to_peer(&self, message: Message, peer: PeerNetworkId) -> Result<(), Error> {
         let network_sender = self.get_sender_for_network_id(&peer.network_id())?;
         let direct_send_protocol_id = self
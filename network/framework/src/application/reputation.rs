@@ -0,0 +1,587 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::PeerReputationConfig;
+use aptos_logger::prelude::*;
+use aptos_types::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// The kinds of misbehavior that applications can report about peers
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PeerMisbehavior {
+    /// The peer sent a message that can't be handled (e.g., of the wrong type)
+    UnexpectedMessage,
+    /// The peer sent invalid data (e.g., that fails proof verification)
+    InvalidData,
+}
+
+impl PeerMisbehavior {
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            PeerMisbehavior::UnexpectedMessage => "unexpected_message",
+            PeerMisbehavior::InvalidData => "invalid_data",
+        }
+    }
+
+    fn get_penalty(&self, config: &PeerReputationConfig) -> f64 {
+        match self {
+            PeerMisbehavior::UnexpectedMessage => config.unexpected_message_penalty as f64,
+            PeerMisbehavior::InvalidData => config.invalid_data_penalty as f64,
+        }
+    }
+}
+
+/// The penalty score of a peer, as of the given time
+#[derive(Clone, Copy, Debug)]
+struct PeerScore {
+    score: f64,
+    updated_at: Duration,
+}
+
+/// Scores below this are considered neutral, and are pruned
+const NEUTRAL_SCORE: f64 = 1.0;
+
+/// The ban of a peer. Peers are also banned by the IP address (or its prefix)
+/// they misbehaved from (if known), as untrusted peers can freely pick new ids.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Ban {
+    banned_until: Duration,
+    ip_prefix: Option<IpPrefix>,
+}
+
+/// The prefix of an IP address, i.e., the address with all but the first
+/// `len` bits cleared. Prefixes of the full length are exact addresses.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct IpPrefix {
+    addr: IpAddr,
+    len: u8,
+}
+
+impl IpPrefix {
+    /// Returns the prefix of the given IP address, of the length configured
+    /// for its address family.
+    fn new(ip_addr: IpAddr, config: &PeerReputationConfig) -> Self {
+        let len = match ip_addr {
+            IpAddr::V4(_) => config.ipv4_ban_prefix_len,
+            IpAddr::V6(_) => config.ipv6_ban_prefix_len,
+        };
+        Self {
+            addr: mask_ip_addr(ip_addr, len),
+            len,
+        }
+    }
+
+    /// Returns true iff the given IP address has this prefix
+    fn contains(&self, ip_addr: IpAddr) -> bool {
+        mask_ip_addr(ip_addr, self.len) == self.addr
+    }
+}
+
+/// A ban, as persisted across restarts
+#[derive(Debug, Deserialize, Serialize)]
+struct PersistedBan {
+    peer_id: PeerId,
+    banned_until_secs: u64,
+    #[serde(default)]
+    ip_prefix: Option<IpPrefix>,
+}
+
+/// Writes the bans to the bans file on a dedicated thread, so that the file
+/// system isn't accessed while the reputations are locked. Only the latest
+/// bans are written if several are pending.
+#[derive(Debug)]
+struct BansWriter {
+    sender: Option<mpsc::Sender<Vec<PersistedBan>>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl BansWriter {
+    fn new(bans_file: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel::<Vec<PersistedBan>>();
+        let handle = thread::Builder::new()
+            .name("peer-bans-writer".into())
+            .spawn(move || {
+                while let Ok(persisted_bans) = receiver.recv() {
+                    let persisted_bans = receiver.try_iter().last().unwrap_or(persisted_bans);
+                    write_bans(&bans_file, &persisted_bans);
+                }
+            })
+            .expect("Failed to spawn the peer bans writer!");
+        Self {
+            sender: Some(sender),
+            handle: Some(handle),
+        }
+    }
+
+    fn write(&self, persisted_bans: Vec<PersistedBan>) {
+        if let Some(sender) = &self.sender {
+            if sender.send(persisted_bans).is_err() {
+                warn!("Failed to persist the peer bans! The bans writer has stopped.");
+            }
+        }
+    }
+}
+
+impl Drop for BansWriter {
+    /// Waits for the pending bans to be written, so that they aren't lost
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Tracks the reputation of the peers of a single network. The scores of
+/// peers decay exponentially, and peers are banned once their score reaches
+/// the ban threshold. All times are durations since the unix epoch, so that
+/// bans can be persisted across restarts.
+#[derive(Debug)]
+pub struct PeerReputations {
+    config: PeerReputationConfig,
+    scores: HashMap<PeerId, PeerScore>,
+    bans: HashMap<PeerId, Ban>,
+    bans_writer: Option<BansWriter>, // The writer of the bans file (if any)
+}
+
+impl PeerReputations {
+    /// Creates the peer reputations, loading any bans persisted in the bans file
+    pub fn new(config: PeerReputationConfig, bans_file: Option<PathBuf>) -> Self {
+        let bans = match &bans_file {
+            Some(bans_file) if bans_file.exists() => load_bans(bans_file),
+            _ => HashMap::new(),
+        };
+        Self {
+            config,
+            scores: HashMap::new(),
+            bans,
+            bans_writer: bans_file.map(BansWriter::new),
+        }
+    }
+
+    /// Adds the penalty of the misbehavior to the peer's score. Returns true
+    /// iff the peer is now banned (because its score reached the threshold).
+    /// If the remote IP address of the peer is known, it's banned too (or its
+    /// prefix, if configured).
+    pub fn report_misbehavior(
+        &mut self,
+        peer_id: PeerId,
+        remote_ip: Option<IpAddr>,
+        misbehavior: PeerMisbehavior,
+        can_be_banned: bool,
+        now: Duration,
+    ) -> bool {
+        // Update the score of the peer, and prune the scores that decayed to neutral
+        let penalty = misbehavior.get_penalty(&self.config);
+        let score = self.get_score(&peer_id, now) + penalty;
+        self.scores.insert(peer_id, PeerScore {
+            score,
+            updated_at: now,
+        });
+        self.prune_scores(now);
+
+        // Ban the peer if the score reached the threshold
+        if !self.config.enabled
+            || !can_be_banned
+            || score < self.config.ban_threshold as f64
+            || self.is_banned(&peer_id, now)
+        {
+            return false;
+        }
+        self.scores.remove(&peer_id);
+        self.bans.retain(|_, ban| ban.banned_until > now);
+        self.bans.insert(peer_id, Ban {
+            banned_until: now + Duration::from_secs(self.config.ban_duration_secs),
+            ip_prefix: remote_ip.map(|remote_ip| IpPrefix::new(remote_ip, &self.config)),
+        });
+        self.persist_bans();
+        true
+    }
+
+    /// Returns true iff the peer is currently banned
+    pub fn is_banned(&self, peer_id: &PeerId, now: Duration) -> bool {
+        self.config.enabled
+            && self
+                .bans
+                .get(peer_id)
+                .map_or(false, |ban| ban.banned_until > now)
+    }
+
+    /// Returns true iff the given IP address is currently banned (i.e., it has
+    /// the prefix of an address a banned peer misbehaved from).
+    pub fn is_address_banned(&self, ip_addr: IpAddr, now: Duration) -> bool {
+        self.config.enabled
+            && self.bans.values().any(|ban| {
+                ban.banned_until > now
+                    && ban
+                        .ip_prefix
+                        .map_or(false, |ip_prefix| ip_prefix.contains(ip_addr))
+            })
+    }
+
+    /// Returns the current (decayed) score of the peer
+    pub fn get_score(&self, peer_id: &PeerId, now: Duration) -> f64 {
+        self.scores.get(peer_id).map_or(0.0, |peer_score| {
+            let elapsed_secs = now.saturating_sub(peer_score.updated_at).as_secs_f64();
+            let half_lives = elapsed_secs / self.config.score_half_life_secs as f64;
+            peer_score.score * 0.5f64.powf(half_lives)
+        })
+    }
+
+    /// Removes the scores that have decayed to neutral, so that the scores
+    /// of peers that misbehaved once don't accumulate forever.
+    fn prune_scores(&mut self, now: Duration) {
+        let half_life_secs = self.config.score_half_life_secs as f64;
+        self.scores.retain(|_, peer_score| {
+            let elapsed_secs = now.saturating_sub(peer_score.updated_at).as_secs_f64();
+            peer_score.score * 0.5f64.powf(elapsed_secs / half_life_secs) >= NEUTRAL_SCORE
+        });
+    }
+
+    /// Persists the bans in the bans file (if any). The file is written by
+    /// the bans writer, off the caller's thread.
+    fn persist_bans(&self) {
+        let bans_writer = match &self.bans_writer {
+            Some(bans_writer) => bans_writer,
+            None => return,
+        };
+        let persisted_bans = self
+            .bans
+            .iter()
+            .map(|(peer_id, ban)| PersistedBan {
+                peer_id: *peer_id,
+                banned_until_secs: ban.banned_until.as_secs(),
+                ip_prefix: ban.ip_prefix,
+            })
+            .collect();
+        bans_writer.write(persisted_bans);
+    }
+}
+
+/// Clears all but the first `len` bits of the given IP address
+fn mask_ip_addr(ip_addr: IpAddr, len: u8) -> IpAddr {
+    match ip_addr {
+        IpAddr::V4(ip_addr) => {
+            let mask = u32::MAX
+                .checked_shl(32u32.saturating_sub(len.into()))
+                .unwrap_or(0);
+            IpAddr::from((u32::from(ip_addr) & mask).to_be_bytes())
+        },
+        IpAddr::V6(ip_addr) => {
+            let mask = u128::MAX
+                .checked_shl(128u32.saturating_sub(len.into()))
+                .unwrap_or(0);
+            IpAddr::from((u128::from(ip_addr) & mask).to_be_bytes())
+        },
+    }
+}
+
+/// Writes the bans to the given file. Failures are logged, as they only
+/// affect the bans after a restart.
+fn write_bans(bans_file: &Path, persisted_bans: &[PersistedBan]) {
+    // Write to a temporary file first, so that the bans file is never partially written
+    let temp_file = bans_file.with_extension("tmp");
+    let result = serde_json::to_vec(persisted_bans)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| fs::write(&temp_file, bytes).map_err(|error| error.to_string()))
+        .and_then(|_| fs::rename(&temp_file, bans_file).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        warn!(
+            "Failed to persist the peer bans to {:?}! Error: {}",
+            bans_file, error
+        );
+    }
+}
+
+/// Loads the bans persisted in the given file. Failures are logged, and
+/// result in no bans.
+fn load_bans(bans_file: &Path) -> HashMap<PeerId, Ban> {
+    let result = fs::read(bans_file)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| {
+            serde_json::from_slice::<Vec<PersistedBan>>(&bytes).map_err(|error| error.to_string())
+        });
+    match result {
+        Ok(persisted_bans) => persisted_bans
+            .into_iter()
+            .map(|ban| {
+                (ban.peer_id, Ban {
+                    banned_until: Duration::from_secs(ban.banned_until_secs),
+                    ip_prefix: ban.ip_prefix,
+                })
+            })
+            .collect(),
+        Err(error) => {
+            warn!(
+                "Failed to load the peer bans from {:?}! Error: {}",
+                bans_file, error
+            );
+            HashMap::new()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_temppath::TempPath;
+
+    fn config() -> PeerReputationConfig {
+        PeerReputationConfig {
+            enabled: true,
+            ban_threshold: 100,
+            ban_duration_secs: 60,
+            score_half_life_secs: 10,
+            unexpected_message_penalty: 10,
+            invalid_data_penalty: 50,
+            ipv4_ban_prefix_len: 32,
+            ipv6_ban_prefix_len: 128,
+        }
+    }
+
+    /// Bans a peer from each of the given IP addresses
+    fn ban_peers(reputations: &mut PeerReputations, remote_ips: &[&str], now: Duration) {
+        for remote_ip in remote_ips {
+            let remote_ip = Some(remote_ip.parse().unwrap());
+            let peer_id = PeerId::random();
+            for _ in 0..2 {
+                reputations.report_misbehavior(
+                    peer_id,
+                    remote_ip,
+                    PeerMisbehavior::InvalidData,
+                    true,
+                    now,
+                );
+            }
+            assert!(reputations.is_banned(&peer_id, now));
+        }
+    }
+
+    #[test]
+    fn test_scores_decay() {
+        let mut reputations = PeerReputations::new(config(), None);
+        let peer_id = PeerId::random();
+        let now = Duration::from_secs(1_000);
+
+        // Report invalid data and verify the score decays by half every half life
+        assert!(!reputations.report_misbehavior(
+            peer_id,
+            None,
+            PeerMisbehavior::InvalidData,
+            true,
+            now
+        ));
+        assert_eq!(reputations.get_score(&peer_id, now), 50.0);
+        assert_eq!(
+            reputations.get_score(&peer_id, now + Duration::from_secs(10)),
+            25.0
+        );
+        assert_eq!(
+            reputations.get_score(&peer_id, now + Duration::from_secs(20)),
+            12.5
+        );
+
+        // Scores of other peers are unaffected
+        assert_eq!(reputations.get_score(&PeerId::random(), now), 0.0);
+    }
+
+    #[test]
+    fn test_peers_are_banned_at_the_threshold() {
+        let mut reputations = PeerReputations::new(config(), None);
+        let peer_id = PeerId::random();
+        let now = Duration::from_secs(1_000);
+
+        // Spread out reports don't get the peer banned
+        assert!(!reputations.report_misbehavior(
+            peer_id,
+            None,
+            PeerMisbehavior::InvalidData,
+            true,
+            now
+        ));
+        let later = now + Duration::from_secs(10);
+        assert!(!reputations.report_misbehavior(
+            peer_id,
+            None,
+            PeerMisbehavior::InvalidData,
+            true,
+            later
+        ));
+        assert!(!reputations.is_banned(&peer_id, later));
+
+        // But reaching the threshold does
+        assert!(reputations.report_misbehavior(
+            peer_id,
+            None,
+            PeerMisbehavior::InvalidData,
+            true,
+            later
+        ));
+        assert!(reputations.is_banned(&peer_id, later));
+
+        // The ban expires after the ban duration, with a clean score
+        let expiry = later + Duration::from_secs(60);
+        assert!(reputations.is_banned(&peer_id, expiry - Duration::from_secs(1)));
+        assert!(!reputations.is_banned(&peer_id, expiry));
+        assert_eq!(reputations.get_score(&peer_id, expiry), 0.0);
+    }
+
+    #[test]
+    fn test_peers_that_cant_be_banned() {
+        let disabled = PeerReputationConfig {
+            enabled: false,
+            ..config()
+        };
+        for (config, can_be_banned) in [(disabled, true), (config(), false)] {
+            let mut reputations = PeerReputations::new(config, None);
+            let peer_id = PeerId::random();
+            let now = Duration::from_secs(1_000);
+            for _ in 0..10 {
+                assert!(!reputations.report_misbehavior(
+                    peer_id,
+                    None,
+                    PeerMisbehavior::InvalidData,
+                    can_be_banned,
+                    now
+                ));
+            }
+            assert!(!reputations.is_banned(&peer_id, now));
+        }
+    }
+
+    #[test]
+    fn test_scores_are_pruned() {
+        let mut reputations = PeerReputations::new(config(), None);
+        let peer_id = PeerId::random();
+        let now = Duration::from_secs(1_000);
+
+        // Report misbehavior and verify the score is kept until it decays to neutral
+        reputations.report_misbehavior(
+            peer_id,
+            None,
+            PeerMisbehavior::UnexpectedMessage,
+            true,
+            now,
+        );
+        let other_peer_id = PeerId::random();
+        let later = now + Duration::from_secs(30);
+        reputations.report_misbehavior(
+            other_peer_id,
+            None,
+            PeerMisbehavior::UnexpectedMessage,
+            true,
+            later,
+        );
+        assert_eq!(reputations.scores.len(), 2);
+
+        // Once it has decayed to neutral, the score is pruned on the next report
+        let decayed = now + Duration::from_secs(40);
+        reputations.report_misbehavior(
+            other_peer_id,
+            None,
+            PeerMisbehavior::UnexpectedMessage,
+            true,
+            decayed,
+        );
+        assert_eq!(reputations.scores.len(), 1);
+        assert_eq!(reputations.get_score(&peer_id, decayed), 0.0);
+    }
+
+    #[test]
+    fn test_ip_addresses_are_banned() {
+        let mut reputations = PeerReputations::new(config(), None);
+        let now = Duration::from_secs(1_000);
+
+        // Ban a peer from an IPv4 and a peer from an IPv6 address
+        ban_peers(&mut reputations, &["1.2.3.4", "2001:db8:1:2:3:4:5:6"], now);
+
+        // Verify only the exact addresses are banned
+        for (ip_addr, banned) in [
+            ("1.2.3.4", true),
+            ("1.2.3.5", false),
+            ("2001:db8:1:2:3:4:5:6", true),
+            ("2001:db8:1:2:3:4:5:7", false),
+        ] {
+            let ip_addr = ip_addr.parse().unwrap();
+            assert_eq!(reputations.is_address_banned(ip_addr, now), banned);
+        }
+
+        // The address bans expire with the peer bans
+        let expiry = now + Duration::from_secs(60);
+        assert!(!reputations.is_address_banned("1.2.3.4".parse().unwrap(), expiry));
+    }
+
+    #[test]
+    fn test_ip_prefixes_are_banned() {
+        let config = PeerReputationConfig {
+            ipv4_ban_prefix_len: 24,
+            ipv6_ban_prefix_len: 64,
+            ..config()
+        };
+        let mut reputations = PeerReputations::new(config, None);
+        let now = Duration::from_secs(1_000);
+
+        // Ban a peer from an IPv4 and a peer from an IPv6 address
+        ban_peers(&mut reputations, &["1.2.3.4", "2001:db8:1:2:3:4:5:6"], now);
+
+        // Verify addresses in the same /24 and /64 are banned, but not others
+        for (ip_addr, banned) in [
+            ("1.2.3.4", true),
+            ("1.2.3.200", true),
+            ("1.2.4.4", false),
+            ("2001:db8:1:2:ffff::1", true),
+            ("2001:db8:1:3:3:4:5:6", false),
+        ] {
+            let ip_addr = ip_addr.parse().unwrap();
+            assert_eq!(reputations.is_address_banned(ip_addr, now), banned);
+        }
+
+        // The prefix bans expire with the peer bans
+        let expiry = now + Duration::from_secs(60);
+        assert!(!reputations.is_address_banned("1.2.3.4".parse().unwrap(), expiry));
+    }
+
+    #[test]
+    fn test_bans_are_persisted() {
+        let bans_dir = TempPath::new();
+        bans_dir.create_as_dir().unwrap();
+        let bans_file = bans_dir.path().join("peer_bans.json");
+
+        // Ban a peer
+        let mut reputations = PeerReputations::new(config(), Some(bans_file.clone()));
+        let peer_id = PeerId::random();
+        let now = Duration::from_secs(1_000);
+        let remote_ip = "1.2.3.4".parse().unwrap();
+        for _ in 0..2 {
+            reputations.report_misbehavior(
+                peer_id,
+                Some(remote_ip),
+                PeerMisbehavior::InvalidData,
+                true,
+                now,
+            );
+        }
+        assert!(reputations.is_banned(&peer_id, now));
+
+        // Verify the ban is loaded on restart (once the pending bans are written)
+        drop(reputations);
+        let reputations = PeerReputations::new(config(), Some(bans_file.clone()));
+        assert!(reputations.is_banned(&peer_id, now));
+        assert!(reputations.is_address_banned(remote_ip, now));
+        assert!(!reputations.is_banned(&peer_id, now + Duration::from_secs(60)));
+
+        // Verify an invalid bans file results in no bans
+        fs::write(&bans_file, b"invalid").unwrap();
+        let reputations = PeerReputations::new(config(), Some(bans_file));
+        assert!(!reputations.is_banned(&peer_id, now));
+    }
+}
@@ -6,19 +6,24 @@ use crate::{
     application::{
         error::Error,
         metadata::{ConnectionState, PeerMetadata},
+        reputation::{PeerMisbehavior, PeerReputations},
     },
+    counters,
     transport::{ConnectionId, ConnectionMetadata},
     ProtocolId,
 };
 use aptos_config::{
-    config::PeerSet,
+    config::{PeerReputationConfig, PeerRole, PeerSet},
     network_id::{NetworkId, PeerNetworkId},
 };
-use aptos_infallible::RwLock;
+use aptos_infallible::{duration_since_epoch, RwLock};
+use aptos_logger::prelude::*;
 use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
 use aptos_types::PeerId;
 use std::{
     collections::{hash_map::Entry, HashMap},
+    net::IpAddr,
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -30,6 +35,7 @@ use std::{
 pub struct PeersAndMetadata {
     peers_and_metadata: HashMap<NetworkId, RwLock<HashMap<PeerId, PeerMetadata>>>,
     trusted_peers: HashMap<NetworkId, Arc<RwLock<PeerSet>>>,
+    peer_reputations: HashMap<NetworkId, RwLock<PeerReputations>>,
 }
 
 impl PeersAndMetadata {
@@ -38,6 +44,7 @@ impl PeersAndMetadata {
         let mut peers_and_metadata = PeersAndMetadata {
             peers_and_metadata: HashMap::new(),
             trusted_peers: HashMap::new(),
+            peer_reputations: HashMap::new(),
         };
 
         // Initialize each network mapping and trusted peer set
@@ -49,6 +56,11 @@ impl PeersAndMetadata {
             peers_and_metadata
                 .trusted_peers
                 .insert(*network_id, Arc::new(RwLock::new(PeerSet::new())));
+
+            peers_and_metadata.peer_reputations.insert(
+                *network_id,
+                RwLock::new(PeerReputations::new(PeerReputationConfig::default(), None)),
+            );
         });
 
         Arc::new(peers_and_metadata)
@@ -140,6 +152,88 @@ impl PeersAndMetadata {
         })
     }
 
+    /// Returns true iff the specified peer is currently banned
+    pub fn is_peer_banned(&self, peer_network_id: PeerNetworkId) -> Result<bool, Error> {
+        let peer_reputations = self.get_peer_reputations(&peer_network_id.network_id())?;
+        Ok(peer_reputations
+            .read()
+            .is_banned(&peer_network_id.peer_id(), duration_since_epoch()))
+    }
+
+    /// Returns true iff the given IP address (or its prefix) is currently banned
+    /// on the given network (because a peer misbehaved from it).
+    pub fn is_address_banned(
+        &self,
+        network_id: &NetworkId,
+        ip_addr: IpAddr,
+    ) -> Result<bool, Error> {
+        let peer_reputations = self.get_peer_reputations(network_id)?;
+        Ok(peer_reputations
+            .read()
+            .is_address_banned(ip_addr, duration_since_epoch()))
+    }
+
+    /// Reports the misbehavior of the specified peer. Returns true iff the
+    /// peer is now banned (and should be disconnected). Trusted validators,
+    /// VFNs and preferred upstreams are never banned.
+    pub fn report_peer_misbehavior(
+        &self,
+        peer_network_id: PeerNetworkId,
+        misbehavior: PeerMisbehavior,
+    ) -> Result<bool, Error> {
+        let network_id = peer_network_id.network_id();
+        let peer_id = peer_network_id.peer_id();
+        let peer_reputations = self.get_peer_reputations(&network_id)?;
+        counters::peer_misbehavior_reports(&network_id, misbehavior.get_label()).inc();
+
+        // Only untrusted peers can be banned. All discovered peers are trusted
+        // (e.g., seeds and exchanged peers), so the role determines the trust.
+        let can_be_banned = self
+            .get_trusted_peers(&network_id)?
+            .read()
+            .get(&peer_id)
+            .map_or(true, |peer| {
+                !matches!(
+                    peer.role,
+                    PeerRole::Validator | PeerRole::ValidatorFullNode | PeerRole::PreferredUpstream
+                )
+            });
+
+        // Ban the address the peer is connected from too (if it's still connected)
+        let remote_ip = self
+            .get_metadata_for_peer(peer_network_id)
+            .ok()
+            .and_then(|metadata| metadata.connection_metadata.addr.find_ip_addr());
+        let banned = peer_reputations.write().report_misbehavior(
+            peer_id,
+            remote_ip,
+            misbehavior,
+            can_be_banned,
+            duration_since_epoch(),
+        );
+        if banned {
+            warn!(
+                "Banning peer {:?} for misbehavior: {:?}",
+                peer_network_id, misbehavior
+            );
+            counters::peer_bans(&network_id).inc();
+        }
+        Ok(banned)
+    }
+
+    /// Sets the reputation config of the given network. Bans are persisted in
+    /// the given file (if any), and the bans already persisted there are loaded.
+    pub fn set_peer_reputation_config(
+        &self,
+        network_id: &NetworkId,
+        config: PeerReputationConfig,
+        bans_file: Option<PathBuf>,
+    ) -> Result<(), Error> {
+        let peer_reputations = self.get_peer_reputations(network_id)?;
+        *peer_reputations.write() = PeerReputations::new(config, bans_file);
+        Ok(())
+    }
+
     /// Updates the connection metadata associated with the given peer.
     /// If no peer metadata exists, a new one is created.
     pub fn insert_connection_metadata(
@@ -251,6 +345,19 @@ impl PeersAndMetadata {
             ))
         })
     }
+
+    /// A helper method that returns the peer reputations for the specified network
+    fn get_peer_reputations(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<&RwLock<PeerReputations>, Error> {
+        self.peer_reputations.get(network_id).ok_or_else(|| {
+            Error::UnexpectedError(format!(
+                "No peer reputations were found for the given network: {:?}",
+                network_id
+            ))
+        })
+    }
 }
 
 /// A simple helper for returning a missing metadata error
@@ -7,6 +7,7 @@ use crate::{
         error::Error,
         interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
         metadata::{ConnectionState, PeerMetadata},
+        reputation::PeerMisbehavior,
         storage::PeersAndMetadata,
     },
     peer_manager::{
@@ -22,7 +23,7 @@ use crate::{
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{Peer, PeerRole},
+    config::{Peer, PeerReputationConfig, PeerRole},
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_types::PeerId;
@@ -279,6 +280,64 @@ fn test_peers_and_metadata_trusted_peers() {
     assert!(trusted_peers.read().is_empty());
 }
 
+#[test]
+fn test_peers_and_metadata_peer_misbehavior() {
+    // Create the peers and metadata container
+    let network_ids = vec![NetworkId::Validator, NetworkId::Public];
+    let peers_and_metadata = PeersAndMetadata::new(&network_ids);
+
+    // Verify that reporting misbehavior in a non-existent network returns an error
+    let invalid_peer = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+    peers_and_metadata
+        .report_peer_misbehavior(invalid_peer, PeerMisbehavior::InvalidData)
+        .unwrap_err();
+    peers_and_metadata.is_peer_banned(invalid_peer).unwrap_err();
+
+    // Add a trusted validator to the validator network
+    let validator_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let trusted_peers = peers_and_metadata
+        .get_trusted_peers(&NetworkId::Validator)
+        .unwrap();
+    trusted_peers.write().insert(
+        validator_peer.peer_id(),
+        Peer::new(vec![], HashSet::new(), PeerRole::Validator),
+    );
+
+    // Verify that an untrusted peer is banned once it reaches the threshold
+    let ban_threshold = PeerReputationConfig::default().ban_threshold;
+    let public_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let mut banned = false;
+    for _ in 0..ban_threshold {
+        assert!(!peers_and_metadata.is_peer_banned(public_peer).unwrap());
+        banned = peers_and_metadata
+            .report_peer_misbehavior(public_peer, PeerMisbehavior::InvalidData)
+            .unwrap();
+        if banned {
+            break;
+        }
+    }
+    assert!(banned);
+    assert!(peers_and_metadata.is_peer_banned(public_peer).unwrap());
+
+    // Verify that the trusted validator is never banned
+    for _ in 0..ban_threshold {
+        assert!(!peers_and_metadata
+            .report_peer_misbehavior(validator_peer, PeerMisbehavior::InvalidData)
+            .unwrap());
+    }
+    assert!(!peers_and_metadata.is_peer_banned(validator_peer).unwrap());
+
+    // Verify that disabling reputations lifts the ban
+    let disabled_config = PeerReputationConfig {
+        enabled: false,
+        ..Default::default()
+    };
+    peers_and_metadata
+        .set_peer_reputation_config(&NetworkId::Public, disabled_config, None)
+        .unwrap();
+    assert!(!peers_and_metadata.is_peer_banned(public_peer).unwrap());
+}
+
 #[test]
 fn test_network_client_available_peers() {
     // Create the peers and metadata container
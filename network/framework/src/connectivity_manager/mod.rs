@@ -36,7 +36,7 @@ use crate::{
 };
 use aptos_config::{
     config::{Peer, PeerRole, PeerSet},
-    network_id::NetworkContext,
+    network_id::{NetworkContext, PeerNetworkId},
 };
 use aptos_crypto::x25519;
 use aptos_infallible::RwLock;
@@ -123,14 +123,16 @@ pub struct ConnectivityManager<TBackoff> {
 }
 
 /// Different sources for peer addresses, ordered by priority (Onchain=highest,
-/// Config=lowest).
+/// PeerExchange=lowest).
 #[repr(u8)]
 #[derive(Copy, Clone, Eq, Hash, PartialEq, Ord, PartialOrd, NumVariants, Serialize)]
 pub enum DiscoverySource {
     OnChainValidatorSet,
     File,
     Rest,
+    Dns,
     Config,
+    PeerExchange,
 }
 
 impl fmt::Debug for DiscoverySource {
@@ -146,6 +148,8 @@ impl fmt::Display for DiscoverySource {
             DiscoverySource::File => "File",
             DiscoverySource::Config => "Config",
             DiscoverySource::Rest => "Rest",
+            DiscoverySource::Dns => "Dns",
+            DiscoverySource::PeerExchange => "PeerExchange",
         })
     }
 }
@@ -188,10 +192,13 @@ impl DiscoveredPeerSet {
 
     /// Converts `DiscoveredPeerSet` into a `PeerSet`, however disregards the source of discovery
     /// TODO: Provide smarter merging based on discovery source
+    ///
+    /// Note: peers (and keys) discovered through the peer exchange are not trusted, and are
+    /// excluded from the eligible peers.
     pub fn to_eligible_peers(&self) -> PeerSet {
         self.0
             .iter()
-            .filter(|(_, peer)| peer.is_eligible())
+            .filter(|(_, peer)| peer.is_trusted())
             .map(|(peer_id, peer)| (*peer_id, peer.into()))
             .collect()
     }
@@ -227,6 +234,11 @@ impl DiscoveredPeer {
         self.is_eligible() && !self.addrs.is_empty()
     }
 
+    /// Peers with keys only from the peer exchange can be dialed, but are not trusted
+    pub fn is_trusted(&self) -> bool {
+        !self.keys.trusted_union().is_empty()
+    }
+
     /// Updates the last time we tried to connect to this node
     pub fn set_last_dial_time(&mut self, time: SystemTime) {
         self.last_dial_time = time;
@@ -260,7 +272,7 @@ impl PartialOrd for DiscoveredPeer {
 
 impl From<&DiscoveredPeer> for Peer {
     fn from(peer: &DiscoveredPeer) -> Self {
-        Peer::new(peer.addrs.union(), peer.keys.union(), peer.role)
+        Peer::new(peer.addrs.union(), peer.keys.trusted_union(), peer.role)
     }
 }
 
@@ -443,7 +455,9 @@ where
             let stale_peers = self
                 .connected
                 .iter()
-                .filter(|(peer_id, _)| !trusted_peers.contains_key(peer_id))
+                .filter(|(peer_id, _)| {
+                    !trusted_peers.contains_key(peer_id) && !self.is_exchanged_peer(peer_id)
+                })
                 .filter_map(|(peer_id, metadata)| {
                     // If we're using server only auth, we need to not evict unknown peers
                     // TODO: We should prevent `Unknown` from discovery sources
@@ -496,7 +510,9 @@ where
             let stale_peer_dials: Vec<AccountAddress> = self
                 .dial_queue
                 .keys()
-                .filter(|peer_id| !trusted_peers.contains_key(peer_id))
+                .filter(|peer_id| {
+                    !trusted_peers.contains_key(peer_id) && !self.is_exchanged_peer(peer_id)
+                })
                 .cloned()
                 .collect();
 
@@ -513,6 +529,15 @@ where
         }
     }
 
+    /// Returns true iff the peer is only known through the peer exchange. Such peers
+    /// are not trusted, but they are dialed (and stay connected) until they are forgotten.
+    fn is_exchanged_peer(&self, peer_id: &PeerId) -> bool {
+        self.discovered_peers
+            .0
+            .get(peer_id)
+            .map_or(false, |peer| peer.is_eligible() && !peer.is_trusted())
+    }
+
     fn dial_eligible_peers<'a>(
         &'a mut self,
         pending_dials: &'a mut FuturesUnordered<BoxFuture<'static, PeerId>>,
@@ -536,6 +561,7 @@ where
                     && !self.connected.contains_key(peer_id) // The node is not already connected.
                     && !self.dial_queue.contains_key(peer_id) // There is no pending dial to this node.
                     && roles_to_dial.contains(&peer.role) // We can dial this role
+                    && !self.is_banned(peer_id) // The node is not banned
             })
             .collect();
 
@@ -575,6 +601,14 @@ where
             .collect()
     }
 
+    /// Returns true iff the peer is banned for misbehavior
+    fn is_banned(&self, peer_id: &PeerId) -> bool {
+        let peer_network_id = PeerNetworkId::new(self.network_context.network_id(), *peer_id);
+        self.peers_and_metadata
+            .is_peer_banned(peer_network_id)
+            .unwrap_or(false)
+    }
+
     fn queue_dial_peer<'a>(
         &'a mut self,
         peer_id: PeerId,
@@ -1027,6 +1061,17 @@ impl PublicKeys {
     fn union(&self) -> HashSet<x25519::PublicKey> {
         self.0.iter().flatten().copied().collect()
     }
+
+    /// The union of the keys from all sources but the (untrusted) peer exchange
+    fn trusted_union(&self) -> HashSet<x25519::PublicKey> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(src_idx, _)| *src_idx != DiscoverySource::PeerExchange.as_usize())
+            .flat_map(|(_, pubkeys)| pubkeys)
+            .copied()
+            .collect()
+    }
 }
 
 impl fmt::Display for PublicKeys {
@@ -815,6 +815,48 @@ fn basic_update_discovered_peers() {
     assert_eq!(*trusted_peers.read(), peers_empty);
 }
 
+#[test]
+fn exchanged_peers_are_not_trusted() {
+    let (peer_id, peer, _, addr) = test_peer(AccountAddress::ONE);
+    let (mut mock, conn_mgr) = TestHarness::new(HashMap::new());
+    let trusted_peers = mock
+        .peers_and_metadata
+        .get_trusted_peers(&mock.network_context.network_id())
+        .unwrap();
+
+    let test = async move {
+        // An exchanged peer is dialed, but not trusted
+        let exchanged_peers = hashmap! {peer_id => peer.clone()};
+        mock.send_update_discovered_peers(DiscoverySource::PeerExchange, exchanged_peers)
+            .await;
+        mock.trigger_connectivity_check().await;
+        mock.trigger_pending_dials().await;
+        mock.expect_one_dial_success(peer_id, addr.clone()).await;
+        assert!(trusted_peers.read().is_empty());
+
+        // The connection isn't stale while the peer is exchanged
+        mock.trigger_connectivity_check().await;
+        assert_eq!(1, mock.get_connected_size().await);
+
+        // The peer is trusted once it's discovered through another source
+        let config_peers = hashmap! {peer_id => peer.clone()};
+        mock.send_update_discovered_peers(DiscoverySource::Config, config_peers.clone())
+            .await;
+        assert_eq!(1, mock.get_connected_size().await);
+        assert_eq!(*trusted_peers.read(), config_peers);
+
+        // Once the peer is forgotten, the connection is stale
+        mock.send_update_discovered_peers(DiscoverySource::Config, PeerSet::new())
+            .await;
+        mock.send_update_discovered_peers(DiscoverySource::PeerExchange, PeerSet::new())
+            .await;
+        mock.trigger_connectivity_check().await;
+        mock.expect_disconnect_success(peer_id, addr).await;
+        assert!(trusted_peers.read().is_empty());
+    };
+    block_on(future::join(conn_mgr.start(), test));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_stale_peers_unknown_inbound() {
     // Create a connectivity manager with mutual authentication disabled
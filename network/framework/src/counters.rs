@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::protocols::wire::handshake::v1::ProtocolId;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::network_id::{NetworkContext, NetworkId};
 use aptos_metrics_core::{
     register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
     Histogram, HistogramTimer, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
@@ -24,6 +24,9 @@ pub const RECEIVED_LABEL: &str = "received";
 pub const SENT_LABEL: &str = "sent";
 pub const SUCCEEDED_LABEL: &str = "succeeded";
 pub const FAILED_LABEL: &str = "failed";
+pub const ENQUEUED_LABEL: &str = "enqueued";
+pub const DEQUEUED_LABEL: &str = "dequeued";
+pub const DROPPED_LABEL: &str = "dropped";
 
 // Serialization labels
 pub const SERIALIZATION_LABEL: &str = "serialization";
@@ -310,6 +313,70 @@ pub fn direct_send_bytes(
     ])
 }
 
+pub static APTOS_NETWORK_OUTBOUND_QUEUE_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_outbound_queue_messages",
+        "Number of messages enqueued, dequeued and dropped by outbound protocol class queues",
+        &["role_type", "network_id", "peer_id", "class", "state"]
+    )
+    .unwrap()
+});
+
+pub fn outbound_queue_messages(
+    network_context: &NetworkContext,
+    class_label: &'static str,
+    state_label: &'static str,
+) -> IntCounter {
+    APTOS_NETWORK_OUTBOUND_QUEUE_MESSAGES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        class_label,
+        state_label,
+    ])
+}
+
+pub static APTOS_NETWORK_PEER_MISBEHAVIOR_REPORTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_misbehavior_reports",
+        "Number of peer misbehaviors reported by applications",
+        &["network_id", "misbehavior"]
+    )
+    .unwrap()
+});
+
+pub fn peer_misbehavior_reports(network_id: &NetworkId, misbehavior_label: &str) -> IntCounter {
+    APTOS_NETWORK_PEER_MISBEHAVIOR_REPORTS
+        .with_label_values(&[network_id.as_str(), misbehavior_label])
+}
+
+pub static APTOS_NETWORK_PEER_BANS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_bans",
+        "Number of peers banned for misbehavior",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
+pub fn peer_bans(network_id: &NetworkId) -> IntCounter {
+    APTOS_NETWORK_PEER_BANS.with_label_values(&[network_id.as_str()])
+}
+
+/// Counters related to the addresses exchanged by the peer exchange
+pub static APTOS_NETWORK_PEER_EXCHANGE_ADDRESSES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_exchange_addresses",
+        "Number of peer addresses shared, received and rejected by the peer exchange",
+        &["network_id", "operation"]
+    )
+    .unwrap()
+});
+
+pub fn peer_exchange_addresses(network_id: &NetworkId, operation: &str) -> IntCounter {
+    APTOS_NETWORK_PEER_EXCHANGE_ADDRESSES.with_label_values(&[network_id.as_str(), operation])
+}
+
 /// Counters(queued,dequeued,dropped) related to inbound network notifications for RPCs and
 /// DirectSends.
 pub static PENDING_NETWORK_NOTIFICATIONS: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -341,6 +408,16 @@ pub static PENDING_HEALTH_CHECKER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::ne
     .unwrap()
 });
 
+/// Counter of pending network events to the Peer Exchange.
+pub static PENDING_PEER_EXCHANGE_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_pending_peer_exchange_events",
+        "Number of pending peer exchange events by state",
+        &["state"]
+    )
+    .unwrap()
+});
+
 /// Counter of pending network events to Discovery.
 pub static PENDING_DISCOVERY_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -5,6 +5,7 @@
 use crate::{
     constants,
     peer::Peer,
+    peer_manager::ConnectionRateLimit,
     protocols::wire::{
         handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
         messaging::v1::{MultiplexMessage, MultiplexMessageSink},
@@ -13,7 +14,10 @@ use crate::{
     transport::{Connection, ConnectionId, ConnectionMetadata},
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{OutboundQueuesConfig, PeerRole},
+    network_id::NetworkContext,
+};
 use aptos_memsocket::MemorySocket;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_proptest_helpers::ValueGenerator;
@@ -86,7 +90,11 @@ pub fn fuzz(data: &[u8]) {
         ProtocolIdSet::all_known(),
         PeerRole::Unknown,
     );
-    let connection = Connection { socket, metadata };
+    let connection = Connection {
+        socket,
+        metadata,
+        quic_streams: None,
+    };
 
     let (connection_notifs_tx, connection_notifs_rx) = aptos_channels::new_test(8);
     let channel_size = 8;
@@ -108,6 +116,9 @@ pub fn fuzz(data: &[u8]) {
         constants::MAX_CONCURRENT_OUTBOUND_RPCS,
         constants::MAX_FRAME_SIZE,
         constants::MAX_MESSAGE_SIZE,
+        OutboundQueuesConfig::default(),
+        ConnectionRateLimit::default(),
+        ConnectionRateLimit::default(),
     );
     executor.spawn(peer.start());
 
@@ -21,7 +21,7 @@ use crate::{
         RECEIVED_LABEL, SENT_LABEL,
     },
     logging::NetworkSchema,
-    peer_manager::{PeerManagerError, TransportNotification},
+    peer_manager::{ConnectionRateLimit, PeerManagerError, TransportNotification},
     protocols::{
         direct_send::Message,
         rpc::{InboundRpcRequest, InboundRpcs, OutboundRpcRequest, OutboundRpcs},
@@ -35,8 +35,9 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::OutboundQueuesConfig, network_id::NetworkContext};
 use aptos_logger::prelude::*;
+use aptos_netcore::transport::quic::QuicStreams;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::PeerId;
@@ -44,13 +45,15 @@ use bytes::Bytes;
 use futures::{
     self,
     channel::oneshot,
+    future::{self, FutureExt},
     io::{AsyncRead, AsyncWrite},
-    stream::StreamExt,
+    stream::{self, SelectAll, StreamExt},
     SinkExt,
 };
 use futures_util::stream::select;
+use outbound_queues::{OutboundQueueSenders, OutboundQueues, ProtocolClass};
 use serde::Serialize;
-use std::{fmt, panic, time::Duration};
+use std::{collections::HashMap, fmt, panic, time::Duration};
 use tokio::runtime::Handle;
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
@@ -62,6 +65,16 @@ mod test;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod fuzzing;
 
+mod outbound_queues;
+
+/// Capacity of each outbound class queue's channel.
+const OUTBOUND_QUEUE_CHANNEL_SIZE: usize = 1024;
+
+/// Capacity of the channel between the scheduler and the writer of a
+/// connection. Kept small, so that pending messages wait in the class queues,
+/// where they can still be reordered or dropped.
+const MULTIPLEX_CHANNEL_SIZE: usize = 8;
+
 /// Requests [`Peer`] receives from the [`PeerManager`](crate::peer_manager::PeerManager).
 #[derive(Debug)]
 pub enum PeerRequest {
@@ -107,6 +120,53 @@ enum State {
     ShuttingDown(DisconnectReason),
 }
 
+/// The queues of outbound messages to the remote peer, each drained by its own
+/// writer task. Messages are written to the connection's socket, except on
+/// connections over QUIC, where the messages of each negotiated protocol are
+/// written to a QUIC stream of their own. Every writer has a queue per
+/// [`ProtocolClass`].
+struct OutboundWriters {
+    /// Queues of the connection's socket.
+    socket_tx: OutboundQueueSenders,
+    /// Queues of the per-protocol QUIC streams.
+    protocol_txs: HashMap<ProtocolId, OutboundQueueSenders>,
+    /// Handles to close the writer tasks.
+    close_txs: Vec<oneshot::Sender<()>>,
+}
+
+impl OutboundWriters {
+    fn new(socket_tx: OutboundQueueSenders, close_tx: oneshot::Sender<()>) -> Self {
+        Self {
+            socket_tx,
+            protocol_txs: HashMap::new(),
+            close_txs: vec![close_tx],
+        }
+    }
+
+    fn add_protocol(
+        &mut self,
+        protocol_id: ProtocolId,
+        write_reqs_tx: OutboundQueueSenders,
+        close_tx: oneshot::Sender<()>,
+    ) {
+        self.protocol_txs.insert(protocol_id, write_reqs_tx);
+        self.close_txs.push(close_tx);
+    }
+
+    /// The queue for messages of the given protocol.
+    fn protocol(&mut self, protocol_id: ProtocolId) -> &mut aptos_channels::Sender<NetworkMessage> {
+        self.protocol_txs
+            .get_mut(&protocol_id)
+            .unwrap_or(&mut self.socket_tx)
+            .sender(ProtocolClass::of(protocol_id))
+    }
+
+    /// The queue for messages that belong to no protocol (e.g., errors).
+    fn socket(&mut self) -> &mut aptos_channels::Sender<NetworkMessage> {
+        self.socket_tx.sender(ProtocolClass::Control)
+    }
+}
+
 /// The `Peer` actor manages a single connection to another remote peer after
 /// the initial connection establishment and handshake.
 pub struct Peer<TSocket> {
@@ -120,6 +180,8 @@ pub struct Peer<TSocket> {
     connection_metadata: ConnectionMetadata,
     /// Underlying connection.
     connection: Option<TSocket>,
+    /// Streams of the underlying connection, if it is over QUIC.
+    quic_streams: Option<QuicStreams>,
     /// Channel to notify PeerManager that we've disconnected.
     connection_notifs_tx: aptos_channels::Sender<TransportNotification<TSocket>>,
     /// Channel to receive requests from PeerManager to send messages and rpcs.
@@ -136,8 +198,19 @@ pub struct Peer<TSocket> {
     max_frame_size: usize,
     /// The maximum size of an inbound or outbound request message
     max_message_size: usize,
+    /// Scheduling of outbound messages across protocol classes
+    outbound_queues_config: OutboundQueuesConfig,
+    /// Bandwidth limits of the bytes read from the connection
+    inbound_rate_limit: ConnectionRateLimit,
+    /// Bandwidth limits of the bytes written to the connection
+    outbound_rate_limit: ConnectionRateLimit,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// Inbound stream buffers of the remote peer's QUIC streams, by the order
+    /// they were accepted in
+    quic_inbound_streams: HashMap<usize, InboundStreamBuffer>,
+    /// The maximum number of fragments of the inbound streams
+    max_fragments: usize,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -158,10 +231,14 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        outbound_queues_config: OutboundQueuesConfig,
+        inbound_rate_limit: ConnectionRateLimit,
+        outbound_rate_limit: ConnectionRateLimit,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
             socket,
+            quic_streams,
         } = connection;
         let remote_peer_id = connection_metadata.remote_peer_id;
         let max_fragments = max_message_size / max_frame_size;
@@ -171,6 +248,7 @@ where
             time_service: time_service.clone(),
             connection_metadata,
             connection: Some(socket),
+            quic_streams,
             connection_notifs_tx,
             peer_reqs_rx,
             peer_notifs_tx,
@@ -190,7 +268,12 @@ where
             state: State::Connected,
             max_frame_size,
             max_message_size,
+            outbound_queues_config,
+            inbound_rate_limit,
+            outbound_rate_limit,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
+            quic_inbound_streams: HashMap::new(),
+            max_fragments,
         }
     }
 
@@ -212,15 +295,22 @@ where
         let (read_socket, write_socket) =
             tokio::io::split(self.connection.take().unwrap().compat());
 
-        let mut reader =
-            MultiplexMessageStream::new(read_socket.compat(), self.max_frame_size).fuse();
-        let writer = MultiplexMessageSink::new(write_socket.compat_write(), self.max_frame_size);
+        // Both halves are subject to the connection's bandwidth limits.
+        let mut reader = MultiplexMessageStream::new(
+            self.inbound_rate_limit.limit(read_socket.compat()),
+            self.max_frame_size,
+        )
+        .fuse();
+        let writer = MultiplexMessageSink::new(
+            self.outbound_rate_limit.limit(write_socket.compat_write()),
+            self.max_frame_size,
+        );
 
         // Start writer "process" as a separate task. We receive two handles to
         // communicate with the task:
-        //   1. `write_reqs_tx`: Queue of pending NetworkMessages to write.
+        //   1. `write_reqs_tx`: Queues of pending NetworkMessages to write, one per protocol class.
         //   2. `close_tx`: Handle to close the task and underlying connection.
-        let (mut write_reqs_tx, writer_close_tx) = Self::start_writer_task(
+        let (write_reqs_tx, writer_close_tx) = Self::start_writer_task(
             &self.executor,
             self.time_service.clone(),
             self.connection_metadata.clone(),
@@ -228,7 +318,46 @@ where
             writer,
             self.max_frame_size,
             self.max_message_size,
+            &self.outbound_queues_config,
         );
+        let mut writers = OutboundWriters::new(write_reqs_tx, writer_close_tx);
+
+        // Over QUIC, every protocol is written to a stream of its own, so that
+        // large messages of one protocol (e.g., state sync responses) are never
+        // queued in front of the messages of another (e.g., consensus). In turn,
+        // we read from every stream the remote peer opens. Messages larger than
+        // a frame are fragmented on each stream as they are on the socket.
+        let mut quic_inbound_streams = match self.quic_streams.clone() {
+            Some(quic_streams) => {
+                for protocol_id in self.connection_metadata.application_protocols.iter() {
+                    let writer = MultiplexMessageSink::new(
+                        self.outbound_rate_limit.limit(quic_streams.open_stream()),
+                        self.max_frame_size,
+                    );
+                    let (write_reqs_tx, close_tx) = Self::start_writer_task(
+                        &self.executor,
+                        self.time_service.clone(),
+                        self.connection_metadata.clone(),
+                        self.network_context,
+                        writer,
+                        self.max_frame_size,
+                        self.max_message_size,
+                        &self.outbound_queues_config,
+                    );
+                    writers.add_protocol(protocol_id, write_reqs_tx, close_tx);
+                }
+                // Ends once the connection is closed
+                stream::unfold(quic_streams, |quic_streams| async move {
+                    let recv_stream = quic_streams.accept_stream().await.ok()?;
+                    Some((recv_stream, quic_streams))
+                })
+                .boxed()
+            },
+            None => stream::empty().boxed(),
+        }
+        .fuse();
+        let mut quic_readers = SelectAll::new();
+        let mut num_quic_inbound_streams = 0;
 
         // Start main Peer event loop.
         let reason = loop {
@@ -240,7 +369,7 @@ where
                 // Handle a new outbound request from the PeerManager.
                 maybe_request = self.peer_reqs_rx.next() => {
                     match maybe_request {
-                        Some(request) => self.handle_outbound_request(request, &mut write_reqs_tx).await,
+                        Some(request) => self.handle_outbound_request(request, &mut writers).await,
                         // The PeerManager is requesting this connection to close
                         // by dropping the corresponding peer_reqs_tx handle.
                         None => self.shutdown(DisconnectReason::Requested),
@@ -251,7 +380,7 @@ where
                 maybe_message = reader.next() => {
                     match maybe_message {
                         Some(message) =>  {
-                            if let Err(err) = self.handle_inbound_message(message, &mut write_reqs_tx).await {
+                            if let Err(err) = self.handle_inbound_message(message, None, &mut writers).await {
                                 warn!(
                                     NetworkSchema::new(&self.network_context)
                                         .connection_metadata(&self.connection_metadata),
@@ -267,10 +396,46 @@ where
                         None => self.shutdown(DisconnectReason::ConnectionLost),
                     }
                 },
+                // Start reading from a new QUIC stream of the remote peer.
+                recv_stream = quic_inbound_streams.select_next_some() => {
+                    let stream_index = num_quic_inbound_streams;
+                    num_quic_inbound_streams += 1;
+                    // Every stream ends with `None`, to release its stream buffer.
+                    let reader = MultiplexMessageStream::new(
+                        self.inbound_rate_limit.limit(recv_stream),
+                        self.max_frame_size,
+                    )
+                    .map(Some)
+                    .chain(stream::once(future::ready(None)))
+                    .map(move |message| (stream_index, message));
+                    quic_readers.push(reader);
+                },
+                // Handle a new inbound MultiplexMessage read off one of the
+                // remote peer's QUIC streams.
+                (stream_index, maybe_message) = quic_readers.select_next_some() => {
+                    match maybe_message {
+                        Some(message) => {
+                            if let Err(err) = self.handle_inbound_message(message, Some(stream_index), &mut writers).await {
+                                warn!(
+                                    NetworkSchema::new(&self.network_context)
+                                        .connection_metadata(&self.connection_metadata),
+                                    error = %err,
+                                    "{} Error in handling inbound message from peer: {}, error: {}",
+                                    self.network_context,
+                                    remote_peer_id.short_str(),
+                                    err
+                                );
+                            }
+                        },
+                        None => {
+                            self.quic_inbound_streams.remove(&stream_index);
+                        },
+                    }
+                },
                 // Drive the queue of pending inbound rpcs. When one is fulfilled
                 // by an upstream protocol, send the response to the remote peer.
-                maybe_response = self.inbound_rpcs.next_completed_response() => {
-                    if let Err(err) = self.inbound_rpcs.send_outbound_response(&mut write_reqs_tx, maybe_response).await {
+                (protocol_id, maybe_response) = self.inbound_rpcs.next_completed_response() => {
+                    if let Err(err) = self.inbound_rpcs.send_outbound_response(writers.protocol(protocol_id), maybe_response).await {
                         warn!(
                             NetworkSchema::new(&self.network_context).connection_metadata(&self.connection_metadata),
                             error = %err,
@@ -286,15 +451,16 @@ where
             }
         };
 
-        // Finish shutting down the connection. Close the writer task and notify
+        // Finish shutting down the connection. Close the writer tasks and notify
         // PeerManager that this connection has shutdown.
-        self.do_shutdown(writer_close_tx, reason).await;
+        self.do_shutdown(writers, reason).await;
     }
 
     // Start a new task on the given executor which is responsible for writing outbound messages on
-    // the wire. The function returns two channels which can be used to send instructions to the
+    // the wire. The function returns two handles which can be used to send instructions to the
     // task:
-    // 1. The first channel is used to send outbound NetworkMessages to the task
+    // 1. The first holds a channel per protocol class to send outbound NetworkMessages to the
+    //    task, which picks the next message to write among the classes as configured.
     // 2. The second channel is used to instruct the task to close the connection and terminate.
     // If outbound messages are queued when the task receives a close instruction, it discards
     // them and immediately closes the connection.
@@ -306,13 +472,18 @@ where
         mut writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
         max_frame_size: usize,
         max_message_size: usize,
-    ) -> (aptos_channels::Sender<NetworkMessage>, oneshot::Sender<()>) {
+        outbound_queues_config: &OutboundQueuesConfig,
+    ) -> (OutboundQueueSenders, oneshot::Sender<()>) {
         let remote_peer_id = connection_metadata.remote_peer_id;
-        let (write_reqs_tx, mut write_reqs_rx): (aptos_channels::Sender<NetworkMessage>, _) =
-            aptos_channels::new(1024, &counters::PENDING_WIRE_MESSAGES);
+        let (write_reqs_tx, mut outbound_queues) = OutboundQueues::new(
+            &network_context,
+            outbound_queues_config,
+            OUTBOUND_QUEUE_CHANNEL_SIZE,
+        );
         let (close_tx, mut close_rx) = oneshot::channel();
 
-        let (mut msg_tx, msg_rx) = aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_MESSAGE);
+        let (mut msg_tx, msg_rx) =
+            aptos_channels::new(MULTIPLEX_CHANNEL_SIZE, &counters::PENDING_MULTIPLEX_MESSAGE);
         let (stream_msg_tx, stream_msg_rx) =
             aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_STREAM);
 
@@ -379,26 +550,39 @@ where
             let mut outbound_stream =
                 OutboundStream::new(max_frame_size, max_message_size, stream_msg_tx);
             loop {
-                futures::select! {
-                    message = write_reqs_rx.select_next_some() => {
-                        // either channel full would block the other one
-                        let result = if outbound_stream.should_stream(&message) {
-                            outbound_stream.stream_message(message).await
-                        } else {
-                            msg_tx.send(MultiplexMessage::Message(message)).await.map_err(|_| anyhow::anyhow!("Writer task ended"))
-                        };
-                        if let Err(err) = result {
-                            warn!(
-                                error = %err,
-                                "{} Error in sending message to peer: {}",
-                                network_context,
-                                remote_peer_id.short_str(),
-                            );
+                if outbound_queues.is_empty() {
+                    futures::select! {
+                        _ = future::poll_fn(|cx| outbound_queues.poll_receive(cx)).fuse() => {},
+                        _ = close_rx => {
+                            break;
                         }
-                    },
-                    _ = close_rx => {
-                        break;
                     }
+                } else if !matches!(close_rx.try_recv(), Ok(None)) {
+                    break;
+                }
+                // Schedule among everything written so far, not just the first arrival
+                let _ = future::poll_fn(|cx| outbound_queues.poll_receive(cx)).now_or_never();
+
+                let message = match outbound_queues.pop() {
+                    Some(message) => message,
+                    None => continue,
+                };
+                // either channel full would block the other one
+                let result = if outbound_stream.should_stream(&message) {
+                    outbound_stream.stream_message(message).await
+                } else {
+                    msg_tx
+                        .send(MultiplexMessage::Message(message))
+                        .await
+                        .map_err(|_| anyhow::anyhow!("Writer task ended"))
+                };
+                if let Err(err) = result {
+                    warn!(
+                        error = %err,
+                        "{} Error in sending message to peer: {}",
+                        network_context,
+                        remote_peer_id.short_str(),
+                    );
                 }
             }
         };
@@ -446,16 +630,56 @@ where
         Ok(())
     }
 
+    /// The buffer of the inbound stream messages read off the given QUIC
+    /// stream, or off the socket if none.
+    fn inbound_stream_buffer(
+        &mut self,
+        quic_stream_index: Option<usize>,
+    ) -> &mut InboundStreamBuffer {
+        match quic_stream_index {
+            Some(stream_index) => {
+                let max_fragments = self.max_fragments;
+                self.quic_inbound_streams
+                    .entry(stream_index)
+                    .or_insert_with(|| InboundStreamBuffer::new(max_fragments))
+            },
+            None => &mut self.inbound_stream,
+        }
+    }
+
     async fn handle_inbound_stream_message(
         &mut self,
         message: StreamMessage,
+        quic_stream_index: Option<usize>,
     ) -> Result<(), PeerManagerError> {
         match message {
             StreamMessage::Header(header) => {
-                self.inbound_stream.new_stream(header)?;
+                if let Some(stream_index) = quic_stream_index {
+                    // Messages are reassembled on all QUIC streams at once, so
+                    // bound the fragments buffered across the streams as if
+                    // they were a single one.
+                    let num_buffered_fragments: usize = self
+                        .quic_inbound_streams
+                        .iter()
+                        .filter(|(index, _)| **index != stream_index)
+                        .map(|(_, buffer)| buffer.num_fragments())
+                        .sum();
+                    if num_buffered_fragments + header.num_fragments as usize > self.max_fragments {
+                        return Err(anyhow::anyhow!(
+                            "Discard stream {}, too many fragments buffered across QUIC streams",
+                            header.request_id
+                        )
+                        .into());
+                    }
+                }
+                self.inbound_stream_buffer(quic_stream_index)
+                    .new_stream(header)?;
             },
             StreamMessage::Fragment(fragment) => {
-                if let Some(message) = self.inbound_stream.append_fragment(fragment)? {
+                if let Some(message) = self
+                    .inbound_stream_buffer(quic_stream_index)
+                    .append_fragment(fragment)?
+                {
                     self.handle_inbound_network_message(message).await?;
                 }
             },
@@ -466,7 +690,8 @@ where
     async fn handle_inbound_message(
         &mut self,
         message: Result<MultiplexMessage, ReadError>,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        quic_stream_index: Option<usize>,
+        writers: &mut OutboundWriters,
     ) -> Result<(), PeerManagerError> {
         trace!(
             NetworkSchema::new(&self.network_context)
@@ -488,7 +713,7 @@ where
                     let error_code = ErrorCode::parsing_error(*message_type, *protocol_id);
                     let message = NetworkMessage::Error(error_code);
 
-                    write_reqs_tx.send(message).await?;
+                    writers.socket().send(message).await?;
                     return Err(err.into());
                 },
                 ReadError::IoError(_) => {
@@ -503,7 +728,10 @@ where
             MultiplexMessage::Message(message) => {
                 self.handle_inbound_network_message(message).await
             },
-            MultiplexMessage::Stream(message) => self.handle_inbound_stream_message(message).await,
+            MultiplexMessage::Stream(message) => {
+                self.handle_inbound_stream_message(message, quic_stream_index)
+                    .await
+            },
         }
     }
 
@@ -547,7 +775,7 @@ where
     async fn handle_outbound_request(
         &mut self,
         request: PeerRequest,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        writers: &mut OutboundWriters,
     ) {
         trace!(
             "Peer {} PeerRequest::{:?}",
@@ -571,7 +799,7 @@ where
                     raw_msg: Vec::from(message.mdata.as_ref()),
                 });
 
-                match write_reqs_tx.send(message).await {
+                match writers.protocol(protocol_id).send(message).await {
                     Ok(_) => {
                         counters::direct_send_messages(&self.network_context, SENT_LABEL).inc();
                         counters::direct_send_bytes(&self.network_context, SENT_LABEL)
@@ -599,7 +827,7 @@ where
                 );
                 if let Err(e) = self
                     .outbound_rpcs
-                    .handle_outbound_request(request, write_reqs_tx)
+                    .handle_outbound_request(request, writers.protocol(protocol_id))
                     .await
                 {
                     warn!(
@@ -622,7 +850,7 @@ where
         self.state = State::ShuttingDown(reason);
     }
 
-    async fn do_shutdown(mut self, writer_close_tx: oneshot::Sender<()>, reason: DisconnectReason) {
+    async fn do_shutdown(mut self, writers: OutboundWriters, reason: DisconnectReason) {
         let remote_peer_id = self.remote_peer_id();
 
         // Send a PeerDisconnected event to PeerManager.
@@ -645,18 +873,20 @@ where
             );
         }
 
-        // Send a close instruction to the writer task. On receipt of this
-        // instruction, the writer task drops all pending outbound messages and
-        // closes the connection.
-        if let Err(e) = writer_close_tx.send(()) {
-            info!(
-                NetworkSchema::new(&self.network_context)
-                    .connection_metadata(&self.connection_metadata),
-                error = ?e,
-                "{} Failed to send close instruction to writer task. It must already be terminating/terminated. Error: {:?}",
-                self.network_context,
-                e
-            );
+        // Send a close instruction to the writer tasks. On receipt of this
+        // instruction, a writer task drops all pending outbound messages and
+        // closes its stream of the connection.
+        for writer_close_tx in writers.close_txs {
+            if let Err(e) = writer_close_tx.send(()) {
+                info!(
+                    NetworkSchema::new(&self.network_context)
+                        .connection_metadata(&self.connection_metadata),
+                    error = ?e,
+                    "{} Failed to send close instruction to writer task. It must already be terminating/terminated. Error: {:?}",
+                    self.network_context,
+                    e
+                );
+            }
         }
 
         trace!(
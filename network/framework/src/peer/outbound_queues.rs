@@ -0,0 +1,447 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Outbound protocol class queues of a connection.
+//!
+//! Every [`ProtocolId`] belongs to a [`ProtocolClass`], and outbound messages
+//! wait in a bounded queue per class until the writer is ready to send them. The
+//! next message to send is then picked among the classes according to the
+//! configured [`OutboundSchedulingPolicy`], so that e.g. consensus votes are not
+//! stuck behind a backlog of state sync responses or mempool broadcasts.
+
+use crate::{
+    counters::{self, DEQUEUED_LABEL, DROPPED_LABEL, ENQUEUED_LABEL},
+    protocols::wire::messaging::v1::NetworkMessage,
+    ProtocolId,
+};
+use aptos_config::{
+    config::{
+        OutboundDropPolicy, OutboundQueueConfig, OutboundQueuesConfig, OutboundSchedulingPolicy,
+    },
+    network_id::NetworkContext,
+};
+use aptos_metrics_core::IntCounter;
+use futures::stream::StreamExt;
+use std::{
+    collections::VecDeque,
+    task::{Context, Poll},
+};
+
+/// The number of bytes a class may send per round of weighted fair scheduling,
+/// per unit of weight.
+const QUANTUM_BYTES: usize = 64 * 1024;
+
+/// The class of a protocol, which determines the outbound queue its messages
+/// wait in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ProtocolClass {
+    Consensus = 0,
+    Control = 1,
+    StateSync = 2,
+    Mempool = 3,
+}
+
+impl ProtocolClass {
+    pub const ALL: [ProtocolClass; 4] = [
+        ProtocolClass::Consensus,
+        ProtocolClass::Control,
+        ProtocolClass::StateSync,
+        ProtocolClass::Mempool,
+    ];
+
+    pub fn of(protocol_id: ProtocolId) -> Self {
+        match protocol_id {
+            ProtocolId::ConsensusRpcBcs
+            | ProtocolId::ConsensusDirectSendBcs
+            | ProtocolId::ConsensusDirectSendJson
+            | ProtocolId::ConsensusRpcJson
+            | ProtocolId::ConsensusRpcCompressed
+            | ProtocolId::ConsensusDirectSendCompressed
+            | ProtocolId::ConsensusRpcZstd
+            | ProtocolId::ConsensusDirectSendZstd => ProtocolClass::Consensus,
+            ProtocolId::DiscoveryDirectSend
+            | ProtocolId::HealthCheckerRpc
+            | ProtocolId::PeerMonitoringServiceRpc
+            | ProtocolId::PeerExchangeRpc => ProtocolClass::Control,
+            ProtocolId::StateSyncDirectSend
+            | ProtocolId::StorageServiceRpc
+            | ProtocolId::StorageServiceRpcZstd => ProtocolClass::StateSync,
+            ProtocolId::MempoolDirectSend
+            | ProtocolId::MempoolRpc
+            | ProtocolId::MempoolDirectSendZstd
+            | ProtocolId::MempoolPrivateDirectSend => ProtocolClass::Mempool,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProtocolClass::Consensus => "consensus",
+            ProtocolClass::Control => "control",
+            ProtocolClass::StateSync => "state_sync",
+            ProtocolClass::Mempool => "mempool",
+        }
+    }
+
+    fn config(self, config: &OutboundQueuesConfig) -> &OutboundQueueConfig {
+        match self {
+            ProtocolClass::Consensus => &config.consensus,
+            ProtocolClass::Control => &config.control,
+            ProtocolClass::StateSync => &config.state_sync,
+            ProtocolClass::Mempool => &config.mempool,
+        }
+    }
+}
+
+/// The write ends of the class queues, indexed by class.
+pub struct OutboundQueueSenders {
+    senders: Vec<aptos_channels::Sender<NetworkMessage>>,
+}
+
+impl OutboundQueueSenders {
+    pub fn sender(&mut self, class: ProtocolClass) -> &mut aptos_channels::Sender<NetworkMessage> {
+        &mut self.senders[class as usize]
+    }
+}
+
+struct ClassQueue {
+    config: OutboundQueueConfig,
+    receiver: aptos_channels::Receiver<NetworkMessage>,
+    messages: VecDeque<NetworkMessage>,
+    /// Bytes this class may still send in the current weighted fair round
+    deficit: usize,
+    enqueued: IntCounter,
+    dequeued: IntCounter,
+    dropped: IntCounter,
+}
+
+impl ClassQueue {
+    fn is_full(&self) -> bool {
+        self.messages.len() >= self.config.max_queue_size.max(1)
+    }
+
+    /// Blocking queues stop taking messages off their channel once full, so
+    /// that senders wait for room.
+    fn accepts_messages(&self) -> bool {
+        self.config.drop_policy != OutboundDropPolicy::Block || !self.is_full()
+    }
+
+    fn push(&mut self, message: NetworkMessage) {
+        self.enqueued.inc();
+        if self.is_full() {
+            match self.config.drop_policy {
+                OutboundDropPolicy::Block => {},
+                OutboundDropPolicy::DropNewest => {
+                    self.dropped.inc();
+                    return;
+                },
+                OutboundDropPolicy::DropOldest => {
+                    self.messages.pop_front();
+                    self.dropped.inc();
+                },
+            }
+        }
+        self.messages.push_back(message);
+    }
+
+    fn pop(&mut self) -> Option<NetworkMessage> {
+        let message = self.messages.pop_front();
+        if message.is_some() {
+            self.dequeued.inc();
+        }
+        message
+    }
+}
+
+/// The outbound class queues of a connection. Messages are written into the
+/// queues through the [`OutboundQueueSenders`] and taken out, in scheduling
+/// order, by the connection's writer.
+pub struct OutboundQueues {
+    scheduling_policy: OutboundSchedulingPolicy,
+    /// Ordered by decreasing priority
+    queues: Vec<ClassQueue>,
+    /// The queue whose turn it is under weighted fair scheduling
+    current: usize,
+}
+
+impl OutboundQueues {
+    pub fn new(
+        network_context: &NetworkContext,
+        config: &OutboundQueuesConfig,
+        channel_size: usize,
+    ) -> (OutboundQueueSenders, Self) {
+        let mut senders = Vec::with_capacity(ProtocolClass::ALL.len());
+        let mut queues = Vec::with_capacity(ProtocolClass::ALL.len());
+        for class in ProtocolClass::ALL {
+            let (sender, receiver) =
+                aptos_channels::new(channel_size, &counters::PENDING_WIRE_MESSAGES);
+            senders.push(sender);
+            queues.push(ClassQueue {
+                config: *class.config(config),
+                receiver,
+                messages: VecDeque::new(),
+                deficit: 0,
+                enqueued: counters::outbound_queue_messages(
+                    network_context,
+                    class.as_str(),
+                    ENQUEUED_LABEL,
+                ),
+                dequeued: counters::outbound_queue_messages(
+                    network_context,
+                    class.as_str(),
+                    DEQUEUED_LABEL,
+                ),
+                dropped: counters::outbound_queue_messages(
+                    network_context,
+                    class.as_str(),
+                    DROPPED_LABEL,
+                ),
+            });
+        }
+        // Stable, so classes of equal priority keep their declaration order
+        queues.sort_by_key(|queue| std::cmp::Reverse(queue.config.priority));
+
+        (OutboundQueueSenders { senders }, Self {
+            scheduling_policy: config.scheduling_policy,
+            queues,
+            current: 0,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queues.iter().all(|queue| queue.messages.is_empty())
+    }
+
+    /// Moves newly written messages into their class queues. Resolves once at
+    /// least one message was moved.
+    pub fn poll_receive(&mut self, context: &mut Context) -> Poll<()> {
+        let mut received = false;
+        for queue in self.queues.iter_mut() {
+            while queue.accepts_messages() {
+                match queue.receiver.poll_next_unpin(context) {
+                    Poll::Ready(Some(message)) => {
+                        queue.push(message);
+                        received = true;
+                    },
+                    // The queue is closed or has no new messages
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+        }
+        if received {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Takes the next message to send out of the queues.
+    pub fn pop(&mut self) -> Option<NetworkMessage> {
+        match self.scheduling_policy {
+            OutboundSchedulingPolicy::StrictPriority => self
+                .queues
+                .iter_mut()
+                .find(|queue| !queue.messages.is_empty())
+                .and_then(ClassQueue::pop),
+            OutboundSchedulingPolicy::WeightedFair => self.pop_weighted_fair(),
+        }
+    }
+
+    /// Deficit round robin: on its turn, every class with pending messages is
+    /// granted a quantum of bytes proportional to its weight, and sends
+    /// messages for as long as its credit covers them.
+    fn pop_weighted_fair(&mut self) -> Option<NetworkMessage> {
+        if self.is_empty() {
+            return None;
+        }
+        loop {
+            let queue = &mut self.queues[self.current];
+            match queue.messages.front() {
+                Some(message) if message.data_len() <= queue.deficit => {
+                    queue.deficit -= message.data_len();
+                    return queue.pop();
+                },
+                Some(_) => {},
+                None => queue.deficit = 0,
+            }
+
+            self.current = (self.current + 1) % self.queues.len();
+            let queue = &mut self.queues[self.current];
+            if !queue.messages.is_empty() {
+                queue.deficit += queue.config.weight.max(1) as usize * QUANTUM_BYTES;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocols::wire::messaging::v1::{DirectSendMsg, Priority};
+
+    fn message(protocol_id: ProtocolId, len: usize) -> NetworkMessage {
+        NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id,
+            priority: Priority::default(),
+            raw_msg: vec![0; len],
+        })
+    }
+
+    fn protocol_of(message: &NetworkMessage) -> ProtocolId {
+        match message {
+            NetworkMessage::DirectSendMsg(message) => message.protocol_id,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Queues that are written to through their senders
+    struct TestQueues {
+        senders: OutboundQueueSenders,
+        queues: OutboundQueues,
+    }
+
+    impl TestQueues {
+        fn push(&mut self, message: NetworkMessage) {
+            let class = match &message {
+                NetworkMessage::DirectSendMsg(message) => ProtocolClass::of(message.protocol_id),
+                _ => unreachable!(),
+            };
+            self.senders.sender(class).try_send(message).unwrap();
+            let waker = futures::task::noop_waker();
+            let mut context = Context::from_waker(&waker);
+            assert_eq!(self.queues.poll_receive(&mut context), Poll::Ready(()));
+        }
+
+        fn pop(&mut self) -> Option<NetworkMessage> {
+            self.queues.pop()
+        }
+
+        fn is_empty(&self) -> bool {
+            self.queues.is_empty()
+        }
+    }
+
+    fn new_queues(config: OutboundQueuesConfig) -> TestQueues {
+        let (senders, queues) = OutboundQueues::new(&NetworkContext::mock(), &config, 1);
+        TestQueues { senders, queues }
+    }
+
+    #[test]
+    fn strict_priority() {
+        let mut queues = new_queues(OutboundQueuesConfig {
+            scheduling_policy: OutboundSchedulingPolicy::StrictPriority,
+            ..OutboundQueuesConfig::default()
+        });
+        queues.push(message(ProtocolId::MempoolDirectSend, 1));
+        queues.push(message(ProtocolId::StateSyncDirectSend, 1));
+        queues.push(message(ProtocolId::ConsensusRpcBcs, 1));
+        queues.push(message(ProtocolId::HealthCheckerRpc, 1));
+        queues.push(message(ProtocolId::ConsensusDirectSendBcs, 1));
+
+        let order: Vec<_> = std::iter::from_fn(|| queues.pop())
+            .map(|message| protocol_of(&message))
+            .collect();
+        assert_eq!(order, vec![
+            ProtocolId::HealthCheckerRpc,
+            ProtocolId::ConsensusRpcBcs,
+            ProtocolId::ConsensusDirectSendBcs,
+            ProtocolId::StateSyncDirectSend,
+            ProtocolId::MempoolDirectSend,
+        ]);
+        assert!(queues.is_empty());
+    }
+
+    #[test]
+    fn weighted_fair_shares_bytes_by_weight() {
+        let mut config = OutboundQueuesConfig::default();
+        config.consensus.weight = 3;
+        config.state_sync.weight = 1;
+        let mut queues = new_queues(config);
+        for _ in 0..100 {
+            queues.push(message(ProtocolId::ConsensusRpcBcs, 16 * 1024));
+            queues.push(message(ProtocolId::StateSyncDirectSend, 16 * 1024));
+        }
+
+        // While both classes are backlogged, consensus gets three times the bytes
+        let mut consensus_bytes = 0;
+        let mut state_sync_bytes = 0;
+        for _ in 0..80 {
+            let message = queues.pop().unwrap();
+            match protocol_of(&message) {
+                ProtocolId::ConsensusRpcBcs => consensus_bytes += message.data_len(),
+                _ => state_sync_bytes += message.data_len(),
+            }
+        }
+        assert_eq!(consensus_bytes, 3 * state_sync_bytes);
+    }
+
+    #[test]
+    fn weighted_fair_sends_messages_larger_than_a_quantum() {
+        let mut queues = new_queues(OutboundQueuesConfig::default());
+        queues.push(message(
+            ProtocolId::StateSyncDirectSend,
+            100 * QUANTUM_BYTES,
+        ));
+        queues.push(message(ProtocolId::MempoolDirectSend, 1));
+
+        // The small message goes first, while state sync accumulates credit
+        assert_eq!(
+            protocol_of(&queues.pop().unwrap()),
+            ProtocolId::MempoolDirectSend
+        );
+        assert_eq!(
+            protocol_of(&queues.pop().unwrap()),
+            ProtocolId::StateSyncDirectSend
+        );
+        assert!(queues.pop().is_none());
+    }
+
+    #[test]
+    fn drop_policies() {
+        let mut config = OutboundQueuesConfig::default();
+        config.mempool.max_queue_size = 2;
+        config.mempool.drop_policy = OutboundDropPolicy::DropOldest;
+        config.state_sync.max_queue_size = 2;
+        config.state_sync.drop_policy = OutboundDropPolicy::DropNewest;
+        config.scheduling_policy = OutboundSchedulingPolicy::StrictPriority;
+        let mut queues = new_queues(config);
+        for len in 1..=3 {
+            queues.push(message(ProtocolId::MempoolDirectSend, len));
+            queues.push(message(ProtocolId::StateSyncDirectSend, len));
+        }
+
+        let lens: Vec<_> = std::iter::from_fn(|| queues.pop())
+            .map(|message| (protocol_of(&message), message.data_len()))
+            .collect();
+        assert_eq!(lens, vec![
+            (ProtocolId::StateSyncDirectSend, 1),
+            (ProtocolId::StateSyncDirectSend, 2),
+            (ProtocolId::MempoolDirectSend, 2),
+            (ProtocolId::MempoolDirectSend, 3),
+        ]);
+    }
+
+    #[test]
+    fn blocking_queues_stop_receiving_when_full() {
+        let mut config = OutboundQueuesConfig::default();
+        config.consensus.max_queue_size = 1;
+        config.consensus.drop_policy = OutboundDropPolicy::Block;
+        let (mut senders, mut queues) = OutboundQueues::new(&NetworkContext::mock(), &config, 4);
+        let sender = senders.sender(ProtocolClass::Consensus);
+        sender
+            .try_send(message(ProtocolId::ConsensusRpcBcs, 1))
+            .unwrap();
+        sender
+            .try_send(message(ProtocolId::ConsensusRpcBcs, 2))
+            .unwrap();
+
+        let waker = futures::task::noop_waker();
+        let mut context = Context::from_waker(&waker);
+        assert_eq!(queues.poll_receive(&mut context), Poll::Ready(()));
+        assert_eq!(queues.poll_receive(&mut context), Poll::Pending);
+
+        // Once there is room again, the second message is taken in
+        assert_eq!(queues.pop().unwrap().data_len(), 1);
+        assert_eq!(queues.poll_receive(&mut context), Poll::Ready(()));
+        assert_eq!(queues.pop().unwrap().data_len(), 2);
+    }
+}
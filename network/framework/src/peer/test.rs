@@ -8,7 +8,7 @@ use crate::{
         MAX_FRAME_SIZE, MAX_MESSAGE_SIZE, NETWORK_CHANNEL_SIZE,
     },
     peer::{DisconnectReason, Peer, PeerNotification, PeerRequest},
-    peer_manager::TransportNotification,
+    peer_manager::{ConnectionRateLimit, TransportNotification},
     protocols::{
         direct_send::Message,
         rpc::{error::RpcError, InboundRpcRequest, OutboundRpcRequest},
@@ -24,7 +24,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{OutboundQueuesConfig, PeerRole},
+    network_id::NetworkContext,
+};
 use aptos_memsocket::MemorySocket;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_time_service::{MockTimeService, TimeService};
@@ -69,6 +72,7 @@ fn build_test_peer(
             PeerRole::Unknown,
         ),
         socket: a,
+        quic_streams: None,
     };
 
     let (connection_notifs_tx, connection_notifs_rx) = aptos_channels::new_test(1);
@@ -90,6 +94,9 @@ fn build_test_peer(
         MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE,
         MAX_MESSAGE_SIZE,
+        OutboundQueuesConfig::default(),
+        ConnectionRateLimit::default(),
+        ConnectionRateLimit::default(),
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
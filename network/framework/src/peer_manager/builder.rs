@@ -18,12 +18,16 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use aptos_config::{
+    config::{OutboundQueuesConfig, RateLimitConfig, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
 use aptos_netcore::transport::memory::MemoryTransport;
 use aptos_netcore::transport::{
+    quic::{QuicSocket, QuicTransport},
     tcp::{TCPBufferCfg, TcpSocket, TcpTransport},
     Transport,
 };
@@ -79,6 +83,9 @@ struct PeerManagerContext {
     max_message_size: usize,
     inbound_connection_limit: usize,
     tcp_buffer_cfg: TCPBufferCfg,
+    outbound_queues_config: OutboundQueuesConfig,
+    inbound_rate_limit_config: Option<RateLimitConfig>,
+    outbound_rate_limit_config: Option<RateLimitConfig>,
 }
 
 impl PeerManagerContext {
@@ -102,6 +109,9 @@ impl PeerManagerContext {
         max_message_size: usize,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        outbound_queues_config: OutboundQueuesConfig,
+        inbound_rate_limit_config: Option<RateLimitConfig>,
+        outbound_rate_limit_config: Option<RateLimitConfig>,
     ) -> Self {
         Self {
             pm_reqs_tx,
@@ -119,6 +129,9 @@ impl PeerManagerContext {
             max_message_size,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            outbound_queues_config,
+            inbound_rate_limit_config,
+            outbound_rate_limit_config,
         }
     }
 
@@ -142,11 +155,13 @@ impl PeerManagerContext {
 type MemoryPeerManager =
     PeerManager<AptosNetTransport<MemoryTransport>, NoiseStream<aptos_memsocket::MemorySocket>>;
 type TcpPeerManager = PeerManager<AptosNetTransport<TcpTransport>, NoiseStream<TcpSocket>>;
+type QuicPeerManager = PeerManager<AptosNetTransport<QuicTransport>, NoiseStream<QuicSocket>>;
 
 enum TransportPeerManager {
     #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
     Memory(MemoryPeerManager),
     Tcp(TcpPeerManager),
+    Quic(QuicPeerManager),
 }
 
 pub struct PeerManagerBuilder {
@@ -177,6 +192,9 @@ impl PeerManagerBuilder {
         enable_proxy_protocol: bool,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        outbound_queues_config: OutboundQueuesConfig,
+        inbound_rate_limit_config: Option<RateLimitConfig>,
+        outbound_rate_limit_config: Option<RateLimitConfig>,
     ) -> Self {
         // Setup channel to send requests to peer manager.
         let (pm_reqs_tx, pm_reqs_rx) = aptos_channel::new(
@@ -212,6 +230,9 @@ impl PeerManagerBuilder {
                 max_message_size,
                 inbound_connection_limit,
                 tcp_buffer_cfg,
+                outbound_queues_config,
+                inbound_rate_limit_config,
+                outbound_rate_limit_config,
             )),
             peer_manager: None,
             listen_address,
@@ -288,6 +309,23 @@ impl PeerManagerBuilder {
                     executor,
                 )))
             },
+            [Ip4(_), Quic(_)] | [Ip6(_), Quic(_)] => {
+                Some(TransportPeerManager::Quic(self.build_with_transport(
+                    AptosNetTransport::new(
+                        QuicTransport::new(),
+                        self.network_context,
+                        self.time_service.clone(),
+                        key,
+                        auth_mode,
+                        HANDSHAKE_VERSION,
+                        chain_id,
+                        protos,
+                        // Proxy protocol headers only precede TCP connections
+                        false,
+                    ),
+                    executor,
+                )))
+            },
             #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
             [Memory(_)] => Some(TransportPeerManager::Memory(self.build_with_transport(
                 AptosNetTransport::new(
@@ -305,7 +343,8 @@ impl PeerManagerBuilder {
             ))),
             _ => panic!(
                 "{} Unsupported listen_address: '{}', expected '/memory/<port>', \
-                 '/ip4/<addr>/tcp/<port>', or '/ip6/<addr>/tcp/<port>'.",
+                 '/ip4/<addr>/tcp/<port>', '/ip6/<addr>/tcp/<port>', \
+                 '/ip4/<addr>/quic/<port>', or '/ip6/<addr>/quic/<port>'.",
                 self.network_context, self.listen_address
             ),
         };
@@ -346,6 +385,9 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
+            pm_context.outbound_queues_config,
+            pm_context.inbound_rate_limit_config,
+            pm_context.outbound_rate_limit_config,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
@@ -376,6 +418,7 @@ impl PeerManagerBuilder {
             #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
             TransportPeerManager::Memory(pm) => self.start_peer_manager(pm, executor),
             TransportPeerManager::Tcp(pm) => self.start_peer_manager(pm, executor),
+            TransportPeerManager::Quic(pm) => self.start_peer_manager(pm, executor),
         }
     }
 
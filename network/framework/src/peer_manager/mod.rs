@@ -23,7 +23,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::network_id::{NetworkContext, PeerNetworkId};
+use aptos_config::{
+    config::{OutboundQueuesConfig, RateLimitConfig},
+    network_id::{NetworkContext, PeerNetworkId},
+};
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::{ConnectionOrigin, Transport};
 use aptos_short_hex_str::AsShortHexStr;
@@ -46,16 +49,20 @@ use tokio::runtime::Handle;
 pub mod builder;
 pub mod conn_notifs_channel;
 mod error;
+mod rate_limit;
 mod senders;
 #[cfg(test)]
 mod tests;
 mod transport;
 mod types;
 
-pub use self::error::PeerManagerError;
+pub use self::{error::PeerManagerError, rate_limit::ConnectionRateLimit};
 use crate::{
     application::{error::Error, storage::PeersAndMetadata},
-    peer_manager::transport::{TransportHandler, TransportRequest},
+    peer_manager::{
+        rate_limit::RateLimiters,
+        transport::{TransportHandler, TransportRequest},
+    },
     protocols::network::SerializedRequest,
 };
 use aptos_config::config::PeerRole;
@@ -119,6 +126,12 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// Scheduling of outbound messages to each peer
+    outbound_queues_config: OutboundQueuesConfig,
+    /// Bandwidth limits of inbound traffic
+    inbound_rate_limiters: RateLimiters,
+    /// Bandwidth limits of outbound traffic
+    outbound_rate_limiters: RateLimiters,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -147,6 +160,9 @@ where
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        outbound_queues_config: OutboundQueuesConfig,
+        inbound_rate_limit_config: Option<RateLimitConfig>,
+        outbound_rate_limit_config: Option<RateLimitConfig>,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = aptos_channels::new(
             channel_size,
@@ -189,6 +205,15 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            outbound_queues_config,
+            inbound_rate_limiters: RateLimiters::inbound(
+                &network_context,
+                inbound_rate_limit_config,
+            ),
+            outbound_rate_limiters: RateLimiters::outbound(
+                &network_context,
+                outbound_rate_limit_config,
+            ),
         }
     }
 
@@ -354,6 +379,35 @@ where
             },
         };
 
+        // Reject connections with banned peers, and connections with untrusted
+        // peers from banned addresses (as untrusted peers can pick new ids).
+        let peer_network_id = PeerNetworkId::new(
+            self.network_context.network_id(),
+            conn.metadata.remote_peer_id,
+        );
+        let peer_banned = self
+            .peers_and_metadata
+            .is_peer_banned(peer_network_id)
+            .unwrap_or(false);
+        let address_banned = conn.metadata.role == PeerRole::Unknown
+            && conn.metadata.addr.find_ip_addr().map_or(false, |ip_addr| {
+                self.peers_and_metadata
+                    .is_address_banned(&self.network_context.network_id(), ip_addr)
+                    .unwrap_or(false)
+            });
+        if peer_banned || address_banned {
+            info!(
+                NetworkSchema::new(&self.network_context)
+                    .connection_metadata_with_address(&conn.metadata),
+                "{} Connection rejected because the peer is banned: {}",
+                self.network_context,
+                conn.metadata
+            );
+            counters::connections_rejected(&self.network_context, conn.metadata.origin).inc();
+            self.disconnect(conn);
+            return;
+        }
+
         // Verify that we have not reached the max connection limit for unknown inbound peers
         if conn.metadata.origin == ConnectionOrigin::Inbound {
             // Everything below here is meant for unknown peers only. The role comes from
@@ -660,6 +714,10 @@ where
         );
 
         // Initialize a new Peer actor for this connection.
+        let inbound_rate_limit = self.inbound_rate_limiters.connection_rate_limit(&conn_meta);
+        let outbound_rate_limit = self
+            .outbound_rate_limiters
+            .connection_rate_limit(&conn_meta);
         let peer = Peer::new(
             self.network_context,
             self.executor.clone(),
@@ -673,6 +731,9 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
+            self.outbound_queues_config.clone(),
+            inbound_rate_limit,
+            outbound_rate_limit,
         );
         self.executor.spawn(peer.start());
 
@@ -0,0 +1,274 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bandwidth limiting of peer connections.
+//!
+//! Every byte read from or written to a connection is taken out of up to three
+//! token buckets: one of the remote peer, one of the remote peer's IP address, and
+//! one shared by all connections of the network. The peer and network buckets only
+//! limit the traffic if configured. The buckets of each direction are configured
+//! separately, through the `inbound_rate_limit_config` and
+//! `outbound_rate_limit_config` of the [`NetworkConfig`].
+//!
+//! [`NetworkConfig`]: aptos_config::config::NetworkConfig
+
+use crate::{counters, transport::ConnectionMetadata};
+use aptos_config::{config::RateLimitConfig, network_id::NetworkContext};
+use aptos_infallible::Mutex;
+use aptos_rate_limiter::{
+    async_lib::AsyncRateLimiter,
+    rate_limit::{Bucket, SharedBucket, TokenBucketRateLimiter},
+};
+use aptos_types::PeerId;
+use std::{collections::HashSet, net::IpAddr, sync::Arc};
+
+/// A socket (or stream) whose reads or writes are limited by the buckets of
+/// the network, the IP address and the peer, in that order.
+pub type RateLimited<T> = AsyncRateLimiter<AsyncRateLimiter<AsyncRateLimiter<T>>>;
+
+/// The token buckets limiting one direction of a connection's traffic. Buckets
+/// that are not set don't limit the traffic.
+#[derive(Clone, Default)]
+pub struct ConnectionRateLimit {
+    network: Option<SharedBucket>,
+    ip: Option<SharedBucket>,
+    peer: Option<SharedBucket>,
+}
+
+impl ConnectionRateLimit {
+    pub fn limit<T>(&self, io: T) -> RateLimited<T> {
+        let io = AsyncRateLimiter::new(io, self.peer.clone());
+        let io = AsyncRateLimiter::new(io, self.ip.clone());
+        AsyncRateLimiter::new(io, self.network.clone())
+    }
+}
+
+/// The token buckets limiting one direction of a network's traffic.
+pub struct RateLimiters {
+    /// `None` if the traffic is not limited
+    limiters: Option<Limiters>,
+}
+
+struct Limiters {
+    network: Option<SharedBucket>,
+    ips: TokenBucketRateLimiter<IpAddr>,
+    peers: Option<TokenBucketRateLimiter<PeerId>>,
+    /// Keys of the buckets handed out, to garbage collect them once unused
+    ip_keys: HashSet<IpAddr>,
+    peer_keys: HashSet<PeerId>,
+}
+
+impl RateLimiters {
+    pub fn inbound(network_context: &NetworkContext, config: Option<RateLimitConfig>) -> Self {
+        Self::new(network_context, config, [
+            "inbound_network",
+            "inbound_ip",
+            "inbound_peer",
+        ])
+    }
+
+    pub fn outbound(network_context: &NetworkContext, config: Option<RateLimitConfig>) -> Self {
+        Self::new(network_context, config, [
+            "outbound_network",
+            "outbound_ip",
+            "outbound_peer",
+        ])
+    }
+
+    fn new(
+        network_context: &NetworkContext,
+        config: Option<RateLimitConfig>,
+        [network_label, ip_label, peer_label]: [&'static str; 3],
+    ) -> Self {
+        let config = match config {
+            Some(config) if config.enabled => config,
+            _ => return Self { limiters: None },
+        };
+        let log_info = network_context.to_string();
+        let metrics = Some(counters::NETWORK_RATE_LIMIT_METRICS.clone());
+
+        let network = config
+            .network_byte_bucket_size
+            .zip(config.network_byte_bucket_rate)
+            .map(|(network_bucket_size, network_bucket_rate)| {
+                Arc::new(Mutex::new(Bucket::new(
+                    network_label.to_string(),
+                    log_info.clone(),
+                    network_context.network_id().to_string(),
+                    network_bucket_size
+                        .saturating_mul(config.initial_bucket_fill_percentage as usize)
+                        / 100,
+                    network_bucket_size,
+                    network_bucket_rate,
+                    metrics.clone(),
+                )))
+            });
+        let ips = TokenBucketRateLimiter::new(
+            ip_label,
+            log_info.clone(),
+            config.initial_bucket_fill_percentage,
+            config.ip_byte_bucket_size,
+            config.ip_byte_bucket_rate,
+            metrics.clone(),
+        );
+        let peers = config
+            .peer_byte_bucket_size
+            .zip(config.peer_byte_bucket_rate)
+            .map(|(peer_bucket_size, peer_bucket_rate)| {
+                TokenBucketRateLimiter::new(
+                    peer_label,
+                    log_info,
+                    config.initial_bucket_fill_percentage,
+                    peer_bucket_size,
+                    peer_bucket_rate,
+                    metrics,
+                )
+            });
+
+        Self {
+            limiters: Some(Limiters {
+                network,
+                ips,
+                peers,
+                ip_keys: HashSet::new(),
+                peer_keys: HashSet::new(),
+            }),
+        }
+    }
+
+    /// Returns the buckets limiting the traffic of a new connection. Buckets of
+    /// IP addresses and peers that no connection uses anymore are dropped.
+    pub fn connection_rate_limit(&mut self, metadata: &ConnectionMetadata) -> ConnectionRateLimit {
+        let limiters = match self.limiters.as_mut() {
+            Some(limiters) => limiters,
+            None => return ConnectionRateLimit::default(),
+        };
+        limiters.garbage_collect();
+
+        let ip = metadata.addr.find_ip_addr().map(|ip_addr| {
+            limiters.ip_keys.insert(ip_addr);
+            limiters.ips.bucket(ip_addr)
+        });
+        let peer = limiters.peers.as_ref().map(|peers| {
+            let peer_id = metadata.remote_peer_id;
+            limiters.peer_keys.insert(peer_id);
+            peers.bucket(peer_id)
+        });
+        ConnectionRateLimit {
+            network: limiters.network.clone(),
+            ip,
+            peer,
+        }
+    }
+}
+
+impl Limiters {
+    fn garbage_collect(&mut self) {
+        let ips = &self.ips;
+        self.ip_keys
+            .retain(|ip_addr| !ips.try_garbage_collect_key(ip_addr));
+        if let Some(peers) = &self.peers {
+            self.peer_keys
+                .retain(|peer_id| !peers.try_garbage_collect_key(peer_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_types::network_address::NetworkAddress;
+    use futures::io::AsyncReadExt;
+    use std::str::FromStr;
+
+    fn metadata(addr: &str, peer_id: PeerId) -> ConnectionMetadata {
+        let mut metadata = ConnectionMetadata::mock(peer_id);
+        metadata.addr = NetworkAddress::from_str(addr).unwrap();
+        metadata
+    }
+
+    fn config() -> RateLimitConfig {
+        RateLimitConfig {
+            ip_byte_bucket_rate: 10,
+            ip_byte_bucket_size: 10,
+            peer_byte_bucket_rate: Some(5),
+            peer_byte_bucket_size: Some(5),
+            network_byte_bucket_rate: Some(100),
+            network_byte_bucket_size: Some(100),
+            initial_bucket_fill_percentage: 100,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn disabled_limits_nothing() {
+        let network_context = NetworkContext::mock();
+        let disabled = RateLimitConfig {
+            enabled: false,
+            ..config()
+        };
+        for config in [None, Some(disabled)] {
+            let mut limiters = RateLimiters::inbound(&network_context, config);
+            let limit = limiters
+                .connection_rate_limit(&metadata("/ip4/127.0.0.1/tcp/6180", PeerId::random()));
+            assert!(limit.network.is_none() && limit.ip.is_none() && limit.peer.is_none());
+        }
+    }
+
+    #[test]
+    fn buckets_are_shared_by_ip_and_peer() {
+        let network_context = NetworkContext::mock();
+        let mut limiters = RateLimiters::outbound(&network_context, Some(config()));
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let limit_a = limiters.connection_rate_limit(&metadata("/ip4/127.0.0.1/tcp/6180", peer_a));
+        let limit_b = limiters.connection_rate_limit(&metadata("/ip4/127.0.0.1/tcp/6181", peer_b));
+        let limit_c = limiters.connection_rate_limit(&metadata("/ip4/127.0.0.2/tcp/6180", peer_a));
+
+        let same = |a: &Option<SharedBucket>, b: &Option<SharedBucket>| {
+            Arc::ptr_eq(a.as_ref().unwrap(), b.as_ref().unwrap())
+        };
+        assert!(same(&limit_a.network, &limit_c.network));
+        assert!(same(&limit_a.ip, &limit_b.ip));
+        assert!(!same(&limit_a.ip, &limit_c.ip));
+        assert!(same(&limit_a.peer, &limit_c.peer));
+        assert!(!same(&limit_a.peer, &limit_b.peer));
+
+        // Once unused, the buckets of the connections are dropped
+        drop((limit_a, limit_b, limit_c));
+        limiters.connection_rate_limit(&metadata("/ip4/127.0.0.3/tcp/6180", PeerId::random()));
+        let limiters = limiters.limiters.unwrap();
+        assert_eq!(limiters.ip_keys.len(), 1);
+        assert_eq!(limiters.peer_keys.len(), 1);
+    }
+
+    #[test]
+    fn unset_buckets_limit_nothing() {
+        let network_context = NetworkContext::mock();
+        let ip_only = RateLimitConfig {
+            peer_byte_bucket_rate: None,
+            peer_byte_bucket_size: None,
+            network_byte_bucket_rate: None,
+            network_byte_bucket_size: None,
+            ..config()
+        };
+        let mut limiters = RateLimiters::inbound(&network_context, Some(ip_only));
+        let limit =
+            limiters.connection_rate_limit(&metadata("/ip4/127.0.0.1/tcp/6180", PeerId::random()));
+        assert!(limit.network.is_none() && limit.ip.is_some() && limit.peer.is_none());
+    }
+
+    #[tokio::test]
+    async fn reads_are_limited_by_the_smallest_bucket() {
+        let network_context = NetworkContext::mock();
+        let mut limiters = RateLimiters::inbound(&network_context, Some(config()));
+        let limit =
+            limiters.connection_rate_limit(&metadata("/ip4/127.0.0.1/tcp/6180", PeerId::random()));
+
+        let source: &[u8] = b"0123456789";
+        let mut reader = limit.limit(source);
+        let mut buf = [0; 10];
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 5);
+        assert_eq!(&buf[..5], b"01234");
+    }
+}
@@ -131,4 +131,12 @@ impl ConnectionRequestSender {
             .push(peer, ConnectionRequest::DisconnectPeer(peer, oneshot_tx))?;
         oneshot_rx.await?
     }
+
+    /// Requests that the peer is disconnected, without waiting for the request to be performed
+    pub fn request_disconnect_peer(&self, peer: PeerId) -> Result<(), PeerManagerError> {
+        let (oneshot_tx, _oneshot_rx) = oneshot::channel();
+        self.inner
+            .push(peer, ConnectionRequest::DisconnectPeer(peer, oneshot_tx))?;
+        Ok(())
+    }
 }
@@ -24,7 +24,7 @@ use crate::{
 use anyhow::anyhow;
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{PeerRole, MAX_INBOUND_CONNECTIONS},
+    config::{OutboundQueuesConfig, PeerRole, MAX_INBOUND_CONNECTIONS},
     network_id::{NetworkContext, NetworkId},
 };
 use aptos_memsocket::MemorySocket;
@@ -61,6 +61,7 @@ pub fn build_test_transport(
                     ProtocolIdSet::mock(),
                     PeerRole::Unknown,
                 ),
+                quic_streams: None,
             })
         })
         .boxed()
@@ -118,6 +119,9 @@ fn build_test_peer_manager(
         constants::MAX_FRAME_SIZE,
         constants::MAX_MESSAGE_SIZE,
         MAX_INBOUND_CONNECTIONS,
+        OutboundQueuesConfig::default(),
+        None,
+        None,
     );
 
     (
@@ -245,6 +249,7 @@ fn create_connection<TSocket: transport::TSocket>(
             ProtocolIdSet::mock(),
             PeerRole::Unknown,
         ),
+        quic_streams: None,
     }
 }
 
@@ -9,6 +9,7 @@ pub mod direct_send;
 pub mod health_checker;
 pub mod identity;
 pub mod network;
+pub mod peer_exchange;
 pub mod rpc;
 pub mod stream;
 pub mod wire;
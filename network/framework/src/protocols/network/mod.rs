@@ -353,6 +353,12 @@ impl<TMessage> NetworkSender<TMessage> {
         self.connection_reqs_tx.disconnect_peer(peer).await?;
         Ok(())
     }
+
+    /// Request that a given Peer be disconnected, without waiting for the request to be performed.
+    pub fn request_disconnect_peer(&self, peer: PeerId) -> Result<(), NetworkError> {
+        self.connection_reqs_tx.request_disconnect_peer(peer)?;
+        Ok(())
+    }
 }
 
 impl<TMessage: Message> NetworkSender<TMessage> {
@@ -0,0 +1,59 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    application::{interface::NetworkClient, storage::PeersAndMetadata},
+    connectivity_manager::ConnectivityRequest,
+    protocols::{
+        network::NetworkSender,
+        peer_exchange::{PeerExchange, PeerExchangeMsg, PeerExchangeNetworkEvents},
+        wire::handshake::v1::ProtocolId::PeerExchangeRpc,
+    },
+};
+use aptos_config::{config::PeerExchangeConfig, network_id::NetworkContext};
+use aptos_logger::prelude::*;
+use aptos_time_service::TimeService;
+use maplit::hashmap;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+
+pub struct PeerExchangeBuilder {
+    service: Option<PeerExchange<NetworkClient<PeerExchangeMsg>>>,
+}
+
+impl PeerExchangeBuilder {
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        config: PeerExchangeConfig,
+        network_sender: NetworkSender<PeerExchangeMsg>,
+        network_rx: PeerExchangeNetworkEvents,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        conn_mgr_reqs_tx: aptos_channels::Sender<ConnectivityRequest>,
+    ) -> Self {
+        let network_senders = hashmap! {network_context.network_id() => network_sender};
+        let network_client = NetworkClient::new(
+            vec![],
+            vec![PeerExchangeRpc],
+            network_senders,
+            peers_and_metadata,
+        );
+        let service = PeerExchange::new(
+            network_context,
+            time_service,
+            config,
+            network_client,
+            network_rx,
+            conn_mgr_reqs_tx,
+        );
+        Self {
+            service: Some(service),
+        }
+    }
+
+    pub fn start(&mut self, executor: &Handle) {
+        if let Some(service) = self.service.take() {
+            spawn_named!("[Network] PeerExchange", executor, service.start());
+        }
+    }
+}
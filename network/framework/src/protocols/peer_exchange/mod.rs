@@ -0,0 +1,438 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Protocol used to discover public peers through other peers
+//!
+//! The PeerExchange periodically asks a random connected peer for the addresses of
+//! other public peers, and hands the received addresses to the ConnectivityManager
+//! (which dials them if more outbound connections are needed). In return, it answers
+//! the requests of other peers with the addresses of the peers it has dialed: the
+//! Noise handshake of these connections verified that the address belongs to the peer.
+//!
+//! The number of exchanged peers held on to is bounded, and the oldest exchanged
+//! peers are forgotten first. Exchanged peers have the lowest dialing priority, and
+//! are only dialed: the ConnectivityManager doesn't add them to the trusted peers,
+//! so their connections are treated like those of any unknown peer.
+use crate::{
+    application::{interface::NetworkClientInterface, reputation::PeerMisbehavior},
+    connectivity_manager::{ConnectivityRequest, DiscoverySource},
+    constants::NETWORK_CHANNEL_SIZE,
+    counters,
+    logging::NetworkSchema,
+    protocols::{
+        network::{
+            Event, NetworkApplicationConfig, NetworkClientConfig, NetworkEvents,
+            NetworkServiceConfig,
+        },
+        rpc::error::RpcError,
+    },
+    ProtocolId,
+};
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::{
+    config::{Peer, PeerExchangeConfig, PeerRole, PeerSet},
+    network_id::{NetworkContext, PeerNetworkId},
+};
+use aptos_logger::prelude::*;
+use aptos_netcore::transport::ConnectionOrigin;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::{network_address::NetworkAddress, PeerId};
+use bytes::Bytes;
+use futures::{
+    channel::oneshot,
+    stream::{FuturesUnordered, StreamExt},
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+pub mod builder;
+#[cfg(test)]
+mod test;
+
+// Useful counter labels
+const INVALID_LABEL: &str = "invalid";
+const RECEIVED_LABEL: &str = "received";
+const SHARED_LABEL: &str = "shared";
+
+/// The interface from Network to the PeerExchange layer
+pub type PeerExchangeNetworkEvents = NetworkEvents<PeerExchangeMsg>;
+
+/// Returns a network application config for the peer exchange client and service
+pub fn peer_exchange_network_config() -> NetworkApplicationConfig {
+    let direct_send_protocols = vec![]; // The peer exchange doesn't use direct send
+    let rpc_protocols = vec![ProtocolId::PeerExchangeRpc];
+
+    let network_client_config =
+        NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
+    let network_service_config = NetworkServiceConfig::new(
+        direct_send_protocols,
+        rpc_protocols,
+        aptos_channel::Config::new(NETWORK_CHANNEL_SIZE)
+            .queue_style(QueueStyle::FIFO)
+            .counters(&counters::PENDING_PEER_EXCHANGE_NETWORK_EVENTS),
+    );
+    NetworkApplicationConfig::new(network_client_config, network_service_config)
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PeerExchangeMsg {
+    GetPeersRequest,
+    GetPeersResponse(Vec<ExchangedPeer>),
+}
+
+/// A public peer, and the address it was dialed at
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExchangedPeer {
+    pub peer_id: PeerId,
+    pub address: NetworkAddress,
+}
+
+/// The actor exchanging the addresses of public peers with other peers
+pub struct PeerExchange<NetworkClient> {
+    network_context: NetworkContext,
+    /// A handle to a time service for easily mocking time-related operations.
+    time_service: TimeService,
+    config: PeerExchangeConfig,
+    /// Network client to send requests to the Network Layer
+    network_client: NetworkClient,
+    /// Events of the peer exchange requests from other peers
+    network_events: PeerExchangeNetworkEvents,
+    /// Channel to send the exchanged peers to the ConnectivityManager
+    conn_mgr_reqs_tx: aptos_channels::Sender<ConnectivityRequest>,
+    /// The exchanged peers, and the time their address was (last) received
+    exchanged_peers: HashMap<PeerId, (NetworkAddress, Instant)>,
+    /// Random-number generator.
+    rng: SmallRng,
+}
+
+impl<NetworkClient: NetworkClientInterface<PeerExchangeMsg> + Unpin> PeerExchange<NetworkClient> {
+    /// Create new instance of the [`PeerExchange`] actor.
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        config: PeerExchangeConfig,
+        network_client: NetworkClient,
+        network_events: PeerExchangeNetworkEvents,
+        conn_mgr_reqs_tx: aptos_channels::Sender<ConnectivityRequest>,
+    ) -> Self {
+        Self {
+            network_context,
+            time_service,
+            config,
+            network_client,
+            network_events,
+            conn_mgr_reqs_tx,
+            exchanged_peers: HashMap::new(),
+            rng: SmallRng::from_entropy(),
+        }
+    }
+
+    pub async fn start(mut self) {
+        let mut pending_requests = FuturesUnordered::new();
+        info!(
+            NetworkSchema::new(&self.network_context),
+            "{} Peer exchange actor started", self.network_context
+        );
+
+        let ticker = self
+            .time_service
+            .interval(Duration::from_millis(self.config.exchange_interval_ms));
+        tokio::pin!(ticker);
+
+        loop {
+            futures::select! {
+                maybe_event = self.network_events.next() => {
+                    // Shutdown the PeerExchange when this network instance shuts
+                    // down. This happens when the `PeerManager` drops.
+                    let event = match maybe_event {
+                        Some(event) => event,
+                        None => break,
+                    };
+
+                    match event {
+                        Event::RpcRequest(peer_id, PeerExchangeMsg::GetPeersRequest, protocol, res_tx) => {
+                            self.handle_get_peers_request(peer_id, protocol, res_tx);
+                        }
+                        Event::RpcRequest(peer_id, msg, _, _) | Event::Message(peer_id, msg) => {
+                            self.handle_unexpected_message(peer_id, msg);
+                        }
+                        Event::NewPeer(_) | Event::LostPeer(_) => {
+                            // Connected peers are tracked by the peers and metadata container
+                        }
+                    }
+                }
+                _ = ticker.select_next_some() => {
+                    // Only ask a single peer at a time
+                    if !pending_requests.is_empty() {
+                        continue;
+                    }
+                    if let Some(peer_network_id) = self.choose_peer_to_ask() {
+                        pending_requests.push(Self::get_peers(
+                            self.network_client.clone(),
+                            peer_network_id,
+                            Duration::from_millis(self.config.request_timeout_ms),
+                        ));
+                    }
+                }
+                (peer_network_id, result) = pending_requests.select_next_some() => {
+                    self.handle_get_peers_response(peer_network_id, result);
+                }
+            }
+        }
+        warn!(
+            NetworkSchema::new(&self.network_context),
+            "{} Peer exchange actor terminated", self.network_context
+        );
+    }
+
+    /// Responds to the request with (a random sample of) the peers we've dialed
+    fn handle_get_peers_request(
+        &mut self,
+        peer_id: PeerId,
+        protocol: ProtocolId,
+        res_tx: oneshot::Sender<Result<Bytes, RpcError>>,
+    ) {
+        let peers = self.get_shareable_peers(peer_id);
+        let num_peers = peers.len();
+        let message = match protocol.to_bytes(&PeerExchangeMsg::GetPeersResponse(peers)) {
+            Ok(msg) => msg,
+            Err(e) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    error = ?e,
+                    "{} Unable to serialize get peers response: {}", self.network_context, e
+                );
+                return;
+            },
+        };
+        trace!(
+            NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+            "{} Sharing {} peers with peer: {}",
+            self.network_context,
+            num_peers,
+            peer_id,
+        );
+        counters::peer_exchange_addresses(&self.network_context.network_id(), SHARED_LABEL)
+            .inc_by(num_peers as u64);
+
+        let _ = res_tx.send(Ok(message.into()));
+    }
+
+    /// Returns the peers of this network we've dialed (except for the
+    /// requesting peer), as long as they're dialed at a public address.
+    fn get_shareable_peers(&mut self, requesting_peer_id: PeerId) -> Vec<ExchangedPeer> {
+        let connected_peers = match self
+            .network_client
+            .get_peers_and_metadata()
+            .get_connected_peers_and_metadata()
+        {
+            Ok(connected_peers) => connected_peers,
+            Err(error) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    "{} Unable to get the connected peers: {:?}", self.network_context, error
+                );
+                return vec![];
+            },
+        };
+        let shareable_peers: Vec<_> = connected_peers
+            .into_iter()
+            .filter_map(|(peer_network_id, peer_metadata)| {
+                let connection_metadata = peer_metadata.get_connection_metadata();
+                let shareable = peer_network_id.network_id() == self.network_context.network_id()
+                    && peer_network_id.peer_id() != requesting_peer_id
+                    && connection_metadata.origin == ConnectionOrigin::Outbound
+                    && is_shareable_address(&connection_metadata.addr);
+                shareable.then(|| ExchangedPeer {
+                    peer_id: peer_network_id.peer_id(),
+                    address: connection_metadata.addr,
+                })
+            })
+            .collect();
+        shareable_peers
+            .choose_multiple(&mut self.rng, self.config.max_peers_per_response)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a random connected peer that supports the peer exchange
+    fn choose_peer_to_ask(&mut self) -> Option<PeerNetworkId> {
+        let network_id = self.network_context.network_id();
+        let available_peers: Vec<_> = self
+            .network_client
+            .get_available_peers()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|peer_network_id| peer_network_id.network_id() == network_id)
+            .collect();
+        available_peers.choose(&mut self.rng).copied()
+    }
+
+    async fn get_peers(
+        network_client: NetworkClient,
+        peer_network_id: PeerNetworkId,
+        request_timeout: Duration,
+    ) -> (PeerNetworkId, Result<PeerExchangeMsg, RpcError>) {
+        let result = network_client
+            .send_to_peer_rpc(
+                PeerExchangeMsg::GetPeersRequest,
+                request_timeout,
+                peer_network_id,
+            )
+            .await
+            .map_err(|error| RpcError::Error(error.into()));
+        (peer_network_id, result)
+    }
+
+    fn handle_get_peers_response(
+        &mut self,
+        peer_network_id: PeerNetworkId,
+        result: Result<PeerExchangeMsg, RpcError>,
+    ) {
+        let peer_id = peer_network_id.peer_id();
+        let peers = match result {
+            Ok(PeerExchangeMsg::GetPeersResponse(peers)) => peers,
+            Ok(msg) => {
+                self.handle_unexpected_message(peer_id, msg);
+                return;
+            },
+            Err(error) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    error = ?error,
+                    "{} Get peers request failed for peer: {} with error: {:?}",
+                    self.network_context,
+                    peer_id,
+                    error
+                );
+                return;
+            },
+        };
+
+        // Hold on to the valid peers (up to the number we'd share ourselves)
+        let now = self.time_service.now();
+        let (mut num_received, mut num_invalid) = (0, 0);
+        for peer in peers.into_iter().take(self.config.max_peers_per_response) {
+            if peer.peer_id != self.network_context.peer_id()
+                && is_shareable_address(&peer.address)
+                && is_peer_id_of_address(peer.peer_id, &peer.address)
+            {
+                self.exchanged_peers
+                    .insert(peer.peer_id, (peer.address, now));
+                num_received += 1;
+            } else {
+                num_invalid += 1;
+            }
+        }
+        let network_id = self.network_context.network_id();
+        counters::peer_exchange_addresses(&network_id, RECEIVED_LABEL).inc_by(num_received);
+        counters::peer_exchange_addresses(&network_id, INVALID_LABEL).inc_by(num_invalid);
+        debug!(
+            NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+            "{} Received {} peers ({} invalid) from peer: {}",
+            self.network_context,
+            num_received,
+            num_invalid,
+            peer_id
+        );
+
+        // Forget the oldest peers if there are too many
+        while self.exchanged_peers.len() > self.config.max_exchanged_peers {
+            let oldest_peer_id = self
+                .exchanged_peers
+                .iter()
+                .min_by_key(|(_, (_, received_at))| *received_at)
+                .map(|(peer_id, _)| *peer_id);
+            if let Some(oldest_peer_id) = oldest_peer_id {
+                self.exchanged_peers.remove(&oldest_peer_id);
+            }
+        }
+
+        self.update_discovered_peers();
+    }
+
+    /// Sends the exchanged peers to the ConnectivityManager
+    fn update_discovered_peers(&mut self) {
+        // Exchanged peers are dialed as upstreams, like the public peers of seeds (but
+        // the ConnectivityManager doesn't trust them)
+        let peers: PeerSet = self
+            .exchanged_peers
+            .iter()
+            .map(|(peer_id, (address, _))| {
+                (
+                    *peer_id,
+                    Peer::from_addrs(PeerRole::Upstream, vec![address.clone()]),
+                )
+            })
+            .collect();
+        let request =
+            ConnectivityRequest::UpdateDiscoveredPeers(DiscoverySource::PeerExchange, peers);
+        if let Err(error) = self.conn_mgr_reqs_tx.try_send(request) {
+            warn!(
+                NetworkSchema::new(&self.network_context),
+                "{} Failed to send the exchanged peers {:?}", self.network_context, error
+            );
+        }
+    }
+
+    fn handle_unexpected_message(&mut self, peer_id: PeerId, msg: PeerExchangeMsg) {
+        warn!(
+            SecurityEvent::InvalidNetworkEvent,
+            NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+            "{} Unexpected peer exchange message from {}: {:?}",
+            self.network_context,
+            peer_id,
+            msg
+        );
+        let peer_network_id = PeerNetworkId::new(self.network_context.network_id(), peer_id);
+        if let Err(error) = self
+            .network_client
+            .report_peer_misbehavior(peer_network_id, PeerMisbehavior::UnexpectedMessage)
+        {
+            warn!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                "{} Failed to report the misbehavior of peer: {}: {:?}",
+                self.network_context,
+                peer_id,
+                error
+            );
+        }
+    }
+}
+
+/// Returns true iff the peer ID is derived from the Noise public key of the
+/// address. Otherwise, a peer could announce its own key under the ID of
+/// another peer (e.g., a trusted one).
+fn is_peer_id_of_address(peer_id: PeerId, address: &NetworkAddress) -> bool {
+    address.find_noise_proto().map_or(false, |public_key| {
+        aptos_types::account_address::from_identity_public_key(public_key) == peer_id
+    })
+}
+
+/// Returns true iff the address can be dialed by other public peers, i.e.,
+/// it's a full AptosNet address, and not a private (or local) IP address.
+fn is_shareable_address(address: &NetworkAddress) -> bool {
+    if !address.is_aptosnet_addr() {
+        return false;
+    }
+    match address.find_ip_addr() {
+        Some(IpAddr::V4(ip)) => {
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast())
+        },
+        Some(IpAddr::V6(ip)) => {
+            let is_unique_local = (ip.segments()[0] & 0xFE00) == 0xFC00;
+            let is_unicast_link_local = (ip.segments()[0] & 0xFFC0) == 0xFE80;
+            !(ip.is_loopback() || ip.is_unspecified() || is_unique_local || is_unicast_link_local)
+        },
+        None => true, // DNS names are resolved by the dialing peer
+    }
+}
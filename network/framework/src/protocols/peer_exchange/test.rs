@@ -0,0 +1,356 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    application::{interface::NetworkClient, storage::PeersAndMetadata},
+    peer_manager::{
+        conn_notifs_channel, ConnectionRequestSender, PeerManagerNotification, PeerManagerRequest,
+        PeerManagerRequestSender,
+    },
+    protocols::{
+        network::{NetworkSender, NewNetworkEvents, NewNetworkSender},
+        rpc::InboundRpcRequest,
+        wire::handshake::v1::ProtocolIdSet,
+    },
+    transport::ConnectionMetadata,
+};
+use aptos_config::{
+    config::{RoleType, HANDSHAKE_VERSION},
+    network_id::NetworkId,
+};
+use aptos_crypto::{x25519, Uniform};
+use aptos_time_service::MockTimeService;
+use aptos_types::account_address::from_identity_public_key;
+use futures::future;
+use maplit::hashmap;
+use rand::rngs::OsRng;
+use std::{str::FromStr, sync::Arc};
+
+const EXCHANGE_INTERVAL: Duration = Duration::from_secs(60);
+
+struct TestHarness {
+    network_context: NetworkContext,
+    mock_time: MockTimeService,
+    peer_mgr_reqs_rx: aptos_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
+    peer_mgr_notifs_tx: aptos_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>,
+    conn_mgr_reqs_rx: aptos_channels::Receiver<ConnectivityRequest>,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    _connection_notifs_tx: conn_notifs_channel::Sender,
+}
+
+impl TestHarness {
+    fn new(max_exchanged_peers: usize) -> (Self, PeerExchange<NetworkClient<PeerExchangeMsg>>) {
+        ::aptos_logger::Logger::init_for_testing();
+        let mock_time = TimeService::mock();
+
+        let (peer_mgr_reqs_tx, peer_mgr_reqs_rx) = aptos_channel::new(QueueStyle::FIFO, 1, None);
+        let (connection_reqs_tx, _connection_reqs_rx) =
+            aptos_channel::new(QueueStyle::FIFO, 1, None);
+        let (peer_mgr_notifs_tx, peer_mgr_notifs_rx) =
+            aptos_channel::new(QueueStyle::FIFO, 1, None);
+        let (connection_notifs_tx, connection_notifs_rx) = conn_notifs_channel::new();
+        let (conn_mgr_reqs_tx, conn_mgr_reqs_rx) =
+            aptos_channels::new(1, &counters::PENDING_CONNECTIVITY_MANAGER_REQUESTS);
+
+        let network_sender = NetworkSender::new(
+            PeerManagerRequestSender::new(peer_mgr_reqs_tx),
+            ConnectionRequestSender::new(connection_reqs_tx),
+        );
+        let network_events =
+            PeerExchangeNetworkEvents::new(peer_mgr_notifs_rx, connection_notifs_rx, None);
+
+        let network_context =
+            NetworkContext::new(RoleType::FullNode, NetworkId::Public, PeerId::random());
+        let peers_and_metadata = PeersAndMetadata::new(&[network_context.network_id()]);
+        let network_client = NetworkClient::new(
+            vec![],
+            vec![ProtocolId::PeerExchangeRpc],
+            hashmap! {network_context.network_id() => network_sender},
+            peers_and_metadata.clone(),
+        );
+
+        let config = PeerExchangeConfig {
+            exchange_interval_ms: EXCHANGE_INTERVAL.as_millis() as u64,
+            max_peers_per_response: 3,
+            max_exchanged_peers,
+            ..Default::default()
+        };
+        let peer_exchange = PeerExchange::new(
+            network_context,
+            mock_time.clone(),
+            config,
+            network_client,
+            network_events,
+            conn_mgr_reqs_tx,
+        );
+
+        (
+            Self {
+                network_context,
+                mock_time: mock_time.into_mock(),
+                peer_mgr_reqs_rx,
+                peer_mgr_notifs_tx,
+                conn_mgr_reqs_rx,
+                peers_and_metadata,
+                _connection_notifs_tx: connection_notifs_tx,
+            },
+            peer_exchange,
+        )
+    }
+
+    /// Adds a connected peer (that supports the peer exchange) to the peers and metadata
+    fn connect_peer(&self, peer_id: PeerId, address: NetworkAddress, origin: ConnectionOrigin) {
+        let mut connection_metadata =
+            ConnectionMetadata::mock_with_role_and_origin(peer_id, PeerRole::Unknown, origin);
+        connection_metadata.addr = address;
+        connection_metadata.application_protocols =
+            ProtocolIdSet::from_iter(vec![ProtocolId::PeerExchangeRpc]);
+        self.peers_and_metadata
+            .insert_connection_metadata(
+                PeerNetworkId::new(self.network_context.network_id(), peer_id),
+                connection_metadata,
+            )
+            .unwrap();
+    }
+
+    async fn trigger_exchange(&self) {
+        self.mock_time.advance_async(EXCHANGE_INTERVAL).await;
+    }
+
+    /// Expects a get peers request, and responds with the given peers
+    async fn expect_get_peers_request(
+        &mut self,
+        expected_peer_id: PeerId,
+        peers: Vec<ExchangedPeer>,
+    ) {
+        let req = self.peer_mgr_reqs_rx.next().await.unwrap();
+        let (peer_id, rpc_req) = match req {
+            PeerManagerRequest::SendRpc(peer_id, rpc_req) => (peer_id, rpc_req),
+            _ => panic!("Unexpected PeerManagerRequest: {:?}", req),
+        };
+        assert_eq!(peer_id, expected_peer_id);
+        assert_eq!(rpc_req.protocol_id, ProtocolId::PeerExchangeRpc);
+        assert_eq!(
+            bcs::from_bytes::<PeerExchangeMsg>(&rpc_req.data).unwrap(),
+            PeerExchangeMsg::GetPeersRequest
+        );
+
+        let res_data = bcs::to_bytes(&PeerExchangeMsg::GetPeersResponse(peers)).unwrap();
+        rpc_req.res_tx.send(Ok(res_data.into())).unwrap();
+    }
+
+    /// Expects the exchanged peers to be sent to the connectivity manager
+    async fn expect_discovered_peers(&mut self, expected_peers: Vec<ExchangedPeer>) {
+        let req = self.conn_mgr_reqs_rx.next().await.unwrap();
+        let peers = match req {
+            ConnectivityRequest::UpdateDiscoveredPeers(DiscoverySource::PeerExchange, peers) => {
+                peers
+            },
+            _ => panic!("Unexpected ConnectivityRequest: {:?}", req),
+        };
+        let expected_peers: PeerSet = expected_peers
+            .into_iter()
+            .map(|peer| {
+                (
+                    peer.peer_id,
+                    Peer::from_addrs(PeerRole::Upstream, vec![peer.address]),
+                )
+            })
+            .collect();
+        assert_eq!(peers, expected_peers);
+    }
+
+    async fn send_inbound_request(
+        &mut self,
+        peer_id: PeerId,
+        message: PeerExchangeMsg,
+    ) -> oneshot::Receiver<Result<Bytes, RpcError>> {
+        let protocol_id = ProtocolId::PeerExchangeRpc;
+        let data = bcs::to_bytes(&message).unwrap().into();
+        let (res_tx, res_rx) = oneshot::channel();
+        let inbound_rpc_req = InboundRpcRequest {
+            protocol_id,
+            data,
+            res_tx,
+        };
+        let key = (peer_id, protocol_id);
+        let (delivered_tx, delivered_rx) = oneshot::channel();
+        self.peer_mgr_notifs_tx
+            .push_with_feedback(
+                key,
+                PeerManagerNotification::RecvRpc(peer_id, inbound_rpc_req),
+                Some(delivered_tx),
+            )
+            .unwrap();
+        delivered_rx.await.unwrap();
+        res_rx
+    }
+}
+
+/// Returns a dialable address with the given IP address
+fn address(ip: &str) -> NetworkAddress {
+    NetworkAddress::from_str(&format!(
+        "/ip4/{}/tcp/6182/noise-ik/080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120/handshake/0",
+        ip
+    ))
+    .unwrap()
+}
+
+/// Returns a peer with a new key, dialable at the given IP address
+fn exchanged_peer(ip: &str) -> ExchangedPeer {
+    let public_key = x25519::PrivateKey::generate(&mut OsRng).public_key();
+    ExchangedPeer {
+        peer_id: from_identity_public_key(public_key),
+        address: NetworkAddress::from_str(&format!("/ip4/{}/tcp/6182", ip))
+            .unwrap()
+            .append_prod_protos(public_key, HANDSHAKE_VERSION),
+    }
+}
+
+#[tokio::test]
+async fn inbound_get_peers() {
+    let (mut harness, peer_exchange) = TestHarness::new(10);
+
+    let test = async move {
+        // Connect to a peer that we dialed at a public address
+        let dialed_peer = exchanged_peer("1.2.3.4");
+        harness.connect_peer(
+            dialed_peer.peer_id,
+            dialed_peer.address.clone(),
+            ConnectionOrigin::Outbound,
+        );
+
+        // Connect to peers that can't be shared: a peer dialed at a private
+        // address, and a peer that dialed us.
+        harness.connect_peer(
+            PeerId::random(),
+            address("10.0.0.1"),
+            ConnectionOrigin::Outbound,
+        );
+        harness.connect_peer(
+            PeerId::random(),
+            address("1.2.3.5"),
+            ConnectionOrigin::Inbound,
+        );
+
+        // Connect to the requesting peer (which shouldn't get its own address)
+        let requesting_peer_id = PeerId::random();
+        harness.connect_peer(
+            requesting_peer_id,
+            address("1.2.3.6"),
+            ConnectionOrigin::Outbound,
+        );
+
+        // Verify that only the dialed peer is shared
+        let res_rx = harness
+            .send_inbound_request(requesting_peer_id, PeerExchangeMsg::GetPeersRequest)
+            .await;
+        let res_data = res_rx.await.unwrap().unwrap();
+        assert_eq!(
+            bcs::from_bytes::<PeerExchangeMsg>(&res_data).unwrap(),
+            PeerExchangeMsg::GetPeersResponse(vec![dialed_peer])
+        );
+    };
+    future::join(peer_exchange.start(), test).await;
+}
+
+#[tokio::test]
+async fn outbound_get_peers() {
+    let (mut harness, peer_exchange) = TestHarness::new(10);
+
+    let test = async move {
+        // Trigger an exchange. This should do nothing, as there are no peers.
+        harness.trigger_exchange().await;
+
+        // Connect to a peer and trigger an exchange
+        let peer_id = PeerId::random();
+        harness.connect_peer(peer_id, address("1.2.3.4"), ConnectionOrigin::Outbound);
+        harness.trigger_exchange().await;
+
+        // Respond with a valid peer, a private peer, ourselves and a peer with
+        // the key of another peer
+        let valid_peer = exchanged_peer("1.2.3.5");
+        let private_peer = exchanged_peer("192.168.0.1");
+        let self_peer = ExchangedPeer {
+            peer_id: harness.network_context.peer_id(),
+            address: address("1.2.3.6"),
+        };
+        let mismatched_peer = ExchangedPeer {
+            peer_id: PeerId::random(),
+            address: exchanged_peer("1.2.3.7").address,
+        };
+        harness
+            .expect_get_peers_request(peer_id, vec![
+                valid_peer.clone(),
+                private_peer,
+                self_peer,
+                mismatched_peer,
+            ])
+            .await;
+
+        // Verify that only the valid peer is discovered
+        harness.expect_discovered_peers(vec![valid_peer]).await;
+    };
+    future::join(peer_exchange.start(), test).await;
+}
+
+#[tokio::test]
+async fn exchanged_peers_are_bounded() {
+    let (mut harness, peer_exchange) = TestHarness::new(2);
+
+    let test = async move {
+        // Connect to a peer
+        let peer_id = PeerId::random();
+        harness.connect_peer(peer_id, address("1.2.3.4"), ConnectionOrigin::Outbound);
+
+        // Exchange two peers
+        let old_peer = exchanged_peer("1.2.3.5");
+        let refreshed_peer = exchanged_peer("1.2.3.6");
+        harness.trigger_exchange().await;
+        harness
+            .expect_get_peers_request(peer_id, vec![old_peer.clone(), refreshed_peer.clone()])
+            .await;
+        harness
+            .expect_discovered_peers(vec![old_peer, refreshed_peer.clone()])
+            .await;
+
+        // Exchange a new peer and refresh one of the existing peers, and
+        // verify that the oldest peer is forgotten.
+        let new_peer = exchanged_peer("1.2.3.7");
+        harness.trigger_exchange().await;
+        harness
+            .expect_get_peers_request(peer_id, vec![new_peer.clone(), refreshed_peer.clone()])
+            .await;
+        harness
+            .expect_discovered_peers(vec![new_peer, refreshed_peer])
+            .await;
+    };
+    future::join(peer_exchange.start(), test).await;
+}
+
+#[test]
+fn shareable_addresses() {
+    // Public addresses are shareable
+    assert!(is_shareable_address(&address("1.2.3.4")));
+    assert!(is_shareable_address(
+        &NetworkAddress::from_str("/dns/example.com/tcp/6182/noise-ik/080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120/handshake/0")
+            .unwrap()
+    ));
+
+    // Private and local addresses are not
+    for ip in [
+        "10.1.2.3",
+        "172.16.0.1",
+        "192.168.1.1",
+        "127.0.0.1",
+        "0.0.0.0",
+    ] {
+        assert!(!is_shareable_address(&address(ip)));
+    }
+
+    // Neither are addresses without a noise key
+    assert!(!is_shareable_address(
+        &NetworkAddress::from_str("/ip4/1.2.3.4/tcp/6182").unwrap()
+    ));
+}
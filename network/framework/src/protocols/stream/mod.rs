@@ -82,6 +82,13 @@ impl InboundStreamBuffer {
         }
     }
 
+    /// The number of fragments of the message being reassembled, if any.
+    pub fn num_fragments(&self) -> usize {
+        self.stream
+            .as_ref()
+            .map_or(0, |stream| stream.num_fragments as usize)
+    }
+
     pub fn append_fragment(
         &mut self,
         fragment: StreamFragment,
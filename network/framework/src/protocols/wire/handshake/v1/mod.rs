@@ -15,9 +15,13 @@
 
 use crate::counters::{start_serialization_timer, DESERIALIZATION_LABEL, SERIALIZATION_LABEL};
 use anyhow::anyhow;
-use aptos_compression::metrics::CompressionClient;
-use aptos_config::{config::MAX_APPLICATION_MESSAGE_SIZE, network_id::NetworkId};
+use aptos_compression::{metrics::CompressionClient, ZstdDictionary, ZstdOptions};
+use aptos_config::{
+    config::{NetworkCompressionConfig, ZstdCompressionConfig, MAX_APPLICATION_MESSAGE_SIZE},
+    network_id::NetworkId,
+};
 use aptos_types::chain_id::ChainId;
+use once_cell::sync::{Lazy, OnceCell};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -57,15 +61,148 @@ pub enum ProtocolId {
     PeerMonitoringServiceRpc = 10,
     ConsensusRpcCompressed = 11,
     ConsensusDirectSendCompressed = 12,
+    ConsensusRpcZstd = 13,
+    ConsensusDirectSendZstd = 14,
+    MempoolDirectSendZstd = 15,
+    StorageServiceRpcZstd = 16,
+    PeerExchangeRpc = 17,
+    MempoolPrivateDirectSend = 18, // Announces support of private transaction broadcasts
 }
 
 /// The encoding types for Protocols
 enum Encoding {
     Bcs(usize),
     CompressedBcs(usize),
+    ZstdBcs(usize),
     Json,
 }
 
+/// The zstd options of the zstd encoded protocols, set once at startup
+static ZSTD_PROTOCOL_OPTIONS: OnceCell<ZstdProtocolOptions> = OnceCell::new();
+
+/// The zstd options used if none were set (e.g., in tests)
+static DEFAULT_ZSTD_PROTOCOL_OPTIONS: Lazy<ZstdProtocolOptions> = Lazy::new(|| {
+    ZstdProtocolOptions::from_config(&NetworkCompressionConfig::default())
+        .expect("The default compression config has no dictionaries to read!")
+});
+
+/// The zstd options of each compression client
+#[derive(Clone, Debug)]
+pub struct ZstdProtocolOptions {
+    consensus: ZstdOptions,
+    mempool: ZstdOptions,
+    state_sync: ZstdOptions,
+}
+
+impl ZstdProtocolOptions {
+    /// Creates the options from the given config, reading the configured dictionaries
+    pub fn from_config(config: &NetworkCompressionConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            consensus: zstd_options_from_config(&config.consensus)?,
+            mempool: zstd_options_from_config(&config.mempool)?,
+            state_sync: zstd_options_from_config(&config.state_sync)?,
+        })
+    }
+
+    fn get(&self, client: CompressionClient) -> &ZstdOptions {
+        match client {
+            CompressionClient::Consensus => &self.consensus,
+            CompressionClient::Mempool => &self.mempool,
+            CompressionClient::StateSync => &self.state_sync,
+        }
+    }
+
+    /// Returns the IDs of the dictionaries of each compression client
+    pub fn dictionary_ids(&self) -> ZstdDictionaryIds {
+        let id = |options: &ZstdOptions| {
+            options
+                .dictionary
+                .as_ref()
+                .map_or(0, |dictionary| dictionary.id())
+        };
+        ZstdDictionaryIds {
+            consensus: id(&self.consensus),
+            mempool: id(&self.mempool),
+            state_sync: id(&self.state_sync),
+        }
+    }
+}
+
+/// The IDs of the zstd dictionaries (0 if none) of each compression client.
+/// These are exchanged in the handshake (see [`ProtocolIdSet`]), as messages
+/// compressed with a dictionary can only be decompressed with the same dictionary.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ZstdDictionaryIds {
+    pub consensus: u32,
+    pub mempool: u32,
+    pub state_sync: u32,
+}
+
+impl ZstdDictionaryIds {
+    fn get(&self, client: CompressionClient) -> u32 {
+        match client {
+            CompressionClient::Consensus => self.consensus,
+            CompressionClient::Mempool => self.mempool,
+            CompressionClient::StateSync => self.state_sync,
+        }
+    }
+
+    fn get_mut(&mut self, client: CompressionClient) -> &mut u32 {
+        match client {
+            CompressionClient::Consensus => &mut self.consensus,
+            CompressionClient::Mempool => &mut self.mempool,
+            CompressionClient::StateSync => &mut self.state_sync,
+        }
+    }
+}
+
+/// The first bit of a [`ProtocolIdSet`] that is reserved for the zstd
+/// dictionary IDs. All [`ProtocolId`]s must stay below it.
+const ZSTD_DICTIONARY_IDS_FIRST_BIT: u16 = 160;
+
+/// The compression clients, in the order of their dictionary IDs in a
+/// [`ProtocolIdSet`]. Each ID takes 32 bits, so the last one ends at bit 255.
+const ZSTD_DICTIONARY_CLIENTS: [CompressionClient; 3] = [
+    CompressionClient::Consensus,
+    CompressionClient::Mempool,
+    CompressionClient::StateSync,
+];
+
+fn zstd_options_from_config(config: &ZstdCompressionConfig) -> anyhow::Result<ZstdOptions> {
+    let dictionary = match &config.dictionary_path {
+        Some(path) => {
+            let bytes = std::fs::read(path).map_err(|error| {
+                anyhow!("Failed to read the zstd dictionary {:?}: {}", path, error)
+            })?;
+            Some(ZstdDictionary::new(bytes).map_err(|error| anyhow!("{:?}: {}", path, error))?)
+        },
+        None => None,
+    };
+    Ok(ZstdOptions {
+        level: config.level,
+        dictionary,
+    })
+}
+
+/// Sets the zstd options of the zstd encoded protocols. Only the first call
+/// has an effect, so this should be called at startup, before any messages
+/// are sent or received.
+pub fn set_zstd_protocol_options_once(options: ZstdProtocolOptions) {
+    ZSTD_PROTOCOL_OPTIONS.set(options).ok();
+}
+
+fn get_zstd_protocol_options() -> &'static ZstdProtocolOptions {
+    ZSTD_PROTOCOL_OPTIONS
+        .get()
+        .unwrap_or(&DEFAULT_ZSTD_PROTOCOL_OPTIONS)
+}
+
+/// Returns the IDs of the dictionaries of the zstd encoded protocols, to
+/// announce in the handshake
+pub fn get_zstd_dictionary_ids() -> ZstdDictionaryIds {
+    get_zstd_protocol_options().dictionary_ids()
+}
+
 impl ProtocolId {
     pub fn as_str(self) -> &'static str {
         use ProtocolId::*;
@@ -83,6 +220,12 @@ impl ProtocolId {
             PeerMonitoringServiceRpc => "PeerMonitoringServiceRpc",
             ConsensusRpcCompressed => "ConsensusRpcCompressed",
             ConsensusDirectSendCompressed => "ConsensusDirectSendCompressed",
+            ConsensusRpcZstd => "ConsensusRpcZstd",
+            ConsensusDirectSendZstd => "ConsensusDirectSendZstd",
+            MempoolDirectSendZstd => "MempoolDirectSendZstd",
+            StorageServiceRpcZstd => "StorageServiceRpcZstd",
+            PeerExchangeRpc => "PeerExchangeRpc",
+            MempoolPrivateDirectSend => "MempoolPrivateDirectSend",
         }
     }
 
@@ -102,6 +245,12 @@ impl ProtocolId {
             ProtocolId::PeerMonitoringServiceRpc,
             ProtocolId::ConsensusRpcCompressed,
             ProtocolId::ConsensusDirectSendCompressed,
+            ProtocolId::ConsensusRpcZstd,
+            ProtocolId::ConsensusDirectSendZstd,
+            ProtocolId::MempoolDirectSendZstd,
+            ProtocolId::StorageServiceRpcZstd,
+            ProtocolId::PeerExchangeRpc,
+            ProtocolId::MempoolPrivateDirectSend,
         ]
     }
 
@@ -112,19 +261,36 @@ impl ProtocolId {
             ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
                 Encoding::CompressedBcs(RECURSION_LIMIT)
             },
-            ProtocolId::MempoolDirectSend => Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT),
-            ProtocolId::MempoolRpc => Encoding::Bcs(USER_INPUT_RECURSION_LIMIT),
+            ProtocolId::MempoolDirectSend | ProtocolId::MempoolPrivateDirectSend => {
+                Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT)
+            },
+            ProtocolId::ConsensusDirectSendZstd
+            | ProtocolId::ConsensusRpcZstd
+            | ProtocolId::StorageServiceRpcZstd => Encoding::ZstdBcs(RECURSION_LIMIT),
+            ProtocolId::MempoolDirectSendZstd => Encoding::ZstdBcs(USER_INPUT_RECURSION_LIMIT),
+            ProtocolId::MempoolRpc | ProtocolId::PeerExchangeRpc => {
+                Encoding::Bcs(USER_INPUT_RECURSION_LIMIT)
+            },
             _ => Encoding::Bcs(RECURSION_LIMIT),
         }
     }
 
+    /// Returns true iff the messages of the protocol are compressed with zstd
+    pub fn is_zstd_encoded(self) -> bool {
+        matches!(self.encoding(), Encoding::ZstdBcs(_))
+    }
+
     /// Returns the compression client label based on the current protocol id
     fn get_compression_client(self) -> CompressionClient {
         match self {
-            ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
-                CompressionClient::Consensus
-            },
-            ProtocolId::MempoolDirectSend => CompressionClient::Mempool,
+            ProtocolId::ConsensusDirectSendCompressed
+            | ProtocolId::ConsensusRpcCompressed
+            | ProtocolId::ConsensusDirectSendZstd
+            | ProtocolId::ConsensusRpcZstd => CompressionClient::Consensus,
+            ProtocolId::MempoolDirectSend
+            | ProtocolId::MempoolDirectSendZstd
+            | ProtocolId::MempoolPrivateDirectSend => CompressionClient::Mempool,
+            ProtocolId::StorageServiceRpcZstd => CompressionClient::StateSync,
             protocol_id => unreachable!(
                 "The given protocol ({:?}) should not be using compression!",
                 protocol_id
@@ -151,6 +317,18 @@ impl ProtocolId {
                 )
                 .map_err(|e| anyhow!("{:?}", e))
             },
+            Encoding::ZstdBcs(limit) => {
+                let compression_client = self.get_compression_client();
+                let bcs_bytes = self.bcs_encode(value, limit)?;
+                let options = get_zstd_protocol_options().get(compression_client.clone());
+                aptos_compression::compress_zstd(
+                    bcs_bytes,
+                    compression_client,
+                    MAX_APPLICATION_MESSAGE_SIZE,
+                    options,
+                )
+                .map_err(|e| anyhow!("{:?}", e))
+            },
             Encoding::Json => serde_json::to_vec(value).map_err(|e| anyhow!("{:?}", e)),
         };
 
@@ -181,6 +359,18 @@ impl ProtocolId {
                 .map_err(|e| anyhow! {"{:?}", e})?;
                 self.bcs_decode(&raw_bytes, limit)
             },
+            Encoding::ZstdBcs(limit) => {
+                let compression_client = self.get_compression_client();
+                let options = get_zstd_protocol_options().get(compression_client.clone());
+                let raw_bytes = aptos_compression::decompress_zstd(
+                    bytes,
+                    compression_client,
+                    MAX_APPLICATION_MESSAGE_SIZE,
+                    options,
+                )
+                .map_err(|e| anyhow!("{:?}", e))?;
+                self.bcs_decode(&raw_bytes, limit)
+            },
             Encoding::Json => serde_json::from_slice(bytes).map_err(|e| anyhow!("{:?}", e)),
         };
 
@@ -225,6 +415,11 @@ impl fmt::Display for ProtocolId {
 /// These sets are sent over-the-wire in the initial [`HandshakeMsg`] to other
 /// AptosNet peers in order to negotiate the set of common supported protocols for
 /// use on a new AptosNet connection.
+///
+/// The bits from [`ZSTD_DICTIONARY_IDS_FIRST_BIT`] up to 255 don't represent
+/// protocols, but carry the [`ZstdDictionaryIds`] of the node. Nodes that don't
+/// know about the dictionary IDs ignore these bits, like any other unknown
+/// protocol.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub struct ProtocolIdSet(aptos_bitvec::BitVec);
@@ -274,6 +469,52 @@ impl ProtocolIdSet {
     pub fn insert(&mut self, protocol: ProtocolId) {
         self.0.set(protocol as u16)
     }
+
+    /// Returns the set with the given zstd dictionary IDs, replacing any
+    /// previous ones.
+    pub fn with_zstd_dictionary_ids(&self, dictionary_ids: ZstdDictionaryIds) -> ProtocolIdSet {
+        let mut protocols = self.without_zstd_dictionary_ids();
+        for (index, client) in ZSTD_DICTIONARY_CLIENTS.into_iter().enumerate() {
+            let dictionary_id = dictionary_ids.get(client);
+            for bit in 0..32 {
+                if dictionary_id & (1 << bit) != 0 {
+                    protocols
+                        .0
+                        .set(ZSTD_DICTIONARY_IDS_FIRST_BIT + 32 * index as u16 + bit);
+                }
+            }
+        }
+        protocols
+    }
+
+    /// Returns the zstd dictionary IDs of the set (0 for each client if none
+    /// were set, e.g., by nodes that don't know about the dictionary IDs).
+    pub fn zstd_dictionary_ids(&self) -> ZstdDictionaryIds {
+        let mut dictionary_ids = ZstdDictionaryIds::default();
+        for (index, client) in ZSTD_DICTIONARY_CLIENTS.into_iter().enumerate() {
+            let dictionary_id = dictionary_ids.get_mut(client);
+            for bit in 0..32 {
+                if self
+                    .0
+                    .is_set(ZSTD_DICTIONARY_IDS_FIRST_BIT + 32 * index as u16 + bit)
+                {
+                    *dictionary_id |= 1 << bit;
+                }
+            }
+        }
+        dictionary_ids
+    }
+
+    /// Returns the set without the bits of the zstd dictionary IDs.
+    fn without_zstd_dictionary_ids(&self) -> ProtocolIdSet {
+        ProtocolIdSet(
+            self.0
+                .iter_ones()
+                .filter(|idx| *idx < ZSTD_DICTIONARY_IDS_FIRST_BIT as usize)
+                .map(|idx| idx as u8)
+                .collect(),
+        )
+    }
 }
 
 impl FromIterator<ProtocolId> for ProtocolIdSet {
@@ -366,9 +607,19 @@ impl HandshakeMsg {
         }
     }
 
+    /// Returns the message with the given zstd dictionary IDs announced for
+    /// every messaging protocol version
+    pub fn with_zstd_dictionary_ids(mut self, dictionary_ids: ZstdDictionaryIds) -> Self {
+        for protocols in self.supported_protocols.values_mut() {
+            *protocols = protocols.with_zstd_dictionary_ids(dictionary_ids);
+        }
+        self
+    }
+
     /// This function:
     /// 1. verifies that both HandshakeMsg are compatible and
-    /// 2. finds out the intersection of protocols that is supported
+    /// 2. finds out the intersection of protocols that is supported, excluding
+    ///    the zstd encoded protocols the peers have different dictionaries for
     pub fn perform_handshake(
         &self,
         other: &HandshakeMsg,
@@ -393,7 +644,8 @@ impl HandshakeMsg {
         // at least one common ProtocolId.
         for (our_handshake_version, our_protocols) in self.supported_protocols.iter().rev() {
             if let Some(their_protocols) = other.supported_protocols.get(our_handshake_version) {
-                let common_protocols = our_protocols.intersect(their_protocols);
+                let common_protocols =
+                    Self::remove_mismatched_zstd_protocols(our_protocols, their_protocols);
 
                 if !common_protocols.is_empty() {
                     return Ok((*our_handshake_version, common_protocols));
@@ -404,6 +656,29 @@ impl HandshakeMsg {
         // no intersection found
         Err(HandshakeError::NoCommonProtocols)
     }
+
+    /// Returns the protocols in common, without the zstd encoded protocols for
+    /// which the peers have different dictionaries, so that the peers fall back
+    /// to the LZ4 ones.
+    fn remove_mismatched_zstd_protocols(
+        our_protocols: &ProtocolIdSet,
+        their_protocols: &ProtocolIdSet,
+    ) -> ProtocolIdSet {
+        let our_dictionary_ids = our_protocols.zstd_dictionary_ids();
+        let their_dictionary_ids = their_protocols.zstd_dictionary_ids();
+        our_protocols
+            .without_zstd_dictionary_ids()
+            .intersect(&their_protocols.without_zstd_dictionary_ids())
+            .iter()
+            .filter(|protocol| {
+                if !protocol.is_zstd_encoded() {
+                    return true;
+                }
+                let client = protocol.get_compression_client();
+                our_dictionary_ids.get(client.clone()) == their_dictionary_ids.get(client)
+            })
+            .collect()
+    }
 }
 
 impl fmt::Debug for HandshakeMsg {
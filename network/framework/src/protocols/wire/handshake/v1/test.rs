@@ -183,3 +183,182 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn zstd_protocols_fall_back_to_lz4() {
+    // A peer that doesn't know the zstd protocols only has the LZ4 ones in common
+    let zstd_protos = ProtocolIdSet::from_iter([
+        ProtocolId::MempoolDirectSendZstd,
+        ProtocolId::MempoolDirectSend,
+    ]);
+    let zstd_hs = HandshakeMsg::from_supported(zstd_protos.clone());
+    let lz4_hs =
+        HandshakeMsg::from_supported(ProtocolIdSet::from_iter([ProtocolId::MempoolDirectSend]));
+    let (_, common_protos) = zstd_hs.perform_handshake(&lz4_hs).unwrap();
+    assert_eq!(
+        common_protos,
+        ProtocolIdSet::from_iter([ProtocolId::MempoolDirectSend])
+    );
+
+    // Peers that both know the zstd protocols have all of them in common
+    let (_, common_protos) = zstd_hs
+        .perform_handshake(&HandshakeMsg::from_supported(zstd_protos.clone()))
+        .unwrap();
+    assert_eq!(common_protos, zstd_protos);
+}
+
+#[test]
+fn zstd_encoding() {
+    let message: Vec<u64> = (0..10_000).map(|i| i % 100).collect();
+    for protocol in [
+        ProtocolId::ConsensusRpcZstd,
+        ProtocolId::ConsensusDirectSendZstd,
+        ProtocolId::MempoolDirectSendZstd,
+        ProtocolId::StorageServiceRpcZstd,
+    ] {
+        let bytes = protocol.to_bytes(&message).unwrap();
+        assert!(bytes.len() < bcs::to_bytes(&message).unwrap().len());
+        assert_eq!(protocol.from_bytes::<Vec<u64>>(&bytes).unwrap(), message);
+
+        // Messages of the LZ4 protocols can't be decoded as zstd
+        let lz4_bytes = ProtocolId::MempoolDirectSend.to_bytes(&message).unwrap();
+        assert!(protocol.from_bytes::<Vec<u64>>(&lz4_bytes).is_err());
+    }
+}
+
+#[test]
+fn protocols_are_below_zstd_dictionary_ids() {
+    for protocol in ProtocolId::all() {
+        assert!((*protocol as u16) < ZSTD_DICTIONARY_IDS_FIRST_BIT);
+    }
+}
+
+#[test]
+fn zstd_dictionary_ids_in_protocol_set() {
+    let protos = ProtocolIdSet::from_iter([
+        ProtocolId::ConsensusRpcZstd,
+        ProtocolId::MempoolDirectSendZstd,
+    ]);
+    assert_eq!(protos.zstd_dictionary_ids(), ZstdDictionaryIds::default());
+
+    let dictionary_ids = ZstdDictionaryIds {
+        consensus: u32::MAX,
+        mempool: 0,
+        state_sync: 0x8000_0001,
+    };
+    let protos_with_ids = protos.with_zstd_dictionary_ids(dictionary_ids);
+    assert_eq!(protos_with_ids.zstd_dictionary_ids(), dictionary_ids);
+
+    // The dictionary IDs are not protocols
+    assert_eq!(ProtocolIdSet::from_iter(protos_with_ids.iter()), protos);
+
+    // Setting the dictionary IDs again replaces the previous ones
+    let new_dictionary_ids = ZstdDictionaryIds {
+        consensus: 1,
+        mempool: 2,
+        state_sync: 3,
+    };
+    assert_eq!(
+        protos_with_ids
+            .with_zstd_dictionary_ids(new_dictionary_ids)
+            .zstd_dictionary_ids(),
+        new_dictionary_ids
+    );
+}
+
+#[test]
+fn zstd_protocols_with_mismatched_dictionaries_fall_back_to_lz4() {
+    let protos = ProtocolIdSet::from_iter([
+        ProtocolId::ConsensusDirectSendZstd,
+        ProtocolId::ConsensusDirectSendCompressed,
+        ProtocolId::MempoolDirectSendZstd,
+        ProtocolId::MempoolDirectSend,
+    ]);
+    let dictionary_ids = ZstdDictionaryIds {
+        consensus: 1,
+        mempool: 2,
+        state_sync: 0,
+    };
+    let hs = HandshakeMsg::from_supported(protos.clone()).with_zstd_dictionary_ids(dictionary_ids);
+
+    // Peers with the same dictionaries have all protocols in common
+    let (_, common_protos) = hs.perform_handshake(&hs.clone()).unwrap();
+    assert_eq!(common_protos, protos);
+
+    // Only the zstd protocols with different dictionaries are dropped
+    let other_hs =
+        HandshakeMsg::from_supported(protos.clone()).with_zstd_dictionary_ids(ZstdDictionaryIds {
+            mempool: 3,
+            ..dictionary_ids
+        });
+    let expected_protos = ProtocolIdSet::from_iter([
+        ProtocolId::ConsensusDirectSendZstd,
+        ProtocolId::ConsensusDirectSendCompressed,
+        ProtocolId::MempoolDirectSend,
+    ]);
+    let (_, common_protos) = hs.perform_handshake(&other_hs).unwrap();
+    assert_eq!(common_protos, expected_protos);
+    let (_, common_protos) = other_hs.perform_handshake(&hs).unwrap();
+    assert_eq!(common_protos, expected_protos);
+
+    // A peer without dictionaries doesn't have the same ones either
+    let no_dictionaries_hs = HandshakeMsg::from_supported(protos);
+    let expected_protos = ProtocolIdSet::from_iter([
+        ProtocolId::ConsensusDirectSendCompressed,
+        ProtocolId::MempoolDirectSend,
+    ]);
+    let (_, common_protos) = hs.perform_handshake(&no_dictionaries_hs).unwrap();
+    assert_eq!(common_protos, expected_protos);
+    let (_, common_protos) = no_dictionaries_hs.perform_handshake(&hs).unwrap();
+    assert_eq!(common_protos, expected_protos);
+
+    // Without a fallback, there are no common protocols
+    let zstd_only_protos = ProtocolIdSet::from_iter([ProtocolId::MempoolDirectSendZstd]);
+    let zstd_only_hs = HandshakeMsg::from_supported(zstd_only_protos.clone())
+        .with_zstd_dictionary_ids(ZstdDictionaryIds {
+            mempool: 2,
+            ..ZstdDictionaryIds::default()
+        });
+    assert_eq!(
+        zstd_only_hs
+            .perform_handshake(&HandshakeMsg::from_supported(zstd_only_protos))
+            .unwrap_err(),
+        HandshakeError::NoCommonProtocols,
+    );
+}
+
+#[test]
+fn handshake_msg_with_dictionaries_decodes_with_legacy_decoder() {
+    /// The layout of the handshake message known to nodes that don't know
+    /// about the zstd dictionary IDs
+    #[derive(Deserialize)]
+    struct LegacyHandshakeMsg {
+        supported_protocols: BTreeMap<MessagingProtocolVersion, ProtocolIdSet>,
+        chain_id: ChainId,
+        network_id: NetworkId,
+    }
+
+    let protos = ProtocolIdSet::from_iter([
+        ProtocolId::MempoolDirectSendZstd,
+        ProtocolId::MempoolDirectSend,
+    ]);
+    let hs =
+        HandshakeMsg::from_supported(protos.clone()).with_zstd_dictionary_ids(ZstdDictionaryIds {
+            consensus: 7,
+            mempool: u32::MAX,
+            state_sync: 42,
+        });
+    let bytes = bcs::to_bytes(&hs).unwrap();
+
+    // The legacy decoder accepts the message, and only sees the protocols
+    let legacy_hs: LegacyHandshakeMsg = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(legacy_hs.chain_id, hs.chain_id);
+    assert_eq!(legacy_hs.network_id, hs.network_id);
+    let legacy_protos = &legacy_hs.supported_protocols[&MessagingProtocolVersion::V1];
+    assert_eq!(ProtocolIdSet::from_iter(legacy_protos.iter()), protos);
+
+    // A legacy node negotiates the protocols as before
+    let common_protos = protos.intersect(legacy_protos);
+    assert!(!common_protos.is_empty());
+    assert_eq!(ProtocolIdSet::from_iter(common_protos.iter()), protos);
+}
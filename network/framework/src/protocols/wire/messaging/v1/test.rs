@@ -76,6 +76,38 @@ fn stream_message() {
     ],);
 }
 
+#[test]
+fn inbound_stream_buffer_num_fragments() {
+    let mut inbound_stream = InboundStreamBuffer::new(10);
+    assert_eq!(inbound_stream.num_fragments(), 0);
+
+    let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
+        protocol_id: ProtocolId::MempoolDirectSend,
+        priority: 0,
+        raw_msg: vec![0],
+    });
+    inbound_stream
+        .new_stream(StreamHeader {
+            request_id: 42,
+            num_fragments: 2,
+            message,
+        })
+        .unwrap();
+    assert_eq!(inbound_stream.num_fragments(), 2);
+
+    for fragment_id in 1..=2 {
+        let message = inbound_stream
+            .append_fragment(StreamFragment {
+                request_id: 42,
+                fragment_id,
+                raw_data: vec![fragment_id],
+            })
+            .unwrap();
+        assert_eq!(message.is_some(), fragment_id == 2);
+    }
+    assert_eq!(inbound_stream.num_fragments(), 0);
+}
+
 #[test]
 fn aptosnet_wire_test_vectors() {
     let message = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
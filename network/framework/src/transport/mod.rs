@@ -7,7 +7,9 @@ use crate::{
     noise::{stream::NoiseStream, AntiReplayTimestamps, HandshakeAuthMode, NoiseUpgrader},
     protocols::{
         identity::exchange_handshake,
-        wire::handshake::v1::{HandshakeMsg, MessagingProtocolVersion, ProtocolIdSet},
+        wire::handshake::v1::{
+            get_zstd_dictionary_ids, HandshakeMsg, MessagingProtocolVersion, ProtocolIdSet,
+        },
     },
 };
 use aptos_config::{
@@ -19,17 +21,23 @@ use aptos_id_generator::{IdGenerator, U32IdGenerator};
 use aptos_logger::prelude::*;
 // Re-exposed for aptos-network-checker
 pub use aptos_netcore::transport::tcp::{resolve_and_connect, TCPBufferCfg, TcpSocket};
-use aptos_netcore::transport::{proxy_protocol, tcp, ConnectionOrigin, Transport};
+use aptos_netcore::transport::{
+    proxy_protocol,
+    quic::{MaybeQuicStreams, QuicStreams},
+    tcp, ConnectionOrigin, Transport,
+};
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{timeout, TimeService, TimeServiceTrait};
 use aptos_types::{
     chain_id::ChainId,
-    network_address::{parse_dns_tcp, parse_ip_tcp, parse_memory, NetworkAddress},
+    network_address::{
+        parse_dns_quic, parse_dns_tcp, parse_ip_quic, parse_ip_tcp, parse_memory, NetworkAddress,
+    },
     PeerId,
 };
 use futures::{
     future::{Future, FutureExt},
-    io::{AsyncRead, AsyncWrite},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
     stream::{Stream, StreamExt, TryStreamExt},
 };
 use serde::{Deserialize, Serialize};
@@ -184,6 +192,8 @@ impl fmt::Display for ConnectionMetadata {
 pub struct Connection<TSocket> {
     pub socket: TSocket,
     pub metadata: ConnectionMetadata,
+    /// Handle for opening additional streams, if the base transport is QUIC.
+    pub quic_streams: Option<QuicStreams>,
 }
 
 /// Convenience function for adding a timeout to a Future that returns an `io::Result`.
@@ -237,12 +247,41 @@ fn add_pp_addr(proxy_protocol_enabled: bool, error: io::Error, addr: &NetworkAdd
     }
 }
 
+/// Label used when exporting TLS keying material for QUIC channel binding.
+const QUIC_CHANNEL_BINDING_LABEL: &[u8] = b"aptosnet quic channel binding";
+
+/// The TLS session underneath a QUIC connection is unauthenticated, so after
+/// the Noise handshake both sides send each other the keying material exported
+/// from their TLS session, over the Noise channel. If the values differ, the
+/// QUIC connection was intercepted and the connection is rejected.
+async fn exchange_channel_binding<T>(
+    quic_streams: &QuicStreams,
+    socket: &mut NoiseStream<T>,
+) -> io::Result<()>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    let own_binding = quic_streams.export_keying_material(QUIC_CHANNEL_BINDING_LABEL)?;
+    socket.write_all(&own_binding).await?;
+    socket.flush().await?;
+
+    let mut remote_binding = [0u8; 32];
+    socket.read_exact(&mut remote_binding).await?;
+    if own_binding != remote_binding {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "QUIC channel binding mismatch",
+        ));
+    }
+    Ok(())
+}
+
 /// Upgrade an inbound connection. This means we run a Noise IK handshake for
 /// authentication and then negotiate common supported protocols. If
 /// `ctxt.noise.auth_mode` is `HandshakeAuthMode::Mutual( anti_replay_timestamps , trusted_peers )`,
 /// then we will only allow connections from peers with a pubkey in the `trusted_peers`
 /// set. Otherwise, we will allow inbound connections from any pubkey.
-async fn upgrade_inbound<T: TSocket>(
+async fn upgrade_inbound<T: TSocket + MaybeQuicStreams>(
     ctxt: Arc<UpgradeContext>,
     fut_socket: impl Future<Output = io::Result<T>>,
     addr: NetworkAddress,
@@ -268,6 +307,7 @@ async fn upgrade_inbound<T: TSocket>(
     } else {
         addr
     };
+    let quic_streams = socket.quic_streams();
 
     // try authenticating via noise handshake
     let (mut socket, remote_peer_id, peer_role) =
@@ -290,12 +330,20 @@ async fn upgrade_inbound<T: TSocket>(
     let remote_pubkey = socket.get_remote_static();
     let addr = addr.append_prod_protos(remote_pubkey, HANDSHAKE_VERSION);
 
+    // bind the noise session to the underlying quic connection
+    if let Some(quic_streams) = &quic_streams {
+        exchange_channel_binding(quic_streams, &mut socket)
+            .await
+            .map_err(|err| add_pp_addr(proxy_protocol_enabled, err, &addr))?;
+    }
+
     // exchange HandshakeMsg
     let handshake_msg = HandshakeMsg {
         supported_protocols: ctxt.supported_protocols.clone(),
         chain_id: ctxt.chain_id,
         network_id: ctxt.network_id,
-    };
+    }
+    .with_zstd_dictionary_ids(get_zstd_dictionary_ids());
     let remote_handshake = exchange_handshake(&handshake_msg, &mut socket)
         .await
         .map_err(|err| add_pp_addr(proxy_protocol_enabled, err, &addr))?;
@@ -328,12 +376,13 @@ async fn upgrade_inbound<T: TSocket>(
             application_protocols,
             peer_role,
         ),
+        quic_streams,
     })
 }
 
 /// Upgrade an outbound connection. This means we run a Noise IK handshake for
 /// authentication and then negotiate common supported protocols.
-pub async fn upgrade_outbound<T: TSocket>(
+pub async fn upgrade_outbound<T: TSocket + MaybeQuicStreams>(
     ctxt: Arc<UpgradeContext>,
     fut_socket: impl Future<Output = io::Result<T>>,
     addr: NetworkAddress,
@@ -342,6 +391,7 @@ pub async fn upgrade_outbound<T: TSocket>(
 ) -> io::Result<Connection<NoiseStream<T>>> {
     let origin = ConnectionOrigin::Outbound;
     let socket = fut_socket.await?;
+    let quic_streams = socket.quic_streams();
 
     // noise handshake
     let (mut socket, peer_role) = ctxt
@@ -372,12 +422,18 @@ pub async fn upgrade_outbound<T: TSocket>(
     // sanity check: Noise IK should always guarantee this is true
     debug_assert_eq!(remote_pubkey, socket.get_remote_static());
 
+    // bind the noise session to the underlying quic connection
+    if let Some(quic_streams) = &quic_streams {
+        exchange_channel_binding(quic_streams, &mut socket).await?;
+    }
+
     // exchange HandshakeMsg
     let handshake_msg = HandshakeMsg {
         supported_protocols: ctxt.supported_protocols.clone(),
         chain_id: ctxt.chain_id,
         network_id: ctxt.network_id,
-    };
+    }
+    .with_zstd_dictionary_ids(get_zstd_dictionary_ids());
     let remote_handshake = exchange_handshake(&handshake_msg, &mut socket).await?;
 
     // try to negotiate common aptosnet version and supported application protocols
@@ -403,6 +459,7 @@ pub async fn upgrade_outbound<T: TSocket>(
             application_protocols,
             peer_role,
         ),
+        quic_streams,
     })
 }
 
@@ -410,7 +467,7 @@ pub async fn upgrade_outbound<T: TSocket>(
 ///
 /// The base transport layer is pluggable, so long as it provides a reliable,
 /// ordered, connection-oriented, byte-stream abstraction (e.g., TCP). We currently
-/// use either `MemoryTransport` or `TcpTransport` as this base layer.
+/// use `MemoryTransport`, `TcpTransport` or `QuicTransport` as this base layer.
 ///
 /// Inbound and outbound connections are first established with the `base_transport`
 /// and then negotiate a secure, authenticated transport layer (currently Noise
@@ -429,7 +486,7 @@ pub struct AptosNetTransport<TTransport> {
 impl<TTransport> AptosNetTransport<TTransport>
 where
     TTransport: Transport<Error = io::Error>,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MaybeQuicStreams,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -482,13 +539,15 @@ where
         let (base_transport_protos, base_transport_suffix) = parse_ip_tcp(protos)
             .map(|x| (&protos[..2], x.1))
             .or_else(|| parse_dns_tcp(protos).map(|x| (&protos[..2], x.1)))
+            .or_else(|| parse_ip_quic(protos).map(|x| (&protos[..2], x.1)))
+            .or_else(|| parse_dns_quic(protos).map(|x| (&protos[..2], x.1)))
             .or_else(|| parse_memory(protos).map(|x| (&protos[..1], x.1)))
             .ok_or_else(|| {
                 io::Error::new(
                     io::ErrorKind::InvalidInput,
                     format!(
                         "Unexpected dialing network address: '{}', expected: \
-                         memory, ip+tcp, or dns+tcp",
+                         memory, ip+tcp, dns+tcp, ip+quic, or dns+quic",
                         addr
                     ),
                 )
@@ -534,6 +593,9 @@ where
     /// `/dns/<ipaddr>/tcp/<port>` or
     /// `/dns4/<ipaddr>/tcp/<port>` or
     /// `/dns6/<ipaddr>/tcp/<port>`
+    ///
+    /// If the base transport is `QuicTransport`, then `/<base_transport>` is
+    /// any of the above with `/quic/<port>` in place of `/tcp/<port>`.
     pub fn dial(
         &self,
         peer_id: PeerId,
@@ -580,6 +642,11 @@ where
     ///
     /// `/ip4/<ipaddr>/tcp/<port>` or
     /// `/ip6/<ipaddr>/tcp/<port>`
+    ///
+    /// If the base transport is `QuicTransport`, then we expect:
+    ///
+    /// `/ip4/<ipaddr>/quic/<port>` or
+    /// `/ip6/<ipaddr>/quic/<port>`
     pub fn listen_on(
         &self,
         addr: NetworkAddress,
@@ -636,7 +703,7 @@ where
 impl<TTransport: Transport> Transport for AptosNetTransport<TTransport>
 where
     TTransport: Transport<Error = io::Error> + Send + 'static,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MaybeQuicStreams,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -12,7 +12,11 @@ use aptos_crypto::{test_utils::TEST_SEED, traits::Uniform, x25519};
 use aptos_infallible::RwLock;
 use aptos_netcore::{
     framing::{read_u16frame, write_u16frame},
-    transport::{memory, ConnectionOrigin, Transport},
+    transport::{
+        memory,
+        quic::{self, MaybeQuicStreams},
+        ConnectionOrigin, Transport,
+    },
 };
 use aptos_time_service::MockTimeService;
 use aptos_types::{
@@ -21,7 +25,11 @@ use aptos_types::{
     PeerId,
 };
 use bytes::{Bytes, BytesMut};
-use futures::{future, io::AsyncWriteExt, stream::StreamExt};
+use futures::{
+    future,
+    io::{AsyncReadExt, AsyncWriteExt},
+    stream::StreamExt,
+};
 use rand::{rngs::StdRng, SeedableRng};
 use std::{io, iter::FromIterator, sync::Arc};
 use tokio::runtime::Runtime;
@@ -57,7 +65,7 @@ fn setup<TTransport>(
 )
 where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MaybeQuicStreams,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -227,6 +235,16 @@ fn expect_ip4_tcp_noise_addr(addr: &NetworkAddress) {
     );
 }
 
+/// Check that the network address matches the format
+/// `"/ip4/<ipaddr>/quic/<port>/noise-ik/<pubkey>/handshake/<version>"`
+fn expect_ip4_quic_noise_addr(addr: &NetworkAddress) {
+    assert!(
+        matches!(addr.as_slice(), [Ip4(_), Quic(_), NoiseIK(_), Handshake(_)]),
+        "addr: '{}'",
+        addr
+    );
+}
+
 fn test_transport_success<TTransport>(
     base_transport: TTransport,
     auth: Auth,
@@ -234,7 +252,7 @@ fn test_transport_success<TTransport>(
     expect_formatted_addr: fn(&NetworkAddress),
 ) where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MaybeQuicStreams,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -317,7 +335,7 @@ fn test_transport_rejects_unauthed_dialer<TTransport>(
     expect_formatted_addr: fn(&NetworkAddress),
 ) where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MaybeQuicStreams,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -371,7 +389,7 @@ fn test_transport_maybe_mutual<TTransport>(
     expect_formatted_addr: fn(&NetworkAddress),
 ) where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MaybeQuicStreams,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -581,3 +599,89 @@ fn test_tcp_transport_rejects_unauthed_dialer() {
         expect_ip4_tcp_noise_addr,
     );
 }
+
+//////////////////////////////////////
+// AptosNetTransport<QuicTransport> //
+//////////////////////////////////////
+
+#[test]
+fn test_quic_transport_mutual_auth() {
+    test_transport_success(
+        quic::QuicTransport::new(),
+        Auth::Mutual,
+        "/ip4/127.0.0.1/quic/0",
+        expect_ip4_quic_noise_addr,
+    );
+}
+
+#[test]
+fn test_quic_transport_server_only_auth() {
+    test_transport_success(
+        quic::QuicTransport::new(),
+        Auth::ServerOnly,
+        "/ip4/127.0.0.1/quic/0",
+        expect_ip4_quic_noise_addr,
+    );
+}
+
+#[test]
+fn test_quic_transport_rejects_unauthed_dialer() {
+    test_transport_rejects_unauthed_dialer(
+        quic::QuicTransport::new(),
+        "/ip4/127.0.0.1/quic/0",
+        expect_ip4_quic_noise_addr,
+    );
+}
+
+#[test]
+fn test_quic_transport_maybe_mutual() {
+    test_transport_maybe_mutual(
+        quic::QuicTransport::new(),
+        "/ip4/127.0.0.1/quic/0",
+        expect_ip4_quic_noise_addr,
+    );
+}
+
+#[test]
+fn test_quic_transport_streams() {
+    let (
+        rt,
+        _mock_time,
+        (listener_peer_id, listener_transport),
+        (_dialer_peer_id, dialer_transport),
+        _peers_and_metadata,
+        _supported_protocols,
+    ) = setup(quic::QuicTransport::new(), Auth::Mutual);
+
+    let _guard = rt.enter();
+    let (mut inbounds, listener_addr) = listener_transport
+        .listen_on("/ip4/127.0.0.1/quic/0".parse().unwrap())
+        .unwrap();
+
+    // the upgraded connection exposes the streams of the quic connection
+    let listener_task = async move {
+        let (inbound, _dialer_addr) = inbounds.next().await.unwrap().unwrap();
+        let conn = inbound.await.unwrap();
+        let quic_streams = conn.quic_streams.unwrap();
+
+        let mut stream = quic_streams.accept_stream().await.unwrap();
+        let mut msg = Vec::new();
+        stream.read_to_end(&mut msg).await.unwrap();
+        assert_eq!(msg, b"foobar");
+    };
+
+    let dialer_task = async move {
+        let conn = dialer_transport
+            .dial(listener_peer_id, listener_addr)
+            .unwrap()
+            .await
+            .unwrap();
+        let quic_streams = conn.quic_streams.unwrap();
+
+        let mut stream = quic_streams.open_stream();
+        stream.write_all(b"foobar").await.unwrap();
+        stream.close().await.unwrap();
+    };
+
+    rt.block_on(future::join(listener_task, dialer_task));
+}
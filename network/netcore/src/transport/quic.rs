@@ -0,0 +1,599 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! QUIC Transport
+//!
+//! Every QUIC connection carries one bidirectional *control* stream, opened by
+//! the dialer, which is exposed as the connection's byte stream through
+//! [`QuicSocket`]. Any number of additional unidirectional streams can be
+//! opened and accepted through [`QuicStreams`]. Since QUIC streams are flow
+//! controlled and retransmitted independently, traffic on one stream is never
+//! blocked behind data of another.
+//!
+//! QUIC mandates TLS, but peers are *not* authenticated at the TLS layer: each
+//! transport uses a self-signed certificate and dialers accept any certificate.
+//! Authentication is left to the upgrades running over the control stream,
+//! which must bind themselves to the TLS session (see
+//! [`QuicStreams::export_keying_material`]) before trusting the other streams.
+use crate::transport::{tcp::TcpSocket, Transport};
+use aptos_memsocket::MemorySocket;
+use aptos_types::{
+    network_address::{parse_dns_quic, parse_ip_quic, NetworkAddress, Protocol},
+    PeerId,
+};
+use futures::{
+    future::Future,
+    io::{AsyncRead, AsyncWrite},
+    ready,
+    stream::{self, Stream},
+};
+use quinn::{ConnectionError, IdleTimeout, VarInt};
+use std::{
+    fmt::{self, Debug},
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+use tokio::net::lookup_host;
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+/// The ALPN protocol negotiated by all AptosNet QUIC connections.
+const ALPN_PROTOCOL: &[u8] = b"aptosnet";
+
+/// The TLS server name of all AptosNet QUIC endpoints. Certificates are never
+/// verified against it, but rustls requires one to be set.
+const SERVER_NAME: &str = "aptosnet";
+
+/// The number of unidirectional streams a remote peer may have open at once.
+/// Peers open one stream per protocol, and every stream may buffer a partial
+/// frame, so this is kept just above the number of protocols.
+const MAX_CONCURRENT_UNI_STREAMS: u32 = 32;
+
+/// How often to send keep-alive packets on otherwise idle connections.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Connections without any traffic (including keep-alives) for this long are
+/// considered lost.
+const MAX_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Transport to build QUIC connections
+#[derive(Clone)]
+pub struct QuicTransport {
+    server_config: quinn::ServerConfig,
+    client_config: quinn::ClientConfig,
+}
+
+impl QuicTransport {
+    pub fn new() -> Self {
+        let mut transport_config = quinn::TransportConfig::default();
+        transport_config
+            // Only the dialer opens a bidirectional (control) stream.
+            .max_concurrent_bidi_streams(VarInt::from_u32(1))
+            .max_concurrent_uni_streams(VarInt::from_u32(MAX_CONCURRENT_UNI_STREAMS))
+            .keep_alive_interval(Some(KEEP_ALIVE_INTERVAL))
+            .max_idle_timeout(Some(
+                IdleTimeout::try_from(MAX_IDLE_TIMEOUT).expect("idle timeout is in bounds"),
+            ));
+        let transport_config = Arc::new(transport_config);
+
+        let cert = rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()])
+            .expect("generating a self-signed certificate should not fail");
+        let cert_chain = vec![rustls::Certificate(
+            cert.serialize_der()
+                .expect("serializing a self-signed certificate should not fail"),
+        )];
+        let key = rustls::PrivateKey(cert.serialize_private_key_der());
+
+        let mut server_crypto = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(cert_chain, key)
+            .expect("a freshly generated certificate should be valid");
+        server_crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+        let mut server_config = quinn::ServerConfig::with_crypto(Arc::new(server_crypto));
+        server_config.transport_config(transport_config.clone());
+
+        let mut client_crypto = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyServerCert))
+            .with_no_client_auth();
+        client_crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+        let mut client_config = quinn::ClientConfig::new(Arc::new(client_crypto));
+        client_config.transport_config(transport_config);
+
+        Self {
+            server_config,
+            client_config,
+        }
+    }
+}
+
+impl Default for QuicTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for QuicTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuicTransport").finish_non_exhaustive()
+    }
+}
+
+impl Transport for QuicTransport {
+    type Error = io::Error;
+    type Inbound = Pin<Box<dyn Future<Output = io::Result<QuicSocket>> + Send + 'static>>;
+    type Listener =
+        Pin<Box<dyn Stream<Item = io::Result<(Self::Inbound, NetworkAddress)>> + Send + 'static>>;
+    type Outbound = Pin<Box<dyn Future<Output = io::Result<QuicSocket>> + Send + 'static>>;
+    type Output = QuicSocket;
+
+    fn listen_on(
+        &self,
+        addr: NetworkAddress,
+    ) -> Result<(Self::Listener, NetworkAddress), Self::Error> {
+        let ((ipaddr, port), addr_suffix) =
+            parse_ip_quic(addr.as_slice()).ok_or_else(|| invalid_addr_error(&addr))?;
+        if !addr_suffix.is_empty() {
+            return Err(invalid_addr_error(&addr));
+        }
+
+        let endpoint =
+            quinn::Endpoint::server(self.server_config.clone(), SocketAddr::new(ipaddr, port))?;
+        let listen_addr = quic_addr(endpoint.local_addr()?);
+
+        let listener = stream::unfold(endpoint, |endpoint| async move {
+            let connecting = endpoint.accept().await?;
+            let dialer_addr = quic_addr(connecting.remote_address());
+            let inbound: Self::Inbound = Box::pin(accept_control_stream(connecting));
+            Some((Ok((inbound, dialer_addr)), endpoint))
+        });
+
+        Ok((Box::pin(listener), listen_addr))
+    }
+
+    fn dial(&self, _peer_id: PeerId, addr: NetworkAddress) -> Result<Self::Outbound, Self::Error> {
+        let protos = addr.as_slice();
+
+        // ensure addr is well formed to save some work before potentially
+        // spawning a dial task that will fail anyway.
+        parse_ip_quic(protos)
+            .map(|_| ())
+            .or_else(|| parse_dns_quic(protos).map(|_| ()))
+            .ok_or_else(|| invalid_addr_error(&addr))?;
+
+        Ok(Box::pin(resolve_and_connect(
+            addr,
+            self.client_config.clone(),
+        )))
+    }
+}
+
+/// Resolves the QUIC `addr` and connects to the first resolved socket address
+/// that accepts the connection.
+async fn resolve_and_connect(
+    addr: NetworkAddress,
+    client_config: quinn::ClientConfig,
+) -> io::Result<QuicSocket> {
+    let protos = addr.as_slice();
+
+    if let Some(((ipaddr, port), _addr_suffix)) = parse_ip_quic(protos) {
+        connect(client_config, SocketAddr::new(ipaddr, port)).await
+    } else if let Some(((ip_filter, dns_name, port), _addr_suffix)) = parse_dns_quic(protos) {
+        let mut last_err = None;
+        for socketaddr in lookup_host((dns_name.as_ref(), port))
+            .await?
+            .filter(|socketaddr| ip_filter.matches(socketaddr.ip()))
+        {
+            match connect(client_config.clone(), socketaddr).await {
+                Ok(socket) => return Ok(socket),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "could not resolve dns name to any address: name: {}, ip filter: {:?}",
+                    dns_name.as_ref(),
+                    ip_filter,
+                ),
+            )
+        }))
+    } else {
+        Err(invalid_addr_error(&addr))
+    }
+}
+
+/// Connects to `remote` from a fresh client endpoint and opens the control
+/// stream. The endpoint is kept alive by the connection.
+async fn connect(client_config: quinn::ClientConfig, remote: SocketAddr) -> io::Result<QuicSocket> {
+    let bind_addr = if remote.is_ipv4() {
+        SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)
+    } else {
+        SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0)
+    };
+    let endpoint = quinn::Endpoint::client(bind_addr)?;
+    let connection = endpoint
+        .connect_with(client_config, remote, SERVER_NAME)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?
+        .await
+        .map_err(connection_error)?;
+    let (send, recv) = connection.open_bi().await.map_err(connection_error)?;
+    Ok(QuicSocket::new(connection, send, recv))
+}
+
+/// Completes an inbound connection and waits for the dialer to open the
+/// control stream.
+async fn accept_control_stream(connecting: quinn::Connecting) -> io::Result<QuicSocket> {
+    let connection = connecting.await.map_err(connection_error)?;
+    let (send, recv) = connection.accept_bi().await.map_err(connection_error)?;
+    Ok(QuicSocket::new(connection, send, recv))
+}
+
+fn quic_addr(addr: SocketAddr) -> NetworkAddress {
+    NetworkAddress::from_protocols(vec![Protocol::from(addr.ip()), Protocol::Quic(addr.port())])
+        .expect("an ip address followed by a quic port is always valid")
+}
+
+fn invalid_addr_error(addr: &NetworkAddress) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid NetworkAddress: '{}'", addr),
+    )
+}
+
+fn connection_error(err: ConnectionError) -> io::Error {
+    let kind = match err {
+        ConnectionError::TimedOut => io::ErrorKind::TimedOut,
+        ConnectionError::LocallyClosed => io::ErrorKind::NotConnected,
+        ConnectionError::Reset
+        | ConnectionError::ConnectionClosed(_)
+        | ConnectionError::ApplicationClosed(_) => io::ErrorKind::ConnectionReset,
+        _ => io::ErrorKind::Other,
+    };
+    io::Error::new(kind, err)
+}
+
+/// Peers are authenticated over the control stream, so any server certificate
+/// is acceptable at the TLS layer.
+struct AcceptAnyServerCert;
+
+impl rustls::client::ServerCertVerifier for AcceptAnyServerCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+/// A QUIC connection, read from and written to through its control stream.
+#[derive(Debug)]
+pub struct QuicSocket {
+    streams: QuicStreams,
+    send: Compat<quinn::SendStream>,
+    recv: QuicRecvStream,
+}
+
+impl QuicSocket {
+    fn new(
+        connection: quinn::Connection,
+        send: quinn::SendStream,
+        recv: quinn::RecvStream,
+    ) -> Self {
+        Self {
+            streams: QuicStreams { connection },
+            send: send.compat_write(),
+            recv: recv.compat(),
+        }
+    }
+
+    /// A handle for opening and accepting further streams on this connection.
+    pub fn streams(&self) -> QuicStreams {
+        self.streams.clone()
+    }
+}
+
+impl AsyncRead for QuicSocket {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.recv).poll_read(context, buf)
+    }
+}
+
+impl AsyncWrite for QuicSocket {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.send).poll_write(context, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_flush(context)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_close(context)
+    }
+}
+
+/// The receiving side of a unidirectional QUIC stream.
+pub type QuicRecvStream = Compat<quinn::RecvStream>;
+
+/// A handle to the streams of a QUIC connection. The connection is closed
+/// once all handles, sockets and streams referring to it are dropped.
+#[derive(Clone, Debug)]
+pub struct QuicStreams {
+    connection: quinn::Connection,
+}
+
+impl QuicStreams {
+    /// Returns a new unidirectional stream to the remote peer. The stream is
+    /// opened on its first write, so it can be handed out synchronously.
+    pub fn open_stream(&self) -> QuicSendStream {
+        let connection = self.connection.clone();
+        QuicSendStream {
+            state: SendState::Opening(Box::pin(async move { connection.open_uni().await })),
+        }
+    }
+
+    /// Waits for the remote peer to open a unidirectional stream. Fails once
+    /// the connection is closed.
+    pub async fn accept_stream(&self) -> io::Result<QuicRecvStream> {
+        let recv = self
+            .connection
+            .accept_uni()
+            .await
+            .map_err(connection_error)?;
+        Ok(recv.compat())
+    }
+
+    /// Derives 32 bytes of keying material from the TLS session of the
+    /// connection, per RFC 5705. Both ends of a connection derive the same
+    /// value for the same `label`, while the ends of two different TLS
+    /// sessions (e.g., those of a man-in-the-middle) do not.
+    pub fn export_keying_material(&self, label: &[u8]) -> io::Result<[u8; 32]> {
+        let mut output = [0u8; 32];
+        self.connection
+            .export_keying_material(&mut output, label, &[])
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    "failed to export keying material from the QUIC connection",
+                )
+            })?;
+        Ok(output)
+    }
+
+    /// Immediately closes the connection and all of its streams.
+    pub fn close(&self) {
+        self.connection.close(VarInt::from_u32(0), b"");
+    }
+}
+
+/// The sending side of a unidirectional QUIC stream.
+#[derive(Debug)]
+pub struct QuicSendStream {
+    state: SendState,
+}
+
+enum SendState {
+    Opening(
+        Pin<Box<dyn Future<Output = Result<quinn::SendStream, ConnectionError>> + Send + 'static>>,
+    ),
+    Open(Compat<quinn::SendStream>),
+}
+
+impl Debug for SendState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendState::Opening(_) => f.write_str("Opening"),
+            SendState::Open(send) => f.debug_tuple("Open").field(send).finish(),
+        }
+    }
+}
+
+impl QuicSendStream {
+    fn poll_open(
+        &mut self,
+        context: &mut Context,
+    ) -> Poll<io::Result<&mut Compat<quinn::SendStream>>> {
+        if let SendState::Opening(open) = &mut self.state {
+            let send = ready!(open.as_mut().poll(context)).map_err(connection_error)?;
+            self.state = SendState::Open(send.compat_write());
+        }
+        match &mut self.state {
+            SendState::Open(send) => Poll::Ready(Ok(send)),
+            SendState::Opening(_) => unreachable!("the stream was just opened"),
+        }
+    }
+}
+
+impl AsyncWrite for QuicSendStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let send = ready!(self.get_mut().poll_open(context))?;
+        Pin::new(send).poll_write(context, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().state {
+            // nothing was written yet, so there is nothing to flush
+            SendState::Opening(_) => Poll::Ready(Ok(())),
+            SendState::Open(send) => Pin::new(send).poll_flush(context),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().state {
+            // never opened, so there is nothing to close
+            SendState::Opening(_) => Poll::Ready(Ok(())),
+            SendState::Open(send) => Pin::new(send).poll_close(context),
+        }
+    }
+}
+
+/// Exposes the [`QuicStreams`] of base transport sockets. Sockets of
+/// transports other than QUIC have none.
+pub trait MaybeQuicStreams {
+    fn quic_streams(&self) -> Option<QuicStreams> {
+        None
+    }
+}
+
+impl MaybeQuicStreams for QuicSocket {
+    fn quic_streams(&self) -> Option<QuicStreams> {
+        Some(self.streams())
+    }
+}
+
+impl MaybeQuicStreams for TcpSocket {}
+
+impl MaybeQuicStreams for MemorySocket {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::{ConnectionOrigin, TransportExt};
+    use futures::{
+        future::join,
+        io::{AsyncReadExt, AsyncWriteExt},
+        stream::StreamExt,
+        FutureExt,
+    };
+
+    #[tokio::test]
+    async fn simple_listen_and_dial() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default().and_then(|mut out, _addr, origin| async move {
+            match origin {
+                ConnectionOrigin::Inbound => {
+                    let mut buf = [0; 4];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Fire");
+                    out.write_all(b"Earth").await?;
+                    let mut buf = [0; 3];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Air");
+                },
+                ConnectionOrigin::Outbound => {
+                    // the listener only sees the control stream once the
+                    // dialer writes to it
+                    out.write_all(b"Fire").await?;
+                    let mut buf = [0; 5];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Earth");
+                    out.write_all(b"Air").await?;
+                    out.close().await?;
+                },
+            }
+            Ok(())
+        });
+
+        let (listener, addr) = t.listen_on("/ip4/127.0.0.1/quic/0".parse().unwrap())?;
+        assert!(matches!(addr.as_slice(), [Protocol::Ip4(_), Protocol::Quic(port)] if *port != 0));
+        let peer_id = PeerId::random();
+        let dial = t.dial(peer_id, addr)?;
+        let listener = listener.into_future().then(|(maybe_result, _stream)| {
+            let (incoming, _addr) = maybe_result.unwrap().unwrap();
+            incoming.map(Result::unwrap)
+        });
+
+        let (outgoing, _incoming) = join(dial, listener).await;
+        assert!(outgoing.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn streams_and_keying_material() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default();
+        let (mut listener, addr) = t.listen_on("/ip4/127.0.0.1/quic/0".parse().unwrap())?;
+
+        let listener_task = async move {
+            let (inbound, _addr) = listener.next().await.unwrap().unwrap();
+            let mut socket = inbound.await.unwrap();
+            let streams = socket.quic_streams().unwrap();
+            let keying_material = streams.export_keying_material(b"test").unwrap();
+
+            // the control stream still works alongside the other streams
+            let mut buf = [0; 4];
+            socket.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"ping");
+
+            // both streams opened by the dialer arrive independently
+            let mut first = streams.accept_stream().await.unwrap();
+            let mut second = streams.accept_stream().await.unwrap();
+            let mut msg = Vec::new();
+            second.read_to_end(&mut msg).await.unwrap();
+            assert_eq!(msg, b"second");
+            msg.clear();
+            first.read_to_end(&mut msg).await.unwrap();
+            assert_eq!(msg, b"first");
+
+            socket.write_all(b"pong").await.unwrap();
+            socket.close().await.unwrap();
+            keying_material
+        };
+
+        let dialer_task = async move {
+            let mut socket = t.dial(PeerId::random(), addr).unwrap().await.unwrap();
+            let streams = socket.quic_streams().unwrap();
+            let keying_material = streams.export_keying_material(b"test").unwrap();
+            socket.write_all(b"ping").await.unwrap();
+
+            let mut first = streams.open_stream();
+            let mut second = streams.open_stream();
+            first.write_all(b"fir").await.unwrap();
+            second.write_all(b"second").await.unwrap();
+            second.close().await.unwrap();
+            first.write_all(b"st").await.unwrap();
+            first.close().await.unwrap();
+
+            let mut buf = [0; 4];
+            socket.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"pong");
+            keying_material
+        };
+
+        let (listener_keys, dialer_keys) = join(listener_task, dialer_task).await;
+        assert_eq!(listener_keys, dialer_keys);
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_multiaddrs() {
+        let t = QuicTransport::default();
+
+        let result = t.listen_on("/ip4/127.0.0.1/tcp/0".parse().unwrap());
+        assert!(result.is_err());
+
+        let peer_id = PeerId::random();
+        let result = t.dial(peer_id, "/memory/22".parse().unwrap());
+        assert!(result.is_err());
+        let result = t.dial(peer_id, "/ip4/127.0.0.1/tcp/22".parse().unwrap());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn other_sockets_have_no_streams() {
+        let (socket, _) = MemorySocket::new_pair();
+        assert!(socket.quic_streams().is_none());
+    }
+}
@@ -23,7 +23,10 @@ use aptos_config::{
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_infallible::RwLock;
 use aptos_logger::{debug, info, sample, sample::SampleRate, trace, warn};
-use aptos_network::{application::interface::NetworkClient, protocols::network::RpcError};
+use aptos_network::{
+    application::{interface::NetworkClient, reputation::PeerMisbehavior},
+    protocols::network::RpcError,
+};
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_types::{
@@ -485,9 +488,28 @@ impl AptosDataClient {
         _request: &StorageServiceRequest,
         error_type: ErrorType,
     ) {
+        let is_malicious = matches!(error_type, ErrorType::Malicious);
         self.peer_states
             .write()
             .update_score_error(peer, error_type);
+
+        // Malicious peers are also reported to the network, which bans them
+        // if they keep misbehaving.
+        if is_malicious {
+            if let Err(error) = self
+                .storage_service_client
+                .report_peer_misbehavior(peer, PeerMisbehavior::InvalidData)
+            {
+                let error = Error::from(error);
+                warn!(
+                    (LogSchema::new(LogEntry::PeerStates)
+                        .event(LogEvent::UnexpectedError)
+                        .message("Failed to report the misbehavior of the peer")
+                        .peer(&peer)
+                        .error(&error))
+                );
+            }
+        }
     }
 
     /// Creates a storage service request using the given data request
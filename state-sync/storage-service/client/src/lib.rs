@@ -6,7 +6,9 @@
 
 use aptos_config::network_id::PeerNetworkId;
 use aptos_network::{
-    application::{interface::NetworkClientInterface, storage::PeersAndMetadata},
+    application::{
+        interface::NetworkClientInterface, reputation::PeerMisbehavior, storage::PeersAndMetadata,
+    },
     protocols::network::RpcError,
 };
 use aptos_storage_service_types::{
@@ -72,4 +74,14 @@ impl<NetworkClient: NetworkClientInterface<StorageServiceMessage>>
     pub fn get_peers_and_metadata(&self) -> Arc<PeersAndMetadata> {
         self.network_client.get_peers_and_metadata()
     }
+
+    pub fn report_peer_misbehavior(
+        &self,
+        peer: PeerNetworkId,
+        misbehavior: PeerMisbehavior,
+    ) -> Result<(), Error> {
+        self.network_client
+            .report_peer_misbehavior(peer, misbehavior)
+            .map_err(|error| Error::NetworkError(error.to_string()))
+    }
 }
@@ -113,9 +113,11 @@ impl TransactionAnalysis {
 
         match txn {
             UserTransaction(signed_txn) => signed_txn.raw_txn_bytes_len(),
-            GenesisTransaction(_) | BlockMetadata(_) | StateCheckpoint(_) => {
-                bcs::to_bytes(txn).expect("Txn should serialize").len()
-            },
+            GenesisTransaction(_)
+            | BlockMetadata(_)
+            | StateCheckpoint(_)
+            | ValidatorTransaction(_)
+            | BlockMetadataExt(_) => bcs::to_bytes(txn).expect("Txn should serialize").len(),
         }
     }
 }
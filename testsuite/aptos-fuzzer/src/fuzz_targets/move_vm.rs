@@ -49,6 +49,7 @@ fn is_valid_layout(layout: &MoveTypeLayout) -> bool {
         },
 
         L::Vector(layout) => is_valid_layout(layout),
+        L::Function => false,
 
         L::Struct(struct_layout) => {
             if !matches!(struct_layout, MoveStructLayout::Runtime(_))
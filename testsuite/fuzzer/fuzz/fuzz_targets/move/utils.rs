@@ -13,6 +13,7 @@ pub(crate) fn is_valid_layout(layout: &MoveTypeLayout) -> bool {
             true
         },
         L::Vector(layout) => is_valid_layout(layout),
+        L::Function => false,
         L::Struct(struct_layout) => {
             if !matches!(struct_layout, MoveStructLayout::Runtime(_))
                 || struct_layout.fields().is_empty()
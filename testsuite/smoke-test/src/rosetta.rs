@@ -1085,6 +1085,7 @@ async fn parse_block_transactions(
                 assert!(matches!(
                     actual_txn.transaction,
                     aptos_types::transaction::Transaction::BlockMetadata(_)
+                        | aptos_types::transaction::Transaction::BlockMetadataExt(_)
                 ));
                 assert!(transaction.operations.is_empty());
             },
@@ -1095,6 +1096,13 @@ async fn parse_block_transactions(
                 ));
                 assert!(transaction.operations.is_empty());
             },
+            TransactionType::Validator => {
+                assert!(matches!(
+                    actual_txn.transaction,
+                    aptos_types::transaction::Transaction::ValidatorTransaction(_)
+                ));
+                assert!(transaction.operations.is_empty());
+            },
         }
 
         parse_operations(
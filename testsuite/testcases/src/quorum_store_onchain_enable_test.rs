@@ -69,6 +69,7 @@ impl NetworkLoadTest for QuorumStoreOnChainEnableTest {
             let inner = match current_consensus_config {
                 OnChainConsensusConfig::V1(inner) => inner,
                 OnChainConsensusConfig::V2(_) => panic!("Unexpected V2 config"),
+                OnChainConsensusConfig::V3 { .. } => panic!("Unexpected V3 config"),
             };
 
             // Change to V2
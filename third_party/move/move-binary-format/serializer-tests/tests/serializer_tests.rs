@@ -5,9 +5,11 @@
 use move_binary_format::{
     access::ModuleAccess,
     file_format::{
-        empty_module, AbilitySet, CompiledModule, FunctionHandle, IdentifierIndex, Signature,
-        SignatureIndex, SignatureToken,
+        empty_module, Ability, AbilitySet, Bytecode, ClosureMask, CodeUnit, CompiledModule,
+        FunctionDefinition, FunctionHandle, FunctionHandleIndex, IdentifierIndex, Signature,
+        SignatureIndex, SignatureToken, Visibility,
     },
+    file_format_common::{VERSION_6, VERSION_MAX},
 };
 use move_core_types::identifier::Identifier;
 use proptest::prelude::*;
@@ -16,7 +18,9 @@ proptest! {
     #[test]
     fn serializer_roundtrip(module in CompiledModule::valid_strategy(20)) {
         let mut serialized = Vec::with_capacity(2048);
-        module.serialize(&mut serialized).expect("serialization should work");
+        module
+            .serialize_for_version(Some(VERSION_MAX), &mut serialized)
+            .expect("serialization should work");
 
         let deserialized_module = CompiledModule::deserialize(&serialized)
             .expect("deserialization should work");
@@ -34,7 +38,9 @@ proptest! {
     #[test]
     fn garbage_inputs(module in any_with::<CompiledModule>(16)) {
         let mut serialized = Vec::with_capacity(65536);
-        module.serialize(&mut serialized).expect("serialization should work");
+        module
+            .serialize_for_version(Some(VERSION_MAX), &mut serialized)
+            .expect("serialization should work");
 
         let deserialized_module = CompiledModule::deserialize_no_check_bounds(&serialized)
             .expect("deserialization should work");
@@ -69,7 +75,70 @@ fn simple_generic_module_round_trip() {
     });
 
     let mut serialized = Vec::with_capacity(2048);
-    m.serialize(&mut serialized)
+    m.serialize_for_version(Some(VERSION_MAX), &mut serialized)
+        .expect("serialization should work");
+
+    let deserialized_m =
+        CompiledModule::deserialize(&serialized).expect("deserialization should work");
+
+    assert_eq!(m, deserialized_m);
+}
+
+#[test]
+fn closure_module_round_trip() {
+    let mut m = empty_module();
+
+    // signature (u64, bool)
+    let sig_params_idx = SignatureIndex::new(m.signatures.len() as u16);
+    m.signatures
+        .push(Signature(vec![SignatureToken::U64, SignatureToken::Bool]));
+
+    // signature |bool| has copy + drop
+    let fun_ty = SignatureToken::Function(
+        vec![SignatureToken::Bool],
+        vec![],
+        AbilitySet::EMPTY.add(Ability::Copy).add(Ability::Drop),
+    );
+    let sig_fun_idx = SignatureIndex::new(m.signatures.len() as u16);
+    m.signatures.push(Signature(vec![fun_ty]));
+
+    // identifier f
+    let ident_f_idx = IdentifierIndex::new(m.identifiers.len() as u16);
+    m.identifiers.push(Identifier::new("f").unwrap());
+
+    // fun f(x: u64, b: bool) { ... }
+    m.function_handles.push(FunctionHandle {
+        module: m.self_handle_idx(),
+        name: ident_f_idx,
+        parameters: sig_params_idx,
+        return_: SignatureIndex::new(0),
+        type_parameters: vec![],
+    });
+    m.function_defs.push(FunctionDefinition {
+        function: FunctionHandleIndex::new(0),
+        visibility: Visibility::Public,
+        is_entry: false,
+        acquires_global_resources: vec![],
+        code: Some(CodeUnit {
+            locals: sig_fun_idx,
+            code: vec![
+                Bytecode::CopyLoc(0),
+                Bytecode::PackClosure(FunctionHandleIndex::new(0), ClosureMask::new(0b1)),
+                Bytecode::StLoc(2),
+                Bytecode::CopyLoc(1),
+                Bytecode::MoveLoc(2),
+                Bytecode::CallClosure(sig_fun_idx),
+                Bytecode::Ret,
+            ],
+        }),
+    });
+
+    let mut serialized = Vec::with_capacity(2048);
+    m.serialize_for_version(Some(VERSION_6), &mut serialized)
+        .expect_err("closures should not serialize at version 6");
+
+    let mut serialized = Vec::with_capacity(2048);
+    m.serialize_for_version(Some(VERSION_MAX), &mut serialized)
         .expect("serialization should work");
 
     let deserialized_m =
@@ -21,7 +21,8 @@ fn sig_to_ty(sig: &SignatureToken) -> Option<MoveTypeLayout> {
         | SignatureToken::MutableReference(_)
         | SignatureToken::Struct(_)
         | SignatureToken::TypeParameter(_)
-        | SignatureToken::StructInstantiation(_, _) => None,
+        | SignatureToken::StructInstantiation(_, _)
+        | SignatureToken::Function(..) => None,
     }
 }
 
@@ -36,7 +37,7 @@ fn ty_to_sig(ty: &MoveTypeLayout) -> Option<SignatureToken> {
         MoveTypeLayout::U128 => Some(SignatureToken::U128),
         MoveTypeLayout::U256 => Some(SignatureToken::U256),
         MoveTypeLayout::Vector(v) => Some(SignatureToken::Vector(Box::new(ty_to_sig(v.as_ref())?))),
-        MoveTypeLayout::Struct(_) => None,
+        MoveTypeLayout::Struct(_) | MoveTypeLayout::Function => None,
         MoveTypeLayout::Bool => Some(SignatureToken::Bool),
     }
 }
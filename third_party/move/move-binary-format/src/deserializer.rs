@@ -986,6 +986,13 @@ fn load_signature_token(cursor: &mut VersionedCursor) -> BinaryLoaderResult<Sign
             arity: usize,
             ty_args: Vec<SignatureToken>,
         },
+        Function {
+            args_arity: usize,
+            results_arity: usize,
+            abilities: AbilitySet,
+            args: Vec<SignatureToken>,
+            results: Vec<SignatureToken>,
+        },
     }
 
     impl TypeBuilder {
@@ -1012,6 +1019,30 @@ fn load_signature_token(cursor: &mut VersionedCursor) -> BinaryLoaderResult<Sign
                         }
                     }
                 },
+                T::Function {
+                    args_arity,
+                    results_arity,
+                    abilities,
+                    mut args,
+                    mut results,
+                } => {
+                    if args.len() < args_arity {
+                        args.push(tok)
+                    } else {
+                        results.push(tok)
+                    }
+                    if args.len() >= args_arity && results.len() >= results_arity {
+                        T::Saturated(SignatureToken::Function(args, results, abilities))
+                    } else {
+                        T::Function {
+                            args_arity,
+                            results_arity,
+                            abilities,
+                            args,
+                            results,
+                        }
+                    }
+                },
                 _ => unreachable!("invalid type constructor application"),
             }
         }
@@ -1041,6 +1072,14 @@ fn load_signature_token(cursor: &mut VersionedCursor) -> BinaryLoaderResult<Sign
                         )),
                     );
                 },
+                S::FUNCTION if (cursor.version() < VERSION_7) => {
+                    return Err(
+                        PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                            "function types not supported in bytecode version {}",
+                            cursor.version()
+                        )),
+                    );
+                },
                 _ => (),
             };
 
@@ -1078,6 +1117,22 @@ fn load_signature_token(cursor: &mut VersionedCursor) -> BinaryLoaderResult<Sign
                     let idx = load_type_parameter_index(cursor)?;
                     T::Saturated(SignatureToken::TypeParameter(idx))
                 },
+                S::FUNCTION => {
+                    let args_arity = load_signature_size(cursor)? as usize;
+                    let results_arity = load_signature_size(cursor)? as usize;
+                    let abilities = load_ability_set(cursor, AbilitySetPosition::FunctionType)?;
+                    if args_arity == 0 && results_arity == 0 {
+                        T::Saturated(SignatureToken::Function(vec![], vec![], abilities))
+                    } else {
+                        T::Function {
+                            args_arity,
+                            results_arity,
+                            abilities,
+                            args: vec![],
+                            results: vec![],
+                        }
+                    }
+                },
             })
         } else {
             Err(PartialVMError::new(StatusCode::MALFORMED)
@@ -1112,6 +1167,7 @@ enum AbilitySetPosition {
     FunctionTypeParameters,
     StructTypeParameters,
     StructHandle,
+    FunctionType,
 }
 
 fn load_ability_set(
@@ -1153,6 +1209,8 @@ fn load_ability_set(
                     },
                 })
             },
+            AbilitySetPosition::FunctionType => Err(PartialVMError::new(StatusCode::MALFORMED)
+                .with_message("Function types not supported in bytecode version 1".to_string())),
             AbilitySetPosition::FunctionTypeParameters
             | AbilitySetPosition::StructTypeParameters => {
                 let set = match DeprecatedKind::from_u8(byte)? {
@@ -1161,7 +1219,9 @@ fn load_ability_set(
                     DeprecatedKind::RESOURCE => AbilitySet::EMPTY | Ability::Key,
                 };
                 Ok(match pos {
-                    AbilitySetPosition::StructHandle => unreachable!(),
+                    AbilitySetPosition::StructHandle | AbilitySetPosition::FunctionType => {
+                        unreachable!()
+                    },
                     AbilitySetPosition::FunctionTypeParameters => set | Ability::Store,
                     AbilitySetPosition::StructTypeParameters => set,
                 })
@@ -1478,6 +1538,20 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
             _ => (),
         };
 
+        match opcode {
+            Opcodes::PACK_CLOSURE | Opcodes::PACK_CLOSURE_GENERIC | Opcodes::CALL_CLOSURE
+                if (cursor.version() < VERSION_7) =>
+            {
+                return Err(
+                    PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                        "Closures not supported in bytecode version {}",
+                        cursor.version()
+                    )),
+                );
+            },
+            _ => (),
+        };
+
         // conversion
         let bytecode = match opcode {
             Opcodes::POP => Bytecode::Pop,
@@ -1594,6 +1668,15 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
             Opcodes::CAST_U16 => Bytecode::CastU16,
             Opcodes::CAST_U32 => Bytecode::CastU32,
             Opcodes::CAST_U256 => Bytecode::CastU256,
+            Opcodes::PACK_CLOSURE => Bytecode::PackClosure(
+                load_function_handle_index(cursor)?,
+                ClosureMask::new(read_u64_internal(cursor)?),
+            ),
+            Opcodes::PACK_CLOSURE_GENERIC => Bytecode::PackClosureGeneric(
+                load_function_inst_index(cursor)?,
+                ClosureMask::new(read_u64_internal(cursor)?),
+            ),
+            Opcodes::CALL_CLOSURE => Bytecode::CallClosure(load_signature_index(cursor)?),
         };
         code.push(bytecode);
     }
@@ -1641,6 +1724,7 @@ impl SerializedType {
             0xD => Ok(SerializedType::U16),
             0xE => Ok(SerializedType::U32),
             0xF => Ok(SerializedType::U256),
+            0x10 => Ok(SerializedType::FUNCTION),
             _ => Err(PartialVMError::new(StatusCode::UNKNOWN_SERIALIZED_TYPE)),
         }
     }
@@ -1774,6 +1858,9 @@ impl Opcodes {
             0x4B => Ok(Opcodes::CAST_U16),
             0x4C => Ok(Opcodes::CAST_U32),
             0x4D => Ok(Opcodes::CAST_U256),
+            0x4E => Ok(Opcodes::PACK_CLOSURE),
+            0x4F => Ok(Opcodes::PACK_CLOSURE_GENERIC),
+            0x50 => Ok(Opcodes::CALL_CLOSURE),
             _ => Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)),
         }
     }
@@ -639,6 +639,9 @@ impl AbilitySet {
     );
     /// The empty ability set
     pub const EMPTY: Self = Self(0);
+    /// Abilities a `Function` type may declare, note they are predicated on the captured values
+    pub const FUNCTIONS: AbilitySet =
+        Self((Ability::Copy as u8) | (Ability::Drop as u8) | (Ability::Store as u8));
     /// Abilities for `Bool`, `U8`, `U64`, `U128`, and `Address`
     pub const PRIMITIVES: AbilitySet =
         Self((Ability::Copy as u8) | (Ability::Drop as u8) | (Ability::Store as u8));
@@ -887,6 +890,9 @@ pub enum SignatureToken {
     U32,
     /// Unsigned integers, 256 bits length.
     U256,
+    /// Function value, with its argument types, result types and abilities (added in
+    /// bytecode version v7).
+    Function(Vec<SignatureToken>, Vec<SignatureToken>, AbilitySet),
 }
 
 /// An iterator to help traverse the `SignatureToken` in a non-recursive fashion to avoid
@@ -914,6 +920,10 @@ impl<'a> Iterator for SignatureTokenPreorderTraversalIter<'a> {
                         self.stack.extend(inner_toks.iter().rev())
                     },
 
+                    Function(args, results, _) => {
+                        self.stack.extend(args.iter().chain(results).rev())
+                    },
+
                     Signer | Bool | Address | U8 | U16 | U32 | U64 | U128 | U256 | Struct(_)
                     | TypeParameter(_) => (),
                 }
@@ -947,6 +957,10 @@ impl<'a> Iterator for SignatureTokenPreorderTraversalIterWithDepth<'a> {
                         .stack
                         .extend(inner_toks.iter().map(|tok| (tok, depth + 1)).rev()),
 
+                    Function(args, results, _) => self
+                        .stack
+                        .extend(args.iter().chain(results).map(|tok| (tok, depth + 1)).rev()),
+
                     Signer | Bool | Address | U8 | U16 | U32 | U64 | U128 | U256 | Struct(_)
                     | TypeParameter(_) => (),
                 }
@@ -1014,6 +1028,9 @@ impl std::fmt::Debug for SignatureToken {
             SignatureToken::Reference(boxed) => write!(f, "Reference({:?})", boxed),
             SignatureToken::MutableReference(boxed) => write!(f, "MutableReference({:?})", boxed),
             SignatureToken::TypeParameter(idx) => write!(f, "TypeParameter({:?})", idx),
+            SignatureToken::Function(args, results, abilities) => {
+                write!(f, "Function({:?}, {:?}, {:?})", args, results, abilities)
+            },
         }
     }
 }
@@ -1040,7 +1057,8 @@ impl SignatureToken {
             | Signer
             | Struct(_)
             | StructInstantiation(_, _)
-            | Vector(_) => SignatureTokenKind::Value,
+            | Vector(_)
+            | Function(..) => SignatureTokenKind::Value,
             // TODO: This is a temporary hack to please the verifier. SignatureTokenKind will soon
             // be completely removed. `SignatureTokenView::kind()` should be used instead.
             TypeParameter(_) => SignatureTokenKind::Value,
@@ -1060,7 +1078,8 @@ impl SignatureToken {
             | StructInstantiation(_, _)
             | Reference(_)
             | MutableReference(_)
-            | TypeParameter(_) => false,
+            | TypeParameter(_)
+            | Function(..) => false,
         }
     }
 
@@ -1098,10 +1117,18 @@ impl SignatureToken {
             | StructInstantiation(_, _)
             | Reference(_)
             | MutableReference(_)
-            | TypeParameter(_) => false,
+            | TypeParameter(_)
+            | Function(..) => false,
         }
     }
 
+    /// Returns true if the `SignatureToken` is a function type.
+    pub fn is_function(&self) -> bool {
+        use SignatureToken::*;
+
+        matches!(self, Function(..))
+    }
+
     /// Set the index to this one. Useful for random testing.
     ///
     /// Panics if this token doesn't contain a struct handle.
@@ -1156,6 +1183,103 @@ pub struct CodeUnit {
     pub code: Vec<Bytecode>,
 }
 
+/// A `ClosureMask` describes which parameters of a function are captured by a closure over it.
+///
+/// Bit `i` of the mask is set if the `i`-th parameter is captured: its value is provided when the
+/// closure is created. The remaining parameters, in their original order, are the parameters of
+/// the closure and are provided when the closure is called. Only the first 64 parameters of a
+/// function can be captured.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct ClosureMask(pub u64);
+
+impl ClosureMask {
+    pub fn new(mask: u64) -> Self {
+        Self(mask)
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns true if the parameter at `idx` is captured.
+    pub fn is_captured(&self, idx: usize) -> bool {
+        idx < u64::BITS as usize && self.0 & (1 << idx) != 0
+    }
+
+    /// Returns the number of captured parameters.
+    pub fn captured_count(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns true if the mask only captures parameters of a function with `param_count`
+    /// parameters.
+    pub fn is_valid_for(&self, param_count: usize) -> bool {
+        param_count >= u64::BITS as usize || self.0 >> param_count == 0
+    }
+
+    /// Splits the parameters of a function into the captured and the remaining ones, keeping
+    /// their order.
+    pub fn split<'a, T>(&self, params: &'a [T]) -> (Vec<&'a T>, Vec<&'a T>) {
+        let mut captured = vec![];
+        let mut remaining = vec![];
+        for (idx, param) in params.iter().enumerate() {
+            if self.is_captured(idx) {
+                captured.push(param)
+            } else {
+                remaining.push(param)
+            }
+        }
+        (captured, remaining)
+    }
+
+    /// Merges the captured values of a closure with the values it is called with into the
+    /// arguments of the underlying function. Returns `None` if the number of values does not
+    /// match the mask.
+    pub fn compose<T>(
+        &self,
+        captured: impl IntoIterator<Item = T>,
+        provided: impl IntoIterator<Item = T>,
+    ) -> Option<Vec<T>> {
+        let mut captured = captured.into_iter();
+        let mut provided = provided.into_iter();
+        let mut args = vec![];
+        let mut captured_left = self.captured_count();
+        let mut idx = 0;
+        loop {
+            if self.is_captured(idx) {
+                args.push(captured.next()?);
+                captured_left -= 1;
+            } else if let Some(arg) = provided.next() {
+                args.push(arg);
+            } else if captured_left == 0 {
+                break;
+            } else {
+                return None;
+            }
+            idx += 1;
+        }
+        if captured.next().is_some() {
+            return None;
+        }
+        Some(args)
+    }
+}
+
+impl ::std::fmt::Debug for ClosureMask {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ClosureMask({:#b})", self.0)
+    }
+}
+
+impl ::std::fmt::Display for ClosureMask {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{:#b}", self.0)
+    }
+}
+
 /// `Bytecode` is a VM instruction of variable size. The type of the bytecode (opcode) defines
 /// the size of the bytecode.
 ///
@@ -1654,6 +1778,26 @@ pub enum Bytecode {
     ///
     /// ```..., integer_value -> ..., u256_value```
     CastU256,
+    /// Create a closure over the function specified via `FunctionHandleIndex`, capturing the
+    /// arguments selected by the `ClosureMask` from the stack. The captured values, in the order
+    /// of the parameters they are provided for, must be pushed on the stack. The closure is a
+    /// value of a function type over the parameters which are not captured.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., captured(1), ..., captured(k) -> ..., function_value```
+    PackClosure(FunctionHandleIndex, ClosureMask),
+    PackClosureGeneric(FunctionInstantiationIndex, ClosureMask),
+    /// Call the function value at the top of the stack with the arguments below it. The
+    /// signature specified via `SignatureIndex` contains the function type of the value.
+    /// The arguments are consumed and, together with the values captured by the closure, pushed
+    /// to the locals of the underlying function. Return values are pushed on the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., arg(1), ..., arg(n), function_value -> ..., return_value(1), ...,
+    /// return_value(k)```
+    CallClosure(SignatureIndex),
 }
 
 impl ::std::fmt::Debug for Bytecode {
@@ -1736,6 +1880,11 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::VecPopBack(a) => write!(f, "VecPopBack({})", a),
             Bytecode::VecUnpack(a, n) => write!(f, "VecUnpack({}, {})", a, n),
             Bytecode::VecSwap(a) => write!(f, "VecSwap({})", a),
+            Bytecode::PackClosure(a, mask) => write!(f, "PackClosure({}, {})", a, mask),
+            Bytecode::PackClosureGeneric(a, mask) => {
+                write!(f, "PackClosureGeneric({}, {})", a, mask)
+            },
+            Bytecode::CallClosure(a) => write!(f, "CallClosure({})", a),
         }
     }
 }
@@ -124,6 +124,7 @@ pub enum SerializedType {
     U16                     = 0xD,
     U32                     = 0xE,
     U256                    = 0xF,
+    FUNCTION                = 0x10,
 }
 
 #[rustfmt::skip]
@@ -218,6 +219,9 @@ pub enum Opcodes {
     CAST_U16                    = 0x4B,
     CAST_U32                    = 0x4C,
     CAST_U256                   = 0x4D,
+    PACK_CLOSURE                = 0x4E,
+    PACK_CLOSURE_GENERIC        = 0x4F,
+    CALL_CLOSURE                = 0x50,
 }
 
 /// Upper limit on the binary size
@@ -405,8 +409,16 @@ pub const VERSION_5: u32 = 5;
 ///  + u16, u32, u256 integers and corresponding Ld, Cast bytecodes
 pub const VERSION_6: u32 = 6;
 
+/// Version 7: changes compared with version 6
+///  + function types and the PackClosure, PackClosureGeneric and CallClosure bytecodes
+pub const VERSION_7: u32 = 7;
+
 /// Mark which version is the latest version
-pub const VERSION_MAX: u32 = VERSION_6;
+pub const VERSION_MAX: u32 = VERSION_7;
+
+/// Mark which version is used by default when serializing. Newer versions must be requested
+/// explicitly until the networks accept them.
+pub const VERSION_DEFAULT: u32 = VERSION_6;
 
 /// A unique version value which is used for experimental code which is not allowed in
 /// production. The bytecode deserializer accepts modules with this version only when the
@@ -633,6 +645,9 @@ pub fn instruction_key(instruction: &Bytecode) -> u8 {
         CastU16 => Opcodes::CAST_U16,
         CastU32 => Opcodes::CAST_U32,
         CastU256 => Opcodes::CAST_U256,
+        PackClosure(..) => Opcodes::PACK_CLOSURE,
+        PackClosureGeneric(..) => Opcodes::PACK_CLOSURE_GENERIC,
+        CallClosure(_) => Opcodes::CALL_CLOSURE,
     };
     opcode as u8
 }
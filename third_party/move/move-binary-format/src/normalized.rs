@@ -60,6 +60,12 @@ pub enum Type {
     U32,
     #[serde(rename = "u256")]
     U256,
+    // NOTE: Added in bytecode version v7, do not reorder!
+    Function {
+        args: Vec<Type>,
+        results: Vec<Type>,
+        abilities: AbilitySet,
+    },
 }
 
 /// Normalized version of a `FieldDefinition`. The `name` is included even though it is
@@ -179,6 +185,11 @@ impl Type {
             TypeParameter(i) => Type::TypeParameter(*i),
             Reference(t) => Type::Reference(Box::new(Type::new(m, t))),
             MutableReference(t) => Type::MutableReference(Box::new(Type::new(m, t))),
+            Function(args, results, abilities) => Type::Function {
+                args: args.iter().map(|t| Type::new(m, t)).collect(),
+                results: results.iter().map(|t| Type::new(m, t)).collect(),
+                abilities: *abilities,
+            },
         }
     }
 
@@ -198,6 +209,7 @@ impl Type {
             Signer => true,
             Struct { type_arguments, .. } => type_arguments.iter().all(|t| t.is_closed()),
             Vector(t) | Reference(t) | MutableReference(t) => t.is_closed(),
+            Function { args, results, .. } => args.iter().chain(results).all(|t| t.is_closed()),
         }
     }
 
@@ -206,7 +218,8 @@ impl Type {
         Some(
             if self.is_closed() {
                 match self {
-                    Reference(_) | MutableReference(_) => return None,
+                    // Function types cannot be used as type arguments and have no type tag
+                    Reference(_) | MutableReference(_) | Function { .. } => return None,
                     Bool => TypeTag::Bool,
                     U8 => TypeTag::U8,
                     U16 => TypeTag::U16,
@@ -278,6 +291,15 @@ impl Type {
                 .get(*i as usize)
                 .expect("Type parameter index out of bound")
                 .clone(),
+            Function {
+                args,
+                results,
+                abilities,
+            } => Function {
+                args: args.iter().map(|t| t.subst(type_args)).collect(),
+                results: results.iter().map(|t| t.subst(type_args)).collect(),
+                abilities: *abilities,
+            },
         }
     }
 }
@@ -419,6 +441,39 @@ impl std::fmt::Display for Type {
             Type::Reference(r) => write!(f, "&{}", r),
             Type::MutableReference(r) => write!(f, "&mut {}", r),
             Type::TypeParameter(i) => write!(f, "T{:?}", i),
+            Type::Function {
+                args,
+                results,
+                abilities,
+            } => {
+                write!(f, "|")?;
+                for (i, ty) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ty)?;
+                }
+                write!(f, "|")?;
+                match results.as_slice() {
+                    [] => {},
+                    [ty] => write!(f, "{}", ty)?,
+                    _ => {
+                        write!(f, "(")?;
+                        for (i, ty) in results.iter().enumerate() {
+                            if i > 0 {
+                                write!(f, ", ")?;
+                            }
+                            write!(f, "{}", ty)?;
+                        }
+                        write!(f, ")")?;
+                    },
+                }
+                for (i, ability) in (*abilities).into_iter().enumerate() {
+                    let ability = format!("{:?}", ability).to_lowercase();
+                    write!(f, "{}{}", if i == 0 { " has " } else { " + " }, ability)?;
+                }
+                Ok(())
+            },
         }
     }
 }
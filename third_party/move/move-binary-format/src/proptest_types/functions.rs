@@ -900,7 +900,7 @@ impl BytecodeGen {
             StructInstantiation(_, type_arguments) => type_arguments
                 .iter()
                 .all(BytecodeGen::check_signature_token),
-            Reference(_) | MutableReference(_) => false,
+            Reference(_) | MutableReference(_) | Function(..) => false,
         }
     }
 
@@ -83,6 +83,7 @@ impl StDefnMaterializeState {
                     acc.intersect(ty_arg_abilities)
                 })
             },
+            Function(_, _, abilities) => *abilities,
         }
     }
 }
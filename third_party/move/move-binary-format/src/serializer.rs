@@ -34,7 +34,7 @@ impl CompiledScript {
         bytecode_version: Option<u32>,
         binary: &mut Vec<u8>,
     ) -> Result<()> {
-        let version = bytecode_version.unwrap_or(VERSION_MAX);
+        let version = bytecode_version.unwrap_or(VERSION_DEFAULT);
         validate_version(version)?;
         let mut binary_data = BinaryData::from(binary.clone());
         let mut ser = ScriptSerializer::new(version);
@@ -226,7 +226,7 @@ impl CompiledModule {
         bytecode_version: Option<u32>,
         binary: &mut Vec<u8>,
     ) -> Result<()> {
-        let version = bytecode_version.unwrap_or(VERSION_MAX);
+        let version = bytecode_version.unwrap_or(VERSION_DEFAULT);
         validate_version(version)?;
         let mut binary_data = BinaryData::from(binary.clone());
         let mut ser = ModuleSerializer::new(version);
@@ -670,6 +670,12 @@ fn serialize_signature_token_single_node_impl(
             binary.push(SerializedType::TYPE_PARAMETER as u8)?;
             serialize_type_parameter_index(binary, *idx)?;
         },
+        SignatureToken::Function(args, results, abilities) => {
+            binary.push(SerializedType::FUNCTION as u8)?;
+            serialize_signature_size(binary, args.len())?;
+            serialize_signature_size(binary, results.len())?;
+            serialize_ability_set(binary, *abilities)?;
+        },
     }
     Ok(())
 }
@@ -748,6 +754,14 @@ fn serialize_instruction_inner(
                 major_version
             ));
         },
+        Bytecode::PackClosure(..) | Bytecode::PackClosureGeneric(..) | Bytecode::CallClosure(_)
+            if (major_version < VERSION_7) =>
+        {
+            return Err(anyhow!(
+                "Closures not supported in bytecode version {}",
+                major_version
+            ));
+        },
         _ => (),
     };
 
@@ -960,6 +974,20 @@ fn serialize_instruction_inner(
         Bytecode::CastU16 => binary.push(Opcodes::CAST_U16 as u8),
         Bytecode::CastU32 => binary.push(Opcodes::CAST_U32 as u8),
         Bytecode::CastU256 => binary.push(Opcodes::CAST_U256 as u8),
+        Bytecode::PackClosure(fun_idx, mask) => {
+            binary.push(Opcodes::PACK_CLOSURE as u8)?;
+            serialize_function_handle_index(binary, fun_idx)?;
+            write_u64(binary, mask.bits())
+        },
+        Bytecode::PackClosureGeneric(fun_inst_idx, mask) => {
+            binary.push(Opcodes::PACK_CLOSURE_GENERIC as u8)?;
+            serialize_function_inst_index(binary, fun_inst_idx)?;
+            write_u64(binary, mask.bits())
+        },
+        Bytecode::CallClosure(sig_idx) => {
+            binary.push(Opcodes::CALL_CLOSURE as u8)?;
+            serialize_signature_index(binary, sig_idx)
+        },
     };
     res?;
     Ok(())
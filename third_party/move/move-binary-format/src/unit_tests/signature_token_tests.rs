@@ -4,7 +4,7 @@
 
 use crate::{
     deserializer::load_signature_token_test_entry,
-    file_format::{SignatureToken, StructHandleIndex},
+    file_format::{AbilitySet, SignatureToken, StructHandleIndex},
     file_format_common::{BinaryData, SerializedType, SIGNATURE_TOKEN_DEPTH_MAX},
     serializer::{serialize_signature_token, serialize_signature_token_unchecked},
 };
@@ -45,6 +45,29 @@ fn serialize_nested_types_too_deep() {
     }
 }
 
+#[test]
+fn serialize_and_deserialize_function_type() {
+    let ty = SignatureToken::Function(
+        vec![
+            SignatureToken::Reference(Box::new(SignatureToken::U64)),
+            SignatureToken::Vector(Box::new(SignatureToken::Struct(StructHandleIndex::new(0)))),
+        ],
+        vec![SignatureToken::Function(
+            vec![],
+            vec![SignatureToken::Bool],
+            AbilitySet::EMPTY,
+        )],
+        AbilitySet::FUNCTIONS,
+    );
+    let mut binary = BinaryData::new();
+    serialize_signature_token(&mut binary, &ty).expect("serialization should succeed");
+
+    let cursor = Cursor::new(binary.as_inner());
+    let deserialized =
+        load_signature_token_test_entry(cursor).expect("deserialization should succeed");
+    assert_eq!(ty, deserialized);
+}
+
 #[test]
 fn deserialize_struct_inst_arity_0() {
     let cursor = Cursor::new(
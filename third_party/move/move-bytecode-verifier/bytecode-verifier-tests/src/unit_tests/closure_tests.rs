@@ -0,0 +1,288 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::*;
+use move_bytecode_verifier::dependencies;
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, vm_status::StatusCode,
+};
+
+fn u64_to_u64(abilities: AbilitySet) -> SignatureToken {
+    SignatureToken::Function(
+        vec![SignatureToken::U64],
+        vec![SignatureToken::U64],
+        abilities,
+    )
+}
+
+// module 0x0::M {
+//     <visibility> fun add(x: u64, y: u64): u64 { x + y }
+//     public fun test(): <return_> { <code> }
+// }
+fn mk_module(
+    visibility: Visibility,
+    return_: SignatureToken,
+    code: Vec<Bytecode>,
+) -> CompiledModule {
+    CompiledModule {
+        version: move_binary_format::file_format_common::VERSION_7,
+        module_handles: vec![ModuleHandle {
+            address: AddressIdentifierIndex(0),
+            name: IdentifierIndex(0),
+        }],
+        self_module_handle_idx: ModuleHandleIndex(0),
+        identifiers: vec![
+            Identifier::new("M").unwrap(),
+            Identifier::new("add").unwrap(),
+            Identifier::new("test").unwrap(),
+        ],
+        address_identifiers: vec![AccountAddress::ZERO],
+        function_handles: vec![
+            // fun add(u64, u64): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(1),
+                parameters: SignatureIndex(1),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // fun test(): <return_>
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(2),
+                parameters: SignatureIndex(0),
+                return_: SignatureIndex(3),
+                type_parameters: vec![],
+            },
+        ],
+        function_defs: vec![
+            FunctionDefinition {
+                function: FunctionHandleIndex(0),
+                visibility,
+                is_entry: false,
+                acquires_global_resources: vec![],
+                code: Some(CodeUnit {
+                    locals: SignatureIndex(0),
+                    code: vec![
+                        Bytecode::MoveLoc(0),
+                        Bytecode::MoveLoc(1),
+                        Bytecode::Add,
+                        Bytecode::Ret,
+                    ],
+                }),
+            },
+            FunctionDefinition {
+                function: FunctionHandleIndex(1),
+                visibility: Visibility::Public,
+                is_entry: false,
+                acquires_global_resources: vec![],
+                code: Some(CodeUnit {
+                    locals: SignatureIndex(0),
+                    code,
+                }),
+            },
+        ],
+        signatures: vec![
+            Signature(vec![]),                                         // void
+            Signature(vec![SignatureToken::U64, SignatureToken::U64]), // u64, u64
+            Signature(vec![SignatureToken::U64]),                      // u64
+            Signature(vec![return_]),                                  // return type of test
+            Signature(vec![u64_to_u64(AbilitySet::EMPTY)]),            // |u64| u64
+            Signature(vec![SignatureToken::Function(
+                vec![SignatureToken::Bool],
+                vec![SignatureToken::U64],
+                AbilitySet::EMPTY,
+            )]), // |bool| u64
+        ],
+        struct_defs: vec![],
+        struct_handles: vec![],
+        constant_pool: vec![],
+        metadata: vec![],
+        field_handles: vec![],
+        friend_decls: vec![],
+        struct_def_instantiations: vec![],
+        function_instantiations: vec![],
+        field_instantiations: vec![],
+    }
+}
+
+// module 0x0::Test {
+//     public fun test(): |u64| u64 { 0x0::M::add(1, _) }
+// }
+fn mk_importing_module() -> CompiledModule {
+    CompiledModule {
+        version: move_binary_format::file_format_common::VERSION_7,
+        module_handles: vec![
+            // self module
+            ModuleHandle {
+                address: AddressIdentifierIndex(0),
+                name: IdentifierIndex(0),
+            },
+            // other module
+            ModuleHandle {
+                address: AddressIdentifierIndex(0),
+                name: IdentifierIndex(2),
+            },
+        ],
+        self_module_handle_idx: ModuleHandleIndex(0),
+        identifiers: vec![
+            Identifier::new("Test").unwrap(),
+            Identifier::new("test").unwrap(),
+            Identifier::new("M").unwrap(),
+            Identifier::new("add").unwrap(),
+        ],
+        address_identifiers: vec![AccountAddress::ZERO],
+        function_handles: vec![
+            // Self::test(): |u64| u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(1),
+                parameters: SignatureIndex(0),
+                return_: SignatureIndex(3),
+                type_parameters: vec![],
+            },
+            // 0x0::M::add(u64, u64): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(1),
+                name: IdentifierIndex(3),
+                parameters: SignatureIndex(1),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+        ],
+        function_defs: vec![FunctionDefinition {
+            function: FunctionHandleIndex(0),
+            visibility: Visibility::Public,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: Some(CodeUnit {
+                locals: SignatureIndex(0),
+                code: vec![
+                    Bytecode::LdU64(1),
+                    Bytecode::PackClosure(FunctionHandleIndex(1), ClosureMask::new(0b1)),
+                    Bytecode::Ret,
+                ],
+            }),
+        }],
+        signatures: vec![
+            Signature(vec![]),                                         // void
+            Signature(vec![SignatureToken::U64, SignatureToken::U64]), // u64, u64
+            Signature(vec![SignatureToken::U64]),                      // u64
+            Signature(vec![u64_to_u64(AbilitySet::FUNCTIONS)]),        // |u64| u64 has ..
+        ],
+        struct_defs: vec![],
+        struct_handles: vec![],
+        constant_pool: vec![],
+        metadata: vec![],
+        field_handles: vec![],
+        friend_decls: vec![],
+        struct_def_instantiations: vec![],
+        function_instantiations: vec![],
+        field_instantiations: vec![],
+    }
+}
+
+#[test]
+fn pack_and_call_closure() {
+    let m = mk_module(Visibility::Private, SignatureToken::Bool, vec![
+        Bytecode::LdU64(2),
+        Bytecode::LdU64(1),
+        Bytecode::PackClosure(FunctionHandleIndex(0), ClosureMask::new(0b1)),
+        Bytecode::CallClosure(SignatureIndex(4)),
+        Bytecode::LdU64(3),
+        Bytecode::Eq,
+        Bytecode::Ret,
+    ]);
+    move_bytecode_verifier::verify_module(&m).unwrap();
+}
+
+#[test]
+fn call_closure_type_mismatch() {
+    let m = mk_module(Visibility::Private, SignatureToken::Bool, vec![
+        Bytecode::LdU64(2),
+        Bytecode::LdU64(1),
+        Bytecode::PackClosure(FunctionHandleIndex(0), ClosureMask::new(0b1)),
+        Bytecode::CallClosure(SignatureIndex(5)),
+        Bytecode::LdU64(3),
+        Bytecode::Eq,
+        Bytecode::Ret,
+    ]);
+    assert_eq!(
+        move_bytecode_verifier::verify_module(&m)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::CALL_TYPE_MISMATCH_ERROR,
+    );
+}
+
+#[test]
+fn closure_mask_out_of_range() {
+    let m = mk_module(Visibility::Private, SignatureToken::Bool, vec![
+        Bytecode::LdU64(2),
+        Bytecode::LdU64(1),
+        Bytecode::PackClosure(FunctionHandleIndex(0), ClosureMask::new(0b100)),
+        Bytecode::CallClosure(SignatureIndex(4)),
+        Bytecode::LdU64(3),
+        Bytecode::Eq,
+        Bytecode::Ret,
+    ]);
+    assert!(move_bytecode_verifier::verify_module(&m).is_err());
+}
+
+#[test]
+fn closure_store_ability_requires_public_function() {
+    let code = vec![
+        Bytecode::LdU64(1),
+        Bytecode::PackClosure(FunctionHandleIndex(0), ClosureMask::new(0b1)),
+        Bytecode::Ret,
+    ];
+
+    let m = mk_module(
+        Visibility::Public,
+        u64_to_u64(AbilitySet::FUNCTIONS),
+        code.clone(),
+    );
+    move_bytecode_verifier::verify_module(&m).unwrap();
+
+    let m = mk_module(
+        Visibility::Private,
+        u64_to_u64(AbilitySet::FUNCTIONS),
+        code.clone(),
+    );
+    assert_eq!(
+        move_bytecode_verifier::verify_module(&m)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::RET_TYPE_MISMATCH_ERROR,
+    );
+
+    let m = mk_module(
+        Visibility::Private,
+        u64_to_u64(AbilitySet::FUNCTIONS.remove(Ability::Store)),
+        code,
+    );
+    move_bytecode_verifier::verify_module(&m).unwrap();
+}
+
+#[test]
+fn closure_over_other_module_requires_public_function() {
+    let importing_module = mk_importing_module();
+    move_bytecode_verifier::verify_module(&importing_module).unwrap();
+
+    let public_dep = mk_module(Visibility::Public, SignatureToken::Bool, vec![
+        Bytecode::LdTrue,
+        Bytecode::Ret,
+    ]);
+    let result = dependencies::verify_module(&importing_module, &[public_dep]);
+    assert!(result.is_ok());
+
+    let friend_dep = mk_module(Visibility::Friend, SignatureToken::Bool, vec![
+        Bytecode::LdTrue,
+        Bytecode::Ret,
+    ]);
+    let result = dependencies::verify_module(&importing_module, &[friend_dep]);
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::LOOKUP_FAILED,
+    );
+}
@@ -265,6 +265,32 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
                         FunctionInstantiationIndex,
                         CallGeneric
                     ),
+                    PackClosure(_, mask) => new_bytecode!(
+                        function_handles_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        FunctionHandleIndex,
+                        PackClosure,
+                        mask
+                    ),
+                    PackClosureGeneric(_, mask) => new_bytecode!(
+                        function_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        FunctionInstantiationIndex,
+                        PackClosureGeneric,
+                        mask
+                    ),
+                    CallClosure(_) => new_bytecode!(
+                        signature_pool_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        SignatureIndex,
+                        CallClosure
+                    ),
                     Pack(_) => struct_bytecode!(
                         struct_defs_len,
                         current_fdef,
@@ -506,6 +532,9 @@ fn is_interesting(bytecode: &Bytecode) -> bool {
         | MutBorrowFieldGeneric(_)
         | Call(_)
         | CallGeneric(_)
+        | PackClosure(..)
+        | PackClosureGeneric(..)
+        | CallClosure(_)
         | Pack(_)
         | PackGeneric(_)
         | Unpack(_)
@@ -177,7 +177,8 @@ fn verify_module_impl<'a>(
     verify_imported_modules(context)?;
     verify_imported_structs(context)?;
     verify_imported_functions(context)?;
-    verify_all_script_visibility_usage(context)
+    verify_all_script_visibility_usage(context)?;
+    verify_all_closure_visibility_usage(context)
 }
 
 pub fn verify_script<'a>(
@@ -196,7 +197,8 @@ pub fn verify_script_impl<'a>(
     verify_imported_modules(context)?;
     verify_imported_structs(context)?;
     verify_imported_functions(context)?;
-    verify_all_script_visibility_usage(context)
+    verify_all_script_visibility_usage(context)?;
+    verify_all_closure_visibility_usage(context)
 }
 
 fn verify_imported_modules(context: &Context) -> PartialVMResult<()> {
@@ -474,6 +476,16 @@ fn compare_types(
                 Ok(())
             }
         },
+        (
+            SignatureToken::Function(args1, results1, abilities1),
+            SignatureToken::Function(args2, results2, abilities2),
+        ) => {
+            if abilities1 != abilities2 {
+                return Err(PartialVMError::new(StatusCode::TYPE_MISMATCH));
+            }
+            compare_cross_module_signatures(context, args1, args2, def_module)?;
+            compare_cross_module_signatures(context, results1, results2, def_module)
+        },
         (SignatureToken::Bool, _)
         | (SignatureToken::U8, _)
         | (SignatureToken::U64, _)
@@ -488,7 +500,10 @@ fn compare_types(
         | (SignatureToken::TypeParameter(_), _)
         | (SignatureToken::U16, _)
         | (SignatureToken::U32, _)
-        | (SignatureToken::U256, _) => Err(PartialVMError::new(StatusCode::TYPE_MISMATCH)),
+        | (SignatureToken::U256, _)
+        | (SignatureToken::Function(_, _, _), _) => {
+            Err(PartialVMError::new(StatusCode::TYPE_MISMATCH))
+        },
     }
 }
 
@@ -551,6 +566,64 @@ fn verify_all_script_visibility_usage(context: &Context) -> PartialVMResult<()>
     }
 }
 
+fn verify_all_closure_visibility_usage(context: &Context) -> PartialVMResult<()> {
+    match &context.resolver {
+        BinaryIndexedView::Module(m) => {
+            for (idx, fdef) in m.function_defs().iter().enumerate() {
+                let code = match &fdef.code {
+                    None => continue,
+                    Some(code) => &code.code,
+                };
+                verify_closure_visibility_usage(
+                    context,
+                    FunctionDefinitionIndex(idx as TableIndex),
+                    code,
+                )?
+            }
+            Ok(())
+        },
+        BinaryIndexedView::Script(s) => {
+            verify_closure_visibility_usage(context, FunctionDefinitionIndex(0), &s.code().code)
+        },
+    }
+}
+
+// Closures may be stored and called in later transactions. Only public functions keep their
+// signature across module upgrades, hence closures over functions of other modules are
+// restricted to public ones.
+fn verify_closure_visibility_usage(
+    context: &Context,
+    fdef_idx: FunctionDefinitionIndex,
+    code: &[Bytecode],
+) -> PartialVMResult<()> {
+    let self_module = context.resolver.self_handle_idx();
+    for (idx, instr) in code.iter().enumerate() {
+        let idx = idx as CodeOffset;
+        let fhandle_idx = match instr {
+            Bytecode::PackClosure(fhandle_idx, _) => fhandle_idx,
+            Bytecode::PackClosureGeneric(finst_idx, _) => {
+                &context
+                    .resolver
+                    .function_instantiation_at(*finst_idx)
+                    .handle
+            },
+            _ => continue,
+        };
+        if Some(context.resolver.function_handle_at(*fhandle_idx).module) == self_module {
+            continue;
+        }
+        if context.function_visibilities.get(fhandle_idx) != Some(&Visibility::Public) {
+            return Err(PartialVMError::new(StatusCode::LOOKUP_FAILED)
+                .at_code_offset(fdef_idx, idx)
+                .with_message(
+                    "closures can only be packed over public functions of other modules"
+                        .to_string(),
+                ));
+        }
+    }
+    Ok(())
+}
+
 fn verify_script_visibility_usage(
     resolver: &BinaryIndexedView,
     script_functions: &BTreeSet<FunctionHandleIndex>,
@@ -154,6 +154,11 @@ impl<'a> InstantiationLoopChecker<'a> {
                         rec(type_params, ty);
                     }
                 },
+                Function(args, results, _) => {
+                    for ty in args.iter().chain(results) {
+                        rec(type_params, ty);
+                    }
+                },
             }
         }
 
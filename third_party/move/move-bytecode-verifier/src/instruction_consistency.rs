@@ -11,8 +11,9 @@ use move_binary_format::{
     binary_views::BinaryIndexedView,
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledScript, FieldHandleIndex,
-        FunctionDefinitionIndex, FunctionHandleIndex, StructDefinitionIndex, TableIndex,
+        Bytecode, ClosureMask, CodeOffset, CodeUnit, CompiledModule, CompiledScript,
+        FieldHandleIndex, FunctionDefinitionIndex, FunctionHandleIndex, StructDefinitionIndex,
+        TableIndex,
     },
 };
 use move_core_types::vm_status::StatusCode;
@@ -83,6 +84,15 @@ impl<'a> InstructionConsistency<'a> {
                     let func_inst = self.resolver.function_instantiation_at(*idx);
                     self.check_function_op(offset, func_inst.handle, /* generic */ true)?;
                 },
+                PackClosure(idx, mask) => {
+                    self.check_function_op(offset, *idx, /* generic */ false)?;
+                    self.check_closure_mask(offset, *idx, *mask)?;
+                },
+                PackClosureGeneric(idx, mask) => {
+                    let func_inst = self.resolver.function_instantiation_at(*idx);
+                    self.check_function_op(offset, func_inst.handle, /* generic */ true)?;
+                    self.check_closure_mask(offset, func_inst.handle, *mask)?;
+                },
                 Pack(idx) => {
                     self.check_type_op(offset, *idx, /* generic */ false)?;
                 },
@@ -148,7 +158,8 @@ impl<'a> InstructionConsistency<'a> {
                 | WriteRef | Add | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor | Shl | Shr
                 | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | CopyLoc(_) | MoveLoc(_)
                 | StLoc(_) | MutBorrowLoc(_) | ImmBorrowLoc(_) | VecLen(_) | VecImmBorrow(_)
-                | VecMutBorrow(_) | VecPushBack(_) | VecPopBack(_) | VecSwap(_) | Abort | Nop => (),
+                | VecMutBorrow(_) | VecPushBack(_) | VecPopBack(_) | VecSwap(_) | Abort | Nop
+                | CallClosure(_) => (),
             }
         }
         Ok(())
@@ -206,4 +217,23 @@ impl<'a> InstructionConsistency<'a> {
         }
         Ok(())
     }
+
+    fn check_closure_mask(
+        &self,
+        offset: usize,
+        func_handle_index: FunctionHandleIndex,
+        mask: ClosureMask,
+    ) -> PartialVMResult<()> {
+        let function_handle = self.resolver.function_handle_at(func_handle_index);
+        let param_count = self.resolver.signature_at(function_handle.parameters).len();
+        if !mask.is_valid_for(param_count) {
+            return Err(PartialVMError::new(StatusCode::CONSTRAINT_NOT_SATISFIED)
+                .at_code_offset(self.current_function(), offset as CodeOffset)
+                .with_message(format!(
+                    "closure mask {} does not fit function with {} parameters",
+                    mask, param_count
+                )));
+        }
+        Ok(())
+    }
 }
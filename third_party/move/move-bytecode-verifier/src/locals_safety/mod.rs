@@ -111,6 +111,9 @@ fn execute_inner(
         | Bytecode::LdFalse
         | Bytecode::Call(_)
         | Bytecode::CallGeneric(_)
+        | Bytecode::PackClosure(..)
+        | Bytecode::PackClosureGeneric(..)
+        | Bytecode::CallClosure(_)
         | Bytecode::Pack(_)
         | Bytecode::PackGeneric(_)
         | Bytecode::Unpack(_)
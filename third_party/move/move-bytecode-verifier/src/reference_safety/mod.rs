@@ -22,8 +22,9 @@ use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        Bytecode, CodeOffset, FunctionDefinitionIndex, FunctionHandle, IdentifierIndex,
-        SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation,
+        Bytecode, CodeOffset, FunctionDefinitionIndex, FunctionHandle, IdentifierIndex, Signature,
+        SignatureIndex, SignatureToken, StructDefinition, StructDefinitionIndex,
+        StructFieldInformation, TableIndex,
     },
     safe_assert, safe_unwrap,
 };
@@ -99,6 +100,40 @@ fn call(
     Ok(())
 }
 
+fn call_closure(
+    verifier: &mut ReferenceSafetyAnalysis,
+    state: &mut AbstractState,
+    offset: CodeOffset,
+    sig_idx: SignatureIndex,
+    meter: &mut impl Meter,
+) -> PartialVMResult<()> {
+    let (parameters, return_) = match verifier.resolver.signature_at(sig_idx).0.as_slice() {
+        [SignatureToken::Function(args, results, _)] => (args, Signature(results.clone())),
+        _ => {
+            return Err(PartialVMError::new(
+                StatusCode::VERIFIER_INVARIANT_VIOLATION,
+            ))
+        },
+    };
+    safe_assert!(safe_unwrap!(verifier.stack.pop()).is_value());
+    let arguments = parameters
+        .iter()
+        .map(|_| verifier.stack.pop().unwrap())
+        .rev()
+        .collect();
+
+    // The function behind a closure is not known statically and may call back into this
+    // module, so the call is treated as acquiring every resource declared here.
+    let acquired_resources = (0..verifier.resolver.struct_defs().map_or(0, |defs| defs.len()))
+        .map(|idx| StructDefinitionIndex(idx as TableIndex))
+        .collect();
+    let values = state.call(offset, arguments, &acquired_resources, &return_, meter)?;
+    for value in values {
+        verifier.stack.push(value)
+    }
+    Ok(())
+}
+
 fn num_fields(struct_def: &StructDefinition) -> usize {
     match &struct_def.field_information {
         StructFieldInformation::Native => 0,
@@ -273,6 +308,13 @@ fn execute_inner(
             let function_handle = verifier.resolver.function_handle_at(func_inst.handle);
             call(verifier, state, offset, function_handle, meter)?
         },
+        Bytecode::PackClosure(_, mask) | Bytecode::PackClosureGeneric(_, mask) => {
+            for _ in 0..mask.captured_count() {
+                safe_assert!(safe_unwrap!(verifier.stack.pop()).is_value())
+            }
+            verifier.stack.push(AbstractValue::NonReference)
+        },
+        Bytecode::CallClosure(idx) => call_closure(verifier, state, offset, *idx, meter)?,
 
         Bytecode::Ret => {
             let mut return_values = vec![];
@@ -137,11 +137,11 @@ impl<'a> SignatureChecker<'a> {
         use Bytecode::*;
         for (offset, instr) in code.code.iter().enumerate() {
             let result = match instr {
-                CallGeneric(idx) => {
+                CallGeneric(idx) | PackClosureGeneric(idx, _) => {
                     let func_inst = self.resolver.function_instantiation_at(*idx);
                     let func_handle = self.resolver.function_handle_at(func_inst.handle);
                     let type_arguments = &self.resolver.signature_at(func_inst.type_parameters).0;
-                    self.check_signature_tokens(type_arguments)?;
+                    self.check_type_arguments(type_arguments)?;
                     self.check_generic_instance(
                         type_arguments,
                         func_handle.type_parameters.iter().copied(),
@@ -159,7 +159,7 @@ impl<'a> SignatureChecker<'a> {
                     let struct_def = self.resolver.struct_def_at(struct_inst.def)?;
                     let struct_handle = self.resolver.struct_handle_at(struct_def.struct_handle);
                     let type_arguments = &self.resolver.signature_at(struct_inst.type_parameters).0;
-                    self.check_signature_tokens(type_arguments)?;
+                    self.check_type_arguments(type_arguments)?;
                     self.check_generic_instance(
                         type_arguments,
                         struct_handle.type_param_constraints(),
@@ -172,7 +172,7 @@ impl<'a> SignatureChecker<'a> {
                     let struct_def = self.resolver.struct_def_at(field_handle.owner)?;
                     let struct_handle = self.resolver.struct_handle_at(struct_def.struct_handle);
                     let type_arguments = &self.resolver.signature_at(field_inst.type_parameters).0;
-                    self.check_signature_tokens(type_arguments)?;
+                    self.check_type_arguments(type_arguments)?;
                     self.check_generic_instance(
                         type_arguments,
                         struct_handle.type_param_constraints(),
@@ -197,19 +197,81 @@ impl<'a> SignatureChecker<'a> {
                             type_arguments.len()
                         )));
                     }
-                    self.check_signature_tokens(type_arguments)
+                    self.check_type_arguments(type_arguments)
+                },
+                CallClosure(idx) => {
+                    let tys = &self.resolver.signature_at(*idx).0;
+                    if tys.len() != 1 || !tys[0].is_function() {
+                        return Err(PartialVMError::new(StatusCode::INVALID_SIGNATURE_TOKEN)
+                            .with_message(
+                                "expected a single function type for closure calls".to_string(),
+                            ));
+                    }
+                    self.check_signature_tokens(tys)
                 },
 
                 // List out the other options explicitly so there's a compile error if a new
                 // bytecode gets added.
-                Pop | Ret | Branch(_) | BrTrue(_) | BrFalse(_) | LdU8(_) | LdU16(_) | LdU32(_)
-                | LdU64(_) | LdU128(_) | LdU256(_) | LdConst(_) | CastU8 | CastU16 | CastU32
-                | CastU64 | CastU128 | CastU256 | LdTrue | LdFalse | Call(_) | Pack(_)
-                | Unpack(_) | ReadRef | WriteRef | FreezeRef | Add | Sub | Mul | Mod | Div
-                | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le
-                | Ge | CopyLoc(_) | MoveLoc(_) | StLoc(_) | MutBorrowLoc(_) | ImmBorrowLoc(_)
-                | MutBorrowField(_) | ImmBorrowField(_) | MutBorrowGlobal(_)
-                | ImmBorrowGlobal(_) | Exists(_) | MoveTo(_) | MoveFrom(_) | Abort | Nop => Ok(()),
+                Pop
+                | Ret
+                | Branch(_)
+                | BrTrue(_)
+                | BrFalse(_)
+                | LdU8(_)
+                | LdU16(_)
+                | LdU32(_)
+                | LdU64(_)
+                | LdU128(_)
+                | LdU256(_)
+                | LdConst(_)
+                | CastU8
+                | CastU16
+                | CastU32
+                | CastU64
+                | CastU128
+                | CastU256
+                | LdTrue
+                | LdFalse
+                | Call(_)
+                | Pack(_)
+                | Unpack(_)
+                | ReadRef
+                | WriteRef
+                | FreezeRef
+                | Add
+                | Sub
+                | Mul
+                | Mod
+                | Div
+                | BitOr
+                | BitAnd
+                | Xor
+                | Shl
+                | Shr
+                | Or
+                | And
+                | Not
+                | Eq
+                | Neq
+                | Lt
+                | Gt
+                | Le
+                | Ge
+                | CopyLoc(_)
+                | MoveLoc(_)
+                | StLoc(_)
+                | MutBorrowLoc(_)
+                | ImmBorrowLoc(_)
+                | MutBorrowField(_)
+                | ImmBorrowField(_)
+                | MutBorrowGlobal(_)
+                | ImmBorrowGlobal(_)
+                | Exists(_)
+                | MoveTo(_)
+                | MoveFrom(_)
+                | Abort
+                | Nop
+                | PackClosure(_, _) => Ok(()),
             };
             result.map_err(|err| {
                 err.append_message_with_separator(' ', format!("at offset {} ", offset))
@@ -247,6 +309,11 @@ impl<'a> SignatureChecker<'a> {
                     ));
                 }
             },
+            SignatureToken::Function(args, results, _) => {
+                for ty in args.iter().chain(results) {
+                    self.check_phantom_params(ty, false, type_parameters)?;
+                }
+            },
 
             SignatureToken::Struct(_)
             | SignatureToken::Reference(_)
@@ -287,6 +354,19 @@ impl<'a> SignatureChecker<'a> {
         Ok(())
     }
 
+    /// Checks if the given types are well defined type arguments in the given context.
+    /// Neither references nor function types are permitted.
+    fn check_type_arguments(&self, tys: &[SignatureToken]) -> PartialVMResult<()> {
+        for ty in tys {
+            if ty.is_function() {
+                return Err(PartialVMError::new(StatusCode::INVALID_SIGNATURE_TOKEN)
+                    .with_message("function types cannot be used as type arguments".to_string()));
+            }
+            self.check_signature_token(ty)?
+        }
+        Ok(())
+    }
+
     /// Checks if the given type is well defined in the given context.
     /// No references are permitted.
     fn check_signature_token(&self, ty: &SignatureToken) -> PartialVMResult<()> {
@@ -300,8 +380,23 @@ impl<'a> SignatureChecker<'a> {
                 Err(PartialVMError::new(StatusCode::INVALID_SIGNATURE_TOKEN)
                     .with_message("reference not allowed".to_string()))
             },
-            Vector(ty) => self.check_signature_token(ty),
-            StructInstantiation(_, type_arguments) => self.check_signature_tokens(type_arguments),
+            Vector(ty) => self.check_type_arguments(std::slice::from_ref(ty)),
+            StructInstantiation(_, type_arguments) => self.check_type_arguments(type_arguments),
+            Function(args, results, abilities) => {
+                if !abilities.is_subset(AbilitySet::FUNCTIONS) {
+                    return Err(PartialVMError::new(StatusCode::INVALID_SIGNATURE_TOKEN)
+                        .with_message("function types cannot have the key ability".to_string()));
+                }
+                for ty in args.iter().chain(results) {
+                    match ty {
+                        Reference(inner) | MutableReference(inner) => {
+                            self.check_signature_token(inner)?
+                        },
+                        _ => self.check_signature_token(ty)?,
+                    }
+                }
+                Ok(())
+            },
         }
     }
 
@@ -363,7 +458,8 @@ impl<'a> SignatureChecker<'a> {
             | SignatureToken::U128
             | SignatureToken::U256
             | SignatureToken::Address
-            | SignatureToken::Signer => Ok(()),
+            | SignatureToken::Signer
+            | SignatureToken::Function(_, _, _) => Ok(()),
         }
     }
 
@@ -164,6 +164,7 @@ fn check_ty<const N: usize>(
         },
         Vector(ty) => {
             assert_abilities(AbilitySet::VECTOR, required_abilities)?;
+            check_not_function_type_argument(ty)?;
             check_ty(
                 struct_handles,
                 ty,
@@ -193,6 +194,7 @@ fn check_ty<const N: usize>(
                 );
             }
             for (ty_param, ty_arg) in handle.type_parameters.iter().zip(ty_args.iter()) {
+                check_not_function_type_argument(ty_arg)?;
                 let required_abilities = if ty_param.is_phantom {
                     ty_param.constraints
                 } else {
@@ -208,11 +210,36 @@ fn check_ty<const N: usize>(
                 )?;
             }
         },
+        Function(args, results, abilities) => {
+            if !abilities.is_subset(AbilitySet::FUNCTIONS) {
+                return Err(PartialVMError::new(StatusCode::INVALID_SIGNATURE_TOKEN)
+                    .with_message("function types cannot have the key ability".to_string()));
+            }
+            assert_abilities(*abilities, required_abilities)?;
+            for ty in args.iter().chain(results) {
+                check_ty(
+                    struct_handles,
+                    ty,
+                    true,
+                    AbilitySet::EMPTY,
+                    param_constraints,
+                )?;
+            }
+        },
     }
 
     Ok(())
 }
 
+/// Function types are not allowed as type arguments, including as the element type of vectors.
+fn check_not_function_type_argument(ty: &SignatureToken) -> PartialVMResult<()> {
+    if ty.is_function() {
+        return Err(PartialVMError::new(StatusCode::INVALID_SIGNATURE_TOKEN)
+            .with_message("function types cannot be used as type arguments".to_string()));
+    }
+    Ok(())
+}
+
 /// Checks if the given type is well formed and has the required abilities within a context.
 ///
 /// Calls `check_ty` internally.
@@ -281,6 +308,12 @@ fn check_phantom_params(
             }
         },
 
+        Function(args, results, _) => {
+            for ty in args.iter().chain(results) {
+                check_phantom_params(struct_handles, context, false, ty)?;
+            }
+        },
+
         Struct(_) | Reference(_) | MutableReference(_) | Bool | U8 | U16 | U32 | U64 | U128
         | U256 | Address | Signer => {},
     }
@@ -480,6 +513,9 @@ impl<'a, const N: usize> SignatureChecker<'a, N> {
                             .with_message("reference not allowed".to_string())
                             .at_index(IndexKind::FunctionInstantiation, func_inst_idx.0));
                     }
+                    check_not_function_type_argument(ty).map_err(|err| {
+                        err.at_index(IndexKind::FunctionInstantiation, func_inst_idx.0)
+                    })?;
 
                     constraints.merge(self.verify_type_in_signature_contextless(
                         ty_args_idx,
@@ -564,6 +600,9 @@ impl<'a, const N: usize> SignatureChecker<'a, N> {
                             .with_message("reference not allowed".to_string())
                             .at_index(IndexKind::StructDefInstantiation, struct_inst_idx.0));
                     }
+                    check_not_function_type_argument(ty).map_err(|err| {
+                        err.at_index(IndexKind::StructDefInstantiation, struct_inst_idx.0)
+                    })?;
 
                     let arg_abilities = if struct_handle.type_parameters[ty_idx].is_phantom {
                         struct_handle.type_parameters[ty_idx].constraints
@@ -644,6 +683,9 @@ impl<'a, const N: usize> SignatureChecker<'a, N> {
                             .with_message("reference not allowed".to_string())
                             .at_index(IndexKind::FieldInstantiation, field_inst_idx.0));
                     }
+                    check_not_function_type_argument(ty).map_err(|err| {
+                        err.at_index(IndexKind::FieldInstantiation, field_inst_idx.0)
+                    })?;
 
                     constraints.merge(self.verify_type_in_signature_contextless(
                         ty_args_idx,
@@ -716,6 +758,7 @@ impl<'a, const N: usize> SignatureChecker<'a, N> {
             BTreeMap::<StructDefInstantiationIndex, ()>::new();
         let mut checked_vec_insts = BTreeMap::<SignatureIndex, ()>::new();
         let mut checked_field_insts = BTreeMap::<FieldInstantiationIndex, ()>::new();
+        let mut checked_closure_sigs = BTreeMap::<SignatureIndex, ()>::new();
 
         for (offset, instr) in code.code.iter().enumerate() {
             let map_err = |res: PartialVMResult<()>| {
@@ -724,7 +767,7 @@ impl<'a, const N: usize> SignatureChecker<'a, N> {
                 })
             };
             match instr {
-                CallGeneric(idx) => {
+                CallGeneric(idx) | PackClosureGeneric(idx, _) => {
                     if let btree_map::Entry::Vacant(entry) = checked_func_insts.entry(*idx) {
                         let constraints = self.verify_function_instantiation_contextless(*idx)?;
                         map_err(constraints.check_in_context(&ability_context))?;
@@ -788,6 +831,23 @@ impl<'a, const N: usize> SignatureChecker<'a, N> {
                             )
                             .with_message("reference not allowed".to_string())));
                         }
+                        map_err(check_not_function_type_argument(&ty_args[0]))?;
+                        map_err(self.verify_signature_in_context(&ability_context, *idx))?;
+
+                        entry.insert(());
+                    }
+                },
+                CallClosure(idx) => {
+                    if let btree_map::Entry::Vacant(entry) = checked_closure_sigs.entry(*idx) {
+                        let tys = &self.resolver.signature_at(*idx).0;
+                        if tys.len() != 1 || !tys[0].is_function() {
+                            return map_err(Err(PartialVMError::new(
+                                StatusCode::INVALID_SIGNATURE_TOKEN,
+                            )
+                            .with_message(
+                                "expected a single function type for closure calls".to_string(),
+                            )));
+                        }
                         map_err(self.verify_signature_in_context(&ability_context, *idx))?;
 
                         entry.insert(());
@@ -796,14 +856,66 @@ impl<'a, const N: usize> SignatureChecker<'a, N> {
 
                 // List out the other options explicitly so there's a compile error if a new
                 // bytecode gets added.
-                Pop | Ret | Branch(_) | BrTrue(_) | BrFalse(_) | LdU8(_) | LdU16(_) | LdU32(_)
-                | LdU64(_) | LdU128(_) | LdU256(_) | LdConst(_) | CastU8 | CastU16 | CastU32
-                | CastU64 | CastU128 | CastU256 | LdTrue | LdFalse | Call(_) | Pack(_)
-                | Unpack(_) | ReadRef | WriteRef | FreezeRef | Add | Sub | Mul | Mod | Div
-                | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le
-                | Ge | CopyLoc(_) | MoveLoc(_) | StLoc(_) | MutBorrowLoc(_) | ImmBorrowLoc(_)
-                | MutBorrowField(_) | ImmBorrowField(_) | MutBorrowGlobal(_)
-                | ImmBorrowGlobal(_) | Exists(_) | MoveTo(_) | MoveFrom(_) | Abort | Nop => (),
+                Pop
+                | Ret
+                | Branch(_)
+                | BrTrue(_)
+                | BrFalse(_)
+                | LdU8(_)
+                | LdU16(_)
+                | LdU32(_)
+                | LdU64(_)
+                | LdU128(_)
+                | LdU256(_)
+                | LdConst(_)
+                | CastU8
+                | CastU16
+                | CastU32
+                | CastU64
+                | CastU128
+                | CastU256
+                | LdTrue
+                | LdFalse
+                | Call(_)
+                | Pack(_)
+                | Unpack(_)
+                | ReadRef
+                | WriteRef
+                | FreezeRef
+                | Add
+                | Sub
+                | Mul
+                | Mod
+                | Div
+                | BitOr
+                | BitAnd
+                | Xor
+                | Shl
+                | Shr
+                | Or
+                | And
+                | Not
+                | Eq
+                | Neq
+                | Lt
+                | Gt
+                | Le
+                | Ge
+                | CopyLoc(_)
+                | MoveLoc(_)
+                | StLoc(_)
+                | MutBorrowLoc(_)
+                | ImmBorrowLoc(_)
+                | MutBorrowField(_)
+                | ImmBorrowField(_)
+                | MutBorrowGlobal(_)
+                | ImmBorrowGlobal(_)
+                | Exists(_)
+                | MoveTo(_)
+                | MoveFrom(_)
+                | Abort
+                | Nop
+                | PackClosure(_, _) => (),
             }
         }
 
@@ -14,7 +14,10 @@ use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     control_flow_graph::{BlockId, ControlFlowGraph},
     errors::{PartialVMError, PartialVMResult},
-    file_format::{Bytecode, CodeUnit, FunctionDefinitionIndex, Signature, StructFieldInformation},
+    file_format::{
+        Bytecode, CodeUnit, FunctionDefinitionIndex, Signature, SignatureToken,
+        StructFieldInformation,
+    },
 };
 use move_core_types::vm_status::StatusCode;
 
@@ -222,6 +225,25 @@ impl<'a> StackUsageVerifier<'a> {
                 (arg_count, return_count)
             },
 
+            // PackClosure pops the captured arguments and pushes the closure
+            Bytecode::PackClosure(_, mask) | Bytecode::PackClosureGeneric(_, mask) => {
+                (mask.captured_count() as u64, 1)
+            },
+
+            // CallClosure pops the closure and its `arg_count` arguments, and performs
+            // `return_count` pushes
+            Bytecode::CallClosure(idx) => match self.resolver.signature_at(*idx).0.as_slice() {
+                [SignatureToken::Function(args, results, _)] => {
+                    (args.len() as u64 + 1, results.len() as u64)
+                },
+                _ => {
+                    return Err(
+                        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                            .with_message("expected a function type for closure calls".to_string()),
+                    )
+                },
+            },
+
             // Pack performs `num_fields` pops and one push
             Bytecode::Pack(idx) => {
                 let struct_definition = self.resolver.struct_def_at(*idx)?;
@@ -115,7 +115,9 @@ impl<'a> StructDefGraphBuilder<'a> {
             | T::U256
             | T::Address
             | T::Signer
-            | T::TypeParameter(_) => (),
+            | T::TypeParameter(_)
+            // Function values do not contain values of their argument or result types
+            | T::Function(_, _, _) => (),
             T::Reference(_) | T::MutableReference(_) => {
                 return Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
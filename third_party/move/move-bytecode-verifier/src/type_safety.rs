@@ -11,9 +11,10 @@ use move_binary_format::{
     control_flow_graph::ControlFlowGraph,
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        AbilitySet, Bytecode, CodeOffset, FieldHandleIndex, FunctionDefinitionIndex,
-        FunctionHandle, LocalIndex, Signature, SignatureToken, SignatureToken as ST,
-        StructDefinition, StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
+        Ability, AbilitySet, Bytecode, ClosureMask, CodeOffset, FieldHandleIndex,
+        FunctionDefinitionIndex, FunctionHandle, FunctionHandleIndex, LocalIndex, Signature,
+        SignatureToken, SignatureToken as ST, StructDefinition, StructDefinitionIndex,
+        StructFieldInformation, StructHandleIndex, Visibility,
     },
     safe_unwrap,
 };
@@ -253,6 +254,99 @@ fn call(
     Ok(())
 }
 
+fn pack_closure(
+    verifier: &mut TypeSafetyChecker,
+    meter: &mut impl Meter,
+    offset: CodeOffset,
+    function_handle_idx: FunctionHandleIndex,
+    type_actuals: &Signature,
+    mask: ClosureMask,
+) -> PartialVMResult<()> {
+    let function_handle = verifier.resolver.function_handle_at(function_handle_idx);
+    let parameters = verifier.resolver.signature_at(function_handle.parameters);
+    let (captured, remaining) = mask.split(&parameters.0);
+
+    // Captured values are stored in the closure, so they can neither be references nor
+    // lose abilities the closure claims to have.
+    let mut abilities = AbilitySet::FUNCTIONS;
+    for parameter in captured.into_iter().rev() {
+        let arg = safe_unwrap!(verifier.stack.pop());
+        if arg.is_reference() || arg != instantiate(parameter, type_actuals) {
+            return Err(verifier.error(StatusCode::CALL_TYPE_MISMATCH_ERROR, offset));
+        }
+        abilities = abilities.intersect(verifier.abilities(&arg)?);
+    }
+    // Storing a closure only records the name of the function, not its type arguments.
+    if !type_actuals.is_empty() {
+        abilities = abilities.remove(Ability::Store);
+    }
+    // Only the signatures of public functions are preserved by module upgrades, so closures over
+    // other functions must not outlive the transaction.
+    if !is_public_function(verifier.resolver, function_handle_idx) {
+        abilities = abilities.remove(Ability::Store);
+    }
+
+    let args = remaining
+        .into_iter()
+        .map(|ty| instantiate(ty, type_actuals))
+        .collect();
+    let results = verifier
+        .resolver
+        .signature_at(function_handle.return_)
+        .0
+        .iter()
+        .map(|ty| instantiate(ty, type_actuals))
+        .collect();
+    verifier.push(meter, ST::Function(args, results, abilities))
+}
+
+/// Returns whether the function is public. Imported functions a closure can be packed over are
+/// always public, as checked when linking.
+fn is_public_function(
+    resolver: &BinaryIndexedView,
+    function_handle_idx: FunctionHandleIndex,
+) -> bool {
+    let function_handle = resolver.function_handle_at(function_handle_idx);
+    if resolver.self_handle_idx() != Some(function_handle.module) {
+        return true;
+    }
+    resolver
+        .function_defs()
+        .into_iter()
+        .flatten()
+        .any(|def| def.function == function_handle_idx && def.visibility == Visibility::Public)
+}
+
+fn call_closure(
+    verifier: &mut TypeSafetyChecker,
+    meter: &mut impl Meter,
+    offset: CodeOffset,
+    expected_ty: &SignatureToken,
+) -> PartialVMResult<()> {
+    let (args, results) = match (safe_unwrap!(verifier.stack.pop()), expected_ty) {
+        (
+            ST::Function(args, results, abilities),
+            ST::Function(expected_args, expected_results, expected_abilities),
+        ) if &args == expected_args
+            && &results == expected_results
+            && expected_abilities.is_subset(abilities) =>
+        {
+            (args, results)
+        },
+        _ => return Err(verifier.error(StatusCode::CALL_TYPE_MISMATCH_ERROR, offset)),
+    };
+    for parameter in args.iter().rev() {
+        let arg = safe_unwrap!(verifier.stack.pop());
+        if &arg != parameter {
+            return Err(verifier.error(StatusCode::CALL_TYPE_MISMATCH_ERROR, offset));
+        }
+    }
+    for return_type in results {
+        verifier.push(meter, return_type)?
+    }
+    Ok(())
+}
+
 fn type_fields_signature(
     verifier: &mut TypeSafetyChecker,
     _meter: &mut impl Meter, // TODO: metering
@@ -585,6 +679,22 @@ fn verify_instr(
             call(verifier, meter, offset, func_handle, type_args)?
         },
 
+        Bytecode::PackClosure(idx, mask) => {
+            pack_closure(verifier, meter, offset, *idx, &Signature(vec![]), *mask)?
+        },
+
+        Bytecode::PackClosureGeneric(idx, mask) => {
+            let func_inst = verifier.resolver.function_instantiation_at(*idx);
+            let type_args = &verifier.resolver.signature_at(func_inst.type_parameters);
+            verifier.charge_tys(meter, &type_args.0)?;
+            pack_closure(verifier, meter, offset, func_inst.handle, type_args, *mask)?
+        },
+
+        Bytecode::CallClosure(idx) => {
+            let expected_ty = safe_unwrap!(verifier.resolver.signature_at(*idx).0.first());
+            call_closure(verifier, meter, offset, expected_ty)?
+        },
+
         Bytecode::Pack(idx) => {
             let struct_definition = verifier.resolver.struct_def_at(*idx)?;
             pack(
@@ -959,6 +1069,11 @@ fn instantiate(token: &SignatureToken, subst: &Signature) -> SignatureToken {
             debug_assert!((*idx as usize) < subst.len());
             subst.0[*idx as usize].clone()
         },
+        Function(args, results, abilities) => Function(
+            args.iter().map(|ty| instantiate(ty, subst)).collect(),
+            results.iter().map(|ty| instantiate(ty, subst)).collect(),
+            *abilities,
+        ),
     }
 }
 
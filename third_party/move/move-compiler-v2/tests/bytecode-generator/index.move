@@ -0,0 +1,41 @@
+module 0x42::index {
+    use std::vector;
+
+    struct S has drop {
+        v: vector<u64>
+    }
+
+    fun index(v: &vector<u64>, i: u64): u64 {
+        v[i]
+    }
+
+    fun index_explicit(v: &vector<u64>, i: u64): u64 {
+        *vector::borrow(v, i)
+    }
+
+    fun index_local(): u64 {
+        let v = vector[1, 2, 3];
+        v[1]
+    }
+
+    fun index_local_explicit(): u64 {
+        let v = vector[1, 2, 3];
+        *vector::borrow(&v, 1)
+    }
+
+    fun borrow_mut_index(s: &mut S) {
+        *&mut s.v[0] = 1
+    }
+
+    fun borrow_mut_index_explicit(s: &mut S) {
+        *vector::borrow_mut(&mut s.v, 0) = 1
+    }
+
+    fun index_nested(v: &vector<vector<u64>>): u64 {
+        v[0][1]
+    }
+
+    fun index_nested_explicit(v: &vector<vector<u64>>): u64 {
+        *vector::borrow(vector::borrow(v, 0), 1)
+    }
+}
@@ -0,0 +1,77 @@
+module 0x42::m {
+    struct S has drop { x: u64 }
+
+    struct G<T> has drop { x: T }
+
+    struct Outer has drop { s: S }
+
+    public fun plus(self: &S, y: u64): u64 {
+        self.x + y
+    }
+
+    public fun set(self: &mut S, x: u64) {
+        self.x = x
+    }
+
+    public fun into_x(self: S): u64 {
+        let S { x } = self;
+        x
+    }
+
+    public fun get<T: copy>(self: &G<T>): T {
+        self.x
+    }
+
+    public fun with<T, U: drop>(self: G<T>, _u: U): G<T> {
+        self
+    }
+
+    fun receiver_value(s: S): u64 {
+        s.plus(1)
+    }
+
+    fun receiver_ref(s: &S): u64 {
+        s.plus(1)
+    }
+
+    fun receiver_mut_ref(s: &mut S) {
+        s.set(2)
+    }
+
+    fun receiver_mut_value(s: S): S {
+        s.set(2);
+        s
+    }
+
+    fun receiver_mut_ref_to_ref(s: &mut S): u64 {
+        s.plus(3)
+    }
+
+    fun receiver_by_value(s: S): u64 {
+        s.into_x()
+    }
+
+    fun receiver_field(o: &Outer): u64 {
+        o.s.plus(1)
+    }
+
+    fun receiver_generic(g: G<u64>): u64 {
+        g.get()
+    }
+
+    fun receiver_type_args(g: G<u64>): G<u64> {
+        g.with<u64, bool>(true)
+    }
+
+    fun receiver_chain(g: G<u64>): u64 {
+        g.with(false).get()
+    }
+}
+
+module 0x42::n {
+    use 0x42::m::S;
+
+    fun receiver_other_module(s: &S): u64 {
+        s.plus(1)
+    }
+}
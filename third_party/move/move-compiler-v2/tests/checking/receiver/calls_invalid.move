@@ -0,0 +1,39 @@
+module 0x42::m {
+    struct S has drop { x: u64 }
+
+    public fun plus(self: &S, y: u64): u64 {
+        self.x + y
+    }
+
+    public fun set(self: &mut S, x: u64) {
+        self.x = x
+    }
+
+    public fun not_receiver(s: &S): u64 {
+        s.x
+    }
+
+    fun missing_function(s: &S): u64 {
+        s.missing()
+    }
+
+    fun missing_self_parameter(s: &S): u64 {
+        s.not_receiver()
+    }
+
+    fun primitive_receiver(x: u64): u64 {
+        x.plus(1)
+    }
+
+    fun wrong_argument(s: &S): u64 {
+        s.plus(true)
+    }
+
+    fun immutable_receiver(s: &S) {
+        s.set(1)
+    }
+
+    fun wrong_type_args(s: &S): u64 {
+        s.plus<u64>(1)
+    }
+}
@@ -0,0 +1,22 @@
+module 0x42::m {
+    struct S has drop { f: u64 }
+
+    struct G<T> has drop { x: T }
+
+    public fun get<T: copy>(self: &G<T>): T {
+        self.x
+    }
+
+    // A '<' after a field is a comparison, not the start of type arguments
+    fun field_less(s: S, x: u64): bool {
+        s.f<x
+    }
+
+    fun field_less_greater(s: S, t: S, x: u64): bool {
+        s.f<x && t.f>x
+    }
+
+    fun receiver_type_args(g: G<u64>): u64 {
+        g.get<u64>()
+    }
+}
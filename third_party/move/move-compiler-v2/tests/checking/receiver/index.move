@@ -0,0 +1,46 @@
+module 0x42::m {
+    struct S has drop { v: vector<u64> }
+
+    fun index_value(v: vector<u64>): u64 {
+        v[0]
+    }
+
+    fun index_ref(v: &vector<u64>, i: u64): u64 {
+        v[i]
+    }
+
+    fun index_mut_ref(v: &mut vector<u64>): u64 {
+        v[1]
+    }
+
+    fun borrow_index(v: &vector<u64>): &u64 {
+        &v[1]
+    }
+
+    fun borrow_mut_index(v: &mut vector<u64>) {
+        *&mut v[0] = 1
+    }
+
+    fun borrow_mut_index_local(): vector<u64> {
+        let v = vector[1, 2];
+        let r = &mut v[1];
+        *r = 3;
+        v
+    }
+
+    fun index_field(s: &S): u64 {
+        s.v[0]
+    }
+
+    fun borrow_mut_index_field(s: &mut S): &mut u64 {
+        &mut s.v[0]
+    }
+
+    fun index_nested(v: &vector<vector<u64>>): u64 {
+        v[0][1]
+    }
+
+    fun borrow_mut_index_nested(v: &mut vector<vector<u64>>): &mut u64 {
+        &mut v[0][1]
+    }
+}
@@ -0,0 +1,17 @@
+module 0x42::m {
+    fun index_non_vector(x: u64): u64 {
+        x[0]
+    }
+
+    fun index_non_integer(v: vector<u64>): u64 {
+        v[true]
+    }
+
+    fun borrow_mut_index_immutable(v: &vector<u64>): &mut u64 {
+        &mut v[0]
+    }
+
+    fun index_wrong_result(v: vector<u64>): bool {
+        v[0]
+    }
+}
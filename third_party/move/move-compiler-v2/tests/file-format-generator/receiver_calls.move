@@ -0,0 +1,23 @@
+module 0x42::receiver_calls {
+    struct S has drop {
+        x: u64
+    }
+
+    fun plus(self: &S, y: u64): u64 {
+        self.x + y
+    }
+
+    fun set(self: &mut S, x: u64) {
+        self.x = x
+    }
+
+    fun receiver_calls(s: S): u64 {
+        s.set(1);
+        s.plus(2)
+    }
+
+    fun receiver_calls_explicit(s: S): u64 {
+        set(&mut s, 1);
+        plus(&s, 2)
+    }
+}
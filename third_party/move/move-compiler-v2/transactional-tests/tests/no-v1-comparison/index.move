@@ -0,0 +1,58 @@
+//# publish
+module 0x42::m {
+    use std::vector;
+
+    struct S has drop {
+        v: vector<u64>
+    }
+
+    public fun sum(v: &vector<u64>): u64 {
+        let i = 0;
+        let sum = 0;
+        while (i < vector::length(v)) {
+            sum = sum + v[i];
+            i = i + 1;
+        };
+        sum
+    }
+
+    public fun test_index(): u64 {
+        let v = vector[1, 2, 3];
+        *&mut v[1] = 5;
+        sum(&v)
+    }
+
+    fun bump(s: &mut S) {
+        let r = &mut s.v[0];
+        *r = *r + 10
+    }
+
+    fun total(s: &S): u64 {
+        s.v[0] + s.v[1]
+    }
+
+    public fun test_field(): u64 {
+        let s = S { v: vector[1, 2] };
+        bump(&mut s);
+        total(&s)
+    }
+
+    public fun test_nested(): u64 {
+        let v = vector[vector[1, 2], vector[3, 4]];
+        *&mut v[1][0] = 7;
+        v[1][0] + v[0][1]
+    }
+
+    public fun test_out_of_bounds(): u64 {
+        let v = vector[1];
+        v[1]
+    }
+}
+
+//# run 0x42::m::test_index
+
+//# run 0x42::m::test_field
+
+//# run 0x42::m::test_nested
+
+//# run 0x42::m::test_out_of_bounds
@@ -0,0 +1,60 @@
+//# publish
+module 0x42::m {
+    struct S has drop {
+        x: u64
+    }
+
+    struct Box<T> has drop {
+        value: T
+    }
+
+    public fun new(x: u64): S {
+        S { x }
+    }
+
+    public fun plus(self: &S, y: u64): u64 {
+        self.x + y
+    }
+
+    public fun set(self: &mut S, x: u64) {
+        self.x = x
+    }
+
+    public fun into_x(self: S): u64 {
+        let S { x } = self;
+        x
+    }
+
+    public fun get<T: copy>(self: &Box<T>): T {
+        self.value
+    }
+
+    public fun test(): u64 {
+        let s = new(1);
+        let y = s.plus(2);
+        s.set(y);
+        s.into_x()
+    }
+
+    public fun test_generic(): u64 {
+        let b = Box { value: 5 };
+        new(4).plus(b.get())
+    }
+}
+
+//# publish
+module 0x42::n {
+    use 0x42::m;
+
+    public fun test(): u64 {
+        let s = m::new(10);
+        s.set(20);
+        s.plus(1)
+    }
+}
+
+//# run 0x42::m::test
+
+//# run 0x42::m::test_generic
+
+//# run 0x42::n::test
@@ -114,6 +114,8 @@ codes!(
         SpecContextRestricted:
             { msg: "syntax item restricted to spec contexts", severity: BlockingError },
         InvalidSpecBlockMember: { msg: "invalid spec block member", severity: NonblockingError },
+        UnsupportedLanguageItem:
+            { msg: "unsupported language construct", severity: BlockingError },
     ],
     // errors for any rules around declaration items
     Declarations: [
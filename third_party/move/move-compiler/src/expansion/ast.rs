@@ -453,7 +453,8 @@ pub enum Exp_ {
 
     Borrow(bool, Box<Exp>),
     ExpDotted(Box<ExpDotted>),
-    Index(Box<Exp>, Box<Exp>), // spec only (no mutation needed right now)
+    MethodCall(Box<Exp>, Name, Option<Vec<Type>>, Spanned<Vec<Exp>>), // v2 only
+    Index(Box<Exp>, Box<Exp>),                                        // spec and v2 only
 
     Cast(Box<Exp>, Type),
     Annotate(Box<Exp>, Type),
@@ -1631,6 +1632,18 @@ impl AstDebug for Exp_ {
                 ty.ast_debug(w);
                 w.write(")");
             },
+            E::MethodCall(e, n, tys_opt, sp!(_, rhs)) => {
+                e.ast_debug(w);
+                w.write(&format!(".{}", n));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("(");
+                w.comma(rhs, |w, e| e.ast_debug(w));
+                w.write(")");
+            },
             E::Index(oper, index) => {
                 oper.ast_debug(w);
                 w.write("[");
@@ -7,6 +7,7 @@ use crate::{
     expansion::ast::{self as E, Address, ModuleIdent},
     shared::{unique_map::UniqueMap, *},
 };
+use move_core_types::account_address::AccountAddress;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use petgraph::{algo::toposort as petgraph_toposort, graphmap::DiGraphMap};
@@ -98,6 +99,8 @@ struct Context<'a> {
     addresses_by_node: BTreeMap<NodeIdent, BTreeSet<Address>>,
     // The module or script we are currently exploring
     current_node: Option<NodeIdent>,
+    // Whether we are currently exploring a spec block
+    in_spec: bool,
 }
 
 impl<'a> Context<'a> {
@@ -108,6 +111,7 @@ impl<'a> Context<'a> {
             neighbors_by_node: BTreeMap::new(),
             addresses_by_node: BTreeMap::new(),
             current_node: None,
+            in_spec: false,
         }
     }
 
@@ -170,6 +174,18 @@ impl<'a> Context<'a> {
         self.add_neighbor(mident, DepType::Friend, loc);
     }
 
+    // Index notation outside of specs is resolved to functions of `std::vector` (compiler v2)
+    fn add_vector_usage(&mut self, loc: Loc) {
+        let vector = self.modules.key_cloned_iter().find(|(sp!(_, m), _)| {
+            m.module.value().as_str() == "vector"
+                && matches!(&m.address, Address::Numerical(_, sp!(_, a))
+                    if a.into_inner() == AccountAddress::ONE)
+        });
+        if let Some((mident, _)) = vector {
+            self.add_usage(mident, loc)
+        }
+    }
+
     fn add_address_usage(&mut self, address: Address) {
         self.addresses_by_node
             .entry(self.current_node.clone().unwrap())
@@ -424,7 +440,7 @@ fn lvalue(context: &mut Context, sp!(_loc, a_): &E::LValue) {
     }
 }
 
-fn exp(context: &mut Context, sp!(_loc, e_): &E::Exp) {
+fn exp(context: &mut Context, sp!(loc, e_): &E::Exp) {
     use crate::expansion::ast::{Exp_ as E, Value_ as V};
     match e_ {
         E::Value(sp!(_, V::Address(a))) => context.add_address_usage(*a),
@@ -456,6 +472,11 @@ fn exp(context: &mut Context, sp!(_loc, e_): &E::Exp) {
             types_opt(context, tys_opt);
             args_.iter().for_each(|e| exp(context, e))
         },
+        E::MethodCall(e, _, tys_opt, sp!(_, args_)) => {
+            exp(context, e);
+            types_opt(context, tys_opt);
+            args_.iter().for_each(|e| exp(context, e))
+        },
 
         E::IfElse(ec, et, ef) => {
             exp(context, ec);
@@ -463,7 +484,14 @@ fn exp(context: &mut Context, sp!(_loc, e_): &E::Exp) {
             exp(context, ef)
         },
 
-        E::BinopExp(e1, _, e2) | E::Mutate(e1, e2) | E::While(e1, e2) | E::Index(e1, e2) => {
+        E::BinopExp(e1, _, e2) | E::Mutate(e1, e2) | E::While(e1, e2) => {
+            exp(context, e1);
+            exp(context, e2)
+        },
+        E::Index(e1, e2) => {
+            if !context.in_spec {
+                context.add_vector_usage(*loc)
+            }
             exp(context, e1);
             exp(context, e2)
         },
@@ -521,9 +549,11 @@ fn exp_dotted(context: &mut Context, sp!(_, ed_): &E::ExpDotted) {
 //**************************************************************************************************
 
 fn spec_block(context: &mut Context, sp!(_, sb_): &E::SpecBlock) {
+    context.in_spec = true;
     sb_.members
         .iter()
-        .for_each(|sbm| spec_block_member(context, sbm))
+        .for_each(|sbm| spec_block_member(context, sbm));
+    context.in_spec = false;
 }
 
 fn spec_block_member(context: &mut Context, sp!(_, sbm_): &E::SpecBlockMember) {
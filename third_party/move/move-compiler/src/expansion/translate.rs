@@ -2029,9 +2029,22 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
                 EE::UnresolvedError
             },
         },
+        PE::DotCall(pe, n, ptys_opt, sp!(rloc, prs)) => {
+            if context.env.flags().lang_v2() {
+                let tys_opt = optional_types(context, ptys_opt);
+                let ers = sp(rloc, exps(context, prs));
+                EE::MethodCall(exp(context, *pe), n, tys_opt, ers)
+            } else {
+                let msg = "receiver-style function calls are only supported by compiler v2";
+                context
+                    .env
+                    .add_diag(diag!(Syntax::UnsupportedLanguageItem, (loc, msg)));
+                EE::UnresolvedError
+            }
+        },
         PE::Cast(e, ty) => EE::Cast(exp(context, *e), type_(context, ty)),
         PE::Index(e, i) => {
-            if context.in_spec_context {
+            if context.in_spec_context || context.env.flags().lang_v2() {
                 EE::Index(exp(context, *e), exp(context, *i))
             } else {
                 let msg = "`_[_]` index operator only allowed in specifications";
@@ -2496,6 +2509,10 @@ fn unbound_names_exp(unbound: &mut UnboundNames, sp!(_, e_): &E::Exp) {
             unbound_names_exp(unbound, ei);
             unbound_names_exp(unbound, el)
         },
+        EE::MethodCall(el, _, _, sp!(_, es_)) => {
+            unbound_names_exps(unbound, es_);
+            unbound_names_exp(unbound, el)
+        },
 
         EE::Spec(_, unbound_vars, unbound_func_ptrs) => {
             unbound.vars.extend(unbound_vars);
@@ -368,6 +368,31 @@ fn write_signature_token(ctx: &mut Context, t: &SignatureToken) -> String {
         SignatureToken::MutableReference(inner) => {
             format!("&mut {}", write_signature_token(ctx, inner))
         },
+        SignatureToken::Function(args, results, abilities) => {
+            let args = args
+                .iter()
+                .map(|ty| write_signature_token(ctx, ty))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let results = match results.len() {
+                0 => "".to_string(),
+                1 => format!(" {}", write_signature_token(ctx, &results[0])),
+                _ => format!(
+                    " ({})",
+                    results
+                        .iter()
+                        .map(|ty| write_signature_token(ctx, ty))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            format!(
+                "|{}|{}{}",
+                args,
+                results,
+                write_ability_modifiers(*abilities)
+            )
+        },
         SignatureToken::TypeParameter(idx) => write_type_parameter(*idx),
     }
 }
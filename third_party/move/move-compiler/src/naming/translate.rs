@@ -1077,6 +1077,8 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
         EE::Index(..) | EE::Quant(..) | EE::Name(_, Some(_)) => {
             panic!("ICE unexpected specification construct")
         },
+        // Only allowed with compiler v2, which does not run naming
+        EE::MethodCall(..) => panic!("ICE unexpected compiler v2 construct"),
     };
     sp(eloc, ne_)
 }
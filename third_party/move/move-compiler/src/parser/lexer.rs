@@ -343,6 +343,46 @@ impl<'input> Lexer<'input> {
         Ok((first, second))
     }
 
+    // Starting at the current '<' token, look ahead for the matching '>' and return whether it
+    // is directly followed by '(', without advancing the state of the lexer. Stops at the first
+    // token which cannot be part of a list of type arguments.
+    pub fn lookahead_type_args_followed_by_call(&mut self) -> Result<bool, Box<Diagnostic>> {
+        let mut depth = 1;
+        let mut offset = self.cur_end;
+        loop {
+            let text = self.trim_whitespace_and_comments(offset)?;
+            let start = self.text.len() - text.len();
+            let (tok, len) = find_token(self.file_hash, text, start)?;
+            offset = start + len;
+            match tok {
+                Tok::Less => depth += 1,
+                Tok::Greater | Tok::GreaterGreater => {
+                    let closed = if tok == Tok::Greater { 1 } else { 2 };
+                    if closed > depth {
+                        return Ok(false);
+                    }
+                    depth -= closed;
+                    if depth == 0 {
+                        let text = self.trim_whitespace_and_comments(offset)?;
+                        let start = self.text.len() - text.len();
+                        let (next, _) = find_token(self.file_hash, text, start)?;
+                        return Ok(next == Tok::LParen);
+                    }
+                },
+                Tok::Identifier
+                | Tok::ColonColon
+                | Tok::Comma
+                | Tok::Amp
+                | Tok::AmpMut
+                | Tok::NumValue
+                | Tok::Pipe
+                | Tok::LParen
+                | Tok::RParen => (),
+                _ => return Ok(false),
+            }
+        }
+    }
+
     // Matches the doc comments after the last token (or the beginning of the file) to the position
     // of the current token. This moves the comments out of `doc_comments` and
     // into `matched_doc_comments`. At the end of parsing, if `doc_comments` is not empty, errors
@@ -1462,7 +1462,8 @@ fn parse_unary_exp(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
 // Parse an expression term optionally followed by a chain of dot or index accesses:
 //      DotOrIndexChain =
 //          <DotOrIndexChain> "." <Identifier>
-//          | <DotOrIndexChain> "[" <Exp> "]"                      spec only
+//          | <DotOrIndexChain> "." <Identifier> <OptionalTypeArgs> <CallArgs>    v2 only
+//          | <DotOrIndexChain> "[" <Exp> "]"                      spec and v2 only
 //          | <Term>
 fn parse_dot_or_index_chain(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
     let start_loc = context.tokens.start_loc();
@@ -1472,7 +1473,26 @@ fn parse_dot_or_index_chain(context: &mut Context) -> Result<Exp, Box<Diagnostic
             Tok::Period => {
                 context.tokens.advance()?;
                 let n = parse_identifier(context)?;
-                Exp_::Dot(Box::new(lhs), n)
+                // In v2, a '<' directly following the identifier starts a list of type
+                // arguments if the matching '>' is followed by call arguments. Otherwise it is
+                // a comparison, as in `s.f<x`.
+                let mut tys = None;
+                let type_args_loc = context.tokens.start_loc();
+                if context.env.flags().lang_v2()
+                    && context.tokens.peek() == Tok::Less
+                    && n.loc.end() as usize == type_args_loc
+                    && context.tokens.lookahead_type_args_followed_by_call()?
+                {
+                    let loc = make_loc(context.tokens.file_hash(), type_args_loc, type_args_loc);
+                    tys = parse_optional_type_args(context)
+                        .map_err(|diag| add_type_args_ambiguity_label(loc, diag))?;
+                }
+                if tys.is_some() || context.tokens.peek() == Tok::LParen {
+                    let args = parse_call_args(context)?;
+                    Exp_::DotCall(Box::new(lhs), n, tys, args)
+                } else {
+                    Exp_::Dot(Box::new(lhs), n)
+                }
             },
             Tok::LBracket => {
                 context.tokens.advance()?;
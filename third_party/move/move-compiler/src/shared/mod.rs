@@ -330,6 +330,11 @@ pub struct Flags {
 	long = cli::SKIP_ATTRIBUTE_CHECKS,
     )]
     pub skip_attribute_checks: bool,
+
+    /// Internal flag used by compiler v2 to enable language features which are only
+    /// supported by the v2 backend, such as index notation and receiver-style calls.
+    #[clap(skip)]
+    lang_v2: bool,
 }
 
 impl Flags {
@@ -342,6 +347,7 @@ impl Flags {
             bytecode_version: None,
             keep_testing_functions: false,
             skip_attribute_checks: false,
+            lang_v2: false,
         }
     }
 
@@ -354,6 +360,7 @@ impl Flags {
             bytecode_version: None,
             keep_testing_functions: false,
             skip_attribute_checks: false,
+            lang_v2: false,
         }
     }
 
@@ -366,6 +373,7 @@ impl Flags {
             bytecode_version: None,
             keep_testing_functions: false,
             skip_attribute_checks: false,
+            lang_v2: false,
         }
     }
 
@@ -378,6 +386,7 @@ impl Flags {
             bytecode_version: None,
             keep_testing_functions: true,
             skip_attribute_checks: false,
+            lang_v2: false,
         }
     }
 
@@ -440,6 +449,17 @@ impl Flags {
             ..self
         }
     }
+
+    pub fn lang_v2(&self) -> bool {
+        self.lang_v2
+    }
+
+    pub fn set_lang_v2(self, value: bool) -> Self {
+        Self {
+            lang_v2: value,
+            ..self
+        }
+    }
 }
 
 //**************************************************************************************************
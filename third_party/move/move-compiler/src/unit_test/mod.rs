@@ -8,8 +8,11 @@ use crate::{
     shared::NumericalAddress,
 };
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
-    value::MoveValue, vm_status::StatusCode,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use std::{collections::BTreeMap, fmt};
 
@@ -34,10 +37,22 @@ pub struct ModuleTestPlan {
 #[derive(Debug, Clone)]
 pub struct TestCase {
     pub test_name: TestName,
+    /// Values of the parameters assigned in the test attribute, in declaration order
     pub arguments: Vec<MoveValue>,
+    /// Parameters not assigned in the test attribute; new values are generated for them each
+    /// time the test is run
+    pub fuzz_parameters: Vec<FuzzParameter>,
     pub expected_failure: Option<ExpectedFailure>,
 }
 
+#[derive(Debug, Clone)]
+pub struct FuzzParameter {
+    pub name: String,
+    /// Position of the parameter in the signature of the test function
+    pub index: usize,
+    pub layout: MoveTypeLayout,
+}
+
 #[derive(Debug, Clone)]
 pub enum ExpectedFailure {
     // expected failure, but codes are not checked
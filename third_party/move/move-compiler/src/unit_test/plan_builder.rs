@@ -8,17 +8,22 @@ use crate::{
     expansion::ast::{
         self as E, Address, Attribute, AttributeValue, ModuleAccess_, ModuleIdent, ModuleIdent_,
     },
+    hlir::ast as H,
+    naming::ast::BuiltinTypeName_,
     parser::ast::ConstantName,
     shared::{
         known_attributes::{AttributeKind, KnownAttribute, TestingAttribute},
         unique_map::UniqueMap,
         CompilationEnv, Identifier, NumericalAddress,
     },
-    unit_test::{ExpectedFailure, ExpectedMoveError, ModuleTestPlan, TestCase},
+    unit_test::{ExpectedFailure, ExpectedMoveError, FuzzParameter, ModuleTestPlan, TestCase},
 };
 use move_core_types::{
-    account_address::AccountAddress as MoveAddress, language_storage::ModuleId, u256::U256,
-    value::MoveValue, vm_status::StatusCode,
+    account_address::AccountAddress as MoveAddress,
+    language_storage::ModuleId,
+    u256::U256,
+    value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
@@ -159,18 +164,28 @@ fn build_test_info<'func>(
 
     let test_annotation_params = parse_test_attribute(context, test_attribute, 0);
     let mut arguments = Vec::new();
-    for (var, _) in &function.signature.parameters {
+    let mut fuzz_parameters = Vec::new();
+    for (index, (var, ty)) in function.signature.parameters.iter().enumerate() {
         match test_annotation_params.get(&var.value()) {
             Some(value) => arguments.push(value.clone()),
-            None => {
-                let missing_param_msg = "Missing test parameter assignment in test. Expected a \
-                                         parameter to be assigned in this attribute";
-                context.env.add_diag(diag!(
-                    Attributes::InvalidTest,
-                    (test_attribute.loc, missing_param_msg),
-                    (var.loc(), "Corresponding to this parameter"),
-                    (fn_loc, IN_THIS_TEST_MSG),
-                ))
+            // Values for unassigned parameters of primitive types are generated when the test
+            // is run
+            None => match fuzz_parameter_layout(ty) {
+                Some(layout) => fuzz_parameters.push(FuzzParameter {
+                    name: var.value().to_string(),
+                    index,
+                    layout,
+                }),
+                None => {
+                    let missing_param_msg = "Missing test parameter assignment in test. Expected \
+                                             a parameter to be assigned in this attribute";
+                    context.env.add_diag(diag!(
+                        Attributes::InvalidTest,
+                        (test_attribute.loc, missing_param_msg),
+                        (var.loc(), "Corresponding to this parameter"),
+                        (fn_loc, IN_THIS_TEST_MSG),
+                    ))
+                },
             },
         }
     }
@@ -183,10 +198,45 @@ fn build_test_info<'func>(
     Some(TestCase {
         test_name: fn_name.to_string(),
         arguments,
+        fuzz_parameters,
         expected_failure,
     })
 }
 
+/// Returns the layout of values generated for an unassigned test parameter of type `ty`, or
+/// `None` if values of this type cannot be generated. Only primitive types, addresses, and vectors
+/// of those are supported; signers must always be assigned in the test attribute.
+fn fuzz_parameter_layout(sp!(_, ty): &H::SingleType) -> Option<MoveTypeLayout> {
+    match ty {
+        H::SingleType_::Base(base_ty) => fuzz_base_type_layout(base_ty),
+        H::SingleType_::Ref(_, _) => None,
+    }
+}
+
+fn fuzz_base_type_layout(sp!(_, ty): &H::BaseType) -> Option<MoveTypeLayout> {
+    use BuiltinTypeName_ as B;
+    use MoveTypeLayout as L;
+
+    let (builtin, ty_args) = match ty {
+        H::BaseType_::Apply(_, sp!(_, H::TypeName_::Builtin(sp!(_, builtin))), ty_args) => {
+            (builtin, ty_args)
+        },
+        _ => return None,
+    };
+    Some(match builtin {
+        B::Bool => L::Bool,
+        B::U8 => L::U8,
+        B::U16 => L::U16,
+        B::U32 => L::U32,
+        B::U64 => L::U64,
+        B::U128 => L::U128,
+        B::U256 => L::U256,
+        B::Address => L::Address,
+        B::Vector => L::Vector(Box::new(fuzz_base_type_layout(ty_args.first()?)?)),
+        B::Signer | B::Fun => return None,
+    })
+}
+
 //***************************************************************************
 // Attribute parsers
 //***************************************************************************
@@ -0,0 +1,12 @@
+module 0x42::M {
+    struct S has drop { f: u64 }
+
+    // A '<' after a field is a comparison, not the start of type arguments
+    fun field_less(s: S, x: u64): bool {
+        s.f<x
+    }
+
+    fun field_less_greater(s: S, t: S, x: u64): bool {
+        s.f<x && t.f>x
+    }
+}
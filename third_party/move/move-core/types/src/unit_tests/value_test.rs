@@ -6,8 +6,8 @@ use crate::{
     account_address::AccountAddress,
     ident_str,
     identifier::Identifier,
-    language_storage::{StructTag, TypeTag},
-    value::{MoveStruct, MoveValue},
+    language_storage::{ModuleId, StructTag, TypeTag},
+    value::{MoveClosure, MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
 };
 use serde_json::json;
 
@@ -137,3 +137,29 @@ fn nested_typed_struct_deserialization() {
         })
     );
 }
+
+#[test]
+fn closure_round_trip() {
+    let closure = MoveValue::Closure(Box::new(MoveClosure {
+        module_id: ModuleId::new(AccountAddress::ONE, ident_str!("M").to_owned()),
+        fun_id: ident_str!("f").to_owned(),
+        mask: 0b101,
+        captured: vec![
+            (MoveTypeLayout::U64, MoveValue::U64(7)),
+            (
+                MoveTypeLayout::Struct(MoveStructLayout::new(vec![
+                    MoveTypeLayout::Bool,
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+                ])),
+                MoveValue::Struct(MoveStruct::Runtime(vec![
+                    MoveValue::Bool(true),
+                    MoveValue::vector_u8(vec![1, 2, 3]),
+                ])),
+            ),
+        ],
+    }));
+    let blob = closure.simple_serialize().unwrap();
+    let value = MoveValue::simple_deserialize(&blob, &MoveTypeLayout::Function).unwrap();
+    assert_eq!(value, closure);
+    assert_eq!(value.to_string(), "0000000000000000000000000000000000000000000000000000000000000001::M::f(7u64, struct[true, vector[1u8, 2u8, 3u8]])");
+}
@@ -5,7 +5,7 @@
 use crate::{
     account_address::AccountAddress,
     identifier::Identifier,
-    language_storage::{StructTag, TypeTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     u256,
 };
 use anyhow::{anyhow, bail, Result as AResult};
@@ -57,8 +57,42 @@ pub enum MoveValue {
     U16(u16),
     U32(u32),
     U256(u256::U256),
+    // NOTE: Added in bytecode version v7, do not reorder!
+    Closure(Box<MoveClosure>),
 }
 
+/// A function value: a function together with the arguments captured when it was packed.
+///
+/// Every captured value carries its own layout, so a closure can be deserialized without
+/// knowing the function it refers to.
+#[derive(Debug, Clone)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(arbitrary::Arbitrary))]
+pub struct MoveClosure {
+    pub module_id: ModuleId,
+    pub fun_id: Identifier,
+    /// Bitmask of the parameters of the function which are captured, see
+    /// `move_binary_format::file_format::ClosureMask`.
+    pub mask: u64,
+    pub captured: Vec<(MoveTypeLayout, MoveValue)>,
+}
+
+impl PartialEq for MoveClosure {
+    fn eq(&self, other: &Self) -> bool {
+        // Layouts are determined by the captured values, so they need not be compared.
+        self.module_id == other.module_id
+            && self.fun_id == other.fun_id
+            && self.mask == other.mask
+            && self.captured.len() == other.captured.len()
+            && self
+                .captured
+                .iter()
+                .zip(&other.captured)
+                .all(|((_, v1), (_, v2))| v1 == v2)
+    }
+}
+
+impl Eq for MoveClosure {}
+
 /// A layout associated with a named field
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(arbitrary::Arbitrary))]
@@ -114,6 +148,10 @@ pub enum MoveTypeLayout {
     U32,
     #[serde(rename(serialize = "u256", deserialize = "u256"))]
     U256,
+
+    // NOTE: Added in bytecode version v7, do not reorder!
+    #[serde(rename(serialize = "function", deserialize = "function"))]
+    Function,
 }
 
 impl MoveValue {
@@ -328,7 +366,110 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
             MoveTypeLayout::Vector(layout) => Ok(MoveValue::Vector(
                 deserializer.deserialize_seq(VectorElementVisitor(layout))?,
             )),
+            MoveTypeLayout::Function => Ok(MoveValue::Closure(Box::new(
+                deserializer.deserialize_tuple(4, ClosureVisitor)?,
+            ))),
+        }
+    }
+}
+
+struct ClosureVisitor;
+
+impl<'d> serde::de::Visitor<'d> for ClosureVisitor {
+    type Value = MoveClosure;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Closure")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'d>,
+    {
+        let module_id = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let fun_id = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        let mask = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+        let captured = seq
+            .next_element_seed(CapturedValuesSeed)?
+            .ok_or_else(|| A::Error::invalid_length(3, &self))?;
+        Ok(MoveClosure {
+            module_id,
+            fun_id,
+            mask,
+            captured,
+        })
+    }
+}
+
+struct CapturedValuesSeed;
+
+impl<'d> serde::de::DeserializeSeed<'d> for CapturedValuesSeed {
+    type Value = Vec<(MoveTypeLayout, MoveValue)>;
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'d> serde::de::Visitor<'d> for CapturedValuesSeed {
+    type Value = Vec<(MoveTypeLayout, MoveValue)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Captured values")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'d>,
+    {
+        let mut vals = Vec::new();
+        while let Some(elem) = seq.next_element_seed(CapturedValueSeed)? {
+            vals.push(elem)
         }
+        Ok(vals)
+    }
+}
+
+struct CapturedValueSeed;
+
+impl<'d> serde::de::DeserializeSeed<'d> for CapturedValueSeed {
+    type Value = (MoveTypeLayout, MoveValue);
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'d> serde::de::Visitor<'d> for CapturedValueSeed {
+    type Value = (MoveTypeLayout, MoveValue);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Captured value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'d>,
+    {
+        let layout: MoveTypeLayout = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let value = seq
+            .next_element_seed(&layout)?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok((layout, value))
     }
 }
 
@@ -465,7 +606,31 @@ impl serde::Serialize for MoveValue {
                 }
                 t.end()
             },
+            MoveValue::Closure(c) => c.serialize(serializer),
+        }
+    }
+}
+
+impl serde::Serialize for MoveClosure {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut t = serializer.serialize_tuple(4)?;
+        t.serialize_element(&self.module_id)?;
+        t.serialize_element(&self.fun_id)?;
+        t.serialize_element(&self.mask)?;
+        t.serialize_element(&CapturedValues(&self.captured))?;
+        t.end()
+    }
+}
+
+struct CapturedValues<'a>(&'a [(MoveTypeLayout, MoveValue)]);
+
+impl<'a> serde::Serialize for CapturedValues<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut t = serializer.serialize_seq(Some(self.0.len()))?;
+        for (layout, value) in self.0.iter() {
+            t.serialize_element(&(layout, value))?;
         }
+        t.end()
     }
 }
 
@@ -529,6 +694,7 @@ impl fmt::Display for MoveTypeLayout {
             Vector(typ) => write!(f, "vector<{}>", typ),
             Struct(s) => write!(f, "{}", s),
             Signer => write!(f, "signer"),
+            Function => write!(f, "function"),
         }
     }
 }
@@ -578,6 +744,9 @@ impl TryInto<TypeTag> for &MoveTypeLayout {
                 TypeTag::Vector(Box::new(inner_type.try_into()?))
             },
             MoveTypeLayout::Struct(v) => TypeTag::Struct(Box::new(v.try_into()?)),
+            MoveTypeLayout::Function => {
+                bail!("Invalid MoveTypeLayout -> TypeTag conversion--function values have no type tag")
+            },
         })
     }
 }
@@ -611,10 +780,18 @@ impl fmt::Display for MoveValue {
             MoveValue::Signer(a) => write!(f, "signer({})", a.to_hex_literal()),
             MoveValue::Vector(v) => fmt_list(f, "vector[", v, "]"),
             MoveValue::Struct(s) => fmt::Display::fmt(s, f),
+            MoveValue::Closure(c) => fmt::Display::fmt(c, f),
         }
     }
 }
 
+impl fmt::Display for MoveClosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.module_id, self.fun_id)?;
+        fmt_list(f, "(", self.captured.iter().map(|(_, v)| v), ")")
+    }
+}
+
 impl fmt::Display for MoveStruct {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
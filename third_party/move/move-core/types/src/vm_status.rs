@@ -570,8 +570,10 @@ pub enum StatusCode {
     MULTISIG_TRANSACTION_INSUFFICIENT_APPROVALS = 34,
     MULTISIG_TRANSACTION_PAYLOAD_DOES_NOT_MATCH_HASH = 35,
     GAS_PAYER_ACCOUNT_MISSING = 36,
+    // Gas unit price submitted with the transaction is below the base fee of the current block.
+    // The transaction may still be executed once the base fee drops.
+    GAS_UNIT_PRICE_BELOW_BASE_FEE = 37,
     // Reserved error code for future use
-    RESERVED_VALIDATION_ERROR_2 = 37,
     RESERVED_VALIDATION_ERROR_3 = 38,
     RESERVED_VALIDATION_ERROR_4 = 39,
     RESERVED_VALIDATION_ERROR_5 = 40,
@@ -793,8 +795,10 @@ pub enum StatusCode {
     IO_LIMIT_REACHED = 4031,
     STORAGE_LIMIT_REACHED = 4032,
     TYPE_TAG_LIMIT_EXCEEDED = 4033,
+    // A native function dispatched to a function that is incompatible with it, or whose module
+    // is already active on the call stack.
+    RUNTIME_DISPATCH_ERROR = 4034,
     // Reserved error code for future use
-    RESERVED_RUNTIME_ERROR_2 = 4034,
     RESERVED_RUNTIME_ERROR_3 = 4035,
     RESERVED_RUNTIME_ERROR_4 = 4036,
     RESERVED_RUNTIME_ERROR_5 = 4037,
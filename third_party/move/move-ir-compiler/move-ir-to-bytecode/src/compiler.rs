@@ -12,7 +12,7 @@ use move_binary_format::{
         StructHandleIndex, StructTypeParameter, TableIndex, TypeParameterIndex, TypeSignature,
         Visibility,
     },
-    file_format_common::VERSION_MAX,
+    file_format_common::VERSION_DEFAULT,
 };
 use move_bytecode_source_map::source_map::SourceMap;
 use move_core_types::value::{MoveTypeLayout, MoveValue};
@@ -376,7 +376,7 @@ pub fn compile_script<'a>(
         source_map,
     ) = context.materialize_pools();
     let script = CompiledScript {
-        version: VERSION_MAX,
+        version: VERSION_DEFAULT,
         module_handles,
         struct_handles,
         function_handles,
@@ -468,7 +468,7 @@ pub fn compile_module<'a>(
         source_map,
     ) = context.materialize_pools();
     let module = CompiledModule {
-        version: VERSION_MAX,
+        version: VERSION_DEFAULT,
         module_handles,
         self_module_handle_idx,
         struct_handles,
@@ -547,7 +547,7 @@ fn compile_explicit_dependency_declarations(
             _source_map,
         ) = context.materialize_pools();
         let compiled_module = CompiledModule {
-            version: VERSION_MAX,
+            version: VERSION_DEFAULT,
             module_handles,
             self_module_handle_idx,
             struct_handles,
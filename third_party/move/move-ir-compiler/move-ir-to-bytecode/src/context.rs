@@ -805,6 +805,17 @@ impl<'a> Context<'a> {
                     .collect::<Result<_>>()?;
                 SignatureToken::StructInstantiation(correct_sh_idx, correct_inners)
             },
+            SignatureToken::Function(args, results, abilities) => {
+                let correct_args = args
+                    .into_iter()
+                    .map(|t| self.reindex_signature_token(dep, t))
+                    .collect::<Result<_>>()?;
+                let correct_results = results
+                    .into_iter()
+                    .map(|t| self.reindex_signature_token(dep, t))
+                    .collect::<Result<_>>()?;
+                SignatureToken::Function(correct_args, correct_results, abilities)
+            },
         })
     }
 
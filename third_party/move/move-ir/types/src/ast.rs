@@ -1807,7 +1807,7 @@ fn format_move_value(v: &MoveValue) -> String {
                 .join(", ");
             format!("vector[{}]", items)
         },
-        MoveValue::Struct(_) | MoveValue::Signer(_) => {
+        MoveValue::Struct(_) | MoveValue::Signer(_) | MoveValue::Closure(_) => {
             panic!("Should be inexpressible as a constant")
         },
         MoveValue::U16(u) => format!("{}u16", u),
@@ -4,7 +4,10 @@
 
 #![forbid(unsafe_code)]
 
-use crate::{function_target::FunctionTarget, function_target_pipeline::FunctionTargetsHolder};
+use crate::{
+    function_target::FunctionTarget,
+    function_target_pipeline::{FunctionTargetsHolder, FunctionVariant},
+};
 use move_model::model::GlobalEnv;
 use std::fmt::Write;
 
@@ -50,7 +53,8 @@ pub fn print_targets_with_annotations_for_test(
     writeln!(&mut text, "============ {} ================", header).unwrap();
     for module_env in env.get_modules() {
         for func_env in module_env.get_functions() {
-            if func_env.is_inline() {
+            // Functions of dependencies only have targets if they are called
+            if func_env.is_inline() || !targets.has_target(&func_env, &FunctionVariant::Baseline) {
                 continue;
             }
             for (variant, target) in targets.get_targets(&func_env) {
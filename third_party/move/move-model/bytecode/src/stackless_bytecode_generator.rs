@@ -1390,6 +1390,16 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                     None,
                 ))
             },
+            MoveBytecode::PackClosure(..)
+            | MoveBytecode::PackClosureGeneric(..)
+            | MoveBytecode::CallClosure(_) => {
+                let loc = self
+                    .func_env
+                    .get_bytecode_loc(code_offset)
+                    .expect(COMPILED_MODULE_AVAILABLE);
+                global_env.error(&loc, "closure bytecode is not supported by the prover");
+                self.code.push(Bytecode::Nop(attr_id));
+            },
         }
     }
 
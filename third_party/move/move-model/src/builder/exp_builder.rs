@@ -36,7 +36,10 @@ use move_compiler::{
 use move_core_types::{account_address::AccountAddress, value::MoveValue};
 use move_ir_types::location::{sp, Spanned};
 use num::{BigInt, FromPrimitive, Zero};
-use std::collections::{BTreeMap, BTreeSet, LinkedList};
+use std::{
+    collections::{BTreeMap, BTreeSet, LinkedList},
+    iter,
+};
 
 #[derive(Debug)]
 pub(crate) struct ExpTranslator<'env, 'translator, 'module_translator> {
@@ -1040,6 +1043,26 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                 self.translate_call(&loc, &Some(module_name), symbol, None, &args, expected_type)
             },
             EA::Exp_::ExpDotted(dotted) => self.translate_dotted(dotted, expected_type),
+            EA::Exp_::MethodCall(receiver, name, generics, args) => {
+                let args = args.value.iter().collect_vec();
+                self.translate_method_call(
+                    &loc,
+                    receiver,
+                    name,
+                    generics.as_deref(),
+                    &args,
+                    expected_type,
+                )
+            },
+            EA::Exp_::Index(target, index) if self.mode == ExpTranslationMode::Impl => {
+                // `v[i]` is `*vector::borrow(&v, i)`
+                let elem_ty = self.fresh_type_var();
+                let ref_ty = Type::Reference(ReferenceKind::Immutable, Box::new(elem_ty.clone()));
+                let borrow = self.translate_index_borrow(&loc, false, target, index, &ref_ty);
+                let ty = self.check_type(&loc, &elem_ty, expected_type, "");
+                let id = self.new_node_id_with_type_loc(&ty, &loc);
+                ExpData::Call(id, Operation::Deref, vec![borrow.into_exp()])
+            },
             EA::Exp_::Index(target, index) => {
                 self.translate_index(&loc, target, index, expected_type)
             },
@@ -1131,6 +1154,19 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
             },
             EA::Exp_::Borrow(mutable, exp) => {
                 self.require_impl_language(&loc);
+                if let EA::Exp_::Index(target, index) = &exp.value {
+                    if self.mode == ExpTranslationMode::Impl {
+                        // `&v[i]` and `&mut v[i]` are `vector::borrow(&v, i)` and
+                        // `vector::borrow_mut(&mut v, i)`, respectively
+                        return self.translate_index_borrow(
+                            &loc,
+                            *mutable,
+                            target,
+                            index,
+                            expected_type,
+                        );
+                    }
+                }
                 let ref_kind = ReferenceKind::from_is_mut(*mutable);
                 let target_ty = self.fresh_type_var();
                 let result_ty = self.check_type(
@@ -2057,6 +2093,157 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
         ExpData::Call(id, oper, vec![vector_exp.into_exp(), ie.into_exp()])
     }
 
+    /// Translates `&v[i]` or `&mut v[i]` in implementation code into a call of
+    /// `vector::borrow` or `vector::borrow_mut`. The vector is borrowed if it is not
+    /// already a reference.
+    fn translate_index_borrow(
+        &mut self,
+        loc: &Loc,
+        mutable: bool,
+        target: &EA::Exp,
+        index: &EA::Exp,
+        expected_type: &Type,
+    ) -> ExpData {
+        let ref_kind = ReferenceKind::from_is_mut(mutable);
+        let (target_ty, target_exp) =
+            if let EA::Exp_::Index(inner_target, inner_index) = &target.value {
+                // For `v[i][j]`, borrow the inner vector instead of copying it
+                let ref_ty = Type::Reference(ref_kind, Box::new(self.fresh_type_var()));
+                let target_loc = self.to_loc(&target.loc);
+                let target_exp = self.translate_index_borrow(
+                    &target_loc,
+                    mutable,
+                    inner_target,
+                    inner_index,
+                    &ref_ty,
+                );
+                (ref_ty, target_exp)
+            } else {
+                self.translate_exp_free(target)
+            };
+        let target_ty = self.subs.specialize(&target_ty);
+        let (target_ty, target_exp) = if target_ty.is_reference() {
+            (target_ty, target_exp)
+        } else {
+            self.borrow_receiver(&self.to_loc(&target.loc), ref_kind, target_ty, target_exp)
+        };
+        let (index_ty, index_exp) = self.translate_exp_free(index);
+        let name = if mutable { "borrow_mut" } else { "borrow" };
+        let name = self.symbol_pool().make(name);
+        let module_name = self.parent.parent.vector_module();
+        self.translate_call_with_args(
+            loc,
+            &Some(module_name),
+            name,
+            None,
+            &[target, index],
+            vec![target_ty, index_ty],
+            vec![target_exp.into_exp(), index_exp.into_exp()],
+            expected_type,
+        )
+    }
+
+    /// Translates a receiver-style call `receiver.name(args)`. The call is resolved to the
+    /// function `name` in the module which declares the type of the receiver, which must
+    /// have a first parameter named `self`. In implementation code, the receiver is borrowed
+    /// if that parameter is a reference and the receiver is not.
+    fn translate_method_call(
+        &mut self,
+        loc: &Loc,
+        receiver: &EA::Exp,
+        name: &Name,
+        generics: Option<&[EA::Type]>,
+        args: &[&EA::Exp],
+        expected_type: &Type,
+    ) -> ExpData {
+        let (receiver_ty, receiver_exp) = self.translate_exp_free(receiver);
+        let receiver_ty = self.subs.specialize(&receiver_ty);
+        let module_name = match receiver_ty.skip_reference() {
+            Type::Struct(mid, sid, _) => self
+                .parent
+                .parent
+                .reverse_struct_table
+                .get(&(*mid, *sid))
+                .map(|qsym| qsym.module_name.clone()),
+            Type::Vector(_) => Some(self.parent.parent.vector_module()),
+            _ => None,
+        };
+        let name = self.symbol_pool().make(name.value.as_str());
+        let receiver_param_ty = module_name.as_ref().and_then(|module_name| {
+            let full_name = QualifiedSymbol {
+                module_name: module_name.clone(),
+                symbol: name,
+            };
+            let self_symbol = self.parent.parent.self_symbol();
+            self.parent
+                .parent
+                .fun_table
+                .get(&full_name)
+                .and_then(|entry| entry.params.first())
+                .filter(|Parameter(param_name, _)| *param_name == self_symbol)
+                .map(|Parameter(_, ty)| ty.clone())
+        });
+        let Some(receiver_param_ty) = receiver_param_ty else {
+            if receiver_ty != Type::Error {
+                self.error(
+                    loc,
+                    &format!(
+                        "no receiver function `{}` found for type `{}`",
+                        name.display(self.symbol_pool()),
+                        receiver_ty.display(&self.type_display_context())
+                    ),
+                );
+            }
+            return self.new_error_exp();
+        };
+        let (receiver_ty, receiver_exp) = match receiver_param_ty {
+            Type::Reference(ref_kind, _)
+                if self.mode == ExpTranslationMode::Impl && !receiver_ty.is_reference() =>
+            {
+                self.borrow_receiver(
+                    &self.to_loc(&receiver.loc),
+                    ref_kind,
+                    receiver_ty,
+                    receiver_exp,
+                )
+            },
+            _ => (receiver_ty, receiver_exp),
+        };
+        let generics = generics.map(|ts| self.translate_types(ts));
+        let (mut arg_types, mut translated_args) = self.translate_exp_list(args, true);
+        arg_types.insert(0, receiver_ty);
+        translated_args.insert(0, receiver_exp.into_exp());
+        let all_args = iter::once(receiver)
+            .chain(args.iter().copied())
+            .collect_vec();
+        self.translate_call_with_args(
+            loc,
+            &module_name,
+            name,
+            generics,
+            &all_args,
+            arg_types,
+            translated_args,
+            expected_type,
+        )
+    }
+
+    /// Borrows the value of an implicitly referenced receiver or vector.
+    fn borrow_receiver(
+        &mut self,
+        loc: &Loc,
+        ref_kind: ReferenceKind,
+        ty: Type,
+        exp: ExpData,
+    ) -> (Type, ExpData) {
+        let ref_ty = Type::Reference(ref_kind, Box::new(ty));
+        let id = self.new_node_id_with_type_loc(&ref_ty, loc);
+        (
+            ref_ty,
+            ExpData::Call(id, Operation::Borrow(ref_kind), vec![exp.into_exp()]),
+        )
+    }
+
     /// Translate a Dotted expression.
     fn translate_dotted(&mut self, dotted: &EA::ExpDotted, expected_type: &Type) -> ExpData {
         match &dotted.value {
@@ -2240,7 +2427,32 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
         // is identified to avoid restrictions with type inference.
         // TODO: try to remove this special treatment as we have new constraint-based inference
         //   powers.
-        let (arg_types, mut translated_args) = self.translate_exp_list(args, true);
+        let (arg_types, translated_args) = self.translate_exp_list(args, true);
+        self.translate_call_with_args(
+            loc,
+            module,
+            name,
+            generics,
+            args,
+            arg_types,
+            translated_args,
+            expected_type,
+        )
+    }
+
+    /// Continues the translation of a call with already translated generics and arguments,
+    /// where lambda arguments have been skipped as in `translate_exp_list`.
+    fn translate_call_with_args(
+        &mut self,
+        loc: &Loc,
+        module: &Option<ModuleName>,
+        name: Symbol,
+        generics: Option<Vec<Type>>,
+        args: &[&EA::Exp],
+        arg_types: Vec<Type>,
+        mut translated_args: Vec<Exp>,
+        expected_type: &Type,
+    ) -> ExpData {
         let args_have_errors = arg_types.iter().any(|t| t == &Type::Error);
         // Lookup candidates.
         let cand_modules = if let Some(m) = module {
@@ -2981,7 +3193,8 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
             | (Type::Error, MoveValue::Vector(_))
             | (Type::Error, MoveValue::Struct(_))
             | (Type::Var(_), MoveValue::Vector(_))
-            | (Type::Var(_), MoveValue::Struct(_)) => {
+            | (Type::Var(_), MoveValue::Struct(_))
+            | (_, MoveValue::Closure(_)) => {
                 self.error(
                     loc,
                     &format!("Not yet supported constant value: {:?}", value),
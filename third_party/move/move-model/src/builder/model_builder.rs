@@ -404,6 +404,19 @@ impl<'env> ModelBuilder<'env> {
         self.env.symbol_pool().make("assert")
     }
 
+    /// Returns the symbol for the receiver parameter of a function.
+    pub fn self_symbol(&self) -> Symbol {
+        self.env.symbol_pool().make("self")
+    }
+
+    /// Returns the name of the standard library module `0x1::vector`.
+    pub fn vector_module(&self) -> ModuleName {
+        ModuleName::new(
+            Address::Numerical(AccountAddress::ONE),
+            self.env.symbol_pool().make("vector"),
+        )
+    }
+
     /// Returns the name for the pseudo builtin module.
     pub fn builtin_module(&self) -> ModuleName {
         ModuleName::new(
@@ -96,7 +96,9 @@ pub fn run_model_builder_in_compiler_mode(
             compile_via_model: true,
             ..ModelBuilderOptions::default()
         },
-        Flags::model_compilation().set_skip_attribute_checks(skip_attribute_checks),
+        Flags::model_compilation()
+            .set_skip_attribute_checks(skip_attribute_checks)
+            .set_lang_v2(true),
         known_attributes,
     )
 }
@@ -2483,6 +2483,14 @@ impl<'env> ModuleEnv<'env> {
                     self.internal_globalize_signatures(module, args),
                 )
             },
+            SignatureToken::Function(args, results, _) => Type::Fun(
+                Box::new(Type::tuple(
+                    self.internal_globalize_signatures(module, args),
+                )),
+                Box::new(Type::tuple(
+                    self.internal_globalize_signatures(module, results),
+                )),
+            ),
         }
     }
 
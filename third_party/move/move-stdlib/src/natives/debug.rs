@@ -567,6 +567,9 @@ mod testing {
                         .with_message("Expected MoveStruct::WithTypes".to_string()))
                 },
             },
+            MoveValue::Closure(closure) => {
+                write!(out, "{}", closure).map_err(fmt_error_to_partial_vm_error)?;
+            },
         }
 
         Ok(())
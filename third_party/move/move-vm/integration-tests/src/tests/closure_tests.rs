@@ -0,0 +1,292 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::{errors::VMResult, file_format::*, file_format_common::VERSION_7};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    vm_status::StatusCode,
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas::UnmeteredGasMeter;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+// |u64| u64 has copy + drop + store
+fn u64_to_u64() -> SignatureToken {
+    SignatureToken::Function(
+        vec![SignatureToken::U64],
+        vec![SignatureToken::U64],
+        AbilitySet::FUNCTIONS,
+    )
+}
+
+fn public_function(function: u16, code: Vec<Bytecode>) -> FunctionDefinition {
+    FunctionDefinition {
+        function: FunctionHandleIndex(function),
+        visibility: Visibility::Public,
+        is_entry: false,
+        acquires_global_resources: vec![],
+        code: Some(CodeUnit {
+            locals: SignatureIndex(0),
+            code,
+        }),
+    }
+}
+
+// module 0x2a::N {
+//     public fun sub(x: u64, y: u64): u64 { x - y }
+//     public fun apply(x: u64, f: |u64| u64): u64 { f(x) }
+// }
+fn mk_n() -> CompiledModule {
+    CompiledModule {
+        version: VERSION_7,
+        module_handles: vec![ModuleHandle {
+            address: AddressIdentifierIndex(0),
+            name: IdentifierIndex(0),
+        }],
+        self_module_handle_idx: ModuleHandleIndex(0),
+        identifiers: vec![
+            Identifier::new("N").unwrap(),
+            Identifier::new("sub").unwrap(),
+            Identifier::new("apply").unwrap(),
+        ],
+        address_identifiers: vec![TEST_ADDR],
+        function_handles: vec![
+            // fun sub(u64, u64): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(1),
+                parameters: SignatureIndex(1),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // fun apply(u64, |u64| u64): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(2),
+                parameters: SignatureIndex(3),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+        ],
+        function_defs: vec![
+            public_function(0, vec![
+                Bytecode::MoveLoc(0),
+                Bytecode::MoveLoc(1),
+                Bytecode::Sub,
+                Bytecode::Ret,
+            ]),
+            public_function(1, vec![
+                Bytecode::MoveLoc(0),
+                Bytecode::MoveLoc(1),
+                Bytecode::CallClosure(SignatureIndex(4)),
+                Bytecode::Ret,
+            ]),
+        ],
+        signatures: vec![
+            Signature(vec![]),                                         // void
+            Signature(vec![SignatureToken::U64, SignatureToken::U64]), // u64, u64
+            Signature(vec![SignatureToken::U64]),                      // u64
+            Signature(vec![SignatureToken::U64, u64_to_u64()]),        // u64, |u64| u64
+            Signature(vec![u64_to_u64()]),                             // |u64| u64
+        ],
+        struct_defs: vec![],
+        struct_handles: vec![],
+        constant_pool: vec![],
+        metadata: vec![],
+        field_handles: vec![],
+        friend_decls: vec![],
+        struct_def_instantiations: vec![],
+        function_instantiations: vec![],
+        field_instantiations: vec![],
+    }
+}
+
+// module 0x2a::M {
+//     public fun sub(x: u64, y: u64): u64 { x - y }
+//     public fun capture_first(): u64 { Self::sub(10, _)(3) }
+//     public fun capture_second(): u64 { Self::sub(_, 3)(10) }
+//     public fun call_other(): u64 { 0x2a::N::apply(3, 0x2a::N::sub(10, _)) }
+//     public fun reenter(): u64 { 0x2a::N::apply(3, Self::sub(10, _)) }
+// }
+fn mk_m() -> CompiledModule {
+    CompiledModule {
+        version: VERSION_7,
+        module_handles: vec![
+            ModuleHandle {
+                address: AddressIdentifierIndex(0),
+                name: IdentifierIndex(0),
+            },
+            ModuleHandle {
+                address: AddressIdentifierIndex(0),
+                name: IdentifierIndex(1),
+            },
+        ],
+        self_module_handle_idx: ModuleHandleIndex(0),
+        identifiers: vec![
+            Identifier::new("M").unwrap(),
+            Identifier::new("N").unwrap(),
+            Identifier::new("sub").unwrap(),
+            Identifier::new("apply").unwrap(),
+            Identifier::new("capture_first").unwrap(),
+            Identifier::new("capture_second").unwrap(),
+            Identifier::new("call_other").unwrap(),
+            Identifier::new("reenter").unwrap(),
+        ],
+        address_identifiers: vec![TEST_ADDR],
+        function_handles: vec![
+            // Self::sub(u64, u64): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(2),
+                parameters: SignatureIndex(1),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // N::sub(u64, u64): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(1),
+                name: IdentifierIndex(2),
+                parameters: SignatureIndex(1),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // N::apply(u64, |u64| u64): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(1),
+                name: IdentifierIndex(3),
+                parameters: SignatureIndex(3),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // Self::capture_first(): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(4),
+                parameters: SignatureIndex(0),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // Self::capture_second(): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(5),
+                parameters: SignatureIndex(0),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // Self::call_other(): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(6),
+                parameters: SignatureIndex(0),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            // Self::reenter(): u64
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(7),
+                parameters: SignatureIndex(0),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+        ],
+        function_defs: vec![
+            public_function(0, vec![
+                Bytecode::MoveLoc(0),
+                Bytecode::MoveLoc(1),
+                Bytecode::Sub,
+                Bytecode::Ret,
+            ]),
+            public_function(3, vec![
+                Bytecode::LdU64(3),
+                Bytecode::LdU64(10),
+                Bytecode::PackClosure(FunctionHandleIndex(0), ClosureMask::new(0b01)),
+                Bytecode::CallClosure(SignatureIndex(4)),
+                Bytecode::Ret,
+            ]),
+            public_function(4, vec![
+                Bytecode::LdU64(10),
+                Bytecode::LdU64(3),
+                Bytecode::PackClosure(FunctionHandleIndex(0), ClosureMask::new(0b10)),
+                Bytecode::CallClosure(SignatureIndex(4)),
+                Bytecode::Ret,
+            ]),
+            public_function(5, vec![
+                Bytecode::LdU64(3),
+                Bytecode::LdU64(10),
+                Bytecode::PackClosure(FunctionHandleIndex(1), ClosureMask::new(0b01)),
+                Bytecode::Call(FunctionHandleIndex(2)),
+                Bytecode::Ret,
+            ]),
+            public_function(6, vec![
+                Bytecode::LdU64(3),
+                Bytecode::LdU64(10),
+                Bytecode::PackClosure(FunctionHandleIndex(0), ClosureMask::new(0b01)),
+                Bytecode::Call(FunctionHandleIndex(2)),
+                Bytecode::Ret,
+            ]),
+        ],
+        signatures: vec![
+            Signature(vec![]),                                         // void
+            Signature(vec![SignatureToken::U64, SignatureToken::U64]), // u64, u64
+            Signature(vec![SignatureToken::U64]),                      // u64
+            Signature(vec![SignatureToken::U64, u64_to_u64()]),        // u64, |u64| u64
+            Signature(vec![u64_to_u64()]),                             // |u64| u64
+        ],
+        struct_defs: vec![],
+        struct_handles: vec![],
+        constant_pool: vec![],
+        metadata: vec![],
+        field_handles: vec![],
+        friend_decls: vec![],
+        struct_def_instantiations: vec![],
+        function_instantiations: vec![],
+        field_instantiations: vec![],
+    }
+}
+
+fn run(fun_name: &str) -> VMResult<u64> {
+    let mut storage = InMemoryStorage::new();
+    for module in [mk_n(), mk_m()] {
+        let mut blob = vec![];
+        module
+            .serialize_for_version(Some(VERSION_7), &mut blob)
+            .unwrap();
+        storage.publish_or_overwrite_module(module.self_id(), blob);
+    }
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut session = vm.new_session(&storage);
+
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+    let return_values = session
+        .execute_function_bypass_visibility(
+            &module_id,
+            &Identifier::new(fun_name).unwrap(),
+            vec![],
+            Vec::<Vec<u8>>::new(),
+            &mut UnmeteredGasMeter,
+        )?
+        .return_values;
+    let (blob, _) = &return_values[0];
+    Ok(u64::from_le_bytes(blob.as_slice().try_into().unwrap()))
+}
+
+#[test]
+fn call_closure_with_captured_args() {
+    assert_eq!(run("capture_first").unwrap(), 7);
+    assert_eq!(run("capture_second").unwrap(), 7);
+}
+
+#[test]
+fn call_closure_in_other_module() {
+    assert_eq!(run("call_other").unwrap(), 7);
+}
+
+#[test]
+fn call_closure_reentrancy_rejected() {
+    let err = run("reenter").unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::RUNTIME_DISPATCH_ERROR);
+}
@@ -12,11 +12,14 @@ use crate::{
 use fail::fail_point;
 use move_binary_format::{
     errors::*,
-    file_format::{Ability, AbilitySet, Bytecode, FunctionHandleIndex, FunctionInstantiationIndex},
+    file_format::{
+        Ability, AbilitySet, Bytecode, ClosureMask, FunctionHandleIndex, FunctionInstantiationIndex,
+    },
 };
 use move_core_types::{
     account_address::AccountAddress,
     gas_algebra::{NumArgs, NumBytes},
+    identifier::Identifier,
     language_storage::TypeTag,
     vm_status::{StatusCode, StatusType},
 };
@@ -25,10 +28,10 @@ use move_vm_types::{
     loaded_data::runtime_types::Type,
     natives::function::NativeResult,
     values::{
-        self, GlobalValue, IntegerValue, Locals, Reference, Struct, StructRef, VMValueCast, Value,
-        Vector, VectorRef,
+        self, Closure, GlobalValue, IntegerValue, Locals, Reference, Struct, StructRef,
+        VMValueCast, Value, Vector, VectorRef,
     },
-    views::TypeView,
+    views::{TypeView, ValueView, ValueVisitor},
 };
 use std::{cmp::min, collections::VecDeque, fmt::Write, sync::Arc};
 
@@ -68,6 +71,8 @@ pub(crate) struct Interpreter {
     call_stack: CallStack,
     /// Whether to perform a paranoid type safety checks at runtime.
     paranoid_type_checks: bool,
+    /// Number of frames on the call stack entered by calling a closure.
+    closure_frames: usize,
 }
 
 struct TypeWithLoader<'a, 'b> {
@@ -97,6 +102,7 @@ impl Interpreter {
             operand_stack: Stack::new(),
             call_stack: CallStack::new(),
             paranoid_type_checks: loader.vm_config().paranoid_type_checks,
+            closure_frames: 0,
         }
         .execute_main(
             loader, data_store, gas_meter, extensions, function, ty_args, args,
@@ -154,6 +160,10 @@ impl Interpreter {
                         .charge_drop_frame(non_ref_vals.iter())
                         .map_err(|e| self.set_location(e))?;
 
+                    if current_frame.is_closure_call {
+                        self.closure_frames -= 1;
+                    }
+
                     if let Some(frame) = self.call_stack.pop() {
                         // Note: the caller will find the callee's return values at the top of the shared operand stack
                         current_frame = frame;
@@ -169,6 +179,10 @@ impl Interpreter {
                     if self.paranoid_type_checks {
                         self.check_friend_or_private_call(&current_frame.function, &func)?;
                     }
+                    if self.closure_frames > 0 {
+                        self.check_reentrancy(&current_frame, &func, true)
+                            .map_err(|e| set_err_info!(current_frame, e))?;
+                    }
 
                     // Charge gas
                     let module_id = func
@@ -190,14 +204,23 @@ impl Interpreter {
                         .map_err(|e| set_err_info!(current_frame, e))?;
 
                     if func.is_native() {
-                        self.call_native(
+                        if let Some((target, ty_args)) = self.call_native(
                             &resolver,
                             data_store,
                             gas_meter,
                             extensions,
                             func,
                             vec![],
-                        )?;
+                        )? {
+                            current_frame = self.make_dispatch_frame(
+                                loader,
+                                gas_meter,
+                                current_frame,
+                                target,
+                                ty_args,
+                            )?;
+                            continue;
+                        }
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
                     }
@@ -223,6 +246,10 @@ impl Interpreter {
                     if self.paranoid_type_checks {
                         self.check_friend_or_private_call(&current_frame.function, &func)?;
                     }
+                    if self.closure_frames > 0 {
+                        self.check_reentrancy(&current_frame, &func, true)
+                            .map_err(|e| set_err_info!(current_frame, e))?;
+                    }
 
                     // Charge gas
                     let module_id = func
@@ -245,9 +272,18 @@ impl Interpreter {
                         .map_err(|e| set_err_info!(current_frame, e))?;
 
                     if func.is_native() {
-                        self.call_native(
+                        if let Some((target, ty_args)) = self.call_native(
                             &resolver, data_store, gas_meter, extensions, func, ty_args,
-                        )?;
+                        )? {
+                            current_frame = self.make_dispatch_frame(
+                                loader,
+                                gas_meter,
+                                current_frame,
+                                target,
+                                ty_args,
+                            )?;
+                            continue;
+                        }
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
                     }
@@ -262,8 +298,170 @@ impl Interpreter {
                     })?;
                     current_frame = frame;
                 },
+                ExitCode::CallClosure(closure) => {
+                    let func = loader.load_closure_target(
+                        closure.module_id(),
+                        closure.fun_name(),
+                        data_store,
+                    )?;
+                    let ty_args = closure.ty_args().to_vec();
+                    self.check_reentrancy(&current_frame, &func, true)
+                        .and_then(|_| self.prepare_closure_args(loader, &func, &ty_args, closure))
+                        .map_err(|e| set_err_info!(current_frame, e))?;
+
+                    // Charge gas
+                    let module_id = func
+                        .module_id()
+                        .ok_or_else(|| {
+                            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                                .with_message("Failed to get native function module id".to_string())
+                        })
+                        .map_err(|e| set_err_info!(current_frame, e))?;
+                    let args = self
+                        .operand_stack
+                        .last_n(func.arg_count())
+                        .map_err(|e| set_err_info!(current_frame, e))?;
+                    if ty_args.is_empty() {
+                        gas_meter.charge_call(
+                            module_id,
+                            func.name(),
+                            args,
+                            (func.local_count() as u64).into(),
+                        )
+                    } else {
+                        gas_meter.charge_call_generic(
+                            module_id,
+                            func.name(),
+                            ty_args.iter().map(|ty| TypeWithLoader { ty, loader }),
+                            args,
+                            (func.local_count() as u64).into(),
+                        )
+                    }
+                    .map_err(|e| set_err_info!(current_frame, e))?;
+
+                    if func.is_native() {
+                        if let Some((target, ty_args)) = self.call_native(
+                            &resolver, data_store, gas_meter, extensions, func, ty_args,
+                        )? {
+                            current_frame = self.make_dispatch_frame(
+                                loader,
+                                gas_meter,
+                                current_frame,
+                                target,
+                                ty_args,
+                            )?;
+                            continue;
+                        }
+                        current_frame.pc += 1; // advance past the CallClosure instruction in the caller
+                        continue;
+                    }
+                    let mut frame = self
+                        .make_call_frame(loader, func, ty_args)
+                        .map_err(|e| self.set_location(e))
+                        .map_err(|err| self.maybe_core_dump(err, &current_frame))?;
+                    frame.is_closure_call = true;
+                    self.call_stack.push(current_frame).map_err(|frame| {
+                        let err = PartialVMError::new(StatusCode::CALL_STACK_OVERFLOW);
+                        let err = set_err_info!(frame, err);
+                        self.maybe_core_dump(err, &frame)
+                    })?;
+                    self.closure_frames += 1;
+                    current_frame = frame;
+                },
+            }
+        }
+    }
+
+    /// Checks that a call does not re-enter a module which is already executing further up the
+    /// call stack, so the callee cannot observe or modify the state of a module in the middle of
+    /// its execution.
+    ///
+    /// Calls within the module of the current frame are not re-entrant unless
+    /// `allow_same_module` is false, as is the case for calls dispatched by natives. Without
+    /// closures or dispatch, modules cannot be re-entered as dependencies are acyclic, so the
+    /// check is only needed when a closure is being called or is executing.
+    fn check_reentrancy(
+        &self,
+        current_frame: &Frame,
+        target: &Function,
+        allow_same_module: bool,
+    ) -> PartialVMResult<()> {
+        let module_id = match target.module_id() {
+            Some(module_id) => module_id,
+            None => return Ok(()),
+        };
+        if allow_same_module && current_frame.function.module_id() == Some(module_id) {
+            return Ok(());
+        }
+        if std::iter::once(current_frame)
+            .chain(self.call_stack.0.iter())
+            .any(|frame| frame.function.module_id() == Some(module_id))
+        {
+            return Err(
+                PartialVMError::new(StatusCode::RUNTIME_DISPATCH_ERROR).with_message(format!(
+                    "Re-entrancy into module {} is not allowed",
+                    module_id
+                )),
+            );
+        }
+        Ok(())
+    }
+
+    /// Places the arguments of the function a closure is over on the operand stack, merging the
+    /// values captured by the closure with the ones it is called with, which are on top of the
+    /// operand stack.
+    fn prepare_closure_args(
+        &mut self,
+        loader: &Loader,
+        func: &Function,
+        ty_args: &[Type],
+        closure: Closure,
+    ) -> PartialVMResult<()> {
+        if func.type_parameters().len() != ty_args.len() {
+            return Err(
+                PartialVMError::new(StatusCode::NUMBER_OF_TYPE_ARGUMENTS_MISMATCH)
+                    .with_message(format!("Closure over {} is ill-formed", func.name())),
+            );
+        }
+        let mask = closure.mask();
+        let provided_count = func
+            .arg_count()
+            .checked_sub(mask.captured_count())
+            .filter(|_| mask.is_valid_for(func.arg_count()))
+            .ok_or_else(|| {
+                PartialVMError::new(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH)
+                    .with_message(format!("Closure over {} is ill-formed", func.name()))
+            })?;
+        let provided = self.operand_stack.popn(provided_count as u16)?;
+        let args = mask
+            .compose(closure.into_captured(), provided)
+            .ok_or_else(|| {
+                PartialVMError::new(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH)
+                    .with_message(format!("Closure over {} is ill-formed", func.name()))
+            })?;
+
+        if self.paranoid_type_checks {
+            // Replace the types of the provided arguments by the types of all arguments of the
+            // function, which are checked when the frame is created.
+            let provided_tys = self.operand_stack.popn_tys(provided_count as u16)?;
+            let resolver = func.get_resolver(loader);
+            let param_tys = func
+                .parameter_types()
+                .iter()
+                .map(|ty| resolver.subst(ty, ty_args))
+                .collect::<PartialVMResult<Vec<_>>>()?;
+            let (_, remaining_tys) = mask.split(&param_tys);
+            for (ty, expected_ty) in provided_tys.iter().zip(remaining_tys) {
+                ty.check_eq(expected_ty)?;
             }
+            for ty in param_tys {
+                self.operand_stack.push_ty(ty)?;
+            }
+        }
+        for arg in args {
+            self.operand_stack.push(arg)?;
         }
+        Ok(())
     }
 
     /// Returns a `Frame` if the call is to a Move function. Calls to native functions are
@@ -305,6 +503,64 @@ impl Interpreter {
         self.make_new_frame(loader, func, ty_args, locals)
     }
 
+    /// Creates the frame of the function a native function dispatched to, after pushing the
+    /// frame of the caller of the native onto the call stack.
+    ///
+    /// The arguments of the target are expected on top of the operand stack. Its return values
+    /// are found by the caller as if the native function had returned them.
+    fn make_dispatch_frame(
+        &mut self,
+        loader: &Loader,
+        gas_meter: &mut impl GasMeter,
+        current_frame: Frame,
+        target: Arc<Function>,
+        ty_args: Vec<Type>,
+    ) -> VMResult<Frame> {
+        let module_id = target
+            .module_id()
+            .ok_or_else(|| {
+                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message("Dispatch target is not located in a module".to_string())
+            })
+            .map_err(|e| set_err_info!(current_frame, e))?;
+
+        self.check_reentrancy(&current_frame, &target, false)
+            .map_err(|e| set_err_info!(current_frame, e))?;
+
+        let args = self
+            .operand_stack
+            .last_n(target.arg_count())
+            .map_err(|e| set_err_info!(current_frame, e))?;
+        if ty_args.is_empty() {
+            gas_meter.charge_call(
+                module_id,
+                target.name(),
+                args,
+                (target.local_count() as u64).into(),
+            )
+        } else {
+            gas_meter.charge_call_generic(
+                module_id,
+                target.name(),
+                ty_args.iter().map(|ty| TypeWithLoader { ty, loader }),
+                args,
+                (target.local_count() as u64).into(),
+            )
+        }
+        .map_err(|e| set_err_info!(current_frame, e))?;
+
+        let frame = self
+            .make_call_frame(loader, target, ty_args)
+            .map_err(|e| self.set_location(e))
+            .map_err(|err| self.maybe_core_dump(err, &current_frame))?;
+        self.call_stack.push(current_frame).map_err(|frame| {
+            let err = PartialVMError::new(StatusCode::CALL_STACK_OVERFLOW);
+            let err = set_err_info!(frame, err);
+            self.maybe_core_dump(err, &frame)
+        })?;
+        Ok(frame)
+    }
+
     /// Create a new `Frame` given a `Function` and the function `Locals`.
     ///
     /// The locals must be loaded before calling this.
@@ -335,10 +591,14 @@ impl Interpreter {
             function,
             ty_args,
             local_tys,
+            is_closure_call: false,
         })
     }
 
     /// Call a native functions.
+    ///
+    /// Returns the function to call in place of the native, and its type arguments, if the
+    /// native dispatched to a Move function.
     fn call_native(
         &mut self,
         resolver: &Resolver,
@@ -347,7 +607,7 @@ impl Interpreter {
         extensions: &mut NativeContextExtensions,
         function: Arc<Function>,
         ty_args: Vec<Type>,
-    ) -> VMResult<()> {
+    ) -> VMResult<Option<(Arc<Function>, Vec<Type>)>> {
         // Note: refactor if native functions push a frame on the stack
         self.call_native_impl(
             resolver,
@@ -377,7 +637,7 @@ impl Interpreter {
         extensions: &mut NativeContextExtensions,
         function: Arc<Function>,
         ty_args: Vec<Type>,
-    ) -> PartialVMResult<()> {
+    ) -> PartialVMResult<Option<(Arc<Function>, Vec<Type>)>> {
         let return_type_count = function.return_type_count();
         let mut args = VecDeque::new();
         let expected_args = function.arg_count();
@@ -437,6 +697,44 @@ impl Interpreter {
 
                 return Err(err);
             },
+            NativeResult::CallFunction {
+                cost,
+                module_name,
+                func_name,
+                ty_args: target_ty_args,
+                args,
+            } => {
+                gas_meter.charge_native_function(cost, Option::<std::iter::Empty<&Value>>::None)?;
+
+                let target = resolver
+                    .loader()
+                    .load_dispatch_target(&module_name, &func_name, data_store)
+                    .map_err(|e| e.to_partial())?;
+                if !function.is_dispatch_compatible(&target) || args.len() != target.arg_count() {
+                    return Err(PartialVMError::new(StatusCode::RUNTIME_DISPATCH_ERROR)
+                        .with_message(format!(
+                            "Function {} is not a valid dispatch target of {}",
+                            target.pretty_string(),
+                            function.pretty_string()
+                        )));
+                }
+                resolver
+                    .loader()
+                    .verify_ty_args(target.type_parameters(), &target_ty_args)?;
+
+                // Put the arguments of the target on top of the operand stack, where the frame
+                // of the target will be created from.
+                for value in args {
+                    self.operand_stack.push(value)?;
+                }
+                if self.paranoid_type_checks {
+                    for ty in target.parameter_types() {
+                        self.operand_stack
+                            .push_ty(resolver.subst(ty, &target_ty_args)?)?;
+                    }
+                }
+                return Ok(Some((target, target_ty_args)));
+            },
         };
 
         // Paranoid check to protect us against incorrect native function implementations. A native function that
@@ -461,7 +759,7 @@ impl Interpreter {
                 self.operand_stack.push_ty(resolver.subst(ty, &ty_args)?)?;
             }
         }
-        Ok(())
+        Ok(None)
     }
 
     /// Make sure only private/friend function can only be invoked by modules under the same address.
@@ -1038,6 +1336,8 @@ fn check_depth_of_type_impl(
         | Type::U256
         | Type::Address
         | Type::Signer => check_depth!(0),
+        // The depth of the values captured by a closure is checked when the closure is packed.
+        Type::Function(_, _, _) => check_depth!(0),
         // Even though this is recursive this is OK since the depth of this recursion is
         // bounded by the depth of the type arguments, which we have already checked.
         Type::Reference(ty) | Type::MutableReference(ty) | Type::Vector(ty) => {
@@ -1085,6 +1385,83 @@ fn check_depth_of_type_impl(
     Ok(ty_depth)
 }
 
+/// Checks the depth of values captured by a closure. Function types do not reflect the values
+/// captured by closures, so unlike other values their depth cannot be bounded by their type.
+fn check_depth_of_values(resolver: &Resolver, vals: &[Value]) -> PartialVMResult<()> {
+    struct DepthVisitor {
+        max_depth: usize,
+    }
+
+    impl ValueVisitor for DepthVisitor {
+        fn visit_u8(&mut self, depth: usize, _val: u8) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_u16(&mut self, depth: usize, _val: u16) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_u32(&mut self, depth: usize, _val: u32) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_u64(&mut self, depth: usize, _val: u64) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_u128(&mut self, depth: usize, _val: u128) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_u256(&mut self, depth: usize, _val: move_core_types::u256::U256) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_bool(&mut self, depth: usize, _val: bool) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_address(&mut self, depth: usize, _val: AccountAddress) {
+            self.visit_leaf(depth)
+        }
+
+        fn visit_struct(&mut self, depth: usize, _len: usize) -> bool {
+            self.visit_leaf(depth);
+            true
+        }
+
+        fn visit_vec(&mut self, depth: usize, _len: usize) -> bool {
+            self.visit_leaf(depth);
+            true
+        }
+
+        fn visit_ref(&mut self, depth: usize, _is_global: bool) -> bool {
+            self.visit_leaf(depth);
+            false
+        }
+    }
+
+    impl DepthVisitor {
+        fn visit_leaf(&mut self, depth: usize) {
+            self.max_depth = self.max_depth.max(depth);
+        }
+    }
+
+    let max_depth = match resolver.loader().vm_config().max_value_nest_depth {
+        Some(max_depth) => max_depth,
+        None => return Ok(()),
+    };
+    let mut visitor = DepthVisitor { max_depth: 0 };
+    for val in vals {
+        val.visit(&mut visitor);
+    }
+    // The closure itself adds a level on top of its captured values, visited at depth 0.
+    if visitor.max_depth as u64 + 2 > max_depth {
+        return Err(PartialVMError::new(StatusCode::VM_MAX_VALUE_DEPTH_REACHED));
+    }
+    Ok(())
+}
+
 /// A `Frame` is the execution context for a function. It holds the locals of the function and
 /// the function itself.
 // #[derive(Debug)]
@@ -1094,6 +1471,8 @@ struct Frame {
     function: Arc<Function>,
     ty_args: Vec<Type>,
     local_tys: Vec<Type>,
+    /// Whether the frame was entered by calling a closure.
+    is_closure_call: bool,
 }
 
 /// An `ExitCode` from `execute_code_unit`.
@@ -1102,6 +1481,7 @@ enum ExitCode {
     Return,
     Call(FunctionHandleIndex),
     CallGeneric(FunctionInstantiationIndex),
+    CallClosure(Closure),
 }
 
 fn check_ability(has_ability: bool) -> PartialVMResult<()> {
@@ -1137,6 +1517,90 @@ impl Frame {
             })
     }
 
+    /// Packs a closure over `func` from the captured values on top of the operand stack.
+    fn pack_closure(
+        resolver: &Resolver,
+        interpreter: &mut Interpreter,
+        gas_meter: &mut impl GasMeter,
+        func: &Function,
+        ty_args: Vec<Type>,
+        mask: ClosureMask,
+    ) -> PartialVMResult<()> {
+        let captured_count = mask.captured_count();
+        gas_meter.charge_pack_closure(
+            !ty_args.is_empty(),
+            interpreter.operand_stack.last_n(captured_count)?,
+        )?;
+        let captured = interpreter.operand_stack.popn(captured_count as u16)?;
+        check_depth_of_values(resolver, &captured)?;
+
+        // Layouts are recorded so that the closure can be serialized without resolving the
+        // function it is over.
+        let (captured_tys, _) = mask.split(func.parameter_types());
+        let captured_layouts = captured_tys
+            .into_iter()
+            .map(|ty| resolver.type_to_type_layout(&resolver.subst(ty, &ty_args)?))
+            .collect::<PartialVMResult<Vec<_>>>()?;
+        let module_id = func.module_id().ok_or_else(|| {
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message("Closures can only be packed over module functions".to_string())
+        })?;
+        let fun_name = Identifier::new(func.name()).map_err(|_| {
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message(format!("Invalid function name {}", func.name()))
+        })?;
+
+        interpreter.operand_stack.push(Value::closure(Closure::pack(
+            module_id.clone(),
+            fun_name,
+            ty_args,
+            mask,
+            captured,
+            captured_layouts,
+        )))
+    }
+
+    /// Paranoid type checks for packing a closure, mirroring the bytecode verifier.
+    fn closure_type_transition(
+        resolver: &Resolver,
+        interpreter: &mut Interpreter,
+        func: &Function,
+        ty_args: &[Type],
+        mask: ClosureMask,
+    ) -> PartialVMResult<()> {
+        let param_tys = func
+            .parameter_types()
+            .iter()
+            .map(|ty| resolver.subst(ty, ty_args))
+            .collect::<PartialVMResult<Vec<_>>>()?;
+        let (captured_tys, remaining_tys) = mask.split(&param_tys);
+
+        let mut abilities = AbilitySet::FUNCTIONS;
+        for (ty, expected_ty) in interpreter
+            .operand_stack
+            .popn_tys(captured_tys.len() as u16)?
+            .iter()
+            .zip(captured_tys)
+        {
+            ty.check_eq(expected_ty)?;
+            abilities = abilities.intersect(resolver.loader().abilities(ty)?);
+        }
+        if !ty_args.is_empty() || func.is_friend_or_private() {
+            abilities = abilities.remove(Ability::Store);
+        }
+
+        let result_tys = func
+            .return_types()
+            .iter()
+            .map(|ty| resolver.subst(ty, ty_args))
+            .collect::<PartialVMResult<Vec<_>>>()?;
+        interpreter.operand_stack.push_ty(Type::Function(
+            remaining_tys.into_iter().cloned().collect(),
+            result_tys,
+            abilities,
+        ))
+    }
+
     /// Paranoid type checks to perform before instruction execution.
     ///
     /// Note that most of the checks should happen after instruction execution, because gas charging will happen during
@@ -1144,7 +1608,7 @@ impl Frame {
     fn pre_execution_type_stack_transition(
         local_tys: &[Type],
         locals: &Locals,
-        _ty_args: &[Type],
+        ty_args: &[Type],
         resolver: &Resolver,
         interpreter: &mut Interpreter,
         instruction: &Bytecode,
@@ -1152,6 +1616,20 @@ impl Frame {
         match instruction {
             // Call instruction will be checked at execute_main.
             Bytecode::Call(_) | Bytecode::CallGeneric(_) => (),
+            // The arguments of a closure call will be checked at execute_main.
+            Bytecode::CallClosure(sig_idx) => {
+                let expected_ty = resolver.instantiate_single_type(*sig_idx, ty_args)?;
+                let ty = interpreter.operand_stack.pop_ty()?;
+                match (&ty, &expected_ty) {
+                    (
+                        Type::Function(args, results, abilities),
+                        Type::Function(expected_args, expected_results, expected_abilities),
+                    ) if args == expected_args
+                        && results == expected_results
+                        && expected_abilities.is_subset(*abilities) => {},
+                    _ => expected_ty.check_eq(&ty)?,
+                }
+            },
             Bytecode::BrFalse(_) | Bytecode::BrTrue(_) => {
                 interpreter.operand_stack.pop_ty()?;
             },
@@ -1244,7 +1722,9 @@ impl Frame {
             | Bytecode::VecPushBack(_)
             | Bytecode::VecPopBack(_)
             | Bytecode::VecUnpack(_, _)
-            | Bytecode::VecSwap(_) => (),
+            | Bytecode::VecSwap(_)
+            | Bytecode::PackClosure(_, _)
+            | Bytecode::PackClosureGeneric(_, _) => (),
         };
         Ok(())
     }
@@ -1265,6 +1745,7 @@ impl Frame {
             | Bytecode::Ret
             | Bytecode::Call(_)
             | Bytecode::CallGeneric(_)
+            | Bytecode::CallClosure(_)
             | Bytecode::Abort => {
                 // Invariants hold because all of the instructions above will force VM to break from the interpreter loop and thus not hit this code path.
                 unreachable!("control flow instruction encountered during type check")
@@ -1384,6 +1865,15 @@ impl Frame {
 
                 interpreter.operand_stack.push_ty(output_ty)?;
             },
+            Bytecode::PackClosure(fh_idx, mask) => {
+                let func = resolver.function_from_handle(*fh_idx);
+                Self::closure_type_transition(resolver, interpreter, &func, &[], *mask)?;
+            },
+            Bytecode::PackClosureGeneric(fi_idx, mask) => {
+                let func = resolver.function_from_instantiation(*fi_idx);
+                let func_ty_args = resolver.instantiate_generic_function(*fi_idx, ty_args)?;
+                Self::closure_type_transition(resolver, interpreter, &func, &func_ty_args, *mask)?;
+            },
             Bytecode::PackGeneric(idx) => {
                 let field_count = resolver.field_instantiation_count(*idx);
                 let args_ty = resolver.instantiate_generic_struct_fields(*idx, ty_args)?;
@@ -1974,6 +2464,28 @@ impl Frame {
                             .operand_stack
                             .push(Value::struct_(Struct::pack(args)))?;
                     },
+                    Bytecode::PackClosure(fh_idx, mask) => {
+                        let func = resolver.function_from_handle(*fh_idx);
+                        Self::pack_closure(resolver, interpreter, gas_meter, &func, vec![], *mask)?;
+                    },
+                    Bytecode::PackClosureGeneric(fi_idx, mask) => {
+                        let ty_args =
+                            resolver.instantiate_generic_function(*fi_idx, self.ty_args())?;
+                        let func = resolver.function_from_instantiation(*fi_idx);
+                        Self::pack_closure(
+                            resolver,
+                            interpreter,
+                            gas_meter,
+                            &func,
+                            ty_args,
+                            *mask,
+                        )?;
+                    },
+                    Bytecode::CallClosure(_sig_idx) => {
+                        gas_meter.charge_simple_instr(S::CallClosure)?;
+                        let closure = interpreter.operand_stack.pop_as::<Closure>()?;
+                        return Ok(ExitCode::CallClosure(closure));
+                    },
                     Bytecode::Unpack(_sd_idx) => {
                         let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
 
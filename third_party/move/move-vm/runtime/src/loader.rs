@@ -432,6 +432,14 @@ impl ModuleCache {
                 let def_idx = resolver(struct_name, &module_id)?;
                 Type::StructInstantiation(def_idx, type_parameters)
             },
+            SignatureToken::Function(args, results, abilities) => {
+                let make_types = |toks: &[SignatureToken]| {
+                    toks.iter()
+                        .map(|tok| Self::make_type_internal(module, tok, resolver))
+                        .collect::<PartialVMResult<Vec<_>>>()
+                };
+                Type::Function(make_types(args)?, make_types(results)?, *abilities)
+            },
         };
         Ok(res)
     }
@@ -531,6 +539,9 @@ impl ModuleCache {
             | Type::U16
             | Type::U32
             | Type::U256 => DepthFormula::constant(1),
+            // The depth of the values captured by a closure is not reflected in its type, and
+            // is checked when the closure is packed instead.
+            Type::Function(_, _, _) => DepthFormula::constant(1),
             Type::Vector(ty) | Type::Reference(ty) | Type::MutableReference(ty) => {
                 let mut inner = self.calculate_depth_of_type(ty, depth_cache)?;
                 inner.scale(1);
@@ -878,6 +889,20 @@ impl Loader {
                         .zip(expected_fields.iter())
                         .all(|types| Self::match_return_type(types.0, types.1, map))
             },
+            // For function types the abilities, arguments and results need to match
+            (
+                Type::Function(ret_args, ret_results, ret_abilities),
+                Type::Function(expected_args, expected_results, expected_abilities),
+            ) => {
+                ret_abilities == expected_abilities
+                    && ret_args.len() == expected_args.len()
+                    && ret_results.len() == expected_results.len()
+                    && ret_args
+                        .iter()
+                        .zip(expected_args.iter())
+                        .chain(ret_results.iter().zip(expected_results.iter()))
+                        .all(|types| Self::match_return_type(types.0, types.1, map))
+            },
             // For primitive types we need to assure the types match
             (Type::U8, Type::U8)
             | (Type::U16, Type::U16)
@@ -904,7 +929,8 @@ impl Loader {
             | (Type::StructInstantiation(_, _), _)
             | (Type::Vector(_), _)
             | (Type::MutableReference(_), _)
-            | (Type::Reference(_), _) => false,
+            | (Type::Reference(_), _)
+            | (Type::Function(_, _, _), _) => false,
         }
     }
 
@@ -971,6 +997,34 @@ impl Loader {
         ))
     }
 
+    // Entry point for runtime dispatch from native functions.
+    // Loading verifies the module if it was never loaded.
+    // Type parameters are checked by the caller, against the instantiation of the native function.
+    pub(crate) fn load_dispatch_target(
+        &self,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        data_store: &TransactionDataCache,
+    ) -> VMResult<Arc<Function>> {
+        let (_, func, _, _) =
+            self.load_function_without_type_args(module_id, function_name, data_store)?;
+        Ok(func)
+    }
+
+    // Entry point for calling closures.
+    // Loading verifies the module if it was never loaded.
+    // Type parameters are recorded by the closure and were checked when it was packed.
+    pub(crate) fn load_closure_target(
+        &self,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        data_store: &TransactionDataCache,
+    ) -> VMResult<Arc<Function>> {
+        let (_, func, _, _) =
+            self.load_function_without_type_args(module_id, function_name, data_store)?;
+        Ok(func)
+    }
+
     // Entry point for function execution (`MoveVM::execute_function`).
     // Loading verifies the module if it was never loaded.
     // Type parameters are checked as well after every type is loaded.
@@ -1008,6 +1062,20 @@ impl Loader {
         Ok((module, func, loaded))
     }
 
+    // Returns the visibility of a function and whether it is an entry function, reading them from
+    // the loaded module.
+    pub(crate) fn load_function_visibility(
+        &self,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        data_store: &TransactionDataCache,
+    ) -> VMResult<(Visibility, bool)> {
+        let (module, func, _, _) =
+            self.load_function_without_type_args(module_id, function_name, data_store)?;
+        let def = module.module().function_def_at(func.index());
+        Ok((def.visibility, def.is_entry))
+    }
+
     // Entry point for module publishing (`MoveVM::publish_module_bundle`).
     //
     // All modules in the bundle to be published must be loadable. This function performs all
@@ -1498,7 +1566,10 @@ impl Loader {
         // If that number is larger than MAX_TYPE_INSTANTIATION_NODES, refuse to construct this type.
         // This prevents constructing larger and lager types via struct instantiation.
         match ty {
-            Type::MutableReference(_) | Type::Reference(_) | Type::Vector(_) => {
+            Type::MutableReference(_)
+            | Type::Reference(_)
+            | Type::Vector(_)
+            | Type::Function(_, _, _) => {
                 if self.vm_config.type_size_limit
                     && self.count_type_nodes(ty) > MAX_TYPE_INSTANTIATION_NODES
                 {
@@ -1532,7 +1603,11 @@ impl Loader {
     // Verify the kind (constraints) of an instantiation.
     // Both function and script invocation use this function to verify correctness
     // of type arguments provided
-    fn verify_ty_args<'a, I>(&self, constraints: I, ty_args: &[Type]) -> PartialVMResult<()>
+    pub(crate) fn verify_ty_args<'a, I>(
+        &self,
+        constraints: I,
+        ty_args: &[Type],
+    ) -> PartialVMResult<()>
     where
         I: IntoIterator<Item = &'a AbilitySet>,
         I::IntoIter: ExactSizeIterator,
@@ -1591,6 +1666,7 @@ impl Loader {
             // Technically unreachable but, no point in erroring if we don't have to
             Type::Reference(_) | Type::MutableReference(_) => Ok(AbilitySet::REFERENCES),
             Type::Signer => Ok(AbilitySet::SIGNER),
+            Type::Function(_, _, abilities) => Ok(*abilities),
 
             Type::TyParam(_) => Err(PartialVMError::new(StatusCode::UNREACHABLE).with_message(
                 "Unexpected TyParam type after translating from TypeTag to Type".to_string(),
@@ -2110,7 +2186,8 @@ impl Module {
                             | Bytecode::VecPushBack(si)
                             | Bytecode::VecPopBack(si)
                             | Bytecode::VecUnpack(si, _)
-                            | Bytecode::VecSwap(si) => {
+                            | Bytecode::VecSwap(si)
+                            | Bytecode::CallClosure(si) => {
                                 if !single_signature_token_map.contains_key(si) {
                                     let ty = match module.signature_at(*si).0.get(0) {
                                         None => {
@@ -2385,7 +2462,8 @@ impl Script {
                 | Bytecode::VecPushBack(si)
                 | Bytecode::VecPopBack(si)
                 | Bytecode::VecUnpack(si, _)
-                | Bytecode::VecSwap(si) => {
+                | Bytecode::VecSwap(si)
+                | Bytecode::CallClosure(si) => {
                     if !single_signature_token_map.contains_key(si) {
                         let ty = match script.signature_at(*si).0.get(0) {
                             None => {
@@ -2629,6 +2707,23 @@ impl Function {
         self.def_is_friend_or_private
     }
 
+    /// Returns whether `target` may be dispatched to from this native function, i.e. called in
+    /// its place with all of its arguments but the last one.
+    ///
+    /// The target must be a public Move function. Signatures are compared before instantiation,
+    /// so both functions must declare the same type parameters, in the same order.
+    pub(crate) fn is_dispatch_compatible(&self, target: &Function) -> bool {
+        let parameters_match = match self.parameter_types.split_last() {
+            Some((_, parameter_types)) => parameter_types == target.parameter_types.as_slice(),
+            None => false,
+        };
+        !target.is_native()
+            && !target.is_friend_or_private()
+            && parameters_match
+            && self.return_types == target.return_types
+            && self.type_parameters == target.type_parameters
+    }
+
     pub(crate) fn get_native(&self) -> PartialVMResult<&UnboxedNativeFunction> {
         self.native.as_deref().ok_or_else(|| {
             PartialVMError::new(StatusCode::MISSING_DEPENDENCY)
@@ -2826,7 +2921,11 @@ impl Loader {
             Type::StructInstantiation(gidx, ty_args) => TypeTag::Struct(Box::new(
                 self.struct_gidx_to_type_tag(*gidx, ty_args, gas_context)?,
             )),
-            Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
+            // Function types cannot be type arguments, hence never need a type tag.
+            Type::Reference(_)
+            | Type::MutableReference(_)
+            | Type::TyParam(_)
+            | Type::Function(_, _, _) => {
                 return Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
                         .with_message(format!("no type tag for {:?}", ty)),
@@ -2969,6 +3068,10 @@ impl Loader {
                     self.struct_gidx_to_type_layout(*gidx, ty_args, count, depth)?,
                 )
             },
+            Type::Function(_, _, _) => {
+                *count += 1;
+                MoveTypeLayout::Function
+            },
             Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
                 return Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
@@ -3071,6 +3174,7 @@ impl Loader {
             Type::StructInstantiation(gidx, ty_args) => MoveTypeLayout::Struct(
                 self.struct_gidx_to_fully_annotated_layout(*gidx, ty_args, count, depth)?,
             ),
+            Type::Function(_, _, _) => MoveTypeLayout::Function,
             Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
                 return Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
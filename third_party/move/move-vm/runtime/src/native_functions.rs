@@ -12,10 +12,10 @@ use move_binary_format::errors::{
 use move_core_types::{
     account_address::AccountAddress,
     gas_algebra::{InternalGas, NumBytes},
-    identifier::Identifier,
-    language_storage::TypeTag,
+    identifier::{IdentStr, Identifier},
+    language_storage::{ModuleId, TypeTag},
     value::MoveTypeLayout,
-    vm_status::StatusCode,
+    vm_status::{StatusCode, StatusType},
 };
 use move_vm_types::{
     loaded_data::runtime_types::Type, natives::function::NativeResult, values::Value,
@@ -168,4 +168,26 @@ impl<'a, 'b, 'c> NativeContext<'a, 'b, 'c> {
     pub fn gas_balance(&self) -> InternalGas {
         self.gas_balance
     }
+
+    /// Returns whether the function `target` may be dispatched to from the native function
+    /// `native`, i.e. whether `native` may return `NativeResult::CallFunction` for it.
+    ///
+    /// Functions which cannot be loaded, e.g. because they do not exist, are not compatible.
+    pub fn is_dispatch_compatible(
+        &self,
+        native: (&ModuleId, &IdentStr),
+        target: (&ModuleId, &IdentStr),
+    ) -> PartialVMResult<bool> {
+        let loader = self.resolver.loader();
+        let load = |(module_id, function_name): (&ModuleId, &IdentStr)| {
+            loader.load_dispatch_target(module_id, function_name, &*self.data_store)
+        };
+        match load(native).and_then(|native| Ok((native, load(target)?))) {
+            Ok((native, target)) => Ok(native.is_dispatch_compatible(&target)),
+            Err(err) if err.status_type() == StatusType::InvariantViolation => {
+                Err(err.to_partial())
+            },
+            Err(_) => Ok(false),
+        }
+    }
 }
@@ -9,7 +9,7 @@ use crate::{
 use move_binary_format::{
     compatibility::Compatibility,
     errors::*,
-    file_format::{AbilitySet, LocalIndex},
+    file_format::{AbilitySet, LocalIndex, Visibility},
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -370,6 +370,20 @@ impl<'r, 'l> Session<'r, 'l> {
         Ok(instantiation)
     }
 
+    /// Returns the visibility of a function and whether it is an entry function. The module of the
+    /// function is loaded into the loader's cache if it is not there yet.
+    pub fn load_function_visibility(
+        &self,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+    ) -> VMResult<(Visibility, bool)> {
+        self.move_vm.runtime.loader().load_function_visibility(
+            module_id,
+            function_name,
+            &self.data_cache,
+        )
+    }
+
     pub fn load_type(&self, type_tag: &TypeTag) -> VMResult<Type> {
         self.move_vm
             .runtime
@@ -10,8 +10,8 @@
 use move_binary_format::{
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        Bytecode, CodeOffset, ConstantPoolIndex, FieldHandleIndex, FieldInstantiationIndex,
-        FunctionHandleIndex, FunctionInstantiationIndex, SignatureIndex,
+        Bytecode, ClosureMask, CodeOffset, ConstantPoolIndex, FieldHandleIndex,
+        FieldInstantiationIndex, FunctionHandleIndex, FunctionInstantiationIndex, SignatureIndex,
         StructDefInstantiationIndex, StructDefinitionIndex,
     },
     file_format_common::{instruction_key, Opcodes},
@@ -302,6 +302,24 @@ impl<'b> GasMeter for GasStatus<'b> {
         )
     }
 
+    fn charge_pack_closure(
+        &mut self,
+        is_generic: bool,
+        captured: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let captured_count = AbstractMemorySize::new(captured.len() as u64);
+        self.charge_instr_with_size(
+            if is_generic {
+                Opcodes::PACK_CLOSURE_GENERIC
+            } else {
+                Opcodes::PACK_CLOSURE
+            },
+            captured.fold(captured_count, |acc, val| {
+                acc + val.legacy_abstract_memory_size()
+            }),
+        )
+    }
+
     fn charge_unpack(
         &mut self,
         is_generic: bool,
@@ -647,6 +665,15 @@ pub fn zero_cost_instruction_table() -> Vec<(Bytecode, GasCost)> {
         (CastU16, GasCost::new(0, 0)),
         (CastU32, GasCost::new(0, 0)),
         (CastU256, GasCost::new(0, 0)),
+        (
+            PackClosure(FunctionHandleIndex::new(0), ClosureMask::new(0)),
+            GasCost::new(0, 0),
+        ),
+        (
+            PackClosureGeneric(FunctionInstantiationIndex::new(0), ClosureMask::new(0)),
+            GasCost::new(0, 0),
+        ),
+        (CallClosure(SignatureIndex::new(0)), GasCost::new(0, 0)),
     ]
 }
 
@@ -780,6 +807,15 @@ pub fn bytecode_instruction_costs() -> Vec<(Bytecode, GasCost)> {
         (CastU16, GasCost::new(2, 1)),
         (CastU32, GasCost::new(2, 1)),
         (CastU256, GasCost::new(2, 1)),
+        (
+            PackClosure(FunctionHandleIndex::new(0), ClosureMask::new(0)),
+            GasCost::new(2, 1),
+        ),
+        (
+            PackClosureGeneric(FunctionInstantiationIndex::new(0), ClosureMask::new(0)),
+            GasCost::new(2, 1),
+        ),
+        (CallClosure(SignatureIndex::new(0)), GasCost::new(132, 1)),
     ]
 }
 
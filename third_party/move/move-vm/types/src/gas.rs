@@ -64,6 +64,8 @@ pub enum SimpleInstruction {
     CastU16,
     CastU32,
     CastU256,
+
+    CallClosure,
 }
 
 impl SimpleInstruction {
@@ -121,6 +123,8 @@ impl SimpleInstruction {
             CastU16 => CAST_U16,
             CastU32 => CAST_U32,
             CastU256 => CAST_U256,
+
+            CallClosure => CALL_CLOSURE,
         }
     }
 }
@@ -181,6 +185,13 @@ pub trait GasMeter {
         args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
     ) -> PartialVMResult<()>;
 
+    /// Charges for packing a closure, `captured` being the values captured by it.
+    fn charge_pack_closure(
+        &mut self,
+        is_generic: bool,
+        captured: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()>;
+
     fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()>;
 
     fn charge_write_ref(
@@ -388,6 +399,14 @@ impl GasMeter for UnmeteredGasMeter {
         Ok(())
     }
 
+    fn charge_pack_closure(
+        &mut self,
+        _is_generic: bool,
+        _captured: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
     fn charge_read_ref(&mut self, _val: impl ValueView) -> PartialVMResult<()> {
         Ok(())
     }
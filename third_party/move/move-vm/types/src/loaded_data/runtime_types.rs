@@ -147,6 +147,8 @@ pub enum Type {
     U16,
     U32,
     U256,
+    /// A function value taking and returning the given types (added in bytecode version v7)
+    Function(Vec<Type>, Vec<Type>, AbilitySet),
 }
 
 impl Type {
@@ -188,6 +190,17 @@ impl Type {
                 }
                 Type::StructInstantiation(*def_idx, inst)
             },
+            Type::Function(args, results, abilities) => {
+                let mut subst_args = vec![];
+                for ty in args {
+                    subst_args.push(ty.apply_subst(subst, depth + 1)?)
+                }
+                let mut subst_results = vec![];
+                for ty in results {
+                    subst_results.push(ty.apply_subst(subst, depth + 1)?)
+                }
+                Type::Function(subst_args, subst_results, *abilities)
+            },
         };
         Ok(res)
     }
@@ -227,6 +240,10 @@ impl Type {
             StructInstantiation(_, tys) => tys
                 .iter()
                 .fold(Self::LEGACY_BASE_MEMORY_SIZE, |acc, ty| acc + ty.size()),
+            Function(args, results, _) => args
+                .iter()
+                .chain(results)
+                .fold(Self::LEGACY_BASE_MEMORY_SIZE, |acc, ty| acc + ty.size()),
         }
     }
 
@@ -252,7 +269,11 @@ impl Type {
                 )
             },
             // Not allowed/Not meaningful
-            S::TypeParameter(_) | S::Reference(_) | S::MutableReference(_) | S::Signer => {
+            S::TypeParameter(_)
+            | S::Reference(_)
+            | S::MutableReference(_)
+            | S::Signer
+            | S::Function(..) => {
                 return Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
                         .with_message("Unable to load const type signature".to_string()),
@@ -17,9 +17,10 @@
 //! This module contains the declarations and utilities to implement a native
 //! function.
 
-use crate::values::Value;
+use crate::{loaded_data::runtime_types::Type, values::Value};
 pub use move_binary_format::errors::{PartialVMError, PartialVMResult};
 pub use move_core_types::{gas_algebra::InternalGas, vm_status::StatusCode};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
 use smallvec::{smallvec, SmallVec};
 
 /// Result of a native function execution requires charges for execution cost.
@@ -43,6 +44,19 @@ pub enum NativeResult {
     OutOfGas {
         partial_cost: InternalGas,
     },
+    /// Instructs the VM to call the given Move function with the given arguments in place of
+    /// the native function, whose return values become the return values of the native.
+    ///
+    /// The target must be a public function whose signature matches the signature of the
+    /// native function without its last parameter. It must not belong to a module that is
+    /// already active on the call stack.
+    CallFunction {
+        cost: InternalGas,
+        module_name: ModuleId,
+        func_name: Identifier,
+        ty_args: Vec<Type>,
+        args: SmallVec<[Value; 1]>,
+    },
 }
 
 impl NativeResult {
@@ -226,3 +226,59 @@ fn test_vm_value_vector_u64_casting() {
         Value::vector_u64([1, 2, 3]).value_as::<Vec<u64>>().unwrap()
     );
 }
+
+#[test]
+fn closure_serialization_round_trip() -> PartialVMResult<()> {
+    use move_binary_format::file_format::ClosureMask;
+    use move_core_types::{
+        identifier::Identifier,
+        language_storage::ModuleId,
+        value::{MoveClosure, MoveTypeLayout, MoveValue},
+    };
+
+    let module_id = ModuleId::new(AccountAddress::ONE, Identifier::new("m").unwrap());
+    let closure = |ty_args: Vec<Type>| {
+        Value::closure(Closure::pack(
+            module_id.clone(),
+            Identifier::new("f").unwrap(),
+            ty_args,
+            ClosureMask::new(0b101),
+            vec![Value::u64(1), Value::vector_u8([2, 3])],
+            vec![
+                MoveTypeLayout::U64,
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            ],
+        ))
+    };
+
+    let val = closure(vec![]);
+    assert!(val.copy_value()?.equals(&val)?);
+
+    let blob = val.simple_serialize(&MoveTypeLayout::Function).unwrap();
+    let deserialized = Value::simple_deserialize(&blob, &MoveTypeLayout::Function).unwrap();
+    assert!(deserialized.equals(&val)?);
+
+    // The runtime representation agrees with the one of `MoveValue`.
+    assert_eq!(
+        MoveValue::simple_deserialize(&blob, &MoveTypeLayout::Function).unwrap(),
+        MoveValue::Closure(Box::new(MoveClosure {
+            module_id: module_id.clone(),
+            fun_id: Identifier::new("f").unwrap(),
+            mask: 0b101,
+            captured: vec![
+                (MoveTypeLayout::U64, MoveValue::U64(1)),
+                (
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+                    MoveValue::Vector(vec![MoveValue::U8(2), MoveValue::U8(3)]),
+                ),
+            ],
+        }))
+    );
+
+    // Type arguments are not serialized, so generic closures cannot be.
+    assert!(closure(vec![Type::U64])
+        .simple_serialize(&MoveTypeLayout::Function)
+        .is_none());
+
+    Ok(())
+}
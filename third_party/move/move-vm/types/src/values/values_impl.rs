@@ -8,12 +8,14 @@ use crate::{
 };
 use move_binary_format::{
     errors::*,
-    file_format::{Constant, SignatureToken},
+    file_format::{ClosureMask, Constant, SignatureToken},
 };
 use move_core_types::{
     account_address::AccountAddress,
     effects::Op,
     gas_algebra::AbstractMemorySize,
+    identifier::{IdentStr, Identifier},
+    language_storage::ModuleId,
     u256,
     value::{MoveStructLayout, MoveTypeLayout},
     vm_status::{sub_status::NFE_VECTOR_ERROR_BASE, StatusCode},
@@ -53,6 +55,21 @@ enum ValueImpl {
 
     ContainerRef(ContainerRef),
     IndexedRef(IndexedRef),
+
+    Closure(Box<Closure>),
+}
+
+/// A function value: a function together with the arguments captured when the closure was
+/// packed. The remaining arguments are provided when the closure is called.
+#[derive(Debug)]
+pub struct Closure {
+    module_id: ModuleId,
+    fun_name: Identifier,
+    ty_args: Vec<Type>,
+    mask: ClosureMask,
+    captured: Vec<ValueImpl>,
+    /// Layouts of the captured values, needed to serialize the closure.
+    captured_layouts: Vec<MoveTypeLayout>,
 }
 
 /// A container is a collection of values. It is used to represent data structures like a
@@ -359,6 +376,25 @@ impl ValueImpl {
             // When cloning a container, we need to make sure we make a deep
             // copy of the data instead of a shallow copy of the Rc.
             Container(c) => Container(c.copy_value()?),
+
+            Closure(c) => Closure(Box::new(c.copy_value()?)),
+        })
+    }
+}
+
+impl Closure {
+    fn copy_value(&self) -> PartialVMResult<Self> {
+        Ok(Self {
+            module_id: self.module_id.clone(),
+            fun_name: self.fun_name.clone(),
+            ty_args: self.ty_args.clone(),
+            mask: self.mask,
+            captured: self
+                .captured
+                .iter()
+                .map(|v| v.copy_value())
+                .collect::<PartialVMResult<_>>()?,
+            captured_layouts: self.captured_layouts.clone(),
         })
     }
 }
@@ -475,6 +511,8 @@ impl ValueImpl {
 
             (ContainerRef(l), ContainerRef(r)) => l.equals(r)?,
             (IndexedRef(l), IndexedRef(r)) => l.equals(r)?,
+
+            (Closure(l), Closure(r)) => l.equals(r)?,
             (Invalid, _)
             | (U8(_), _)
             | (U16(_), _)
@@ -486,7 +524,8 @@ impl ValueImpl {
             | (Address(_), _)
             | (Container(_), _)
             | (ContainerRef(_), _)
-            | (IndexedRef(_), _) => {
+            | (IndexedRef(_), _)
+            | (Closure(_), _) => {
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
                     .with_message(format!("cannot compare values: {:?}, {:?}", self, other)))
             },
@@ -496,6 +535,25 @@ impl ValueImpl {
     }
 }
 
+impl Closure {
+    fn equals(&self, other: &Self) -> PartialVMResult<bool> {
+        if self.module_id != other.module_id
+            || self.fun_name != other.fun_name
+            || self.ty_args != other.ty_args
+            || self.mask != other.mask
+            || self.captured.len() != other.captured.len()
+        {
+            return Ok(false);
+        }
+        for (v1, v2) in self.captured.iter().zip(other.captured.iter()) {
+            if !v1.equals(v2)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
 impl Container {
     fn equals(&self, other: &Self) -> PartialVMResult<bool> {
         use Container::*;
@@ -948,7 +1006,8 @@ impl Locals {
             | ValueImpl::U128(_)
             | ValueImpl::U256(_)
             | ValueImpl::Bool(_)
-            | ValueImpl::Address(_) => Ok(Value(ValueImpl::IndexedRef(IndexedRef {
+            | ValueImpl::Address(_)
+            | ValueImpl::Closure(_) => Ok(Value(ValueImpl::IndexedRef(IndexedRef {
                 container_ref: ContainerRef::Local(Container::Locals(Rc::clone(&self.0))),
                 idx,
             }))),
@@ -1130,6 +1189,10 @@ impl Value {
         )))
     }
 
+    pub fn closure(c: Closure) -> Self {
+        Self(ValueImpl::Closure(Box::new(c)))
+    }
+
     pub fn struct_(s: Struct) -> Self {
         Self(ValueImpl::Container(Container::Struct(Rc::new(
             RefCell::new(s.fields),
@@ -1283,6 +1346,16 @@ impl VMValueCast<Struct> for Value {
     }
 }
 
+impl VMValueCast<Closure> for Value {
+    fn cast(self) -> PartialVMResult<Closure> {
+        match self.0 {
+            ValueImpl::Closure(c) => Ok(*c),
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to closure", v,))),
+        }
+    }
+}
+
 impl VMValueCast<StructRef> for Value {
     fn cast(self) -> PartialVMResult<StructRef> {
         Ok(StructRef(VMValueCast::cast(self)?))
@@ -1970,10 +2043,13 @@ fn check_elem_layout(ty: &Type, v: &Container) -> PartialVMResult<()> {
         | (Type::Signer, Container::Vec(_))
         | (Type::StructInstantiation(_, _), Container::Vec(_)) => Ok(()),
 
-        (Type::Reference(_), _) | (Type::MutableReference(_), _) | (Type::TyParam(_), _) => Err(
-            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
-                .with_message(format!("invalid type param for vector: {:?}", ty)),
-        ),
+        (Type::Reference(_), _)
+        | (Type::MutableReference(_), _)
+        | (Type::TyParam(_), _)
+        | (Type::Function(_, _, _), _) => Err(PartialVMError::new(
+            StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+        )
+        .with_message(format!("invalid type param for vector: {:?}", ty))),
 
         (Type::U8, _)
         | (Type::U64, _)
@@ -2202,7 +2278,10 @@ impl Vector {
                 )))))
             },
 
-            Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
+            Type::Reference(_)
+            | Type::MutableReference(_)
+            | Type::TyParam(_)
+            | Type::Function(_, _, _) => {
                 return Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
                         .with_message(format!("invalid type param for vector: {:?}", type_param)),
@@ -2370,6 +2449,7 @@ impl ValueImpl {
             IndexedRef(r) => r.legacy_size(),
             // TODO: in case the borrow fails the VM will panic.
             Container(c) => c.legacy_size(),
+            Closure(c) => Struct::legacy_size_impl(&c.captured),
         }
     }
 }
@@ -2431,6 +2511,56 @@ impl Struct {
     }
 }
 
+/***************************************************************************************
+*
+* Closure
+*
+*   Closures are packed from a function and the values captured for a subset of its
+*   parameters, as described by the closure mask.
+*
+**************************************************************************************/
+
+impl Closure {
+    pub fn pack<I: IntoIterator<Item = Value>>(
+        module_id: ModuleId,
+        fun_name: Identifier,
+        ty_args: Vec<Type>,
+        mask: ClosureMask,
+        captured: I,
+        captured_layouts: Vec<MoveTypeLayout>,
+    ) -> Self {
+        Self {
+            module_id,
+            fun_name,
+            ty_args,
+            mask,
+            captured: captured.into_iter().map(|v| v.0).collect(),
+            captured_layouts,
+        }
+    }
+
+    pub fn module_id(&self) -> &ModuleId {
+        &self.module_id
+    }
+
+    pub fn fun_name(&self) -> &IdentStr {
+        &self.fun_name
+    }
+
+    pub fn ty_args(&self) -> &[Type] {
+        &self.ty_args
+    }
+
+    pub fn mask(&self) -> ClosureMask {
+        self.mask
+    }
+
+    /// Consumes the closure, returning its captured values.
+    pub fn into_captured(self) -> impl Iterator<Item = Value> {
+        self.captured.into_iter().map(Value)
+    }
+}
+
 /***************************************************************************************
  *
  * Global Value Operations
@@ -2631,7 +2761,28 @@ impl Display for ValueImpl {
 
             Self::ContainerRef(r) => write!(f, "{}", r),
             Self::IndexedRef(r) => write!(f, "{}", r),
+
+            Self::Closure(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+impl Display for Closure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Closure({}::{}", self.module_id, self.fun_name)?;
+        if !self.ty_args.is_empty() {
+            write!(f, "<")?;
+            for (i, ty) in self.ty_args.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{:?}", ty)?;
+            }
+            write!(f, ">")?;
         }
+        write!(f, ", mask: {:#b}, ", self.mask.bits())?;
+        display_list_of_items(&self.captured, f)?;
+        write!(f, ")")
     }
 }
 
@@ -2799,9 +2950,16 @@ pub mod debug {
 
             ValueImpl::ContainerRef(r) => print_container_ref(buf, r),
             ValueImpl::IndexedRef(r) => print_indexed_ref(buf, r),
+
+            ValueImpl::Closure(c) => print_closure(buf, c),
         }
     }
 
+    fn print_closure<B: Write>(buf: &mut B, c: &Closure) -> PartialVMResult<()> {
+        debug_write!(buf, "{}::{}", c.module_id, c.fun_name)?;
+        print_list(buf, "(", c.captured.iter(), print_value_impl, ")")
+    }
+
     fn print_list<'a, B, I, X, F>(
         buf: &mut B,
         begin: &str,
@@ -3046,6 +3204,8 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveTypeLayout, ValueIm
                 .serialize(serializer)
             },
 
+            (MoveTypeLayout::Function, ValueImpl::Closure(c)) => c.serialize(serializer),
+
             (ty, val) => Err(invariant_violation::<S>(format!(
                 "cannot serialize value {:?} as {:?}",
                 val, ty
@@ -3054,6 +3214,43 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveTypeLayout, ValueIm
     }
 }
 
+impl serde::Serialize for Closure {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Type arguments are not part of the serialized representation, which is sound
+        // because the bytecode verifier never grants `store` to generic closures.
+        if !self.ty_args.is_empty() {
+            return Err(invariant_violation::<S>(format!(
+                "cannot serialize generic closure {}",
+                self
+            )));
+        }
+        if self.captured.len() != self.captured_layouts.len() {
+            return Err(invariant_violation::<S>(format!(
+                "cannot serialize closure {} -- number of captured layouts mismatch",
+                self
+            )));
+        }
+        let mut t = serializer.serialize_tuple(4)?;
+        t.serialize_element(&self.module_id)?;
+        t.serialize_element(&self.fun_name)?;
+        t.serialize_element(&self.mask.bits())?;
+        t.serialize_element(&CapturedValues(self))?;
+        t.end()
+    }
+}
+
+struct CapturedValues<'a>(&'a Closure);
+
+impl<'a> serde::Serialize for CapturedValues<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut t = serializer.serialize_seq(Some(self.0.captured.len()))?;
+        for (layout, val) in self.0.captured_layouts.iter().zip(self.0.captured.iter()) {
+            t.serialize_element(&(layout, AnnotatedValue { layout, val }))?;
+        }
+        t.end()
+    }
+}
+
 impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveStructLayout, Vec<ValueImpl>> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let values = &self.val;
@@ -3141,10 +3338,118 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveTypeLayout> {
                 };
                 Ok(Value(ValueImpl::Container(container)))
             },
+
+            L::Function => Ok(Value(ValueImpl::Closure(Box::new(
+                deserializer.deserialize_tuple(4, ClosureVisitor)?,
+            )))),
         }
     }
 }
 
+struct ClosureVisitor;
+
+impl<'d> serde::de::Visitor<'d> for ClosureVisitor {
+    type Value = Closure;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Closure")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'d>,
+    {
+        let module_id = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let fun_name = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        let mask = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+        let (captured_layouts, captured) = seq
+            .next_element_seed(CapturedValuesVisitor)?
+            .ok_or_else(|| A::Error::invalid_length(3, &self))?;
+        Ok(Closure {
+            module_id,
+            fun_name,
+            ty_args: vec![],
+            mask: ClosureMask::new(mask),
+            captured,
+            captured_layouts,
+        })
+    }
+}
+
+struct CapturedValuesVisitor;
+
+impl<'d> serde::de::DeserializeSeed<'d> for CapturedValuesVisitor {
+    type Value = (Vec<MoveTypeLayout>, Vec<ValueImpl>);
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'d> serde::de::Visitor<'d> for CapturedValuesVisitor {
+    type Value = (Vec<MoveTypeLayout>, Vec<ValueImpl>);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Captured values")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'d>,
+    {
+        let mut layouts = vec![];
+        let mut vals = vec![];
+        while let Some((layout, val)) = seq.next_element_seed(CapturedValueVisitor)? {
+            layouts.push(layout);
+            vals.push(val);
+        }
+        Ok((layouts, vals))
+    }
+}
+
+struct CapturedValueVisitor;
+
+impl<'d> serde::de::DeserializeSeed<'d> for CapturedValueVisitor {
+    type Value = (MoveTypeLayout, ValueImpl);
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'d> serde::de::Visitor<'d> for CapturedValueVisitor {
+    type Value = (MoveTypeLayout, ValueImpl);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Captured value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'d>,
+    {
+        let layout: MoveTypeLayout = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let val = seq
+            .next_element_seed(SeedWrapper { layout: &layout })?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok((layout, val.0))
+    }
+}
+
 impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveStructLayout> {
     type Value = Struct;
 
@@ -3234,7 +3539,10 @@ impl Value {
             // Not yet supported
             S::Struct(_) | S::StructInstantiation(_, _) => return None,
             // Not allowed/Not meaningful
-            S::TypeParameter(_) | S::Reference(_) | S::MutableReference(_) => return None,
+            S::TypeParameter(_)
+            | S::Reference(_)
+            | S::MutableReference(_)
+            | S::Function(_, _, _) => return None,
         })
     }
 
@@ -3362,6 +3670,14 @@ impl ValueImpl {
 
             ContainerRef(r) => r.visit_impl(visitor, depth),
             IndexedRef(r) => r.visit_impl(visitor, depth),
+
+            Closure(c) => {
+                if visitor.visit_closure(depth, c.captured.len()) {
+                    for val in c.captured.iter() {
+                        val.visit_impl(visitor, depth + 1);
+                    }
+                }
+            },
         }
     }
 }
@@ -138,6 +138,12 @@ pub trait ValueVisitor {
 
     fn visit_ref(&mut self, depth: usize, is_global: bool) -> bool;
 
+    /// Visits a closure with `len` captured values. By default a closure is treated like
+    /// a struct whose fields are the captured values.
+    fn visit_closure(&mut self, depth: usize, len: usize) -> bool {
+        self.visit_struct(depth, len)
+    }
+
     fn visit_vec_u8(&mut self, depth: usize, vals: &[u8]) {
         self.visit_vec(depth, vals.len());
         for val in vals {
@@ -60,7 +60,8 @@ impl AbstractValue {
                 | SignatureToken::MutableReference(_)
                 | SignatureToken::Signer
                 | SignatureToken::Vector(_)
-                | SignatureToken::TypeParameter(_) => false,
+                | SignatureToken::TypeParameter(_)
+                | SignatureToken::Function(..) => false,
                 SignatureToken::Bool
                 | SignatureToken::Address
                 | SignatureToken::U8
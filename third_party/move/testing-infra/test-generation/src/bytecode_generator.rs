@@ -981,7 +981,8 @@ impl<'a> BytecodeGenerator<'a> {
             | SignatureToken::Vector(_)
             | SignatureToken::Reference(_)
             | SignatureToken::MutableReference(_)
-            | SignatureToken::TypeParameter(_) => {
+            | SignatureToken::TypeParameter(_)
+            | SignatureToken::Function(..) => {
                 unimplemented!("Unsupported inhabitation. Type: {:#?}", token)
             },
         }
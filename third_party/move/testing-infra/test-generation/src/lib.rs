@@ -111,6 +111,7 @@ fn run_vm(module: CompiledModule) -> Result<(), VMStatus> {
             | SignatureToken::Reference(_)
             | SignatureToken::MutableReference(_)
             | SignatureToken::TypeParameter(_)
+            | SignatureToken::Function(..)
             | SignatureToken::U16
             | SignatureToken::U32
             | SignatureToken::U256 => unimplemented!("Unsupported argument type: {:#?}", sig_tok),
@@ -503,6 +504,7 @@ pub(crate) fn get_type_actuals_from_reference(
         | Struct(_)
         | StructInstantiation(_, _)
         | TypeParameter(_)
+        | Function(..)
         | U16
         | U32
         | U256 => None,
@@ -617,5 +617,13 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
         | Bytecode::VecPopBack(_)
         | Bytecode::VecUnpack(..)
         | Bytecode::VecSwap(_) => unimplemented!("Vector bytecode not supported yet"),
+        // TODO: implement summaries for closure-related instructions. Until then the
+        // generator never selects them.
+        Bytecode::PackClosure(..) | Bytecode::PackClosureGeneric(..) | Bytecode::CallClosure(_) => {
+            Summary {
+                preconditions: vec![state_never!()],
+                effects: Effects::NoTyParams(vec![]),
+            }
+        },
     }
 }
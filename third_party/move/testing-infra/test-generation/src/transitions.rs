@@ -233,7 +233,8 @@ pub fn stack_top_is_castable_to(state: &AbstractState, typ: SignatureToken) -> b
             | SignatureToken::StructInstantiation(_, _)
             | SignatureToken::Reference(_)
             | SignatureToken::MutableReference(_)
-            | SignatureToken::TypeParameter(_) => false,
+            | SignatureToken::TypeParameter(_)
+            | SignatureToken::Function(..) => false,
         }
 }
 
@@ -341,6 +342,7 @@ pub fn stack_ref_polymorphic_eq(state: &AbstractState, index1: usize, index2: us
                 | SignatureToken::Struct(_)
                 | SignatureToken::StructInstantiation(_, _)
                 | SignatureToken::TypeParameter(_)
+                | SignatureToken::Function(..)
                 | SignatureToken::U16
                 | SignatureToken::U32
                 | SignatureToken::U256 => return false,
@@ -569,6 +571,7 @@ pub fn stack_has_struct(state: &AbstractState, struct_index: StructDefinitionInd
                 | SignatureToken::Reference(_)
                 | SignatureToken::MutableReference(_)
                 | SignatureToken::TypeParameter(_)
+                | SignatureToken::Function(..)
                 | SignatureToken::U16
                 | SignatureToken::U32
                 | SignatureToken::U256 => return false,
@@ -668,6 +671,7 @@ pub fn stack_has_reference(state: &AbstractState, index: usize, mutability: Muta
                 | SignatureToken::Struct(_)
                 | SignatureToken::StructInstantiation(_, _)
                 | SignatureToken::TypeParameter(_)
+                | SignatureToken::Function(..)
                 | SignatureToken::U16
                 | SignatureToken::U32
                 | SignatureToken::U256 => return false,
@@ -765,6 +769,7 @@ pub fn stack_unpack_struct_instantiation(
             | SignatureToken::Reference(_)
             | SignatureToken::MutableReference(_)
             | SignatureToken::TypeParameter(_)
+            | SignatureToken::Function(..)
             | SignatureToken::U16
             | SignatureToken::U32
             | SignatureToken::U256 => {
@@ -893,6 +898,7 @@ pub fn register_dereference(state: &AbstractState) -> Result<AbstractState, VMEr
             | SignatureToken::Struct(_)
             | SignatureToken::StructInstantiation(_, _)
             | SignatureToken::TypeParameter(_)
+            | SignatureToken::Function(..)
             | SignatureToken::U16
             | SignatureToken::U32
             | SignatureToken::U256 => Err(VMError::new(
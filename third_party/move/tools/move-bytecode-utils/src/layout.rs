@@ -30,6 +30,10 @@ const ADDRESS: &str = "AccountAddress";
 /// Name of the Move `signer` type in the serde registry
 const SIGNER: &str = "Signer";
 
+/// Name of Move function values (closures) in the serde registry. Captured values are
+/// self-describing, so no static layout is registered for this name.
+const CLOSURE: &str = "Closure";
+
 /// Name of the Move `u256` type in the serde registry
 const U256_SERDE_NAME: &str = "u256";
 
@@ -169,6 +173,7 @@ impl<'a, T: GetModule> SerdeLayoutBuilder<'a, T> {
                 }
             },
             TypeParameter(i) => input_type_args[*i as usize].clone(),
+            Function { .. } => Format::TypeName(CLOSURE.to_string()),
             Reference(_) | MutableReference(_) => unreachable!(), // structs cannot store references
         })
     }
@@ -415,6 +420,7 @@ impl TypeLayoutBuilder {
             U256 => MoveTypeLayout::U256,
             Address => MoveTypeLayout::Address,
             Signer => bail!("Type layouts cannot contain signer"),
+            Function(..) => MoveTypeLayout::Function,
             Reference(_) | MutableReference(_) => bail!("Type layouts cannot contain references"),
         })
     }
@@ -26,7 +26,7 @@ impl Build {
 
         match architecture {
             Architecture::Move | Architecture::AsyncMove => {
-                config.compile_package(&rerooted_path, &mut std::io::stdout())?;
+                config.compile_package_and_update_lock(&rerooted_path, &mut std::io::stdout())?;
             },
 
             Architecture::Ethereum => {
@@ -5,8 +5,10 @@ use super::reroot_path;
 use crate::NativeFunctionRecord;
 use anyhow::Result;
 use clap::*;
+use move_binary_format::access::ModuleAccess;
 use move_command_line_common::files::{FileHash, MOVE_COVERAGE_MAP_EXTENSION};
 use move_compiler::{
+    compiled_unit::{CompiledUnit, NamedCompiledModule},
     diagnostics::{self, codes::Severity},
     shared::{NumberFormat, NumericalAddress},
     unit_test::{plan_builder::construct_test_plan, TestPlan},
@@ -17,7 +19,14 @@ use move_package::{
     compilation::{build_plan::BuildPlan, compiled_package::unimplemented_v2_driver},
     BuildConfig, CompilerConfig,
 };
-use move_unit_test::UnitTestingConfig;
+use move_unit_test::{
+    fuzz::DEFAULT_FUZZ_ITERATIONS,
+    mutation::{
+        run_mutation_tests, MutationReport, MutationTestingConfig, DEFAULT_MUTANT_EXECUTION_BOUND,
+    },
+    test_runner::GasProfilingMeter,
+    UnitTestingConfig,
+};
 use move_vm_test_utils::gas_schedule::CostTable;
 // if unix
 #[cfg(target_family = "unix")]
@@ -26,11 +35,12 @@ use std::os::unix::prelude::ExitStatusExt;
 #[cfg(target_family = "windows")]
 use std::os::windows::process::ExitStatusExt;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     io::Write,
     path::{Path, PathBuf},
     process::ExitStatus,
+    sync::Arc,
 };
 // if not windows nor unix
 #[cfg(not(any(target_family = "windows", target_family = "unix")))]
@@ -79,6 +89,23 @@ pub struct Test {
     /// Collect coverage information for later use with the various `move coverage` subcommands
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
+    /// Seed for the inputs generated for tests with parameters not assigned in their test
+    /// attribute. A random seed is used if none is given; it is reported for failing tests.
+    #[clap(long = "seed")]
+    pub seed: Option<u64>,
+    /// Number of generated inputs each test with unassigned parameters is run with
+    #[clap(long = "fuzz_iterations", default_value_t = DEFAULT_FUZZ_ITERATIONS)]
+    pub fuzz_iterations: u64,
+    /// Record the gas used by each test in this file, and fail if the gas used by a test exceeds
+    /// the recorded amount by more than the tolerance
+    #[clap(long = "gas_snapshot")]
+    pub gas_snapshot: Option<PathBuf>,
+    /// Percentage by which the gas used by a test may exceed its gas snapshot
+    #[clap(long = "gas_snapshot_tolerance", default_value_t = 0)]
+    pub gas_snapshot_tolerance: u64,
+    /// Overwrite the gas snapshot with the gas used in this run, even where it increased
+    #[clap(long = "update_gas_snapshot")]
+    pub update_gas_snapshot: bool,
 
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
@@ -107,6 +134,11 @@ impl Test {
             check_stackless_vm,
             verbose_mode,
             compute_coverage,
+            seed,
+            fuzz_iterations,
+            gas_snapshot,
+            gas_snapshot_tolerance,
+            update_gas_snapshot,
             #[cfg(feature = "evm-backend")]
             evm,
         } = self;
@@ -120,6 +152,11 @@ impl Test {
             check_stackless_vm,
             verbose: verbose_mode,
             ignore_compile_warnings,
+            seed,
+            fuzz_iterations,
+            gas_snapshot,
+            gas_snapshot_tolerance,
+            update_gas_snapshot,
             #[cfg(feature = "evm-backend")]
             evm,
 
@@ -150,15 +187,14 @@ pub enum UnitTestResult {
     Failure,
 }
 
-pub fn run_move_unit_tests<W: Write + Send>(
+/// Compiles the package at `pkg_path` in test mode and constructs the plan of its unit tests. The
+/// named addresses of the package are recorded in `unit_test_config`.
+pub fn build_test_plan<W: Write>(
     pkg_path: &Path,
     mut build_config: move_package::BuildConfig,
-    mut unit_test_config: UnitTestingConfig,
-    natives: Vec<NativeFunctionRecord>,
-    cost_table: Option<CostTable>,
-    compute_coverage: bool,
+    unit_test_config: &mut UnitTestingConfig,
     writer: &mut W,
-) -> Result<UnitTestResult> {
+) -> Result<TestPlan> {
     let mut test_plan = None;
     build_config.test_mode = true;
     build_config.dev_mode = true;
@@ -233,9 +269,44 @@ pub fn run_move_unit_tests<W: Write + Send>(
 
     let (test_plan, mut files, units) = test_plan.unwrap();
     files.extend(dep_file_map);
-    let test_plan = test_plan.unwrap();
-    let no_tests = test_plan.is_empty();
-    let test_plan = TestPlan::new(test_plan, files, units);
+    Ok(TestPlan::new(test_plan.unwrap(), files, units))
+}
+
+pub fn run_move_unit_tests<W: Write + Send>(
+    pkg_path: &Path,
+    build_config: move_package::BuildConfig,
+    unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    compute_coverage: bool,
+    writer: &mut W,
+) -> Result<UnitTestResult> {
+    run_move_unit_tests_with_gas_meter(
+        pkg_path,
+        build_config,
+        unit_test_config,
+        natives,
+        cost_table,
+        None,
+        compute_coverage,
+        writer,
+    )
+}
+
+/// Like `run_move_unit_tests`, but charges gas with `gas_meter` instead of the unit cost table if
+/// one is given.
+pub fn run_move_unit_tests_with_gas_meter<W: Write + Send>(
+    pkg_path: &Path,
+    build_config: move_package::BuildConfig,
+    mut unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    gas_meter: Option<Arc<dyn GasProfilingMeter>>,
+    compute_coverage: bool,
+    writer: &mut W,
+) -> Result<UnitTestResult> {
+    let test_plan = build_test_plan(pkg_path, build_config, &mut unit_test_config, writer)?;
+    let no_tests = test_plan.module_tests.is_empty();
 
     let trace_path = pkg_path.join(".trace");
     let coverage_map_path = pkg_path
@@ -258,7 +329,13 @@ pub fn run_move_unit_tests<W: Write + Send>(
     // Run the tests. If any of the tests fail, then we don't produce a coverage report, so cleanup
     // the trace files.
     if !unit_test_config
-        .run_and_report_unit_tests(test_plan, Some(natives), cost_table, writer)
+        .run_and_report_unit_tests_with_gas_meter(
+            test_plan,
+            Some(natives),
+            cost_table,
+            gas_meter,
+            writer,
+        )
         .unwrap()
         .1
     {
@@ -274,6 +351,57 @@ pub fn run_move_unit_tests<W: Write + Send>(
     Ok(UnitTestResult::Success)
 }
 
+/// Runs the unit tests of the package at `pkg_path` against mutants of the package's own
+/// functions. Test-only code is never mutated.
+pub fn run_move_mutation_tests<W: Write>(
+    pkg_path: &Path,
+    build_config: move_package::BuildConfig,
+    mut unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    writer: &mut W,
+) -> Result<MutationReport> {
+    // Compiling outside of test mode leaves exactly the functions which are deployed
+    let mut production_config = build_config.clone();
+    production_config.dev_mode = true;
+    production_config.test_mode = false;
+    let production_package = production_config.compile_package_no_exit(pkg_path, writer)?;
+    let targets = production_package
+        .root_compiled_units
+        .iter()
+        .filter_map(|unit| match &unit.unit {
+            CompiledUnit::Module(NamedCompiledModule { module, .. }) => Some((
+                module.self_id(),
+                module
+                    .function_defs()
+                    .iter()
+                    .map(|fdef| {
+                        module
+                            .identifier_at(module.function_handle_at(fdef.function).name)
+                            .to_owned()
+                    })
+                    .collect(),
+            )),
+            CompiledUnit::Script(_) => None,
+        })
+        .collect::<BTreeMap<_, Vec<_>>>();
+
+    let test_plan = build_test_plan(pkg_path, build_config, &mut unit_test_config, writer)?;
+    run_mutation_tests(
+        &test_plan,
+        &targets,
+        Some(natives),
+        cost_table,
+        &MutationTestingConfig {
+            execution_bound: unit_test_config
+                .gas_limit
+                .unwrap_or(DEFAULT_MUTANT_EXECUTION_BOUND),
+            num_threads: unit_test_config.num_threads,
+            test_filter: unit_test_config.filter,
+        },
+    )
+}
+
 impl From<UnitTestResult> for ExitStatus {
     fn from(result: UnitTestResult) -> Self {
         match result {
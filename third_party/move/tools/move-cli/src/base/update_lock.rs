@@ -0,0 +1,21 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_package::BuildConfig;
+use std::path::PathBuf;
+
+/// Resolve the dependencies of the package at `path` and record the versions selected for its
+/// registry dependencies in `Move.lock`. If no path is provided defaults to current directory.
+#[derive(Parser)]
+#[clap(name = "update-lock")]
+pub struct UpdateLock;
+
+impl UpdateLock {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        config.update_lock_file(&rerooted_path, &mut std::io::stdout())?;
+        Ok(())
+    }
+}
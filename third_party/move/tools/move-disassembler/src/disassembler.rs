@@ -520,6 +520,29 @@ impl<'a> Disassembler<'a> {
                 "&mut {}",
                 self.disassemble_sig_tok(*sig_tok, type_param_context)?
             ),
+            SignatureToken::Function(args, results, abilities) => {
+                let args = args
+                    .into_iter()
+                    .map(|tok| self.disassemble_sig_tok(tok, type_param_context))
+                    .collect::<Result<Vec<_>>>()?;
+                let results = results
+                    .into_iter()
+                    .map(|tok| self.disassemble_sig_tok(tok, type_param_context))
+                    .collect::<Result<Vec<_>>>()?;
+                let results = match results.len() {
+                    0 => "".to_string(),
+                    1 => format!(" {}", results[0]),
+                    _ => format!(" ({})", results.join(", ")),
+                };
+                let abilities = if abilities == AbilitySet::EMPTY {
+                    "".to_string()
+                } else {
+                    let ability_vec: Vec<_> =
+                        abilities.into_iter().map(Self::format_ability).collect();
+                    format!(" has {}", ability_vec.join(" + "))
+                };
+                format!("|{}|{}{}", args.join(", "), results, abilities)
+            },
             SignatureToken::TypeParameter(ty_param_index) => type_param_context
                 .get(ty_param_index as usize)
                 .ok_or_else(|| {
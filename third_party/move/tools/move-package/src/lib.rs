@@ -2,10 +2,9 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-mod package_lock;
-
 pub mod compilation;
 pub mod package_hooks;
+pub mod package_lock;
 pub mod resolution;
 pub mod source_package;
 
@@ -13,7 +12,7 @@ use crate::{
     compilation::{
         build_plan::BuildPlan, compiled_package::CompiledPackage, model_builder::ModelBuilder,
     },
-    package_lock::PackageLock,
+    package_lock::{PackageLock, PackageLockFile},
     resolution::resolution_graph::{ResolutionGraph, ResolvedGraph},
     source_package::manifest_parser,
 };
@@ -193,6 +192,21 @@ impl BuildConfig {
         ret
     }
 
+    /// Compile the package at `path` or the containing Move package like `compile_package`, and
+    /// update its `Move.lock` file.
+    pub fn compile_package_and_update_lock<W: Write>(
+        self,
+        path: &Path,
+        writer: &mut W,
+    ) -> Result<CompiledPackage> {
+        let config = self.compiler_config.clone(); // Need clone because of mut self
+        let resolved_graph = self.update_lock_file(path, writer)?;
+        let mutx = PackageLock::lock();
+        let ret = BuildPlan::create(resolved_graph)?.compile(&config, writer);
+        mutx.unlock();
+        ret
+    }
+
     /// Compile the package at `path` or the containing Move package. Do not exit process on warning
     /// or failure.
     pub fn compile_package_no_exit<W: Write>(
@@ -272,6 +286,17 @@ impl BuildConfig {
         ret
     }
 
+    /// Resolve the package at `path` and record the versions selected for its registry
+    /// dependencies in the `Move.lock` file of the package.
+    pub fn update_lock_file<W: Write>(self, path: &Path, writer: &mut W) -> Result<ResolvedGraph> {
+        let resolved_graph = self.resolution_graph_for_package(path, writer)?;
+        let mutx = PackageLock::lock();
+        let ret = PackageLockFile::from_resolved_graph(&resolved_graph)
+            .write(&resolved_graph.root_package_path);
+        mutx.unlock();
+        ret.map(|_| resolved_graph)
+    }
+
     fn parse_toml_manifest(&self, path: PathBuf) -> Result<toml::Value> {
         let manifest_string = std::fs::read_to_string(path)?;
         manifest_parser::parse_move_manifest_string(manifest_string)
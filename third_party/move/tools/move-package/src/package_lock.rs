@@ -2,12 +2,30 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{
+    resolution::{registry::version_string, resolution_graph::ResolvedGraph},
+    source_package::{
+        manifest_parser::parse_version,
+        parsed_manifest::{PackageName, Version},
+    },
+};
+use anyhow::{Context, Result};
 use named_lock::{NamedLock, NamedLockGuard};
 use once_cell::sync::Lazy;
-use std::sync::{Mutex, MutexGuard};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
 use whoami::username;
 
 const PACKAGE_LOCK_NAME: &str = "move_pkg_lock";
+
+/// The file, next to the package manifest, recording the versions selected for registry
+/// dependencies.
+pub const LOCK_FILE_NAME: &str = "Move.lock";
 static PACKAGE_THREAD_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 static PACKAGE_PROCESS_MUTEX: Lazy<NamedLock> = Lazy::new(|| {
     let user_lock_file = format!("{}_{}", PACKAGE_LOCK_NAME, username());
@@ -63,3 +81,81 @@ impl PackageLock {
         }
     }
 }
+
+/// The contents of a `Move.lock` file. Resolution prefers the locked version of a registry
+/// package as long as it still satisfies every requirement in the dependency graph, so builds
+/// stay reproducible when newer versions are published to the registry.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PackageLockFile {
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    pub registry: String,
+}
+
+impl PackageLockFile {
+    /// Reads the lock file of the package at `package_path`, if there is one.
+    pub fn read(package_path: &Path) -> Result<Self> {
+        let path = package_path.join(LOCK_FILE_NAME);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)?;
+        toml::from_str(&contents).with_context(|| format!("Unable to parse lock file {:?}", path))
+    }
+
+    /// Writes the lock file for the package at `package_path`. Nothing is written if the lock
+    /// file is unchanged, or if it would be empty and does not yet exist.
+    pub fn write(&self, package_path: &Path) -> Result<()> {
+        let path = package_path.join(LOCK_FILE_NAME);
+        if (self.packages.is_empty() && !path.exists()) || &Self::read(package_path)? == self {
+            return Ok(());
+        }
+        fs::write(&path, toml::to_string(self)?)
+            .with_context(|| format!("Unable to write lock file {:?}", path))
+    }
+
+    /// Records the version that was selected for every registry dependency in `graph`.
+    pub fn from_resolved_graph(graph: &ResolvedGraph) -> Self {
+        let mut packages = BTreeMap::new();
+        for package in graph.package_table.values() {
+            for (dep_name, dep) in package
+                .source_package
+                .dependencies
+                .iter()
+                .chain(package.source_package.dev_dependencies.iter())
+            {
+                if let (Some(info), Some(resolved)) =
+                    (&dep.registry_info, graph.package_table.get(dep_name))
+                {
+                    packages.entry(*dep_name).or_insert_with(|| LockedPackage {
+                        name: dep_name.to_string(),
+                        version: version_string(&resolved.source_package.package.version),
+                        registry: info.registry.to_string(),
+                    });
+                }
+            }
+        }
+        Self {
+            packages: packages.into_values().collect(),
+        }
+    }
+
+    pub fn locked_versions(&self) -> Result<BTreeMap<PackageName, Version>> {
+        self.packages
+            .iter()
+            .map(|locked| {
+                let version = parse_version(toml::Value::String(locked.version.clone()))
+                    .with_context(|| {
+                        format!("Invalid locked version of package '{}'", locked.name)
+                    })?;
+                Ok((PackageName::from(locked.name.as_str()), version))
+            })
+            .collect()
+    }
+}
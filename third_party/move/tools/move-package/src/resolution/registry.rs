@@ -0,0 +1,328 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::source_package::{
+    layout::SourcePackageLayout,
+    manifest_parser::{parse_move_manifest_from_file, parse_version},
+    parsed_manifest::{Dependency, GitInfo, PackageName, RegistryInfo, Version, VersionReq},
+};
+use anyhow::{bail, Context, Result};
+use move_compiler::command_line::DEFAULT_OUTPUT_DIR;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value as TV;
+
+/// The number of times resolution is restarted with new version selections before giving up.
+const MAX_RESOLUTION_ROUNDS: usize = 16;
+
+const FILE_URL_PREFIX: &str = "file://";
+
+/// A registry index is a directory on the file system holding every published version of a set
+/// of packages. It can be shared between packages by pointing them at the same directory, which
+/// also makes it easy to serve a registry locally for tests.
+///
+/// a_registry
+/// ├── Foo
+/// │   ├── 1.0.0      (a Move package whose manifest declares version 1.0.0)
+/// │   └── 1.1.0
+/// └── Bar
+///     └── 0.3.2
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RegistryIndex {
+    root: PathBuf,
+}
+
+impl RegistryIndex {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Opens the registry at `location`, as written in the manifest of the package at
+    /// `declaring_package_path`.
+    pub fn from_location(location: &str, declaring_package_path: &Path) -> Self {
+        Self::new(declaring_package_path.join(location_path(location)))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns all published versions of `package_name`, in ascending order.
+    pub fn versions(&self, package_name: PackageName) -> Result<Vec<Version>> {
+        let package_dir = self.root.join(package_name.as_str());
+        if !package_dir.is_dir() {
+            bail!(
+                "Package '{}' not found in registry '{}'",
+                package_name,
+                self.root.display()
+            )
+        }
+        let mut versions = Vec::new();
+        for entry in fs::read_dir(&package_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            let version = parse_version(TV::String(name)).with_context(|| {
+                format!(
+                    "Invalid version directory {:?} in registry '{}'",
+                    entry.path(),
+                    self.root.display()
+                )
+            })?;
+            versions.push(version);
+        }
+        versions.sort();
+        Ok(versions)
+    }
+
+    pub fn package_path(&self, package_name: PackageName, version: &Version) -> PathBuf {
+        self.root
+            .join(package_name.as_str())
+            .join(version_string(version))
+    }
+
+    /// Copies the package at `package_path` into the registry under the name and version declared
+    /// in its manifest, returning the location of the published package. Build artifacts are not
+    /// copied. Published versions are immutable.
+    pub fn publish(&self, package_path: &Path) -> Result<PathBuf> {
+        let manifest = parse_move_manifest_from_file(package_path)?;
+        let target = self.package_path(manifest.package.name, &manifest.package.version);
+        if target.exists() {
+            bail!(
+                "Version {} of package '{}' is already published to registry '{}'",
+                version_string(&manifest.package.version),
+                manifest.package.name,
+                self.root.display()
+            )
+        }
+        let build_dir = package_path.join(DEFAULT_OUTPUT_DIR);
+        for entry in walkdir::WalkDir::new(package_path)
+            .into_iter()
+            .filter_entry(|e| e.path() != build_dir)
+        {
+            let entry = entry?;
+            let relative = entry.path().strip_prefix(package_path)?;
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(target.join(relative))?;
+            } else {
+                fs::copy(entry.path(), target.join(relative))?;
+            }
+        }
+        if !target.join(SourcePackageLayout::Manifest.path()).is_file() {
+            bail!("Failed to publish package '{}'", manifest.package.name)
+        }
+        Ok(target)
+    }
+
+    fn canonical_root(&self) -> PathBuf {
+        fs::canonicalize(&self.root).unwrap_or_else(|_| self.root.clone())
+    }
+}
+
+pub fn version_string((major, minor, patch): &Version) -> String {
+    format!("{}.{}.{}", major, minor, patch)
+}
+
+/// The path a registry location in a manifest refers to, relative to the declaring package unless
+/// it is absolute.
+fn location_path(location: &str) -> PathBuf {
+    PathBuf::from(location.strip_prefix(FILE_URL_PREFIX).unwrap_or(location))
+}
+
+#[derive(Debug, Clone)]
+struct Selection {
+    index: RegistryIndex,
+    version: Version,
+}
+
+/// Selects a single version for every registry package in the dependency graph.
+///
+/// Versions are selected greedily while the graph is built: the first time a registry package is
+/// encountered, the highest version satisfying that requirement is chosen (or the preferred
+/// version, if it does). When a later requirement is not satisfied by an earlier selection, the
+/// conflict is recorded and, once the round is complete, `finish_round` picks the highest version
+/// satisfying every requirement seen and asks for resolution to be restarted.
+///
+/// Git packages cannot be unified, so every git package must be required at a single revision.
+#[derive(Debug, Default)]
+pub struct RegistryResolver {
+    /// Versions to select if they are compatible, e.g., from a lock file or a previous round
+    preferred: BTreeMap<PackageName, Version>,
+    /// Versions selected in the current round
+    selected: BTreeMap<PackageName, Selection>,
+    /// Requirements seen in the current round: package => [(required by, requirement)]
+    requirements: BTreeMap<PackageName, Vec<(PackageName, VersionReq)>>,
+    /// Packages whose selection did not satisfy a requirement in the current round
+    conflicts: BTreeSet<PackageName>,
+    /// Git sources seen in the current round: package => (required by, source)
+    git_sources: BTreeMap<PackageName, (PackageName, GitInfo)>,
+    rounds: usize,
+}
+
+impl RegistryResolver {
+    pub fn new(preferred: BTreeMap<PackageName, Version>) -> Self {
+        Self {
+            preferred,
+            ..Default::default()
+        }
+    }
+
+    /// If `dep` is a registry dependency, selects a version for it and points the dependency at
+    /// the location of that version. Other dependencies are returned unchanged, after checking
+    /// that a git dependency does not conflict with an earlier one.
+    pub fn resolve_dependency(
+        &mut self,
+        declaring_package: PackageName,
+        declaring_package_path: &Path,
+        dep_name: PackageName,
+        mut dep: Dependency,
+    ) -> Result<Dependency> {
+        if let Some(info) = &dep.registry_info {
+            let version = self.select(declaring_package, declaring_package_path, dep_name, info)?;
+            dep.local = location_path(info.registry.as_str())
+                .join(dep_name.as_str())
+                .join(version_string(&version));
+        }
+        if let Some(info) = &dep.git_info {
+            self.check_git_source(declaring_package, dep_name, info)?;
+        }
+        Ok(dep)
+    }
+
+    fn check_git_source(
+        &mut self,
+        declaring_package: PackageName,
+        dep_name: PackageName,
+        info: &GitInfo,
+    ) -> Result<()> {
+        match self.git_sources.get(&dep_name) {
+            Some((required_by, other))
+                if (other.git_url, other.git_rev, &other.subdir)
+                    != (info.git_url, info.git_rev, &info.subdir) =>
+            {
+                bail!(
+                    "Conflicting git dependencies found: package '{}' is required at revision \
+                     '{}' of '{}' by '{}', but at revision '{}' of '{}' by '{}'",
+                    dep_name,
+                    info.git_rev,
+                    info.git_url,
+                    declaring_package,
+                    other.git_rev,
+                    other.git_url,
+                    required_by
+                )
+            },
+            Some(_) => (),
+            None => {
+                self.git_sources
+                    .insert(dep_name, (declaring_package, info.clone()));
+            },
+        }
+        Ok(())
+    }
+
+    fn select(
+        &mut self,
+        declaring_package: PackageName,
+        declaring_package_path: &Path,
+        dep_name: PackageName,
+        info: &RegistryInfo,
+    ) -> Result<Version> {
+        let index = RegistryIndex::from_location(info.registry.as_str(), declaring_package_path);
+        self.requirements
+            .entry(dep_name)
+            .or_default()
+            .push((declaring_package, info.version_req.clone()));
+
+        if let Some(selection) = self.selected.get(&dep_name) {
+            if selection.index.canonical_root() != index.canonical_root() {
+                bail!(
+                    "Package '{}' is required from registry '{}' but was already resolved \
+                     from registry '{}'",
+                    dep_name,
+                    index.root().display(),
+                    selection.index.root().display()
+                )
+            }
+            if !info.version_req.matches(&selection.version) {
+                // Keep building the graph with the current selection so that all requirements
+                // are known when the conflict is resolved.
+                self.conflicts.insert(dep_name);
+            }
+            return Ok(selection.version);
+        }
+
+        let candidates = index
+            .versions(dep_name)?
+            .into_iter()
+            .filter(|v| info.version_req.matches(v))
+            .collect::<Vec<_>>();
+        let version = match self.preferred.get(&dep_name) {
+            Some(preferred) if candidates.contains(preferred) => *preferred,
+            _ => match candidates.last() {
+                Some(highest) => *highest,
+                None => bail!(
+                    "No version of package '{}' in registry '{}' satisfies requirement '{}'",
+                    dep_name,
+                    index.root().display(),
+                    info.version_req
+                ),
+            },
+        };
+        self.selected.insert(dep_name, Selection { index, version });
+        Ok(version)
+    }
+
+    /// Completes a resolution round. Returns `true` if some selections had to change, in which
+    /// case the dependency graph must be resolved again.
+    pub fn finish_round(&mut self) -> Result<bool> {
+        if self.conflicts.is_empty() {
+            return Ok(false);
+        }
+        self.rounds += 1;
+        if self.rounds >= MAX_RESOLUTION_ROUNDS {
+            bail!(
+                "Unable to find compatible versions for registry packages {} after {} attempts",
+                self.conflicts
+                    .iter()
+                    .map(|name| format!("'{}'", name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                self.rounds
+            )
+        }
+        for dep_name in std::mem::take(&mut self.conflicts) {
+            let index = &self.selected[&dep_name].index;
+            let requirements = &self.requirements[&dep_name];
+            let compatible = index
+                .versions(dep_name)?
+                .into_iter()
+                .filter(|v| requirements.iter().all(|(_, req)| req.matches(v)))
+                .last();
+            match compatible {
+                Some(version) => {
+                    self.preferred.insert(dep_name, version);
+                },
+                None => bail!(
+                    "No version of package '{}' in registry '{}' satisfies all requirements: {}",
+                    dep_name,
+                    index.root().display(),
+                    requirements
+                        .iter()
+                        .map(|(by, req)| format!("'{}' (required by '{}')", req, by))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+        self.selected.clear();
+        self.requirements.clear();
+        self.git_sources.clear();
+        Ok(true)
+    }
+}
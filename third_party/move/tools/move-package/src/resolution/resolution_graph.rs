@@ -4,7 +4,8 @@
 
 use crate::{
     package_hooks,
-    resolution::digest::compute_digest,
+    package_lock::PackageLockFile,
+    resolution::{digest::compute_digest, registry::RegistryResolver},
     source_package::{
         layout::SourcePackageLayout,
         manifest_parser::{parse_move_manifest_string, parse_source_manifest},
@@ -105,23 +106,44 @@ impl ResolvingGraph {
                 build_options.architecture = info.architecture;
             }
         }
-        let mut resolution_graph = Self {
-            root_package_path: root_package_path.clone(),
-            build_options,
-            root_package: root_package.clone(),
-            graph: DiGraphMap::new(),
-            package_table: BTreeMap::new(),
-        };
-
-        resolution_graph
-            .build_resolution_graph(root_package.clone(), root_package_path, true, writer)
-            .with_context(|| {
+        let locked_versions = PackageLockFile::read(&root_package_path)?.locked_versions()?;
+        let mut registry = RegistryResolver::new(locked_versions);
+        loop {
+            let mut resolution_graph = Self {
+                root_package_path: root_package_path.clone(),
+                build_options: build_options.clone(),
+                root_package: root_package.clone(),
+                graph: DiGraphMap::new(),
+                package_table: BTreeMap::new(),
+            };
+
+            resolution_graph
+                .build_resolution_graph(
+                    root_package.clone(),
+                    root_package_path.clone(),
+                    true,
+                    &mut registry,
+                    writer,
+                )
+                .with_context(|| {
+                    format!(
+                        "Unable to resolve packages for package '{}'",
+                        root_package.package.name
+                    )
+                })?;
+            // Registry versions are selected as they are encountered, so a selection may turn
+            // out to be incompatible with a later requirement. In that case start over with
+            // versions satisfying all requirements.
+            let retry = registry.finish_round().with_context(|| {
                 format!(
-                    "Unable to resolve packages for package '{}'",
+                    "Unable to resolve registry dependencies for package '{}'",
                     root_package.package.name
                 )
             })?;
-        Ok(resolution_graph)
+            if !retry {
+                return Ok(resolution_graph);
+            }
+        }
     }
 
     pub fn resolve(self) -> Result<ResolvedGraph> {
@@ -199,6 +221,7 @@ impl ResolvingGraph {
         package: SourceManifest,
         package_path: PathBuf,
         is_root_package: bool,
+        registry: &mut RegistryResolver,
         writer: &mut W,
     ) -> Result<()> {
         let package_name = package.package.name;
@@ -251,8 +274,16 @@ impl ResolvingGraph {
             })?;
             self.graph.add_edge(package_node_id, dep_node_id, ());
 
+            let dep = registry
+                .resolve_dependency(package_name, &package_path, dep_name, dep)
+                .with_context(|| {
+                    format!(
+                        "While selecting a version of dependency '{}' in package '{}'",
+                        dep_name, package_name
+                    )
+                })?;
             let (dep_renaming, dep_resolution_table) = self
-                .process_dependency(dep_name, dep, package_path.clone(), writer)
+                .process_dependency(dep_name, dep, package_path.clone(), registry, writer)
                 .with_context(|| {
                     format!(
                         "While resolving dependency '{}' in package '{}'",
@@ -393,6 +424,7 @@ impl ResolvingGraph {
         dep_name_in_pkg: PackageName,
         dep: Dependency,
         root_path: PathBuf,
+        registry: &mut RegistryResolver,
         writer: &mut W,
     ) -> Result<(Renaming, ResolvingTable)> {
         Self::download_and_update_if_remote(
@@ -404,10 +436,14 @@ impl ResolvingGraph {
         let (dep_package, dep_package_dir) =
             Self::parse_package_manifest(&dep, &dep_name_in_pkg, root_path)
                 .with_context(|| format!("While processing dependency '{}'", dep_name_in_pkg))?;
-        self.build_resolution_graph(dep_package.clone(), dep_package_dir, false, writer)
-            .with_context(|| {
-                format!("Unable to resolve package dependency '{}'", dep_name_in_pkg)
-            })?;
+        self.build_resolution_graph(
+            dep_package.clone(),
+            dep_package_dir,
+            false,
+            registry,
+            writer,
+        )
+        .with_context(|| format!("Unable to resolve package dependency '{}'", dep_name_in_pkg))?;
 
         if dep_name_in_pkg != dep_package.package.name {
             bail!("Name of dependency declared in package '{}' does not match dependency's package name '{}'",
@@ -539,6 +575,11 @@ impl ResolvingGraph {
         };
 
         for (dep_name, dep) in manifest.dependencies.iter().chain(additional_deps.iter()) {
+            // Registry packages are read from their index once a version has been selected
+            // during resolution, which also fetches their own dependencies.
+            if dep.registry_info.is_some() {
+                continue;
+            }
             Self::download_and_update_if_remote(
                 *dep_name,
                 dep,
@@ -317,6 +317,7 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                 "rev",
                 "subdir",
                 "address",
+                "registry",
             ];
             let custom_key_opt = &package_hooks::custom_dependency_key();
             if let Some(key) = custom_key_opt {
@@ -327,10 +328,17 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                 .remove("addr_subst")
                 .map(parse_substitution)
                 .transpose()?;
-            let version = table.remove("version").map(parse_version).transpose()?;
+            let registry = table.remove("registry");
+            // For registry dependencies, `version` is a requirement rather than an exact version
+            let version = if registry.is_none() {
+                table.remove("version").map(parse_version).transpose()?
+            } else {
+                None
+            };
             let digest = table.remove("digest").map(parse_digest).transpose()?;
             let mut git_info = None;
             let mut node_info = None;
+            let mut registry_info = None;
             match (
                 table.remove("local"),
                 table.remove("git"),
@@ -339,8 +347,9 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                 } else {
                     None
                 },
+                registry,
             ) {
-                (Some(local), None, None) => {
+                (Some(local), None, None, None) => {
                     let local_str = local
                         .as_str()
                         .ok_or_else(|| format_err!("Local source path not a string"))?;
@@ -352,9 +361,10 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                         local: local_path,
                         git_info,
                         node_info,
+                        registry_info,
                     })
                 },
-                (None, Some(git), None) => {
+                (None, Some(git), None, None) => {
                     let move_home = MOVE_HOME.clone();
                     let rev_name = match table.remove("rev") {
                         None => bail!("Git revision not supplied for dependency"),
@@ -393,9 +403,10 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                         local: local_path.join(subdir),
                         git_info,
                         node_info,
+                        registry_info,
                     })
                 },
-                (None, None, Some(custom_key)) => {
+                (None, None, Some(custom_key), None) => {
                     let package_name = Symbol::from(dep_name);
                     let address = match table.remove("address") {
                         None => bail!("Address not supplied for 'node' dependency"),
@@ -427,10 +438,35 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                         local: local_path,
                         git_info,
                         node_info,
+                        registry_info,
+                    })
+                },
+                (None, None, None, Some(registry)) => {
+                    let registry = registry
+                        .as_str()
+                        .ok_or_else(|| format_err!("Registry location not a string"))?;
+                    let version_req = match table.remove("version") {
+                        None => bail!("Version requirement not supplied for registry dependency"),
+                        Some(v) => parse_version_req(v)?,
+                    };
+                    registry_info = Some(PM::RegistryInfo {
+                        registry: Symbol::from(registry),
+                        version_req,
+                    });
+                    // The location of the package is only known once a version has been
+                    // selected during resolution.
+                    Ok(PM::Dependency {
+                        subst,
+                        version,
+                        digest,
+                        local: PathBuf::new(),
+                        git_info,
+                        node_info,
+                        registry_info,
                     })
                 },
                 _ => {
-                    let mut keys = vec!["local", "git"];
+                    let mut keys = vec!["local", "git", "registry"];
                     if let Some(k) = custom_key_opt {
                         keys.push(k.as_str())
                     }
@@ -488,7 +524,7 @@ fn parse_substitution(tval: TV) -> Result<PM::Substitution> {
     }
 }
 
-fn parse_version(tval: TV) -> Result<PM::Version> {
+pub fn parse_version(tval: TV) -> Result<PM::Version> {
     let version_str = tval.as_str().unwrap();
     let version_parts = version_str.split('.').collect::<Vec<_>>();
     if version_parts.len() != 3 {
@@ -511,6 +547,47 @@ fn parse_version(tval: TV) -> Result<PM::Version> {
     ))
 }
 
+fn parse_version_req(tval: TV) -> Result<PM::VersionReq> {
+    let req_str = tval
+        .as_str()
+        .ok_or_else(|| format_err!("Version requirement must be a string"))?;
+    let comparators = req_str
+        .split(',')
+        .map(|comparator| parse_version_comparator(comparator.trim()))
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("Invalid version requirement '{}'", req_str))?;
+    Ok(PM::VersionReq { comparators })
+}
+
+fn parse_version_comparator(comparator_str: &str) -> Result<PM::VersionComparator> {
+    use crate::source_package::parsed_manifest::VersionComparator as C;
+    if comparator_str == "*" {
+        return Ok(C::Wildcard);
+    }
+    // Two-character operators must be checked before their one-character prefixes
+    let (make, version_str): (fn(PM::Version) -> C, &str) =
+        if let Some(rest) = comparator_str.strip_prefix(">=") {
+            (C::GreaterEq, rest)
+        } else if let Some(rest) = comparator_str.strip_prefix("<=") {
+            (C::LessEq, rest)
+        } else if let Some(rest) = comparator_str.strip_prefix('>') {
+            (C::Greater, rest)
+        } else if let Some(rest) = comparator_str.strip_prefix('<') {
+            (C::Less, rest)
+        } else if let Some(rest) = comparator_str.strip_prefix('=') {
+            (C::Exact, rest)
+        } else if let Some(rest) = comparator_str.strip_prefix('~') {
+            (C::Tilde, rest)
+        } else if let Some(rest) = comparator_str.strip_prefix('^') {
+            (C::Caret, rest)
+        } else {
+            (C::Caret, comparator_str)
+        };
+    Ok(make(parse_version(TV::String(
+        version_str.trim().to_string(),
+    ))?))
+}
+
 fn parse_architecture(tval: TV) -> Result<Architecture> {
     Architecture::try_parse_from_str(tval.as_str().unwrap())
 }
@@ -5,7 +5,7 @@
 use crate::Architecture;
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::symbol::Symbol;
-use std::{collections::BTreeMap, path::PathBuf};
+use std::{collections::BTreeMap, fmt, path::PathBuf};
 
 pub type NamedAddress = Symbol;
 pub type PackageName = Symbol;
@@ -45,6 +45,7 @@ pub struct Dependency {
     pub digest: Option<PackageDigest>,
     pub git_info: Option<GitInfo>,
     pub node_info: Option<CustomDepInfo>,
+    pub registry_info: Option<RegistryInfo>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -73,6 +74,41 @@ pub struct CustomDepInfo {
     pub download_to: PathBuf,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RegistryInfo {
+    /// The location of the registry index, either a path relative to the declaring package or a
+    /// `file://` URL
+    pub registry: Symbol,
+    /// The range of versions of the package which are acceptable to the declaring package
+    pub version_req: VersionReq,
+}
+
+/// A set of comparators which a version must all satisfy, e.g. `>= 1.2.0, < 2.0.0`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VersionReq {
+    pub comparators: Vec<VersionComparator>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum VersionComparator {
+    /// `=1.2.3`: exactly this version
+    Exact(Version),
+    /// `>1.2.3`
+    Greater(Version),
+    /// `>=1.2.3`
+    GreaterEq(Version),
+    /// `<1.2.3`
+    Less(Version),
+    /// `<=1.2.3`
+    LessEq(Version),
+    /// `^1.2.3` or `1.2.3`: any version which is semver compatible with this version
+    Caret(Version),
+    /// `~1.2.3`: any version with the same major and minor version
+    Tilde(Version),
+    /// `*`: any version
+    Wildcard,
+}
+
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct BuildInfo {
     pub language_version: Option<Version>,
@@ -84,3 +120,62 @@ pub enum SubstOrRename {
     RenameFrom(NamedAddress),
     Assign(AccountAddress),
 }
+
+impl VersionReq {
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+}
+
+impl VersionComparator {
+    pub fn matches(&self, version: &Version) -> bool {
+        match self {
+            Self::Exact(v) => version == v,
+            Self::Greater(v) => version > v,
+            Self::GreaterEq(v) => version >= v,
+            Self::Less(v) => version < v,
+            Self::LessEq(v) => version <= v,
+            Self::Caret(v) => {
+                // The left-most non-zero component must not change, following semver rules
+                // for pre-1.0 versions.
+                version >= v
+                    && if v.0 > 0 {
+                        version.0 == v.0
+                    } else if v.1 > 0 {
+                        version.0 == 0 && version.1 == v.1
+                    } else {
+                        version == v
+                    }
+            },
+            Self::Tilde(v) => version >= v && version.0 == v.0 && version.1 == v.1,
+            Self::Wildcard => true,
+        }
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let comparators = self
+            .comparators
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", comparators.join(", "))
+    }
+}
+
+impl fmt::Display for VersionComparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (op, (major, minor, patch)) = match self {
+            Self::Exact(v) => ("=", v),
+            Self::Greater(v) => (">", v),
+            Self::GreaterEq(v) => (">=", v),
+            Self::Less(v) => ("<", v),
+            Self::LessEq(v) => ("<=", v),
+            Self::Caret(v) => ("^", v),
+            Self::Tilde(v) => ("~", v),
+            Self::Wildcard => return write!(f, "*"),
+        };
+        write!(f, "{}{}.{}.{}", op, major, minor, patch)
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{
+    package_lock::{LockedPackage, PackageLockFile, LOCK_FILE_NAME},
+    resolution::{
+        registry::{RegistryIndex, RegistryResolver},
+        resolution_graph as RG,
+    },
+    source_package::{
+        manifest_parser as MP,
+        parsed_manifest::{Dependency, GitInfo},
+    },
+    BuildConfig,
+};
+use move_symbol_pool::Symbol;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::{tempdir, TempDir};
+
+fn write_package(path: &Path, name: &str, version: &str, dependencies: &str) {
+    fs::create_dir_all(path.join("sources")).unwrap();
+    fs::write(
+        path.join("Move.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"{}\"\n\n[dependencies]\n{}\n",
+            name, version, dependencies
+        ),
+    )
+    .unwrap();
+}
+
+/// Publishes each `(name, version, dependencies)` to a fresh registry.
+fn make_registry(packages: &[(&str, &str, &str)]) -> TempDir {
+    let registry_dir = tempdir().unwrap();
+    let index = RegistryIndex::new(registry_dir.path());
+    for (name, version, dependencies) in packages {
+        let staging = tempdir().unwrap();
+        write_package(staging.path(), name, version, dependencies);
+        index.publish(staging.path()).unwrap();
+    }
+    registry_dir
+}
+
+fn resolve(root: &Path) -> anyhow::Result<RG::ResolvedGraph> {
+    let manifest = MP::parse_move_manifest_from_file(root)?;
+    RG::ResolutionGraph::new(
+        manifest,
+        root.to_path_buf(),
+        BuildConfig {
+            install_dir: Some(tempdir().unwrap().path().to_path_buf()),
+            ..Default::default()
+        },
+        &mut Vec::new(), /* empty writer as no diags needed */
+    )?
+    .resolve()
+}
+
+fn resolved_version(graph: &RG::ResolvedGraph, name: &str) -> (u64, u64, u64) {
+    graph
+        .get_package(&Symbol::from(name))
+        .source_package
+        .package
+        .version
+}
+
+fn registry_dep(registry: &Path, name: &str, req: &str) -> String {
+    format!(
+        "{} = {{ registry = \"file://{}\", version = \"{}\" }}",
+        name,
+        registry.display(),
+        req
+    )
+}
+
+#[test]
+fn test_registry_selects_highest_compatible_version() {
+    let registry = make_registry(&[
+        ("Foo", "1.0.0", ""),
+        ("Foo", "1.2.0", ""),
+        ("Foo", "2.0.0", ""),
+    ]);
+    let root = tempdir().unwrap();
+    write_package(
+        root.path(),
+        "Root",
+        "0.0.0",
+        &registry_dep(registry.path(), "Foo", "^1.0.0"),
+    );
+
+    let graph = resolve(root.path()).unwrap();
+    assert_eq!(resolved_version(&graph, "Foo"), (1, 2, 0));
+}
+
+#[test]
+fn test_registry_unifies_requirements_across_graph() {
+    let registry = make_registry(&[("Foo", "1.0.0", ""), ("Foo", "1.2.0", "")]);
+    // `Util` only accepts patch releases of Foo 1.0, which is incompatible with the version the
+    // root package would pick on its own.
+    let util_deps = registry_dep(registry.path(), "Foo", "~1.0.0");
+    let index = RegistryIndex::new(registry.path());
+    let staging = tempdir().unwrap();
+    write_package(staging.path(), "Util", "0.1.0", &util_deps);
+    index.publish(staging.path()).unwrap();
+
+    let root = tempdir().unwrap();
+    write_package(
+        root.path(),
+        "Root",
+        "0.0.0",
+        &[
+            registry_dep(registry.path(), "Foo", "^1.0.0"),
+            registry_dep(registry.path(), "Util", "^0.1.0"),
+        ]
+        .join("\n"),
+    );
+
+    let graph = resolve(root.path()).unwrap();
+    assert_eq!(resolved_version(&graph, "Foo"), (1, 0, 0));
+    assert_eq!(resolved_version(&graph, "Util"), (0, 1, 0));
+}
+
+#[test]
+fn test_registry_incompatible_requirements() {
+    let registry = make_registry(&[("Foo", "1.0.0", ""), ("Foo", "2.0.0", "")]);
+    let util_deps = registry_dep(registry.path(), "Foo", ">=2.0.0");
+    let index = RegistryIndex::new(registry.path());
+    let staging = tempdir().unwrap();
+    write_package(staging.path(), "Util", "0.1.0", &util_deps);
+    index.publish(staging.path()).unwrap();
+
+    let root = tempdir().unwrap();
+    write_package(
+        root.path(),
+        "Root",
+        "0.0.0",
+        &[
+            registry_dep(registry.path(), "Foo", "<2.0.0"),
+            registry_dep(registry.path(), "Util", "*"),
+        ]
+        .join("\n"),
+    );
+
+    let err = format!("{:#}", resolve(root.path()).unwrap_err());
+    assert!(err.contains("No version of package 'Foo'"), "{}", err);
+}
+
+#[test]
+fn test_registry_rejects_republishing_version() {
+    let registry = make_registry(&[("Foo", "1.0.0", "")]);
+    let staging = tempdir().unwrap();
+    write_package(staging.path(), "Foo", "1.0.0", "");
+    assert!(RegistryIndex::new(registry.path())
+        .publish(staging.path())
+        .is_err());
+}
+
+#[test]
+fn test_lock_file_pins_registry_versions() {
+    let registry = make_registry(&[("Foo", "1.0.0", ""), ("Foo", "1.1.0", "")]);
+    let root = tempdir().unwrap();
+    write_package(
+        root.path(),
+        "Root",
+        "0.0.0",
+        &registry_dep(registry.path(), "Foo", "^1.0.0"),
+    );
+
+    let graph = resolve(root.path()).unwrap();
+    let lock_file = PackageLockFile::from_resolved_graph(&graph);
+    assert_eq!(lock_file.packages, vec![LockedPackage {
+        name: "Foo".to_string(),
+        version: "1.1.0".to_string(),
+        registry: format!("file://{}", registry.path().display()),
+    }]);
+
+    // A locked version is preferred over newer compatible versions.
+    PackageLockFile {
+        packages: vec![LockedPackage {
+            version: "1.0.0".to_string(),
+            ..lock_file.packages[0].clone()
+        }],
+    }
+    .write(root.path())
+    .unwrap();
+    let graph = resolve(root.path()).unwrap();
+    assert_eq!(resolved_version(&graph, "Foo"), (1, 0, 0));
+}
+
+#[test]
+fn test_lock_file_only_written_on_update() {
+    let registry = make_registry(&[("Foo", "1.0.0", "")]);
+    let root = tempdir().unwrap();
+    write_package(
+        root.path(),
+        "Root",
+        "0.0.0",
+        &registry_dep(registry.path(), "Foo", "^1.0.0"),
+    );
+    let build_config = BuildConfig {
+        install_dir: Some(tempdir().unwrap().path().to_path_buf()),
+        ..Default::default()
+    };
+
+    build_config
+        .clone()
+        .resolution_graph_for_package(root.path(), &mut Vec::new())
+        .unwrap();
+    assert!(!root.path().join(LOCK_FILE_NAME).exists());
+
+    build_config
+        .update_lock_file(root.path(), &mut Vec::new())
+        .unwrap();
+    let lock_file = PackageLockFile::read(root.path()).unwrap();
+    assert_eq!(lock_file.packages.len(), 1);
+    assert_eq!(lock_file.packages[0].version, "1.0.0");
+}
+
+fn git_dep(rev: &str) -> Dependency {
+    Dependency {
+        local: PathBuf::from("deps/Foo"),
+        subst: None,
+        version: None,
+        digest: None,
+        git_info: Some(GitInfo {
+            git_url: Symbol::from("https://example.com/foo.git"),
+            git_rev: Symbol::from(rev),
+            subdir: PathBuf::new(),
+            download_to: PathBuf::from("deps/Foo"),
+        }),
+        node_info: None,
+        registry_info: None,
+    }
+}
+
+#[test]
+fn test_git_dependency_revision_conflict() {
+    let mut resolver = RegistryResolver::default();
+    let foo = Symbol::from("Foo");
+    let path = Path::new(".");
+    resolver
+        .resolve_dependency(Symbol::from("A"), path, foo, git_dep("main"))
+        .unwrap();
+    resolver
+        .resolve_dependency(Symbol::from("B"), path, foo, git_dep("main"))
+        .unwrap();
+
+    let err = resolver
+        .resolve_dependency(Symbol::from("C"), path, foo, git_dep("v2"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Conflicting git dependencies"), "{}", err);
+    assert!(err.contains("'v2'") && err.contains("'main'"), "{}", err);
+}
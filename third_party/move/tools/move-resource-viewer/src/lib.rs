@@ -258,7 +258,8 @@ impl<'a, T: MoveResolver + ?Sized> MoveValueAnnotator<'a, T> {
             | (MoveValue::Signer(_), _)
             | (MoveValue::U16(_), _)
             | (MoveValue::U32(_), _)
-            | (MoveValue::U256(_), _) => {
+            | (MoveValue::U256(_), _)
+            | (MoveValue::Closure(_), _) => {
                 return Err(anyhow!(
                     "Cannot annotate value {:?} with type {:?}",
                     value,
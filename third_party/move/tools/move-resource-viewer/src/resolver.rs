@@ -171,6 +171,9 @@ impl<'a, T: MoveResolver + ?Sized> Resolver<'a, T> {
                 ))
             },
             SignatureToken::TypeParameter(idx) => FatType::TyParam(*idx as usize),
+            SignatureToken::Function(..) => {
+                return Err(anyhow!("Function values cannot be annotated yet"))
+            },
             SignatureToken::MutableReference(_) => return Err(anyhow!("Unexpected Reference")),
             SignatureToken::Reference(inner) => match **inner {
                 SignatureToken::Signer => FatType::Reference(Box::new(FatType::Signer)),
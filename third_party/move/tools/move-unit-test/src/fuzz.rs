@@ -0,0 +1,325 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Property-based testing for Move unit tests.
+//!
+//! Parameters of a `#[test]` function which are not assigned in the test attribute are fuzz
+//! parameters: the test is run repeatedly with generated values for them. Generation is biased
+//! towards boundary values (zero, one, the maximum of a type) which are likely to uncover bugs.
+//! Once a failing input is found, it is shrunk towards a minimal one by repeatedly trying simpler
+//! values and keeping those for which the test still fails.
+
+use move_compiler::unit_test::{FuzzParameter, TestCase};
+use move_core_types::{
+    account_address::AccountAddress,
+    u256::U256,
+    value::{MoveTypeLayout, MoveValue},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The default number of inputs a fuzz test is run with.
+pub const DEFAULT_FUZZ_ITERATIONS: u64 = 100;
+
+/// The maximum length of generated vectors.
+const MAX_VECTOR_LENGTH: usize = 32;
+
+/// Bound on the number of runs spent on shrinking a failing input.
+const MAX_SHRINK_RUNS: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// Seed from which the inputs of every fuzz test are derived
+    pub seed: u64,
+    /// Number of inputs each fuzz test is run with
+    pub iterations: u64,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            iterations: DEFAULT_FUZZ_ITERATIONS,
+        }
+    }
+}
+
+impl FuzzConfig {
+    /// Returns the generator of inputs for the test `test_name`. Every test gets its own
+    /// generator so that its inputs do not depend on the order tests are run in.
+    pub fn rng_for_test(&self, test_name: &str) -> StdRng {
+        // FNV-1a, which unlike `DefaultHasher` is stable across Rust versions
+        let name_hash = test_name
+            .bytes()
+            .fold(0xCBF2_9CE4_8422_2325u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3)
+            });
+        StdRng::seed_from_u64(self.seed ^ name_hash)
+    }
+}
+
+pub fn is_fuzz_test(test_info: &TestCase) -> bool {
+    !test_info.fuzz_parameters.is_empty()
+}
+
+/// Generates a value for every fuzz parameter of a test.
+pub fn generate_inputs(rng: &mut StdRng, parameters: &[FuzzParameter]) -> Vec<MoveValue> {
+    parameters
+        .iter()
+        .map(|param| generate_value(rng, &param.layout))
+        .collect()
+}
+
+/// Returns the arguments to call the test function with, merging the values assigned in the test
+/// attribute with the generated `inputs` of the fuzz parameters.
+pub fn test_arguments(test_info: &TestCase, inputs: &[MoveValue]) -> Vec<MoveValue> {
+    let mut assigned = test_info.arguments.iter();
+    let mut generated = test_info.fuzz_parameters.iter().zip(inputs).peekable();
+    let num_parameters = test_info.arguments.len() + test_info.fuzz_parameters.len();
+    (0..num_parameters)
+        .map(|index| {
+            if generated
+                .peek()
+                .map_or(false, |(param, _)| param.index == index)
+            {
+                generated.next().unwrap().1.clone()
+            } else {
+                assigned
+                    .next()
+                    .expect("every parameter is either assigned or generated")
+                    .clone()
+            }
+        })
+        .collect()
+}
+
+/// Renders the inputs of a fuzz test, e.g. `a = 0u64, v = vector[]`.
+pub fn format_inputs(parameters: &[FuzzParameter], inputs: &[MoveValue]) -> String {
+    parameters
+        .iter()
+        .zip(inputs)
+        .map(|(param, value)| format!("{} = {}", param.name, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn generate_value(rng: &mut StdRng, layout: &MoveTypeLayout) -> MoveValue {
+    use MoveTypeLayout as L;
+    match layout {
+        L::Bool => MoveValue::Bool(rng.gen()),
+        L::Address => MoveValue::Address(
+            if rng.gen_bool(0.25) {
+                // Small addresses are where the framework and named addresses of tests live
+                AccountAddress::from_hex_literal(&format!("0x{:x}", rng.gen_range(0u8..16)))
+                    .unwrap()
+            } else {
+                AccountAddress::new(rng.gen())
+            },
+        ),
+        L::Vector(elem_layout) => {
+            let len = rng.gen_range(0..=MAX_VECTOR_LENGTH);
+            MoveValue::Vector((0..len).map(|_| generate_value(rng, elem_layout)).collect())
+        },
+        L::U8 | L::U16 | L::U32 | L::U64 | L::U128 | L::U256 => {
+            let bits = int_bits(layout);
+            let max = int_max(bits);
+            let n = match rng.gen_range(0..4) {
+                0 => [U256::zero(), U256::one(), max - U256::one(), max][rng.gen_range(0..4)],
+                1 => U256::from(rng.gen_range(0u8..=u8::MAX)),
+                // A uniformly chosen number of significant bits, so that values of all orders of
+                // magnitude are generated
+                _ => {
+                    let significant_bits = rng.gen_range(1..=bits);
+                    U256::from_le_bytes(&rng.gen()) >> (256 - significant_bits) as u8
+                },
+            };
+            int_value(layout, n)
+        },
+        L::Signer | L::Struct(_) | L::Function => {
+            unreachable!("values are only generated for primitive types and vectors")
+        },
+    }
+}
+
+/// Shrinks the failing `inputs` of a fuzz test: simpler inputs are tried one at a time, and the
+/// first one for which `fails` still holds replaces the current input, until no simpler failing
+/// input is found.
+pub fn shrink_inputs(
+    inputs: Vec<MoveValue>,
+    mut fails: impl FnMut(&[MoveValue]) -> bool,
+) -> Vec<MoveValue> {
+    let mut current = inputs;
+    let mut runs = 0;
+    'outer: loop {
+        for i in 0..current.len() {
+            for candidate in shrink_value(&current[i]) {
+                if runs == MAX_SHRINK_RUNS {
+                    break 'outer;
+                }
+                runs += 1;
+                let mut inputs = current.clone();
+                inputs[i] = candidate;
+                if fails(&inputs) {
+                    current = inputs;
+                    continue 'outer;
+                }
+            }
+        }
+        break;
+    }
+    current
+}
+
+/// Returns values which are simpler than `value`, simplest first.
+fn shrink_value(value: &MoveValue) -> Vec<MoveValue> {
+    match value {
+        MoveValue::Bool(true) => vec![MoveValue::Bool(false)],
+        MoveValue::Bool(false) => vec![],
+        MoveValue::Address(addr) => [AccountAddress::ZERO, AccountAddress::ONE]
+            .into_iter()
+            .filter(|simpler| simpler < addr)
+            .map(MoveValue::Address)
+            .collect(),
+        MoveValue::Vector(elems) => {
+            let mut candidates = vec![];
+            if !elems.is_empty() {
+                candidates.push(MoveValue::Vector(vec![]));
+            }
+            // Halves of the vector, then the vector without each single element
+            if elems.len() > 2 {
+                let mid = elems.len() / 2;
+                candidates.push(MoveValue::Vector(elems[..mid].to_vec()));
+                candidates.push(MoveValue::Vector(elems[mid..].to_vec()));
+            }
+            if elems.len() > 1 {
+                for i in 0..elems.len() {
+                    let mut smaller = elems.clone();
+                    smaller.remove(i);
+                    candidates.push(MoveValue::Vector(smaller));
+                }
+            }
+            for (i, elem) in elems.iter().enumerate() {
+                for simpler in shrink_value(elem) {
+                    let mut shrunk = elems.clone();
+                    shrunk[i] = simpler;
+                    candidates.push(MoveValue::Vector(shrunk));
+                }
+            }
+            candidates
+        },
+        _ => match int_of_value(value) {
+            // Values between zero and `n`, approaching `n` by halving the distance, such that
+            // the boundary at which a test starts to fail is found by bisection.
+            Some((layout, n)) if n != U256::zero() => {
+                let mut candidates = vec![int_value(&layout, U256::zero())];
+                let mut delta = n >> 1u8;
+                while delta != U256::zero() {
+                    candidates.push(int_value(&layout, n - delta));
+                    delta = delta >> 1u8;
+                }
+                if n != U256::one() {
+                    candidates.push(int_value(&layout, n - U256::one()));
+                }
+                candidates.dedup();
+                candidates
+            },
+            _ => vec![],
+        },
+    }
+}
+
+fn int_bits(layout: &MoveTypeLayout) -> u16 {
+    match layout {
+        MoveTypeLayout::U8 => 8,
+        MoveTypeLayout::U16 => 16,
+        MoveTypeLayout::U32 => 32,
+        MoveTypeLayout::U64 => 64,
+        MoveTypeLayout::U128 => 128,
+        MoveTypeLayout::U256 => 256,
+        _ => unreachable!("not an integer type"),
+    }
+}
+
+fn int_max(bits: u16) -> U256 {
+    U256::max_value() >> (256 - bits) as u8
+}
+
+fn int_value(layout: &MoveTypeLayout, n: U256) -> MoveValue {
+    match layout {
+        MoveTypeLayout::U8 => MoveValue::U8(n.unchecked_as_u8()),
+        MoveTypeLayout::U16 => MoveValue::U16(n.unchecked_as_u16()),
+        MoveTypeLayout::U32 => MoveValue::U32(n.unchecked_as_u32()),
+        MoveTypeLayout::U64 => MoveValue::U64(n.unchecked_as_u64()),
+        MoveTypeLayout::U128 => MoveValue::U128(n.unchecked_as_u128()),
+        MoveTypeLayout::U256 => MoveValue::U256(n),
+        _ => unreachable!("not an integer type"),
+    }
+}
+
+fn int_of_value(value: &MoveValue) -> Option<(MoveTypeLayout, U256)> {
+    Some(match value {
+        MoveValue::U8(n) => (MoveTypeLayout::U8, U256::from(*n)),
+        MoveValue::U16(n) => (MoveTypeLayout::U16, U256::from(*n)),
+        MoveValue::U32(n) => (MoveTypeLayout::U32, U256::from(*n)),
+        MoveValue::U64(n) => (MoveTypeLayout::U64, U256::from(*n)),
+        MoveValue::U128(n) => (MoveTypeLayout::U128, U256::from(*n)),
+        MoveValue::U256(n) => (MoveTypeLayout::U256, *n),
+        _ => return None,
+    })
+}
+
+#[test]
+fn shrinking_finds_boundary() {
+    let inputs = shrink_inputs(
+        vec![MoveValue::U64(u64::MAX), MoveValue::Bool(true)],
+        |i| matches!(i[0], MoveValue::U64(n) if n >= 1000),
+    );
+    assert_eq!(inputs, vec![MoveValue::U64(1000), MoveValue::Bool(false)]);
+
+    let inputs = shrink_inputs(
+        vec![MoveValue::Vector(
+            (0..20u8).map(|n| MoveValue::U8(n * 10)).collect(),
+        )],
+        |i| matches!(&i[0], MoveValue::Vector(v) if v.contains(&MoveValue::U8(70))),
+    );
+    assert_eq!(inputs, vec![MoveValue::Vector(vec![MoveValue::U8(70)])]);
+}
+
+#[test]
+fn generated_inputs_are_deterministic() {
+    let params = [
+        FuzzParameter {
+            name: "n".to_string(),
+            index: 0,
+            layout: MoveTypeLayout::U8,
+        },
+        FuzzParameter {
+            name: "v".to_string(),
+            index: 2,
+            layout: MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Address)),
+        },
+    ];
+    let config = FuzzConfig::default();
+    let generate = || {
+        let mut rng = config.rng_for_test("0x1::M::test");
+        (0..10)
+            .map(|_| generate_inputs(&mut rng, &params))
+            .collect::<Vec<_>>()
+    };
+    let inputs = generate();
+    assert_eq!(inputs, generate());
+    assert!(inputs
+        .iter()
+        .all(|i| matches!((&i[0], &i[1]), (MoveValue::U8(_), MoveValue::Vector(_)))));
+
+    let test_info = TestCase {
+        test_name: "test".to_string(),
+        arguments: vec![MoveValue::Signer(AccountAddress::ONE)],
+        fuzz_parameters: params.to_vec(),
+        expected_failure: None,
+    };
+    let args = test_arguments(&test_info, &inputs[0]);
+    assert_eq!(args.len(), 3);
+    assert_eq!(args[0], inputs[0][0]);
+    assert_eq!(args[1], MoveValue::Signer(AccountAddress::ONE));
+    assert_eq!(args[2], inputs[0][1]);
+}
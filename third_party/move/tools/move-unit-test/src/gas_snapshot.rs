@@ -0,0 +1,160 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas snapshots record the gas used by each unit test in a file checked in with the package, so
+//! that changes in gas usage show up in code review and regressions fail the test run.
+//!
+//! A snapshot has one line per test, sorted by the fully qualified name of the test:
+//!
+//! ```text
+//! 0x1::coin::test_transfer: 4520
+//! 0x1::coin::test_withdraw: 3911
+//! ```
+//!
+//! Gas is recorded in the units charged by the test runner: instructions executed under the unit
+//! cost table by default, or the units of the gas meter tests are run with, e.g., the external gas
+//! units of transactions for `aptos move test --gas-snapshot`.
+
+use anyhow::{anyhow, Context, Result};
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasSnapshot {
+    /// Gas used by each test, by fully qualified test name
+    pub gas_used: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasRegression {
+    pub test_name: String,
+    pub snapshot_gas: u64,
+    pub actual_gas: u64,
+}
+
+impl GasSnapshot {
+    pub fn new(gas_used: BTreeMap<String, u64>) -> Self {
+        Self { gas_used }
+    }
+
+    /// Reads the snapshot at `path`, or returns an empty snapshot if there is none yet.
+    pub fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read gas snapshot {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid gas snapshot {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut gas_used = BTreeMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (test_name, gas) = line
+                .rsplit_once(':')
+                .ok_or_else(|| anyhow!("Expected `<test name>: <gas>` on line {}", i + 1))?;
+            let gas = gas
+                .trim()
+                .parse::<u64>()
+                .with_context(|| format!("Invalid amount of gas on line {}", i + 1))?;
+            gas_used.insert(test_name.trim().to_string(), gas);
+        }
+        Ok(Self { gas_used })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .with_context(|| format!("Failed to write gas snapshot {}", path.display()))
+    }
+
+    /// Returns the tests in `current` whose gas usage exceeds the one recorded in this snapshot
+    /// by more than `tolerance_percent` percent. Tests which are not in the snapshot are new and
+    /// never regress.
+    pub fn regressions(&self, current: &GasSnapshot, tolerance_percent: u64) -> Vec<GasRegression> {
+        current
+            .gas_used
+            .iter()
+            .filter_map(|(test_name, &actual_gas)| {
+                let snapshot_gas = *self.gas_used.get(test_name)?;
+                let allowed_gas = snapshot_gas as u128 * (100 + tolerance_percent as u128) / 100;
+                (actual_gas as u128 > allowed_gas).then(|| GasRegression {
+                    test_name: test_name.clone(),
+                    snapshot_gas,
+                    actual_gas,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns this snapshot updated with the gas used in `current`. If `retain_others` is set,
+    /// tests not in `current` keep their recorded gas, e.g., because they were filtered out of the
+    /// test run; otherwise they are dropped.
+    pub fn updated(&self, current: &GasSnapshot, retain_others: bool) -> Self {
+        let mut gas_used = if retain_others {
+            self.gas_used.clone()
+        } else {
+            BTreeMap::new()
+        };
+        gas_used.extend(
+            current
+                .gas_used
+                .iter()
+                .map(|(test_name, gas)| (test_name.clone(), *gas)),
+        );
+        Self { gas_used }
+    }
+}
+
+impl fmt::Display for GasSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (test_name, gas) in &self.gas_used {
+            writeln!(f, "{}: {}", test_name, gas)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GasRegression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: used {} gas, snapshot is {} (+{:.2}%)",
+            self.test_name,
+            self.actual_gas,
+            self.snapshot_gas,
+            (self.actual_gas - self.snapshot_gas) as f64 * 100.0 / self.snapshot_gas.max(1) as f64
+        )
+    }
+}
+
+#[test]
+fn gas_snapshot_regressions() {
+    let snapshot =
+        GasSnapshot::parse("0x1::M::a: 100\n0x1::M::b: 200\n\n0x1::M::gone: 5\n").unwrap();
+    assert_eq!(GasSnapshot::parse(&snapshot.to_string()).unwrap(), snapshot);
+
+    let current = GasSnapshot::new(
+        [("0x1::M::a", 110), ("0x1::M::b", 190), ("0x1::M::new", 7)]
+            .into_iter()
+            .map(|(name, gas)| (name.to_string(), gas))
+            .collect(),
+    );
+    assert_eq!(snapshot.regressions(&current, 10), vec![]);
+    assert_eq!(snapshot.regressions(&current, 5), vec![GasRegression {
+        test_name: "0x1::M::a".to_string(),
+        snapshot_gas: 100,
+        actual_gas: 110,
+    }]);
+
+    assert_eq!(
+        snapshot.updated(&current, false).to_string(),
+        "0x1::M::a: 110\n0x1::M::b: 190\n0x1::M::new: 7\n"
+    );
+    assert!(snapshot
+        .updated(&current, true)
+        .gas_used
+        .contains_key("0x1::M::gone"));
+    assert!(GasSnapshot::parse("0x1::M::a 100").is_err());
+}
@@ -4,10 +4,18 @@
 
 pub mod cargo_runner;
 pub mod extensions;
+pub mod fuzz;
+pub mod gas_snapshot;
+pub mod mutation;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::{
+    fuzz::{FuzzConfig, DEFAULT_FUZZ_ITERATIONS},
+    gas_snapshot::GasSnapshot,
+    test_reporter::TestResults,
+    test_runner::{GasProfilingMeter, TestRunner},
+};
 use clap::*;
 use move_command_line_common::files::verify_and_create_named_address_mapping;
 use move_compiler::{
@@ -22,9 +30,10 @@ use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::{
     collections::BTreeMap,
-    io::{Result, Write},
+    io::{Error, ErrorKind, Result, Write},
     marker::Send,
-    sync::Mutex,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
 /// The default value bounding the amount of gas consumed in a test.
@@ -107,6 +116,36 @@ pub struct UnitTestingConfig {
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
 
+    /// Record the gas used by each test in this file, and fail if the gas used by a test exceeds
+    /// the recorded amount by more than the tolerance
+    #[clap(name = "gas_snapshot", long = "gas_snapshot")]
+    pub gas_snapshot: Option<PathBuf>,
+
+    /// Percentage by which the gas used by a test may exceed its gas snapshot
+    #[clap(
+        name = "gas_snapshot_tolerance",
+        long = "gas_snapshot_tolerance",
+        default_value_t = 0
+    )]
+    pub gas_snapshot_tolerance: u64,
+
+    /// Overwrite the gas snapshot with the gas used in this run, even where it increased
+    #[clap(name = "update_gas_snapshot", long = "update_gas_snapshot")]
+    pub update_gas_snapshot: bool,
+
+    /// Seed for the inputs generated for tests with parameters not assigned in their test
+    /// attribute. A random seed is used if none is given; it is reported for failing tests.
+    #[clap(name = "seed", long = "seed")]
+    pub seed: Option<u64>,
+
+    /// Number of generated inputs each test with unassigned parameters is run with
+    #[clap(
+        name = "fuzz_iterations",
+        long = "fuzz_iterations",
+        default_value_t = DEFAULT_FUZZ_ITERATIONS
+    )]
+    pub fuzz_iterations: u64,
+
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
     #[cfg(feature = "evm-backend")]
@@ -137,6 +176,11 @@ impl UnitTestingConfig {
             dep_files: vec![],
             check_stackless_vm: false,
             verbose: false,
+            gas_snapshot: None,
+            gas_snapshot_tolerance: 0,
+            update_gas_snapshot: false,
+            seed: None,
+            fuzz_iterations: DEFAULT_FUZZ_ITERATIONS,
             list: false,
             named_address_values: vec![],
 
@@ -154,6 +198,14 @@ impl UnitTestingConfig {
         self
     }
 
+    /// The configuration for running tests with generated inputs
+    pub fn fuzz_config(&self) -> FuzzConfig {
+        FuzzConfig {
+            seed: self.seed.unwrap_or_else(rand::random),
+            iterations: self.fuzz_iterations,
+        }
+    }
+
     fn compile_to_test_plan(
         &self,
         source_files: Vec<String>,
@@ -217,6 +269,25 @@ impl UnitTestingConfig {
         native_function_table: Option<NativeFunctionTable>,
         cost_table: Option<CostTable>,
         writer: W,
+    ) -> Result<(W, bool)> {
+        self.run_and_report_unit_tests_with_gas_meter(
+            test_plan,
+            native_function_table,
+            cost_table,
+            None,
+            writer,
+        )
+    }
+
+    /// Like `run_and_report_unit_tests`, but charges gas with `gas_meter` instead of the unit
+    /// cost table if one is given.
+    pub fn run_and_report_unit_tests_with_gas_meter<W: Write + Send>(
+        &self,
+        test_plan: TestPlan,
+        native_function_table: Option<NativeFunctionTable>,
+        cost_table: Option<CostTable>,
+        gas_meter: Option<Arc<dyn GasProfilingMeter>>,
+        writer: W,
     ) -> Result<(W, bool)> {
         let shared_writer = Mutex::new(writer);
 
@@ -244,6 +315,8 @@ impl UnitTestingConfig {
             native_function_table,
             cost_table,
             self.verbose,
+            self.fuzz_config(),
+            gas_meter,
             #[cfg(feature = "evm-backend")]
             self.evm,
         )
@@ -262,11 +335,56 @@ impl UnitTestingConfig {
             test_results.report_goldens(&shared_writer)?;
         }
 
-        let ok = test_results.summarize(&shared_writer)?;
+        let mut ok = test_results.summarize(&shared_writer)?;
+        if let Some(snapshot_path) = &self.gas_snapshot {
+            ok &= self.check_gas_snapshot(snapshot_path, &test_results, ok, &shared_writer)?;
+        }
 
         let writer = shared_writer.into_inner().unwrap();
         Ok((writer, ok))
     }
+
+    /// Compares the gas used by the tests with the snapshot at `path`. The snapshot is updated
+    /// with the gas used in this run unless some test exceeds its snapshot, in which case `false`
+    /// is returned.
+    fn check_gas_snapshot<W: Write>(
+        &self,
+        path: &Path,
+        test_results: &TestResults,
+        all_tests_passed: bool,
+        writer: &Mutex<W>,
+    ) -> Result<bool> {
+        let to_io_error = |err: anyhow::Error| Error::new(ErrorKind::Other, format!("{:#}", err));
+        let snapshot = GasSnapshot::read(path).map_err(to_io_error)?;
+        let current = GasSnapshot::new(test_results.gas_used());
+
+        let regressions = if self.update_gas_snapshot {
+            vec![]
+        } else {
+            snapshot.regressions(&current, self.gas_snapshot_tolerance)
+        };
+        if !regressions.is_empty() {
+            let mut writer = writer.lock().unwrap();
+            writeln!(
+                writer,
+                "\nGas used exceeds the snapshot in {} by more than {}%:",
+                path.display(),
+                self.gas_snapshot_tolerance
+            )?;
+            for regression in regressions {
+                writeln!(writer, "  {}", regression)?;
+            }
+            return Ok(false);
+        }
+
+        // Tests which did not run or failed keep their previous gas
+        let retain_others = self.filter.is_some() || !all_tests_passed;
+        snapshot
+            .updated(&current, retain_others)
+            .write(path)
+            .map_err(to_io_error)?;
+        Ok(true)
+    }
 }
 
 #[test]
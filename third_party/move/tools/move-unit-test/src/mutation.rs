@@ -0,0 +1,361 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mutation testing for Move unit tests.
+//!
+//! A mutant is a copy of a compiled module in which a single instruction has been replaced by a
+//! type-compatible one, e.g. `Add` by `Sub` or `BrTrue` by `BrFalse`. Each mutant is run against
+//! the unit tests of the package; if all tests still pass, the mutant *survived*, which points at
+//! behavior the tests do not check.
+
+use crate::{fuzz::FuzzConfig, test_runner::TestRunner};
+use anyhow::{bail, Result};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, CompiledModule, FunctionDefinitionIndex},
+};
+use move_compiler::{diagnostics::FilesSourceText, unit_test::TestPlan};
+use move_core_types::{
+    identifier::{IdentStr, Identifier},
+    language_storage::ModuleId,
+    u256::U256,
+};
+use move_vm_runtime::native_functions::NativeFunctionTable;
+use move_vm_test_utils::gas_schedule::CostTable;
+use rayon::prelude::*;
+use std::{collections::BTreeMap, fmt, sync::Mutex};
+
+/// The default bound on the number of instructions executed by one test against a mutant. Mutants
+/// often loop forever, so tests against them are always bounded.
+pub const DEFAULT_MUTANT_EXECUTION_BOUND: u64 = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MutationKind {
+    /// An arithmetic, bitwise, logical or comparison operator was replaced
+    Operator,
+    /// A constant was changed
+    Constant,
+    /// The condition of a branch was negated
+    Condition,
+}
+
+#[derive(Debug, Clone)]
+pub struct Mutant {
+    pub module_id: ModuleId,
+    pub function_name: Identifier,
+    pub function_index: FunctionDefinitionIndex,
+    pub code_offset: CodeOffset,
+    pub kind: MutationKind,
+    pub original: Bytecode,
+    pub replacement: Bytecode,
+}
+
+#[derive(Debug, Clone)]
+pub struct MutantResult {
+    pub mutant: Mutant,
+    /// Whether at least one test failed when run against the mutant
+    pub killed: bool,
+    /// `file:line:column` of the mutated instruction, if known
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MutationReport {
+    pub results: Vec<MutantResult>,
+}
+
+pub struct MutationTestingConfig {
+    /// Bound on the number of instructions executed by one test
+    pub execution_bound: u64,
+    /// Number of mutants tested in parallel
+    pub num_threads: usize,
+    /// Only run tests whose name contains this string
+    pub test_filter: Option<String>,
+}
+
+impl fmt::Display for MutationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Operator => write!(f, "operator"),
+            Self::Constant => write!(f, "constant"),
+            Self::Condition => write!(f, "condition"),
+        }
+    }
+}
+
+impl Mutant {
+    /// Returns a copy of `module` with this mutation applied.
+    pub fn apply(&self, module: &CompiledModule) -> CompiledModule {
+        let mut mutated = module.clone();
+        let code = mutated.function_defs[self.function_index.0 as usize]
+            .code
+            .as_mut()
+            .expect("mutants are only generated for functions with code");
+        code.code[self.code_offset as usize] = self.replacement.clone();
+        mutated
+    }
+}
+
+impl MutationReport {
+    pub fn num_killed(&self) -> usize {
+        self.results.iter().filter(|r| r.killed).count()
+    }
+
+    /// Surviving mutants, grouped by the function they mutate.
+    pub fn survivors_by_function(&self) -> BTreeMap<(ModuleId, Identifier), Vec<&MutantResult>> {
+        let mut survivors: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for result in self.results.iter().filter(|r| !r.killed) {
+            survivors
+                .entry((
+                    result.mutant.module_id.clone(),
+                    result.mutant.function_name.clone(),
+                ))
+                .or_default()
+                .push(result);
+        }
+        survivors
+    }
+}
+
+/// Returns the type-preserving replacements of `instr` which are used as mutations.
+pub fn mutations_of(instr: &Bytecode) -> Vec<(MutationKind, Bytecode)> {
+    use Bytecode::*;
+    use MutationKind::*;
+    let operator = |b: Bytecode| vec![(Operator, b)];
+    match instr {
+        Add => operator(Sub),
+        Sub => operator(Add),
+        Mul => operator(Div),
+        Div => operator(Mul),
+        Mod => operator(Div),
+        BitOr => operator(BitAnd),
+        BitAnd => operator(BitOr),
+        Xor => operator(BitOr),
+        Shl => operator(Shr),
+        Shr => operator(Shl),
+        Or => operator(And),
+        And => operator(Or),
+        Eq => operator(Neq),
+        Neq => operator(Eq),
+        // Comparison mutations target off-by-one boundaries
+        Lt => operator(Le),
+        Le => operator(Lt),
+        Gt => operator(Ge),
+        Ge => operator(Gt),
+        LdTrue => vec![(Constant, LdFalse)],
+        LdFalse => vec![(Constant, LdTrue)],
+        LdU8(n) => int_constant(*n, 0, |n| n.checked_add(1), LdU8),
+        LdU16(n) => int_constant(*n, 0, |n| n.checked_add(1), LdU16),
+        LdU32(n) => int_constant(*n, 0, |n| n.checked_add(1), LdU32),
+        LdU64(n) => int_constant(*n, 0, |n| n.checked_add(1), LdU64),
+        LdU128(n) => int_constant(*n, 0, |n| n.checked_add(1), LdU128),
+        LdU256(n) => int_constant(*n, U256::zero(), |n| n.checked_add(U256::one()), LdU256),
+        BrTrue(offset) => vec![(Condition, BrFalse(*offset))],
+        BrFalse(offset) => vec![(Condition, BrTrue(*offset))],
+        _ => vec![],
+    }
+}
+
+/// Mutates an integer constant to its successor and, unless it is already zero, to zero.
+fn int_constant<T: PartialEq + Copy>(
+    n: T,
+    zero: T,
+    succ: impl Fn(T) -> Option<T>,
+    make: impl Fn(T) -> Bytecode,
+) -> Vec<(MutationKind, Bytecode)> {
+    let mut mutations = vec![];
+    if let Some(m) = succ(n) {
+        mutations.push((MutationKind::Constant, make(m)));
+    }
+    if n != zero {
+        mutations.push((MutationKind::Constant, make(zero)));
+    }
+    mutations
+}
+
+/// Generates all mutants of the functions of `module` for which `include_function` holds.
+pub fn module_mutants(
+    module: &CompiledModule,
+    include_function: impl Fn(&IdentStr) -> bool,
+) -> Vec<Mutant> {
+    let module_id = module.self_id();
+    let mut mutants = vec![];
+    for (idx, fdef) in module.function_defs().iter().enumerate() {
+        let function_name = module.identifier_at(module.function_handle_at(fdef.function).name);
+        let code = match &fdef.code {
+            Some(code) if include_function(function_name) => code,
+            _ => continue,
+        };
+        for (offset, instr) in code.code.iter().enumerate() {
+            for (kind, replacement) in mutations_of(instr) {
+                mutants.push(Mutant {
+                    module_id: module_id.clone(),
+                    function_name: function_name.to_owned(),
+                    function_index: FunctionDefinitionIndex(idx as u16),
+                    code_offset: offset as CodeOffset,
+                    kind,
+                    original: instr.clone(),
+                    replacement,
+                });
+            }
+        }
+    }
+    mutants
+}
+
+/// Runs the tests in `test_plan` against every mutant of the functions in `targets`, a map from
+/// module to the names of the functions to mutate. Fails if the tests do not pass against the
+/// unmutated package, as every mutant would then be reported as killed.
+pub fn run_mutation_tests(
+    test_plan: &TestPlan,
+    targets: &BTreeMap<ModuleId, Vec<Identifier>>,
+    native_function_table: Option<NativeFunctionTable>,
+    cost_table: Option<CostTable>,
+    config: &MutationTestingConfig,
+) -> Result<MutationReport> {
+    if !run_tests(
+        test_plan.clone(),
+        native_function_table.clone(),
+        cost_table.clone(),
+        config,
+    )? {
+        bail!("Tests fail against the unmutated package, fix them before running mutation tests")
+    }
+
+    let mut mutants = vec![];
+    for (module_id, functions) in targets {
+        if let Some(info) = test_plan.module_info.get(module_id) {
+            mutants.extend(module_mutants(&info.module, |name| {
+                functions.iter().any(|f| f.as_ident_str() == name)
+            }));
+        }
+    }
+
+    let results = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.num_threads)
+        .build()?
+        .install(|| {
+            mutants
+                .into_par_iter()
+                .map(|mutant| {
+                    let killed = run_mutant(
+                        test_plan,
+                        &mutant,
+                        native_function_table.clone(),
+                        cost_table.clone(),
+                        config,
+                    )?;
+                    let location = mutant_location(test_plan, &mutant);
+                    Ok(MutantResult {
+                        mutant,
+                        killed,
+                        location,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+    Ok(MutationReport { results })
+}
+
+/// Returns whether any test fails against `mutant`.
+fn run_mutant(
+    test_plan: &TestPlan,
+    mutant: &Mutant,
+    native_function_table: Option<NativeFunctionTable>,
+    cost_table: Option<CostTable>,
+    config: &MutationTestingConfig,
+) -> Result<bool> {
+    let mut mutated_plan = test_plan.clone();
+    let info = mutated_plan
+        .module_info
+        .get_mut(&mutant.module_id)
+        .expect("mutated module is part of the test plan");
+    info.module = mutant.apply(&info.module);
+    let passed = run_tests(mutated_plan, native_function_table, cost_table, config)?;
+    Ok(!passed)
+}
+
+/// Returns whether all tests in `test_plan` pass.
+fn run_tests(
+    test_plan: TestPlan,
+    native_function_table: Option<NativeFunctionTable>,
+    cost_table: Option<CostTable>,
+    config: &MutationTestingConfig,
+) -> Result<bool> {
+    let mut test_runner = TestRunner::new(
+        config.execution_bound,
+        // Mutants are already tested in parallel
+        1,
+        false,
+        false,
+        test_plan,
+        native_function_table,
+        cost_table,
+        false,
+        // Every mutant is tested with the same generated inputs
+        FuzzConfig::default(),
+        None,
+        #[cfg(feature = "evm-backend")]
+        false,
+    )?;
+    if let Some(filter) = &config.test_filter {
+        test_runner.filter(filter)
+    }
+    // Test output is not interesting, only whether all tests passed
+    let sink = Mutex::new(std::io::sink());
+    test_runner.run(&sink)?.summarize(&sink)
+}
+
+fn mutant_location(test_plan: &TestPlan, mutant: &Mutant) -> Option<String> {
+    let loc = test_plan
+        .module_info
+        .get(&mutant.module_id)?
+        .source_map
+        .get_code_location(mutant.function_index, mutant.code_offset)
+        .ok()?;
+    let (file_name, contents) = file_for_hash(&test_plan.files, loc)?;
+    let prefix = contents.get(..loc.start() as usize)?;
+    let line = prefix.matches('\n').count() + 1;
+    let column = prefix.len() - prefix.rfind('\n').map_or(0, |i| i + 1) + 1;
+    Some(format!("{}:{}:{}", file_name, line, column))
+}
+
+fn file_for_hash(
+    files: &FilesSourceText,
+    loc: move_ir_types::location::Loc,
+) -> Option<(&str, &str)> {
+    files
+        .get(&loc.file_hash())
+        .map(|(name, contents)| (name.as_str(), contents.as_str()))
+}
+
+#[test]
+fn mutants_preserve_instruction_count() {
+    use move_binary_format::file_format::basic_test_module;
+
+    let mut module = basic_test_module();
+    module.function_defs[0].code.as_mut().unwrap().code = vec![
+        Bytecode::LdU64(0),
+        Bytecode::LdU64(1),
+        Bytecode::Add,
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ];
+    let mutants = module_mutants(&module, |_| true);
+    let replacements = mutants
+        .iter()
+        .map(|m| (m.code_offset, m.replacement.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(replacements, vec![
+        (0, Bytecode::LdU64(1)),
+        (1, Bytecode::LdU64(2)),
+        (1, Bytecode::LdU64(0)),
+        (2, Bytecode::Sub),
+    ]);
+
+    let mutated = mutants[3].apply(&module);
+    let code = &mutated.function_defs[0].code.as_ref().unwrap().code;
+    assert_eq!(code.len(), 5);
+    assert_eq!(code[2], Bytecode::Sub);
+    assert!(module_mutants(&module, |name| name.as_str() != "foo").is_empty());
+}
@@ -2,7 +2,7 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::format_module_id;
+use crate::{format_module_id, fuzz::is_fuzz_test};
 use codespan_reporting::files::{Files, SimpleFiles};
 use colored::{control, Colorize};
 use move_binary_format::{
@@ -58,6 +58,8 @@ pub struct TestFailure {
     pub vm_error: Option<VMError>,
     pub failure_reason: FailureReason,
     pub storage_state: Option<String>,
+    /// The minimal input for which a fuzz test fails
+    pub failing_input: Option<String>,
 }
 
 #[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
@@ -155,9 +157,15 @@ impl TestFailure {
             vm_error,
             failure_reason,
             storage_state,
+            failing_input: None,
         }
     }
 
+    pub fn with_failing_input(mut self, failing_input: Option<String>) -> Self {
+        self.failing_input = failing_input;
+        self
+    }
+
     pub fn render_error(&self, test_plan: &TestPlan) -> String {
         let error_string = match &self.failure_reason {
             FailureReason::NoError(message) => message.to_string(),
@@ -234,6 +242,14 @@ impl TestFailure {
             },
         };
 
+        let error_string = match &self.failing_input {
+            None => error_string,
+            Some(failing_input) => format!(
+                "{}\n────── Minimal failing input ──────\n{}",
+                error_string, failing_input
+            ),
+        };
+
         match &self.storage_state {
             None => error_string,
             Some(storage_state) => {
@@ -445,6 +461,31 @@ impl TestResults {
         }
     }
 
+    /// Returns the gas used by each passing test, by fully qualified test name. Tests run with
+    /// generated inputs are left out since the gas they use depends on the inputs.
+    pub fn gas_used(&self) -> BTreeMap<String, u64> {
+        let mut gas_used = BTreeMap::new();
+        for (module_id, test_results) in &self.final_statistics.passed {
+            let module_tests = self.test_plan.module_tests.get(module_id);
+            for test_result in test_results {
+                let is_fuzz_test = module_tests
+                    .and_then(|plan| plan.tests.get(&test_result.function_ident))
+                    .map_or(false, is_fuzz_test);
+                if !is_fuzz_test {
+                    gas_used.insert(
+                        format!(
+                            "{}::{}",
+                            format_module_id(module_id),
+                            test_result.function_ident
+                        ),
+                        test_result.instructions_executed,
+                    );
+                }
+            }
+        }
+        gas_used
+    }
+
     pub fn report_goldens<W: Write>(&self, writer: &Mutex<W>) -> Result<()> {
         for (module_name, test_outputs) in self.final_statistics.output.iter() {
             for (test_name, write_set) in test_outputs.iter() {
@@ -4,6 +4,7 @@
 
 use crate::{
     extensions, format_module_id,
+    fuzz::{self, FuzzConfig},
     test_reporter::{
         FailureReason, MoveError, TestFailure, TestResults, TestRunInfo, TestStatistics,
     },
@@ -17,20 +18,28 @@ use move_core_types::{
     account_address::AccountAddress,
     effects::{ChangeSet, Op},
     identifier::IdentStr,
-    value::serialize_values,
+    language_storage::ModuleId,
+    value::{serialize_values, MoveValue},
     vm_status::StatusCode,
 };
 use move_resource_viewer::MoveValueAnnotator;
 use move_vm_runtime::{
-    move_vm::MoveVM, native_extensions::NativeContextExtensions,
+    move_vm::MoveVM,
+    native_extensions::NativeContextExtensions,
     native_functions::NativeFunctionTable,
+    session::{SerializedReturnValues, Session},
 };
 use move_vm_test_utils::{
     gas_schedule::{zero_cost_schedule, CostTable, Gas, GasCost, GasStatus},
     InMemoryStorage,
 };
 use rayon::prelude::*;
-use std::{io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    io::Write,
+    marker::Send,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 #[cfg(feature = "evm-backend")]
 use {
     evm::{backend::MemoryVicinity, ExitReason},
@@ -52,11 +61,27 @@ pub struct SharedTestingConfig {
     #[allow(dead_code)] // used by some features
     source_files: Vec<String>,
     record_writeset: bool,
+    fuzz_config: FuzzConfig,
+    gas_meter: Option<Arc<dyn GasProfilingMeter>>,
 
     #[cfg(feature = "evm-backend")]
     evm: bool,
 }
 
+/// Executes test functions with a gas meter other than the unit cost one, which only bounds the
+/// execution of a test, e.g. to measure the gas a test uses with a production gas schedule.
+pub trait GasProfilingMeter: Send + Sync {
+    /// Executes `function_name` in `session`, returning its result and the gas it used, in the
+    /// units in which gas is charged to the user, e.g., the external gas units of a transaction.
+    fn execute_function(
+        &self,
+        session: &mut Session,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        args: Vec<Vec<u8>>,
+    ) -> (VMResult<SerializedReturnValues>, u64);
+}
+
 pub struct TestRunner {
     num_threads: usize,
     testing_config: SharedTestingConfig,
@@ -120,6 +145,24 @@ fn print_resources_and_extensions(
     Ok(buf)
 }
 
+/// Returns whether a run of a test had the expected outcome.
+fn test_passed(test_info: &TestCase, exec_result: &VMResult<Vec<Vec<u8>>>) -> bool {
+    let err = match exec_result {
+        Ok(_) => return test_info.expected_failure.is_none(),
+        Err(err) => err,
+    };
+    match &test_info.expected_failure {
+        None => false,
+        Some(ExpectedFailure::Expected) => true,
+        Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
+            expected_err == &MoveError(err.major_status(), err.sub_status(), err.location().clone())
+        },
+        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code)) => {
+            err.major_status() == StatusCode::ABORTED && err.sub_status() == Some(*code)
+        },
+    }
+}
+
 impl TestRunner {
     pub fn new(
         execution_bound: u64,
@@ -132,6 +175,8 @@ impl TestRunner {
         native_function_table: Option<NativeFunctionTable>,
         cost_table: Option<CostTable>,
         record_writeset: bool,
+        fuzz_config: FuzzConfig,
+        gas_meter: Option<Arc<dyn GasProfilingMeter>>,
         #[cfg(feature = "evm-backend")] evm: bool,
     ) -> Result<Self> {
         let source_files = tests
@@ -162,6 +207,8 @@ impl TestRunner {
                 cost_table: cost_table.unwrap_or_else(unit_cost_table),
                 source_files,
                 record_writeset,
+                fuzz_config,
+                gas_meter,
                 #[cfg(feature = "evm-backend")]
                 evm,
             },
@@ -252,7 +299,7 @@ impl SharedTestingConfig {
         &self,
         test_plan: &ModuleTestPlan,
         function_name: &str,
-        test_info: &TestCase,
+        arguments: &[MoveValue],
     ) -> (
         VMResult<ChangeSet>,
         VMResult<NativeContextExtensions>,
@@ -263,17 +310,35 @@ impl SharedTestingConfig {
         let extensions = extensions::new_extensions();
         let mut session =
             move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
-        let mut gas_meter = GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
 
         let now = Instant::now();
-        let serialized_return_values_result = session.execute_function_bypass_visibility(
-            &test_plan.module_id,
-            IdentStr::new(function_name).unwrap(),
-            vec![], // no ty args, at least for now
-            serialize_values(test_info.arguments.iter()),
-            &mut gas_meter,
-        );
+        let (serialized_return_values_result, gas_used) = match &self.gas_meter {
+            Some(gas_meter) => gas_meter.execute_function(
+                &mut session,
+                &test_plan.module_id,
+                IdentStr::new(function_name).unwrap(),
+                serialize_values(arguments),
+            ),
+            None => {
+                let mut gas_meter =
+                    GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
+                let result = session.execute_function_bypass_visibility(
+                    &test_plan.module_id,
+                    IdentStr::new(function_name).unwrap(),
+                    vec![], // no ty args, at least for now
+                    serialize_values(arguments),
+                    &mut gas_meter,
+                );
+                // TODO(Gas): This doesn't look quite right...
+                //            We're not computing the number of instructions executed even with a unit gas schedule.
+                let gas_used = Gas::new(self.execution_bound)
+                    .checked_sub(gas_meter.remaining_gas())
+                    .unwrap()
+                    .into();
+                (result, gas_used)
+            },
+        };
         let mut return_result = serialized_return_values_result.map(|res| {
             res.return_values
                 .into_iter()
@@ -285,22 +350,55 @@ impl SharedTestingConfig {
                 err.remove_exec_state();
             }
         }
-        let test_run_info = TestRunInfo::new(
-            function_name.to_string(),
-            now.elapsed(),
-            // TODO(Gas): This doesn't look quite right...
-            //            We're not computing the number of instructions executed even with a unit gas schedule.
-            Gas::new(self.execution_bound)
-                .checked_sub(gas_meter.remaining_gas())
-                .unwrap()
-                .into(),
-        );
+        let test_run_info = TestRunInfo::new(function_name.to_string(), now.elapsed(), gas_used);
         match session.finish_with_extensions() {
             Ok((cs, extensions)) => (Ok(cs), Ok(extensions), return_result, test_run_info),
             Err(err) => (Err(err.clone()), Err(err), return_result, test_run_info),
         }
     }
 
+    /// Runs a fuzz test with generated inputs until it fails or the configured number of
+    /// iterations is reached. Returns the arguments of the run reported as the result of the test
+    /// and, if a failing input was found, a description of that input after shrinking it.
+    fn fuzz_test_arguments(
+        &self,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+    ) -> (Vec<MoveValue>, Option<String>) {
+        let full_name = format!(
+            "{}::{}",
+            format_module_id(&test_plan.module_id),
+            function_name
+        );
+        let mut rng = self.fuzz_config.rng_for_test(&full_name);
+        let fails = |inputs: &[MoveValue]| {
+            let arguments = fuzz::test_arguments(test_info, inputs);
+            let (_, _, exec_result, _) =
+                self.execute_via_move_vm(test_plan, function_name, &arguments);
+            !test_passed(test_info, &exec_result)
+        };
+
+        let mut inputs = vec![];
+        for run in 1..=self.fuzz_config.iterations.max(1) {
+            inputs = fuzz::generate_inputs(&mut rng, &test_info.fuzz_parameters);
+            if fails(&inputs) {
+                let minimal_inputs = fuzz::shrink_inputs(inputs, &fails);
+                let description = format!(
+                    "{}\nFound with seed {} after {} run(s)",
+                    fuzz::format_inputs(&test_info.fuzz_parameters, &minimal_inputs),
+                    self.fuzz_config.seed,
+                    run
+                );
+                return (
+                    fuzz::test_arguments(test_info, &minimal_inputs),
+                    Some(description),
+                );
+            }
+        }
+        (fuzz::test_arguments(test_info, &inputs), None)
+    }
+
     fn exec_module_tests_move_vm_and_stackless_vm(
         &self,
         test_plan: &ModuleTestPlan,
@@ -309,8 +407,13 @@ impl SharedTestingConfig {
         let mut stats = TestStatistics::new();
 
         for (function_name, test_info) in &test_plan.tests {
+            let (arguments, failing_input) = if fuzz::is_fuzz_test(test_info) {
+                self.fuzz_test_arguments(test_plan, function_name, test_info)
+            } else {
+                (test_info.arguments.clone(), None)
+            };
             let (cs_result, ext_result, exec_result, test_run_info) =
-                self.execute_via_move_vm(test_plan, function_name, test_info);
+                self.execute_via_move_vm(test_plan, function_name, &arguments);
 
             if self.record_writeset {
                 stats.test_output(
@@ -369,7 +472,8 @@ impl SharedTestingConfig {
                                     test_run_info,
                                     Some(err),
                                     save_session_state(),
-                                ),
+                                )
+                                .with_failing_input(failing_input),
                                 test_plan,
                             )
                         },
@@ -384,7 +488,8 @@ impl SharedTestingConfig {
                                     test_run_info,
                                     Some(err),
                                     save_session_state(),
-                                ),
+                                )
+                                .with_failing_input(failing_input),
                                 test_plan,
                             )
                         },
@@ -397,7 +502,8 @@ impl SharedTestingConfig {
                                     test_run_info,
                                     Some(err),
                                     save_session_state(),
-                                ),
+                                )
+                                .with_failing_input(failing_input),
                                 test_plan,
                             )
                         },
@@ -409,7 +515,8 @@ impl SharedTestingConfig {
                                     test_run_info,
                                     Some(err),
                                     save_session_state(),
-                                ),
+                                )
+                                .with_failing_input(failing_input),
                                 test_plan,
                             )
                         },
@@ -425,7 +532,8 @@ impl SharedTestingConfig {
                                 test_run_info,
                                 None,
                                 save_session_state(),
-                            ),
+                            )
+                            .with_failing_input(failing_input),
                             test_plan,
                         )
                     } else {
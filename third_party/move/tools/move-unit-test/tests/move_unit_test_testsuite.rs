@@ -90,6 +90,8 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
             .collect(),
         verbose: true,
         report_stacktrace_on_abort: true,
+        // Inputs of tests with unassigned parameters must not change between runs
+        seed: Some(0),
 
         ..UnitTestingConfig::default_with_bound(None)
     };
@@ -0,0 +1,34 @@
+address 0x1 {
+module M {
+    #[test_only]
+    use std::signer;
+    #[test_only]
+    use std::vector;
+
+    #[test]
+    fun addition_commutes(a: u64, b: u64) {
+        let (a, b) = (a / 2, b / 2);
+        assert!(a + b == b + a, 0);
+    }
+
+    #[test(s = @0x42)]
+    fun assigned_and_generated(n: u8, s: signer, v: vector<address>) {
+        assert!(signer::address_of(&s) == @0x42, 0);
+        vector::push_back(&mut v, @0x1);
+        assert!(vector::length(&v) > ((n / 255) as u64), 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1, location = Self)]
+    fun aborts_for_all_inputs(x: u128, flag: bool) {
+        if (flag) abort 1;
+        if (x >= 0) abort 1
+    }
+
+    #[test]
+    #[expected_failure]
+    fun aborts_for_no_input(a: u64, v: vector<u8>) {
+        vector::push_back(&mut v, ((a % 256) as u8));
+    }
+}
+}
@@ -16,6 +16,7 @@ use std::{
 };
 
 const MULTISIG_ACCOUNT_DOMAIN_SEPARATOR: &[u8] = b"aptos_framework::multisig_account";
+const OBJECT_CODE_DEPLOYMENT_DOMAIN_SEPARATOR: &[u8] = b"aptos_framework::object_code_deployment";
 const STAKING_CONTRACT_DOMAIN_SEPARATOR: &[u8] = b"aptos_framework::staking_contract";
 const VESTING_POOL_DOMAIN_SEPARATOR: &[u8] = b"aptos_framework::vesting";
 
@@ -181,6 +182,17 @@ pub fn create_object_address(creator: AccountAddress, seed: &[u8]) -> AccountAdd
     AccountAddress::from_bytes(hash.as_ref()).unwrap()
 }
 
+/// Address of the object created by `object_code_deployment::publish`, where `creator_sequence_number`
+/// is the sequence number of the creator account after the publish transaction.
+pub fn create_object_code_deployment_address(
+    creator: AccountAddress,
+    creator_sequence_number: u64,
+) -> AccountAddress {
+    let mut seed = bcs::to_bytes(&OBJECT_CODE_DEPLOYMENT_DOMAIN_SEPARATOR).unwrap();
+    seed.extend(bcs::to_bytes(&creator_sequence_number).unwrap());
+    create_object_address(creator, &seed)
+}
+
 pub fn default_owner_stake_pool_address(owner: AccountAddress) -> AccountAddress {
     default_stake_pool_address(owner, owner)
 }
@@ -0,0 +1,78 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_metadata::BlockMetadata;
+use aptos_crypto::HashValue;
+use move_core_types::{account_address::AccountAddress, value::MoveValue};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+/// Block metadata that carries more than [`BlockMetadata`]. It is persisted as its own
+/// transaction type, so the format of the original block metadata transaction stays unchanged.
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockMetadataExt {
+    /// Block metadata with the randomness seed of the block, executed by
+    /// `block::block_prologue_ext`. See `crate::randomness` for how the seed is derived.
+    V1(BlockMetadataWithRandomness),
+}
+
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMetadataWithRandomness {
+    block_metadata: BlockMetadata,
+    randomness_seed: HashValue,
+}
+
+impl BlockMetadataExt {
+    pub fn new_v1(block_metadata: BlockMetadata, randomness_seed: HashValue) -> Self {
+        Self::V1(BlockMetadataWithRandomness {
+            block_metadata,
+            randomness_seed,
+        })
+    }
+
+    pub fn block_metadata(&self) -> &BlockMetadata {
+        match self {
+            BlockMetadataExt::V1(v1) => &v1.block_metadata,
+        }
+    }
+
+    pub fn randomness_seed(&self) -> HashValue {
+        match self {
+            BlockMetadataExt::V1(v1) => v1.randomness_seed,
+        }
+    }
+
+    pub fn id(&self) -> HashValue {
+        self.block_metadata().id()
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.block_metadata().epoch()
+    }
+
+    pub fn round(&self) -> u64 {
+        self.block_metadata().round()
+    }
+
+    pub fn timestamp_usecs(&self) -> u64 {
+        self.block_metadata().timestamp_usecs()
+    }
+
+    pub fn proposer(&self) -> AccountAddress {
+        self.block_metadata().proposer()
+    }
+
+    /// Returns the arguments of `block::block_prologue_ext`.
+    pub fn get_prologue_move_args(self, signer: AccountAddress) -> Vec<MoveValue> {
+        match self {
+            BlockMetadataExt::V1(v1) => {
+                let mut args = v1.block_metadata.get_prologue_move_args(signer);
+                args.push(MoveValue::vector_u8(v1.randomness_seed.to_vec()));
+                args
+            },
+        }
+    }
+}
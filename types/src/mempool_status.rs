@@ -59,11 +59,13 @@ pub enum MempoolStatusCode {
     MempoolIsFull = 2,
     // Account reached max capacity per account
     TooManyTransactions = 3,
-    // Invalid update. Only gas price increase is allowed
+    // Invalid update. Only a sufficient gas price increase is allowed
     InvalidUpdate = 4,
     // transaction didn't pass vm_validation
     VmError = 5,
     UnknownStatus = 6,
+    // Transaction replaced the transaction with the same sender and sequence number
+    Replaced = 7,
 }
 
 impl TryFrom<u64> for MempoolStatusCode {
@@ -78,6 +80,7 @@ impl TryFrom<u64> for MempoolStatusCode {
             4 => Ok(MempoolStatusCode::InvalidUpdate),
             5 => Ok(MempoolStatusCode::VmError),
             6 => Ok(MempoolStatusCode::UnknownStatus),
+            7 => Ok(MempoolStatusCode::Replaced),
             _ => Err("invalid StatusCode"),
         }
     }
@@ -123,6 +123,8 @@ pub enum Protocol {
     // probably need to move network wire into its own crate to avoid circular
     // dependency b/w network and types.
     Handshake(u8),
+    // QUIC over UDP on the given port
+    Quic(u16),
 }
 
 /// A minimally parsed DNS name. We don't really do any checking other than
@@ -193,11 +195,11 @@ pub enum ParseError {
     NetworkLayerMissing,
 
     #[error(
-        "NetworkAddress must start with one of Protocol::Ip4/Ip6/Dns/Dns4/Dns6 followed by TCP"
+        "NetworkAddress must start with one of Protocol::Ip4/Ip6/Dns/Dns4/Dns6 followed by TCP or QUIC"
     )]
     TransportLayerMissing,
 
-    #[error("NetworkAddress must have a NoiseIK protocol following the TCP protocol")]
+    #[error("NetworkAddress must have a NoiseIK protocol following the TCP or QUIC protocol")]
     SessionLayerMissing,
 
     #[error("NetworkAddress must have a Handshake protocol following the NoiseIK protocol")]
@@ -237,7 +239,7 @@ fn is_network_layer(p: Option<&Protocol>) -> bool {
 fn is_transport_layer(p: Option<&Protocol>) -> bool {
     use Protocol::*;
 
-    matches!(p, Some(Tcp(_)))
+    matches!(p, Some(Tcp(_)) | Some(Quic(_)))
 }
 
 fn is_session_layer(p: Option<&Protocol>, allow_empty: bool) -> bool {
@@ -356,6 +358,7 @@ impl NetworkAddress {
     /// `"/dns4/<domain>/tcp/<port>"` or
     /// `"/dns6/<domain>/tcp/<port>"` or
     /// `"/dns/<domain>/tcp/<port>"` or
+    /// any of the above with `"/quic/<port>"` in place of `"/tcp/<port>"` or
     /// cfg!(test) `"/memory/<port>"`
     ///
     /// followed by transport upgrade handshake protocols:
@@ -388,7 +391,7 @@ impl NetworkAddress {
     /// Retrieves the port from the network address
     pub fn find_port(&self) -> Option<u16> {
         self.0.iter().find_map(|proto| match proto {
-            Protocol::Tcp(port) => Some(*port),
+            Protocol::Tcp(port) | Protocol::Quic(port) => Some(*port),
             _ => None,
         })
     }
@@ -613,6 +616,7 @@ impl fmt::Display for Protocol {
                     .expect("ValidCryptoMaterialStringExt::to_encoded_string is infallible")
             ),
             Handshake(version) => write!(f, "/handshake/{}", version),
+            Quic(port) => write!(f, "/quic/{}", port),
         }
     }
 }
@@ -643,6 +647,7 @@ impl Protocol {
                 args.next().ok_or(ParseError::UnexpectedEnd)?,
             )?),
             "handshake" => Protocol::Handshake(parse_one(args)?),
+            "quic" => Protocol::Quic(parse_one(args)?),
             unknown => return Err(ParseError::UnknownProtocolType(unknown.to_string())),
         };
         Ok(protocol)
@@ -819,6 +824,42 @@ pub fn parse_dns_tcp(protos: &[Protocol]) -> Option<((IpFilter, &DnsName, u16),
     }
 }
 
+/// parse the `&[Protocol]` into the `"/ip4/<addr>/quic/<port>"` or
+/// `"/ip6/<addr>/quic/<port>"` prefix and unparsed `&[Protocol]` suffix.
+pub fn parse_ip_quic(protos: &[Protocol]) -> Option<((IpAddr, u16), &[Protocol])> {
+    use Protocol::*;
+
+    if protos.len() < 2 {
+        return None;
+    }
+
+    let (prefix, suffix) = protos.split_at(2);
+    match prefix {
+        [Ip4(ip), Quic(port)] => Some(((IpAddr::V4(*ip), *port), suffix)),
+        [Ip6(ip), Quic(port)] => Some(((IpAddr::V6(*ip), *port), suffix)),
+        _ => None,
+    }
+}
+
+/// parse the `&[Protocol]` into the `"/dns/<domain>/quic/<port>"`,
+/// `"/dns4/<domain>/quic/<port>"`, or `"/dns6/<domain>/quic/<port>"` prefix and
+/// unparsed `&[Protocol]` suffix.
+pub fn parse_dns_quic(protos: &[Protocol]) -> Option<((IpFilter, &DnsName, u16), &[Protocol])> {
+    use Protocol::*;
+
+    if protos.len() < 2 {
+        return None;
+    }
+
+    let (prefix, suffix) = protos.split_at(2);
+    match prefix {
+        [Dns(name), Quic(port)] => Some(((IpFilter::Any, name, *port), suffix)),
+        [Dns4(name), Quic(port)] => Some(((IpFilter::OnlyIp4, name, *port), suffix)),
+        [Dns6(name), Quic(port)] => Some(((IpFilter::OnlyIp6, name, *port), suffix)),
+        _ => None,
+    }
+}
+
 pub fn parse_tcp(protos: &[Protocol]) -> Option<((String, u16), &[Protocol])> {
     use Protocol::*;
 
@@ -863,11 +904,15 @@ fn parse_aptosnet_protos(protos: &[Protocol]) -> Option<&[Protocol]> {
     // ---
     // parse_ip_tcp
     // <or> parse_dns_tcp
+    // <or> parse_ip_quic
+    // <or> parse_dns_quic
     // <or> cfg!(test) parse_memory
 
     let transport_suffix = parse_ip_tcp(protos)
         .map(|x| x.1)
         .or_else(|| parse_dns_tcp(protos).map(|x| x.1))
+        .or_else(|| parse_ip_quic(protos).map(|x| x.1))
+        .or_else(|| parse_dns_quic(protos).map(|x| x.1))
         .or_else(|| {
             if cfg!(test) {
                 parse_memory(protos).map(|x| x.1)
@@ -965,6 +1010,22 @@ mod test {
                 NoiseIK(pubkey),
                 Handshake(5),
             ]),
+            ("/ip4/1.2.3.4/quic/6180", vec![
+                Ip4(Ipv4Addr::new(1, 2, 3, 4)),
+                Quic(6180),
+            ]),
+            (
+                &(format!(
+                    "/dns/example.com/quic/6180/noise-ik/{}/handshake/0",
+                    pubkey_str
+                )),
+                vec![
+                    Dns(DnsName("example.com".to_owned())),
+                    Quic(6180),
+                    NoiseIK(pubkey),
+                    Handshake(0),
+                ],
+            ),
         ];
 
         for (addr_str, expected_address) in &test_cases {
@@ -988,6 +1049,9 @@ mod test {
             "/tcp/1234/",
             "/tcp/1234/foobar/5",
             "/tcp/99999",
+            "/quic",
+            "/quic/99999",
+            "/ip4/1.2.3.4/tcp/123/quic/123",
             "/ip4/1.1.1",
             "/ip4/1.1.1.1.",
             "/ip4/1.1.1.1.1",
@@ -1031,6 +1095,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_ip_quic() {
+        let addr = NetworkAddress::from_str("/ip4/1.2.3.4/quic/123").unwrap();
+        let expected_suffix: &[Protocol] = &[];
+        assert_eq!(
+            parse_ip_quic(addr.as_slice()).unwrap(),
+            ((IpAddr::from_str("1.2.3.4").unwrap(), 123), expected_suffix)
+        );
+        assert!(parse_ip_tcp(addr.as_slice()).is_none());
+
+        let addr = NetworkAddress::from_str("/dns6/example.com/quic/123").unwrap();
+        let dns_name = DnsName::from_str("example.com").unwrap();
+        assert_eq!(
+            parse_dns_quic(addr.as_slice()).unwrap(),
+            ((IpFilter::OnlyIp6, &dns_name, 123), expected_suffix)
+        );
+        assert!(parse_dns_tcp(addr.as_slice()).is_none());
+    }
+
     #[test]
     fn test_parse_dns_tcp() {
         let dns_name = DnsName::from_str("example.com").unwrap();
@@ -40,6 +40,13 @@ pub enum FeatureFlag {
     AGGREGATOR_SNAPSHOTS = 30,
     SAFER_RESOURCE_GROUPS = 31,
     SAFER_METADATA = 32,
+    ON_CHAIN_RANDOMNESS = 33,
+    TRANSACTION_CONTEXT_EXTENSION = 34,
+    CONCURRENT_FUNGIBLE_BALANCE = 35,
+    DISPATCHABLE_FUNGIBLE_ASSET = 36,
+    OBJECT_CODE_DEPLOYMENT = 37,
+    BASE_FEE_MARKET = 38,
+    VM_BINARY_FORMAT_V7 = 39,
 }
 
 /// Representation of features on chain as a bitset.
@@ -96,6 +103,30 @@ impl Features {
     pub fn is_aggregator_snapshots_enabled(&self) -> bool {
         self.is_enabled(FeatureFlag::AGGREGATOR_SNAPSHOTS)
     }
+
+    pub fn is_on_chain_randomness_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::ON_CHAIN_RANDOMNESS)
+    }
+
+    pub fn is_transaction_context_extension_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::TRANSACTION_CONTEXT_EXTENSION)
+    }
+
+    pub fn is_concurrent_fungible_balance_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::CONCURRENT_FUNGIBLE_BALANCE)
+    }
+
+    pub fn is_dispatchable_fungible_asset_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::DISPATCHABLE_FUNGIBLE_ASSET)
+    }
+
+    pub fn is_object_code_deployment_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::OBJECT_CODE_DEPLOYMENT)
+    }
+
+    pub fn is_base_fee_market_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::BASE_FEE_MARKET)
+    }
 }
 
 // --------------------------------------------------------------------------------------------
@@ -13,6 +13,10 @@ use std::collections::HashMap;
 pub enum OnChainConsensusConfig {
     V1(ConsensusConfigV1),
     V2(ConsensusConfigV1),
+    V3 {
+        alg: ConsensusConfigV1,
+        vtxn: ValidatorTxnConfig,
+    },
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -20,9 +24,9 @@ impl OnChainConsensusConfig {
     /// The number of recent rounds that don't count into reputations.
     pub fn leader_reputation_exclude_round(&self) -> u64 {
         match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                config.exclude_round
-            },
+            OnChainConsensusConfig::V1(config)
+            | OnChainConsensusConfig::V2(config)
+            | OnChainConsensusConfig::V3 { alg: config, .. } => config.exclude_round,
         }
     }
 
@@ -35,18 +39,18 @@ impl OnChainConsensusConfig {
     // to this max size.
     pub fn max_failed_authors_to_store(&self) -> usize {
         match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                config.max_failed_authors_to_store
-            },
+            OnChainConsensusConfig::V1(config)
+            | OnChainConsensusConfig::V2(config)
+            | OnChainConsensusConfig::V3 { alg: config, .. } => config.max_failed_authors_to_store,
         }
     }
 
     // Type and configuration used for proposer election.
     pub fn proposer_election_type(&self) -> &ProposerElectionType {
         match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                &config.proposer_election_type
-            },
+            OnChainConsensusConfig::V1(config)
+            | OnChainConsensusConfig::V2(config)
+            | OnChainConsensusConfig::V3 { alg: config, .. } => &config.proposer_election_type,
         }
     }
 
@@ -54,8 +58,23 @@ impl OnChainConsensusConfig {
         match &self {
             OnChainConsensusConfig::V1(_config) => false,
             OnChainConsensusConfig::V2(_config) => true,
+            OnChainConsensusConfig::V3 { .. } => true,
         }
     }
+
+    /// The validator transaction config, which is disabled before V3.
+    pub fn effective_validator_txn_config(&self) -> ValidatorTxnConfig {
+        match &self {
+            OnChainConsensusConfig::V1(_) | OnChainConsensusConfig::V2(_) => {
+                ValidatorTxnConfig::default_disabled()
+            },
+            OnChainConsensusConfig::V3 { vtxn, .. } => vtxn.clone(),
+        }
+    }
+
+    pub fn is_vtxn_enabled(&self) -> bool {
+        self.effective_validator_txn_config().enabled()
+    }
 }
 
 /// This is used when on-chain config is not initialized.
@@ -120,6 +139,58 @@ impl Default for ConsensusConfigV1 {
     }
 }
 
+/// Whether validator transactions (see `crate::validator_txn`) are enabled, and how much of a
+/// block they may take.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum ValidatorTxnConfig {
+    /// Disabled. Proposals must not carry validator transactions.
+    V0,
+    V1 {
+        per_block_limit_txn_count: u64,
+        per_block_limit_total_bytes: u64,
+    },
+}
+
+impl ValidatorTxnConfig {
+    pub fn default_disabled() -> Self {
+        Self::V0
+    }
+
+    pub fn default_enabled() -> Self {
+        Self::V1 {
+            per_block_limit_txn_count: 2,
+            per_block_limit_total_bytes: 1024 * 1024,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        match self {
+            ValidatorTxnConfig::V0 => false,
+            ValidatorTxnConfig::V1 { .. } => true,
+        }
+    }
+
+    pub fn per_block_limit_txn_count(&self) -> u64 {
+        match self {
+            ValidatorTxnConfig::V0 => 0,
+            ValidatorTxnConfig::V1 {
+                per_block_limit_txn_count,
+                ..
+            } => *per_block_limit_txn_count,
+        }
+    }
+
+    pub fn per_block_limit_total_bytes(&self) -> u64 {
+        match self {
+            ValidatorTxnConfig::V0 => 0,
+            ValidatorTxnConfig::V1 {
+                per_block_limit_total_bytes,
+                ..
+            } => *per_block_limit_total_bytes,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum ProposerElectionType {
@@ -210,6 +281,25 @@ mod test {
         bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap();
     }
 
+    #[test]
+    fn test_config_with_validator_txns() {
+        let config = OnChainConsensusConfig::V3 {
+            alg: ConsensusConfigV1::default(),
+            vtxn: ValidatorTxnConfig::default_enabled(),
+        };
+        let s = bcs::to_bytes(&config).unwrap();
+        let result = bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap();
+        assert!(result.quorum_store_enabled());
+        assert!(result.is_vtxn_enabled());
+        assert_eq!(
+            result
+                .effective_validator_txn_config()
+                .per_block_limit_txn_count(),
+            2
+        );
+        assert!(!OnChainConsensusConfig::default().is_vtxn_enabled());
+    }
+
     #[test]
     fn test_config_serialization_non_default() {
         let config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
@@ -14,7 +14,8 @@ pub enum OnChainExecutionConfig {
     /// To maintain backwards compatibility on replay, we must ensure that any new features resolve
     /// to previous behavior (before OnChainExecutionConfig was registered) in case of Missing.
     Missing,
-    // Reminder: Add V4 and future versions here, after Missing (order matters for enums).
+    V4(ExecutionConfigV4),
+    // Reminder: Add V5 and future versions here, after Missing (order matters for enums).
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -26,19 +27,26 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V1(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V2(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V3(config) => config.transaction_shuffler_type.clone(),
+            OnChainExecutionConfig::V4(config) => config.transaction_shuffler_type.clone(),
         }
     }
 
-    /// The per-block gas limit being used.
-    pub fn block_gas_limit(&self) -> Option<u64> {
+    /// The type of the per-block gas limit being used.
+    pub fn block_gas_limit_type(&self) -> BlockGasLimitType {
         match &self {
-            OnChainExecutionConfig::Missing => None,
-            OnChainExecutionConfig::V1(_config) => None,
-            OnChainExecutionConfig::V2(config) => config.block_gas_limit,
-            OnChainExecutionConfig::V3(config) => config.block_gas_limit,
+            OnChainExecutionConfig::Missing => BlockGasLimitType::NoLimit,
+            OnChainExecutionConfig::V1(_config) => BlockGasLimitType::NoLimit,
+            OnChainExecutionConfig::V2(config) => config.block_gas_limit.into(),
+            OnChainExecutionConfig::V3(config) => config.block_gas_limit.into(),
+            OnChainExecutionConfig::V4(config) => config.block_gas_limit_type.clone(),
         }
     }
 
+    /// The per-block gas limit being used, in the effective units of the block gas limit type.
+    pub fn block_gas_limit(&self) -> Option<u64> {
+        self.block_gas_limit_type().block_gas_limit()
+    }
+
     /// The type of the transaction deduper being used.
     pub fn transaction_deduper_type(&self) -> TransactionDeduperType {
         match &self {
@@ -47,15 +55,16 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V1(_config) => TransactionDeduperType::NoDedup,
             OnChainExecutionConfig::V2(_config) => TransactionDeduperType::NoDedup,
             OnChainExecutionConfig::V3(config) => config.transaction_deduper_type.clone(),
+            OnChainExecutionConfig::V4(config) => config.transaction_deduper_type.clone(),
         }
     }
 
     /// The default values to use for new networks, e.g., devnet, forge.
     /// Features that are ready for deployment can be enabled here.
     pub fn default_for_genesis() -> Self {
-        OnChainExecutionConfig::V3(ExecutionConfigV3 {
+        OnChainExecutionConfig::V4(ExecutionConfigV4 {
             transaction_shuffler_type: TransactionShufflerType::SenderAwareV2(32),
-            block_gas_limit: Some(35000),
+            block_gas_limit_type: BlockGasLimitType::Limit(35000),
             transaction_deduper_type: TransactionDeduperType::TxnHashAndAuthenticatorV1,
         })
     }
@@ -103,6 +112,13 @@ pub struct ExecutionConfigV3 {
     pub transaction_deduper_type: TransactionDeduperType,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ExecutionConfigV4 {
+    pub transaction_shuffler_type: TransactionShufflerType,
+    pub block_gas_limit_type: BlockGasLimitType,
+    pub transaction_deduper_type: TransactionDeduperType,
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum TransactionShufflerType {
@@ -118,6 +134,95 @@ pub enum TransactionDeduperType {
     TxnHashAndAuthenticatorV1,
 }
 
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
+pub enum BlockGasLimitType {
+    NoLimit,
+    /// Limit on the sum of the execution and IO gas of the committed transactions.
+    Limit(u64),
+    /// Limit on the weighted sum of the execution gas, IO gas and storage fee of the committed
+    /// transactions, where the contribution of a transaction is further multiplied by one plus
+    /// the number of transactions in the preceding `conflict_penalty_window` it conflicts with,
+    /// i.e. which write to a location the transaction reads.
+    ComplexLimitV1 {
+        /// Limit on the accumulated effective gas of the block.
+        effective_block_gas_limit: u64,
+        /// Weight of a unit of execution gas in effective gas.
+        execution_gas_effective_multiplier: u64,
+        /// Weight of a unit of IO gas in effective gas.
+        io_gas_effective_multiplier: u64,
+        /// Weight of an octa of storage fee in effective gas.
+        storage_fee_effective_multiplier: u64,
+        /// Number of preceding transactions checked for conflicts, 0 disables the penalty.
+        conflict_penalty_window: u32,
+    },
+}
+
+impl BlockGasLimitType {
+    /// The limit on the accumulated effective gas of a block, if any.
+    pub fn block_gas_limit(&self) -> Option<u64> {
+        match self {
+            BlockGasLimitType::NoLimit => None,
+            BlockGasLimitType::Limit(limit) => Some(*limit),
+            BlockGasLimitType::ComplexLimitV1 {
+                effective_block_gas_limit,
+                ..
+            } => Some(*effective_block_gas_limit),
+        }
+    }
+
+    pub fn execution_gas_effective_multiplier(&self) -> u64 {
+        match self {
+            BlockGasLimitType::NoLimit | BlockGasLimitType::Limit(_) => 1,
+            BlockGasLimitType::ComplexLimitV1 {
+                execution_gas_effective_multiplier,
+                ..
+            } => *execution_gas_effective_multiplier,
+        }
+    }
+
+    pub fn io_gas_effective_multiplier(&self) -> u64 {
+        match self {
+            BlockGasLimitType::NoLimit | BlockGasLimitType::Limit(_) => 1,
+            BlockGasLimitType::ComplexLimitV1 {
+                io_gas_effective_multiplier,
+                ..
+            } => *io_gas_effective_multiplier,
+        }
+    }
+
+    /// Storage fees do not count towards the simple limit, which only measures execution cost.
+    pub fn storage_fee_effective_multiplier(&self) -> u64 {
+        match self {
+            BlockGasLimitType::NoLimit | BlockGasLimitType::Limit(_) => 0,
+            BlockGasLimitType::ComplexLimitV1 {
+                storage_fee_effective_multiplier,
+                ..
+            } => *storage_fee_effective_multiplier,
+        }
+    }
+
+    /// The number of preceding transactions checked for conflicts, if the penalty is enabled.
+    pub fn conflict_penalty_window(&self) -> Option<u32> {
+        match self {
+            BlockGasLimitType::NoLimit | BlockGasLimitType::Limit(_) => None,
+            BlockGasLimitType::ComplexLimitV1 {
+                conflict_penalty_window,
+                ..
+            } => (*conflict_penalty_window > 0).then_some(*conflict_penalty_window),
+        }
+    }
+}
+
+impl From<Option<u64>> for BlockGasLimitType {
+    fn from(maybe_block_gas_limit: Option<u64>) -> Self {
+        match maybe_block_gas_limit {
+            Some(limit) => BlockGasLimitType::Limit(limit),
+            None => BlockGasLimitType::NoLimit,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -250,4 +355,40 @@ mod test {
         ));
         assert!(matches!(result.block_gas_limit(), None));
     }
+
+    #[test]
+    fn test_config_v4_serialization() {
+        let config = OnChainExecutionConfig::V4(ExecutionConfigV4 {
+            transaction_shuffler_type: TransactionShufflerType::SenderAwareV2(32),
+            block_gas_limit_type: BlockGasLimitType::ComplexLimitV1 {
+                effective_block_gas_limit: 3_500_000,
+                execution_gas_effective_multiplier: 100,
+                io_gas_effective_multiplier: 100,
+                storage_fee_effective_multiplier: 1,
+                conflict_penalty_window: 8,
+            },
+            transaction_deduper_type: TransactionDeduperType::TxnHashAndAuthenticatorV1,
+        });
+
+        let s = serde_yaml::to_string(&config).unwrap();
+        let result = serde_yaml::from_str::<OnChainExecutionConfig>(&s).unwrap();
+        assert_eq!(result, config);
+        let result = bcs::from_bytes::<OnChainExecutionConfig>(&bcs::to_bytes(&config).unwrap())
+            .unwrap();
+        assert_eq!(result, config);
+
+        let block_gas_limit_type = result.block_gas_limit_type();
+        assert_eq!(result.block_gas_limit(), Some(3_500_000));
+        assert_eq!(block_gas_limit_type.execution_gas_effective_multiplier(), 100);
+        assert_eq!(block_gas_limit_type.storage_fee_effective_multiplier(), 1);
+        assert_eq!(block_gas_limit_type.conflict_penalty_window(), Some(8));
+
+        // Older versions only have the simple limit.
+        let config = OnChainExecutionConfig::V2(ExecutionConfigV2 {
+            transaction_shuffler_type: TransactionShufflerType::SenderAwareV2(32),
+            block_gas_limit: Some(35000),
+        });
+        assert_eq!(config.block_gas_limit_type(), BlockGasLimitType::Limit(35000));
+        assert_eq!(config.block_gas_limit_type().conflict_penalty_window(), None);
+    }
 }
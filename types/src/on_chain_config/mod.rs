@@ -37,16 +37,16 @@ pub use self::{
     },
     consensus_config::{
         ConsensusConfigV1, LeaderReputationType, OnChainConsensusConfig, ProposerAndVoterConfig,
-        ProposerElectionType,
+        ProposerElectionType, ValidatorTxnConfig,
     },
     execution_config::{
-        ExecutionConfigV1, ExecutionConfigV2, OnChainExecutionConfig, TransactionDeduperType,
-        TransactionShufflerType,
+        BlockGasLimitType, ExecutionConfigV1, ExecutionConfigV2, ExecutionConfigV3,
+        ExecutionConfigV4, OnChainExecutionConfig, TransactionDeduperType, TransactionShufflerType,
     },
     gas_schedule::{GasSchedule, GasScheduleV2, StorageGasSchedule},
     timed_features::{TimedFeatureFlag, TimedFeatureOverride, TimedFeatures},
     timestamp::CurrentTimeMicroseconds,
-    transaction_fee::TransactionFeeBurnCap,
+    transaction_fee::{BaseFee, TransactionFeeBurnCap},
     validator_set::{ConsensusScheme, ValidatorSet},
 };
 
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::on_chain_config::OnChainConfig;
+use crate::on_chain_config::{ConfigStorage, Features, OnChainConfig};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -11,3 +11,30 @@ impl OnChainConfig for TransactionFeeBurnCap {
     const MODULE_IDENTIFIER: &'static str = "transaction_fee";
     const TYPE_IDENTIFIER: &'static str = "AptosCoinCapabilities";
 }
+
+/// The minimum gas unit price of the transactions of the current block, adjusted every block
+/// by the base fee market.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct BaseFee {
+    pub base_fee_per_gas: u64,
+}
+
+impl BaseFee {
+    /// Returns the base fee per gas unit the next transactions have to pay, or `None` if the
+    /// base fee market is disabled or not initialized.
+    pub fn fetch_base_fee_per_gas<T>(storage: &T) -> Option<u64>
+    where
+        T: ConfigStorage + ?Sized,
+    {
+        let features = Features::fetch_config(storage).unwrap_or_default();
+        if !features.is_base_fee_market_enabled() {
+            return None;
+        }
+        Self::fetch_config(storage).map(|base_fee| base_fee.base_fee_per_gas)
+    }
+}
+
+impl OnChainConfig for BaseFee {
+    const MODULE_IDENTIFIER: &'static str = "transaction_fee";
+    const TYPE_IDENTIFIER: &'static str = "BaseFee";
+}
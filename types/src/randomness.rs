@@ -0,0 +1,50 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-block randomness.
+//!
+//! The proposer of a block signs the [`RandomnessSeedMessage`] of the block's epoch and round with
+//! its BLS12-381 consensus key, and the randomness seed of the block is the SHA3-256 hash of that
+//! signature. BLS signatures are unique, so any validator can check the seed against the
+//! proposer's public key and the proposer cannot pick another seed for the same round.
+//!
+//! The seed is not a source of unpredictable or unbiasable randomness:
+//! - the proposer can compute the seed of every round it leads in advance, and
+//! - the proposer can withhold a block whose seed it does not like, at the cost of a failed
+//!   proposal.
+//!
+//! Consensus therefore neither produces nor accepts randomness proofs, and blocks never carry a
+//! seed, until the seed is derived from a threshold signature of the validators, which no single
+//! validator can predict or withhold.
+
+use crate::block_info::Round;
+use aptos_crypto::{bls12381, HashValue};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use serde::{Deserialize, Serialize};
+
+/// The message the proposer of a block signs to prove the randomness seed of the block.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, CryptoHasher, BCSCryptoHash)]
+pub struct RandomnessSeedMessage {
+    epoch: u64,
+    round: Round,
+}
+
+impl RandomnessSeedMessage {
+    pub fn new(epoch: u64, round: Round) -> Self {
+        Self { epoch, round }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn round(&self) -> Round {
+        self.round
+    }
+}
+
+/// Returns the randomness seed of a block, given the proposer's signature of the block's
+/// `RandomnessSeedMessage`.
+pub fn randomness_seed(proof: &bls12381::Signature) -> HashValue {
+    HashValue::sha3_256_of(&proof.to_bytes())
+}
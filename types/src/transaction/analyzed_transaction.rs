@@ -302,7 +302,9 @@ impl From<Transaction> for AnalyzedTransaction {
             Transaction::StateCheckpoint(hash_value) => {
                 Self::analyzed_transaction_for_state_checkpoint(hash_value)
             },
-            Transaction::GenesisTransaction(_) => Self::new_with_no_hints(txn),
+            Transaction::GenesisTransaction(_)
+            | Transaction::ValidatorTransaction(_)
+            | Transaction::BlockMetadataExt(_) => Self::new_with_no_hints(txn),
         }
     }
 }
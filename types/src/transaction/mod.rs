@@ -5,6 +5,7 @@
 use crate::{
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     chain_id::ChainId,
     contract_event::{ContractEvent, FEE_STATEMENT_EVENT_TYPE},
     ledger_info::LedgerInfo,
@@ -13,6 +14,7 @@ use crate::{
     },
     state_store::ShardedStateUpdates,
     transaction::authenticator::{AccountAuthenticator, TransactionAuthenticator},
+    validator_txn::ValidatorTransaction,
     vm_status::{DiscardedVMStatus, KeptVMStatus, StatusCode, StatusType, VMStatus},
     write_set::WriteSet,
 };
@@ -28,7 +30,7 @@ use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use move_core_types::transaction_argument::convert_txn_args;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStructVariant, Deserialize, Serialize, Serializer};
 use std::{
     convert::TryFrom,
     fmt,
@@ -42,6 +44,7 @@ mod module;
 mod multisig;
 mod script;
 mod transaction_argument;
+pub mod user_transaction_context;
 
 use crate::fee_statement::FeeStatement;
 pub use change_set::ChangeSet;
@@ -60,9 +63,8 @@ pub type Version = u64; // Height - also used for MVCC in StateDB
 pub type AtomicVersion = AtomicU64;
 
 /// RawTransaction is the portion of a transaction that a client signs.
-#[derive(
-    Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, BCSCryptoHash,
-)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Deserialize, CryptoHasher, BCSCryptoHash)]
+#[serde(from = "RawTransactionData<RawTransactionPayload>")]
 pub struct RawTransaction {
     /// Sender's address.
     sender: AccountAddress,
@@ -77,9 +79,16 @@ pub struct RawTransaction {
     /// Maximal total gas to spend for this transaction.
     max_gas_amount: u64,
 
-    /// Price to be paid per gas unit.
+    /// Maximal price to be paid per gas unit (the max fee). Under the base fee market the price
+    /// charged is the base fee of the block plus at most `max_priority_fee_per_gas`, and never
+    /// more than this; the part covered by the base fee is burned.
     gas_unit_price: u64,
 
+    /// Maximal priority fee (tip) per gas unit paid on top of the base fee. If not set, all of
+    /// the gas unit price above the base fee is paid as the tip. Ignored if the base fee market
+    /// is disabled.
+    max_priority_fee_per_gas: Option<u64>,
+
     /// Expiration timestamp for this transaction, represented
     /// as seconds from the Unix Epoch. If the current blockchain timestamp
     /// is greater than or equal to this time, then the transaction has
@@ -111,6 +120,7 @@ impl RawTransaction {
             payload,
             max_gas_amount,
             gas_unit_price,
+            max_priority_fee_per_gas: None,
             expiration_timestamp_secs,
             chain_id,
         }
@@ -134,6 +144,7 @@ impl RawTransaction {
             payload: TransactionPayload::Script(script),
             max_gas_amount,
             gas_unit_price,
+            max_priority_fee_per_gas: None,
             expiration_timestamp_secs,
             chain_id,
         }
@@ -155,6 +166,7 @@ impl RawTransaction {
             payload: TransactionPayload::EntryFunction(entry_function),
             max_gas_amount,
             gas_unit_price,
+            max_priority_fee_per_gas: None,
             expiration_timestamp_secs,
             chain_id,
         }
@@ -176,6 +188,7 @@ impl RawTransaction {
             payload: TransactionPayload::Multisig(multisig),
             max_gas_amount,
             gas_unit_price,
+            max_priority_fee_per_gas: None,
             expiration_timestamp_secs,
             chain_id,
         }
@@ -197,6 +210,7 @@ impl RawTransaction {
             payload: TransactionPayload::ModuleBundle(ModuleBundle::from(module)),
             max_gas_amount,
             gas_unit_price,
+            max_priority_fee_per_gas: None,
             expiration_timestamp_secs,
             chain_id,
         }
@@ -220,6 +234,7 @@ impl RawTransaction {
             payload: TransactionPayload::ModuleBundle(modules),
             max_gas_amount,
             gas_unit_price,
+            max_priority_fee_per_gas: None,
             expiration_timestamp_secs,
             chain_id,
         }
@@ -353,6 +368,13 @@ impl RawTransaction {
         ))
     }
 
+    /// Caps the priority fee (tip) per gas unit paid on top of the base fee, so that the rest of
+    /// the gas unit price isn't charged when the base fee is lower.
+    pub fn with_max_priority_fee_per_gas(mut self, max_priority_fee_per_gas: u64) -> Self {
+        self.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        self
+    }
+
     pub fn into_payload(self) -> TransactionPayload {
         self.payload
     }
@@ -391,6 +413,7 @@ impl RawTransaction {
              \t}}, \n\
              \tmax_gas_amount: {}, \n\
              \tgas_unit_price: {}, \n\
+             \tmax_priority_fee_per_gas: {:?}, \n\
              \texpiration_timestamp_secs: {:#?}, \n\
              \tchain_id: {},
              }}",
@@ -400,6 +423,7 @@ impl RawTransaction {
             f_args,
             self.max_gas_amount,
             self.gas_unit_price,
+            self.max_priority_fee_per_gas,
             self.expiration_timestamp_secs,
             self.chain_id,
         )
@@ -416,6 +440,116 @@ impl RawTransaction {
     }
 }
 
+/// The serialized form of a `RawTransaction`. The max priority fee is carried in the payload, so
+/// that transactions without one serialize exactly as before it was introduced.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "RawTransaction")]
+struct RawTransactionData<P> {
+    sender: AccountAddress,
+    sequence_number: u64,
+    payload: P,
+    max_gas_amount: u64,
+    gas_unit_price: u64,
+    expiration_timestamp_secs: u64,
+    chain_id: ChainId,
+}
+
+/// A `TransactionPayload`, whose variants it shares, extended with a variant for payloads with a
+/// max priority fee.
+#[derive(Deserialize)]
+pub enum RawTransactionPayload {
+    Script(Script),
+    ModuleBundle(ModuleBundle),
+    EntryFunction(EntryFunction),
+    Multisig(Multisig),
+    WithMaxPriorityFee {
+        payload: TransactionPayload,
+        max_priority_fee_per_gas: u64,
+    },
+}
+
+/// Serializes a borrowed payload as a `RawTransactionPayload`.
+struct RawTransactionPayloadRef<'a> {
+    payload: &'a TransactionPayload,
+    max_priority_fee_per_gas: Option<u64>,
+}
+
+impl Serialize for RawTransactionPayloadRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "RawTransactionPayload";
+        match (self.payload, self.max_priority_fee_per_gas) {
+            (TransactionPayload::Script(script), None) => {
+                serializer.serialize_newtype_variant(NAME, 0, "Script", script)
+            },
+            (TransactionPayload::ModuleBundle(modules), None) => {
+                serializer.serialize_newtype_variant(NAME, 1, "ModuleBundle", modules)
+            },
+            (TransactionPayload::EntryFunction(entry_function), None) => {
+                serializer.serialize_newtype_variant(NAME, 2, "EntryFunction", entry_function)
+            },
+            (TransactionPayload::Multisig(multisig), None) => {
+                serializer.serialize_newtype_variant(NAME, 3, "Multisig", multisig)
+            },
+            (payload, Some(max_priority_fee_per_gas)) => {
+                let mut variant =
+                    serializer.serialize_struct_variant(NAME, 4, "WithMaxPriorityFee", 2)?;
+                variant.serialize_field("payload", payload)?;
+                variant.serialize_field("max_priority_fee_per_gas", &max_priority_fee_per_gas)?;
+                variant.end()
+            },
+        }
+    }
+}
+
+impl Serialize for RawTransaction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawTransactionData {
+            sender: self.sender,
+            sequence_number: self.sequence_number,
+            payload: RawTransactionPayloadRef {
+                payload: &self.payload,
+                max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+            },
+            max_gas_amount: self.max_gas_amount,
+            gas_unit_price: self.gas_unit_price,
+            expiration_timestamp_secs: self.expiration_timestamp_secs,
+            chain_id: self.chain_id,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl From<RawTransactionData<RawTransactionPayload>> for RawTransaction {
+    fn from(data: RawTransactionData<RawTransactionPayload>) -> Self {
+        let (payload, max_priority_fee_per_gas) = match data.payload {
+            RawTransactionPayload::Script(script) => (TransactionPayload::Script(script), None),
+            RawTransactionPayload::ModuleBundle(modules) => {
+                (TransactionPayload::ModuleBundle(modules), None)
+            },
+            RawTransactionPayload::EntryFunction(entry_function) => {
+                (TransactionPayload::EntryFunction(entry_function), None)
+            },
+            RawTransactionPayload::Multisig(multisig) => {
+                (TransactionPayload::Multisig(multisig), None)
+            },
+            RawTransactionPayload::WithMaxPriorityFee {
+                payload,
+                max_priority_fee_per_gas,
+            } => (payload, Some(max_priority_fee_per_gas)),
+        };
+        RawTransaction {
+            sender: data.sender,
+            sequence_number: data.sequence_number,
+            payload,
+            max_gas_amount: data.max_gas_amount,
+            gas_unit_price: data.gas_unit_price,
+            max_priority_fee_per_gas,
+            expiration_timestamp_secs: data.expiration_timestamp_secs,
+            chain_id: data.chain_id,
+        }
+    }
+}
+
 #[derive(
     Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, BCSCryptoHash,
 )]
@@ -686,6 +820,30 @@ impl SignedTransaction {
         self.raw_txn.gas_unit_price
     }
 
+    pub fn max_priority_fee_per_gas(&self) -> Option<u64> {
+        self.raw_txn.max_priority_fee_per_gas
+    }
+
+    /// The priority fee (tip) per gas unit paid on top of `base_fee_per_gas`, i.e. the gas unit
+    /// price above the base fee capped by the max priority fee, or `None` if the gas unit price
+    /// doesn't cover the base fee and the transaction can't be included.
+    pub fn priority_fee_per_gas(&self, base_fee_per_gas: u64) -> Option<u64> {
+        let priority_fee = self.gas_unit_price().checked_sub(base_fee_per_gas)?;
+        Some(match self.max_priority_fee_per_gas() {
+            Some(max_priority_fee) => priority_fee.min(max_priority_fee),
+            None => priority_fee,
+        })
+    }
+
+    /// The price per gas unit charged under `base_fee_per_gas`: the base fee plus the priority
+    /// fee, which is at most the gas unit price. The rest of the gas unit price is not charged.
+    pub fn effective_gas_unit_price(&self, base_fee_per_gas: u64) -> u64 {
+        match self.priority_fee_per_gas(base_fee_per_gas) {
+            Some(priority_fee) => base_fee_per_gas + priority_fee,
+            None => self.gas_unit_price(),
+        }
+    }
+
     pub fn expiration_timestamp_secs(&self) -> u64 {
         self.raw_txn.expiration_timestamp_secs
     }
@@ -1712,6 +1870,14 @@ pub enum Transaction {
     /// in the TransactionInfo
     /// The hash value inside is unique block id which can generate unique hash of state checkpoint transaction
     StateCheckpoint(HashValue),
+
+    /// Transaction proposed by validators to update on-chain state outside of the user mempool,
+    /// executed right after the block metadata. See `crate::validator_txn`.
+    ValidatorTransaction(ValidatorTransaction),
+
+    /// Transaction to update the block metadata resource at the beginning of a block, when the
+    /// block carries more than `BlockMetadata`, e.g. a randomness seed. See `BlockMetadataExt`.
+    BlockMetadataExt(BlockMetadataExt),
 }
 
 impl Transaction {
@@ -1729,6 +1895,13 @@ impl Transaction {
         }
     }
 
+    pub fn try_as_block_metadata_ext(&self) -> Option<&BlockMetadataExt> {
+        match self {
+            Transaction::BlockMetadataExt(ext) => Some(ext),
+            _ => None,
+        }
+    }
+
     pub fn format_for_client(&self, get_transaction_name: impl Fn(&[u8]) -> String) -> String {
         match self {
             Transaction::UserTransaction(user_txn) => {
@@ -1740,6 +1913,10 @@ impl Transaction {
             Transaction::BlockMetadata(_block_metadata) => String::from("block_metadata"),
             // TODO: display proper information for client
             Transaction::StateCheckpoint(_) => String::from("state_checkpoint"),
+            // TODO: display proper information for client
+            Transaction::ValidatorTransaction(_) => String::from("validator_transaction"),
+            // TODO: display proper information for client
+            Transaction::BlockMetadataExt(_) => String::from("block_metadata_ext"),
         }
     }
 }
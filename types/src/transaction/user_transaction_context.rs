@@ -0,0 +1,113 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{chain_id::ChainId, transaction::EntryFunction};
+use move_core_types::account_address::AccountAddress;
+
+/// The data of the currently executing user transaction which is exposed to Move through the
+/// natives of `aptos_framework::transaction_context`.
+#[derive(Debug, Clone)]
+pub struct UserTransactionContext {
+    sender: AccountAddress,
+    secondary_signers: Vec<AccountAddress>,
+    gas_payer: AccountAddress,
+    max_gas_amount: u64,
+    gas_unit_price: u64,
+    chain_id: u8,
+    entry_function_payload: Option<EntryFunctionPayload>,
+}
+
+impl UserTransactionContext {
+    pub fn new(
+        sender: AccountAddress,
+        secondary_signers: Vec<AccountAddress>,
+        gas_payer: AccountAddress,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        chain_id: ChainId,
+        entry_function_payload: Option<EntryFunctionPayload>,
+    ) -> Self {
+        Self {
+            sender,
+            secondary_signers,
+            gas_payer,
+            max_gas_amount,
+            gas_unit_price,
+            chain_id: chain_id.id(),
+            entry_function_payload,
+        }
+    }
+
+    pub fn sender(&self) -> AccountAddress {
+        self.sender
+    }
+
+    pub fn secondary_signers(&self) -> &[AccountAddress] {
+        &self.secondary_signers
+    }
+
+    pub fn gas_payer(&self) -> AccountAddress {
+        self.gas_payer
+    }
+
+    pub fn max_gas_amount(&self) -> u64 {
+        self.max_gas_amount
+    }
+
+    pub fn gas_unit_price(&self) -> u64 {
+        self.gas_unit_price
+    }
+
+    pub fn chain_id(&self) -> u8 {
+        self.chain_id
+    }
+
+    pub fn entry_function_payload(&self) -> Option<&EntryFunctionPayload> {
+        self.entry_function_payload.as_ref()
+    }
+}
+
+/// The entry function called by a user transaction, with its type arguments in their canonical
+/// string representation and its arguments BCS-serialized.
+#[derive(Debug, Clone)]
+pub struct EntryFunctionPayload {
+    pub account_address: AccountAddress,
+    pub module_name: String,
+    pub function_name: String,
+    pub ty_arg_names: Vec<String>,
+    pub args: Vec<Vec<u8>>,
+}
+
+impl EntryFunctionPayload {
+    pub fn new(
+        account_address: AccountAddress,
+        module_name: String,
+        function_name: String,
+        ty_arg_names: Vec<String>,
+        args: Vec<Vec<u8>>,
+    ) -> Self {
+        Self {
+            account_address,
+            module_name,
+            function_name,
+            ty_arg_names,
+            args,
+        }
+    }
+}
+
+impl From<&EntryFunction> for EntryFunctionPayload {
+    fn from(entry_function: &EntryFunction) -> Self {
+        Self::new(
+            *entry_function.module().address(),
+            entry_function.module().name().to_string(),
+            entry_function.function().to_string(),
+            entry_function
+                .ty_args()
+                .iter()
+                .map(|ty| ty.to_canonical_string())
+                .collect(),
+            entry_function.args().to_vec(),
+        )
+    }
+}
@@ -16,6 +16,7 @@ use aptos_crypto::{
 };
 use bcs::test_helpers::assert_canonical_encode_decode;
 use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
 #[test]
@@ -36,6 +37,70 @@ fn test_invalid_signature() {
     assert!(!txn.signature_is_valid(), "Signature checking should fail")
 }
 
+fn script_raw_transaction(gas_unit_price: u64) -> RawTransaction {
+    RawTransaction::new_script(
+        AccountAddress::random(),
+        3,
+        Script::new(vec![1, 2, 3], vec![], vec![]),
+        1_000,
+        gas_unit_price,
+        5,
+        ChainId::test(),
+    )
+}
+
+/// `RawTransaction` as serialized before the max priority fee was introduced.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename = "RawTransaction")]
+struct LegacyRawTransaction {
+    sender: AccountAddress,
+    sequence_number: u64,
+    payload: TransactionPayload,
+    max_gas_amount: u64,
+    gas_unit_price: u64,
+    expiration_timestamp_secs: u64,
+    chain_id: ChainId,
+}
+
+#[test]
+fn test_raw_transaction_without_max_priority_fee_is_legacy_encoded() {
+    let raw_txn = script_raw_transaction(100);
+    let legacy_raw_txn = LegacyRawTransaction {
+        sender: raw_txn.sender(),
+        sequence_number: 3,
+        payload: raw_txn.clone().into_payload(),
+        max_gas_amount: 1_000,
+        gas_unit_price: 100,
+        expiration_timestamp_secs: 5,
+        chain_id: ChainId::test(),
+    };
+
+    let bytes = bcs::to_bytes(&raw_txn).unwrap();
+    assert_eq!(bytes, bcs::to_bytes(&legacy_raw_txn).unwrap());
+    assert_eq!(bcs::from_bytes::<RawTransaction>(&bytes).unwrap(), raw_txn);
+}
+
+#[test]
+fn test_raw_transaction_with_max_priority_fee() {
+    let raw_txn = script_raw_transaction(100).with_max_priority_fee_per_gas(10);
+
+    let bytes = bcs::to_bytes(&raw_txn).unwrap();
+    assert_eq!(bcs::from_bytes::<RawTransaction>(&bytes).unwrap(), raw_txn);
+    // Legacy decoders reject the transaction instead of dropping the max priority fee.
+    assert!(bcs::from_bytes::<LegacyRawTransaction>(&bytes).is_err());
+
+    let txn = SignedTransaction::new(
+        raw_txn,
+        Ed25519PrivateKey::generate_for_testing().public_key(),
+        Ed25519Signature::try_from(&[1u8; 64][..]).unwrap(),
+    );
+    assert_eq!(txn.max_priority_fee_per_gas(), Some(10));
+    assert_eq!(txn.effective_gas_unit_price(50), 60);
+    assert_eq!(txn.effective_gas_unit_price(95), 100);
+    assert_eq!(txn.priority_fee_per_gas(95), Some(5));
+    assert_eq!(txn.priority_fee_per_gas(101), None);
+}
+
 proptest! {
     #[test]
     fn test_sign_raw_transaction(raw_txn in any::<RawTransaction>(), keypair in ed25519::keypair_strategy()) {
@@ -0,0 +1,72 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validator transactions.
+//!
+//! System updates that originate from the validators themselves (e.g. oracle results or JWK
+//! updates) do not go through the user mempool. Each validator puts them into its local validator
+//! transaction pool, the proposer of a block pulls them from the pool (within the per-block limits
+//! of the on-chain [`crate::on_chain_config::ValidatorTxnConfig`]), the voters verify them and the
+//! VM executes them right after the block metadata transaction of the block.
+
+use crate::validator_verifier::ValidatorVerifier;
+use anyhow::ensure;
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+#[derive(
+    Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, CryptoHasher, BCSCryptoHash,
+)]
+pub enum ValidatorTransaction {
+    /// A placeholder which carries opaque bytes and has no effect on chain. It exercises the
+    /// proposal, verification and execution paths until the first real topics are added.
+    DummyTopic1(DummyValidatorTransaction),
+}
+
+impl ValidatorTransaction {
+    pub fn dummy(payload: Vec<u8>) -> Self {
+        Self::DummyTopic1(DummyValidatorTransaction {
+            valid: true,
+            payload,
+        })
+    }
+
+    /// The topic of the transaction. The pool keeps at most one transaction per topic.
+    pub fn topic(&self) -> Topic {
+        match self {
+            ValidatorTransaction::DummyTopic1(_) => Topic::Dummy1,
+        }
+    }
+
+    pub fn size_in_bytes(&self) -> usize {
+        bcs::serialized_size(self).expect("ValidatorTransaction serialization must not fail")
+    }
+
+    /// Checks the transaction against the validator set of the epoch it is proposed in. Voters
+    /// call this on every validator transaction of a proposal before voting for it.
+    pub fn verify(&self, _verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        match self {
+            ValidatorTransaction::DummyTopic1(txn) => {
+                ensure!(txn.valid, "Invalid dummy validator transaction");
+                Ok(())
+            },
+        }
+    }
+}
+
+/// The kind of update a validator transaction carries.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Topic {
+    Dummy1,
+}
+
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DummyValidatorTransaction {
+    pub valid: bool,
+    #[serde(with = "serde_bytes")]
+    pub payload: Vec<u8>,
+}
@@ -12,9 +12,10 @@ use aptos_storage_interface::{
 use aptos_types::{
     account_address::AccountAddress,
     account_view::AccountView,
+    on_chain_config::BaseFee,
     transaction::{SignedTransaction, VMValidatorResult},
 };
-use aptos_vm::AptosVM;
+use aptos_vm::{data_cache::StorageAdapter, AptosVM};
 use fail::fail_point;
 use std::sync::Arc;
 
@@ -110,3 +111,8 @@ pub fn get_account_sequence_number(
         None => Ok(0),
     }
 }
+
+/// returns the base fee per gas unit from storage, if the base fee market is enabled
+pub fn get_base_fee_per_gas(state_view: &DbStateView) -> Option<u64> {
+    BaseFee::fetch_base_fee_per_gas(&StorageAdapter::new(state_view))
+}